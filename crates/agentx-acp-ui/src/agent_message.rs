@@ -2,10 +2,18 @@ use std::sync::Arc;
 
 use agent_client_protocol::{ContentBlock, ContentChunk, SessionId};
 use gpui::{
-    App, AppContext, Context, ElementId, Entity, IntoElement, ParentElement, Render, RenderOnce,
-    SharedString, Styled, Window, div, prelude::FluentBuilder as _, px,
+    App, AppContext, ClipboardItem, Context, ElementId, Entity, InteractiveElement as _,
+    IntoElement, ParentElement, Render, RenderOnce, SharedString, StatefulInteractiveElement as _,
+    Styled, Window, div, prelude::FluentBuilder as _, px,
 };
-use gpui_component::{ActiveTheme, Icon, IconName, h_flex, text::TextView, v_flex};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    text::TextView,
+    v_flex,
+};
+use rust_i18n::t;
 use serde::{Deserialize, Serialize};
 
 pub type AgentIconProvider = Arc<dyn Fn(&str) -> Icon + Send + Sync>;
@@ -144,6 +152,152 @@ impl AgentMessageData {
     }
 }
 
+/// A run of Markdown text or a fenced code block within a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MarkdownSegment<'a> {
+    Text(&'a str),
+    Code { language: &'a str, code: &'a str },
+}
+
+/// Opening fence of a code block: the fence character, its length and the language
+fn parse_fence_open(line: &str) -> Option<(char, usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let line = &line[indent..];
+    let fence_char = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = line.len() - line.trim_start_matches(fence_char).len();
+    if fence_len < 3 {
+        return None;
+    }
+    let info = line[fence_len..].trim();
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+    let language = info.split_whitespace().next().unwrap_or_default();
+    Some((fence_char, fence_len, language))
+}
+
+fn is_fence_close(line: &str, fence_char: char, fence_len: usize) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() >= fence_len && trimmed.chars().all(|c| c == fence_char)
+}
+
+/// Split Markdown into text and fenced code blocks
+///
+/// A block that is still open (while the message streams) runs to the end.
+fn split_code_blocks(markdown: &str) -> Vec<MarkdownSegment<'_>> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut offset = 0;
+    let mut lines = markdown.split_inclusive('\n');
+
+    while let Some(line) = lines.next() {
+        let line_start = offset;
+        offset += line.len();
+        let Some((fence_char, fence_len, language)) = parse_fence_open(line) else {
+            continue;
+        };
+
+        if !markdown[text_start..line_start].trim().is_empty() {
+            segments.push(MarkdownSegment::Text(&markdown[text_start..line_start]));
+        }
+        let code_start = offset;
+        let mut code_end = markdown.len();
+        for line in lines.by_ref() {
+            let line_start = offset;
+            offset += line.len();
+            if is_fence_close(line, fence_char, fence_len) {
+                code_end = line_start;
+                break;
+            }
+        }
+        segments.push(MarkdownSegment::Code {
+            language,
+            code: markdown[code_start..code_end].trim_end_matches(['\n', '\r']),
+        });
+        text_start = offset;
+    }
+
+    if !markdown[text_start..].trim().is_empty() {
+        segments.push(MarkdownSegment::Text(&markdown[text_start..]));
+    }
+    segments
+}
+
+/// Fenced code block with a language label and a copy button
+///
+/// Long lines scroll horizontally instead of wrapping.
+#[derive(IntoElement)]
+struct CodeBlock {
+    id: SharedString,
+    language: SharedString,
+    code: SharedString,
+}
+
+impl RenderOnce for CodeBlock {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let language = if self.language.is_empty() {
+            SharedString::from("text")
+        } else {
+            self.language
+        };
+        let code = self.code.clone();
+
+        v_flex()
+            .w_full()
+            .rounded(cx.theme().radius)
+            .border_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().secondary)
+            .overflow_hidden()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .items_center()
+                    .pl_3()
+                    .pr_1()
+                    .py_0p5()
+                    .bg(cx.theme().muted)
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(language),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("{}-copy", self.id)))
+                            .icon(IconName::Copy)
+                            .ghost()
+                            .xsmall()
+                            .tooltip(t!("acp_ui.message.copy_code").to_string())
+                            .on_click(move |_, _, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(code.to_string()));
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .id(SharedString::from(format!("{}-scroll", self.id)))
+                    .w_full()
+                    .overflow_x_scroll()
+                    .p_3()
+                    .child(
+                        div()
+                            .font_family("Monaco, 'Courier New', monospace")
+                            .text_size(px(12.))
+                            .line_height(px(18.))
+                            .text_color(cx.theme().foreground)
+                            .whitespace_nowrap()
+                            .child(self.code),
+                    ),
+            )
+    }
+}
+
 /// Agent message component
 #[derive(IntoElement)]
 pub struct AgentMessage {
@@ -179,7 +333,26 @@ impl RenderOnce for AgentMessage {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let agent_name = self.data.agent_name().unwrap_or("Agent");
         let full_text = self.data.full_text();
-        let markdown_id = SharedString::from(format!("{}-markdown", self.id));
+        let segments = split_code_blocks(&full_text)
+            .into_iter()
+            .enumerate()
+            .map(|(index, segment)| match segment {
+                MarkdownSegment::Text(text) => TextView::markdown(
+                    SharedString::from(format!("{}-markdown-{}", self.id, index)),
+                    SharedString::from(text.to_string()),
+                )
+                .text_sm()
+                .text_color(cx.theme().foreground)
+                .selectable(true)
+                .into_any_element(),
+                MarkdownSegment::Code { language, code } => CodeBlock {
+                    id: format!("{}-code-{}", self.id, index).into(),
+                    language: language.to_string().into(),
+                    code: code.to_string().into(),
+                }
+                .into_any_element(),
+            })
+            .collect::<Vec<_>>();
 
         // Get icon based on agent name
         let icon = (self.options.icon_provider)(agent_name);
@@ -195,15 +368,11 @@ impl RenderOnce for AgentMessage {
                     .child(icon.size(px(16.)).mt_1().text_color(cx.theme().foreground))
                     // Message content with markdown rendering
                     .child(
-                        div()
+                        v_flex()
                             .w_full()
-                            .child(
-                                TextView::markdown(markdown_id, full_text)
-                                    .text_sm()
-                                    .text_color(cx.theme().foreground)
-                                    .selectable(true)
-                                    .pr_3(),
-                            )
+                            .min_w(px(0.))
+                            .gap_2()
+                            .children(segments)
                             .pr_3(),
                     ),
             )
@@ -315,6 +484,35 @@ mod tests {
         assert_eq!(data.full_text().as_ref(), "Hello world");
     }
 
+    #[test]
+    fn split_code_blocks_separates_fences() {
+        let markdown = "Intro\n\n```rust\nfn main() {}\n```\nOutro\n````\n```nested```\n````\n";
+        assert_eq!(
+            split_code_blocks(markdown),
+            vec![
+                MarkdownSegment::Text("Intro\n\n"),
+                MarkdownSegment::Code {
+                    language: "rust",
+                    code: "fn main() {}",
+                },
+                MarkdownSegment::Text("Outro\n"),
+                MarkdownSegment::Code {
+                    language: "",
+                    code: "```nested```",
+                },
+            ]
+        );
+
+        // A block still streaming in runs to the end
+        assert_eq!(
+            split_code_blocks("```py\nprint(1)\n"),
+            vec![MarkdownSegment::Code {
+                language: "py",
+                code: "print(1)",
+            }]
+        );
+    }
+
     #[test]
     fn agent_message_data_handles_non_text_chunks() {
         let mut data = AgentMessageData::new("session-1");
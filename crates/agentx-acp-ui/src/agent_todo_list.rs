@@ -1,10 +1,15 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
 use gpui::{
-    App, AppContext, Context, ElementId, Entity, InteractiveElement, IntoElement, ParentElement,
-    Render, RenderOnce, SharedString, Styled, Window, div, px,
+    App, AppContext, Context, ElementId, Entity, Hsla, InteractiveElement, IntoElement,
+    ParentElement, Render, RenderOnce, SharedString, StatefulInteractiveElement, Styled, Window,
+    div, prelude::FluentBuilder as _, px, relative,
 };
 
 use agent_client_protocol::{Plan, PlanEntry, PlanEntryPriority, PlanEntryStatus};
-use gpui_component::{ActiveTheme, Icon, IconName, h_flex, v_flex};
+use gpui_component::{ActiveTheme, Icon, IconName, h_flex, tooltip::Tooltip, v_flex};
+use rust_i18n::t;
 use serde::{Deserialize, Serialize};
 
 /// Extended metadata for Plan (stored in Plan's meta field)
@@ -16,11 +21,55 @@ pub struct PlanMeta {
     pub title: Option<String>,
 }
 
+/// Extended metadata for a PlanEntry (stored in the entry's meta field)
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanEntryMeta {
+    /// Tool calls the agent made while working on this entry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_call_ids: Vec<String>,
+}
+
+impl PlanEntryMeta {
+    /// Parse the metadata of an entry, falling back to defaults
+    pub fn from_entry(entry: &PlanEntry) -> Self {
+        entry
+            .meta
+            .as_ref()
+            .and_then(|m| serde_json::from_value(serde_json::Value::Object(m.clone())).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Handler invoked when a plan entry is clicked
+pub type PlanEntryClickHandler = Arc<dyn Fn(&PlanEntry, &mut Window, &mut App) + Send + Sync>;
+
+/// Collapse state of a rendered plan, kept per list
+#[derive(Default)]
+struct PlanCollapseState {
+    /// Set when the user toggles; `None` follows the automatic behavior
+    user_collapsed: Option<bool>,
+    /// Whether every entry was completed when the state was last seen
+    all_completed: bool,
+}
+
+/// Accent color for an entry priority
+fn priority_color(priority: &PlanEntryPriority, cx: &App) -> Hsla {
+    match priority {
+        PlanEntryPriority::High => cx.theme().red,
+        PlanEntryPriority::Medium => cx.theme().yellow,
+        _ => cx.theme().muted_foreground,
+    }
+}
+
 /// A list item component for displaying a plan entry
 #[derive(IntoElement)]
 struct PlanEntryItem {
     id: ElementId,
     entry: PlanEntry,
+    /// Whether the entry has related tool calls to jump to
+    linked: bool,
+    on_click: Option<PlanEntryClickHandler>,
 }
 
 impl PlanEntryItem {
@@ -28,8 +77,20 @@ impl PlanEntryItem {
         Self {
             id: id.into(),
             entry,
+            linked: false,
+            on_click: None,
         }
     }
+
+    fn linked(mut self, linked: bool) -> Self {
+        self.linked = linked;
+        self
+    }
+
+    fn on_click(mut self, handler: Option<PlanEntryClickHandler>) -> Self {
+        self.on_click = handler;
+        self
+    }
 }
 
 impl RenderOnce for PlanEntryItem {
@@ -48,41 +109,69 @@ impl RenderOnce for PlanEntryItem {
             _ => (Icon::new(IconName::Dash), cx.theme().muted_foreground),
         };
 
-        div().id(self.id).child(
-            h_flex()
-                .items_start()
-                .gap_2()
-                .child(
-                    div()
-                        .mt(px(1.))
-                        .child(icon.text_color(icon_color).size(px(16.))),
-                )
-                .child(
-                    div()
-                        .flex_1()
-                        .text_size(px(14.))
-                        .text_color(text_color)
-                        .line_height(px(20.))
-                        .child(self.entry.content.clone()),
-                ),
-        )
+        let entry = self.entry.clone();
+        let clickable = self.on_click.is_some();
+
+        div()
+            .id(self.id)
+            .when_some(self.on_click.filter(|_| self.linked), |this, handler| {
+                this.cursor_pointer()
+                    .rounded(cx.theme().radius)
+                    .hover(|style| style.bg(cx.theme().muted.opacity(0.5)))
+                    .on_click(move |_, window, cx| handler(&entry, window, cx))
+            })
+            .when(clickable && !self.linked, |this| {
+                this.tooltip(|window, cx| {
+                    Tooltip::new(t!("acp_ui.plan.no_related_tool_calls").to_string())
+                        .build(window, cx)
+                })
+            })
+            .child(
+                h_flex()
+                    .items_start()
+                    .gap_2()
+                    .pl_2()
+                    .border_l_2()
+                    .border_color(priority_color(&self.entry.priority, cx).opacity(0.8))
+                    .child(
+                        div()
+                            .mt(px(1.))
+                            .child(icon.text_color(icon_color).size(px(16.))),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_size(px(14.))
+                            .text_color(text_color)
+                            .line_height(px(20.))
+                            .child(self.entry.content.clone()),
+                    ),
+            )
     }
 }
 
 /// Agent Todo List component for displaying plan execution progress
 /// Based on ACP's Plan structure from SessionUpdate::Plan
+#[derive(IntoElement)]
 pub struct AgentTodoList {
+    id: ElementId,
     /// The plan data following ACP's Plan structure
     plan: Plan,
     /// Extended metadata (title, etc.) - extracted from plan.meta
     meta: PlanMeta,
+    /// Indices of entries that have related tool calls
+    linked_entries: HashSet<usize>,
+    on_entry_click: Option<PlanEntryClickHandler>,
 }
 
 impl AgentTodoList {
     pub fn new() -> Self {
         Self {
+            id: "agent-todo-list".into(),
             plan: Plan::new(Vec::new()),
             meta: PlanMeta::default(),
+            linked_entries: HashSet::new(),
+            on_entry_click: None,
         }
     }
 
@@ -97,7 +186,31 @@ impl AgentTodoList {
             })
             .unwrap_or_default();
 
-        Self { plan, meta }
+        Self {
+            id: "agent-todo-list".into(),
+            plan,
+            meta,
+            linked_entries: HashSet::new(),
+            on_entry_click: None,
+        }
+    }
+
+    /// Set the element id, which keys the collapse state of this list
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Mark which entries (by index) have related tool calls to jump to
+    pub fn linked_entries(mut self, linked_entries: HashSet<usize>) -> Self {
+        self.linked_entries = linked_entries;
+        self
+    }
+
+    /// Set the handler called when a linked entry is clicked
+    pub fn on_entry_click(mut self, handler: PlanEntryClickHandler) -> Self {
+        self.on_entry_click = Some(handler);
+        self
     }
 
     /// Set the title of the todo list (stored in meta)
@@ -156,6 +269,12 @@ impl AgentTodoList {
         self.plan.entries.len()
     }
 
+    /// Whether the plan has entries and all of them are completed
+    fn all_completed(&self) -> bool {
+        let total = self.total_count();
+        total > 0 && self.completed_count() == total
+    }
+
     /// Get the display title
     fn display_title(&self) -> &str {
         self.meta.title.as_deref().unwrap_or("Tasks")
@@ -168,28 +287,79 @@ impl Default for AgentTodoList {
     }
 }
 
-impl IntoElement for AgentTodoList {
-    type Element = gpui::Div;
-
-    fn into_element(self) -> Self::Element {
+impl RenderOnce for AgentTodoList {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let title = self.display_title().to_string();
         let completed = self.completed_count();
         let total = self.total_count();
+        let all_completed = self.all_completed();
+        let progress = if total > 0 {
+            completed as f32 / total as f32
+        } else {
+            0.
+        };
+
+        let state_id =
+            ElementId::NamedChild(std::sync::Arc::new(self.id.clone()), "collapse".into());
+        let state = window.use_keyed_state(state_id, cx, |_, _| PlanCollapseState {
+            user_collapsed: None,
+            all_completed,
+        });
+        // A change in completion resets manual toggling, so a finished plan collapses itself
+        if state.read(cx).all_completed != all_completed {
+            state.update(cx, |state, _| {
+                state.user_collapsed = None;
+                state.all_completed = all_completed;
+            });
+        }
+        let collapsed = state.read(cx).user_collapsed.unwrap_or(all_completed);
+
+        let (header_icon, header_icon_color) = if all_completed {
+            (IconName::CircleCheck, cx.theme().green)
+        } else {
+            (IconName::Check, cx.theme().foreground)
+        };
+
+        let entries = self
+            .plan
+            .entries
+            .into_iter()
+            .enumerate()
+            .filter(|(_, entry)| !collapsed || entry.status != PlanEntryStatus::Completed)
+            .map(|(i, entry)| {
+                PlanEntryItem::new(SharedString::from(format!("plan-entry-{}", i)), entry)
+                    .linked(self.linked_entries.contains(&i))
+                    .on_click(self.on_entry_click.clone())
+            })
+            .collect::<Vec<_>>();
 
         v_flex()
+            .id(self.id)
             .gap_3()
             .w_full()
             .child(
-                // Header with title and count
+                // Header with title, count and collapse toggle
                 h_flex()
+                    .id("plan-header")
                     .justify_between()
                     .items_center()
                     .w_full()
+                    .cursor_pointer()
+                    .on_click(move |_, _, cx| {
+                        state.update(cx, |state, cx| {
+                            state.user_collapsed = Some(!collapsed);
+                            cx.notify();
+                        });
+                    })
                     .child(
                         h_flex()
                             .gap_2()
                             .items_center()
-                            .child(Icon::new(IconName::Check).size(px(16.)))
+                            .child(
+                                Icon::new(header_icon)
+                                    .size(px(16.))
+                                    .text_color(header_icon_color),
+                            )
                             .child(
                                 div()
                                     .text_size(px(14.))
@@ -198,20 +368,46 @@ impl IntoElement for AgentTodoList {
                             ),
                     )
                     .child(
-                        div()
-                            .text_size(px(14.))
-                            .child(format!("{}/{}", completed, total)),
+                        h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(
+                                div()
+                                    .text_size(px(14.))
+                                    .child(format!("{}/{}", completed, total)),
+                            )
+                            .child(
+                                Icon::new(if collapsed {
+                                    IconName::ChevronDown
+                                } else {
+                                    IconName::ChevronUp
+                                })
+                                .size(px(14.))
+                                .text_color(cx.theme().muted_foreground),
+                            ),
                     ),
             )
             .child(
-                // Task list
-                v_flex()
-                    .gap_2()
+                // Overall progress
+                div()
                     .w_full()
-                    .children(self.plan.entries.into_iter().enumerate().map(|(i, entry)| {
-                        PlanEntryItem::new(SharedString::from(format!("plan-entry-{}", i)), entry)
-                    })),
+                    .h(px(4.))
+                    .rounded_full()
+                    .bg(cx.theme().muted)
+                    .child(
+                        div()
+                            .h_full()
+                            .w(relative(progress))
+                            .rounded_full()
+                            .bg(cx.theme().green),
+                    ),
             )
+            .when(!entries.is_empty(), |this| {
+                this.child(
+                    // Task list
+                    v_flex().gap_2().w_full().children(entries),
+                )
+            })
     }
 }
 
@@ -369,6 +565,35 @@ mod tests {
         assert_eq!(parsed.title.as_deref(), Some("My Plan"));
     }
 
+    #[test]
+    fn plan_entry_meta_reads_tool_call_ids() {
+        let mut entry = PlanEntry::new("Task", PlanEntryPriority::Low, PlanEntryStatus::Pending);
+        assert!(PlanEntryMeta::from_entry(&entry).tool_call_ids.is_empty());
+
+        entry.meta = serde_json::json!({ "toolCallIds": ["call-1", "call-2"] })
+            .as_object()
+            .cloned();
+        assert_eq!(
+            PlanEntryMeta::from_entry(&entry).tool_call_ids,
+            vec!["call-1".to_string(), "call-2".to_string()]
+        );
+    }
+
+    #[test]
+    fn all_completed_requires_entries() {
+        assert!(!AgentTodoList::new().all_completed());
+
+        let list = AgentTodoList::new().add_entry(
+            "Task one",
+            PlanEntryPriority::Medium,
+            PlanEntryStatus::Completed,
+        );
+        assert!(list.all_completed());
+
+        let list = list.add_entry("Task two", PlanEntryPriority::Low, PlanEntryStatus::Pending);
+        assert!(!list.all_completed());
+    }
+
     #[test]
     fn completed_count_tracks_entries() {
         let list = AgentTodoList::new()
@@ -1,11 +1,34 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 
 use agent_client_protocol::Diff;
 use gpui::prelude::FluentBuilder;
-use gpui::{AnyElement, App, IntoElement, ParentElement, RenderOnce, Styled, Window, div, px};
-use gpui_component::{ActiveTheme, Icon, IconName, h_flex, v_flex};
+use gpui::{
+    AnyElement, App, ElementId, Entity, InteractiveElement as _, IntoElement, ParentElement,
+    Pixels, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled, Window, canvas, div,
+    px,
+};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable,
+    button::{Button, ButtonVariants as _},
+    h_flex, v_flex,
+};
+use rust_i18n::t;
 use similar::{ChangeTag, TextDiff};
 
+use crate::utils::looks_binary;
+
+/// Combined old + new size above which a file gets a time-limited, approximate diff
+const LARGE_DIFF_BYTES: usize = 1024 * 1024;
+
+/// Time budget for diffing large files; `similar` falls back to a coarser diff after it
+const LARGE_DIFF_TIMEOUT: Duration = Duration::from_millis(500);
+
 /// Represents a single line in a diff view
 #[derive(Debug, Clone)]
 pub enum DiffLine {
@@ -34,6 +57,112 @@ pub enum DiffDisplayItem {
     },
 }
 
+/// Added and deleted line counts of a diff
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffLineStats {
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl DiffLineStats {
+    /// Count changed lines in the computed diff
+    pub fn from_lines(diff_lines: &[DiffLine]) -> Self {
+        let mut stats = Self::default();
+        for line in diff_lines {
+            match line {
+                DiffLine::Insert { .. } => stats.additions += 1,
+                DiffLine::Delete { .. } => stats.deletions += 1,
+                DiffLine::Context { .. } => {}
+            }
+        }
+        stats
+    }
+}
+
+/// Window-space top of every rendered hunk, filled in during layout
+///
+/// Shared between a scroll container and the `DiffView`s inside it so the container
+/// can scroll hunks into view. Clear it before each render of the container.
+#[derive(Clone, Default)]
+pub struct DiffHunkPositions(Rc<RefCell<Vec<Pixels>>>);
+
+impl DiffHunkPositions {
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+
+    /// Hunk tops in layout order
+    pub fn tops(&self) -> Vec<Pixels> {
+        self.0.borrow().clone()
+    }
+
+    fn push(&self, top: Pixels) {
+        self.0.borrow_mut().push(top);
+    }
+}
+
+/// Indices of display items that start a hunk (a run of changed lines)
+pub fn hunk_starts(items: &[DiffDisplayItem]) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut in_hunk = false;
+    for (index, item) in items.iter().enumerate() {
+        let is_change = matches!(
+            item,
+            DiffDisplayItem::Line(DiffLine::Insert { .. } | DiffLine::Delete { .. })
+        );
+        if is_change && !in_hunk {
+            starts.push(index);
+        }
+        in_hunk = is_change;
+    }
+    starts
+}
+
+/// Called when the user asks for the lines past `max_lines`
+pub type DiffLoadMoreHandler = Arc<dyn Fn(&mut Window, &mut App) + Send + Sync>;
+
+/// Collapsed regions the user has revealed, kept per diff
+///
+/// Only region keys are stored; the lines themselves are rebuilt from the diff on render.
+#[derive(Debug, Clone, Default)]
+pub struct DiffExpansion {
+    /// Old-file start line of each expanded collapsed region
+    expanded: HashSet<usize>,
+    expand_all: bool,
+}
+
+impl DiffExpansion {
+    /// Whether the collapsed region starting at `start_old` should be shown in full
+    pub fn is_expanded(&self, start_old: usize) -> bool {
+        self.expand_all || self.expanded.contains(&start_old)
+    }
+
+    pub fn expand(&mut self, start_old: usize) {
+        self.expanded.insert(start_old);
+    }
+
+    pub fn expand_all(&mut self) {
+        self.expand_all = true;
+    }
+
+    pub fn collapse_all(&mut self) {
+        self.expanded.clear();
+        self.expand_all = false;
+    }
+
+    pub fn is_expanded_all(&self) -> bool {
+        self.expand_all
+    }
+}
+
 /// Configuration for DiffView rendering
 #[derive(Debug, Clone)]
 pub struct DiffViewConfig {
@@ -65,6 +194,9 @@ impl Default for DiffViewConfig {
 pub struct DiffView {
     diff: Diff,
     config: DiffViewConfig,
+    /// Handler and the number of lines it loads per click
+    on_load_more: Option<(DiffLoadMoreHandler, usize)>,
+    hunk_positions: Option<DiffHunkPositions>,
 }
 
 impl DiffView {
@@ -73,12 +205,19 @@ impl DiffView {
         Self {
             diff,
             config: DiffViewConfig::default(),
+            on_load_more: None,
+            hunk_positions: None,
         }
     }
 
     /// Create a new DiffView with custom configuration
     pub fn with_config(diff: Diff, config: DiffViewConfig) -> Self {
-        Self { diff, config }
+        Self {
+            diff,
+            config,
+            on_load_more: None,
+            hunk_positions: None,
+        }
     }
 
     /// Set maximum number of lines to display
@@ -111,9 +250,37 @@ impl DiffView {
         self
     }
 
+    /// Show a button at the truncation boundary that loads up to `lines` more lines
+    pub fn on_load_more(mut self, lines: usize, handler: DiffLoadMoreHandler) -> Self {
+        self.on_load_more = Some((handler, lines));
+        self
+    }
+
+    /// Whether either side of the diff is binary content
+    fn is_binary(&self) -> bool {
+        looks_binary(&self.diff.new_text) || self.diff.old_text.as_deref().is_some_and(looks_binary)
+    }
+
+    /// Combined size of both sides of the diff in bytes
+    fn total_bytes(&self) -> usize {
+        self.diff.new_text.len() + self.diff.old_text.as_ref().map_or(0, String::len)
+    }
+
+    /// Record where each hunk is laid out, for hunk navigation in the parent
+    pub fn track_hunks(mut self, positions: DiffHunkPositions) -> Self {
+        self.hunk_positions = Some(positions);
+        self
+    }
+
     /// Compute line-by-line diff using similar crate
     fn compute_diff(&self, old_text: &str, new_text: &str) -> Vec<DiffLine> {
-        let diff = TextDiff::from_lines(old_text, new_text);
+        let diff = if old_text.len() + new_text.len() > LARGE_DIFF_BYTES {
+            TextDiff::configure()
+                .timeout(LARGE_DIFF_TIMEOUT)
+                .diff_lines(old_text, new_text)
+        } else {
+            TextDiff::from_lines(old_text, new_text)
+        };
         let mut result = Vec::new();
         let mut old_line_num = 1;
         let mut new_line_num = 1;
@@ -152,9 +319,63 @@ impl DiffView {
         result
     }
 
+    /// Key for the per-diff expansion state
+    ///
+    /// Built from the path and the text lengths so it stays cheap for large
+    /// diffs that are rebuilt on every render.
+    fn state_id(&self) -> ElementId {
+        let mut hasher = DefaultHasher::new();
+        self.diff.path.hash(&mut hasher);
+        self.diff
+            .old_text
+            .as_ref()
+            .map(String::len)
+            .hash(&mut hasher);
+        self.diff.new_text.len().hash(&mut hasher);
+        ElementId::Name(SharedString::from(format!(
+            "diff-view-{:x}",
+            hasher.finish()
+        )))
+    }
+
+    /// Id for a control of this diff, unique among all diffs on screen
+    fn child_id(&self, name: &'static str) -> ElementId {
+        ElementId::NamedChild(Arc::new(self.state_id()), name.into())
+    }
+
+    /// Push a run of unchanged lines, either in full or as a single collapsed placeholder
+    fn push_hidden_context(
+        display_items: &mut Vec<DiffDisplayItem>,
+        hidden: &[DiffLine],
+        show_placeholder: bool,
+        expansion: &DiffExpansion,
+    ) {
+        let Some(DiffLine::Context {
+            old_num, new_num, ..
+        }) = hidden.first()
+        else {
+            return;
+        };
+
+        if expansion.is_expanded(*old_num) {
+            display_items.extend(hidden.iter().cloned().map(DiffDisplayItem::Line));
+        } else if show_placeholder {
+            display_items.push(DiffDisplayItem::Collapsed {
+                start_old: *old_num,
+                start_new: *new_num,
+                count: hidden.len(),
+            });
+        }
+    }
+
     /// Apply context collapsing to diff lines
-    /// Only show changed lines with N lines of context before/after
-    fn apply_context_collapsing(&self, diff_lines: Vec<DiffLine>) -> Vec<DiffDisplayItem> {
+    /// Only show changed lines with N lines of context before/after,
+    /// except for regions opened in `expansion`
+    fn apply_context_collapsing(
+        &self,
+        diff_lines: Vec<DiffLine>,
+        expansion: &DiffExpansion,
+    ) -> Vec<DiffDisplayItem> {
         let context_lines = self.config.context_lines;
         let min_collapse_size = context_lines * 2 + 1; // Minimum lines to collapse
 
@@ -183,18 +404,12 @@ impl DiffView {
 
                                 // Collapse the middle
                                 let collapsed_count = distance - context_lines * 2;
-                                if collapsed_count > 0 {
-                                    if let DiffLine::Context {
-                                        old_num, new_num, ..
-                                    } = &context_buffer[context_lines]
-                                    {
-                                        display_items.push(DiffDisplayItem::Collapsed {
-                                            start_old: *old_num,
-                                            start_new: *new_num,
-                                            count: collapsed_count,
-                                        });
-                                    }
-                                }
+                                Self::push_hidden_context(
+                                    &mut display_items,
+                                    &context_buffer[context_lines..context_lines + collapsed_count],
+                                    true,
+                                    expansion,
+                                );
 
                                 // Show context_lines before current change
                                 let start = context_buffer.len().saturating_sub(context_lines);
@@ -211,21 +426,14 @@ impl DiffView {
                             // This is the first change
                             if context_buffer.len() > context_lines {
                                 // Collapse leading context, only show last context_lines
-                                if self.config.show_edge_collapsed {
-                                    let collapsed_count = context_buffer.len() - context_lines;
-                                    if let DiffLine::Context {
-                                        old_num, new_num, ..
-                                    } = &context_buffer[0]
-                                    {
-                                        display_items.push(DiffDisplayItem::Collapsed {
-                                            start_old: *old_num,
-                                            start_new: *new_num,
-                                            count: collapsed_count,
-                                        });
-                                    }
-                                }
-
                                 let start = context_buffer.len() - context_lines;
+                                Self::push_hidden_context(
+                                    &mut display_items,
+                                    &context_buffer[..start],
+                                    self.config.show_edge_collapsed,
+                                    expansion,
+                                );
+
                                 for ctx in context_buffer.iter().skip(start) {
                                     display_items.push(DiffDisplayItem::Line(ctx.clone()));
                                 }
@@ -254,19 +462,12 @@ impl DiffView {
                 for ctx in context_buffer.iter().take(context_lines) {
                     display_items.push(DiffDisplayItem::Line(ctx.clone()));
                 }
-                if self.config.show_edge_collapsed {
-                    let collapsed_count = context_buffer.len() - context_lines;
-                    if let DiffLine::Context {
-                        old_num, new_num, ..
-                    } = &context_buffer[context_lines]
-                    {
-                        display_items.push(DiffDisplayItem::Collapsed {
-                            start_old: *old_num,
-                            start_new: *new_num,
-                            count: collapsed_count,
-                        });
-                    }
-                }
+                Self::push_hidden_context(
+                    &mut display_items,
+                    &context_buffer[context_lines..],
+                    self.config.show_edge_collapsed,
+                    expansion,
+                );
             } else {
                 // Show all trailing context
                 for ctx in &context_buffer {
@@ -358,34 +559,47 @@ impl DiffView {
         }
     }
 
-    /// Render a collapsed section placeholder
+    /// Render a collapsed section placeholder, click to expand that region
     fn render_collapsed_section<'a>(
         &self,
         start_old: usize,
         start_new: usize,
         count: usize,
+        expansion: &Entity<DiffExpansion>,
         _window: &'a mut Window,
         cx: &'a mut App,
     ) -> impl IntoElement + 'a {
+        let expansion = expansion.clone();
+        let hover_bg = cx.theme().muted.opacity(0.6);
         h_flex()
+            .id(("diff-collapsed", start_old))
             .w_full()
             .items_center()
             .justify_center()
             .bg(cx.theme().muted.opacity(0.3))
             .border_y_1()
             .border_color(cx.theme().border)
+            .cursor_pointer()
+            .hover(move |this| this.bg(hover_bg))
+            .on_click(move |_, _, cx| {
+                expansion.update(cx, |expansion, cx| {
+                    expansion.expand(start_old);
+                    cx.notify();
+                });
+            })
             .child(
                 div()
                     .text_size(px(11.))
                     .text_color(cx.theme().muted_foreground)
-                    .child(format!(
-                        "... {} unchanged lines hidden ({}..{}, {}..{}) ...",
-                        count,
-                        start_old,
-                        start_old + count - 1,
-                        start_new,
-                        start_new + count - 1
-                    )),
+                    .child(
+                        t!(
+                            "acp_ui.diff.hidden_lines",
+                            count = count,
+                            old = format!("{}..{}", start_old, start_old + count - 1),
+                            new = format!("{}..{}", start_new, start_new + count - 1)
+                        )
+                        .to_string(),
+                    ),
             )
     }
 
@@ -393,6 +607,7 @@ impl DiffView {
     fn render_diff_display_item<'a>(
         &self,
         item: &'a DiffDisplayItem,
+        expansion: &Entity<DiffExpansion>,
         window: &'a mut Window,
         cx: &'a mut App,
     ) -> AnyElement {
@@ -405,19 +620,24 @@ impl DiffView {
                 start_new,
                 count,
             } => self
-                .render_collapsed_section(*start_old, *start_new, *count, window, cx)
+                .render_collapsed_section(*start_old, *start_new, *count, expansion, window, cx)
                 .into_any_element(),
         }
     }
 
-    /// Render file header
+    /// Render file header, with an expand/collapse all toggle when lines are hidden
     fn render_file_header<'a>(
         &self,
         path: &'a PathBuf,
         is_new_file: bool,
+        stats: Option<DiffLineStats>,
+        has_hidden_lines: bool,
+        expansion: &Entity<DiffExpansion>,
         _window: &'a mut Window,
         cx: &'a mut App,
     ) -> impl IntoElement + 'a {
+        let expanded_all = expansion.read(cx).is_expanded_all();
+        let expansion = expansion.clone();
         h_flex()
             .items_center()
             .gap_2()
@@ -445,7 +665,54 @@ impl DiffView {
                         .bg(cx.theme().green.opacity(0.2))
                         .text_size(px(11.))
                         .text_color(cx.theme().green)
-                        .child("NEW FILE"),
+                        .child(t!("acp_ui.diff.new_file").to_string()),
+                )
+            })
+            .when_some(stats, |this, stats| {
+                let stats_text = h_flex().gap_1().text_size(px(11.));
+                this.child(if is_new_file {
+                    stats_text
+                        .text_color(cx.theme().muted_foreground)
+                        .child(t!("acp_ui.diff.line_count", count = stats.additions).to_string())
+                } else {
+                    stats_text
+                        .child(
+                            div()
+                                .text_color(cx.theme().green)
+                                .child(format!("+{}", stats.additions)),
+                        )
+                        .child(
+                            div()
+                                .text_color(cx.theme().red)
+                                .child(format!("\u{2212}{}", stats.deletions)),
+                        )
+                        .child(
+                            div()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t!("acp_ui.diff.lines").to_string()),
+                        )
+                })
+            })
+            .when(has_hidden_lines || expanded_all, |this| {
+                this.child(div().flex_1()).child(
+                    Button::new(self.child_id("expand-all"))
+                        .label(if expanded_all {
+                            t!("acp_ui.diff.collapse_unchanged").to_string()
+                        } else {
+                            t!("acp_ui.diff.expand_all").to_string()
+                        })
+                        .ghost()
+                        .xsmall()
+                        .on_click(move |_, _, cx| {
+                            expansion.update(cx, |expansion, cx| {
+                                if expanded_all {
+                                    expansion.collapse_all();
+                                } else {
+                                    expansion.expand_all();
+                                }
+                                cx.notify();
+                            });
+                        }),
                 )
             })
     }
@@ -457,6 +724,36 @@ impl DiffView {
         max_lines: usize,
         _window: &'a mut Window,
         cx: &'a mut App,
+    ) -> impl IntoElement + 'a {
+        h_flex()
+            .items_center()
+            .justify_between()
+            .gap_2()
+            .p_2()
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().yellow.opacity(0.1))
+            .text_size(px(12.))
+            .text_color(cx.theme().yellow)
+            .child(
+                t!(
+                    "acp_ui.diff.too_large",
+                    total = total_lines,
+                    shown = max_lines
+                )
+                .to_string(),
+            )
+            .when_some(
+                self.render_load_more_button("load-more-banner", total_lines - max_lines),
+                |this, button| this.child(button),
+            )
+    }
+
+    /// Render the warning shown above time-limited diffs of large files
+    fn render_large_file_warning<'a>(
+        &self,
+        total_bytes: usize,
+        _window: &'a mut Window,
+        cx: &'a mut App,
     ) -> impl IntoElement + 'a {
         div()
             .p_2()
@@ -464,15 +761,77 @@ impl DiffView {
             .bg(cx.theme().yellow.opacity(0.1))
             .text_size(px(12.))
             .text_color(cx.theme().yellow)
-            .child(format!(
-                "Warning: Diff too large ({} lines). Showing first {}.",
-                total_lines, max_lines
-            ))
+            .child(t!("acp_ui.diff.large_file", size = total_bytes / 1024).to_string())
+    }
+
+    /// Render a size summary in place of the line diff for binary files
+    fn render_binary_summary(&self, window: &mut Window, cx: &mut App) -> AnyElement {
+        let new_bytes = self.diff.new_text.len();
+        let summary = match &self.diff.old_text {
+            Some(old_text) => t!(
+                "acp_ui.diff.binary_changed",
+                old = old_text.len(),
+                new = new_bytes
+            ),
+            None => t!("acp_ui.diff.binary_added", size = new_bytes),
+        };
+        let expansion =
+            window.use_keyed_state(self.state_id(), cx, |_, _| DiffExpansion::default());
+
+        v_flex()
+            .w_full()
+            .gap_2()
+            .when(self.config.show_file_header, |this| {
+                this.child(self.render_file_header(
+                    &self.diff.path,
+                    self.diff.old_text.is_none(),
+                    None,
+                    false,
+                    &expansion,
+                    window,
+                    cx,
+                ))
+            })
+            .child(
+                div()
+                    .w_full()
+                    .p_4()
+                    .flex()
+                    .justify_center()
+                    .rounded(cx.theme().radius)
+                    .bg(cx.theme().secondary)
+                    .text_color(cx.theme().muted_foreground)
+                    .text_size(px(12.))
+                    .child(summary.to_string()),
+            )
+            .into_any_element()
+    }
+
+    /// Button that loads the lines hidden past `max_lines`, if a handler is set
+    fn render_load_more_button(&self, id: &'static str, remaining: usize) -> Option<Button> {
+        let (handler, lines) = self.on_load_more.clone()?;
+        let label = if remaining <= lines {
+            t!("acp_ui.diff.load_remaining", count = remaining)
+        } else {
+            t!("acp_ui.diff.load_more", count = lines)
+        };
+        Some(
+            Button::new(self.child_id(id))
+                .label(label.to_string())
+                .ghost()
+                .xsmall()
+                .on_click(move |_, window, cx| handler(window, cx)),
+        )
     }
 }
 
 impl RenderOnce for DiffView {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        // A line diff of binary content is meaningless, show the sizes instead
+        if self.is_binary() {
+            return self.render_binary_summary(window, cx);
+        }
+
         // Compute diff
         let diff_lines = match &self.diff.old_text {
             Some(old_text) => {
@@ -496,19 +855,46 @@ impl RenderOnce for DiffView {
             }
         };
 
+        // Counted before collapsing so hidden regions don't skew the numbers
+        let stats = DiffLineStats::from_lines(&diff_lines);
+
+        // Expanded regions live in per-diff view state
+        let expansion =
+            window.use_keyed_state(self.state_id(), cx, |_, _| DiffExpansion::default());
         // Apply context collapsing to show only changed parts + context
-        let display_items = self.apply_context_collapsing(diff_lines);
+        let display_items = self.apply_context_collapsing(diff_lines, expansion.read(cx));
+        let has_hidden_lines = display_items
+            .iter()
+            .any(|item| matches!(item, DiffDisplayItem::Collapsed { .. }));
+        let hunk_starts: HashSet<usize> = if self.hunk_positions.is_some() {
+            hunk_starts(&display_items).into_iter().collect()
+        } else {
+            HashSet::new()
+        };
 
         let total_lines = display_items.len();
         let truncated = total_lines > self.config.max_lines;
         let is_new_file = self.diff.old_text.is_none();
+        let total_bytes = self.total_bytes();
 
         v_flex()
             .w_full()
             .gap_2()
             // File header
             .when(self.config.show_file_header, |this| {
-                this.child(self.render_file_header(&self.diff.path, is_new_file, window, cx))
+                this.child(self.render_file_header(
+                    &self.diff.path,
+                    is_new_file,
+                    Some(stats),
+                    has_hidden_lines,
+                    &expansion,
+                    window,
+                    cx,
+                ))
+            })
+            // Time-limited diff warning
+            .when(total_bytes > LARGE_DIFF_BYTES, |this| {
+                this.child(self.render_large_file_warning(total_bytes, window, cx))
             })
             // Large file warning
             .when(truncated && self.config.show_truncation_warning, |this| {
@@ -537,17 +923,62 @@ impl RenderOnce for DiffView {
                                         .justify_center()
                                         .text_color(cx.theme().muted_foreground)
                                         .text_size(px(12.))
-                                        .child("No changes"),
+                                        .child(t!("acp_ui.diff.no_changes").to_string()),
                                 )
                             })
                             .children(
                                 display_items
                                     .iter()
                                     .take(self.config.max_lines)
-                                    .map(|item| self.render_diff_display_item(item, window, cx)),
-                            ),
+                                    .enumerate()
+                                    .map(|(index, item)| {
+                                        let element = self
+                                            .render_diff_display_item(item, &expansion, window, cx);
+                                        match &self.hunk_positions {
+                                            Some(positions) if hunk_starts.contains(&index) => {
+                                                let positions = positions.clone();
+                                                div()
+                                                    .relative()
+                                                    .child(element)
+                                                    .child(
+                                                        canvas(
+                                                            move |bounds, _, _| {
+                                                                positions.push(bounds.top())
+                                                            },
+                                                            |_, _, _, _| {},
+                                                        )
+                                                        .absolute()
+                                                        .top_0()
+                                                        .left_0()
+                                                        .size_0(),
+                                                    )
+                                                    .into_any_element()
+                                            }
+                                            _ => element,
+                                        }
+                                    }),
+                            )
+                            .when(truncated, |this| {
+                                this.when_some(
+                                    self.render_load_more_button(
+                                        "load-more",
+                                        total_lines - self.config.max_lines,
+                                    ),
+                                    |this, button| {
+                                        this.child(
+                                            h_flex()
+                                                .w_full()
+                                                .justify_center()
+                                                .border_t_1()
+                                                .border_color(cx.theme().border)
+                                                .child(button),
+                                        )
+                                    },
+                                )
+                            }),
                     ),
             )
+            .into_any_element()
     }
 }
 
@@ -571,7 +1002,65 @@ mod tests {
             .context_lines(1)
             .show_edge_collapsed(true);
         let lines = view.compute_diff("a\nb\nc\nX\ne\nf", "a\nb\nc\nd\ne\nf");
-        let items = view.apply_context_collapsing(lines);
+        let items = view.apply_context_collapsing(lines, &DiffExpansion::default());
         assert!(!items.is_empty());
     }
+
+    #[test]
+    fn hunk_starts_finds_runs_of_changes() {
+        let old_text = "a\nb\nc\nd\ne\nf\ng\n";
+        let new_text = "A\nb\nc\nD\nE\nf\ng\nh\n";
+        let diff = Diff::new("file.txt", new_text.to_string()).old_text(old_text.to_string());
+        let view = DiffView::new(diff).context_lines(5);
+        let lines = view.compute_diff(old_text, new_text);
+        let items = view.apply_context_collapsing(lines, &DiffExpansion::default());
+
+        // -a +A | b c | -d -e +D +E | f g | +h
+        assert_eq!(hunk_starts(&items), vec![0, 4, 10]);
+    }
+
+    #[test]
+    fn line_stats_count_changes() {
+        let diff =
+            Diff::new("file.txt", "a\nB\nc\nd\n".to_string()).old_text("a\nb\nc\n".to_string());
+        let view = DiffView::new(diff);
+        let lines = view.compute_diff("a\nb\nc\n", "a\nB\nc\nd\n");
+        assert_eq!(
+            DiffLineStats::from_lines(&lines),
+            DiffLineStats {
+                additions: 2,
+                deletions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn apply_context_collapsing_reveals_expanded_regions() {
+        let old_text = "X\nb\nc\nd\ne\nf\nY";
+        let new_text = "x\nb\nc\nd\ne\nf\ny";
+        let diff = Diff::new("file.txt", new_text.to_string()).old_text(old_text.to_string());
+        let view = DiffView::new(diff).context_lines(1);
+        let lines = view.compute_diff(old_text, new_text);
+
+        let collapsed = view.apply_context_collapsing(lines.clone(), &DiffExpansion::default());
+        let Some(DiffDisplayItem::Collapsed {
+            start_old, count, ..
+        }) = collapsed
+            .iter()
+            .find(|item| matches!(item, DiffDisplayItem::Collapsed { .. }))
+        else {
+            panic!("expected a collapsed region");
+        };
+        assert_eq!((*start_old, *count), (3, 3));
+
+        let mut expansion = DiffExpansion::default();
+        expansion.expand(*start_old);
+        let expanded = view.apply_context_collapsing(lines, &expansion);
+        assert_eq!(expanded.len(), collapsed.len() + 2);
+        assert!(
+            expanded
+                .iter()
+                .all(|item| matches!(item, DiffDisplayItem::Line(_)))
+        );
+    }
 }
@@ -1,8 +1,12 @@
+// Shares the app's locale files; the active locale is set by the app
+rust_i18n::i18n!("../../locales", fallback = "en");
+
 mod agent_message;
 mod agent_thought;
 mod agent_todo_list;
 mod diff_summary;
 mod diff_view;
+mod message_copy;
 mod message_stream;
 mod permission_request;
 mod tool_call_item;
@@ -14,21 +18,35 @@ pub use agent_message::{
     AgentMessageView,
 };
 pub use agent_thought::AgentThoughtItem;
-pub use agent_todo_list::{AgentTodoList, AgentTodoListView, PlanMeta};
+pub use agent_todo_list::{
+    AgentTodoList, AgentTodoListView, PlanEntryClickHandler, PlanEntryMeta, PlanMeta,
+};
 pub use diff_summary::{
     DiffSummary, DiffSummaryData, DiffSummaryOptions, DiffSummaryToolCallHandler, FileChangeStats,
 };
-pub use diff_view::{DiffDisplayItem, DiffLine, DiffView, DiffViewConfig};
-pub use message_stream::{AcpMessageStream, AcpMessageStreamOptions};
+pub use diff_view::{
+    DiffDisplayItem, DiffExpansion, DiffHunkPositions, DiffLine, DiffLineStats,
+    DiffLoadMoreHandler, DiffView, DiffViewConfig, hunk_starts,
+};
+pub use message_copy::{
+    MessageCopyFormat, markdown_to_plain_text, tool_call_markdown, user_message_markdown,
+    user_message_plain_text,
+};
+pub use message_stream::{
+    AcpMessageStream, AcpMessageStreamEvent, AcpMessageStreamOptions, RevealTextResult,
+};
 pub use permission_request::{
     PermissionRequest, PermissionRequestOptions, PermissionRequestView, PermissionResponseHandler,
     permission_is_allow, permission_option_kind_to_icon,
 };
 pub use tool_call_item::{
-    ToolCallDetailHandler, ToolCallItem, ToolCallItemOptions, ToolCallItemView,
+    PreviewMaxLinesProvider, ToolCallDetailHandler, ToolCallItem, ToolCallItemOptions,
+    ToolCallItemView,
 };
 pub use user_message::{
     ResourceInfo, UserMessage, UserMessageData, UserMessageView, get_resource_info,
 };
 
-pub use utils::{extract_terminal_output, extract_xml_content, truncate_lines};
+pub use utils::{
+    extract_terminal_output, extract_xml_content, hidden_line_count, looks_binary, truncate_lines,
+};
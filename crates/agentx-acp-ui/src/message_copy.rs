@@ -0,0 +1,230 @@
+//! Plain text and Markdown versions of conversation items for the clipboard
+
+use agent_client_protocol::{
+    ContentBlock, EmbeddedResourceResource, ToolCall, ToolCallContent, ToolCallStatus,
+};
+use similar::TextDiff;
+
+use crate::UserMessageData;
+use crate::utils::{extract_terminal_output, extract_xml_content};
+
+/// What to put on the clipboard when copying a message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageCopyFormat {
+    /// The text as it is displayed, without Markdown syntax
+    Text,
+    /// Markdown source, keeping formatting and code fences
+    Markdown,
+    /// Markdown of the whole agent turn, including the tool calls it made
+    MarkdownWithToolCalls,
+}
+
+/// Strip Markdown syntax, keeping the text a reader sees
+pub fn markdown_to_plain_text(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let mut text = trimmed;
+        while let Some(rest) = text.strip_prefix('>') {
+            text = rest.trim_start();
+        }
+        let heading = text.trim_start_matches('#');
+        if heading.len() < text.len() && (heading.is_empty() || heading.starts_with(' ')) {
+            text = heading.trim_start();
+        }
+        lines.push(strip_inline_markdown(text));
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// Remove emphasis and code markers and reduce links and images to their text
+fn strip_inline_markdown(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' {
+            let escaped = rest[1..].chars().next();
+            if let Some(escaped) = escaped.filter(char::is_ascii_punctuation) {
+                output.push(escaped);
+                rest = &rest[1 + escaped.len_utf8()..];
+                continue;
+            }
+        }
+        if c == '`' {
+            rest = &rest[1..];
+            continue;
+        }
+        if rest.starts_with("**") || rest.starts_with("__") || rest.starts_with("~~") {
+            rest = &rest[2..];
+            continue;
+        }
+        let link_start = rest.strip_prefix("![").or_else(|| rest.strip_prefix('['));
+        if let Some((label, after)) = link_start.and_then(split_link) {
+            output.push_str(label);
+            rest = after;
+            continue;
+        }
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    output
+}
+
+/// Split `label](url)rest` into the label and the rest
+fn split_link(text: &str) -> Option<(&str, &str)> {
+    let (label, after_label) = text.split_once("](")?;
+    let (_, rest) = after_label.split_once(')')?;
+    Some((label, rest))
+}
+
+/// Wrap `body` in a code fence longer than any backtick run inside it
+fn fenced(info: &str, body: &str) -> String {
+    let longest_run = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{info}\n{}\n{fence}", body.trim_end_matches('\n'))
+}
+
+pub fn user_message_plain_text(data: &UserMessageData) -> String {
+    user_message_blocks(data, false).join("\n\n")
+}
+
+/// Text blocks as written, resources as links or fenced file contents
+pub fn user_message_markdown(data: &UserMessageData) -> String {
+    user_message_blocks(data, true).join("\n\n")
+}
+
+fn user_message_blocks(data: &UserMessageData, markdown: bool) -> Vec<String> {
+    data.contents
+        .iter()
+        .filter_map(|content| match content {
+            ContentBlock::Text(text) => Some(text.text.clone()),
+            ContentBlock::ResourceLink(link) if markdown => {
+                Some(format!("[{}]({})", link.name, link.uri))
+            }
+            ContentBlock::ResourceLink(link) => Some(link.uri.clone()),
+            ContentBlock::Resource(embedded) => match &embedded.resource {
+                EmbeddedResourceResource::TextResourceContents(resource) if markdown => Some(
+                    format!("`{}`\n\n{}", resource.uri, fenced("", &resource.text)),
+                ),
+                EmbeddedResourceResource::TextResourceContents(resource) => {
+                    Some(format!("{}\n{}", resource.uri, resource.text))
+                }
+                EmbeddedResourceResource::BlobResourceContents(resource) => {
+                    Some(resource.uri.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn status_label(status: &ToolCallStatus) -> &'static str {
+    match status {
+        ToolCallStatus::Pending => "pending",
+        ToolCallStatus::InProgress => "in progress",
+        ToolCallStatus::Completed => "completed",
+        ToolCallStatus::Failed => "failed",
+        _ => "unknown",
+    }
+}
+
+/// Title, status and output of a tool call, with output in code fences
+pub fn tool_call_markdown(tool_call: &ToolCall) -> String {
+    let mut sections = vec![format!(
+        "**Tool call: {}** ({})",
+        tool_call.title,
+        status_label(&tool_call.status)
+    )];
+
+    for content in &tool_call.content {
+        match content {
+            ToolCallContent::Content(content) => {
+                if let ContentBlock::Text(text) = &content.content {
+                    let text = extract_xml_content(&text.text, &tool_call.kind);
+                    if !text.trim().is_empty() {
+                        sections.push(fenced("", &text));
+                    }
+                }
+            }
+            ToolCallContent::Diff(diff) => {
+                let path = diff.path.display().to_string();
+                let old_text = diff.old_text.as_deref().unwrap_or_default();
+                let unified = TextDiff::from_lines(old_text, &diff.new_text)
+                    .unified_diff()
+                    .header(&path, &path)
+                    .to_string();
+                sections.push(fenced("diff", &unified));
+            }
+            ToolCallContent::Terminal(terminal) => {
+                if let Some(output) = extract_terminal_output(terminal) {
+                    sections.push(fenced("", &output));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if tool_call.content.is_empty() {
+        if let Some(input) = &tool_call.raw_input {
+            if let Ok(input) = serde_json::to_string_pretty(input) {
+                sections.push(fenced("json", &input));
+            }
+        }
+    }
+
+    sections.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_to_plain_text_keeps_visible_text() {
+        let markdown = "# Title\n\nSee **bold** and `code` in [the docs](https://example.com).\n\n> quoted\n\n```rust\nlet a = `b`;\n```";
+        assert_eq!(
+            markdown_to_plain_text(markdown),
+            "Title\n\nSee bold and code in the docs.\n\nquoted\n\nlet a = `b`;"
+        );
+    }
+
+    #[test]
+    fn fenced_outgrows_backticks_in_body() {
+        assert_eq!(fenced("", "plain"), "```\nplain\n```");
+        assert_eq!(
+            fenced("md", "```\ncode\n```"),
+            "````md\n```\ncode\n```\n````"
+        );
+    }
+
+    #[test]
+    fn user_message_markdown_fences_embedded_files() {
+        let data = UserMessageData::new("session")
+            .add_text("Explain this")
+            .add_embedded_resource("file:///src/main.rs", "fn main() {}", None)
+            .add_resource_link("notes.md", "file:///notes.md");
+        assert_eq!(
+            user_message_markdown(&data),
+            "Explain this\n\n`file:///src/main.rs`\n\n```\nfn main() {}\n```\n\n[notes.md](file:///notes.md)"
+        );
+        assert_eq!(
+            user_message_plain_text(&data),
+            "Explain this\n\nfile:///src/main.rs\nfn main() {}\n\nfile:///notes.md"
+        );
+    }
+}
@@ -1,19 +1,35 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Arc;
 
 use agent_client_protocol::{
-    ContentBlock, ContentChunk, Plan, PlanEntryStatus, SessionUpdate, ToolCall, ToolCallUpdate,
+    ContentBlock, ContentChunk, Plan, PlanEntry, PlanEntryStatus, SessionUpdate, ToolCall,
+    ToolCallUpdate,
 };
 use gpui::{
-    App, Context, Entity, IntoElement, ParentElement, Render, Styled, Window, div, prelude::*,
+    AnyElement, App, ClickEvent, ClipboardItem, Context, Entity, EventEmitter, IntoElement,
+    ParentElement, Pixels, Render, SharedString, Styled, Window, canvas, div, prelude::*,
 };
-use gpui_component::{ActiveTheme, v_flex};
+use gpui_component::{
+    ActiveTheme, IconName, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    menu::{DropdownMenu as _, PopupMenuItem},
+    v_flex,
+};
+use rust_i18n::t;
 
 use crate::agent_thought::AgentThoughtItem;
+use crate::message_copy::{
+    MessageCopyFormat, markdown_to_plain_text, tool_call_markdown, user_message_markdown,
+    user_message_plain_text,
+};
 use crate::user_message::{ResourceItem, get_resource_info};
 use crate::{
     AgentIconProvider, AgentMessage, AgentMessageData, AgentMessageOptions, AgentTodoList,
-    DiffSummary, DiffSummaryData, DiffSummaryOptions, PermissionRequestView, ToolCallItem,
-    ToolCallItemOptions, UserMessageData, UserMessageView,
+    DiffSummary, DiffSummaryData, DiffSummaryOptions, PermissionRequestView, PlanEntryMeta,
+    ToolCallItem, ToolCallItemOptions, UserMessageData, UserMessageView,
 };
 
 #[derive(Clone)]
@@ -33,14 +49,39 @@ impl Default for AcpMessageStreamOptions {
     }
 }
 
+/// Events emitted by the message stream.
+pub enum AcpMessageStreamEvent {
+    /// Bring the given window-space position into view in the enclosing scroll container
+    ScrollTo(Pixels),
+}
+
+/// Outcome of [`AcpMessageStream::reveal_text`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevealTextResult {
+    /// The message was found and a scroll was requested
+    Revealed,
+    /// The message was found but has not been laid out yet; retry after the next frame
+    NotLaidOut,
+    /// No user or agent message contains the text
+    NotFound,
+}
+
 /// Message stream UI for ACP SessionUpdate rendering.
 pub struct AcpMessageStream {
     items: Vec<RenderedItem>,
     index: UpdateStateIndex,
     next_index: usize,
     options: AcpMessageStreamOptions,
+    /// Tool call ids started while a plan entry (keyed by content) was in progress
+    plan_tool_calls: HashMap<String, Vec<String>>,
+    /// Window-space top of each rendered tool call, recorded during layout
+    tool_call_tops: Rc<RefCell<HashMap<String, Pixels>>>,
+    /// Window-space top of each rendered user or agent message, keyed by item index
+    message_tops: Rc<RefCell<HashMap<usize, Pixels>>>,
 }
 
+impl EventEmitter<AcpMessageStreamEvent> for AcpMessageStream {}
+
 impl AcpMessageStream {
     pub fn new() -> Self {
         Self::with_options(AcpMessageStreamOptions::default())
@@ -52,6 +93,9 @@ impl AcpMessageStream {
             index: UpdateStateIndex::new(),
             next_index: 0,
             options,
+            plan_tool_calls: HashMap::new(),
+            tool_call_tops: Rc::new(RefCell::new(HashMap::new())),
+            message_tops: Rc::new(RefCell::new(HashMap::new())),
         }
     }
 
@@ -85,6 +129,10 @@ impl AcpMessageStream {
         agent_name: Option<&str>,
         cx: &mut Context<Self>,
     ) {
+        if let SessionUpdate::ToolCall(tool_call) = &update {
+            self.link_tool_call_to_plan(tool_call.tool_call_id.to_string());
+        }
+
         let mut processor = UpdateProcessor::new(
             &mut self.items,
             &mut self.index,
@@ -99,6 +147,93 @@ impl AcpMessageStream {
         cx.notify();
     }
 
+    /// Associate a newly started tool call with the plan entry in progress
+    fn link_tool_call_to_plan(&mut self, tool_call_id: String) {
+        if self.index.find_tool_call(&tool_call_id).is_some() {
+            return;
+        }
+        if let Some(content) = self.current_todo_in_progress() {
+            self.plan_tool_calls
+                .entry(content)
+                .or_default()
+                .push(tool_call_id);
+        }
+    }
+
+    /// Tool call ids related to a plan entry, preferring ids carried in the entry's meta
+    fn related_tool_calls(&self, entry: &PlanEntry) -> Vec<String> {
+        let meta = PlanEntryMeta::from_entry(entry);
+        let ids = if meta.tool_call_ids.is_empty() {
+            self.plan_tool_calls
+                .get(&entry.content)
+                .cloned()
+                .unwrap_or_default()
+        } else {
+            meta.tool_call_ids
+        };
+        ids.into_iter()
+            .filter(|id| self.index.find_tool_call(id).is_some())
+            .collect()
+    }
+
+    /// Expand the tool calls related to a plan entry and scroll to the first one
+    pub fn reveal_plan_entry(&mut self, entry: &PlanEntry, cx: &mut Context<Self>) {
+        let ids = self.related_tool_calls(entry);
+        let mut first_top = None;
+
+        for item in &self.items {
+            if let RenderedItem::ToolCall(entity) = item {
+                let id = entity.read(cx).tool_call_id().to_string();
+                if ids.contains(&id) {
+                    entity.update(cx, |item, cx| item.set_open(true, cx));
+                    if first_top.is_none() {
+                        first_top = self.tool_call_tops.borrow().get(&id).copied();
+                    }
+                }
+            }
+        }
+
+        if let Some(top) = first_top {
+            cx.emit(AcpMessageStreamEvent::ScrollTo(top));
+        }
+    }
+
+    /// Scroll to the `occurrence`-th user or agent message containing every word of `query`
+    ///
+    /// Matching ignores case. With fewer matches than expected the last one is used.
+    pub fn reveal_text(
+        &mut self,
+        query: &str,
+        occurrence: usize,
+        cx: &mut Context<Self>,
+    ) -> RevealTextResult {
+        let query = query.to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        if terms.is_empty() {
+            return RevealTextResult::NotFound;
+        }
+
+        let matches: Vec<usize> = (0..self.items.len())
+            .filter(|&index| {
+                self.message_copy_text(index, MessageCopyFormat::Markdown, cx)
+                    .map(|text| text.to_lowercase())
+                    .is_some_and(|text| terms.iter().all(|term| text.contains(term)))
+            })
+            .collect();
+        let Some(&index) = matches.get(occurrence).or(matches.last()) else {
+            return RevealTextResult::NotFound;
+        };
+
+        let top = self.message_tops.borrow().get(&index).copied();
+        match top {
+            Some(top) => {
+                cx.emit(AcpMessageStreamEvent::ScrollTo(top));
+                RevealTextResult::Revealed
+            }
+            None => RevealTextResult::NotLaidOut,
+        }
+    }
+
     pub fn add_permission_request(
         &mut self,
         request: Entity<PermissionRequestView>,
@@ -133,6 +268,149 @@ impl AcpMessageStream {
         }
     }
 
+    /// Indices of the items in the turn around `index`, between two user messages
+    fn turn_range(&self, index: usize) -> std::ops::Range<usize> {
+        let is_user = |item: &RenderedItem| matches!(item, RenderedItem::UserMessage(_));
+        let start = self.items[..index]
+            .iter()
+            .rposition(is_user)
+            .map_or(0, |i| i + 1);
+        let end = self.items[index..]
+            .iter()
+            .position(is_user)
+            .map_or(self.items.len(), |i| index + i);
+        start..end
+    }
+
+    fn turn_has_tool_calls(&self, index: usize) -> bool {
+        self.items[self.turn_range(index)]
+            .iter()
+            .any(|item| matches!(item, RenderedItem::ToolCall(_)))
+    }
+
+    /// Clipboard text for the user or agent message at `index`
+    pub fn message_copy_text(
+        &self,
+        index: usize,
+        format: MessageCopyFormat,
+        cx: &App,
+    ) -> Option<String> {
+        match (self.items.get(index)?, format) {
+            (RenderedItem::UserMessage(entity), MessageCopyFormat::Text) => {
+                Some(user_message_plain_text(entity.read(cx).data.read(cx)))
+            }
+            (RenderedItem::UserMessage(entity), _) => {
+                Some(user_message_markdown(entity.read(cx).data.read(cx)))
+            }
+            (RenderedItem::AgentMessage(_, data), MessageCopyFormat::Text) => {
+                Some(markdown_to_plain_text(&data.full_text()))
+            }
+            (RenderedItem::AgentMessage(_, data), MessageCopyFormat::Markdown) => {
+                Some(data.full_text().to_string())
+            }
+            (RenderedItem::AgentMessage(..), MessageCopyFormat::MarkdownWithToolCalls) => {
+                let sections: Vec<String> = self.items[self.turn_range(index)]
+                    .iter()
+                    .filter_map(|item| match item {
+                        RenderedItem::AgentMessage(_, data) => Some(data.full_text().to_string()),
+                        RenderedItem::ToolCall(entity) => {
+                            Some(tool_call_markdown(entity.read(cx).tool_call()))
+                        }
+                        _ => None,
+                    })
+                    .filter(|section| !section.trim().is_empty())
+                    .collect();
+                Some(sections.join("\n\n"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Wrap a message with copy buttons that show while it is hovered
+    fn with_copy_actions(
+        &self,
+        index: usize,
+        message: impl IntoElement,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let group = SharedString::from(format!("message-{}", index));
+        let is_agent_message = matches!(self.items[index], RenderedItem::AgentMessage(..));
+        let has_tool_calls = is_agent_message && self.turn_has_tool_calls(index);
+        let stream = cx.entity().downgrade();
+        let tops = self.message_tops.clone();
+        let copy = move |format: MessageCopyFormat| {
+            let stream = stream.clone();
+            move |_: &ClickEvent, _: &mut Window, cx: &mut App| {
+                let text = stream
+                    .upgrade()
+                    .and_then(|stream| stream.read(cx).message_copy_text(index, format, cx));
+                if let Some(text) = text {
+                    cx.write_to_clipboard(ClipboardItem::new_string(text));
+                }
+            }
+        };
+
+        div()
+            .relative()
+            .w_full()
+            .group(group.clone())
+            .child(message)
+            .child(
+                canvas(
+                    move |bounds, _, _| {
+                        tops.borrow_mut().insert(index, bounds.top());
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .top_0()
+                .left_0()
+                .size_0(),
+            )
+            .child(
+                h_flex()
+                    .absolute()
+                    .top_0()
+                    .right_0()
+                    .gap_0p5()
+                    .invisible()
+                    .group_hover(group, |style| style.visible())
+                    .child(
+                        Button::new(("copy-message", index))
+                            .icon(IconName::Copy)
+                            .ghost()
+                            .xsmall()
+                            .tooltip(t!("acp_ui.message.copy").to_string())
+                            .on_click(copy(MessageCopyFormat::Text)),
+                    )
+                    .child(
+                        Button::new(("copy-message-options", index))
+                            .icon(IconName::ChevronDown)
+                            .ghost()
+                            .xsmall()
+                            .dropdown_menu(move |menu, _, _| {
+                                let menu = menu.item(
+                                    PopupMenuItem::new(
+                                        t!("acp_ui.message.copy_markdown").to_string(),
+                                    )
+                                    .on_click(copy(MessageCopyFormat::Markdown)),
+                                );
+                                if !has_tool_calls {
+                                    return menu;
+                                }
+                                menu.item(
+                                    PopupMenuItem::new(
+                                        t!("acp_ui.message.copy_markdown_with_tool_calls")
+                                            .to_string(),
+                                    )
+                                    .on_click(copy(MessageCopyFormat::MarkdownWithToolCalls)),
+                                )
+                            }),
+                    ),
+            )
+            .into_any_element()
+    }
+
     fn collect_tool_calls(&self, cx: &App) -> Vec<ToolCall> {
         let mut tool_calls = Vec::new();
 
@@ -151,25 +429,53 @@ impl Render for AcpMessageStream {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let mut children = v_flex().gap_3().w_full();
 
-        for item in &self.items {
+        for (index, item) in self.items.iter().enumerate() {
             match item {
                 RenderedItem::UserMessage(entity) => {
-                    children = children.child(entity.clone());
+                    children = children.child(self.with_copy_actions(index, entity.clone(), cx));
                 }
                 RenderedItem::AgentMessage(id, data) => {
                     let msg = AgentMessage::new(get_element_id(id), data.clone())
                         .icon_provider(self.options.agent_icon_provider.clone());
-                    children = children.child(msg);
+                    children = children.child(self.with_copy_actions(index, msg, cx));
                 }
                 RenderedItem::AgentThought(entity) => {
                     children = children.child(entity.clone());
                 }
                 RenderedItem::Plan(plan) => {
-                    let todo_list = AgentTodoList::from_plan(plan.clone());
+                    let linked_entries = plan
+                        .entries
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, entry)| !self.related_tool_calls(entry).is_empty())
+                        .map(|(i, _)| i)
+                        .collect::<HashSet<_>>();
+                    let stream = cx.entity().downgrade();
+                    let todo_list = AgentTodoList::from_plan(plan.clone())
+                        .id(("agent-plan", index))
+                        .linked_entries(linked_entries)
+                        .on_entry_click(Arc::new(move |entry, _, cx| {
+                            _ = stream.update(cx, |this, cx| this.reveal_plan_entry(entry, cx));
+                        }));
                     children = children.child(v_flex().pl_6().child(todo_list));
                 }
                 RenderedItem::ToolCall(entity) => {
-                    children = children.child(v_flex().pl_6().child(entity.clone()));
+                    let tops = self.tool_call_tops.clone();
+                    let id = entity.read(cx).tool_call_id().to_string();
+                    children = children.child(
+                        v_flex().pl_6().relative().child(entity.clone()).child(
+                            canvas(
+                                move |bounds, _, _| {
+                                    tops.borrow_mut().insert(id, bounds.top());
+                                },
+                                |_, _, _, _| {},
+                            )
+                            .absolute()
+                            .top_0()
+                            .left_0()
+                            .size_0(),
+                        ),
+                    );
                 }
                 RenderedItem::PermissionRequest(entity) => {
                     children = children.child(v_flex().pl_6().child(entity.clone()));
@@ -14,17 +14,24 @@ use gpui_component::{
     collapsible::Collapsible,
     h_flex, v_flex,
 };
+use rust_i18n::t;
 use similar::{ChangeTag, TextDiff};
 
 use crate::diff_view::DiffView;
-use crate::utils::{extract_terminal_output, extract_xml_content, truncate_lines};
+use crate::utils::{
+    extract_terminal_output, extract_xml_content, hidden_line_count, truncate_lines,
+};
 
 pub type ToolCallDetailHandler =
     Arc<dyn Fn(ToolCall, &mut Window, &mut Context<ToolCallItem>) + Send + Sync>;
 
+/// Reads the preview line limit at render time, so setting changes apply to existing items
+pub type PreviewMaxLinesProvider = Arc<dyn Fn(&App) -> usize + Send + Sync>;
+
 #[derive(Clone)]
 pub struct ToolCallItemOptions {
     pub preview_max_lines: usize,
+    pub preview_max_lines_provider: Option<PreviewMaxLinesProvider>,
     pub on_open_detail: Option<ToolCallDetailHandler>,
 }
 
@@ -32,6 +39,7 @@ impl Default for ToolCallItemOptions {
     fn default() -> Self {
         Self {
             preview_max_lines: 10,
+            preview_max_lines_provider: None,
             on_open_detail: None,
         }
     }
@@ -43,6 +51,20 @@ impl ToolCallItemOptions {
         self
     }
 
+    /// Read the preview line limit from `provider` on every render instead of a fixed value
+    pub fn preview_max_lines_provider(mut self, provider: PreviewMaxLinesProvider) -> Self {
+        self.preview_max_lines_provider = Some(provider);
+        self
+    }
+
+    /// Current preview line limit (0 disables truncation)
+    pub fn resolve_preview_max_lines(&self, cx: &App) -> usize {
+        match &self.preview_max_lines_provider {
+            Some(provider) => provider(cx),
+            None => self.preview_max_lines,
+        }
+    }
+
     pub fn on_open_detail(mut self, handler: ToolCallDetailHandler) -> Self {
         self.on_open_detail = Some(handler);
         self
@@ -125,6 +147,8 @@ fn tool_call_status_icon(status: &ToolCallStatus) -> Icon {
 pub struct ToolCallItem {
     tool_call: ToolCall,
     open: bool,
+    /// Show the full content instead of the truncated preview
+    preview_expanded: bool,
     options: ToolCallItemOptions,
 }
 
@@ -137,6 +161,7 @@ impl ToolCallItem {
         Self {
             tool_call,
             open: false,
+            preview_expanded: false,
             options,
         }
     }
@@ -145,6 +170,7 @@ impl ToolCallItem {
         Self {
             tool_call,
             open,
+            preview_expanded: false,
             options: ToolCallItemOptions::default(),
         }
     }
@@ -157,6 +183,7 @@ impl ToolCallItem {
         Self {
             tool_call,
             open,
+            preview_expanded: false,
             options,
         }
     }
@@ -181,6 +208,12 @@ impl ToolCallItem {
         cx.notify();
     }
 
+    /// Toggle between the truncated preview and the full content
+    pub fn toggle_preview_expanded(&mut self, cx: &mut Context<Self>) {
+        self.preview_expanded = !self.preview_expanded;
+        cx.notify();
+    }
+
     /// Update the tool call data
     pub fn update_tool_call(&mut self, tool_call: ToolCall, cx: &mut Context<Self>) {
         log::debug!("tool_call: {:?}", &tool_call);
@@ -256,13 +289,15 @@ impl ToolCallItem {
         self.tool_call.title.clone()
     }
 
-    /// Render content based on type
+    /// Render content based on type, returning the element and the number of lines
+    /// hidden by the `max_lines` preview limit
     fn render_content(
         &self,
         content: &ToolCallContent,
+        max_lines: usize,
         window: &mut Window,
         cx: &mut Context<Self>,
-    ) -> AnyElement {
+    ) -> (AnyElement, usize) {
         match content {
             ToolCallContent::Diff(diff) => {
                 // Use DiffView component for diff content, limited to 10 lines
@@ -271,32 +306,31 @@ impl ToolCallItem {
                     .context_lines(1)
                     .show_file_header(false); // Hide file header in compact view
 
-                diff_view.render(window, cx).into_any_element()
+                (diff_view.render(window, cx).into_any_element(), 0)
             }
             ToolCallContent::Content(c) => match &c.content {
                 acp::ContentBlock::Text(text) => {
                     let cleaned_text = extract_xml_content(&text.text, &self.tool_call.kind);
-                    let display_text = if cleaned_text.lines().count() > 20 {
-                        let max_lines = self.options.preview_max_lines;
-                        truncate_lines(&cleaned_text, max_lines)
-                    } else {
-                        cleaned_text
-                    };
-                    div()
+                    let hidden_lines = hidden_line_count(&cleaned_text, max_lines);
+                    let display_text = truncate_lines(&cleaned_text, max_lines);
+                    let element = div()
                         .text_size(px(12.))
                         .text_color(cx.theme().muted_foreground)
                         .line_height(px(18.))
                         .child(display_text)
-                        .into_any_element()
+                        .into_any_element();
+                    (element, hidden_lines)
                 }
-                _ => div()
-                    .text_size(px(12.))
-                    .text_color(cx.theme().muted_foreground)
-                    .child("Unsupported content type")
-                    .into_any_element(),
+                _ => (
+                    div()
+                        .text_size(px(12.))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Unsupported content type")
+                        .into_any_element(),
+                    0,
+                ),
             },
             ToolCallContent::Terminal(terminal) => {
-                let max_lines = self.options.preview_max_lines;
                 let output = extract_terminal_output(terminal).and_then(|text| {
                     if text.trim().is_empty() {
                         None
@@ -304,25 +338,32 @@ impl ToolCallItem {
                         Some(text)
                     }
                 });
-                let display_text = match output {
+                let (display_text, hidden_lines) = match output {
                     Some(text) => {
                         let truncated = truncate_lines(&text, max_lines);
-                        format!("Terminal: {}\n{}", terminal.terminal_id, truncated)
+                        (
+                            format!("Terminal: {}\n{}", terminal.terminal_id, truncated),
+                            hidden_line_count(&text, max_lines),
+                        )
                     }
-                    None => format!("Terminal: {}", terminal.terminal_id),
+                    None => (format!("Terminal: {}", terminal.terminal_id), 0),
                 };
-                div()
+                let element = div()
                     .text_size(px(12.))
                     .text_color(cx.theme().muted_foreground)
                     .line_height(px(18.))
                     .child(display_text)
-                    .into_any_element()
+                    .into_any_element();
+                (element, hidden_lines)
             }
-            _ => div()
-                .text_size(px(12.))
-                .text_color(cx.theme().muted_foreground)
-                .child("Unknown content type")
-                .into_any_element(),
+            _ => (
+                div()
+                    .text_size(px(12.))
+                    .text_color(cx.theme().muted_foreground)
+                    .child("Unknown content type")
+                    .into_any_element(),
+                0,
+            ),
         }
     }
 }
@@ -448,15 +489,49 @@ impl Render for ToolCallItem {
             )
             // Content - only visible when open and has content
             .when(has_content, |this| {
-                this.content(
-                    v_flex().gap_2().pl_8().children(
-                        self.tool_call
-                            .content
-                            .iter()
-                            .map(|content| self.render_content(content, window, cx)),
-                    ),
-                )
-                .max_h(px(300.))
+                let preview_expanded = self.preview_expanded;
+                let max_lines = if preview_expanded {
+                    0
+                } else {
+                    self.options.resolve_preview_max_lines(cx)
+                };
+                let mut hidden_lines = 0;
+                let contents: Vec<AnyElement> = self
+                    .tool_call
+                    .content
+                    .iter()
+                    .map(|content| {
+                        let (element, hidden) = self.render_content(content, max_lines, window, cx);
+                        hidden_lines += hidden;
+                        element
+                    })
+                    .collect();
+
+                this.content(v_flex().gap_2().pl_8().children(contents).when(
+                    hidden_lines > 0 || preview_expanded,
+                    |this| {
+                        this.child(
+                            Button::new(SharedString::from(format!(
+                                "tool-call-{}-preview-toggle",
+                                tool_call_id
+                            )))
+                            .label(if preview_expanded {
+                                t!("acp_ui.tool_call.show_less").to_string()
+                            } else if hidden_lines == 1 {
+                                t!("acp_ui.tool_call.show_more_one").to_string()
+                            } else {
+                                t!("acp_ui.tool_call.show_more", count = hidden_lines).to_string()
+                            })
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(
+                                |this, _ev, _window, cx| {
+                                    this.toggle_preview_expanded(cx);
+                                },
+                            )),
+                        )
+                    },
+                ))
                 .overflow_hidden()
             })
     }
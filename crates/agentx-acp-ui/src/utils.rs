@@ -17,6 +17,41 @@ pub fn truncate_lines(text: &str, max_lines: usize) -> String {
     result
 }
 
+/// Number of lines `truncate_lines` drops from `text`
+pub fn hidden_line_count(text: &str, max_lines: usize) -> usize {
+    if max_lines == 0 {
+        return 0;
+    }
+
+    text.lines().count().saturating_sub(max_lines)
+}
+
+/// Characters sampled by `looks_binary`
+const BINARY_SAMPLE_CHARS: usize = 8192;
+
+/// Heuristic check for binary content that was decoded into a `String`
+///
+/// Null bytes always mean binary. Otherwise the sample is binary when more than
+/// 10% of it is U+FFFD (left by lossy UTF-8 decoding) or control characters other
+/// than common whitespace and escape sequences. Non-ASCII text is never penalized.
+pub fn looks_binary(text: &str) -> bool {
+    if text.contains('\0') {
+        return true;
+    }
+
+    let mut total = 0;
+    let mut suspicious = 0;
+    for c in text.chars().take(BINARY_SAMPLE_CHARS) {
+        total += 1;
+        let is_text_control = matches!(c, '\n' | '\r' | '\t' | '\x0c' | '\x1b');
+        if c == char::REPLACEMENT_CHARACTER || (c.is_control() && !is_text_control) {
+            suspicious += 1;
+        }
+    }
+
+    suspicious * 10 > total
+}
+
 pub fn extract_terminal_output(terminal: &acp::Terminal) -> Option<String> {
     let meta = terminal.meta.as_ref()?;
     extract_terminal_output_from_meta(meta)
@@ -186,6 +221,30 @@ mod tests {
         let text = "line1\nline2\nline3";
         assert_eq!(truncate_lines(text, 2), "line1\nline2");
         assert_eq!(truncate_lines(text, 0), text);
+        assert_eq!(hidden_line_count(text, 2), 1);
+        assert_eq!(hidden_line_count(text, 0), 0);
+    }
+
+    #[test]
+    fn looks_binary_detects_binary_content() {
+        assert!(looks_binary("PK\u{3}\u{4}\0\0\0\0"));
+        let png = String::from_utf8_lossy(&[
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0xd8, 0xfe, 0x81, 0x02, 0x03,
+        ]);
+        assert!(looks_binary(&png));
+    }
+
+    #[test]
+    fn looks_binary_accepts_unusual_utf8_text() {
+        assert!(!looks_binary(""));
+        assert!(!looks_binary("fn main() {\n\tprintln!(\"hi\");\r\n}\n"));
+        assert!(!looks_binary("日本語のテキスト、中文文本，한국어 텍스트"));
+        assert!(!looks_binary("Ünïcödé çafé naïve — “quotes” … €100 🦀🚀👩‍💻"));
+        assert!(!looks_binary("\u{1b}[31mred\u{1b}[0m output\u{c}next page"));
+        assert!(!looks_binary(
+            "a mostly normal file with one stray \u{fffd} replacement character in it"
+        ));
+        assert!(!looks_binary("\u{200b}zero\u{200d}width\u{feff}joiners"));
     }
 
     #[test]
@@ -5,12 +5,15 @@
 
 use std::{
     collections::HashMap,
+    future::Future,
+    path::PathBuf,
     rc::Rc,
     sync::{
         Arc,
         atomic::{AtomicU64, AtomicUsize, Ordering},
     },
     thread,
+    time::Duration,
 };
 
 use agent_client_protocol::{self as acp, Agent as _};
@@ -18,22 +21,94 @@ use agent_client_protocol::{self as acp, Agent as _};
 use anyhow::{Context, Result, anyhow};
 use log::{error, warn};
 use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
     runtime::Builder as RuntimeBuilder,
     sync::{RwLock, mpsc, oneshot},
     task::LocalSet,
 };
 
-use agentx_event_bus::{EventHub, PermissionRequestEvent, SessionUpdateEvent};
+use agentx_event_bus::{AgentConfigEvent, EventHub, PermissionRequestEvent, SessionUpdateEvent};
 use agentx_types::{AgentProcessConfig, ProxyConfig};
 
+use crate::logs::{AgentLogBuffer, DEFAULT_LOG_BUFFER_BYTES, SharedLogBuffer};
+use crate::trace::{SharedTrace, TraceDirection, TraceRecorder, TraceRedactor, tap};
+
 use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 
+/// Time an agent gets to spawn and answer `initialize` before it is marked failed,
+/// unless its config sets `startup_timeout_secs`
+pub const AGENT_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+/// Number of trailing stderr lines attached to a startup failure
+const STARTUP_STDERR_TAIL_LINES: usize = 20;
+/// Time to wait for a failed agent to flush its stderr before reporting
+const STARTUP_STDERR_GRACE: Duration = Duration::from_millis(500);
+/// Buffer size of the pipes between the ACP connection and the agent's stdio
+const TRACE_PIPE_CAPACITY: usize = 64 * 1024;
+
+/// Lifecycle of an agent process, from spawn to a usable connection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AgentLifecycle {
+    /// The process is being spawned or is answering `initialize`
+    Initializing,
+    /// The agent answered `initialize` and accepts requests
+    Ready,
+    /// The last start failed or timed out, with the reported error
+    Failed(String),
+}
+
+/// Error returned when a request can't reach an agent's worker
+///
+/// The process exited, was stopped or is being restarted, so the request may
+/// succeed once the agent is back. Callers can detect it with
+/// `error.downcast_ref::<AgentUnavailable>()`.
+#[derive(Clone, Debug)]
+pub struct AgentUnavailable {
+    pub agent_name: String,
+    /// Whether the agent went away while the request was in flight
+    pub stopped: bool,
+}
+
+impl AgentUnavailable {
+    fn not_running(agent_name: &str) -> Self {
+        Self {
+            agent_name: agent_name.to_string(),
+            stopped: false,
+        }
+    }
+
+    fn stopped(agent_name: &str) -> Self {
+        Self {
+            agent_name: agent_name.to_string(),
+            stopped: true,
+        }
+    }
+}
+
+impl std::fmt::Display for AgentUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.stopped {
+            write!(f, "agent {} stopped", self.agent_name)
+        } else {
+            write!(f, "agent {} is not running", self.agent_name)
+        }
+    }
+}
+
+impl std::error::Error for AgentUnavailable {}
+
 #[derive(Clone)]
 pub struct AgentManager {
     agents: Arc<RwLock<HashMap<String, Arc<AgentHandle>>>>,
+    /// Lifecycle state per agent, including agents that are starting or failed
+    states: Arc<std::sync::Mutex<HashMap<String, AgentLifecycle>>>,
     permission_store: Arc<PermissionStore>,
     event_hub: EventHub,
     proxy_config: Arc<RwLock<ProxyConfig>>,
+    /// Recent stderr output per agent, kept across restarts and failed starts
+    logs: Arc<std::sync::Mutex<HashMap<String, SharedLogBuffer>>>,
+    log_buffer_bytes: Arc<AtomicUsize>,
+    /// ACP trace recorder per agent, kept across restarts
+    traces: Arc<std::sync::Mutex<HashMap<String, SharedTrace>>>,
 }
 
 impl AgentManager {
@@ -47,9 +122,13 @@ impl AgentManager {
         let _ = configs;
         Self {
             agents: Arc::new(RwLock::new(HashMap::new())),
+            states: Arc::new(std::sync::Mutex::new(HashMap::new())),
             permission_store,
             event_hub,
             proxy_config: Arc::new(RwLock::new(proxy_config)),
+            logs: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            traces: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            log_buffer_bytes: Arc::new(AtomicUsize::new(DEFAULT_LOG_BUFFER_BYTES)),
         }
     }
 
@@ -65,27 +144,45 @@ impl AgentManager {
         let proxy_config = Arc::new(RwLock::new(proxy_config));
         let manager = Arc::new(Self {
             agents: Arc::new(RwLock::new(HashMap::new())),
+            states: Arc::new(std::sync::Mutex::new(HashMap::new())),
             permission_store,
             event_hub,
             proxy_config,
+            logs: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            traces: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            log_buffer_bytes: Arc::new(AtomicUsize::new(DEFAULT_LOG_BUFFER_BYTES)),
         });
-        let remaining = Arc::new(AtomicUsize::new(configs.len()));
+
+        // Every configured agent is listed as initializing right away
+        for name in configs.keys() {
+            manager.set_state(name, AgentLifecycle::Initializing);
+        }
+        // Startup timeouts are only reported on the bus
+        {
+            let states = manager.states.clone();
+            manager
+                .event_hub
+                .subscribe_agent_config_agent_events(move |event| {
+                    if let AgentConfigEvent::AgentFailed { name, error } = event {
+                        states
+                            .lock()
+                            .unwrap()
+                            .insert(name.clone(), AgentLifecycle::Failed(error.clone()));
+                    }
+                });
+        }
 
         // Initialize agents in parallel and insert them as soon as each is ready.
-        for (name, cfg) in configs {
+        let event_hub = manager.event_hub.clone();
+        let tasks = start_agents(configs, AGENT_STARTUP_TIMEOUT, event_hub, {
             let manager = manager.clone();
-            let remaining = remaining.clone();
-            smol::spawn(async move {
-                if let Err(e) = manager.add_agent(name.clone(), cfg).await {
-                    warn!("Failed to initialize agent '{}': {}", name, e);
-                }
-                if remaining.fetch_sub(1, Ordering::SeqCst) == 1
-                    && manager.list_agents().await.is_empty()
-                {
-                    warn!("No agents could be initialized, continuing without agents");
-                }
-            })
-            .detach();
+            move |name, cfg| {
+                let manager = manager.clone();
+                async move { manager.add_agent(name, cfg).await }
+            }
+        });
+        for task in tasks {
+            task.detach();
         }
 
         Ok(manager)
@@ -122,6 +219,42 @@ impl AgentManager {
         agents.get(name).cloned()
     }
 
+    /// Lifecycle state of an agent; None for agents the manager never started
+    pub fn agent_state(&self, name: &str) -> Option<AgentLifecycle> {
+        self.states.lock().unwrap().get(name).cloned()
+    }
+
+    /// Lifecycle state of every known agent, sorted by name
+    ///
+    /// Unlike [`Self::list_agents`] this includes agents that are still
+    /// starting or failed to start.
+    pub fn agent_states(&self) -> Vec<(String, AgentLifecycle)> {
+        let mut states: Vec<_> = self
+            .states
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, state)| (name.clone(), state.clone()))
+            .collect();
+        states.sort_by(|a, b| a.0.cmp(&b.0));
+        states
+    }
+
+    /// Record an agent's lifecycle state, announcing when it starts initializing
+    fn set_state(&self, name: &str, state: AgentLifecycle) {
+        let previous = self
+            .states
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), state.clone());
+        if state == AgentLifecycle::Initializing && previous != Some(AgentLifecycle::Initializing) {
+            self.event_hub
+                .publish_agent_config_update(AgentConfigEvent::AgentStarting {
+                    name: name.to_string(),
+                });
+        }
+    }
+
     /// Add a new agent to the manager
     pub async fn add_agent(&self, name: String, config: AgentProcessConfig) -> Result<()> {
         // Check if agent already exists
@@ -133,18 +266,29 @@ impl AgentManager {
         }
 
         // Spawn new agent
-        let handle = AgentHandle::spawn(
+        self.set_state(&name, AgentLifecycle::Initializing);
+        let handle = match AgentHandle::spawn(
             name.clone(),
             config,
             self.permission_store.clone(),
             self.event_hub.clone(),
             self.proxy_config.read().await.clone(),
+            self.log_buffer(&name),
+            self.trace(&name),
         )
-        .await?;
+        .await
+        {
+            Ok(handle) => handle,
+            Err(e) => {
+                self.set_state(&name, AgentLifecycle::Failed(format!("{:#}", e)));
+                return Err(e);
+            }
+        };
 
         // Add to agents map
         let mut agents = self.agents.write().await;
         agents.insert(name.clone(), Arc::new(handle));
+        self.set_state(&name, AgentLifecycle::Ready);
         log::info!("Successfully added agent '{}'", name);
         Ok(())
     }
@@ -158,8 +302,12 @@ impl AgentManager {
                 .ok_or_else(|| anyhow!("Agent '{}' not found", name))?
         };
 
+        self.states.lock().unwrap().remove(name);
+
         // Shutdown the agent
         handle.shutdown().await?;
+        self.logs.lock().unwrap().remove(name);
+        self.traces.lock().unwrap().remove(name);
         log::info!("Successfully removed agent '{}'", name);
         Ok(())
     }
@@ -170,6 +318,8 @@ impl AgentManager {
             let mut agents = self.agents.write().await;
             agents.remove(name)
         };
+        // Also forget agents that never finished starting
+        self.states.lock().unwrap().remove(name);
 
         let Some(handle) = handle else {
             return Ok(false);
@@ -177,38 +327,159 @@ impl AgentManager {
 
         // Shutdown the agent
         handle.shutdown().await?;
+        self.logs.lock().unwrap().remove(name);
+        self.traces.lock().unwrap().remove(name);
         log::info!("Successfully removed agent '{}'", name);
         Ok(true)
     }
 
+    /// Recent stderr output of an agent, oldest line first
+    ///
+    /// Also available for agents that failed to start.
+    pub fn agent_logs(&self, name: &str) -> String {
+        self.logs
+            .lock()
+            .unwrap()
+            .get(name)
+            .map(|buffer| buffer.lock().unwrap().contents())
+            .unwrap_or_default()
+    }
+
+    /// Set the per-agent log buffer size in bytes
+    pub fn set_log_buffer_size(&self, bytes: usize) {
+        self.log_buffer_bytes.store(bytes, Ordering::SeqCst);
+        for buffer in self.logs.lock().unwrap().values() {
+            buffer.lock().unwrap().set_capacity(bytes);
+        }
+    }
+
+    /// Start recording an agent's ACP traffic to `path`, replacing a running trace
+    ///
+    /// Takes effect immediately, also for agents that are already running.
+    pub fn start_trace(&self, name: &str, path: PathBuf, redact: TraceRedactor) -> Result<()> {
+        let recorder = TraceRecorder::open(path, redact)?;
+        log::info!("Recording ACP trace of '{}' to {:?}", name, recorder.path());
+        *self.trace(name).lock().unwrap() = Some(recorder);
+        Ok(())
+    }
+
+    /// Stop recording an agent's ACP traffic, returning the trace file
+    pub fn stop_trace(&self, name: &str) -> Option<PathBuf> {
+        let recorder = self.trace(name).lock().unwrap().take()?;
+        log::info!("Stopped ACP trace of '{}'", name);
+        Some(recorder.path().to_path_buf())
+    }
+
+    /// File the agent's ACP traffic is being recorded to
+    pub fn trace_path(&self, name: &str) -> Option<PathBuf> {
+        self.traces.lock().unwrap().get(name).and_then(|trace| {
+            trace
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|r| r.path().to_path_buf())
+        })
+    }
+
+    /// Trace recorder slot for an agent, created on first use
+    fn trace(&self, name: &str) -> SharedTrace {
+        self.traces
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Log buffer for an agent, created on first use
+    fn log_buffer(&self, name: &str) -> SharedLogBuffer {
+        self.logs
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| AgentLogBuffer::shared(self.log_buffer_bytes.load(Ordering::SeqCst)))
+            .clone()
+    }
+
+    /// Shut down all agents and wait for their processes to exit
+    ///
+    /// Called when the application quits so no agent process outlives it.
+    pub async fn shutdown_all(&self) {
+        let handles: Vec<_> = {
+            let mut agents = self.agents.write().await;
+            agents.drain().collect()
+        };
+
+        let tasks: Vec<_> = handles
+            .into_iter()
+            .map(|(name, handle)| {
+                smol::spawn(async move {
+                    if let Err(e) = handle.shutdown().await {
+                        warn!("Failed to shutdown agent '{}': {}", name, e);
+                    }
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await;
+        }
+    }
+
     /// Restart an agent with new configuration
+    ///
+    /// Also starts agents whose previous start failed and so have no process.
     pub async fn restart_agent(&self, name: &str, config: AgentProcessConfig) -> Result<()> {
         // Remove old agent
         let old_handle = {
             let mut agents = self.agents.write().await;
-            agents
-                .remove(name)
-                .ok_or_else(|| anyhow!("Agent '{}' not found", name))?
+            agents.remove(name)
         };
 
         // Shutdown old agent
-        if let Err(e) = old_handle.shutdown().await {
-            warn!("Failed to shutdown old agent '{}': {}", name, e);
+        match old_handle {
+            Some(old_handle) => {
+                if let Err(e) = old_handle.shutdown().await {
+                    warn!("Failed to shutdown old agent '{}': {}", name, e);
+                }
+            }
+            None if self.agent_state(name).is_some() => {}
+            None => return Err(anyhow!("Agent '{}' not found", name)),
         }
 
         // Spawn new agent
-        let new_handle = AgentHandle::spawn(
+        self.set_state(name, AgentLifecycle::Initializing);
+        let new_handle = match AgentHandle::spawn(
             name.to_string(),
             config,
             self.permission_store.clone(),
             self.event_hub.clone(),
             self.proxy_config.read().await.clone(),
+            self.log_buffer(name),
+            self.trace(name),
         )
-        .await?;
+        .await
+        {
+            Ok(handle) => handle,
+            Err(e) => {
+                self.set_state(name, AgentLifecycle::Failed(format!("{:#}", e)));
+                self.event_hub
+                    .publish_agent_config_update(AgentConfigEvent::AgentFailed {
+                        name: name.to_string(),
+                        error: format!("{:#}", e),
+                    });
+                return Err(e);
+            }
+        };
 
         // Add new agent to map
         let mut agents = self.agents.write().await;
         agents.insert(name.to_string(), Arc::new(new_handle));
+        self.set_state(name, AgentLifecycle::Ready);
+        self.event_hub
+            .publish_agent_config_update(AgentConfigEvent::AgentReady {
+                name: name.to_string(),
+            });
         log::info!("Successfully restarted agent '{}'", name);
         Ok(())
     }
@@ -257,6 +528,8 @@ impl AgentHandle {
         permission_store: Arc<PermissionStore>,
         event_hub: EventHub,
         proxy_config: ProxyConfig,
+        logs: SharedLogBuffer,
+        trace: SharedTrace,
     ) -> Result<Self> {
         let (sender, receiver) = mpsc::channel(32);
         let (ready_tx, ready_rx) = oneshot::channel();
@@ -277,6 +550,8 @@ impl AgentHandle {
                     ready_tx,
                     init_response_clone,
                     proxy_config,
+                    logs,
+                    trace,
                 ) {
                     error!("agent {log_name} exited with error: {:?}", err);
                 }
@@ -305,10 +580,10 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         let result = rx
             .await
-            .map_err(|_| anyhow!("agent {} stopped", self.name))?;
+            .map_err(|_| AgentUnavailable::stopped(&self.name))?;
         result
     }
 
@@ -323,10 +598,10 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         let result = rx
             .await
-            .map_err(|_| anyhow!("agent {} stopped", self.name))?;
+            .map_err(|_| AgentUnavailable::stopped(&self.name))?;
         result
     }
 
@@ -341,10 +616,10 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         let result = rx
             .await
-            .map_err(|_| anyhow!("agent {} stopped", self.name))?;
+            .map_err(|_| AgentUnavailable::stopped(&self.name))?;
         result
     }
 
@@ -359,10 +634,10 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         let result = rx
             .await
-            .map_err(|_| anyhow!("agent {} stopped", self.name))?;
+            .map_err(|_| AgentUnavailable::stopped(&self.name))?;
         result
     }
 
@@ -374,10 +649,10 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         let result = rx
             .await
-            .map_err(|_| anyhow!("agent {} stopped", self.name))?;
+            .map_err(|_| AgentUnavailable::stopped(&self.name))?;
         result
     }
 
@@ -391,7 +666,7 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         rx.await
             .map_err(|_| anyhow!("agent {} cancel channel closed", self.name))?
     }
@@ -408,10 +683,10 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         let result = rx
             .await
-            .map_err(|_| anyhow!("agent {} stopped", self.name))?;
+            .map_err(|_| AgentUnavailable::stopped(&self.name))?;
         result
     }
 
@@ -428,10 +703,10 @@ impl AgentHandle {
                 respond: tx,
             })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         let result = rx
             .await
-            .map_err(|_| anyhow!("agent {} stopped", self.name))?;
+            .map_err(|_| AgentUnavailable::stopped(&self.name))?;
         result
     }
 
@@ -441,7 +716,7 @@ impl AgentHandle {
         self.sender
             .send(AgentCommand::Shutdown { respond: tx })
             .await
-            .map_err(|_| anyhow!("agent {} is not running", self.name))?;
+            .map_err(|_| AgentUnavailable::not_running(&self.name))?;
         rx.await
             .map_err(|_| anyhow!("agent {} shutdown channel closed", self.name))?
     }
@@ -452,6 +727,71 @@ impl AgentHandle {
     }
 }
 
+/// Startup timeout for an agent, falling back to `default` when its config has none
+fn startup_timeout(config: &AgentProcessConfig, default: Duration) -> Duration {
+    config
+        .startup_timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or(default)
+}
+
+/// Start every agent concurrently, each bounded by its own timeout.
+///
+/// Readiness and failures are published per agent on the agent config bus,
+/// so one slow or broken agent never holds up the others.
+fn start_agents<F, Fut>(
+    configs: HashMap<String, AgentProcessConfig>,
+    default_timeout: Duration,
+    event_hub: EventHub,
+    start: F,
+) -> Vec<smol::Task<()>>
+where
+    F: Fn(String, AgentProcessConfig) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let total = configs.len();
+    let remaining = Arc::new(AtomicUsize::new(total));
+    let failed = Arc::new(AtomicUsize::new(0));
+
+    configs
+        .into_iter()
+        .map(|(name, cfg)| {
+            let timeout = startup_timeout(&cfg, default_timeout);
+            let startup = start(name.clone(), cfg);
+            let event_hub = event_hub.clone();
+            let remaining = remaining.clone();
+            let failed = failed.clone();
+
+            smol::spawn(async move {
+                let result = smol::future::or(startup, async move {
+                    smol::Timer::after(timeout).await;
+                    Err(anyhow!("agent did not start within {:?}", timeout))
+                })
+                .await;
+
+                let event = match result {
+                    Ok(()) => AgentConfigEvent::AgentReady { name },
+                    Err(e) => {
+                        warn!("Failed to initialize agent '{}': {:#}", name, e);
+                        failed.fetch_add(1, Ordering::SeqCst);
+                        AgentConfigEvent::AgentFailed {
+                            name,
+                            error: format!("{:#}", e),
+                        }
+                    }
+                };
+                event_hub.publish_agent_config_update(event);
+
+                if remaining.fetch_sub(1, Ordering::SeqCst) == 1
+                    && failed.load(Ordering::SeqCst) == total
+                {
+                    warn!("No agents could be initialized, continuing without agents");
+                }
+            })
+        })
+        .collect()
+}
+
 enum AgentCommand {
     Initialize {
         request: Box<acp::InitializeRequest>,
@@ -504,6 +844,8 @@ fn run_agent_worker(
     ready_tx: oneshot::Sender<Result<agent_client_protocol::InitializeResponse>>,
     init_response: Arc<std::sync::RwLock<Option<acp::InitializeResponse>>>,
     proxy_config: ProxyConfig,
+    logs: SharedLogBuffer,
+    trace: SharedTrace,
 ) -> Result<()> {
     let runtime = RuntimeBuilder::new_current_thread()
         .enable_all()
@@ -522,6 +864,8 @@ fn run_agent_worker(
                 ready_tx,
                 init_response,
                 proxy_config,
+                logs,
+                trace,
             ))
             .await
     })
@@ -536,6 +880,8 @@ async fn agent_event_loop(
     ready_tx: oneshot::Sender<Result<agent_client_protocol::InitializeResponse>>,
     init_response: Arc<std::sync::RwLock<Option<acp::InitializeResponse>>>,
     proxy_config: ProxyConfig,
+    logs: SharedLogBuffer,
+    trace: SharedTrace,
 ) -> Result<()> {
     // Node.js environment validation
     let requires_nodejs = config.command.ends_with(".js")
@@ -615,24 +961,73 @@ async fn agent_event_loop(
         }
     }
 
+    if let Some(dir) = &config.working_dir_override {
+        // Fail with a clear message instead of a spawn error that reads like a missing command
+        if !dir.is_dir() {
+            let message = format!(
+                "working directory for agent {agent_name} does not exist: {}",
+                dir.display()
+            );
+            let _ = ready_tx.send(Err(anyhow!(message.clone())));
+            return Err(anyhow!(message));
+        }
+        command.current_dir(dir);
+    }
+
+    // Never outlive the worker, whether it shuts down or startup is abandoned
+    command.kill_on_drop(true);
+
     // Set stdio for all platforms
     command.stdin(std::process::Stdio::piped());
     command.stdout(std::process::Stdio::piped());
-    command.stderr(std::process::Stdio::inherit());
+    command.stderr(std::process::Stdio::piped());
 
-    let mut child = command
-        .spawn()
-        .with_context(|| format!("failed to spawn agent {agent_name}"))?;
-    let outgoing = child
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            let message = startup_failure_message(
+                &format!("failed to spawn agent {agent_name}: {err}"),
+                &config.command,
+                &[],
+            );
+            let _ = ready_tx.send(Err(anyhow!(message.clone())));
+            return Err(anyhow!(message));
+        }
+    };
+
+    // Forward stderr to our own stderr and capture it in the agent's log buffer
+    let stderr_handle = child.stderr.take().map(|stderr| {
+        let logs = logs.clone();
+        let agent_name = agent_name.clone();
+        tokio::task::spawn_local(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                eprintln!("[{agent_name}] {line}");
+                logs.lock().unwrap().push_line(line);
+            }
+        })
+    });
+    let stdin = child
         .stdin
         .take()
-        .ok_or_else(|| anyhow!("agent {agent_name} missing stdin"))?
-        .compat_write();
-    let incoming = child
+        .ok_or_else(|| anyhow!("agent {agent_name} missing stdin"))?;
+    let stdout = child
         .stdout
         .take()
-        .ok_or_else(|| anyhow!("agent {agent_name} missing stdout"))?
-        .compat();
+        .ok_or_else(|| anyhow!("agent {agent_name} missing stdout"))?;
+
+    // Route the traffic through taps so it can be recorded while running
+    let (outgoing, to_agent) = tokio::io::duplex(TRACE_PIPE_CAPACITY);
+    let (from_agent, incoming) = tokio::io::duplex(TRACE_PIPE_CAPACITY);
+    tokio::task::spawn_local(tap(
+        to_agent,
+        stdin,
+        trace.clone(),
+        TraceDirection::Outgoing,
+    ));
+    tokio::task::spawn_local(tap(stdout, from_agent, trace, TraceDirection::Incoming));
+    let outgoing = outgoing.compat_write();
+    let incoming = incoming.compat();
 
     let client = GuiClient::new(agent_name.clone(), permission_store, event_hub);
     let (conn, io_task) = acp::ClientSideConnection::new(client, outgoing, incoming, |fut| {
@@ -656,7 +1051,24 @@ async fn agent_event_loop(
     init_request.client_capabilities = acp::ClientCapabilities::default();
     init_request.client_info = Some(client_info);
     init_request.meta = None;
-    let init_result = conn.initialize(init_request).await;
+
+    // The caller drops the receiver when it stops waiting; a wedged agent must
+    // not keep its process and this thread alive after that
+    let mut ready_tx = ready_tx;
+    let timeout = startup_timeout(&config, AGENT_STARTUP_TIMEOUT);
+    let init_result = tokio::select! {
+        result = conn.initialize(init_request) => result,
+        _ = ready_tx.closed() => {
+            let reason = "the caller stopped waiting";
+            return Err(abandon_startup(&agent_name, &mut child, reason).await);
+        }
+        _ = tokio::time::sleep(timeout) => {
+            let reason = format!("no initialize response within {:?}", timeout);
+            let error = abandon_startup(&agent_name, &mut child, &reason).await;
+            let _ = ready_tx.send(Err(anyhow!("{error}")));
+            return Err(error);
+        }
+    };
     log::info!(
         "Agent {} initialized  === >>> {:?}",
         agent_name,
@@ -669,12 +1081,23 @@ async fn agent_event_loop(
             let _ = ready_tx.send(Ok(res));
         }
         Err(err) => {
-            let message = format!("failed to initialize agent {agent_name}: {:?}", err);
+            // The agent usually exits on a startup error; give it a moment to
+            // flush stderr so the report contains the actual cause
+            if let Some(handle) = stderr_handle {
+                let _ = tokio::time::timeout(STARTUP_STDERR_GRACE, handle).await;
+            }
+            let tail = logs.lock().unwrap().tail(STARTUP_STDERR_TAIL_LINES);
+            let message = startup_failure_message(
+                &format!("failed to initialize agent {agent_name}: {:?}", err),
+                &config.command,
+                &tail,
+            );
             let _ = ready_tx.send(Err(anyhow!(message.clone())));
             return Err(anyhow!(message));
         }
     }
 
+    let mut shutdown_respond = None;
     while let Some(command) = command_rx.recv().await {
         match command {
             AgentCommand::Initialize { request, respond } => {
@@ -768,7 +1191,8 @@ async fn agent_event_loop(
             }
             AgentCommand::Shutdown { respond } => {
                 log::info!("Agent {} received shutdown command", agent_name);
-                let _ = respond.send(Ok(()));
+                // Respond once the process is gone so callers can wait for it
+                shutdown_respond = Some(respond);
                 break; // Exit the command loop to shutdown
             }
         }
@@ -776,10 +1200,7 @@ async fn agent_event_loop(
 
     log::info!("Agent {} command loop ended, cleaning up", agent_name);
 
-    drop(conn);
-    let _ = io_handle.await;
-
-    // Check if child process is still running
+    // Stop the process before draining IO so a hung agent can't block shutdown
     match child.try_wait() {
         Ok(Some(status)) => {
             log::warn!(
@@ -800,9 +1221,43 @@ async fn agent_event_loop(
         }
     }
 
+    drop(conn);
+    let _ = io_handle.await;
+
+    if let Some(respond) = shutdown_respond {
+        let _ = respond.send(Ok(()));
+    }
+
     Ok(())
 }
 
+/// Kill an agent whose startup was given up on, returning the error ending its worker
+async fn abandon_startup(
+    agent_name: &str,
+    child: &mut tokio::process::Child,
+    reason: &str,
+) -> anyhow::Error {
+    warn!("Abandoning startup of agent {}: {}", agent_name, reason);
+    if let Err(e) = child.kill().await {
+        error!("Failed to kill agent {} process: {}", agent_name, e);
+    }
+    anyhow!("startup of agent {agent_name} abandoned: {reason}")
+}
+
+/// Describe a startup failure with the resolved command and the agent's last stderr lines
+fn startup_failure_message(error: &str, command: &str, stderr_tail: &[String]) -> String {
+    let resolved = match which::which(command) {
+        Ok(path) => path.display().to_string(),
+        Err(_) => format!("{command} (not found in PATH)"),
+    };
+    let mut message = format!("{error}\nCommand: {resolved}");
+    if !stderr_tail.is_empty() {
+        message.push_str("\nStderr:\n");
+        message.push_str(&stderr_tail.join("\n"));
+    }
+    message
+}
+
 /// GUI Client that publishes session updates to the event bus
 struct GuiClient {
     agent_name: String,
@@ -991,3 +1446,141 @@ impl PermissionStore {
         self.pending.write().await.remove(id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    fn mock_config(command: &str) -> AgentProcessConfig {
+        AgentProcessConfig {
+            command: command.to_string(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            icon: None,
+            order: None,
+            pinned: false,
+            request_timeout_secs: None,
+            startup_timeout_secs: None,
+            working_dir_override: None,
+            modes: Vec::new(),
+            nodejs_path: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_and_failing_agents_do_not_block_others() {
+        let event_hub = EventHub::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        {
+            let events = events.clone();
+            event_hub.subscribe_agent_config_agent_events(move |event| {
+                let entry = match event {
+                    AgentConfigEvent::AgentReady { name } => (name.clone(), None),
+                    AgentConfigEvent::AgentFailed { name, error } => {
+                        (name.clone(), Some(error.clone()))
+                    }
+                    _ => return,
+                };
+                events.lock().unwrap().push(entry);
+            });
+        }
+
+        let configs = HashMap::from([
+            ("fast".to_string(), mock_config("fast")),
+            ("slow".to_string(), mock_config("slow")),
+            ("failing".to_string(), mock_config("failing")),
+        ]);
+
+        let started = Instant::now();
+        let tasks = start_agents(
+            configs,
+            Duration::from_millis(200),
+            event_hub,
+            |_name, cfg| async move {
+                match cfg.command.as_str() {
+                    "slow" => {
+                        smol::Timer::after(Duration::from_secs(30)).await;
+                        Ok(())
+                    }
+                    "failing" => Err(anyhow!("command not found")),
+                    _ => Ok(()),
+                }
+            },
+        );
+        for task in tasks {
+            task.await;
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        // The slow agent only finishes once its timeout elapses
+        assert_eq!(events[2].0, "slow");
+        assert!(events[2].1.as_deref().unwrap().contains("did not start"));
+
+        let fast = events.iter().find(|(name, _)| name == "fast").unwrap();
+        assert_eq!(fast.1, None);
+        let failing = events.iter().find(|(name, _)| name == "failing").unwrap();
+        assert_eq!(failing.1.as_deref(), Some("command not found"));
+    }
+
+    #[test]
+    fn test_startup_timeout_prefers_agent_config() {
+        let default = Duration::from_secs(60);
+        let mut config = mock_config("slow");
+        assert_eq!(startup_timeout(&config, default), default);
+
+        config.startup_timeout_secs = Some(300);
+        assert_eq!(startup_timeout(&config, default), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_agent_states_announce_each_start_once() {
+        let event_hub = EventHub::new();
+        let starts = Arc::new(AtomicUsize::new(0));
+        {
+            let starts = starts.clone();
+            event_hub.subscribe_agent_config_agent_events(move |event| {
+                if matches!(event, AgentConfigEvent::AgentStarting { .. }) {
+                    starts.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+        let manager = AgentManager::new(
+            HashMap::new(),
+            Arc::new(PermissionStore::default()),
+            event_hub,
+            ProxyConfig::default(),
+        );
+
+        manager.set_state("b", AgentLifecycle::Initializing);
+        manager.set_state("a", AgentLifecycle::Initializing);
+        manager.set_state("a", AgentLifecycle::Initializing);
+        manager.set_state("b", AgentLifecycle::Failed("boom".to_string()));
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            manager.agent_states(),
+            vec![
+                ("a".to_string(), AgentLifecycle::Initializing),
+                ("b".to_string(), AgentLifecycle::Failed("boom".to_string())),
+            ]
+        );
+
+        // A restart after a failure is announced again
+        manager.set_state("b", AgentLifecycle::Initializing);
+        assert_eq!(starts.load(Ordering::SeqCst), 3);
+        assert_eq!(manager.agent_state("missing"), None);
+    }
+
+    #[test]
+    fn test_startup_failure_message_includes_command_and_stderr() {
+        let tail = vec!["Error: Cannot find module 'x'".to_string()];
+        let message =
+            startup_failure_message("failed to initialize agent a", "no-such-agent-bin", &tail);
+        assert!(message.starts_with("failed to initialize agent a\n"));
+        assert!(message.contains("Command: no-such-agent-bin (not found in PATH)"));
+        assert!(message.ends_with("Stderr:\nError: Cannot find module 'x'"));
+    }
+}
@@ -1,4 +1,8 @@
 pub mod client;
+pub mod logs;
 pub mod nodejs;
+pub mod trace;
 
-pub use client::{AgentHandle, AgentManager, PermissionStore};
+pub use client::{AgentHandle, AgentLifecycle, AgentManager, AgentUnavailable, PermissionStore};
+pub use logs::{AgentLogBuffer, DEFAULT_LOG_BUFFER_BYTES};
+pub use trace::{TraceRecorder, TraceRedactor};
@@ -0,0 +1,94 @@
+//! Bounded in-memory capture of agent stderr output
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use agentx_types::DEFAULT_AGENT_LOG_BUFFER_KB;
+
+/// Default size of an agent's log buffer in bytes
+pub const DEFAULT_LOG_BUFFER_BYTES: usize = DEFAULT_AGENT_LOG_BUFFER_KB * 1024;
+
+/// Log buffer shared between an agent's worker thread and the manager
+pub type SharedLogBuffer = Arc<Mutex<AgentLogBuffer>>;
+
+/// Ring buffer keeping the most recent lines of an agent's stderr
+///
+/// Old lines are dropped once the total size exceeds the capacity, so a chatty
+/// agent never grows memory without bound.
+#[derive(Debug)]
+pub struct AgentLogBuffer {
+    lines: VecDeque<String>,
+    bytes: usize,
+    capacity: usize,
+}
+
+impl AgentLogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            bytes: 0,
+            capacity,
+        }
+    }
+
+    pub fn shared(capacity: usize) -> SharedLogBuffer {
+        Arc::new(Mutex::new(Self::new(capacity)))
+    }
+
+    pub fn push_line(&mut self, line: String) {
+        self.bytes += line.len() + 1;
+        self.lines.push_back(line);
+        self.trim();
+    }
+
+    /// Change the capacity, dropping old lines if the buffer no longer fits
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    /// The last `count` lines, oldest first
+    pub fn tail(&self, count: usize) -> Vec<String> {
+        let skip = self.lines.len().saturating_sub(count);
+        self.lines.iter().skip(skip).cloned().collect()
+    }
+
+    /// All buffered lines joined with newlines
+    pub fn contents(&self) -> String {
+        let mut contents = String::with_capacity(self.bytes);
+        for line in &self.lines {
+            contents.push_str(line);
+            contents.push('\n');
+        }
+        contents
+    }
+
+    fn trim(&mut self) {
+        while self.bytes > self.capacity {
+            let Some(line) = self.lines.pop_front() else {
+                break;
+            };
+            self.bytes -= line.len() + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drops_oldest_lines_when_full() {
+        let mut buffer = AgentLogBuffer::new(13);
+        buffer.push_line("first".to_string());
+        buffer.push_line("second".to_string());
+        assert_eq!(buffer.contents(), "first\nsecond\n");
+
+        buffer.push_line("third".to_string());
+        assert_eq!(buffer.contents(), "second\nthird\n");
+        assert_eq!(buffer.tail(1), vec!["third".to_string()]);
+
+        buffer.set_capacity(6);
+        assert_eq!(buffer.contents(), "third\n");
+    }
+}
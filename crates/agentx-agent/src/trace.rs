@@ -0,0 +1,136 @@
+//! Recording of the raw ACP JSON-RPC traffic of an agent
+//!
+//! Every agent's stdin/stdout goes through [`tap`], which copies the
+//! newline-delimited messages and appends them to the agent's trace file
+//! while a recorder is set. Recording can be started and stopped while the
+//! agent is running.
+
+use std::fs::File;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context as _, Result};
+use tokio::io::{AsyncBufReadExt as _, AsyncRead, AsyncWrite, AsyncWriteExt as _, BufReader};
+
+/// Scrubs secrets from a message before it is written
+pub type TraceRedactor = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Recorder slot shared between an agent's worker thread and the manager
+pub type SharedTrace = Arc<Mutex<Option<TraceRecorder>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceDirection {
+    /// App to agent
+    Outgoing,
+    /// Agent to app
+    Incoming,
+}
+
+impl TraceDirection {
+    fn marker(self) -> &'static str {
+        match self {
+            Self::Outgoing => "-->",
+            Self::Incoming => "<--",
+        }
+    }
+}
+
+/// Appends timestamped, redacted messages to a trace file
+pub struct TraceRecorder {
+    path: PathBuf,
+    file: File,
+    redact: TraceRedactor,
+}
+
+impl TraceRecorder {
+    /// Open `path` for appending, creating it and its directory if needed
+    pub fn open(path: PathBuf, redact: TraceRedactor) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open trace file {}", path.display()))?;
+        Ok(Self { path, file, redact })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write one message as `<timestamp> <direction> <message>`
+    pub fn record(&mut self, direction: TraceDirection, message: &str) {
+        let line = format!(
+            "{} {} {}\n",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            direction.marker(),
+            (self.redact)(message)
+        );
+        if let Err(e) = self.file.write_all(line.as_bytes()) {
+            log::warn!("Failed to write ACP trace {:?}: {}", self.path, e);
+        }
+    }
+}
+
+/// Copy newline-delimited messages from `reader` to `writer`, recording each
+/// one while `trace` holds a recorder
+pub(crate) async fn tap<R, W>(
+    reader: R,
+    mut writer: W,
+    trace: SharedTrace,
+    direction: TraceDirection,
+) -> std::io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line).await? == 0 {
+            break;
+        }
+        if let Some(recorder) = trace.lock().unwrap().as_mut() {
+            recorder.record(direction, String::from_utf8_lossy(&line).trim_end());
+        }
+        writer.write_all(&line).await?;
+        writer.flush().await?;
+    }
+    writer.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tap_forwards_and_records_redacted_lines() {
+        let path =
+            std::env::temp_dir().join(format!("agentx_trace_test_{}.log", std::process::id()));
+        _ = std::fs::remove_file(&path);
+        let redact: TraceRedactor = Arc::new(|message| message.replace("sk-secret", "[REDACTED]"));
+        let trace: SharedTrace = Arc::new(Mutex::new(Some(
+            TraceRecorder::open(path.clone(), redact).unwrap(),
+        )));
+
+        let input: &[u8] = b"{\"id\":1,\"key\":\"sk-secret\"}\n{\"id\":2}\n";
+        let mut output = Vec::new();
+        tap(input, &mut output, trace, TraceDirection::Outgoing)
+            .await
+            .unwrap();
+
+        // The agent still receives the original bytes
+        assert_eq!(output, input);
+
+        let recorded = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = recorded.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("--> {\"id\":1,\"key\":\"[REDACTED]\"}"));
+        assert!(!recorded.contains("sk-secret"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
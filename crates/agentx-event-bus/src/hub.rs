@@ -324,6 +324,9 @@ impl EventHub {
                         AgentConfigEvent::AgentAdded { .. }
                             | AgentConfigEvent::AgentUpdated { .. }
                             | AgentConfigEvent::AgentRemoved { .. }
+                            | AgentConfigEvent::AgentStarting { .. }
+                            | AgentConfigEvent::AgentReady { .. }
+                            | AgentConfigEvent::AgentFailed { .. }
                     )
                 )
             },
@@ -444,6 +447,9 @@ impl EventHub {
                         AgentConfigEvent::AgentAdded { name, .. }
                             | AgentConfigEvent::AgentUpdated { name, .. }
                             | AgentConfigEvent::AgentRemoved { name }
+                            | AgentConfigEvent::AgentStarting { name }
+                            | AgentConfigEvent::AgentReady { name }
+                            | AgentConfigEvent::AgentFailed { name, .. }
                     ) if name == &agent_name
                 )
             },
@@ -492,6 +498,36 @@ impl Default for EventHub {
     }
 }
 
+/// Hub subscription that is removed when dropped
+///
+/// UI panels keep these in a field, so closing the panel also removes its
+/// callbacks from the hub. Dropping the callback drops the channel sender it
+/// captured, which ends the panel's receiving task.
+#[must_use = "the subscription is removed as soon as this is dropped"]
+pub struct EventSubscription {
+    hub: EventHub,
+    id: SubscriptionId,
+}
+
+impl EventSubscription {
+    pub fn new(hub: &EventHub, id: SubscriptionId) -> Self {
+        Self {
+            hub: hub.clone(),
+            id,
+        }
+    }
+
+    pub fn id(&self) -> SubscriptionId {
+        self.id
+    }
+}
+
+impl Drop for EventSubscription {
+    fn drop(&mut self) {
+        self.hub.unsubscribe(self.id);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -524,4 +560,29 @@ mod tests {
 
         assert_eq!(received.lock().unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_dropped_subscriptions_do_not_accumulate() {
+        let hub = EventHub::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        // Simulates a panel opened and closed many times
+        for _ in 0..100 {
+            let tx = tx.clone();
+            let id = hub.subscribe_workspace_updates(move |event| {
+                let _ = tx.send(event.clone());
+            });
+            let subscription = EventSubscription::new(&hub, id);
+            assert_eq!(hub.subscriber_count(), 1);
+            drop(subscription);
+        }
+        drop(tx);
+
+        assert_eq!(hub.subscriber_count(), 0);
+        hub.publish_workspace_update(WorkspaceUpdateEvent::WorkspaceAdded {
+            workspace_id: "ws".to_string(),
+        });
+        // Every sender went away with its callback
+        assert!(rx.recv().is_err());
+    }
 }
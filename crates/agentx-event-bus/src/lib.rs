@@ -3,7 +3,7 @@ pub mod core;
 pub mod hub;
 
 pub use core::{EventBus, EventBusContainer, EventBusStats, SubscriptionId};
-pub use hub::{AppEvent, EventHub};
+pub use hub::{AppEvent, EventHub, EventSubscription};
 
 // Re-export types for convenience
 pub use agentx_types::{
@@ -3,15 +3,326 @@
 //! This service manages agent configuration CRUD operations, validation,
 //! persistence, and hot-reload functionality.
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::AgentService;
 use agentx_agent::AgentManager;
 use agentx_event_bus::{AgentConfigEvent, EventHub};
+use agentx_types::config::{
+    MAX_TOOL_CALL_PREVIEW_MAX_LINES, MIN_TOOL_CALL_PREVIEW_MAX_LINES, McpServerConfig,
+    compare_agents, config_dir, describe_json_error, resolve_config_command, resolve_config_path,
+};
 use agentx_types::{AgentProcessConfig, Config};
 use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+/// Number of timestamped config backups kept next to the config file
+const MAX_CONFIG_BACKUPS: usize = 10;
+/// Timestamp in backup file names, e.g. `config-20250101T120000123456.json`
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%6f";
+
+/// The config file contents as last read or written by this service
+struct DiskSnapshot {
+    hash: u64,
+    value: serde_json::Value,
+}
+
+impl DiskSnapshot {
+    fn read(path: &Path) -> Option<Self> {
+        let json = std::fs::read_to_string(path).ok()?;
+        Self::from_json(&json)
+    }
+
+    fn from_json(json: &str) -> Option<Self> {
+        Some(Self {
+            hash: content_hash(json),
+            value: serde_json::from_str(json).ok()?,
+        })
+    }
+}
+
+/// A timestamped copy of the configuration file
+#[derive(Debug, Clone)]
+pub struct ConfigBackup {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Part of an agent configuration that failed validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentConfigField {
+    Command,
+    /// Argument at this index
+    Arg(usize),
+    /// Environment variable with this key
+    EnvKey(String),
+    WorkingDir,
+}
+
+/// A single problem found in an agent configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentConfigIssue {
+    pub field: AgentConfigField,
+    pub message: String,
+}
+
+/// Problems that would make an agent fail to spawn, one per offending field
+///
+/// Returned (wrapped in `anyhow::Error`) by add, update and restart; callers
+/// can downcast to it to point at the specific fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentConfigValidationError {
+    pub issues: Vec<AgentConfigIssue>,
+}
+
+impl std::fmt::Display for AgentConfigValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let messages: Vec<&str> = self
+            .issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect();
+        write!(f, "Invalid agent configuration: {}", messages.join("; "))
+    }
+}
+
+impl std::error::Error for AgentConfigValidationError {}
+
+/// Where uploads end up and whether the app can write there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadDirStatus {
+    /// Absolute form of the configured directory
+    pub path: PathBuf,
+    /// Why the directory can't be used, if it can't
+    pub error: Option<String>,
+}
+
+impl UploadDirStatus {
+    /// Check the resolved directory `path`, creating it if it is missing
+    ///
+    /// See [`AgentConfigService::resolve_config_path`].
+    pub fn check(path: PathBuf) -> Self {
+        let error = ensure_upload_dir(&path).err().map(|e| format!("{:#}", e));
+        Self { path, error }
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Create `dir` if needed and make sure files can be written to it
+fn ensure_upload_dir(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create upload directory {}", dir.display()))?;
+    if !dir.is_dir() {
+        return Err(anyhow!(
+            "Upload directory {} is not a directory",
+            dir.display()
+        ));
+    }
+    let probe = dir.join(format!(".agentx-write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"")
+        .with_context(|| format!("Upload directory {} is not writable", dir.display()))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Agents to spawn, restart or stop after the config changed
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AgentChanges {
+    pub added: Vec<String>,
+    /// Agents whose command, arguments or environment changed
+    pub restarted: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl AgentChanges {
+    /// Compare two agent tables; changes to order, pin or icon need no restart
+    pub fn between(
+        previous: &HashMap<String, AgentProcessConfig>,
+        current: &HashMap<String, AgentProcessConfig>,
+    ) -> Self {
+        let mut changes = Self::default();
+        for (name, config) in current {
+            match previous.get(name) {
+                None => changes.added.push(name.clone()),
+                Some(old) if process_settings_changed(old, config) => {
+                    changes.restarted.push(name.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed = previous
+            .keys()
+            .filter(|name| !current.contains_key(*name))
+            .cloned()
+            .collect();
+        changes.added.sort();
+        changes.restarted.sort();
+        changes.removed.sort();
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.restarted.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// MCP servers added, changed or removed when replacing the whole set
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct McpServerChanges {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl McpServerChanges {
+    /// Compare two server tables; a renamed server is removed and added
+    pub fn between(
+        current: &HashMap<String, McpServerConfig>,
+        desired: &HashMap<String, McpServerConfig>,
+    ) -> Self {
+        let mut changes = Self::default();
+        for (name, config) in desired {
+            match current.get(name) {
+                None => changes.added.push(name.clone()),
+                Some(existing) if existing != config => changes.updated.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+        changes.removed = current
+            .keys()
+            .filter(|name| !desired.contains_key(*name))
+            .cloned()
+            .collect();
+        changes.added.sort();
+        changes.updated.sort();
+        changes.removed.sort();
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Log a warning when the configured upload directory can't be used
+fn warn_if_upload_dir_unusable(path: PathBuf) {
+    if let Some(error) = UploadDirStatus::check(path).error {
+        log::warn!("{}", error);
+    }
+}
+
+/// Why some agents look like they would get in each other's way
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AgentConflictKind {
+    /// Same command, arguments and environment
+    Duplicate,
+    /// Same port in their arguments or environment
+    Port(u16),
+}
+
+/// Agents that may fail when run side by side
+///
+/// Found by [`detect_agent_conflicts`]. It's a heuristic: both agents are
+/// still started, the conflict is only reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentConflict {
+    pub kind: AgentConflictKind,
+    /// Names of the agents involved, sorted, at least two
+    pub agents: Vec<String>,
+}
+
+impl AgentConflict {
+    /// Stable identity, e.g. for remembering that the user dismissed it
+    pub fn key(&self) -> String {
+        let kind = match self.kind {
+            AgentConflictKind::Duplicate => "duplicate".to_string(),
+            AgentConflictKind::Port(port) => format!("port:{port}"),
+        };
+        format!("{kind}:{}", self.agents.join(","))
+    }
+}
+
+/// Find agents configured identically or declaring the same port
+pub fn detect_agent_conflicts(agents: &HashMap<String, AgentProcessConfig>) -> Vec<AgentConflict> {
+    type CommandLine<'a> = (&'a str, &'a [String], BTreeMap<&'a String, &'a String>);
+    let mut by_command: BTreeMap<CommandLine, Vec<String>> = BTreeMap::new();
+    let mut by_port: BTreeMap<u16, Vec<String>> = BTreeMap::new();
+    for (name, config) in agents {
+        let env = config.env.iter().collect();
+        by_command
+            .entry((config.command.trim(), config.args.as_slice(), env))
+            .or_default()
+            .push(name.clone());
+        for port in declared_ports(config) {
+            by_port.entry(port).or_default().push(name.clone());
+        }
+    }
+
+    let duplicates = by_command
+        .into_values()
+        .map(|agents| (AgentConflictKind::Duplicate, agents));
+    let ports = by_port
+        .into_iter()
+        .map(|(port, agents)| (AgentConflictKind::Port(port), agents));
+    let mut conflicts: Vec<_> = duplicates
+        .chain(ports)
+        .filter(|(_, agents)| agents.len() > 1)
+        .map(|(kind, mut agents)| {
+            agents.sort();
+            AgentConflict { kind, agents }
+        })
+        .collect();
+    conflicts.sort_by(|a, b| a.agents.cmp(&b.agents).then(a.kind.cmp(&b.kind)));
+    conflicts
+}
+
+/// Ports an agent asks for with `--port N`, `--port=N`, `-p N` (or any
+/// `--…-port` flag) or with an env var whose name ends in `PORT`
+fn declared_ports(config: &AgentProcessConfig) -> BTreeSet<u16> {
+    let is_port_flag =
+        |flag: &str| flag == "-p" || (flag.starts_with("--") && flag.ends_with("port"));
+    let mut ports = BTreeSet::new();
+    let mut args = config.args.iter().map(|arg| arg.trim()).peekable();
+    while let Some(arg) = args.next() {
+        let value = match arg.split_once('=') {
+            Some((flag, value)) if is_port_flag(flag) => Some(value),
+            None if is_port_flag(arg) => args.peek().copied(),
+            _ => None,
+        };
+        ports.extend(value.and_then(|value| value.parse().ok()));
+    }
+    for (key, value) in &config.env {
+        if key.to_ascii_uppercase().ends_with("PORT") {
+            ports.extend(value.trim().parse::<u16>().ok());
+        }
+    }
+    ports
+}
+
+/// Log the agents that may fail to run alongside each other
+fn warn_about_agent_conflicts(agents: &HashMap<String, AgentProcessConfig>) {
+    for conflict in detect_agent_conflicts(agents) {
+        match conflict.kind {
+            AgentConflictKind::Duplicate => log::warn!(
+                "Agents {} have the same command, arguments and environment",
+                conflict.agents.join(", ")
+            ),
+            AgentConflictKind::Port(port) => log::warn!(
+                "Agents {} all use port {}; only one of them may start",
+                conflict.agents.join(", "),
+                port
+            ),
+        }
+    }
+}
 
 /// Agent Configuration Service
 ///
@@ -27,6 +338,12 @@ pub struct AgentConfigService {
     agent_service: Option<Arc<AgentService>>,
     /// Event hub for publishing configuration changes
     event_hub: EventHub,
+    /// Serializes writes to the config file
+    save_lock: Mutex<()>,
+    /// What the file looked like when we last loaded or saved it, to detect other writers
+    disk_snapshot: std::sync::Mutex<Option<DiskSnapshot>>,
+    /// Config as it was at `begin_batch`; set while a batch is open
+    batch_base: std::sync::Mutex<Option<Config>>,
 }
 
 impl AgentConfigService {
@@ -37,12 +354,21 @@ impl AgentConfigService {
         agent_manager: Arc<AgentManager>,
         event_hub: EventHub,
     ) -> Self {
+        let disk_snapshot = DiskSnapshot::read(&config_path);
+        warn_if_upload_dir_unusable(resolve_config_path(
+            &config_dir(&config_path),
+            &initial_config.upload_dir,
+        ));
+        warn_about_agent_conflicts(&initial_config.agent_servers);
         Self {
             config: Arc::new(RwLock::new(initial_config)),
             config_path,
             agent_manager,
             agent_service: None,
             event_hub,
+            save_lock: Mutex::new(()),
+            disk_snapshot: std::sync::Mutex::new(disk_snapshot),
+            batch_base: std::sync::Mutex::new(None),
         }
     }
 
@@ -61,10 +387,21 @@ impl AgentConfigService {
             .iter()
             .map(|(name, cfg)| (name.clone(), cfg.clone()))
             .collect();
-        agents.sort_by(|a, b| a.0.cmp(&b.0));
+        agents.sort_by(|a, b| compare_agents((&a.0, Some(&a.1)), (&b.0, Some(&b.1))));
         agents
     }
 
+    /// Sort agent names into the configured display order
+    pub async fn sort_agent_names(&self, names: &mut [String]) {
+        let config = self.config.read().await;
+        names.sort_by(|a, b| {
+            compare_agents(
+                (a, config.agent_servers.get(a)),
+                (b, config.agent_servers.get(b)),
+            )
+        });
+    }
+
     /// Get a specific agent's configuration
     pub async fn get_agent(&self, name: &str) -> Option<AgentProcessConfig> {
         let config = self.config.read().await;
@@ -77,16 +414,58 @@ impl AgentConfigService {
         config.upload_dir.clone()
     }
 
+    /// Resolve the upload directory and check that it can be written to
+    pub async fn upload_dir_status(&self) -> UploadDirStatus {
+        UploadDirStatus::check(self.resolve_config_path(&self.get_upload_dir().await))
+    }
+
     /// Get proxy configuration (sync)
     pub fn proxy_config(&self) -> agentx_types::config::ProxyConfig {
         self.config.blocking_read().proxy.clone()
     }
 
+    /// Get the icon configured for an agent (sync)
+    pub fn agent_icon(&self, name: &str) -> Option<String> {
+        self.config
+            .blocking_read()
+            .agent_servers
+            .get(name)
+            .and_then(|config| config.icon.clone())
+    }
+
+    /// Get the session modes configured for an agent (sync)
+    pub fn agent_modes(&self, name: &str) -> Vec<String> {
+        self.config
+            .blocking_read()
+            .agent_servers
+            .get(name)
+            .map(|config| config.modes.clone())
+            .unwrap_or_default()
+    }
+
     /// Get the config file path
     pub fn config_path(&self) -> &PathBuf {
         &self.config_path
     }
 
+    /// Resolve a path from the config file against the config file's directory
+    ///
+    /// All relative paths in the config (`upload_dir`, agent commands like
+    /// `./bin/agent`) are resolved this way, never against the process cwd.
+    pub fn resolve_config_path(&self, path: &Path) -> PathBuf {
+        resolve_config_path(&config_dir(&self.config_path), path)
+    }
+
+    /// Resolve an agent command written in the config, see [`Self::resolve_config_path`]
+    pub fn resolve_config_command(&self, command: &str) -> String {
+        resolve_config_command(&config_dir(&self.config_path), command)
+    }
+
+    /// The agent config to validate and spawn, with its command path resolved
+    pub fn spawn_config(&self, config: &AgentProcessConfig) -> AgentProcessConfig {
+        config.with_resolved_command(&config_dir(&self.config_path))
+    }
+
     /// Check if an agent has active sessions
     pub async fn has_active_sessions(&self, agent_name: &str) -> bool {
         if let Some(agent_service) = &self.agent_service {
@@ -140,11 +519,80 @@ impl AgentConfigService {
     /// On Windows, commands are executed via `cmd /C`, so we allow any command
     /// that can be found in PATH. On Unix-like systems, we check if the file exists.
     pub fn validate_command(&self, command: &str) -> Result<()> {
-        // Check if command is an absolute path
+        Self::resolve_command(command, None).map(|_| ())
+    }
+
+    /// Check the command, args and env of an agent before it is spawned
+    ///
+    /// Catches empty or whitespace-only args, env keys that contain spaces or
+    /// `=`, and commands that can't be resolved (against the agent's own PATH
+    /// if it sets one). All problems are reported, not just the first.
+    pub fn validate_process_config(
+        config: &AgentProcessConfig,
+    ) -> std::result::Result<(), AgentConfigValidationError> {
+        let mut issues = Vec::new();
+        let mut issue = |field, message: String| issues.push(AgentConfigIssue { field, message });
+
+        let command = config.command.trim();
+        if command.is_empty() {
+            issue(AgentConfigField::Command, "Command is empty".to_string());
+        } else if let Err(e) =
+            Self::resolve_command(command, config.env.get("PATH").map(String::as_str))
+        {
+            issue(AgentConfigField::Command, e.to_string());
+        }
+
+        for (index, arg) in config.args.iter().enumerate() {
+            if arg.trim().is_empty() {
+                issue(
+                    AgentConfigField::Arg(index),
+                    format!("Argument {} is empty", index + 1),
+                );
+            }
+        }
+
+        let mut env: Vec<_> = config.env.iter().collect();
+        env.sort();
+        for (key, value) in env {
+            let message = if key.trim().is_empty() {
+                "Environment variable name is empty".to_string()
+            } else if key.chars().any(char::is_whitespace) {
+                format!("Environment variable '{}' contains whitespace", key)
+            } else if key.contains('=') {
+                format!("Environment variable '{}' contains '='", key)
+            } else if key.contains('\0') || value.contains('\0') {
+                format!("Environment variable '{}' contains a NUL character", key)
+            } else {
+                continue;
+            };
+            issue(AgentConfigField::EnvKey(key.clone()), message);
+        }
+
+        if let Some(dir) = &config.working_dir_override
+            && !dir.is_dir()
+        {
+            issue(
+                AgentConfigField::WorkingDir,
+                format!("Working directory does not exist: {}", dir.display()),
+            );
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(AgentConfigValidationError { issues })
+        }
+    }
+
+    /// Resolve a command to the absolute path that would be executed
+    ///
+    /// `path` overrides the app's PATH (e.g. a PATH set in the agent's env).
+    /// The error names the specific failure: missing, not a file, not
+    /// executable or not found in PATH.
+    pub fn resolve_command(command: &str, path: Option<&str>) -> Result<PathBuf> {
         let command_path = Path::new(command);
 
         if command_path.is_absolute() {
-            // Absolute path - check if file exists
             if !command_path.exists() {
                 return Err(anyhow!(
                     "Command path does not exist: {}",
@@ -159,39 +607,56 @@ impl AgentConfigService {
                 ));
             }
 
-            Ok(())
-        } else {
-            // Relative path or command name - try to find in PATH
-            // On Windows, commands are executed via `cmd /C`, so we trust the shell
-            // to find the command. We just verify it's findable via `which`.
-            // On Unix-like systems, we also verify via `which`.
-            if let Ok(resolved) = which::which(command) {
-                log::info!("Resolved command '{}' to: {:?}", command, resolved);
+            if !is_executable(command_path) {
+                return Err(anyhow!(
+                    "Command path is not executable: {}",
+                    command_path.display()
+                ));
+            }
 
-                // On Windows, cmd.exe will handle .cmd, .bat, .exe files
-                // so we don't need additional validation
-                #[cfg(target_os = "windows")]
-                {
-                    Ok(())
-                }
+            return Ok(command_path.to_path_buf());
+        }
 
-                // On Unix-like systems, verify the resolved path exists and is executable
-                #[cfg(not(target_os = "windows"))]
-                {
-                    if resolved.exists() && resolved.is_file() {
-                        Ok(())
-                    } else {
-                        Err(anyhow!(
-                            "Resolved command path does not exist or is not a file: {}",
-                            resolved.display()
-                        ))
-                    }
+        // Relative path or command name - try to find in PATH
+        // On Windows, commands are executed via `cmd /C`, so we trust the shell
+        // to find the command. We just verify it's findable via `which`.
+        let search_path = path
+            .map(std::ffi::OsString::from)
+            .or_else(|| std::env::var_os("PATH"));
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        match which::which_in(command, search_path.as_ref(), &cwd) {
+            Ok(resolved) => {
+                log::info!("Resolved command '{}' to: {:?}", command, resolved);
+                if resolved.is_file() {
+                    Ok(resolved)
+                } else {
+                    Err(anyhow!(
+                        "Resolved command path does not exist or is not a file: {}",
+                        resolved.display()
+                    ))
+                }
+            }
+            Err(_) => {
+                // `which` skips files without the executable bit, so point those out
+                let non_executable =
+                    search_path
+                        .iter()
+                        .flat_map(std::env::split_paths)
+                        .find_map(|dir| {
+                            let candidate = dir.join(command);
+                            (candidate.is_file() && !is_executable(&candidate)).then_some(candidate)
+                        });
+                match non_executable {
+                    Some(candidate) => Err(anyhow!(
+                        "Found '{}' at {} but it is not executable",
+                        command,
+                        candidate.display()
+                    )),
+                    None => Err(anyhow!(
+                        "Command '{}' not found in PATH. Please provide an absolute path or ensure the command is in your system PATH.",
+                        command
+                    )),
                 }
-            } else {
-                Err(anyhow!(
-                    "Command '{}' not found in PATH. Please provide an absolute path or ensure the command is in your system PATH.",
-                    command
-                ))
             }
         }
     }
@@ -200,8 +665,8 @@ impl AgentConfigService {
 
     /// Add a new agent
     pub async fn add_agent(&self, name: String, config: AgentProcessConfig) -> Result<()> {
-        // Validate command
-        self.validate_command(&config.command)?;
+        let spawn_config = self.spawn_config(&config);
+        Self::validate_process_config(&spawn_config)?;
 
         // Check for duplicate
         {
@@ -212,16 +677,26 @@ impl AgentConfigService {
         }
 
         // Add to AgentManager (spawns new process)
-        self.agent_manager
-            .add_agent(name.clone(), config.clone())
-            .await?;
+        if !self.in_batch() {
+            self.agent_manager
+                .add_agent(name.clone(), spawn_config)
+                .await?;
+        }
 
-        // Update config
+        // Update config, appending to the custom order if there is one
+        let mut config = config;
         {
             let mut current_config = self.config.write().await;
+            config.order = current_config
+                .agent_servers
+                .values()
+                .filter_map(|agent| agent.order)
+                .max()
+                .map(|last| last + 1);
             current_config
                 .agent_servers
                 .insert(name.clone(), config.clone());
+            warn_about_agent_conflicts(&current_config.agent_servers);
         }
 
         // Save to file
@@ -240,21 +715,36 @@ impl AgentConfigService {
 
     /// Update an existing agent's configuration
     pub async fn update_agent(&self, name: &str, config: AgentProcessConfig) -> Result<()> {
-        // Validate command
-        self.validate_command(&config.command)?;
+        Self::validate_process_config(&self.spawn_config(&config))?;
 
-        // Check if agent exists
+        // Check if agent exists and keep its position, pin and timeout overrides
+        let mut config = config;
         {
             let current_config = self.config.read().await;
-            if !current_config.agent_servers.contains_key(name) {
-                return Err(anyhow!("Agent '{}' not found", name));
+            match current_config.agent_servers.get(name) {
+                Some(existing) => {
+                    config.order = config.order.or(existing.order);
+                    config.pinned |= existing.pinned;
+                    config.request_timeout_secs = config
+                        .request_timeout_secs
+                        .or(existing.request_timeout_secs);
+                    config.startup_timeout_secs = config
+                        .startup_timeout_secs
+                        .or(existing.startup_timeout_secs);
+                    if config.modes.is_empty() {
+                        config.modes = existing.modes.clone();
+                    }
+                }
+                None => return Err(anyhow!("Agent '{}' not found", name)),
             }
         }
 
         // Restart agent with new config (hot-reload)
-        self.agent_manager
-            .restart_agent(name, config.clone())
-            .await?;
+        if !self.in_batch() {
+            self.agent_manager
+                .restart_agent(name, self.spawn_config(&config))
+                .await?;
+        }
 
         // Update config
         {
@@ -289,12 +779,14 @@ impl AgentConfigService {
         }
 
         // Remove from AgentManager (shuts down process)
-        match self.agent_manager.remove_agent_if_present(name).await {
-            Ok(true) => {}
-            Ok(false) => {
-                log::warn!("Agent '{}' not running; removing config only.", name);
+        if !self.in_batch() {
+            match self.agent_manager.remove_agent_if_present(name).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    log::warn!("Agent '{}' not running; removing config only.", name);
+                }
+                Err(err) => return Err(err),
             }
-            Err(err) => return Err(err),
         }
 
         // Update config
@@ -653,20 +1145,24 @@ impl AgentConfigService {
                 .ok_or_else(|| anyhow!("Agent '{}' not found", name))?
         };
 
-        // Validate command before restart
-        self.validate_command(&config.command)?;
+        // Validate before restart
+        let config = self.spawn_config(&config);
+        Self::validate_process_config(&config)?;
 
         // Restart agent
-        self.agent_manager
-            .restart_agent(name, config.clone())
-            .await?;
+        self.agent_manager.restart_agent(name, config).await?;
 
         log::info!("Successfully restarted agent '{}'", name);
         Ok(())
     }
 
     /// Set the upload directory
+    ///
+    /// The directory is created if it doesn't exist; the setting is only
+    /// saved once it is known to be writable.
     pub async fn set_upload_dir(&self, path: PathBuf) -> Result<()> {
+        ensure_upload_dir(&self.resolve_config_path(&path))?;
+
         // Update config
         {
             let mut current_config = self.config.write().await;
@@ -676,6 +1172,12 @@ impl AgentConfigService {
         // Save to file
         self.save_to_file().await?;
 
+        let config = self.config.read().await;
+        self.event_hub
+            .publish_agent_config_update(AgentConfigEvent::ConfigReloaded {
+                config: Box::new(config.clone()),
+            });
+
         log::info!("Successfully updated upload_dir to: {:?}", path);
         Ok(())
     }
@@ -705,35 +1207,395 @@ impl AgentConfigService {
         Ok(())
     }
 
+    /// Set the line limit for collapsed tool call previews
+    pub async fn update_tool_call_preview_max_lines(&self, max_lines: usize) -> Result<()> {
+        if !(MIN_TOOL_CALL_PREVIEW_MAX_LINES..=MAX_TOOL_CALL_PREVIEW_MAX_LINES).contains(&max_lines)
+        {
+            return Err(anyhow!(
+                "Tool call preview line limit must be between {} and {}, got {}",
+                MIN_TOOL_CALL_PREVIEW_MAX_LINES,
+                MAX_TOOL_CALL_PREVIEW_MAX_LINES,
+                max_lines
+            ));
+        }
+
+        let updated_config = {
+            let mut config = self.config.write().await;
+            config.tool_call_preview_max_lines = max_lines;
+            config.clone()
+        };
+
+        self.save_to_file().await?;
+
+        self.event_hub
+            .publish_agent_config_update(AgentConfigEvent::ConfigReloaded {
+                config: Box::new(updated_config),
+            });
+
+        log::info!(
+            "Successfully updated tool call preview line limit to {}",
+            max_lines
+        );
+        Ok(())
+    }
+
+    /// Store a custom agent order; agents not listed keep sorting alphabetically after them
+    pub async fn reorder_agents(&self, names: &[String]) -> Result<()> {
+        let updated_config = {
+            let mut config = self.config.write().await;
+            if let Some(unknown) = names
+                .iter()
+                .find(|name| !config.agent_servers.contains_key(*name))
+            {
+                return Err(anyhow!("Agent '{}' not found", unknown));
+            }
+            for agent in config.agent_servers.values_mut() {
+                agent.order = None;
+            }
+            for (index, name) in names.iter().enumerate() {
+                if let Some(agent) = config.agent_servers.get_mut(name) {
+                    agent.order = Some(index);
+                }
+            }
+            config.clone()
+        };
+
+        self.save_to_file().await?;
+
+        self.event_hub
+            .publish_agent_config_update(AgentConfigEvent::ConfigReloaded {
+                config: Box::new(updated_config),
+            });
+
+        log::info!("Successfully reordered {} agents", names.len());
+        Ok(())
+    }
+
+    /// Pin or unpin an agent so it is listed first
+    pub async fn set_agent_pinned(&self, name: &str, pinned: bool) -> Result<()> {
+        let updated_config = {
+            let mut config = self.config.write().await;
+            let agent = config
+                .agent_servers
+                .get_mut(name)
+                .ok_or_else(|| anyhow!("Agent '{}' not found", name))?;
+            if agent.pinned == pinned {
+                return Ok(());
+            }
+            agent.pinned = pinned;
+            config.clone()
+        };
+
+        self.save_to_file().await?;
+
+        self.event_hub
+            .publish_agent_config_update(AgentConfigEvent::ConfigReloaded {
+                config: Box::new(updated_config),
+            });
+
+        log::info!(
+            "{} agent: {}",
+            if pinned { "Pinned" } else { "Unpinned" },
+            name
+        );
+        Ok(())
+    }
+
+    /// Drop the custom agent order so agents sort alphabetically again
+    pub async fn reset_agent_order(&self) -> Result<()> {
+        self.reorder_agents(&[]).await
+    }
+
     // ========== Persistence ==========
 
-    /// Save configuration to file
+    /// Write the config as it is in memory to the config file
+    ///
+    /// Used to keep the fallback config after the file on disk failed to parse.
+    pub async fn save_current_config(&self) -> Result<()> {
+        self.save_to_file().await
+    }
+
+    /// Save configuration to file, deferred while a batch is open
     async fn save_to_file(&self) -> Result<()> {
-        let config = self.config.read().await;
+        if self.in_batch() {
+            return Ok(());
+        }
+        self.write_config_file().await
+    }
 
-        // Create backup before saving
-        if self.config_path.exists() {
-            let backup_path = self.config_path.with_extension("json.backup");
-            if let Err(e) = std::fs::copy(&self.config_path, &backup_path) {
-                log::warn!("Failed to create backup: {}", e);
+    /// Write the configuration to the config file
+    ///
+    /// If the file changed on disk since we last loaded or saved it, the other writer's
+    /// changes are merged in; keys changed on both sides keep the in-memory value and are
+    /// reported with a `ConfigConflict` event.
+    async fn write_config_file(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        let mut config = self.config.write().await;
+
+        // Merge in changes written by someone else since our last load/save
+        let on_disk = std::fs::read_to_string(&self.config_path).ok();
+        let base = self.disk_snapshot.lock().unwrap().take();
+        let mut merged_from_disk = false;
+        if let (Some(on_disk), Some(base)) = (on_disk.as_deref(), base)
+            && content_hash(on_disk) != base.hash
+            && let Ok(theirs) = serde_json::from_str::<serde_json::Value>(on_disk)
+        {
+            log::warn!(
+                "Config file {:?} changed since it was loaded, merging",
+                self.config_path
+            );
+            let ours =
+                serde_json::to_value(&*config).context("Failed to serialize configuration")?;
+            let (merged, conflicts) = merge_json(&base.value, &ours, &theirs);
+            match serde_json::from_value::<Config>(merged) {
+                Ok(merged) => {
+                    merged_from_disk = true;
+                    *config = merged;
+                }
+                Err(e) => log::warn!("Merged configuration is invalid, keeping ours: {}", e),
+            }
+            if !conflicts.is_empty() {
+                log::warn!(
+                    "Config keys changed both here and on disk, keeping ours: {}",
+                    conflicts.join(", ")
+                );
+                self.event_hub
+                    .publish_agent_config_update(AgentConfigEvent::ConfigConflict {
+                        keys: conflicts,
+                    });
             }
         }
 
+        // Create backup before saving
+        if let Err(e) = self.create_backup() {
+            log::warn!("Failed to create backup: {:#}", e);
+        }
+
         // Serialize config
         let json =
             serde_json::to_string_pretty(&*config).context("Failed to serialize configuration")?;
 
         // Write to file (atomic write using temp file)
         let temp_path = self.config_path.with_extension("json.tmp");
-        std::fs::write(&temp_path, json).context("Failed to write configuration to temp file")?;
+        std::fs::write(&temp_path, &json).context("Failed to write configuration to temp file")?;
 
         std::fs::rename(&temp_path, &self.config_path)
             .context("Failed to replace configuration file")?;
+        *self.disk_snapshot.lock().unwrap() = DiskSnapshot::from_json(&json);
+
+        if merged_from_disk {
+            self.event_hub
+                .publish_agent_config_update(AgentConfigEvent::ConfigReloaded {
+                    config: Box::new(config.clone()),
+                });
+        }
 
         log::info!("Configuration saved to: {:?}", self.config_path);
         Ok(())
     }
 
+    // ========== Batch Updates ==========
+
+    /// Collect changes without restarting agents or writing the file
+    ///
+    /// Until [`Self::commit_batch`] the CRUD operations only update the config
+    /// in memory and publish their events. Every batch has to be committed.
+    pub async fn begin_batch(&self) -> Result<()> {
+        let base = self.config.read().await.clone();
+        let mut batch_base = self.batch_base.lock().unwrap();
+        if batch_base.is_some() {
+            return Err(anyhow!("A configuration batch is already open"));
+        }
+        *batch_base = Some(base);
+        Ok(())
+    }
+
+    /// Write the file once and restart only the agents whose process settings changed
+    pub async fn commit_batch(&self) -> Result<AgentChanges> {
+        let base = self
+            .batch_base
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| anyhow!("No configuration batch is open"))?;
+
+        self.write_config_file().await?;
+        let changes = self.apply_agent_changes(&base.agent_servers).await;
+
+        log::info!(
+            "Configuration batch committed: {} added, {} restarted, {} removed",
+            changes.added.len(),
+            changes.restarted.len(),
+            changes.removed.len()
+        );
+        Ok(changes)
+    }
+
+    fn in_batch(&self) -> bool {
+        self.batch_base.lock().unwrap().is_some()
+    }
+
+    /// Bring the running agents in line with the config, starting from `previous`
+    async fn apply_agent_changes(
+        &self,
+        previous: &HashMap<String, AgentProcessConfig>,
+    ) -> AgentChanges {
+        let agents = self.config.read().await.agent_servers.clone();
+        let changes = AgentChanges::between(previous, &agents);
+
+        for name in &changes.added {
+            if let Err(e) = self
+                .agent_manager
+                .add_agent(name.clone(), self.spawn_config(&agents[name]))
+                .await
+            {
+                log::warn!("Failed to start agent '{}': {}", name, e);
+            }
+        }
+        for name in &changes.restarted {
+            if let Err(e) = self
+                .agent_manager
+                .restart_agent(name, self.spawn_config(&agents[name]))
+                .await
+            {
+                log::warn!("Failed to restart agent '{}': {}", name, e);
+            }
+        }
+        for name in &changes.removed {
+            if let Err(e) = self.agent_manager.remove_agent_if_present(name).await {
+                log::warn!("Failed to remove agent '{}': {}", name, e);
+            }
+        }
+        changes
+    }
+
+    /// Replace all MCP servers, touching only the ones that differ
+    ///
+    /// The new set is checked before anything changes, so an invalid entry
+    /// leaves the existing servers as they are.
+    pub async fn replace_mcp_servers(
+        &self,
+        mut servers: HashMap<String, McpServerConfig>,
+    ) -> Result<McpServerChanges> {
+        for (name, config) in &servers {
+            if name.trim().is_empty() {
+                return Err(anyhow!("MCP server name cannot be empty"));
+            }
+            if config.command.trim().is_empty() {
+                return Err(anyhow!("MCP server '{}' has no command", name));
+            }
+        }
+
+        let current = self.config.read().await.mcp_servers.clone();
+        let changes = McpServerChanges::between(&current, &servers);
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+
+        self.begin_batch().await?;
+        let mut result = Ok(());
+        for name in &changes.removed {
+            result = result.and(self.remove_mcp_server(name).await);
+        }
+        for name in &changes.updated {
+            if let Some(config) = servers.remove(name) {
+                result = result.and(self.update_mcp_server(name, config).await);
+            }
+        }
+        for name in &changes.added {
+            if let Some(config) = servers.remove(name) {
+                result = result.and(self.add_mcp_server(name.clone(), config).await);
+            }
+        }
+        self.commit_batch().await?;
+        result.map(|()| changes)
+    }
+
+    // ========== Backups ==========
+
+    /// Directory holding the rotating config backups
+    fn backups_dir(&self) -> PathBuf {
+        self.config_path.with_extension("backups")
+    }
+
+    /// Copy the current config file into a new timestamped backup and drop the oldest ones
+    fn create_backup(&self) -> Result<()> {
+        if !self.config_path.exists() {
+            return Ok(());
+        }
+
+        let dir = self.backups_dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create backup directory: {:?}", dir))?;
+        // Saves can land within the same timestamp tick, so step forward until the name is free
+        let mut created_at = Utc::now();
+        let mut backup_path = backup_file_path(&dir, created_at);
+        while backup_path.exists() {
+            created_at += chrono::Duration::microseconds(1);
+            backup_path = backup_file_path(&dir, created_at);
+        }
+        std::fs::copy(&self.config_path, &backup_path)
+            .with_context(|| format!("Failed to write backup: {:?}", backup_path))?;
+
+        for old in self.list_backups().into_iter().skip(MAX_CONFIG_BACKUPS) {
+            if let Err(e) = std::fs::remove_file(&old.path) {
+                log::warn!("Failed to remove old backup {:?}: {}", old.path, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// List config backups, newest first
+    pub fn list_backups(&self) -> Vec<ConfigBackup> {
+        let Ok(entries) = std::fs::read_dir(self.backups_dir()) else {
+            return Vec::new();
+        };
+
+        let mut backups: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let created_at = parse_backup_timestamp(path.file_name()?.to_str()?)?;
+                Some(ConfigBackup { path, created_at })
+            })
+            .collect();
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        backups
+    }
+
+    /// Replace the config with a backup, then reload it and restart agents whose process settings changed
+    ///
+    /// The current config is backed up first, so a restore can itself be undone.
+    pub async fn restore_backup(&self, backup_path: &Path) -> Result<()> {
+        let json = std::fs::read_to_string(backup_path)
+            .with_context(|| format!("Failed to read backup: {:?}", backup_path))?;
+        serde_json::from_str::<Config>(&json).map_err(|e| {
+            anyhow!(
+                "Backup is not a valid configuration: {}",
+                describe_json_error(&json, &e)
+            )
+        })?;
+
+        let previous_agents = self.config.read().await.agent_servers.clone();
+
+        {
+            let _guard = self.save_lock.lock().await;
+            self.create_backup()?;
+            let temp_path = self.config_path.with_extension("json.tmp");
+            std::fs::write(&temp_path, json)
+                .context("Failed to write configuration to temp file")?;
+            std::fs::rename(&temp_path, &self.config_path)
+                .context("Failed to replace configuration file")?;
+        }
+
+        self.reload_from_file().await?;
+
+        self.apply_agent_changes(&previous_agents).await;
+
+        log::info!("Configuration restored from backup: {:?}", backup_path);
+        Ok(())
+    }
+
     /// Reload configuration from file
     pub async fn reload_from_file(&self) -> Result<()> {
         // Read file
@@ -741,14 +1603,23 @@ impl AgentConfigService {
             .with_context(|| format!("Failed to read config file: {:?}", self.config_path))?;
 
         // Parse config
-        let new_config: Config =
-            serde_json::from_str(&json).context("Failed to parse configuration file")?;
+        let new_config: Config = serde_json::from_str(&json).map_err(|e| {
+            anyhow!(
+                "Failed to parse configuration file: {}",
+                describe_json_error(&json, &e)
+            )
+        })?;
+        *self.disk_snapshot.lock().unwrap() = DiskSnapshot::from_json(&json);
 
         // Update internal config
         {
             let mut config = self.config.write().await;
             *config = new_config.clone();
         }
+        self.agent_manager
+            .set_log_buffer_size(new_config.agent_log_buffer_kb * 1024);
+        warn_if_upload_dir_unusable(self.resolve_config_path(&new_config.upload_dir));
+        warn_about_agent_conflicts(&new_config.agent_servers);
 
         // Publish reload event with full config
         self.event_hub
@@ -761,6 +1632,110 @@ impl AgentConfigService {
     }
 }
 
+fn backup_file_path(dir: &Path, created_at: DateTime<Utc>) -> PathBuf {
+    dir.join(format!(
+        "config-{}.json",
+        created_at.format(BACKUP_TIMESTAMP_FORMAT)
+    ))
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Three-way merge of JSON objects: keep whichever side changed a key relative to `base`,
+/// recursing into objects. Keys changed differently on both sides keep `ours` and are
+/// returned as dotted paths.
+fn merge_json(
+    base: &serde_json::Value,
+    ours: &serde_json::Value,
+    theirs: &serde_json::Value,
+) -> (serde_json::Value, Vec<String>) {
+    let mut conflicts = Vec::new();
+    let merged = merge_json_at(Some(base), Some(ours), Some(theirs), "", &mut conflicts);
+    (merged.unwrap_or(serde_json::Value::Null), conflicts)
+}
+
+fn merge_json_at(
+    base: Option<&serde_json::Value>,
+    ours: Option<&serde_json::Value>,
+    theirs: Option<&serde_json::Value>,
+    path: &str,
+    conflicts: &mut Vec<String>,
+) -> Option<serde_json::Value> {
+    use serde_json::Value;
+
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+
+    let (Some(Value::Object(ours_map)), Some(Value::Object(theirs_map))) = (ours, theirs) else {
+        conflicts.push(path.to_string());
+        return ours.cloned();
+    };
+    let base_map = match base {
+        Some(Value::Object(map)) => Some(map),
+        _ => None,
+    };
+
+    let mut keys: Vec<&String> = ours_map.keys().chain(theirs_map.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut merged = serde_json::Map::new();
+    for key in keys {
+        let child_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        if let Some(value) = merge_json_at(
+            base_map.and_then(|map| map.get(key)),
+            ours_map.get(key),
+            theirs_map.get(key),
+            &child_path,
+            conflicts,
+        ) {
+            merged.insert(key.clone(), value);
+        }
+    }
+    Some(Value::Object(merged))
+}
+
+/// Parse the creation time out of a backup file name
+fn parse_backup_timestamp(file_name: &str) -> Option<DateTime<Utc>> {
+    let timestamp = file_name.strip_prefix("config-")?.strip_suffix(".json")?;
+    NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT)
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// Whether an agent's process has to be restarted to pick up a config change
+fn process_settings_changed(old: &AgentProcessConfig, new: &AgentProcessConfig) -> bool {
+    old.command != new.command
+        || old.args != new.args
+        || old.env != new.env
+        || old.working_dir_override != new.working_dir_override
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use agentx_types::ProxyConfig;
@@ -791,6 +1766,413 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn process_config(args: &[&str], env: &[(&str, &str)]) -> AgentProcessConfig {
+        AgentProcessConfig {
+            command: if cfg!(target_os = "windows") {
+                "cmd".to_string()
+            } else {
+                "ls".to_string()
+            },
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            env: env
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            icon: None,
+            order: None,
+            pinned: false,
+            request_timeout_secs: None,
+            startup_timeout_secs: None,
+            working_dir_override: None,
+            modes: Vec::new(),
+            nodejs_path: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_process_config_rejects_malformed_env_keys() {
+        let config = process_config(&[], &[("API KEY", "x"), ("A=B", "y"), ("GOOD", "z")]);
+        let err = AgentConfigService::validate_process_config(&config).unwrap_err();
+        let fields: Vec<_> = err.issues.iter().map(|issue| issue.field.clone()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                AgentConfigField::EnvKey("A=B".to_string()),
+                AgentConfigField::EnvKey("API KEY".to_string()),
+            ]
+        );
+
+        let config = process_config(&["--flag"], &[("GOOD", "z")]);
+        assert!(AgentConfigService::validate_process_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_process_config_rejects_whitespace_args() {
+        let config = process_config(&["--acp", "", "  "], &[]);
+        let err = AgentConfigService::validate_process_config(&config).unwrap_err();
+        let fields: Vec<_> = err.issues.iter().map(|issue| issue.field.clone()).collect();
+        assert_eq!(
+            fields,
+            vec![AgentConfigField::Arg(1), AgentConfigField::Arg(2)]
+        );
+
+        // The structured error survives the trip through anyhow
+        let err = anyhow::Error::from(err);
+        assert!(err.downcast_ref::<AgentConfigValidationError>().is_some());
+    }
+
+    #[test]
+    fn test_detect_agent_conflicts() {
+        let agents: HashMap<String, AgentProcessConfig> = [
+            ("a", process_config(&["--acp"], &[])),
+            ("b", process_config(&["--acp"], &[])),
+            ("c", process_config(&["--port", "8080"], &[])),
+            ("d", process_config(&["--listen-port=8080"], &[])),
+            ("e", process_config(&[], &[("SERVER_PORT", "8080")])),
+            ("f", process_config(&["-p", "9000"], &[("PATH", "/bin")])),
+        ]
+        .into_iter()
+        .map(|(name, config)| (name.to_string(), config))
+        .collect();
+
+        let conflicts = detect_agent_conflicts(&agents);
+        assert_eq!(
+            conflicts,
+            vec![
+                AgentConflict {
+                    kind: AgentConflictKind::Duplicate,
+                    agents: vec!["a".to_string(), "b".to_string()],
+                },
+                AgentConflict {
+                    kind: AgentConflictKind::Port(8080),
+                    agents: vec!["c".to_string(), "d".to_string(), "e".to_string()],
+                },
+            ]
+        );
+        assert_eq!(conflicts[1].key(), "port:8080:c,d,e");
+    }
+
+    #[test]
+    fn test_validate_process_config_requires_existing_working_dir() {
+        let mut config = process_config(&[], &[]);
+        config.working_dir_override = Some(std::env::temp_dir());
+        assert!(AgentConfigService::validate_process_config(&config).is_ok());
+
+        config.working_dir_override = Some(std::env::temp_dir().join("agentx-missing-dir"));
+        let err = AgentConfigService::validate_process_config(&config).unwrap_err();
+        let fields: Vec<_> = err.issues.iter().map(|issue| issue.field.clone()).collect();
+        assert_eq!(fields, vec![AgentConfigField::WorkingDir]);
+    }
+
+    #[tokio::test]
+    async fn test_backups_rotate_and_restore() {
+        let dir = std::env::temp_dir().join(format!("agentx-backup-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut service = create_test_service();
+        service.config_path = dir.join("config.json");
+
+        service.save_to_file().await.unwrap();
+        for max_lines in 1..=MAX_CONFIG_BACKUPS + 2 {
+            service.config.write().await.tool_call_preview_max_lines = max_lines;
+            service.save_to_file().await.unwrap();
+        }
+        let backups = service.list_backups();
+        assert_eq!(backups.len(), MAX_CONFIG_BACKUPS);
+        assert!(backups[0].created_at >= backups[1].created_at);
+
+        // The newest backup holds the config from before the last save
+        service.restore_backup(&backups[0].path).await.unwrap();
+        assert_eq!(
+            service.config.read().await.tool_call_preview_max_lines,
+            MAX_CONFIG_BACKUPS + 1
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_merge_json_keeps_both_sides_and_reports_conflicts() {
+        let base = serde_json::json!({
+            "agent_servers": { "a": { "command": "a" }, "b": { "command": "b" } },
+            "upload_dir": "/tmp",
+        });
+        // We added "c" and changed the upload dir
+        let ours = serde_json::json!({
+            "agent_servers": {
+                "a": { "command": "a" },
+                "b": { "command": "b" },
+                "c": { "command": "c" },
+            },
+            "upload_dir": "/ours",
+        });
+        // Someone else removed "b" and also changed the upload dir
+        let theirs = serde_json::json!({
+            "agent_servers": { "a": { "command": "a" } },
+            "upload_dir": "/theirs",
+        });
+
+        let (merged, conflicts) = merge_json(&base, &ours, &theirs);
+        assert_eq!(
+            merged,
+            serde_json::json!({
+                "agent_servers": { "a": { "command": "a" }, "c": { "command": "c" } },
+                "upload_dir": "/ours",
+            })
+        );
+        assert_eq!(conflicts, vec!["upload_dir".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_save_merges_changes_written_elsewhere() {
+        let dir = std::env::temp_dir().join(format!("agentx-merge-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut service = create_test_service();
+        service.config_path = dir.join("config.json");
+        service.save_to_file().await.unwrap();
+
+        // Another writer changes the preview limit on disk
+        let mut other = service.config.read().await.clone();
+        other.tool_call_preview_max_lines = 42;
+        std::fs::write(
+            &service.config_path,
+            serde_json::to_string_pretty(&other).unwrap(),
+        )
+        .unwrap();
+
+        // A local edit to a different key must not drop it
+        service.config.write().await.upload_dir = dir.join("uploads");
+        service.save_to_file().await.unwrap();
+
+        let saved: Config =
+            serde_json::from_str(&std::fs::read_to_string(&service.config_path).unwrap()).unwrap();
+        assert_eq!(saved.tool_call_preview_max_lines, 42);
+        assert_eq!(saved.upload_dir, dir.join("uploads"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_agent_changes_skip_unchanged_agents() {
+        let unchanged = process_config(&["--acp"], &[]);
+        let mut reordered = process_config(&["--acp"], &[]);
+        let previous = HashMap::from([
+            ("unchanged".to_string(), unchanged.clone()),
+            ("reordered".to_string(), reordered.clone()),
+            ("edited".to_string(), process_config(&["--acp"], &[])),
+            ("removed".to_string(), process_config(&[], &[])),
+        ]);
+        reordered.order = Some(3);
+        reordered.pinned = true;
+        let current = HashMap::from([
+            ("unchanged".to_string(), unchanged),
+            ("reordered".to_string(), reordered),
+            (
+                "edited".to_string(),
+                process_config(&["--acp"], &[("API_KEY", "new")]),
+            ),
+            ("added".to_string(), process_config(&[], &[])),
+        ]);
+
+        assert_eq!(
+            AgentChanges::between(&previous, &current),
+            AgentChanges {
+                added: vec!["added".to_string()],
+                restarted: vec!["edited".to_string()],
+                removed: vec!["removed".to_string()],
+            }
+        );
+    }
+
+    fn mcp_server(command: &str) -> McpServerConfig {
+        McpServerConfig {
+            enabled: true,
+            command: command.to_string(),
+            args: Vec::new(),
+            env: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_mcp_server_changes() {
+        let names = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        let current = HashMap::from([
+            ("fs".to_string(), mcp_server("mcp-fs")),
+            ("git".to_string(), mcp_server("mcp-git")),
+        ]);
+
+        // Add only
+        let mut desired = current.clone();
+        desired.insert("web".to_string(), mcp_server("mcp-web"));
+        let changes = McpServerChanges::between(&current, &desired);
+        assert_eq!(changes.added, names(&["web"]));
+        assert!(changes.updated.is_empty() && changes.removed.is_empty());
+
+        // Remove only
+        let mut desired = current.clone();
+        desired.remove("git");
+        let changes = McpServerChanges::between(&current, &desired);
+        assert_eq!(changes.removed, names(&["git"]));
+        assert!(changes.added.is_empty() && changes.updated.is_empty());
+
+        // Rename, with the other server edited
+        let desired = HashMap::from([
+            ("files".to_string(), mcp_server("mcp-fs")),
+            ("git".to_string(), mcp_server("mcp-git2")),
+        ]);
+        assert_eq!(
+            McpServerChanges::between(&current, &desired),
+            McpServerChanges {
+                added: names(&["files"]),
+                updated: names(&["git"]),
+                removed: names(&["fs"]),
+            }
+        );
+
+        assert!(McpServerChanges::between(&current, &current).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_writes_config_once_on_commit() {
+        let dir = std::env::temp_dir().join(format!("agentx-batch-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut service = create_test_service();
+        service.config_path = dir.join("config.json");
+        service.begin_batch().await.unwrap();
+        assert!(service.begin_batch().await.is_err());
+        service
+            .add_mcp_server("a".to_string(), mcp_server("a"))
+            .await
+            .unwrap();
+        service
+            .add_mcp_server("b".to_string(), mcp_server("b"))
+            .await
+            .unwrap();
+        assert!(!service.config_path.exists());
+
+        let changes = service.commit_batch().await.unwrap();
+        assert!(changes.is_empty());
+        let saved: Config =
+            serde_json::from_str(&std::fs::read_to_string(&service.config_path).unwrap()).unwrap();
+        assert_eq!(saved.mcp_servers.len(), 2);
+
+        // Unchanged servers are kept, others replaced
+        let changes = service
+            .replace_mcp_servers(HashMap::from([
+                ("a".to_string(), mcp_server("a")),
+                ("c".to_string(), mcp_server("c")),
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(changes.added, vec!["c"]);
+        assert_eq!(changes.removed, vec!["b"]);
+        assert!(changes.updated.is_empty());
+
+        // An invalid entry leaves everything as it was
+        let invalid = HashMap::from([("a".to_string(), mcp_server(""))]);
+        assert!(service.replace_mcp_servers(invalid).await.is_err());
+
+        let mut names: Vec<_> = service
+            .list_mcp_servers()
+            .await
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["a", "c"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_set_upload_dir_creates_missing_directory() {
+        let dir = std::env::temp_dir().join(format!("agentx-upload-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut service = create_test_service();
+        service.config_path = dir.join("config.json");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let uploads = dir.join("nested").join("uploads");
+        service.set_upload_dir(uploads.clone()).await.unwrap();
+        assert!(uploads.is_dir());
+        assert!(service.upload_dir_status().await.is_writable());
+
+        // A file in the way can't become the upload directory
+        let blocked = dir.join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        assert!(
+            service
+                .set_upload_dir(blocked.join("uploads"))
+                .await
+                .is_err()
+        );
+        assert_eq!(service.get_upload_dir().await, uploads);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_resolve_config_path_uses_config_directory() {
+        let mut service = create_test_service();
+        let config_dir = std::env::temp_dir().join("agentx-config");
+        service.config_path = config_dir.join("config.json");
+
+        assert_eq!(
+            service.resolve_config_path(Path::new("uploads")),
+            config_dir.join("uploads")
+        );
+        assert_eq!(
+            service.resolve_config_path(Path::new(".")),
+            config_dir.clone()
+        );
+        let absolute = std::env::temp_dir().join("elsewhere");
+        assert_eq!(service.resolve_config_path(&absolute), absolute);
+
+        // Command paths resolve the same way; bare names are left for PATH lookup
+        assert_eq!(
+            service.resolve_config_command("./bin/agent"),
+            config_dir.join("bin").join("agent").to_string_lossy()
+        );
+        assert_eq!(service.resolve_config_command("npx"), "npx");
+    }
+
+    #[test]
+    fn test_parse_backup_timestamp() {
+        let parsed = parse_backup_timestamp("config-20250102T030405678901.json").unwrap();
+        assert_eq!(
+            parsed.format("%Y-%m-%d %H:%M:%S%.6f").to_string(),
+            "2025-01-02 03:04:05.678901"
+        );
+        assert!(parse_backup_timestamp("config.json").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_command_reports_non_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("agentx-resolve-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("agent-script");
+        std::fs::write(&script, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let path = dir.to_str().unwrap();
+        let err = AgentConfigService::resolve_command("agent-script", Some(path)).unwrap_err();
+        assert!(err.to_string().contains("not executable"));
+
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(
+            AgentConfigService::resolve_command("agent-script", Some(path)).unwrap(),
+            script
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[tokio::test]
     async fn test_add_duplicate_agent() {
         let _service = create_test_service();
@@ -803,6 +2185,13 @@ mod tests {
             },
             args: vec![],
             env: HashMap::new(),
+            icon: None,
+            order: None,
+            pinned: false,
+            request_timeout_secs: None,
+            startup_timeout_secs: None,
+            working_dir_override: None,
+            modes: Vec::new(),
             nodejs_path: None,
         };
 
@@ -811,6 +2200,86 @@ mod tests {
         // Note: This test requires mocking AgentManager for full coverage
     }
 
+    #[tokio::test]
+    async fn test_tool_call_preview_max_lines_out_of_range() {
+        let service = create_test_service();
+
+        assert!(service.update_tool_call_preview_max_lines(0).await.is_err());
+        assert!(
+            service
+                .update_tool_call_preview_max_lines(MAX_TOOL_CALL_PREVIEW_MAX_LINES + 1)
+                .await
+                .is_err()
+        );
+        assert_eq!(service.config.read().await.tool_call_preview_max_lines, 10);
+    }
+
+    #[tokio::test]
+    async fn test_agent_order_with_alphabetical_fallback() {
+        let service = create_test_service();
+        {
+            let mut config = service.config.write().await;
+            for (name, order) in [
+                ("beta", None),
+                ("alpha", None),
+                ("zeta", Some(1)),
+                ("omega", Some(0)),
+            ] {
+                config.agent_servers.insert(
+                    name.to_string(),
+                    AgentProcessConfig {
+                        command: "ls".to_string(),
+                        args: vec![],
+                        env: HashMap::new(),
+                        icon: None,
+                        order,
+                        pinned: false,
+                        request_timeout_secs: None,
+                        startup_timeout_secs: None,
+                        working_dir_override: None,
+                        modes: Vec::new(),
+                        nodejs_path: None,
+                    },
+                );
+            }
+        }
+
+        let names: Vec<_> = service
+            .list_agents()
+            .await
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["omega", "zeta", "alpha", "beta"]);
+
+        let mut names = vec![
+            "beta".to_string(),
+            "zeta".to_string(),
+            "unknown".to_string(),
+        ];
+        service.sort_agent_names(&mut names).await;
+        assert_eq!(names, ["zeta", "beta", "unknown"]);
+
+        // Pinned agents come first, keeping their relative order
+        for name in ["beta", "zeta"] {
+            service
+                .config
+                .write()
+                .await
+                .agent_servers
+                .get_mut(name)
+                .unwrap()
+                .pinned = true;
+        }
+        let names: Vec<_> = service
+            .list_agents()
+            .await
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["zeta", "beta", "omega", "alpha"]);
+    }
+
     fn create_test_service() -> AgentConfigService {
         // Create test dependencies
         let config = Config {
@@ -821,7 +2290,9 @@ mod tests {
             commands: HashMap::new(),
             system_prompts: HashMap::new(),
             tool_call_preview_max_lines: 10,
+            agent_log_buffer_kb: 64,
             proxy: ProxyConfig::default(),
+            request_retry: Default::default(),
         };
 
         let event_hub = EventHub::new();
@@ -6,6 +6,8 @@
 
 use std::{
     collections::{HashMap, HashSet, hash_map::Entry},
+    fmt,
+    path::PathBuf,
     sync::{Arc, RwLock},
     time::Duration,
 };
@@ -15,9 +17,12 @@ use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use agentx_agent::{AgentHandle, AgentManager};
-use agentx_event_bus::{EventHub, WorkspaceUpdateEvent};
-use agentx_types::SessionStatus;
+use agentx_agent::{AgentHandle, AgentLifecycle, AgentManager, AgentUnavailable};
+use agentx_event_bus::{AgentConfigEvent, EventHub, WorkspaceUpdateEvent};
+use agentx_types::schemas::workspace::SessionTitleSource;
+use agentx_types::{AgentProcessConfig, RequestRetryConfig, SessionStatus};
+
+use crate::WorkspaceService;
 
 /// Agent service - manages agents and their sessions
 pub struct AgentService {
@@ -28,6 +33,112 @@ pub struct AgentService {
     loading_sessions: Arc<RwLock<HashSet<String>>>,
     /// Event hub for publishing status updates
     event_hub: Option<EventHub>,
+    /// Persists session titles
+    workspace_service: Option<Arc<WorkspaceService>>,
+    /// Session titles, keyed by session ID (mirrors WorkspaceService)
+    session_titles: Arc<RwLock<HashMap<String, String>>>,
+    /// Where each session title came from
+    session_title_sources: Arc<RwLock<HashMap<String, SessionTitleSource>>>,
+    /// Mode chosen for each session, keyed by session ID (mirrors WorkspaceService)
+    session_modes: Arc<RwLock<HashMap<String, String>>>,
+    /// Retry policy for idempotent agent requests (`request_retry` in config)
+    retry_policy: Arc<RwLock<RetryPolicy>>,
+    /// Timeout for session requests when the agent has no override
+    request_timeout: Duration,
+    /// Per-agent timeout overrides (`request_timeout_secs` in config)
+    agent_request_timeouts: Arc<RwLock<HashMap<String, Duration>>>,
+    /// Agents known not to implement session/list, until their config changes
+    session_list_unsupported: Arc<RwLock<HashSet<String>>>,
+    /// Capabilities each running agent advertised in its initialize response
+    agent_capabilities: Arc<RwLock<HashMap<String, acp::AgentCapabilities>>>,
+}
+
+/// Default time an agent gets to answer a session request
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Error returned when an agent does not answer a session request in time
+///
+/// Callers can detect it with `error.downcast_ref::<RequestTimeout>()`.
+#[derive(Clone, Debug)]
+pub struct RequestTimeout {
+    pub agent_name: String,
+    pub operation: &'static str,
+    pub after: Duration,
+}
+
+impl fmt::Display for RequestTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Agent '{}' did not respond to {} within {}s",
+            self.agent_name,
+            self.operation,
+            self.after.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for RequestTimeout {}
+
+/// Error returned when an agent does not implement session/list
+///
+/// Callers can detect it with `error.downcast_ref::<SessionListUnsupported>()`.
+#[derive(Clone, Debug)]
+pub struct SessionListUnsupported {
+    pub agent_name: String,
+}
+
+impl fmt::Display for SessionListUnsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Agent '{}' does not support listing sessions",
+            self.agent_name
+        )
+    }
+}
+
+impl std::error::Error for SessionListUnsupported {}
+
+/// Exponential backoff for idempotent agent requests (list/load/resume)
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further retry
+    pub initial_delay: Duration,
+    /// Upper bound for a single delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl From<&RequestRetryConfig> for RetryPolicy {
+    fn from(config: &RequestRetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.max(1),
+            initial_delay: Duration::from_millis(config.initial_delay_ms),
+            max_delay: Duration::from_millis(config.max_delay_ms),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay to wait after the given failed attempt (1-based)
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
 }
 
 /// Agent session information
@@ -42,6 +153,10 @@ pub struct AgentSessionInfo {
     pub new_session_response: Option<acp::NewSessionResponse>,
     /// Available commands for this session (slash commands, etc.)
     pub available_commands: Vec<AvailableCommand>,
+    /// Session title; None displays the short session ID
+    pub title: Option<String>,
+    /// Working directory the session was started in
+    pub cwd: Option<PathBuf>,
 }
 
 impl AgentService {
@@ -51,15 +166,158 @@ impl AgentService {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             loading_sessions: Arc::new(RwLock::new(HashSet::new())),
             event_hub: None,
+            workspace_service: None,
+            session_titles: Arc::new(RwLock::new(HashMap::new())),
+            session_title_sources: Arc::new(RwLock::new(HashMap::new())),
+            session_modes: Arc::new(RwLock::new(HashMap::new())),
+            retry_policy: Arc::new(RwLock::new(RetryPolicy::default())),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            agent_request_timeouts: Arc::new(RwLock::new(HashMap::new())),
+            session_list_unsupported: Arc::new(RwLock::new(HashSet::new())),
+            agent_capabilities: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
     /// Set the event hub for publishing status updates
+    ///
+    /// Also keeps the request timeouts and the retry policy in sync with config
+    /// changes, forgets cached session/list support when an agent's config
+    /// changes and records the capabilities of agents as they become ready.
     pub fn set_event_hub(&mut self, hub: EventHub) {
         log::info!("AgentService: Setting event hub");
+        let timeouts = self.agent_request_timeouts.clone();
+        let retry_policy = self.retry_policy.clone();
+        let list_unsupported = self.session_list_unsupported.clone();
+        let capabilities = self.agent_capabilities.clone();
+        let agent_manager = self.agent_manager.clone();
+        hub.subscribe_agent_config_updates(move |event| {
+            let mut timeouts = timeouts.write().unwrap();
+            match event {
+                AgentConfigEvent::AgentReady { name } => {
+                    let name = name.clone();
+                    let capabilities = capabilities.clone();
+                    let agent_manager = agent_manager.clone();
+                    smol::spawn(async move {
+                        let init_response = agent_manager.get_agent_init_response(&name).await;
+                        let mut capabilities = capabilities.write().unwrap();
+                        match init_response {
+                            Some(response) => {
+                                capabilities.insert(name, response.agent_capabilities)
+                            }
+                            None => capabilities.remove(&name),
+                        };
+                    })
+                    .detach();
+                }
+                AgentConfigEvent::AgentFailed { name, .. } => {
+                    capabilities.write().unwrap().remove(name);
+                }
+                AgentConfigEvent::AgentAdded { name, config }
+                | AgentConfigEvent::AgentUpdated { name, config } => {
+                    match config.request_timeout_secs {
+                        Some(secs) => timeouts.insert(name.clone(), Duration::from_secs(secs)),
+                        None => timeouts.remove(name),
+                    };
+                    list_unsupported.write().unwrap().remove(name);
+                }
+                AgentConfigEvent::AgentRemoved { name } => {
+                    timeouts.remove(name);
+                    list_unsupported.write().unwrap().remove(name);
+                    capabilities.write().unwrap().remove(name);
+                }
+                AgentConfigEvent::ConfigReloaded { config } => {
+                    *timeouts = request_timeouts_from_config(&config.agent_servers);
+                    *retry_policy.write().unwrap() = RetryPolicy::from(&config.request_retry);
+                    list_unsupported.write().unwrap().clear();
+                }
+                _ => {}
+            }
+        });
         self.event_hub = Some(hub);
     }
 
+    /// Set the timeout for session requests of agents without an override
+    pub fn set_request_timeout(&mut self, timeout: Duration) {
+        self.request_timeout = timeout;
+    }
+
+    /// Load per-agent timeout overrides from the agent configs
+    pub fn set_agent_request_timeouts(&self, agents: &HashMap<String, AgentProcessConfig>) {
+        *self.agent_request_timeouts.write().unwrap() = request_timeouts_from_config(agents);
+    }
+
+    /// Timeout for an agent's session requests
+    pub fn request_timeout(&self, agent_name: &str) -> Duration {
+        self.agent_request_timeouts
+            .read()
+            .unwrap()
+            .get(agent_name)
+            .copied()
+            .unwrap_or(self.request_timeout)
+    }
+
+    /// Fail with [`RequestTimeout`] if `request` does not finish in time
+    async fn with_timeout<T>(
+        &self,
+        agent_name: &str,
+        operation: &'static str,
+        request: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let after = self.request_timeout(agent_name);
+        smol::future::or(request, async {
+            smol::Timer::after(after).await;
+            log::warn!(
+                "{} for agent {} timed out after {:?}",
+                operation,
+                agent_name,
+                after
+            );
+            Err(RequestTimeout {
+                agent_name: agent_name.to_string(),
+                operation,
+                after,
+            }
+            .into())
+        })
+        .await
+    }
+
+    /// Run an idempotent request with the retry policy, each attempt bounded by the timeout
+    ///
+    /// A hung attempt fails with [`RequestTimeout`] and is retried like a
+    /// stopped agent; `op` fetches the agent handle again on every attempt so
+    /// a restarted agent is picked up.
+    async fn retry<T, F, Fut>(
+        &self,
+        agent_name: &str,
+        operation: &'static str,
+        mut op: F,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        retry_with_backoff(&self.retry_policy(), operation, || {
+            self.with_timeout(agent_name, operation, op())
+        })
+        .await
+    }
+
+    /// Set the workspace service used to persist session titles
+    pub fn set_workspace_service(&mut self, workspace_service: Arc<WorkspaceService>) {
+        self.workspace_service = Some(workspace_service);
+    }
+
+    /// Set the retry policy for list/load/resume requests
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry_policy.write().unwrap() = policy;
+    }
+
+    /// Current retry policy for list/load/resume requests
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy.read().unwrap().clone()
+    }
+
     // ========== Agent Operations ==========
 
     /// List all available agents
@@ -80,6 +338,68 @@ impl AgentService {
         self.agent_manager.list_agents_with_info().await
     }
 
+    /// Lifecycle state of an agent (initializing, ready or failed)
+    pub fn agent_state(&self, agent_name: &str) -> Option<AgentLifecycle> {
+        self.agent_manager.agent_state(agent_name)
+    }
+
+    /// Lifecycle state of every known agent, including ones still starting
+    pub fn agent_states(&self) -> Vec<(String, AgentLifecycle)> {
+        self.agent_manager.agent_states()
+    }
+
+    /// Whether the agent finished initializing and accepts requests
+    pub fn is_agent_ready(&self, agent_name: &str) -> bool {
+        self.agent_state(agent_name) == Some(AgentLifecycle::Ready)
+    }
+
+    // ========== Agent Capabilities ==========
+
+    /// Record the capabilities of every agent that has finished initializing
+    ///
+    /// Agents that become ready later are picked up from `AgentReady` events.
+    pub async fn load_agent_capabilities(&self) {
+        let agents = self.list_agents_with_info().await;
+        let mut capabilities = self.agent_capabilities.write().unwrap();
+        for (name, init_response) in agents {
+            if let Some(response) = init_response {
+                capabilities.insert(name, response.agent_capabilities);
+            }
+        }
+    }
+
+    /// Capabilities the agent advertised on initialize
+    ///
+    /// None while the agent is starting, failed or unknown; callers should
+    /// then leave features enabled and let the request itself report errors.
+    pub fn agent_capabilities(&self, agent_name: &str) -> Option<acp::AgentCapabilities> {
+        self.agent_capabilities
+            .read()
+            .unwrap()
+            .get(agent_name)
+            .cloned()
+    }
+
+    /// Whether the agent can list its sessions (session/list)
+    pub fn supports_session_list(&self, agent_name: &str) -> bool {
+        !self.session_list_unsupported(agent_name)
+            && self
+                .agent_capabilities(agent_name)
+                .is_none_or(|caps| caps.session_capabilities.list.is_some())
+    }
+
+    /// Whether the agent can load earlier sessions (session/load)
+    pub fn supports_load_session(&self, agent_name: &str) -> bool {
+        self.agent_capabilities(agent_name)
+            .is_none_or(|caps| caps.load_session)
+    }
+
+    /// Whether the agent accepts image content in prompts
+    pub fn supports_images(&self, agent_name: &str) -> bool {
+        self.agent_capabilities(agent_name)
+            .is_none_or(|caps| caps.prompt_capabilities.image)
+    }
+
     /// Get agent handle (internal use)
     async fn get_agent_handle(&self, name: &str) -> Result<Arc<AgentHandle>> {
         self.agent_manager
@@ -115,12 +435,9 @@ impl AgentService {
         agent_name: &str,
         mcp_servers: Vec<acp::McpServer>,
     ) -> Result<String> {
-        self.create_session_with_mcp_and_cwd(
-            agent_name,
-            mcp_servers,
-            std::env::current_dir().unwrap_or_default(),
-        )
-        .await
+        let cwd = self.default_session_cwd(None).await;
+        self.create_session_with_mcp_and_cwd(agent_name, mcp_servers, cwd)
+            .await
     }
 
     /// Create a new session with MCP servers and custom working directory
@@ -128,19 +445,23 @@ impl AgentService {
         &self,
         agent_name: &str,
         mcp_servers: Vec<acp::McpServer>,
-        cwd: std::path::PathBuf,
+        cwd: PathBuf,
     ) -> Result<String> {
         let agent_handle = self.get_agent_handle(agent_name).await?;
 
         let mut request = acp::NewSessionRequest::new(cwd.clone());
-        request.cwd = cwd;
+        request.cwd = cwd.clone();
         request.mcp_servers = mcp_servers;
         request.meta = None;
 
-        let new_session_response: acp::NewSessionResponse = agent_handle
-            .new_session(request)
-            .await
-            .map_err(|e| anyhow!("Failed to create session: {}", e))?;
+        let new_session_response: acp::NewSessionResponse = self
+            .with_timeout(agent_name, "session/new", async {
+                agent_handle
+                    .new_session(request)
+                    .await
+                    .map_err(|e| anyhow!("Failed to create session: {}", e))
+            })
+            .await?;
 
         let session_id = new_session_response.session_id.to_string();
 
@@ -160,6 +481,7 @@ impl AgentService {
                 info.last_active = now;
                 info.status = SessionStatus::Active;
                 info.new_session_response = Some(new_session_response);
+                info.cwd = Some(cwd);
                 log::info!(
                     "Session {} for agent {} already exists; refreshed metadata",
                     session_id,
@@ -175,6 +497,8 @@ impl AgentService {
                     status: SessionStatus::Active,
                     new_session_response: Some(new_session_response),
                     available_commands: Vec::new(), // Will be populated by AvailableCommandsUpdate
+                    title: self.session_title(&session_id),
+                    cwd: Some(cwd),
                 });
                 log::info!("Created session {} for agent {}", session_id, agent_name);
             }
@@ -195,13 +519,9 @@ impl AgentService {
         session_id: &str,
         mcp_servers: Vec<acp::McpServer>,
     ) -> Result<String> {
-        self.resume_session_with_mcp_and_cwd(
-            agent_name,
-            session_id,
-            mcp_servers,
-            std::env::current_dir().unwrap_or_default(),
-        )
-        .await
+        let cwd = self.default_session_cwd(Some(session_id)).await;
+        self.resume_session_with_mcp_and_cwd(agent_name, session_id, mcp_servers, cwd)
+            .await
     }
 
     /// Resume an existing session with MCP servers and custom working directory
@@ -210,22 +530,29 @@ impl AgentService {
         agent_name: &str,
         session_id: &str,
         mcp_servers: Vec<acp::McpServer>,
-        cwd: std::path::PathBuf,
+        cwd: PathBuf,
     ) -> Result<String> {
-        let agent_handle = self.get_agent_handle(agent_name).await?;
-
         let mut request = acp::ResumeSessionRequest::new(
             acp::SessionId::from(session_id.to_string()),
             cwd.clone(),
         );
-        request.cwd = cwd;
+        request.cwd = cwd.clone();
         request.mcp_servers = mcp_servers;
         request.meta = None;
 
-        let resume_session_response: acp::ResumeSessionResponse = agent_handle
-            .resume_session(request)
+        // Fetch the handle on every attempt so a restarted agent is picked up
+        let resume_session_response: acp::ResumeSessionResponse = self
+            .retry(agent_name, "session/resume", || {
+                let request = request.clone();
+                async move {
+                    self.get_agent_handle(agent_name)
+                        .await?
+                        .resume_session(request)
+                        .await
+                }
+            })
             .await
-            .map_err(|e| anyhow!("Failed to resume session: {}", e))?;
+            .map_err(|e| request_failed("Failed to resume session", e))?;
 
         // Convert ResumeSessionResponse to NewSessionResponse for consistency
         let new_session_response = acp::NewSessionResponse::new(session_id.to_string())
@@ -249,6 +576,7 @@ impl AgentService {
                 info.last_active = now;
                 info.status = SessionStatus::Active;
                 info.new_session_response = Some(new_session_response);
+                info.cwd = Some(cwd);
                 log::info!("Resumed session {} for agent {}", session_id, agent_name);
             }
             Entry::Vacant(entry) => {
@@ -260,6 +588,8 @@ impl AgentService {
                     status: SessionStatus::Active,
                     new_session_response: Some(new_session_response),
                     available_commands: Vec::new(),
+                    title: self.session_title(session_id),
+                    cwd: Some(cwd),
                 });
                 log::info!(
                     "Resumed session {} for agent {} (created new entry)",
@@ -268,6 +598,8 @@ impl AgentService {
                 );
             }
         }
+        drop(sessions);
+        self.restore_session_mode(session_id).await;
         Ok(session_id.to_string())
     }
 
@@ -284,13 +616,9 @@ impl AgentService {
         session_id: &str,
         mcp_servers: Vec<acp::McpServer>,
     ) -> Result<String> {
-        self.load_session_with_mcp_and_cwd(
-            agent_name,
-            session_id,
-            mcp_servers,
-            std::env::current_dir().unwrap_or_default(),
-        )
-        .await
+        let cwd = self.default_session_cwd(Some(session_id)).await;
+        self.load_session_with_mcp_and_cwd(agent_name, session_id, mcp_servers, cwd)
+            .await
     }
 
     /// Load an existing session with MCP servers and custom working directory
@@ -299,7 +627,7 @@ impl AgentService {
         agent_name: &str,
         session_id: &str,
         mcp_servers: Vec<acp::McpServer>,
-        cwd: std::path::PathBuf,
+        cwd: PathBuf,
     ) -> Result<String> {
         let init_response = self
             .get_agent_init_response(agent_name)
@@ -313,20 +641,28 @@ impl AgentService {
             ));
         }
 
-        let agent_handle = self.get_agent_handle(agent_name).await?;
-
         let mut request =
             acp::LoadSessionRequest::new(acp::SessionId::from(session_id.to_string()), cwd.clone());
-        request.cwd = cwd;
+        request.cwd = cwd.clone();
         request.mcp_servers = mcp_servers;
         request.meta = None;
 
         self.set_session_loading(session_id, true);
-        let load_session_response = agent_handle.load_session(request).await;
+        let load_session_response = self
+            .retry(agent_name, "session/load", || {
+                let request = request.clone();
+                async move {
+                    self.get_agent_handle(agent_name)
+                        .await?
+                        .load_session(request)
+                        .await
+                }
+            })
+            .await
+            .map_err(|e| request_failed("Failed to load session", e));
         self.set_session_loading(session_id, false);
 
-        let load_session_response: acp::LoadSessionResponse =
-            load_session_response.map_err(|e| anyhow!("Failed to load session: {}", e))?;
+        let load_session_response: acp::LoadSessionResponse = load_session_response?;
 
         // Convert LoadSessionResponse to NewSessionResponse for consistency
         let new_session_response = acp::NewSessionResponse::new(session_id.to_string())
@@ -350,6 +686,7 @@ impl AgentService {
                 info.last_active = now;
                 info.status = SessionStatus::Active;
                 info.new_session_response = Some(new_session_response);
+                info.cwd = Some(cwd);
                 log::info!("Loaded session {} for agent {}", session_id, agent_name);
             }
             Entry::Vacant(entry) => {
@@ -361,6 +698,8 @@ impl AgentService {
                     status: SessionStatus::Active,
                     new_session_response: Some(new_session_response),
                     available_commands: Vec::new(),
+                    title: self.session_title(session_id),
+                    cwd: Some(cwd),
                 });
                 log::info!(
                     "Loaded session {} for agent {} (created new entry)",
@@ -369,9 +708,290 @@ impl AgentService {
                 );
             }
         }
+        drop(sessions);
+        self.restore_session_mode(session_id).await;
         Ok(session_id.to_string())
     }
 
+    // ========== Session Modes ==========
+
+    /// Load persisted session modes (call once after startup)
+    pub async fn load_session_modes(&self) {
+        let Some(workspace_service) = &self.workspace_service else {
+            return;
+        };
+        let modes = workspace_service.session_modes().await;
+        *self.session_modes.write().unwrap() = modes.into_iter().collect();
+    }
+
+    /// Modes the agent reported for a session, if any
+    pub fn session_mode_state(&self, session_id: &str) -> Option<acp::SessionModeState> {
+        self.get_session_by_id(session_id)?
+            .new_session_response?
+            .modes
+            .filter(|modes| !modes.available_modes.is_empty())
+    }
+
+    /// The session's current mode: the one chosen in the app, else the agent's
+    pub fn session_mode(&self, session_id: &str) -> Option<String> {
+        if let Some(mode) = self.session_modes.read().unwrap().get(session_id) {
+            return Some(mode.clone());
+        }
+        self.session_mode_state(session_id)
+            .map(|modes| modes.current_mode_id.to_string())
+    }
+
+    /// Switch a session to `mode_id` via ACP and remember the choice
+    pub async fn set_session_mode(&self, session_id: &str, mode_id: &str) -> Result<()> {
+        let agent_name = self
+            .get_agent_for_session(session_id)
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+        let request = acp::SetSessionModeRequest::new(
+            acp::SessionId::from(session_id.to_string()),
+            mode_id.to_string(),
+        );
+        self.get_agent_handle(&agent_name)
+            .await?
+            .set_session_mode(request)
+            .await
+            .map_err(|e| anyhow!("Failed to set session mode: {}", e))?;
+
+        self.record_session_mode(session_id, mode_id).await;
+        log::info!("Set mode of session {} to {}", session_id, mode_id);
+        Ok(())
+    }
+
+    /// Remember a session's mode, e.g. after the agent reported a mode change
+    pub async fn record_session_mode(&self, session_id: &str, mode_id: &str) {
+        self.session_modes
+            .write()
+            .unwrap()
+            .insert(session_id.to_string(), mode_id.to_string());
+        if let Some(modes) = self
+            .sessions
+            .write()
+            .unwrap()
+            .values_mut()
+            .find_map(|agent_sessions| agent_sessions.get_mut(session_id))
+            .and_then(|info| info.new_session_response.as_mut())
+            .and_then(|response| response.modes.as_mut())
+        {
+            modes.current_mode_id = mode_id.to_string().into();
+        }
+
+        if let Some(workspace_service) = &self.workspace_service
+            && let Err(e) = workspace_service
+                .set_session_mode(session_id, mode_id)
+                .await
+        {
+            log::warn!("Failed to persist mode of session {}: {}", session_id, e);
+        }
+    }
+
+    /// Re-apply the mode chosen earlier when a session is resumed or loaded
+    async fn restore_session_mode(&self, session_id: &str) {
+        let Some(mode) = self.session_modes.read().unwrap().get(session_id).cloned() else {
+            return;
+        };
+        if let Some(modes) = self.session_mode_state(session_id) {
+            if modes.current_mode_id.to_string() == mode {
+                return;
+            }
+            if !modes
+                .available_modes
+                .iter()
+                .any(|available| available.id.to_string() == mode)
+            {
+                log::info!(
+                    "Mode {} of session {} is no longer offered by the agent",
+                    mode,
+                    session_id
+                );
+                return;
+            }
+        }
+        if let Err(e) = self.set_session_mode(session_id, &mode).await {
+            log::warn!(
+                "Failed to restore mode {} of session {}: {}",
+                mode,
+                session_id,
+                e
+            );
+        }
+    }
+
+    // ========== Session Titles ==========
+
+    /// Load persisted session titles (call once after startup)
+    pub async fn load_session_titles(&self) {
+        let Some(workspace_service) = &self.workspace_service else {
+            return;
+        };
+        let titles = workspace_service.session_titles().await;
+        let sources = workspace_service.session_title_sources().await;
+
+        for agent_sessions in self.sessions.write().unwrap().values_mut() {
+            for (session_id, info) in agent_sessions.iter_mut() {
+                if let Some(title) = titles.get(session_id) {
+                    info.title = Some(title.clone());
+                }
+            }
+        }
+        *self.session_titles.write().unwrap() = titles.into_iter().collect();
+        *self.session_title_sources.write().unwrap() = sources.into_iter().collect();
+    }
+
+    /// Get a session's title
+    pub fn session_title(&self, session_id: &str) -> Option<String> {
+        self.session_titles.read().unwrap().get(session_id).cloned()
+    }
+
+    /// Get the working directory a session was started in
+    pub fn session_cwd(&self, session_id: &str) -> Option<PathBuf> {
+        let sessions = self.sessions.read().unwrap();
+        sessions
+            .values()
+            .find_map(|agent_sessions| agent_sessions.get(session_id))
+            .and_then(|info| info.cwd.clone())
+    }
+
+    /// Pick the cwd for a session when the caller does not supply one
+    ///
+    /// Prefers the session's recorded cwd, then the workspace of the task bound
+    /// to it, then the active workspace, and finally the process directory.
+    async fn default_session_cwd(&self, session_id: Option<&str>) -> PathBuf {
+        if let Some(cwd) = session_id.and_then(|id| self.session_cwd(id)) {
+            return cwd;
+        }
+        if let Some(workspace_service) = &self.workspace_service {
+            if let Some(session_id) = session_id
+                && let Some(task) = workspace_service.get_task_by_session(session_id).await
+                && let Some(workspace) = workspace_service.get_workspace(&task.workspace_id).await
+            {
+                return workspace.path;
+            }
+            if let Some(workspace) = workspace_service.get_active_workspace().await {
+                return workspace.path;
+            }
+        }
+        std::env::current_dir().unwrap_or_default()
+    }
+
+    /// Rename a session; an empty title reverts to the short-id display
+    pub async fn set_session_title(
+        &self,
+        agent_name: &str,
+        session_id: &str,
+        title: &str,
+    ) -> Result<()> {
+        self.suggest_session_title(agent_name, session_id, title, SessionTitleSource::Manual)
+            .await?;
+        Ok(())
+    }
+
+    /// Title a session from its first user message, unless it already has a title
+    pub async fn title_session_from_message(
+        &self,
+        agent_name: &str,
+        session_id: &str,
+        message: &str,
+    ) -> Result<()> {
+        if let Some(title) = session_title_from_message(message) {
+            self.suggest_session_title(
+                agent_name,
+                session_id,
+                &title,
+                SessionTitleSource::FirstMessage,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Apply titles reported by the agent to sessions in this workspace
+    pub async fn apply_agent_session_titles(
+        &self,
+        agent_name: &str,
+        sessions: &[acp::SessionInfo],
+    ) {
+        for session in sessions {
+            let session_id = session.session_id.to_string();
+            let Some(title) = &session.title else {
+                continue;
+            };
+            if self.get_session_info(agent_name, &session_id).is_none() {
+                continue;
+            }
+            if let Err(e) = self
+                .suggest_session_title(agent_name, &session_id, title, SessionTitleSource::Agent)
+                .await
+            {
+                log::warn!(
+                    "Failed to apply agent title for session {}: {}",
+                    session_id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Set a session's title unless a title from a preferred source exists
+    ///
+    /// Returns true if the title was applied.
+    async fn suggest_session_title(
+        &self,
+        agent_name: &str,
+        session_id: &str,
+        title: &str,
+        source: SessionTitleSource,
+    ) -> Result<bool> {
+        let title = Some(title.trim())
+            .filter(|title| !title.is_empty())
+            .map(str::to_string);
+
+        {
+            let mut sources = self.session_title_sources.write().unwrap();
+            let existing = sources.get(session_id).copied();
+            if !source.may_replace(existing) {
+                return Ok(false);
+            }
+            if existing == Some(source) && self.session_title(session_id) == title {
+                return Ok(false);
+            }
+            sources.insert(session_id.to_string(), source);
+
+            let mut titles = self.session_titles.write().unwrap();
+            match &title {
+                Some(title) => titles.insert(session_id.to_string(), title.clone()),
+                None => titles.remove(session_id),
+            };
+        }
+        if let Some(info) = self
+            .sessions
+            .write()
+            .unwrap()
+            .get_mut(agent_name)
+            .and_then(|agent_sessions| agent_sessions.get_mut(session_id))
+        {
+            info.title = title.clone();
+        }
+
+        if let Some(workspace_service) = &self.workspace_service {
+            workspace_service
+                .set_session_title(session_id, title.clone(), source)
+                .await?;
+        }
+
+        if let Some(ref event_hub) = self.event_hub {
+            event_hub.publish_workspace_update(WorkspaceUpdateEvent::SessionTitleUpdated {
+                session_id: session_id.to_string(),
+                agent_name: agent_name.to_string(),
+                title,
+            });
+        }
+        Ok(true)
+    }
+
     /// Get session information
     pub fn get_session_info(&self, agent_name: &str, session_id: &str) -> Option<AgentSessionInfo> {
         self.sessions
@@ -389,6 +1009,18 @@ impl AgentService {
             if let Some(info) = agent_sessions.get_mut(session_id) {
                 info.status = SessionStatus::Closed;
                 log::info!("Closed session {} for agent {}", session_id, agent_name);
+
+                if let Some(ref event_hub) = self.event_hub {
+                    event_hub.publish_workspace_update(
+                        WorkspaceUpdateEvent::SessionStatusUpdated {
+                            session_id: session_id.to_string(),
+                            agent_name: agent_name.to_string(),
+                            status: SessionStatus::Closed,
+                            last_active: info.last_active,
+                            message_count: 0,
+                        },
+                    );
+                }
             }
         }
         Ok(())
@@ -462,6 +1094,10 @@ impl AgentService {
         agent_name: &str,
         request: acp::ListSessionsRequest,
     ) -> Result<acp::ListSessionsResponse> {
+        if self.session_list_unsupported(agent_name) {
+            return Err(self.mark_session_list_unsupported(agent_name));
+        }
+
         let init_response = self
             .get_agent_init_response(agent_name)
             .await
@@ -473,17 +1109,81 @@ impl AgentService {
             .list
             .is_none()
         {
-            return Err(anyhow!(
-                "Agent '{}' does not support session/list",
-                agent_name
-            ));
+            return Err(self.mark_session_list_unsupported(agent_name));
         }
 
-        let agent_handle = self.get_agent_handle(agent_name).await?;
-        agent_handle
-            .list_sessions(request)
+        let response = self
+            .retry(agent_name, "session/list", || {
+                let request = request.clone();
+                async move {
+                    self.get_agent_handle(agent_name)
+                        .await?
+                        .list_sessions(request)
+                        .await
+                }
+            })
             .await
-            .map_err(|e| anyhow!("Failed to list agent sessions: {}", e))
+            .map_err(|e| {
+                // Agents may advertise the capability yet reject the method
+                if is_method_unsupported_error(&e) {
+                    self.mark_session_list_unsupported(agent_name)
+                } else {
+                    request_failed("Failed to list agent sessions", e)
+                }
+            })?;
+
+        self.apply_agent_session_titles(agent_name, &response.sessions)
+            .await;
+        Ok(response)
+    }
+
+    /// Whether the agent is known not to implement session/list
+    ///
+    /// Learned from the first list attempt and kept until the agent's config
+    /// changes, so the UI can disable listing without asking the agent again.
+    pub fn session_list_unsupported(&self, agent_name: &str) -> bool {
+        self.session_list_unsupported
+            .read()
+            .unwrap()
+            .contains(agent_name)
+    }
+
+    /// Remember that the agent lacks session/list and build the error for it
+    fn mark_session_list_unsupported(&self, agent_name: &str) -> anyhow::Error {
+        if self
+            .session_list_unsupported
+            .write()
+            .unwrap()
+            .insert(agent_name.to_string())
+        {
+            log::info!("Agent '{}' does not support session/list", agent_name);
+        }
+        SessionListUnsupported {
+            agent_name: agent_name.to_string(),
+        }
+        .into()
+    }
+
+    /// List every session reported by the agent, following `next_cursor`
+    /// until the agent reports no further pages.
+    pub async fn list_all_agent_sessions(&self, agent_name: &str) -> Result<Vec<acp::SessionInfo>> {
+        let mut sessions = Vec::new();
+        let mut seen_cursors = HashSet::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let request = acp::ListSessionsRequest::new().cursor(cursor.clone());
+            let response = self.list_agent_sessions(agent_name, request).await?;
+            sessions.extend(response.sessions);
+
+            match response.next_cursor {
+                // Guard against agents that hand back a cursor they already returned
+                Some(next) if seen_cursors.insert(next.clone()) => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(sessions)
     }
 
     /// List all sessions
@@ -540,6 +1240,8 @@ impl AgentService {
                     status: SessionStatus::Active,
                     new_session_response: None,
                     available_commands: commands,
+                    title: self.session_title(session_id),
+                    cwd: None,
                 });
             }
         }
@@ -587,16 +1289,26 @@ impl AgentService {
 
     // ========== Prompt Operations ==========
 
-    /// Send a prompt to an agent's session
+    /// Run one prompt turn in a session
+    ///
+    /// `content` is the full prompt: text plus any attachments (images,
+    /// resources, code context). The agent owning the session is looked up
+    /// from the session. Streamed updates for the turn arrive through the
+    /// event hub as session updates; the returned future resolves with the
+    /// stop reason once the turn ends.
     pub async fn send_prompt(
         &self,
-        agent_name: &str,
         session_id: &str,
-        prompt: Vec<acp::ContentBlock>,
+        content: Vec<acp::ContentBlock>,
     ) -> Result<PromptResponse> {
+        let agent_name = self
+            .get_agent_for_session(session_id)
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+        let agent_name = agent_name.as_str();
         let agent_handle = self.get_agent_handle(agent_name).await?;
         self.update_session_status(agent_name, session_id, SessionStatus::InProgress);
-        let request = acp::PromptRequest::new(acp::SessionId::from(session_id.to_string()), prompt);
+        let request =
+            acp::PromptRequest::new(acp::SessionId::from(session_id.to_string()), content);
 
         let result = agent_handle
             .prompt(request)
@@ -666,3 +1378,265 @@ impl AgentService {
             .map(|info| info.agent_name)
     }
 }
+
+/// Maximum length of a title generated from a message, in characters
+const GENERATED_TITLE_MAX_CHARS: usize = 40;
+
+/// Build a short session title from a user message
+///
+/// Uses the first non-empty line with whitespace collapsed, cut at a word
+/// boundary when it is longer than [`GENERATED_TITLE_MAX_CHARS`].
+pub fn session_title_from_message(message: &str) -> Option<String> {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= GENERATED_TITLE_MAX_CHARS {
+        return Some(line);
+    }
+
+    let cut: String = line.chars().take(GENERATED_TITLE_MAX_CHARS).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    Some(format!(
+        "{}…",
+        cut.trim_end_matches(|c: char| c.is_ascii_punctuation())
+    ))
+}
+
+/// Timeout overrides for the agents that set `request_timeout_secs`
+fn request_timeouts_from_config(
+    agents: &HashMap<String, AgentProcessConfig>,
+) -> HashMap<String, Duration> {
+    agents
+        .iter()
+        .filter_map(|(name, config)| {
+            config
+                .request_timeout_secs
+                .map(|secs| (name.clone(), Duration::from_secs(secs)))
+        })
+        .collect()
+}
+
+/// Prefix a failed request's error with `what`, keeping a [`RequestTimeout`]
+/// detectable by downcasting
+fn request_failed(what: &str, error: anyhow::Error) -> anyhow::Error {
+    match error.downcast::<RequestTimeout>() {
+        Ok(timeout) => timeout.into(),
+        Err(error) => anyhow!("{}: {}", what, error),
+    }
+}
+
+/// Whether an agent request error is worth retrying
+///
+/// Only a stopped or restarting agent ([`AgentUnavailable`]), an attempt that
+/// timed out ([`RequestTimeout`]) and dropped connections are retried.
+/// Everything else, including unknown agents and errors the agent itself
+/// returned, is fatal.
+pub fn is_transient_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if cause.downcast_ref::<AgentUnavailable>().is_some()
+            || cause.downcast_ref::<RequestTimeout>().is_some()
+        {
+            return true;
+        }
+        cause.downcast_ref::<std::io::Error>().is_some_and(|e| {
+            matches!(
+                e.kind(),
+                std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::UnexpectedEof
+            )
+        })
+    })
+}
+
+/// Whether an agent rejected a request because it does not implement the method
+///
+/// Matches the JSON-RPC "method not found" error (-32601) and the wording
+/// agents commonly use for unimplemented methods.
+pub fn is_method_unsupported_error(error: &anyhow::Error) -> bool {
+    const UNSUPPORTED: &[&str] = &[
+        "method not found",
+        "-32601",
+        "not supported",
+        "unsupported",
+        "not implemented",
+    ];
+    let message = format!("{:#}", error).to_lowercase();
+    UNSUPPORTED.iter().any(|pattern| message.contains(pattern))
+}
+
+/// Run `op`, retrying transient failures with exponential backoff
+async fn retry_with_backoff<T, F, Fut>(policy: &RetryPolicy, label: &str, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => {
+                if attempt > 1 {
+                    log::info!("{} succeeded on attempt {}", label, attempt);
+                }
+                return Ok(value);
+            }
+            Err(e) if attempt < policy.max_attempts && is_transient_error(&e) => {
+                let delay = policy.delay_after(attempt);
+                log::warn!(
+                    "{} failed on attempt {}/{} ({:#}); retrying in {:?}",
+                    label,
+                    attempt,
+                    policy.max_attempts,
+                    e,
+                    delay
+                );
+                smol::Timer::after(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                if attempt > 1 {
+                    log::error!("{} failed after {} attempts", label, attempt);
+                }
+                return Err(e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        };
+
+        // Transient failures are retried until they succeed
+        let calls = &AtomicU32::new(0);
+        let result = smol::block_on(retry_with_backoff(&policy, "test", || async move {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(AgentUnavailable {
+                    agent_name: "claude".to_string(),
+                    stopped: true,
+                }
+                .into()),
+                _ => Ok(7),
+            }
+        }));
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Fatal errors and exhausted attempts stop immediately
+        let calls = &AtomicU32::new(0);
+        let result: Result<()> =
+            smol::block_on(retry_with_backoff(&policy, "test", || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(anyhow!("Agent 'claude' does not support session/load"))
+            }));
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let calls = &AtomicU32::new(0);
+        let result: Result<()> =
+            smol::block_on(retry_with_backoff(&policy, "test", || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(
+                    anyhow::Error::from(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+                        .context("Failed to list agent sessions"),
+                )
+            }));
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Messages that merely look transient are not retried
+        assert!(!is_transient_error(&anyhow!("Agent not found: claude")));
+        assert!(!is_transient_error(&anyhow!("Invalid timeout parameter")));
+
+        let policy = RetryPolicy::from(&RequestRetryConfig {
+            max_attempts: 0,
+            ..Default::default()
+        });
+        assert_eq!(policy.max_attempts, 1);
+
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_after(1), Duration::from_millis(250));
+        assert_eq!(policy.delay_after(2), Duration::from_millis(500));
+        assert_eq!(policy.delay_after(10), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_session_title_from_message() {
+        assert_eq!(session_title_from_message("  \n\n"), None);
+        assert_eq!(
+            session_title_from_message("\n  Fix   the build\nmore details"),
+            Some("Fix the build".to_string())
+        );
+        assert_eq!(
+            session_title_from_message(
+                "Refactor the configuration loader so that it merges, please"
+            ),
+            Some("Refactor the configuration loader so…".to_string())
+        );
+        // No word boundary: cut at the limit
+        assert_eq!(
+            session_title_from_message(&"x".repeat(50)),
+            Some(format!("{}…", "x".repeat(40)))
+        );
+    }
+
+    #[test]
+    fn test_request_timeouts_from_config() {
+        let agents: HashMap<String, AgentProcessConfig> = serde_json::from_str(
+            r#"{
+                "slow": { "command": "slow-acp", "request_timeout_secs": 300 },
+                "fast": { "command": "fast-acp" }
+            }"#,
+        )
+        .unwrap();
+        let timeouts = request_timeouts_from_config(&agents);
+        assert_eq!(timeouts.len(), 1);
+        assert_eq!(timeouts["slow"], Duration::from_secs(300));
+
+        let error: anyhow::Error = RequestTimeout {
+            agent_name: "slow".to_string(),
+            operation: "session/load",
+            after: Duration::from_secs(300),
+        }
+        .into();
+        assert!(error.downcast_ref::<RequestTimeout>().is_some());
+        assert!(is_transient_error(&error));
+        let error = request_failed("Failed to load session", error);
+        assert!(error.downcast_ref::<RequestTimeout>().is_some());
+    }
+
+    #[test]
+    fn test_is_method_unsupported_error() {
+        assert!(is_method_unsupported_error(&anyhow!(
+            "Method not found: session/list"
+        )));
+        assert!(is_method_unsupported_error(&anyhow!(
+            "JSON-RPC error -32601"
+        )));
+        assert!(!is_method_unsupported_error(&anyhow!(
+            "agent claude is not running"
+        )));
+
+        let error: anyhow::Error = SessionListUnsupported {
+            agent_name: "basic".to_string(),
+        }
+        .into();
+        assert!(error.downcast_ref::<SessionListUnsupported>().is_some());
+        assert!(!is_transient_error(&error));
+    }
+}
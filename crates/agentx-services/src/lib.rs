@@ -4,14 +4,24 @@ pub mod ai_service;
 pub mod config_watcher;
 pub mod message_service;
 pub mod persistence_service;
+pub mod search_index;
 pub mod workspace_service;
 
-pub use agent_config_service::AgentConfigService;
-pub use agent_service::{AgentService, AgentSessionInfo};
+pub use agent_config_service::{
+    AgentChanges, AgentConfigField, AgentConfigIssue, AgentConfigService,
+    AgentConfigValidationError, AgentConflict, AgentConflictKind, ConfigBackup, McpServerChanges,
+    UploadDirStatus, detect_agent_conflicts,
+};
+pub use agent_service::{
+    AgentService, AgentSessionInfo, RequestTimeout, RetryPolicy, SessionListUnsupported,
+};
 pub use ai_service::{AiService, AiServiceConfig, CommentStyle};
 pub use config_watcher::ConfigWatcher;
-pub use message_service::MessageService;
-pub use persistence_service::PersistenceService;
+pub use message_service::{MessageService, coalesce_session_updates};
+pub use persistence_service::{
+    CompactionReport, PersistenceService, RetentionPolicy, StorageUsage,
+};
+pub use search_index::{IndexedRole, SessionSearchHit};
 pub use workspace_service::WorkspaceService;
 
 // Re-export SessionStatus from types for convenience
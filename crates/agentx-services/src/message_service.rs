@@ -14,11 +14,12 @@ use agent_client_protocol::{
 };
 use anyhow::{Result, anyhow};
 
-use agentx_event_bus::{EventHub, SessionUpdateEvent, WorkspaceUpdateEvent};
+use agentx_event_bus::{EventHub, EventSubscription, SessionUpdateEvent, WorkspaceUpdateEvent};
 use agentx_types::SessionStatus;
 
 use super::agent_service::AgentService;
 use super::persistence_service::{PersistedMessage, PersistenceService};
+use super::search_index::SessionSearchHit;
 
 /// Message service - handles message sending and event bus interaction
 pub struct MessageService {
@@ -87,6 +88,17 @@ impl MessageService {
                 }
             }
 
+            // Remember mode changes made by the agent itself
+            if let SessionUpdate::CurrentModeUpdate(ref mode_update) = update {
+                let agent_svc = agent_svc.clone();
+                let session_id = session_id.clone();
+                let mode_id = mode_update.current_mode_id.to_string();
+                smol::spawn(async move {
+                    agent_svc.record_session_mode(&session_id, &mode_id).await;
+                })
+                .detach();
+            }
+
             let is_loading = is_agent_event && agent_svc.is_session_loading(&session_id);
             let should_persist = if is_loading {
                 let mut policy_map = load_policy.lock().unwrap();
@@ -164,16 +176,11 @@ impl MessageService {
     /// the UI panel has subscribed before the message is sent.
     pub async fn send_message_to_session(
         &self,
-        agent_name: &str,
         session_id: &str,
         content_blocks: Vec<ContentBlock>,
     ) -> Result<PromptResponse> {
         // 1. Verify session exists
-        if self
-            .agent_service
-            .get_session_info(agent_name, session_id)
-            .is_none()
-        {
+        if self.agent_service.get_session_by_id(session_id).is_none() {
             return Err(anyhow!("Session not found: {}", session_id));
         }
 
@@ -185,7 +192,7 @@ impl MessageService {
         // 3. Send prompt to agent
         let result = self
             .agent_service
-            .send_prompt(agent_name, session_id, content_blocks)
+            .send_prompt(session_id, content_blocks)
             .await
             .map_err(|e| anyhow!("Failed to send message: {}", e))?;
 
@@ -245,14 +252,18 @@ impl MessageService {
     /// Subscribe to session updates
     ///
     /// Returns a channel receiver for session updates with metadata. If session_id
-    /// is provided, only updates for that session will be received.
+    /// is provided, only updates for that session will be received. The
+    /// receiver is closed once the returned subscription is dropped.
     pub fn subscribe_session_updates(
         &self,
         session_id: Option<String>,
-    ) -> tokio::sync::mpsc::UnboundedReceiver<SessionUpdateEvent> {
+    ) -> (
+        EventSubscription,
+        tokio::sync::mpsc::UnboundedReceiver<SessionUpdateEvent>,
+    ) {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
-        self.event_hub.subscribe_session_updates(move |event| {
+        let id = self.event_hub.subscribe_session_updates(move |event| {
             // Filter by session_id if specified
             if let Some(ref filter_id) = session_id {
                 if &event.session_id != filter_id {
@@ -263,7 +274,7 @@ impl MessageService {
             let _ = tx.send(event.clone());
         });
 
-        rx
+        (EventSubscription::new(&self.event_hub, id), rx)
     }
 
     /// Load historical messages for a session
@@ -278,6 +289,17 @@ impl MessageService {
         self.persistence_service.delete_session(session_id).await
     }
 
+    /// Search the persisted messages of all sessions
+    ///
+    /// Returns at most `limit` hits, best matches first.
+    pub async fn search_sessions(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SessionSearchHit>> {
+        self.persistence_service.search(query, limit).await
+    }
+
     /// List all available sessions with history
     pub async fn list_workspace_sessions_with_history(&self) -> Result<Vec<String>> {
         self.persistence_service.list_workspace_sessions().await
@@ -306,3 +328,102 @@ impl MessageService {
             .get_session_commands(&agent_name, session_id)
     }
 }
+
+/// Merge consecutive text chunks of the same session into a single update
+///
+/// Streaming agents send messages a few tokens at a time. Merging runs of
+/// message or thought chunks cuts the work per batch while keeping the order
+/// of everything else; no update is dropped.
+pub fn coalesce_session_updates(events: Vec<SessionUpdateEvent>) -> Vec<SessionUpdateEvent> {
+    let mut coalesced: Vec<SessionUpdateEvent> = Vec::with_capacity(events.len());
+    for event in events {
+        if let Some(last) = coalesced.last_mut() {
+            if last.session_id == event.session_id {
+                if let Some(merged) = merge_text_chunks(&last.update, &event.update) {
+                    last.update = Arc::new(merged);
+                    continue;
+                }
+            }
+        }
+        coalesced.push(event);
+    }
+    coalesced
+}
+
+fn merge_text_chunks(previous: &SessionUpdate, next: &SessionUpdate) -> Option<SessionUpdate> {
+    fn append(previous: &ContentChunk, next: &ContentChunk) -> Option<ContentChunk> {
+        let ContentBlock::Text(next_text) = &next.content else {
+            return None;
+        };
+        let mut merged = previous.clone();
+        let ContentBlock::Text(text) = &mut merged.content else {
+            return None;
+        };
+        text.text.push_str(&next_text.text);
+        Some(merged)
+    }
+
+    match (previous, next) {
+        (SessionUpdate::AgentMessageChunk(previous), SessionUpdate::AgentMessageChunk(next)) => {
+            append(previous, next).map(SessionUpdate::AgentMessageChunk)
+        }
+        (SessionUpdate::AgentThoughtChunk(previous), SessionUpdate::AgentThoughtChunk(next)) => {
+            append(previous, next).map(SessionUpdate::AgentThoughtChunk)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(session_id: &str, update: SessionUpdate) -> SessionUpdateEvent {
+        SessionUpdateEvent {
+            session_id: session_id.to_string(),
+            agent_name: None,
+            update: Arc::new(update),
+        }
+    }
+
+    fn message_chunk(text: &str) -> SessionUpdate {
+        SessionUpdate::AgentMessageChunk(ContentChunk::new(ContentBlock::from(text)))
+    }
+
+    fn thought_chunk(text: &str) -> SessionUpdate {
+        SessionUpdate::AgentThoughtChunk(ContentChunk::new(ContentBlock::from(text)))
+    }
+
+    fn text_of(event: &SessionUpdateEvent) -> &str {
+        match &*event.update {
+            SessionUpdate::AgentMessageChunk(chunk) | SessionUpdate::AgentThoughtChunk(chunk) => {
+                match &chunk.content {
+                    ContentBlock::Text(text) => &text.text,
+                    _ => "",
+                }
+            }
+            _ => "",
+        }
+    }
+
+    #[test]
+    fn test_coalesce_merges_runs_of_text_chunks_in_order() {
+        let events = vec![
+            event("a", message_chunk("Hel")),
+            event("a", message_chunk("lo")),
+            event("a", thought_chunk("hmm")),
+            event("a", thought_chunk("...")),
+            event("b", message_chunk("other")),
+            event("a", message_chunk(" world")),
+        ];
+
+        let coalesced = coalesce_session_updates(events);
+
+        let texts: Vec<_> = coalesced.iter().map(text_of).collect();
+        assert_eq!(texts, vec!["Hello", "hmm...", "other", " world"]);
+        assert!(matches!(
+            &*coalesced[1].update,
+            SessionUpdate::AgentThoughtChunk(_)
+        ));
+    }
+}
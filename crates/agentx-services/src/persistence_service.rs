@@ -2,10 +2,15 @@
 //!
 //! This service saves session updates to disk in JSONL format (one JSON object per line)
 //! and loads historical messages when needed.
+//!
+//! Files are only ever appended to, one complete record per write, and fsynced
+//! in batches. A record torn by a crash is skipped on load and cut off before
+//! the next append, so the rest of the history always stays readable.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
 use agent_client_protocol::{
     ContentBlock, ContentChunk, SessionUpdate, TextContent, ToolCallStatus, ToolCallUpdate,
@@ -14,6 +19,8 @@ use anyhow::{Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+use super::search_index::{SessionSearchHit, SessionSearchIndex};
+
 /// Persisted message entry with timestamp
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersistedMessage {
@@ -253,6 +260,11 @@ impl ChunkAccumulator {
         Some((timestamp, update))
     }
 
+    /// Whether chunks or tool call updates are waiting to be written
+    fn has_pending(&self) -> bool {
+        self.chunk_type != AccumulatedChunkType::Empty || !self.tool_call_updates.is_empty()
+    }
+
     /// Flush all tool call updates
     /// Returns a vector of (timestamp, update) pairs
     fn flush_tool_call_updates(&mut self) -> Vec<(String, SessionUpdate)> {
@@ -338,12 +350,108 @@ fn extract_text_from_content_chunk(chunk: &ContentChunk) -> String {
     }
 }
 
+/// How long archived session data is kept before compaction deletes it
+pub const ARCHIVE_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Longest time an appended record may stay only in the OS cache before fsync
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Limits enforced by [`PersistenceService::compact`]; `None` means unlimited
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RetentionPolicy {
+    /// Sessions kept, most recently written first
+    pub max_sessions: Option<usize>,
+    /// Sessions not written for longer than this are removed
+    pub max_age: Option<Duration>,
+    /// Newest messages kept per session
+    pub max_messages_per_session: Option<usize>,
+    /// Newest bytes kept per session file
+    pub max_bytes_per_session: Option<u64>,
+    /// Move removed data to the archive folder, deleted after [`ARCHIVE_RETENTION`]
+    pub archive: bool,
+}
+
+impl RetentionPolicy {
+    /// Whether the policy never removes anything
+    pub fn is_unlimited(&self) -> bool {
+        self.max_sessions.is_none()
+            && self.max_age.is_none()
+            && self.max_messages_per_session.is_none()
+            && self.max_bytes_per_session.is_none()
+    }
+}
+
+/// Disk space used by persisted sessions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StorageUsage {
+    pub session_count: usize,
+    /// Size of the session files
+    pub session_bytes: u64,
+    /// Size of the archive folder
+    pub archive_bytes: u64,
+    /// Size of the search index
+    pub index_bytes: u64,
+}
+
+impl StorageUsage {
+    pub fn total_bytes(&self) -> u64 {
+        self.session_bytes + self.archive_bytes + self.index_bytes
+    }
+}
+
+/// What a compaction pass removed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactionReport {
+    /// Sessions removed for exceeding the session count or age limit
+    pub sessions_removed: usize,
+    /// Sessions whose oldest messages were trimmed
+    pub sessions_trimmed: usize,
+    /// Sessions skipped because they are still being written
+    pub sessions_skipped: usize,
+    /// Archived files deleted after [`ARCHIVE_RETENTION`]
+    pub archives_expired: usize,
+    /// Bytes taken out of session files, whether archived or deleted
+    pub bytes_removed: u64,
+}
+
 /// Message persistence service
 pub struct PersistenceService {
     /// Base directory for session files
     base_dir: PathBuf,
     /// Thread-safe storage for chunk accumulators per session
     accumulators: Arc<Mutex<HashMap<String, ChunkAccumulator>>>,
+    /// Full-text index of the persisted user and agent messages
+    search_index: Arc<SessionSearchIndex>,
+    /// Held while a session file is appended to or rewritten, so compaction never loses a write
+    file_lock: Arc<Mutex<SyncState>>,
+}
+
+/// Session files appended to since their last fsync
+#[derive(Debug, Default)]
+struct SyncState {
+    unsynced: HashSet<PathBuf>,
+    last_sync: Option<Instant>,
+}
+
+impl SyncState {
+    /// Fsync every pending file when forced or when [`SYNC_INTERVAL`] has passed
+    fn sync(&mut self, force: bool) {
+        let due = self
+            .last_sync
+            .is_none_or(|last_sync| last_sync.elapsed() >= SYNC_INTERVAL);
+        if !(force || due) || self.unsynced.is_empty() {
+            return;
+        }
+        for path in self.unsynced.drain() {
+            // Files removed by compaction or deletion have nothing left to sync
+            if let Ok(file) = std::fs::File::open(&path)
+                && let Err(e) = file.sync_data()
+            {
+                log::warn!("Failed to sync session file {}: {}", path.display(), e);
+            }
+        }
+        self.last_sync = Some(Instant::now());
+    }
 }
 
 impl PersistenceService {
@@ -353,8 +461,10 @@ impl PersistenceService {
     /// * `base_dir` - Base directory for storing session files (e.g., "target/sessions")
     pub fn new(base_dir: PathBuf) -> Self {
         Self {
+            search_index: Arc::new(SessionSearchIndex::new(base_dir.clone())),
             base_dir,
             accumulators: Arc::new(Mutex::new(HashMap::new())),
+            file_lock: Arc::new(Mutex::new(SyncState::default())),
         }
     }
 
@@ -482,34 +592,35 @@ impl PersistenceService {
     ) -> Result<()> {
         let file_path = self.session_file_path(session_id);
         let base_dir = self.base_dir.clone();
+        let search_index = self.search_index.clone();
+        let file_lock = self.file_lock.clone();
+        let session_id = session_id.to_string();
         let message = PersistedMessage::with_timestamp(timestamp, update);
 
         smol::unblock(move || {
+            let mut sync_state = file_lock.lock().unwrap();
+
             // Ensure directory exists
             if !base_dir.exists() {
                 std::fs::create_dir_all(&base_dir).context("Failed to create base directory")?;
             }
 
             // Serialize to JSON and append newline
-            let json = serde_json::to_string(&message).context("Failed to serialize message")?;
-
-            // Open file in append mode
-            use std::fs::OpenOptions;
-            use std::io::Write;
-
-            let mut file = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&file_path)
-                .context("Failed to open session file")?;
-
-            // Write JSON line
-            write!(file, "{}\n", json).context("Failed to write message")?;
+            let mut record = serde_json::to_vec(&message).context("Failed to serialize message")?;
+            record.push(b'\n');
+            append_record(&file_path, &record)?;
+            sync_state.unsynced.insert(file_path.clone());
+            sync_state.sync(false);
 
             log::debug!(
                 "Wrote merged message to session file: {}",
                 file_path.display()
             );
+
+            // A stale index only affects search, never the session history
+            if let Err(e) = search_index.append(&session_id, &message.timestamp, &message.update) {
+                log::warn!("Failed to index message for session {}: {}", session_id, e);
+            }
             Ok(())
         })
         .await
@@ -568,6 +679,10 @@ impl PersistenceService {
             log::debug!("No accumulated data to flush for session: {}", session_id);
         }
 
+        // A finished turn is durable even if the machine goes down right after
+        let file_lock = self.file_lock.clone();
+        smol::unblock(move || file_lock.lock().unwrap().sync(true)).await;
+
         Ok(())
     }
 
@@ -586,28 +701,7 @@ impl PersistenceService {
                 return Ok(Vec::new());
             }
 
-            use std::fs::File;
-            use std::io::{BufRead, BufReader};
-
-            let file = File::open(&file_path).context("Failed to open session file")?;
-
-            let reader = BufReader::new(file);
-            let mut messages = Vec::new();
-
-            for line in reader.lines() {
-                let line = line?;
-                if line.trim().is_empty() {
-                    continue;
-                }
-
-                match serde_json::from_str::<PersistedMessage>(&line) {
-                    Ok(message) => messages.push(message),
-                    Err(e) => {
-                        log::warn!("Failed to parse line in session file: {}", e);
-                        // Continue reading other lines
-                    }
-                }
-            }
+            let messages = read_session_log(&file_path)?;
 
             log::info!(
                 "Loaded {} messages from session file: {}",
@@ -645,6 +739,61 @@ impl PersistenceService {
         .await
     }
 
+    /// Search the text of all persisted sessions, best matches first
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<SessionSearchHit>> {
+        let search_index = self.search_index.clone();
+        let query = query.to_string();
+        smol::unblock(move || search_index.search(&query, limit)).await
+    }
+
+    /// Disk space used by session files, the archive and the search index
+    pub async fn storage_usage(&self) -> Result<StorageUsage> {
+        let base_dir = self.base_dir.clone();
+
+        smol::unblock(move || {
+            let sessions = session_files(&base_dir)?;
+            Ok(StorageUsage {
+                session_count: sessions.len(),
+                session_bytes: sessions.iter().map(|file| file.len).sum(),
+                archive_bytes: dir_size(&base_dir.join("archive")),
+                index_bytes: dir_size(&base_dir.join("index")),
+            })
+        })
+        .await
+    }
+
+    /// Remove or trim session files exceeding the retention policy, oldest data first
+    ///
+    /// Sessions in `in_progress` and sessions with unflushed updates are left
+    /// untouched. Files are rewritten through a temporary file while holding the
+    /// write lock, so a concurrent write is never lost and a crash never leaves
+    /// a truncated log. Expired archives are deleted on every pass.
+    pub async fn compact(
+        &self,
+        policy: RetentionPolicy,
+        in_progress: HashSet<String>,
+    ) -> Result<CompactionReport> {
+        let mut protected = in_progress;
+        protected.extend(
+            self.accumulators
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, accumulator)| accumulator.has_pending())
+                .map(|(session_id, _)| session_id.clone()),
+        );
+
+        let base_dir = self.base_dir.clone();
+        let file_lock = self.file_lock.clone();
+
+        let report =
+            smol::unblock(move || compact_sessions(&base_dir, &policy, &protected, &file_lock))
+                .await?;
+
+        log::info!("Session storage compaction finished: {:?}", report);
+        Ok(report)
+    }
+
     /// List all available sessions
     pub async fn list_workspace_sessions(&self) -> Result<Vec<String>> {
         let base_dir = self.base_dir.clone();
@@ -677,3 +826,390 @@ impl PersistenceService {
         .await
     }
 }
+
+/// A session file found in the sessions directory
+struct SessionFile {
+    session_id: String,
+    path: PathBuf,
+    len: u64,
+    modified: SystemTime,
+}
+
+/// Session files directly in `base_dir`; subdirectories (archive, index) are skipped
+fn session_files(base_dir: &Path) -> Result<Vec<SessionFile>> {
+    if !base_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(base_dir).context("Failed to read sessions directory")? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+        let Some(session_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let metadata = std::fs::metadata(&path)?;
+        files.push(SessionFile {
+            session_id: session_id.to_string(),
+            len: metadata.len(),
+            modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            path,
+        });
+    }
+    Ok(files)
+}
+
+/// Total size of the files in a directory (not recursive)
+fn dir_size(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.metadata().ok())
+                .filter(|metadata| metadata.is_file())
+                .map(|metadata| metadata.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn compact_sessions(
+    base_dir: &Path,
+    policy: &RetentionPolicy,
+    protected: &HashSet<String>,
+    file_lock: &Mutex<SyncState>,
+) -> Result<CompactionReport> {
+    let mut report = CompactionReport::default();
+    let archive_dir = base_dir.join("archive");
+    let now = SystemTime::now();
+    let age = |modified: SystemTime| now.duration_since(modified).unwrap_or_default();
+
+    let mut files = session_files(base_dir)?;
+    files.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+    for (rank, file) in files.iter().enumerate() {
+        if protected.contains(&file.session_id) {
+            report.sessions_skipped += 1;
+            continue;
+        }
+
+        let mut sync_state = file_lock.lock().unwrap();
+        // Pending appends must be on disk before the file is replaced or archived
+        sync_state.sync(true);
+        let too_many = policy.max_sessions.is_some_and(|max| rank >= max);
+        let too_old = policy.max_age.is_some_and(|max| age(file.modified) > max);
+        if too_many || too_old {
+            if policy.archive {
+                let content = std::fs::read(&file.path).context("Failed to read session file")?;
+                append_to_archive(&archive_dir, &file.session_id, &content)?;
+            }
+            std::fs::remove_file(&file.path).context("Failed to delete session file")?;
+            report.sessions_removed += 1;
+            report.bytes_removed += file.len;
+            continue;
+        }
+
+        if policy.max_messages_per_session.is_none() && policy.max_bytes_per_session.is_none() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&file.path).context("Failed to read session file")?;
+        let lines: Vec<&str> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let keep_from = trim_start(&lines, policy);
+        if keep_from == 0 {
+            continue;
+        }
+
+        if policy.archive {
+            let trimmed = lines[..keep_from]
+                .iter()
+                .map(|line| format!("{}\n", line))
+                .collect::<String>();
+            append_to_archive(&archive_dir, &file.session_id, trimmed.as_bytes())?;
+        }
+        let kept = lines[keep_from..]
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect::<String>();
+        let tmp_path = file.path.with_extension("jsonl.tmp");
+        write_synced(&tmp_path, kept.as_bytes()).context("Failed to write trimmed session file")?;
+        std::fs::rename(&tmp_path, &file.path).context("Failed to replace session file")?;
+        report.sessions_trimmed += 1;
+        report.bytes_removed += file.len.saturating_sub(kept.len() as u64);
+    }
+
+    // Archived data is only a grace period before deletion
+    if let Ok(entries) = std::fs::read_dir(&archive_dir) {
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let expired = metadata
+                .modified()
+                .is_ok_and(|modified| age(modified) > ARCHIVE_RETENTION);
+            if metadata.is_file() && expired && std::fs::remove_file(entry.path()).is_ok() {
+                report.archives_expired += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Index of the first line kept so the newest lines fit the per-session limits
+///
+/// The newest message is always kept, even when it alone exceeds the byte limit.
+fn trim_start(lines: &[&str], policy: &RetentionPolicy) -> usize {
+    let mut keep_from = policy
+        .max_messages_per_session
+        .map_or(0, |max| lines.len().saturating_sub(max.max(1)));
+    if let Some(max_bytes) = policy.max_bytes_per_session {
+        let mut bytes = 0u64;
+        let mut first = lines.len();
+        while first > keep_from {
+            bytes += lines[first - 1].len() as u64 + 1;
+            if bytes > max_bytes && first < lines.len() {
+                break;
+            }
+            first -= 1;
+        }
+        keep_from = first;
+    }
+    keep_from
+}
+
+/// Append one newline-terminated record to a session file
+///
+/// A record cut short by a crash during an earlier append is dropped first,
+/// so it cannot merge with the new record into an unreadable line.
+fn append_record(path: &Path, record: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .context("Failed to open session file")?;
+    truncate_partial_record(&mut file, path)?;
+    // A single write keeps the record contiguous in append mode
+    file.write_all(record).context("Failed to write message")
+}
+
+/// Cut a session file back to its last complete record
+fn truncate_partial_record(file: &mut std::fs::File, path: &Path) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let len = file
+        .metadata()
+        .context("Failed to read session file")?
+        .len();
+    if len == 0 {
+        return Ok(());
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::Start(len - 1))?;
+    file.read_exact(&mut last)?;
+    if last[0] == b'\n' {
+        return Ok(());
+    }
+
+    let mut content = Vec::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_end(&mut content)?;
+    let keep = content
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |pos| pos + 1);
+    file.set_len(keep as u64)
+        .context("Failed to truncate partial record")?;
+    log::warn!(
+        "Dropped {} bytes of an incomplete record at the end of {}",
+        len - keep as u64,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Read every complete record of a session log
+///
+/// Unparsable lines are skipped, and so is a trailing record without its
+/// newline, which is what a crash in the middle of an append leaves behind.
+pub(crate) fn read_session_log(path: &Path) -> Result<Vec<PersistedMessage>> {
+    let content = std::fs::read(path).context("Failed to read session file")?;
+    let mut records: Vec<&[u8]> = content.split(|byte| *byte == b'\n').collect();
+    // The last piece is empty when the file ends with a newline
+    if let Some(partial) = records.pop()
+        && !partial.iter().all(u8::is_ascii_whitespace)
+    {
+        log::warn!(
+            "Skipping incomplete last record ({} bytes) in session file: {}",
+            partial.len(),
+            path.display()
+        );
+    }
+
+    let mut messages = Vec::new();
+    for record in records {
+        if record.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        match serde_json::from_slice::<PersistedMessage>(record) {
+            Ok(message) => messages.push(message),
+            Err(e) => {
+                log::warn!("Failed to parse line in session file: {}", e);
+                // Continue reading other lines
+            }
+        }
+    }
+    Ok(messages)
+}
+
+/// Write a file and flush it to disk, for replacing another file by rename
+fn write_synced(path: &Path, content: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Append removed session data to the session's file in the archive folder
+fn append_to_archive(archive_dir: &Path, session_id: &str, content: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    std::fs::create_dir_all(archive_dir).context("Failed to create archive directory")?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(archive_dir.join(format!("{}.jsonl", session_id)))
+        .context("Failed to open archive file")?;
+    file.write_all(content)
+        .context("Failed to write archive file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent_text(text: &str) -> SessionUpdate {
+        SessionUpdate::AgentMessageChunk(ContentChunk::new(ContentBlock::Text(TextContent::new(
+            text,
+        ))))
+    }
+
+    #[test]
+    fn test_truncated_last_record_still_loads_the_rest() {
+        let dir = std::env::temp_dir().join(format!("agentx-journal-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let service = PersistenceService::new(dir.clone());
+
+        smol::block_on(async {
+            for text in ["first", "second"] {
+                service
+                    .write_with_current_timestamp("s1", agent_text(text))
+                    .await
+                    .unwrap();
+            }
+
+            // Simulate a crash halfway through appending the second record
+            let path = service.session_file_path("s1");
+            let len = std::fs::metadata(&path).unwrap().len();
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_len(len - 10)
+                .unwrap();
+            assert_eq!(service.load_messages("s1").await.unwrap().len(), 1);
+
+            // The next append replaces the torn record instead of merging with it
+            service
+                .write_with_current_timestamp("s1", agent_text("third"))
+                .await
+                .unwrap();
+            let messages = service.load_messages("s1").await.unwrap();
+            assert_eq!(messages.len(), 2);
+            assert!(matches!(
+                &messages[1].update,
+                SessionUpdate::AgentMessageChunk(ContentChunk {
+                    content: ContentBlock::Text(text),
+                    ..
+                }) if text.text == "third"
+            ));
+        });
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_trim_start_keeps_newest_messages_within_limits() {
+        let lines = ["aaaa", "bbbb", "cccc", "dddd"];
+        let by_count = RetentionPolicy {
+            max_messages_per_session: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(trim_start(&lines, &by_count), 1);
+
+        // Each line takes 5 bytes with its newline
+        let by_bytes = RetentionPolicy {
+            max_bytes_per_session: Some(11),
+            ..Default::default()
+        };
+        assert_eq!(trim_start(&lines, &by_bytes), 2);
+
+        let tiny = RetentionPolicy {
+            max_bytes_per_session: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(trim_start(&lines, &tiny), 3);
+        assert_eq!(trim_start(&lines, &RetentionPolicy::default()), 0);
+    }
+
+    #[test]
+    fn test_compact_skips_protected_sessions_and_archives_removed_ones() {
+        let dir = std::env::temp_dir().join(format!("agentx-compact-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("old.jsonl"), "1\n2\n3\n").unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        std::fs::write(dir.join("running.jsonl"), "1\n2\n3\n").unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        std::fs::write(dir.join("new.jsonl"), "1\n2\n3\n").unwrap();
+
+        let policy = RetentionPolicy {
+            max_sessions: Some(1),
+            max_messages_per_session: Some(2),
+            archive: true,
+            ..Default::default()
+        };
+        let protected = HashSet::from(["running".to_string()]);
+        let report = compact_sessions(&dir, &policy, &protected, &Mutex::default()).unwrap();
+
+        assert_eq!(report.sessions_removed, 1);
+        assert_eq!(report.sessions_trimmed, 1);
+        assert_eq!(report.sessions_skipped, 1);
+        assert!(!dir.join("old.jsonl").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("new.jsonl")).unwrap(),
+            "2\n3\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("running.jsonl")).unwrap(),
+            "1\n2\n3\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("archive").join("old.jsonl")).unwrap(),
+            "1\n2\n3\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("archive").join("new.jsonl")).unwrap(),
+            "1\n"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
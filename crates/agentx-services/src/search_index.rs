@@ -0,0 +1,383 @@
+//! Search Index - Full-text search over persisted session messages
+//!
+//! User and agent messages are appended to a JSONL index file as they are
+//! persisted, so a global search scans one bounded file instead of every
+//! session history. The file is rebuilt from the session files when missing
+//! and compacted to the newest [`MAX_INDEX_ENTRIES`] messages when it grows.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use agent_client_protocol::{ContentBlock, SessionUpdate};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::persistence_service::read_session_log;
+
+/// Messages kept in the index; older messages drop out of search results
+pub const MAX_INDEX_ENTRIES: usize = 10_000;
+/// Appends allowed past the limit before the file is compacted
+const COMPACT_SLACK: usize = 1_000;
+/// Longest message text stored per entry, in characters
+const MAX_ENTRY_CHARS: usize = 2_000;
+/// Hits reported per session, so one long session does not fill the results
+const MAX_HITS_PER_SESSION: usize = 3;
+/// Characters of context shown before the match in a snippet
+const SNIPPET_BEFORE_CHARS: usize = 40;
+/// Characters of context shown after the start of the match in a snippet
+const SNIPPET_AFTER_CHARS: usize = 100;
+
+/// Who wrote an indexed message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexedRole {
+    User,
+    Agent,
+}
+
+/// One message line of the index file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    session_id: String,
+    timestamp: String,
+    role: IndexedRole,
+    text: String,
+}
+
+/// A message matching a search query
+#[derive(Debug, Clone)]
+pub struct SessionSearchHit {
+    pub session_id: String,
+    /// Timestamp of the matching message (ISO 8601)
+    pub timestamp: String,
+    pub role: IndexedRole,
+    /// Single-line excerpt around the first matching term
+    pub snippet: String,
+    /// Higher is better; counts term occurrences with a bonus for the exact phrase
+    pub score: usize,
+    /// Zero-based position of this message among the session's matching messages
+    pub occurrence: usize,
+}
+
+/// Bounded on-disk index of the text of persisted messages
+pub struct SessionSearchIndex {
+    sessions_dir: PathBuf,
+    index_path: PathBuf,
+    /// Lines in the index file; None until the file has been opened once
+    entry_count: Mutex<Option<usize>>,
+}
+
+impl SessionSearchIndex {
+    /// Create an index for the session files in `sessions_dir`
+    ///
+    /// The index lives in an `index` subdirectory so it is never listed as a session.
+    pub fn new(sessions_dir: PathBuf) -> Self {
+        let index_path = sessions_dir.join("index").join("messages.jsonl");
+        Self {
+            sessions_dir,
+            index_path,
+            entry_count: Mutex::new(None),
+        }
+    }
+
+    /// Index a message that was just written to its session file
+    ///
+    /// Blocking; call from a background thread.
+    pub fn append(&self, session_id: &str, timestamp: &str, update: &SessionUpdate) -> Result<()> {
+        let Some((role, text)) = indexable_text(update) else {
+            return Ok(());
+        };
+
+        let mut entry_count = self.entry_count.lock().unwrap();
+        if entry_count.is_none() && !self.index_path.exists() {
+            // First use: the rebuild already picks up the message from its session file
+            *entry_count = Some(self.rebuild()?);
+            return Ok(());
+        }
+        let count = self.ensure_counted(&mut entry_count)?;
+
+        let entry = IndexEntry {
+            session_id: session_id.to_string(),
+            timestamp: timestamp.to_string(),
+            role,
+            text,
+        };
+        let json = serde_json::to_string(&entry).context("Failed to serialize index entry")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.index_path)
+            .context("Failed to open search index")?;
+        writeln!(file, "{}", json).context("Failed to write search index")?;
+
+        let count = count + 1;
+        *entry_count = Some(if count > MAX_INDEX_ENTRIES + COMPACT_SLACK {
+            self.compact()?
+        } else {
+            count
+        });
+        Ok(())
+    }
+
+    /// Find messages containing every term of `query`, best matches first
+    ///
+    /// Blocking; call from a background thread.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SessionSearchHit>> {
+        let phrase = fold_case(query.trim());
+        let terms: Vec<&str> = phrase.split_whitespace().collect();
+        if terms.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let entries = {
+            let mut entry_count = self.entry_count.lock().unwrap();
+            if entry_count.is_none() && !self.index_path.exists() {
+                *entry_count = Some(self.rebuild()?);
+            }
+            read_entries(&self.index_path)?
+        };
+
+        let mut hits = Vec::new();
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        let mut session_exists: HashMap<String, bool> = HashMap::new();
+        for entry in entries {
+            let folded = fold_case(&entry.text);
+            if !terms.iter().all(|term| folded.contains(term)) {
+                continue;
+            }
+            // Deleted sessions stay in the file until the next compaction
+            let exists = *session_exists
+                .entry(entry.session_id.clone())
+                .or_insert_with(|| self.session_file(&entry.session_id).exists());
+            if !exists {
+                continue;
+            }
+
+            let occurrence = occurrences.entry(entry.session_id.clone()).or_insert(0);
+            let mut score: usize = terms.iter().map(|term| folded.matches(term).count()).sum();
+            if terms.len() > 1 && folded.contains(phrase.as_str()) {
+                score += 5 * terms.len();
+            }
+            hits.push(SessionSearchHit {
+                snippet: snippet(&entry.text, &folded, terms[0]),
+                session_id: entry.session_id,
+                timestamp: entry.timestamp,
+                role: entry.role,
+                score,
+                occurrence: *occurrence,
+            });
+            *occurrence += 1;
+        }
+
+        // Best score first, newer messages first among equals
+        hits.reverse();
+        hits.sort_by(|a, b| b.score.cmp(&a.score));
+
+        let mut per_session: HashMap<String, usize> = HashMap::new();
+        hits.retain(|hit| {
+            let count = per_session.entry(hit.session_id.clone()).or_insert(0);
+            *count += 1;
+            *count <= MAX_HITS_PER_SESSION
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    fn session_file(&self, session_id: &str) -> PathBuf {
+        self.sessions_dir.join(format!("{}.jsonl", session_id))
+    }
+
+    fn ensure_counted(&self, entry_count: &mut Option<usize>) -> Result<usize> {
+        if let Some(count) = *entry_count {
+            return Ok(count);
+        }
+        let count = if self.index_path.exists() {
+            let file = File::open(&self.index_path).context("Failed to open search index")?;
+            BufReader::new(file).lines().count()
+        } else {
+            0
+        };
+        *entry_count = Some(count);
+        Ok(count)
+    }
+
+    /// Recreate the index from all session files, returning the number of entries
+    fn rebuild(&self) -> Result<usize> {
+        let mut entries = Vec::new();
+        if self.sessions_dir.exists() {
+            for dir_entry in std::fs::read_dir(&self.sessions_dir)
+                .context("Failed to read sessions directory")?
+            {
+                let path = dir_entry?.path();
+                if path.extension().is_none_or(|ext| ext != "jsonl") {
+                    continue;
+                }
+                let Some(session_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                for message in read_session_log(&path)? {
+                    if let Some((role, text)) = indexable_text(&message.update) {
+                        entries.push(IndexEntry {
+                            session_id: session_id.to_string(),
+                            timestamp: message.timestamp,
+                            role,
+                            text,
+                        });
+                    }
+                }
+            }
+        }
+
+        // RFC 3339 timestamps in UTC sort chronologically as strings
+        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        let skip = entries.len().saturating_sub(MAX_INDEX_ENTRIES);
+        let count = write_entries(&self.index_path, &entries[skip..])?;
+        log::info!("Rebuilt session search index with {} messages", count);
+        Ok(count)
+    }
+
+    /// Keep only the newest entries of live sessions, returning the number kept
+    fn compact(&self) -> Result<usize> {
+        let mut entries = read_entries(&self.index_path)?;
+        let live: HashSet<String> = entries
+            .iter()
+            .map(|entry| entry.session_id.clone())
+            .filter(|session_id| self.session_file(session_id).exists())
+            .collect();
+        entries.retain(|entry| live.contains(&entry.session_id));
+        let skip = entries.len().saturating_sub(MAX_INDEX_ENTRIES);
+        let count = write_entries(&self.index_path, &entries[skip..])?;
+        log::debug!("Compacted session search index to {} messages", count);
+        Ok(count)
+    }
+}
+
+/// Role and text of a user or agent message, truncated for the index
+fn indexable_text(update: &SessionUpdate) -> Option<(IndexedRole, String)> {
+    let (role, chunk) = match update {
+        SessionUpdate::UserMessageChunk(chunk) => (IndexedRole::User, chunk),
+        SessionUpdate::AgentMessageChunk(chunk) => (IndexedRole::Agent, chunk),
+        _ => return None,
+    };
+    let ContentBlock::Text(text) = &chunk.content else {
+        return None;
+    };
+    let text = text.text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some((role, text.chars().take(MAX_ENTRY_CHARS).collect()))
+}
+
+/// Lowercase `text` one character at a time, keeping character positions aligned
+fn fold_case(text: &str) -> String {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// One-line excerpt of `text` around the first occurrence of `term` in its folded form
+fn snippet(text: &str, folded: &str, term: &str) -> String {
+    let match_start = folded
+        .find(term)
+        .map_or(0, |byte| folded[..byte].chars().count());
+    let start = match_start.saturating_sub(SNIPPET_BEFORE_CHARS);
+    let total = text.chars().count();
+    let end = (match_start + SNIPPET_AFTER_CHARS).min(total);
+
+    let excerpt: String = text.chars().skip(start).take(end - start).collect();
+    let mut snippet = excerpt.split_whitespace().collect::<Vec<_>>().join(" ");
+    if start > 0 {
+        snippet.insert_str(0, "…");
+    }
+    if end < total {
+        snippet.push('…');
+    }
+    snippet
+}
+
+fn read_entries(path: &Path) -> Result<Vec<IndexEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = File::open(path).context("Failed to open search index")?;
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        match serde_json::from_str::<IndexEntry>(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => log::warn!("Skipping unreadable search index line: {}", e),
+        }
+    }
+    Ok(entries)
+}
+
+/// Replace the index file with `entries` through a temporary file
+fn write_entries(path: &Path, entries: &[IndexEntry]) -> Result<usize> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create search index directory")?;
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    let mut file = File::create(&tmp_path).context("Failed to create search index")?;
+    for entry in entries {
+        let json = serde_json::to_string(entry).context("Failed to serialize index entry")?;
+        writeln!(file, "{}", json).context("Failed to write search index")?;
+    }
+    file.sync_all().ok();
+    std::fs::rename(&tmp_path, path).context("Failed to replace search index")?;
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agent_client_protocol::{ContentChunk, TextContent};
+
+    fn agent_text(text: &str) -> SessionUpdate {
+        SessionUpdate::AgentMessageChunk(ContentChunk::new(ContentBlock::Text(TextContent::new(
+            text,
+        ))))
+    }
+
+    #[test]
+    fn test_search_ranks_hits_and_counts_occurrences() {
+        let dir = std::env::temp_dir().join(format!("agentx-search-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("s1.jsonl"), "").unwrap();
+        std::fs::write(dir.join("s2.jsonl"), "").unwrap();
+        // An existing index is appended to instead of rebuilt from the session files
+        std::fs::create_dir_all(dir.join("index")).unwrap();
+        std::fs::write(dir.join("index").join("messages.jsonl"), "").unwrap();
+
+        let index = SessionSearchIndex::new(dir.clone());
+        let messages = [
+            ("s1", "We should plan the database migration soon"),
+            ("s1", "Tool output without the keyword"),
+            ("s1", "The Migration finished; migration logs are attached"),
+            ("s2", "Nothing relevant here"),
+            ("deleted", "A migration in a session that no longer exists"),
+        ];
+        for (i, (session_id, text)) in messages.iter().enumerate() {
+            let timestamp = format!("2026-01-01T00:00:0{}Z", i);
+            index
+                .append(session_id, &timestamp, &agent_text(text))
+                .unwrap();
+        }
+
+        let hits = index.search("MIGRATION", 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        // Two occurrences outrank one
+        assert_eq!(hits[0].session_id, "s1");
+        assert_eq!(hits[0].occurrence, 1);
+        assert!(hits[0].snippet.starts_with("The Migration finished"));
+        assert_eq!(hits[1].occurrence, 0);
+
+        assert!(index.search("migration banana", 10).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,11 +1,23 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
 
 use agentx_event_bus::{EventHub, WorkspaceUpdateEvent};
 use agentx_types::SessionStatus;
-use agentx_types::schemas::workspace::{Workspace, WorkspaceConfig, WorkspaceTask};
+use agentx_types::schemas::workspace::{
+    SessionImportOutcome, SessionTitleSource, Workspace, WorkspaceConfig, WorkspaceTask,
+};
+
+/// Minimum delay between two writes of the workspace config.
+///
+/// Mutations only mark the config dirty; a background task flushes it once
+/// the delay has elapsed, so bursts of updates result in a single write.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
 
 /// Service for managing workspaces and tasks
 ///
@@ -20,6 +32,12 @@ pub struct WorkspaceService {
     config: Arc<RwLock<WorkspaceConfig>>,
     config_path: PathBuf,
     event_hub: Option<EventHub>,
+    /// Set when the in-memory config has changes not yet written to disk
+    dirty: Arc<AtomicBool>,
+    /// Set while a debounced flush is pending
+    save_scheduled: Arc<AtomicBool>,
+    /// Serializes writes so concurrent flushes never interleave
+    save_lock: Arc<Mutex<()>>,
 }
 
 impl WorkspaceService {
@@ -31,6 +49,9 @@ impl WorkspaceService {
             config: Arc::new(RwLock::new(config)),
             config_path,
             event_hub: None,
+            dirty: Arc::new(AtomicBool::new(false)),
+            save_scheduled: Arc::new(AtomicBool::new(false)),
+            save_lock: Arc::new(Mutex::new(())),
         }
     }
 
@@ -48,11 +69,33 @@ impl WorkspaceService {
     }
 
     /// Load workspace configuration from disk
+    ///
+    /// Falls back to the rolling backup if the primary file is unreadable.
     fn load_config(path: &PathBuf) -> Result<WorkspaceConfig> {
         if !path.exists() {
             return Ok(WorkspaceConfig::default());
         }
 
+        match Self::read_config_file(path) {
+            Ok(config) => Ok(config),
+            Err(e) => {
+                let backup_path = Self::backup_path(path);
+                if !backup_path.exists() {
+                    return Err(e);
+                }
+
+                log::warn!(
+                    "Failed to load workspace config {:?}: {:#}. Restoring from backup {:?}",
+                    path,
+                    e,
+                    backup_path
+                );
+                Self::read_config_file(&backup_path)
+            }
+        }
+    }
+
+    fn read_config_file(path: &Path) -> Result<WorkspaceConfig> {
         let content = std::fs::read_to_string(path).context("Failed to read workspace config")?;
 
         let config: WorkspaceConfig =
@@ -61,13 +104,83 @@ impl WorkspaceService {
         Ok(config)
     }
 
-    /// Save workspace configuration to disk
-    async fn save_config(&self) -> Result<()> {
-        let config = self.config.read().await;
-        let content = serde_json::to_string_pretty(&*config)
-            .context("Failed to serialize workspace config")?;
+    fn backup_path(path: &Path) -> PathBuf {
+        path.with_extension("json.backup")
+    }
+
+    /// Mark the config dirty and schedule a debounced flush
+    fn schedule_save(&self) {
+        self.dirty.store(true, Ordering::SeqCst);
+
+        if self.save_scheduled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let service = self.clone();
+        smol::spawn(async move {
+            smol::Timer::after(SAVE_DEBOUNCE).await;
+            service.save_scheduled.store(false, Ordering::SeqCst);
+            if let Err(e) = service.flush().await {
+                log::error!("Failed to save workspace config: {:#}", e);
+            }
+        })
+        .detach();
+    }
+
+    /// Write pending changes to disk immediately
+    ///
+    /// Call this before reading the config file directly (e.g. exports) and
+    /// on shutdown. Does nothing if there are no unsaved changes.
+    pub async fn flush(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let content = {
+            let config = self.config.read().await;
+            serde_json::to_string_pretty(&*config)
+        };
+
+        let result = content
+            .context("Failed to serialize workspace config")
+            .and_then(|content| Self::write_config_atomic(&self.config_path, &content));
+
+        if result.is_err() {
+            // Keep the changes pending so the next flush retries
+            self.dirty.store(true, Ordering::SeqCst);
+        }
+
+        result
+    }
 
-        std::fs::write(&self.config_path, content).context("Failed to write workspace config")?;
+    /// Write the config via temp file + rename so a crash mid-write never
+    /// leaves a truncated file behind. The previous file is kept as a backup.
+    fn write_config_atomic(path: &Path, content: &str) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .context("Failed to create workspace config directory")?;
+        }
+
+        let temp_path = path.with_extension("json.tmp");
+        {
+            let mut file = std::fs::File::create(&temp_path)
+                .context("Failed to create workspace config temp file")?;
+            file.write_all(content.as_bytes())
+                .context("Failed to write workspace config to temp file")?;
+            file.sync_all()
+                .context("Failed to sync workspace config temp file")?;
+        }
+
+        // Keep a single rolling backup of the last good config
+        if path.exists() {
+            if let Err(e) = std::fs::copy(path, Self::backup_path(path)) {
+                log::warn!("Failed to create workspace config backup: {}", e);
+            }
+        }
+
+        std::fs::rename(&temp_path, path).context("Failed to replace workspace config")?;
 
         Ok(())
     }
@@ -102,7 +215,7 @@ impl WorkspaceService {
             }
         }
 
-        self.save_config().await?;
+        self.schedule_save();
 
         // Publish WorkspaceAdded event
         self.publish_event(WorkspaceUpdateEvent::WorkspaceAdded {
@@ -117,22 +230,70 @@ impl WorkspaceService {
         Ok(workspace_clone)
     }
 
-    /// Remove a workspace by ID
+    /// Remove a workspace that has no tasks
+    ///
+    /// Fails if the workspace still has tasks; use [`Self::remove_workspace_cascade`]
+    /// to remove them along with it.
     pub async fn remove_workspace(&self, workspace_id: &str) -> Result<()> {
         {
+            let config = self.config.read().await;
+            let task_count = config.tasks_for_workspace(workspace_id).len();
+            if task_count > 0 {
+                anyhow::bail!(
+                    "Workspace {} still has {} task(s)",
+                    workspace_id,
+                    task_count
+                );
+            }
+        }
+
+        self.remove_workspace_cascade(workspace_id).await?;
+        Ok(())
+    }
+
+    /// Remove a workspace together with its tasks
+    ///
+    /// Publishes `TaskRemoved` for each task, then `WorkspaceRemoved`.
+    /// Returns the removed tasks.
+    pub async fn remove_workspace_cascade(&self, workspace_id: &str) -> Result<Vec<WorkspaceTask>> {
+        let removed_tasks = {
             let mut config = self.config.write().await;
+            if config.get_workspace(workspace_id).is_none() {
+                anyhow::bail!("Workspace not found: {}", workspace_id);
+            }
+
+            let removed_tasks: Vec<WorkspaceTask> = config
+                .tasks_for_workspace(workspace_id)
+                .into_iter()
+                .cloned()
+                .collect();
             config.remove_workspace(workspace_id);
 
             // Clear active workspace if it was removed
             if config.active_workspace_id.as_ref() == Some(&workspace_id.to_string()) {
                 config.active_workspace_id = config.workspaces.first().map(|w| w.id.clone());
             }
-        }
+            removed_tasks
+        };
 
-        self.save_config().await?;
+        self.schedule_save();
 
-        log::info!("Removed workspace: {}", workspace_id);
-        Ok(())
+        for task in &removed_tasks {
+            self.publish_event(WorkspaceUpdateEvent::TaskRemoved {
+                workspace_id: workspace_id.to_string(),
+                task_id: task.id.clone(),
+            });
+        }
+        self.publish_event(WorkspaceUpdateEvent::WorkspaceRemoved {
+            workspace_id: workspace_id.to_string(),
+        });
+
+        log::info!(
+            "Removed workspace: {} ({} task(s))",
+            workspace_id,
+            removed_tasks.len()
+        );
+        Ok(removed_tasks)
     }
 
     /// List all workspaces
@@ -178,7 +339,7 @@ impl WorkspaceService {
             }
         }
 
-        self.save_config().await?;
+        self.schedule_save();
 
         log::info!("Set active workspace: {}", workspace_id);
         Ok(())
@@ -206,7 +367,7 @@ impl WorkspaceService {
             config.add_task(task);
         }
 
-        self.save_config().await?;
+        self.schedule_save();
 
         // Publish TaskCreated event
         self.publish_event(WorkspaceUpdateEvent::TaskCreated {
@@ -236,7 +397,11 @@ impl WorkspaceService {
             task.set_session(session_id);
         }
 
-        self.save_config().await?;
+        self.schedule_save();
+
+        self.publish_event(WorkspaceUpdateEvent::TaskUpdated {
+            task_id: task_id.to_string(),
+        });
 
         Ok(())
     }
@@ -253,7 +418,7 @@ impl WorkspaceService {
 
     /// Update task status
     pub async fn update_task_status(&self, task_id: &str, status: SessionStatus) -> Result<()> {
-        {
+        let changed = {
             let mut config = self.config.write().await;
 
             let task = config
@@ -262,26 +427,41 @@ impl WorkspaceService {
                 .find(|t| t.id == task_id)
                 .context("Task not found")?;
 
+            let changed = task.status != status;
             task.status = status;
-        }
+            changed
+        };
+
+        if changed {
+            self.schedule_save();
 
-        self.save_config().await?;
+            // Publish TaskUpdated event
+            self.publish_event(WorkspaceUpdateEvent::TaskUpdated {
+                task_id: task_id.to_string(),
+            });
+        }
 
         Ok(())
     }
 
     /// Update task's last message
     pub async fn update_task_message(&self, session_id: &str, message: String) -> Result<()> {
-        {
+        let updated = {
             let mut config = self.config.write().await;
 
-            if let Some(task) = config.find_task_by_session(session_id) {
-                task.update_last_message(message);
+            match config.find_task_by_session(session_id) {
+                Some(task) => {
+                    task.update_last_message(message);
+                    true
+                }
+                None => false,
             }
-        }
+        };
 
-        // Note: We don't save config for message updates to avoid excessive I/O
-        // Messages are transient and will be lost on restart
+        // Message previews change often; the debounce keeps this cheap
+        if updated {
+            self.schedule_save();
+        }
 
         Ok(())
     }
@@ -308,6 +488,172 @@ impl WorkspaceService {
         config.tasks.iter().find(|t| t.id == task_id).cloned()
     }
 
+    /// Archive or unarchive a session
+    ///
+    /// Archived sessions keep their history but are hidden from the active list.
+    pub async fn set_session_archived(&self, session_id: &str, archived: bool) -> Result<()> {
+        let changed = {
+            let mut config = self.config.write().await;
+            config.set_session_archived(session_id, archived)
+        };
+
+        if changed {
+            self.schedule_save();
+            log::info!(
+                "{} session: {}",
+                if archived { "Archived" } else { "Unarchived" },
+                session_id
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a session is archived
+    pub async fn is_session_archived(&self, session_id: &str) -> bool {
+        let config = self.config.read().await;
+        config.is_session_archived(session_id)
+    }
+
+    /// Get all archived session IDs
+    pub async fn archived_sessions(&self) -> BTreeSet<String> {
+        let config = self.config.read().await;
+        config.archived_sessions.clone()
+    }
+
+    /// Pin or unpin a session
+    pub async fn set_session_pinned(&self, session_id: &str, pinned: bool) -> Result<()> {
+        let changed = {
+            let mut config = self.config.write().await;
+            config.set_session_pinned(session_id, pinned)
+        };
+
+        if changed {
+            self.schedule_save();
+        }
+
+        Ok(())
+    }
+
+    /// Get all pinned session IDs
+    pub async fn pinned_sessions(&self) -> BTreeSet<String> {
+        let config = self.config.read().await;
+        config.pinned_sessions.clone()
+    }
+
+    /// Record the outcomes of an agent session import run
+    pub async fn record_session_imports(
+        &self,
+        agent_name: &str,
+        outcomes: Vec<(String, SessionImportOutcome)>,
+    ) -> Result<()> {
+        let changed = {
+            let mut config = self.config.write().await;
+            outcomes
+                .into_iter()
+                .fold(false, |changed, (session_id, outcome)| {
+                    config.record_session_import(agent_name, &session_id, outcome) || changed
+                })
+        };
+
+        if changed {
+            self.schedule_save();
+        }
+
+        Ok(())
+    }
+
+    /// Get the recorded import outcomes of an agent's sessions
+    pub async fn session_imports(
+        &self,
+        agent_name: &str,
+    ) -> BTreeMap<String, SessionImportOutcome> {
+        let config = self.config.read().await;
+        config
+            .session_imports
+            .get(agent_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Store or clear (`None`) a session's title
+    ///
+    /// Returns false if an existing title takes precedence over `source`.
+    pub async fn set_session_title(
+        &self,
+        session_id: &str,
+        title: Option<String>,
+        source: SessionTitleSource,
+    ) -> Result<bool> {
+        let changed = {
+            let mut config = self.config.write().await;
+            config.set_session_title(session_id, title, source)
+        };
+
+        if changed {
+            self.schedule_save();
+        }
+
+        Ok(changed)
+    }
+
+    /// Store the mode chosen for a session
+    pub async fn set_session_mode(&self, session_id: &str, mode: &str) -> Result<()> {
+        let changed = {
+            let mut config = self.config.write().await;
+            config.set_session_mode(session_id, mode)
+        };
+
+        if changed {
+            self.schedule_save();
+        }
+
+        Ok(())
+    }
+
+    /// Get the stored mode of every session, keyed by session ID
+    pub async fn session_modes(&self) -> BTreeMap<String, String> {
+        let config = self.config.read().await;
+        config.session_modes.clone()
+    }
+
+    /// Remember the session whose conversation the user last had open
+    pub async fn set_last_active_session(&self, session_id: &str) -> Result<()> {
+        let changed = {
+            let mut config = self.config.write().await;
+            config.set_last_active_session(session_id)
+        };
+
+        if changed {
+            self.schedule_save();
+        }
+
+        Ok(())
+    }
+
+    /// Last active session of a workspace, if it still belongs to one of its tasks
+    pub async fn last_active_session(&self, workspace_id: &str) -> Option<String> {
+        let config = self.config.read().await;
+        config.last_active_session(workspace_id).map(str::to_string)
+    }
+
+    /// Get all stored session titles, keyed by session ID
+    pub async fn session_titles(&self) -> BTreeMap<String, String> {
+        let config = self.config.read().await;
+        config.session_titles.clone()
+    }
+
+    /// Get the source of every session title, keyed by session ID
+    pub async fn session_title_sources(&self) -> BTreeMap<String, SessionTitleSource> {
+        let config = self.config.read().await;
+        config
+            .session_titles
+            .keys()
+            .chain(config.session_title_sources.keys())
+            .filter_map(|id| Some((id.clone(), config.session_title_source(id)?)))
+            .collect()
+    }
+
     /// Remove a task by ID
     pub async fn remove_task(&self, task_id: &str) -> Result<()> {
         let workspace_id = {
@@ -318,7 +664,7 @@ impl WorkspaceService {
             task.workspace_id.clone()
         };
 
-        self.save_config().await?;
+        self.schedule_save();
 
         // Publish TaskRemoved event
         self.publish_event(WorkspaceUpdateEvent::TaskRemoved {
@@ -330,3 +676,376 @@ impl WorkspaceService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "agentx-workspace-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_save_keeps_backup_of_previous_config() {
+        let dir = test_dir("backup");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        let workspace = service.add_workspace(dir.clone()).await.unwrap();
+        service.flush().await.unwrap();
+        service
+            .create_task(&workspace.id, "task".into(), "agent".into(), "Auto".into())
+            .await
+            .unwrap();
+        service.flush().await.unwrap();
+
+        let backup =
+            WorkspaceService::read_config_file(&WorkspaceService::backup_path(&config_path))
+                .unwrap();
+        assert_eq!(backup.workspaces.len(), 1);
+        assert!(backup.tasks.is_empty());
+
+        let current = WorkspaceService::read_config_file(&config_path).unwrap();
+        assert_eq!(current.tasks.len(), 1);
+        assert!(!config_path.with_extension("json.tmp").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_write_falls_back_to_backup() {
+        let dir = test_dir("interrupted");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        let workspace = service.add_workspace(dir.clone()).await.unwrap();
+        service.flush().await.unwrap();
+        service
+            .create_task(&workspace.id, "task".into(), "agent".into(), "Auto".into())
+            .await
+            .unwrap();
+        service.flush().await.unwrap();
+
+        // Simulate a crash that left the primary file cut off mid-write,
+        // along with a stray temp file
+        let content = std::fs::read_to_string(&config_path).unwrap();
+        std::fs::write(&config_path, &content[..content.len() / 2]).unwrap();
+        std::fs::write(config_path.with_extension("json.tmp"), "{\"workspaces\": [").unwrap();
+        assert!(WorkspaceService::read_config_file(&config_path).is_err());
+
+        // The backup holds the config from before the task was added
+        let reloaded = WorkspaceService::new(config_path.clone());
+        assert_eq!(reloaded.list_workspaces().await.len(), 1);
+        assert!(reloaded.get_all_tasks().await.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_session_titles_persist_and_clear() {
+        let dir = test_dir("titles");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        service
+            .set_session_title(
+                "session-1",
+                Some("Refactor parser".into()),
+                SessionTitleSource::Manual,
+            )
+            .await
+            .unwrap();
+        service.flush().await.unwrap();
+
+        let reloaded = WorkspaceService::new(config_path.clone());
+        assert_eq!(
+            reloaded
+                .session_titles()
+                .await
+                .get("session-1")
+                .map(String::as_str),
+            Some("Refactor parser")
+        );
+
+        reloaded
+            .set_session_title("session-1", None, SessionTitleSource::Manual)
+            .await
+            .unwrap();
+        assert!(reloaded.session_titles().await.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_session_modes_persist_and_follow_the_task() {
+        let dir = test_dir("modes");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        let workspace = service.add_workspace(dir.clone()).await.unwrap();
+        let task = service
+            .create_task(&workspace.id, "Task".into(), "agent".into(), "Auto".into())
+            .await
+            .unwrap();
+        service
+            .set_task_session(&task.id, "session-1".into())
+            .await
+            .unwrap();
+
+        service.set_session_mode("session-1", "plan").await.unwrap();
+        service.flush().await.unwrap();
+
+        let reloaded = WorkspaceService::new(config_path.clone());
+        assert_eq!(
+            reloaded
+                .session_modes()
+                .await
+                .get("session-1")
+                .map(String::as_str),
+            Some("plan")
+        );
+        assert_eq!(
+            reloaded
+                .get_task_by_session("session-1")
+                .await
+                .unwrap()
+                .mode,
+            "plan"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_last_active_session_is_kept_per_workspace() {
+        let dir = test_dir("last-active");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        let workspace = service.add_workspace(dir.clone()).await.unwrap();
+        let task = service
+            .create_task(&workspace.id, "Task".into(), "agent".into(), "Auto".into())
+            .await
+            .unwrap();
+        service
+            .set_task_session(&task.id, "session-1".into())
+            .await
+            .unwrap();
+
+        // Sessions without a task are not tied to a workspace
+        service.set_last_active_session("orphan").await.unwrap();
+        assert_eq!(service.last_active_session(&workspace.id).await, None);
+
+        service.set_last_active_session("session-1").await.unwrap();
+        service.flush().await.unwrap();
+        let reloaded = WorkspaceService::new(config_path.clone());
+        assert_eq!(
+            reloaded.last_active_session(&workspace.id).await.as_deref(),
+            Some("session-1")
+        );
+
+        // Once the task is gone the session is no longer offered
+        reloaded.remove_task(&task.id).await.unwrap();
+        assert_eq!(reloaded.last_active_session(&workspace.id).await, None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_session_imports_persist_per_agent() {
+        let dir = test_dir("imports");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        service
+            .record_session_imports(
+                "claude",
+                vec![
+                    ("session-1".into(), SessionImportOutcome::Imported),
+                    (
+                        "session-2".into(),
+                        SessionImportOutcome::Failed {
+                            reason: "not found".into(),
+                        },
+                    ),
+                ],
+            )
+            .await
+            .unwrap();
+        service.flush().await.unwrap();
+
+        let reloaded = WorkspaceService::new(config_path.clone());
+        let imports = reloaded.session_imports("claude").await;
+        assert_eq!(
+            imports.get("session-1"),
+            Some(&SessionImportOutcome::Imported)
+        );
+        assert!(matches!(
+            imports.get("session-2"),
+            Some(SessionImportOutcome::Failed { .. })
+        ));
+        assert!(reloaded.session_imports("codex").await.is_empty());
+
+        // A successful retry replaces the failure
+        reloaded
+            .record_session_imports(
+                "claude",
+                vec![("session-2".into(), SessionImportOutcome::Imported)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            reloaded.session_imports("claude").await.get("session-2"),
+            Some(&SessionImportOutcome::Imported)
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_remove_workspace_requires_cascade_for_tasks() {
+        let dir = test_dir("remove-workspace");
+        let first_dir = dir.join("first");
+        let second_dir = dir.join("second");
+        std::fs::create_dir_all(&first_dir).unwrap();
+        std::fs::create_dir_all(&second_dir).unwrap();
+
+        let service = WorkspaceService::new(dir.join("workspace-config.json"));
+        let first = service.add_workspace(first_dir).await.unwrap();
+        let second = service.add_workspace(second_dir).await.unwrap();
+        service
+            .create_task(&first.id, "Task".into(), "agent".into(), "code".into())
+            .await
+            .unwrap();
+
+        assert!(service.remove_workspace(&first.id).await.is_err());
+        assert_eq!(service.list_workspaces().await.len(), 2);
+
+        let removed = service.remove_workspace_cascade(&first.id).await.unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(service.get_config().await.tasks.is_empty());
+        assert_eq!(
+            service.get_active_workspace().await.map(|w| w.id),
+            Some(second.id.clone())
+        );
+
+        // Workspaces without tasks can be removed safely
+        service.remove_workspace(&second.id).await.unwrap();
+        assert!(service.get_active_workspace().await.is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_session_title_sources_take_precedence() {
+        let dir = test_dir("title-sources");
+        let service = WorkspaceService::new(dir.join("workspace-config.json"));
+        let set = |title: &str, source| service.set_session_title("s", Some(title.into()), source);
+
+        assert!(
+            set("Fix the build", SessionTitleSource::FirstMessage)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !set("Other message", SessionTitleSource::FirstMessage)
+                .await
+                .unwrap()
+        );
+        assert!(set("Build fixes", SessionTitleSource::Agent).await.unwrap());
+        assert!(set("Mine", SessionTitleSource::Manual).await.unwrap());
+        assert!(!set("Agent again", SessionTitleSource::Agent).await.unwrap());
+        assert_eq!(
+            service.session_title_sources().await.get("s"),
+            Some(&SessionTitleSource::Manual)
+        );
+
+        // A title the user cleared is not regenerated
+        service
+            .set_session_title("s", None, SessionTitleSource::Manual)
+            .await
+            .unwrap();
+        assert!(
+            !set("New title", SessionTitleSource::FirstMessage)
+                .await
+                .unwrap()
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_config_falls_back_to_backup() {
+        let dir = test_dir("corrupt");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        let workspace = service.add_workspace(dir.clone()).await.unwrap();
+        service.flush().await.unwrap();
+        service.set_active_workspace(&workspace.id).await.unwrap();
+        service.flush().await.unwrap();
+
+        // Simulate a truncated primary file (e.g. written by an older version)
+        std::fs::write(&config_path, "{\"workspaces\": [").unwrap();
+
+        let reloaded = WorkspaceService::new(config_path.clone());
+        assert_eq!(reloaded.list_workspaces().await.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_updates_are_coalesced_until_flush() {
+        let dir = test_dir("debounce");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        let workspace = service.add_workspace(dir.clone()).await.unwrap();
+        let task = service
+            .create_task(&workspace.id, "task".into(), "agent".into(), "Auto".into())
+            .await
+            .unwrap();
+        service
+            .set_task_session(&task.id, "session-1".into())
+            .await
+            .unwrap();
+        service
+            .update_task_message("session-1", "hello".into())
+            .await
+            .unwrap();
+
+        // Nothing is written until the debounce elapses or flush() is called
+        assert!(!config_path.exists());
+
+        service.flush().await.unwrap();
+
+        let saved = WorkspaceService::read_config_file(&config_path).unwrap();
+        assert_eq!(saved.tasks.len(), 1);
+        assert_eq!(saved.tasks[0].last_message.as_deref(), Some("hello"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_debounced_save_writes_in_background() {
+        let dir = test_dir("background");
+        let config_path = dir.join("workspace-config.json");
+
+        let service = WorkspaceService::new(config_path.clone());
+        service.add_workspace(dir.clone()).await.unwrap();
+
+        smol::Timer::after(SAVE_DEBOUNCE * 4).await;
+
+        let saved = WorkspaceService::read_config_file(&config_path).unwrap();
+        assert_eq!(saved.workspaces.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,10 +1,15 @@
 use agent_client_protocol as acp;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub agent_servers: HashMap<String, AgentProcessConfig>,
+    /// Relative to the directory containing the config file, see [`resolve_config_path`]
     #[serde(default = "default_upload_dir")]
     pub upload_dir: PathBuf,
     #[serde(default)]
@@ -17,12 +22,20 @@ pub struct Config {
     /// Keys: "doc_comment", "inline_comment", "explain", "improve"
     #[serde(default)]
     pub system_prompts: HashMap<String, String>,
-    /// Max lines to show in tool call previews (0 disables truncation)
+    /// Max lines to show in collapsed tool call previews
+    /// (`MIN_TOOL_CALL_PREVIEW_MAX_LINES..=MAX_TOOL_CALL_PREVIEW_MAX_LINES`; longer output is
+    /// expanded inline on demand)
     #[serde(default = "default_tool_call_preview_max_lines")]
     pub tool_call_preview_max_lines: usize,
+    /// Size of each agent's stderr log buffer in KB
+    #[serde(default = "default_agent_log_buffer_kb")]
+    pub agent_log_buffer_kb: usize,
     /// Network proxy configuration
     #[serde(default)]
     pub proxy: ProxyConfig,
+    /// Retries for idempotent agent session requests (list/load/resume)
+    #[serde(default)]
+    pub request_retry: RequestRetryConfig,
 }
 
 fn default_upload_dir() -> PathBuf {
@@ -30,24 +43,171 @@ fn default_upload_dir() -> PathBuf {
 }
 
 pub const DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES: usize = 10;
+pub const MIN_TOOL_CALL_PREVIEW_MAX_LINES: usize = 1;
+pub const MAX_TOOL_CALL_PREVIEW_MAX_LINES: usize = 500;
 
 fn default_tool_call_preview_max_lines() -> usize {
     DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES
 }
 
+pub const DEFAULT_AGENT_LOG_BUFFER_KB: usize = 64;
+
+fn default_agent_log_buffer_kb() -> usize {
+    DEFAULT_AGENT_LOG_BUFFER_KB
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AgentProcessConfig {
+    /// Command name looked up in PATH, or a path; relative paths are
+    /// relative to the config file, see [`resolve_config_command`]
     pub command: String,
     #[serde(default)]
     pub args: Vec<String>,
     #[serde(default)]
     pub env: HashMap<String, String>,
+    /// Built-in icon key (e.g. "claude", "gemini"); the name-based mapping is used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Position in user-defined agent lists; unordered agents follow, sorted by name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<usize>,
+    /// Pinned agents are listed before all others, regardless of `order`
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pinned: bool,
+    /// Seconds to wait for session requests (new/load/resume/list) before giving up;
+    /// raise it for slow models. The service default applies when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+    /// Seconds the agent gets to spawn and answer `initialize` before it is
+    /// marked failed; the client default applies when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_timeout_secs: Option<u64>,
+    /// Directory the agent runs in instead of the app's; relative paths are
+    /// relative to the config file, see [`resolve_config_path`]
+    ///
+    /// There is deliberately no option to feed the agent text on stdin: stdin
+    /// carries the ACP JSON-RPC stream from the first byte, so extra input
+    /// would corrupt the first frame. Pass such data through `args` or `env`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir_override: Option<PathBuf>,
+    /// Session modes offered in the conversation header (e.g. "plan", "code")
+    /// when the agent does not report its own
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<String>,
 
     /// Custom Node.js path (populated at runtime from AppSettings)
     #[serde(skip)]
     pub nodejs_path: Option<String>,
 }
 
+/// Absolute directory containing the config file, the base for relative config paths
+pub fn config_dir(config_path: &Path) -> PathBuf {
+    let dir = match config_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Resolve a path written in the config file
+///
+/// Relative paths are relative to `config_dir`, the directory containing the
+/// config file, not to the process working directory, which depends on how
+/// the app was launched (terminal, Finder, a desktop shortcut, ...).
+pub fn resolve_config_path(config_dir: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    path.components()
+        .filter(|component| *component != std::path::Component::CurDir)
+        .fold(config_dir.to_path_buf(), |resolved, component| {
+            resolved.join(component)
+        })
+}
+
+/// Resolve a relative command path such as `./bin/agent` with [`resolve_config_path`]
+///
+/// Bare command names like `npx` are left as they are and looked up in PATH.
+pub fn resolve_config_command(config_dir: &Path, command: &str) -> String {
+    let path = Path::new(command);
+    if path.is_absolute() || path.components().count() < 2 {
+        return command.to_string();
+    }
+    resolve_config_path(config_dir, path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Characters of the offending line shown before and after the error column
+const JSON_SNIPPET_BEFORE: usize = 40;
+const JSON_SNIPPET_AFTER: usize = 30;
+
+/// Describe a JSON parse failure with the offending line and a caret under the column
+///
+/// serde_json already names the line and column; the snippet makes the
+/// problem findable without opening the file in an editor. Long lines are
+/// cut around the column.
+pub fn describe_json_error(source: &str, error: &serde_json::Error) -> String {
+    let Some(line) = error
+        .line()
+        .checked_sub(1)
+        .and_then(|index| source.lines().nth(index))
+    else {
+        return error.to_string();
+    };
+
+    // serde_json counts columns in bytes
+    let byte_column = error.column().saturating_sub(1).min(line.len());
+    let column = line
+        .char_indices()
+        .take_while(|(index, _)| *index < byte_column)
+        .count();
+    let chars: Vec<char> = line.chars().collect();
+    let start = column.saturating_sub(JSON_SNIPPET_BEFORE);
+    let end = (column + JSON_SNIPPET_AFTER).min(chars.len());
+    let snippet: String = chars[start..end].iter().collect();
+    let (prefix, suffix) = (
+        if start > 0 { "…" } else { "" },
+        if end < chars.len() { "…" } else { "" },
+    );
+
+    let gutter = format!("{} | ", error.line());
+    let caret_indent = gutter.chars().count() + prefix.chars().count() + (column - start);
+    format!(
+        "{error}\n{gutter}{prefix}{snippet}{suffix}\n{}^",
+        " ".repeat(caret_indent)
+    )
+}
+
+impl AgentProcessConfig {
+    /// The config to spawn with, its command resolved by [`resolve_config_command`]
+    /// and its working directory by [`resolve_config_path`]
+    pub fn with_resolved_command(&self, config_dir: &Path) -> Self {
+        Self {
+            command: resolve_config_command(config_dir, &self.command),
+            working_dir_override: self
+                .working_dir_override
+                .as_deref()
+                .map(|dir| resolve_config_path(config_dir, dir)),
+            ..self.clone()
+        }
+    }
+}
+
+/// Compare agents for display: pinned first, then custom `order`, then alphabetically by name
+pub fn compare_agents(
+    a: (&str, Option<&AgentProcessConfig>),
+    b: (&str, Option<&AgentProcessConfig>),
+) -> Ordering {
+    let pinned = |config: Option<&AgentProcessConfig>| config.is_some_and(|c| c.pinned);
+    let order =
+        |config: Option<&AgentProcessConfig>| config.and_then(|c| c.order).unwrap_or(usize::MAX);
+    pinned(b.1)
+        .cmp(&pinned(a.1))
+        .then_with(|| order(a.1).cmp(&order(b.1)))
+        .then_with(|| a.0.cmp(b.0))
+}
+
 /// Model configuration for LLM providers
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ModelConfig {
@@ -59,7 +219,7 @@ pub struct ModelConfig {
 }
 
 /// MCP (Model Context Protocol) server configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct McpServerConfig {
     #[serde(default = "default_true")]
     pub enabled: bool,
@@ -122,6 +282,42 @@ pub struct CommandConfig {
     pub template: String,
 }
 
+/// Exponential backoff for idempotent agent session requests
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RequestRetryConfig {
+    /// Total attempts including the first one; 1 disables retries
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds, doubled for each further retry
+    #[serde(default = "default_retry_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    /// Upper bound for a single delay in milliseconds
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+impl Default for RequestRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            initial_delay_ms: default_retry_initial_delay_ms(),
+            max_delay_ms: default_retry_max_delay_ms(),
+        }
+    }
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_initial_delay_ms() -> u64 {
+    250
+}
+
+fn default_retry_max_delay_ms() -> u64 {
+    2000
+}
+
 /// Network proxy configuration
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct ProxyConfig {
@@ -221,6 +417,29 @@ impl ProxyConfig {
     }
 }
 
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 fn is_zero(value: &u16) -> bool {
     *value == 0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_json_error_points_at_line_and_column() {
+        let source =
+            "{\n  \"agent_servers\": {\n    \"a\": { \"command\": \"x\" }\n    \"b\": {}\n  }\n}\n";
+        let error = serde_json::from_str::<serde_json::Value>(source).unwrap_err();
+        assert_eq!((error.line(), error.column()), (4, 5));
+
+        let description = describe_json_error(source, &error);
+        let lines: Vec<_> = description.lines().collect();
+        assert!(lines[0].ends_with("at line 4 column 5"));
+        assert_eq!(lines[1], "4 |     \"b\": {}");
+        assert_eq!(lines[2], "        ^");
+    }
+}
@@ -24,6 +24,14 @@ pub enum AgentConfigEvent {
     /// An agent was removed
     AgentRemoved { name: String },
 
+    // ========== Agent Lifecycle Events ==========
+    /// An agent process is being spawned and initialized (start or restart)
+    AgentStarting { name: String },
+    /// An agent process started and finished initialization
+    AgentReady { name: String },
+    /// An agent process failed to start or timed out during startup
+    AgentFailed { name: String, error: String },
+
     // ========== Model Events ==========
     /// A new model was added
     ModelAdded { name: String, config: ModelConfig },
@@ -57,6 +65,9 @@ pub enum AgentConfigEvent {
     // ========== Full Reload ==========
     /// The entire configuration was reloaded from file
     ConfigReloaded { config: Box<Config> },
+    /// The config file was changed by another writer and both sides changed `keys`;
+    /// the in-memory values were kept
+    ConfigConflict { keys: Vec<String> },
 }
 
 /// Session update event that can be broadcast to subscribers
@@ -109,6 +120,12 @@ pub enum WorkspaceUpdateEvent {
         last_active: DateTime<Utc>,
         message_count: usize,
     },
+    /// A session was renamed (`None` reverts to the short-id display)
+    SessionTitleUpdated {
+        session_id: String,
+        agent_name: String,
+        title: Option<String>,
+    },
 }
 
 /// Pure data struct for code selection (no GPUI dependency)
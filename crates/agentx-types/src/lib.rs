@@ -5,8 +5,9 @@ pub mod schemas;
 pub mod session;
 
 pub use config::{
-    AgentProcessConfig, CommandConfig, Config, DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES,
-    McpServerConfig, ModelConfig, ProxyConfig,
+    AgentProcessConfig, CommandConfig, Config, DEFAULT_AGENT_LOG_BUFFER_KB,
+    DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES, McpServerConfig, ModelConfig, ProxyConfig,
+    describe_json_error,
 };
 pub use events::{
     AgentConfigEvent, CodeSelectionEvent, PermissionRequestEvent, SessionUpdateEvent,
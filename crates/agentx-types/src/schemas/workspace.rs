@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 
 use crate::session::SessionStatus;
@@ -67,7 +68,7 @@ pub struct WorkspaceTask {
     #[serde(with = "chrono::serde::ts_seconds")]
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Last message preview (plain String, UI layer can convert to SharedString)
-    #[serde(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_message: Option<String>,
 }
 
@@ -99,6 +100,42 @@ impl WorkspaceTask {
     }
 }
 
+/// Where a session title came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionTitleSource {
+    /// Generated from the first user message
+    FirstMessage,
+    /// Reported by the agent
+    Agent,
+    /// Set by the user
+    Manual,
+}
+
+impl SessionTitleSource {
+    /// Whether a title from this source may replace one from `existing`
+    ///
+    /// Manual titles always win; agent titles replace anything but a manual
+    /// title; first-message titles are only used when nothing was set yet.
+    pub fn may_replace(self, existing: Option<SessionTitleSource>) -> bool {
+        match self {
+            SessionTitleSource::Manual => true,
+            SessionTitleSource::Agent => existing != Some(SessionTitleSource::Manual),
+            SessionTitleSource::FirstMessage => existing.is_none(),
+        }
+    }
+}
+
+/// Result of the last attempt to import an agent-reported session
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SessionImportOutcome {
+    /// The session was resumed into the workspace
+    Imported,
+    /// Resuming the session failed; it is retried by the next import
+    Failed { reason: String },
+}
+
 /// Persistent workspace configuration
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct WorkspaceConfig {
@@ -108,6 +145,27 @@ pub struct WorkspaceConfig {
     pub tasks: Vec<WorkspaceTask>,
     /// Currently active workspace ID
     pub active_workspace_id: Option<String>,
+    /// Session IDs hidden from the active session list (history is kept)
+    #[serde(default)]
+    pub archived_sessions: BTreeSet<String>,
+    /// Session IDs listed above the others in the session manager
+    #[serde(default)]
+    pub pinned_sessions: BTreeSet<String>,
+    /// User-given session titles, keyed by session ID
+    #[serde(default)]
+    pub session_titles: BTreeMap<String, String>,
+    /// Where each session title came from; titles without an entry are manual
+    #[serde(default)]
+    pub session_title_sources: BTreeMap<String, SessionTitleSource>,
+    /// Import outcomes of agent-reported sessions, keyed by agent name and session ID
+    #[serde(default)]
+    pub session_imports: BTreeMap<String, BTreeMap<String, SessionImportOutcome>>,
+    /// Session mode last chosen for each session, keyed by session ID
+    #[serde(default)]
+    pub session_modes: BTreeMap<String, String>,
+    /// Session whose conversation was last active, keyed by workspace ID
+    #[serde(default)]
+    pub last_active_sessions: BTreeMap<String, String>,
 }
 
 impl WorkspaceConfig {
@@ -121,6 +179,7 @@ impl WorkspaceConfig {
         self.workspaces.retain(|w| w.id != workspace_id);
         // Also remove all tasks for this workspace
         self.tasks.retain(|t| t.workspace_id != workspace_id);
+        self.last_active_sessions.remove(workspace_id);
     }
 
     /// Add a task to a workspace
@@ -160,6 +219,138 @@ impl WorkspaceConfig {
             .find(|t| t.session_id.as_ref() == Some(&session_id.to_string()))
     }
 
+    /// Mark a session as archived or unarchived
+    ///
+    /// Returns true if the archived set changed.
+    pub fn set_session_archived(&mut self, session_id: &str, archived: bool) -> bool {
+        if archived {
+            self.archived_sessions.insert(session_id.to_string())
+        } else {
+            self.archived_sessions.remove(session_id)
+        }
+    }
+
+    /// Check whether a session is archived
+    pub fn is_session_archived(&self, session_id: &str) -> bool {
+        self.archived_sessions.contains(session_id)
+    }
+
+    /// Pin or unpin a session
+    ///
+    /// Returns true if the pinned set changed.
+    pub fn set_session_pinned(&mut self, session_id: &str, pinned: bool) -> bool {
+        if pinned {
+            self.pinned_sessions.insert(session_id.to_string())
+        } else {
+            self.pinned_sessions.remove(session_id)
+        }
+    }
+
+    /// Record the outcome of importing one of an agent's sessions
+    ///
+    /// Returns true if the stored outcome changed.
+    pub fn record_session_import(
+        &mut self,
+        agent_name: &str,
+        session_id: &str,
+        outcome: SessionImportOutcome,
+    ) -> bool {
+        let imports = self
+            .session_imports
+            .entry(agent_name.to_string())
+            .or_default();
+        imports.insert(session_id.to_string(), outcome.clone()) != Some(outcome)
+    }
+
+    /// Where a session's title came from, if it has one or was cleared by the user
+    pub fn session_title_source(&self, session_id: &str) -> Option<SessionTitleSource> {
+        self.session_title_sources
+            .get(session_id)
+            .copied()
+            .or_else(|| {
+                self.session_titles
+                    .contains_key(session_id)
+                    .then_some(SessionTitleSource::Manual)
+            })
+    }
+
+    /// Remember the mode chosen for a session and mirror it on the session's task
+    ///
+    /// Returns true if anything changed.
+    pub fn set_session_mode(&mut self, session_id: &str, mode: &str) -> bool {
+        let mut changed = self
+            .session_modes
+            .insert(session_id.to_string(), mode.to_string())
+            .as_deref()
+            != Some(mode);
+        for task in &mut self.tasks {
+            if task.session_id.as_deref() == Some(session_id) && task.mode != mode {
+                task.mode = mode.to_string();
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Remember a session as the last active one of its task's workspace
+    ///
+    /// Sessions without a task belong to no workspace and are ignored.
+    /// Returns true if anything changed.
+    pub fn set_last_active_session(&mut self, session_id: &str) -> bool {
+        let Some(workspace_id) = self
+            .tasks
+            .iter()
+            .find(|t| t.session_id.as_deref() == Some(session_id))
+            .map(|t| t.workspace_id.clone())
+        else {
+            return false;
+        };
+        self.last_active_sessions
+            .insert(workspace_id, session_id.to_string())
+            .as_deref()
+            != Some(session_id)
+    }
+
+    /// Last active session of a workspace, if one of its tasks still has it
+    pub fn last_active_session(&self, workspace_id: &str) -> Option<&str> {
+        let session_id = self.last_active_sessions.get(workspace_id)?;
+        self.tasks
+            .iter()
+            .any(|t| t.workspace_id == workspace_id && t.session_id.as_ref() == Some(session_id))
+            .then_some(session_id.as_str())
+    }
+
+    /// Set or clear (`None`) a session's title
+    ///
+    /// Titles that may not replace the current one (see
+    /// [`SessionTitleSource::may_replace`]) are ignored. Clearing keeps the
+    /// source, so a title the user removed is not regenerated automatically.
+    /// Returns true if the stored title or source changed.
+    pub fn set_session_title(
+        &mut self,
+        session_id: &str,
+        title: Option<String>,
+        source: SessionTitleSource,
+    ) -> bool {
+        if !source.may_replace(self.session_title_source(session_id)) {
+            return false;
+        }
+
+        let title_changed = match title {
+            Some(title) => {
+                self.session_titles
+                    .insert(session_id.to_string(), title.clone())
+                    != Some(title)
+            }
+            None => self.session_titles.remove(session_id).is_some(),
+        };
+        let source_changed = self
+            .session_title_sources
+            .insert(session_id.to_string(), source)
+            != Some(source);
+        title_changed || source_changed
+    }
+
     /// Get workspace by ID
     pub fn get_workspace(&self, workspace_id: &str) -> Option<&Workspace> {
         self.workspaces.iter().find(|w| w.id == workspace_id)
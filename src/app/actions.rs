@@ -46,6 +46,8 @@ pub enum PanelCommand {
         placement: DockPlacement,
     },
     Show(PanelKind),
+    /// 在中心区域分屏显示（与已有内容并排）
+    Split(PanelKind),
 }
 
 /// 统一的面板操作 Action
@@ -91,6 +93,12 @@ impl PanelAction {
         })
     }
 
+    pub fn split_conversation_for_session(session_id: String) -> Self {
+        Self(PanelCommand::Split(PanelKind::Conversation {
+            session_id: Some(session_id),
+        }))
+    }
+
     pub fn show_welcome(workspace_id: Option<String>) -> Self {
         Self(PanelCommand::Show(PanelKind::Welcome { workspace_id }))
     }
@@ -134,6 +142,15 @@ fn default_dock_placement() -> DockPlacement {
 // 切换 Dock 切换按钮的显示状态 / 打开会话管理面板
 actions!(agent_studio, [ToggleDockToggleButton, OpenSessionManager]);
 
+// 重新运行启动向导
+actions!(agent_studio, [RerunStartupWizard]);
+
+// 打开日志查看面板
+actions!(agent_studio, [OpenLogViewer]);
+
+// 将 Dock 布局恢复为默认布局
+actions!(agent_studio, [ResetLayout]);
+
 // ============================================================================
 // Task List Actions - 任务列表相关操作
 // ============================================================================
@@ -236,6 +253,44 @@ pub struct CancelSession {
     /// 会话唯一标识符
     pub session_id: String,
 }
+
+/// 切换会话模式
+///
+/// 通过 ACP 的 session/set_mode 切换会话模式（如 plan / code），并按会话持久化，
+/// 由 ConversationPanel 标题栏的模式菜单触发
+#[derive(Action, Clone, Debug, PartialEq, Deserialize)]
+#[action(namespace = agentx, no_json)]
+pub struct SetSessionMode {
+    /// 会话唯一标识符
+    pub session_id: String,
+    /// 模式 ID
+    pub mode_id: String,
+}
+
+/// 打开会话并滚动到全局搜索命中的消息
+///
+/// 由会话管理面板的搜索结果触发，会话未打开时先打开并加载历史
+#[derive(Action, Clone, Debug, PartialEq, Deserialize)]
+#[action(namespace = agentx, no_json)]
+pub struct RevealSessionMatch {
+    /// 会话唯一标识符
+    pub session_id: String,
+    /// 搜索词
+    pub query: String,
+    /// 命中消息在该会话所有匹配消息中的序号（从 0 开始）
+    pub occurrence: usize,
+}
+/// 为任务启动会话
+///
+/// 使用任务的 Agent 和所在工作区的目录创建新会话并关联到任务；
+/// 如果任务已有会话，则直接打开该会话
+#[derive(Action, Clone, Debug, PartialEq, Deserialize)]
+#[action(namespace = agentx, no_json)]
+pub struct StartTaskSession {
+    /// 任务唯一标识符
+    pub task_id: String,
+}
+
 /// 显示会话对话面板
 ///
 #[derive(Action, Clone, PartialEq, Deserialize)]
@@ -269,6 +324,17 @@ pub struct AddCodeSelection {
     pub content: String,
 }
 
+impl AddCodeSelection {
+    /// 简短的 `file:line` 引用，用于插入聊天输入框
+    pub fn reference(&self) -> String {
+        if self.start_line == self.end_line {
+            format!("{}:{}", self.file_path, self.start_line)
+        } else {
+            format!("{}:{}-{}", self.file_path, self.start_line, self.end_line)
+        }
+    }
+}
+
 impl From<agentx_types::events::CodeSelectionData> for AddCodeSelection {
     fn from(data: agentx_types::events::CodeSelectionData) -> Self {
         Self {
@@ -295,6 +361,18 @@ impl From<AddCodeSelection> for agentx_types::events::CodeSelectionData {
     }
 }
 
+// 保存代码编辑器中当前打开的文件
+actions!(code_editor, [SaveFile]);
+
+// 将代码编辑器中的选中内容添加到聊天
+actions!(code_editor, [AddSelectionToChat]);
+
+// 在工具调用详情的差异视图中跳转到下一个/上一个变更块
+actions!(tool_call_detail, [NextHunk, PreviousHunk]);
+
+// 在设置对话框的输入框之间循环切换焦点
+actions!(settings_dialog, [FocusNextField, FocusPreviousField]);
+
 // 通用应用级操作 - 包含各种应用级别的命令和操作
 actions!(
     agent_studio,
@@ -355,6 +433,9 @@ pub struct SwitchTheme(pub SharedString);
 #[action(namespace = themes, no_json)]
 pub struct SwitchThemeMode(pub ThemeMode);
 
+// 主题模式跟随系统外观（自动切换亮色/暗色）
+actions!(themes, [FollowSystemThemeMode]);
+
 // ============================================================================
 // Agent Configuration Actions - Agent 配置相关操作
 // ============================================================================
@@ -373,6 +454,10 @@ pub struct AddAgent {
     pub args: Vec<String>,
     /// Environment variables / 环境变量
     pub env: std::collections::HashMap<String, String>,
+    /// Built-in icon key, None for name-based icon / 内置图标，None 表示按名称匹配
+    pub icon: Option<String>,
+    /// Working directory for the process / 进程工作目录
+    pub working_dir_override: Option<std::path::PathBuf>,
 }
 
 /// 更新现有 Agent 的配置
@@ -389,6 +474,10 @@ pub struct UpdateAgent {
     pub args: Vec<String>,
     /// Environment variables / 环境变量
     pub env: std::collections::HashMap<String, String>,
+    /// Built-in icon key, None for name-based icon / 内置图标，None 表示按名称匹配
+    pub icon: Option<String>,
+    /// Working directory for the process / 进程工作目录
+    pub working_dir_override: Option<std::path::PathBuf>,
 }
 
 /// 移除 Agent
@@ -437,3 +526,13 @@ pub struct ChangeConfigPath {
     /// Config file path / 配置文件路径
     pub path: std::path::PathBuf,
 }
+
+/// 从备份恢复配置
+///
+/// 用备份文件替换 config.json，重新加载配置并重启受影响的 agent
+#[derive(Action, Clone, PartialEq, Eq, Deserialize)]
+#[action(namespace = agent_config, no_json)]
+pub struct RestoreConfigBackup {
+    /// Backup file path / 备份文件路径
+    pub path: std::path::PathBuf,
+}
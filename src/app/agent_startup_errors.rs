@@ -0,0 +1,92 @@
+//! Agent startup failures reported on the agent config bus
+//!
+//! Failures are kept until the agent starts successfully or is removed, so the
+//! workspace banner and the agent settings page can show them at any time.
+
+use std::collections::BTreeMap;
+
+use gpui::{App, Global};
+
+use crate::{AppState, core::event_bus::AgentConfigEvent};
+
+#[derive(Default)]
+pub struct AgentStartupErrors {
+    /// Startup error per agent name
+    failures: BTreeMap<String, String>,
+    /// Whether the user dismissed the banner for the current failures
+    dismissed: bool,
+}
+
+impl Global for AgentStartupErrors {}
+
+impl AgentStartupErrors {
+    /// Startup error of a single agent, if its last start failed
+    pub fn error(name: &str, cx: &App) -> Option<String> {
+        cx.try_global::<Self>()
+            .and_then(|state| state.failures.get(name).cloned())
+    }
+
+    /// Failures the workspace banner should show, sorted by agent name
+    pub fn visible_failures(cx: &App) -> Vec<(String, String)> {
+        match cx.try_global::<Self>() {
+            Some(state) if !state.dismissed => state
+                .failures
+                .iter()
+                .map(|(name, error)| (name.clone(), error.clone()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Hide the banner until another agent fails
+    pub fn dismiss(cx: &mut App) {
+        cx.global_mut::<Self>().dismissed = true;
+        cx.refresh_windows();
+    }
+
+    /// Text copied by the "Copy error" button
+    pub fn report(failures: &[(String, String)]) -> String {
+        failures
+            .iter()
+            .map(|(name, error)| format!("[{name}]\n{error}"))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Track agent readiness and failures published by the agent manager
+pub fn init(cx: &mut App) {
+    cx.set_global(AgentStartupErrors::default());
+
+    let event_hub = AppState::global(cx).event_hub().clone();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+    event_hub.subscribe_agent_config_agent_events(move |event| {
+        let _ = tx.send(event.clone());
+    });
+
+    cx.spawn(async move |cx| {
+        while let Some(event) = rx.recv().await {
+            let _ = cx.update(|cx| on_agent_event(event, cx));
+        }
+    })
+    .detach();
+}
+
+fn on_agent_event(event: AgentConfigEvent, cx: &mut App) {
+    let state = cx.global_mut::<AgentStartupErrors>();
+    match event {
+        AgentConfigEvent::AgentFailed { name, error } => {
+            log::error!("Agent '{}' failed to start: {}", name, error);
+            state.failures.insert(name, error);
+            state.dismissed = false;
+        }
+        AgentConfigEvent::AgentReady { name } | AgentConfigEvent::AgentRemoved { name } => {
+            if state.failures.remove(&name).is_none() {
+                return;
+            }
+        }
+        _ => return,
+    }
+    cx.refresh_windows();
+}
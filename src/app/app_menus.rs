@@ -4,7 +4,10 @@ use rust_i18n::t;
 
 use crate::{
     About, CloseWindow, Open, Quit, SelectLocale, ToggleSearch,
-    app::actions::{SwitchTheme, SwitchThemeMode},
+    app::actions::{
+        FollowSystemThemeMode, OpenLogViewer, ResetLayout, SwitchTheme, SwitchThemeMode,
+    },
+    app::themes,
 };
 
 pub fn init(title: impl Into<SharedString>, cx: &mut App) {
@@ -27,7 +30,15 @@ pub fn init(title: impl Into<SharedString>, cx: &mut App) {
                             t!("menu.app.appearance.dark").to_string(),
                             SwitchThemeMode(ThemeMode::Dark),
                         ),
-                    ],
+                    ]
+                    .into_iter()
+                    .chain(themes::system_appearance_supported().then(|| {
+                        MenuItem::action(
+                            t!("menu.app.appearance.auto").to_string(),
+                            FollowSystemThemeMode,
+                        )
+                    }))
+                    .collect(),
                 }),
                 theme_menu(cx),
                 language_menu(cx),
@@ -87,14 +98,16 @@ pub fn init(title: impl Into<SharedString>, cx: &mut App) {
                 MenuItem::action(t!("menu.window.close").to_string(), CloseWindow),
                 MenuItem::separator(),
                 MenuItem::action(t!("menu.window.toggle_search").to_string(), ToggleSearch),
+                MenuItem::separator(),
+                MenuItem::action(t!("menu.window.reset_layout").to_string(), ResetLayout),
             ],
         },
         Menu {
             name: t!("menu.help.title").to_string().into(),
-            items: vec![MenuItem::action(
-                t!("menu.help.open_website").to_string(),
-                Open,
-            )],
+            items: vec![
+                MenuItem::action(t!("menu.help.open_website").to_string(), Open),
+                MenuItem::action(t!("menu.help.view_logs").to_string(), OpenLogViewer),
+            ],
         },
     ]);
 }
@@ -102,16 +115,10 @@ pub fn init(title: impl Into<SharedString>, cx: &mut App) {
 fn language_menu(_cx: &App) -> MenuItem {
     MenuItem::Submenu(Menu {
         name: t!("menu.app.language").to_string().into(),
-        items: vec![
-            MenuItem::action(
-                t!("menu.app.language.english").to_string(),
-                SelectLocale("en".into()),
-            ),
-            MenuItem::action(
-                t!("menu.app.language.zh_cn").to_string(),
-                SelectLocale("zh-CN".into()),
-            ),
-        ],
+        items: crate::i18n::available_locales()
+            .into_iter()
+            .map(|(locale, name)| MenuItem::action(name, SelectLocale(locale.into())))
+            .collect(),
     })
 }
 
@@ -1,14 +1,16 @@
 use gpui::{App, AppContext, Entity, Global, SharedString};
+use std::collections::HashSet;
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::{
     core::agent::{AgentManager, PermissionStore},
-    core::config::DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES,
+    core::config::{DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES, ProxyConfig},
     core::event_bus::EventHub,
     core::services::{
-        AgentConfigService, AgentService, AiService, MessageService, PersistenceService,
-        WorkspaceService,
+        AgentConfigService, AgentService, AiService, CompactionReport, MessageService,
+        PersistenceService, RetentionPolicy, RetryPolicy, SessionStatus, WorkspaceService,
     },
 };
 
@@ -150,6 +152,11 @@ impl AppState {
         // Initialize services when agent_manager is set
         let mut agent_service = AgentService::new(manager.clone());
         agent_service.set_event_hub(event_hub.clone());
+        agent_service.set_agent_request_timeouts(&initial_config.agent_servers);
+        agent_service.set_retry_policy(RetryPolicy::from(&initial_config.request_retry));
+        if let Ok(workspace_service) = self.services.workspace_service() {
+            agent_service.set_workspace_service(workspace_service.clone());
+        }
         let agent_service = Arc::new(agent_service);
 
         let message_service = Arc::new(MessageService::new(
@@ -265,6 +272,31 @@ impl AppState {
         self.services.persistence_service().ok()
     }
 
+    /// Apply a retention policy to the persisted sessions
+    ///
+    /// Sessions with a prompt in flight are spared so their logs are never
+    /// rewritten while the agent is still answering.
+    pub fn compact_session_storage(
+        &self,
+        policy: RetentionPolicy,
+    ) -> Option<impl Future<Output = anyhow::Result<CompactionReport>> + 'static> {
+        let persistence_service = self.persistence_service()?.clone();
+        let in_progress: HashSet<String> = self
+            .agent_service()
+            .map(|service| service.list_workspace_sessions())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|session| {
+                matches!(
+                    session.status,
+                    SessionStatus::InProgress | SessionStatus::Pending
+                )
+            })
+            .map(|session| session.session_id)
+            .collect();
+        Some(async move { persistence_service.compact(policy, in_progress).await })
+    }
+
     pub fn workspace_service(&self) -> Option<&Arc<WorkspaceService>> {
         self.services.workspace_service().ok()
     }
@@ -273,11 +305,21 @@ impl AppState {
         self.services.agent_config_service().ok()
     }
 
+    /// Proxy settings from the agent config (disabled until the config is loaded)
+    pub fn proxy_config(&self) -> ProxyConfig {
+        self.agent_config_service()
+            .map(|service| service.proxy_config())
+            .unwrap_or_default()
+    }
+
     pub fn ai_service(&self) -> Option<&Arc<AiService>> {
         self.services.ai_service().ok()
     }
 
     /// Get the current working directory
+    ///
+    /// This is only the default for new panels; each session keeps its own cwd
+    /// (see `AgentService::session_cwd`).
     pub fn current_working_dir(&self) -> &PathBuf {
         &self.current_working_dir
     }
@@ -292,5 +334,10 @@ impl AppState {
     pub fn tool_call_preview_max_lines(&self) -> usize {
         self.tool_call_preview_max_lines
     }
+
+    /// Set the tool call preview line limit (persisted separately via the config service)
+    pub fn set_tool_call_preview_max_lines(&mut self, max_lines: usize) {
+        self.tool_call_preview_max_lines = max_lines;
+    }
 }
 impl Global for AppState {}
@@ -0,0 +1,114 @@
+//! Recovery from a config file that failed to parse at startup
+//!
+//! The broken file is moved aside so saving settings can't overwrite it, and
+//! the app runs on the embedded default config meanwhile. The workspace banner
+//! shows the parse error and lets the user fix the file and load it, or keep
+//! the default.
+
+use std::path::PathBuf;
+
+use gpui::{App, Global};
+
+use crate::{AppState, utils::external_editor};
+
+/// A config file that could not be parsed
+#[derive(Clone)]
+pub struct ConfigLoadFailure {
+    /// Where the config is read from and saved to
+    pub config_path: PathBuf,
+    /// The broken file after moving it aside; `None` if it couldn't be moved
+    pub preserved_path: Option<PathBuf>,
+    /// Parse error with the offending line, or why loading the fixed file failed
+    pub detail: String,
+}
+
+impl ConfigLoadFailure {
+    /// The file holding the user's broken config
+    pub fn broken_file(&self) -> &PathBuf {
+        self.preserved_path.as_ref().unwrap_or(&self.config_path)
+    }
+}
+
+#[derive(Default)]
+pub struct ConfigRecovery {
+    failure: Option<ConfigLoadFailure>,
+}
+
+impl Global for ConfigRecovery {}
+
+impl ConfigRecovery {
+    /// The unresolved config failure, if the app is running on the fallback config
+    pub fn failure(cx: &App) -> Option<ConfigLoadFailure> {
+        cx.try_global::<Self>()
+            .and_then(|state| state.failure.clone())
+    }
+
+    /// Record a config that failed to parse, shown until the user resolves it
+    pub fn report(failure: ConfigLoadFailure, cx: &mut App) {
+        cx.set_global(Self {
+            failure: Some(failure),
+        });
+        cx.refresh_windows();
+    }
+
+    /// Open the broken file in the system editor
+    pub fn edit(cx: &App) -> Result<(), String> {
+        let failure = Self::failure(cx).ok_or_else(|| "No config error to fix".to_string())?;
+        external_editor::open_in_system_editor(failure.broken_file())
+    }
+
+    /// Load the (fixed) broken file as the config, restarting agents as needed
+    ///
+    /// On failure the banner stays with the new error.
+    pub fn load_fixed(cx: &mut App) {
+        let Some(failure) = Self::failure(cx) else {
+            return;
+        };
+        let Some(service) = AppState::global(cx).agent_config_service().cloned() else {
+            log::error!("AgentConfigService not initialized");
+            return;
+        };
+        cx.spawn(async move |cx| {
+            let result = service.restore_backup(failure.broken_file()).await;
+            let _ = cx.update(|cx| match result {
+                Ok(()) => {
+                    log::info!("Loaded fixed config from {:?}", failure.broken_file());
+                    Self::resolve(cx);
+                }
+                Err(e) => {
+                    log::error!("Fixed config still can't be loaded: {:#}", e);
+                    Self::report(
+                        ConfigLoadFailure {
+                            detail: format!("{:#}", e),
+                            ..failure
+                        },
+                        cx,
+                    );
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Keep the default config the app is running on and save it as the config file
+    ///
+    /// The broken file stays where it was moved to.
+    pub fn keep_default(cx: &mut App) {
+        let Some(service) = AppState::global(cx).agent_config_service().cloned() else {
+            log::error!("AgentConfigService not initialized");
+            return;
+        };
+        cx.spawn(async move |cx| match service.save_current_config().await {
+            Ok(()) => {
+                let _ = cx.update(Self::resolve);
+            }
+            Err(e) => log::error!("Failed to save the default config: {:#}", e),
+        })
+        .detach();
+    }
+
+    fn resolve(cx: &mut App) {
+        cx.set_global(Self::default());
+        cx.refresh_windows();
+    }
+}
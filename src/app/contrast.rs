@@ -0,0 +1,96 @@
+//! Minimum contrast enforcement for low-contrast theme colors
+//!
+//! Ratios follow WCAG 2.x: 4.5:1 for text and 3:1 for UI component borders.
+
+use gpui::{Hsla, Rgba};
+use gpui_component::Theme;
+
+/// Minimum contrast ratio for secondary text such as `muted_foreground`
+pub const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// Minimum contrast ratio for borders
+pub const MIN_BORDER_CONTRAST: f32 = 3.0;
+
+/// Relative luminance of a color, ignoring alpha
+fn relative_luminance(color: Hsla) -> f32 {
+    fn channel(c: f32) -> f32 {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    let rgb = Rgba::from(color);
+    0.2126 * channel(rgb.r) + 0.7152 * channel(rgb.g) + 0.0722 * channel(rgb.b)
+}
+
+/// Contrast ratio between two colors, from 1.0 (identical) to 21.0 (black on white)
+pub fn contrast_ratio(a: Hsla, b: Hsla) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Adjust the lightness of `color` away from `background` until the pair
+/// reaches `min_ratio`; colors that already pass are returned unchanged
+pub fn ensure_contrast(color: Hsla, background: Hsla, min_ratio: f32) -> Hsla {
+    if contrast_ratio(color, background) >= min_ratio {
+        return color;
+    }
+
+    let darken = relative_luminance(background) > 0.5;
+    let mut adjusted = Hsla { a: 1.0, ..color };
+    for _ in 0..50 {
+        adjusted.l = if darken {
+            (adjusted.l - 0.02).max(0.0)
+        } else {
+            (adjusted.l + 0.02).min(1.0)
+        };
+        if contrast_ratio(adjusted, background) >= min_ratio
+            || adjusted.l <= 0.0
+            || adjusted.l >= 1.0
+        {
+            break;
+        }
+    }
+    adjusted
+}
+
+/// Raise the contrast of secondary text and borders in `theme` against its background
+pub fn enforce_min_contrast(theme: &mut Theme) {
+    let background = theme.background;
+    theme.muted_foreground = ensure_contrast(theme.muted_foreground, background, MIN_TEXT_CONTRAST);
+    theme.border = ensure_contrast(theme.border, background, MIN_BORDER_CONTRAST);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{black, hsla, white};
+
+    #[test]
+    fn test_contrast_ratio_bounds() {
+        assert!((contrast_ratio(black(), white()) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(white(), white()) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_ensure_contrast_raises_low_contrast_colors() {
+        let light_gray = hsla(0., 0., 0.8, 1.);
+        let adjusted = ensure_contrast(light_gray, white(), MIN_TEXT_CONTRAST);
+        assert!(contrast_ratio(adjusted, white()) >= MIN_TEXT_CONTRAST);
+        assert!(adjusted.l < light_gray.l);
+
+        let dark_gray = hsla(0., 0., 0.25, 1.);
+        let adjusted = ensure_contrast(dark_gray, black(), MIN_TEXT_CONTRAST);
+        assert!(contrast_ratio(adjusted, black()) >= MIN_TEXT_CONTRAST);
+        assert!(adjusted.l > dark_gray.l);
+
+        // Already readable colors are kept as they are
+        assert_eq!(
+            ensure_contrast(black(), white(), MIN_TEXT_CONTRAST),
+            black()
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! OS-level notifications for agents that finish while the window is in the background
+//!
+//! Status updates are collected for a short debounce window so that a burst of
+//! sessions finishing at once produces a single notification.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use agentx_types::SessionStatus;
+use gpui::{App, Global, Window};
+use rust_i18n::t;
+
+use crate::{AppSettings, AppState, core::event_bus::WorkspaceUpdateEvent};
+
+/// Time to wait for more sessions to finish before showing a notification
+const NOTIFICATION_DEBOUNCE: Duration = Duration::from_secs(3);
+/// Maximum number of characters of the last message shown in the body
+const SNIPPET_MAX_CHARS: usize = 120;
+/// How long after a notification without click support the next focus still opens its session
+const FOCUS_FALLBACK_EXPIRY: Duration = Duration::from_secs(15);
+
+/// A session that finished while the window was unfocused
+#[derive(Clone, Debug)]
+struct FinishedSession {
+    session_id: String,
+    agent_name: String,
+}
+
+#[derive(Default)]
+pub struct DesktopNotifications {
+    /// Sessions currently in progress, used to detect when they finish
+    ///
+    /// Only in-progress sessions are tracked, so a session's entry is dropped
+    /// as soon as it finishes, fails or is closed.
+    in_progress: HashSet<String>,
+    /// Sessions waiting for the debounce window to elapse
+    queued: Vec<FinishedSession>,
+    flush_scheduled: bool,
+    /// Session to open the next time the main window is focused, set by a click
+    pending_open: Option<String>,
+    /// Session of the last notification shown where clicks can't be observed;
+    /// the first focus takes it and opens it only before it expires
+    focus_fallback: Option<(String, Instant)>,
+}
+
+impl Global for DesktopNotifications {}
+
+impl DesktopNotifications {
+    /// Take the session that should be opened after a notification was clicked
+    ///
+    /// Only returns a session while `window` is active, so callers can check
+    /// this on every render.
+    pub fn take_pending_session(window: &Window, cx: &mut App) -> Option<String> {
+        if !cx.has_global::<Self>() || !window.is_window_active() {
+            return None;
+        }
+        let state = cx.global_mut::<Self>();
+        // Any focus consumes the fallback, so a later unrelated focus never opens it
+        let fallback = state
+            .focus_fallback
+            .take()
+            .filter(|(_, shown_at)| shown_at.elapsed() < FOCUS_FALLBACK_EXPIRY)
+            .map(|(session_id, _)| session_id);
+        state.pending_open.take().or(fallback)
+    }
+}
+
+/// Bring the main window to the front and open a session in it
+///
+/// The session is picked up by the workspace on its next render.
+pub fn open_session(session_id: String, cx: &mut App) {
+    cx.global_mut::<DesktopNotifications>().pending_open = Some(session_id);
+    if let Some(window) = cx.windows().first() {
+        let _ = window.update(cx, |_, window, _| {
+            window.activate_window();
+            window.refresh();
+        });
+    }
+}
+
+/// Show a plain notification without click handling
+pub fn show_message(title: String, body: String) {
+    std::thread::spawn(move || {
+        if let Err(e) = notify_rust::Notification::new()
+            .appname("AgentX Studio")
+            .summary(&title)
+            .body(&body)
+            .show()
+        {
+            log::warn!("Failed to show desktop notification: {}", e);
+        }
+    });
+}
+
+/// Subscribe to session status updates and notify when agents finish
+pub fn init(cx: &mut App) {
+    cx.set_global(DesktopNotifications::default());
+
+    let event_hub = AppState::global(cx).event_hub().clone();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+    event_hub.subscribe_workspace_updates(move |event| {
+        if let WorkspaceUpdateEvent::SessionStatusUpdated {
+            session_id,
+            agent_name,
+            status,
+            ..
+        } = event
+        {
+            let _ = tx.send((session_id.clone(), agent_name.clone(), status.clone()));
+        }
+    });
+
+    cx.spawn(async move |cx| {
+        while let Some((session_id, agent_name, status)) = rx.recv().await {
+            let _ = cx.update(|cx| on_status_updated(session_id, agent_name, status, cx));
+        }
+    })
+    .detach();
+}
+
+fn on_status_updated(session_id: String, agent_name: String, status: SessionStatus, cx: &mut App) {
+    let in_progress = &mut cx.global_mut::<DesktopNotifications>().in_progress;
+    let was_in_progress = if status == SessionStatus::InProgress {
+        !in_progress.insert(session_id.clone())
+    } else {
+        in_progress.remove(&session_id)
+    };
+
+    let finished =
+        was_in_progress && matches!(status, SessionStatus::Completed | SessionStatus::Idle);
+    if !finished {
+        return;
+    }
+
+    let settings = AppSettings::global(cx);
+    if !settings.notifications_enabled || settings.muted_agents.contains(&agent_name) {
+        return;
+    }
+    if any_window_active(cx) {
+        return;
+    }
+
+    let state = cx.global_mut::<DesktopNotifications>();
+    state.queued.retain(|s| s.session_id != session_id);
+    state.queued.push(FinishedSession {
+        session_id,
+        agent_name,
+    });
+
+    if state.flush_scheduled {
+        return;
+    }
+    state.flush_scheduled = true;
+
+    cx.spawn(async move |cx| {
+        smol::Timer::after(NOTIFICATION_DEBOUNCE).await;
+
+        let Ok((sessions, workspace_service)) = cx.update(|cx| {
+            let state = cx.global_mut::<DesktopNotifications>();
+            state.flush_scheduled = false;
+            let sessions = std::mem::take(&mut state.queued);
+            (sessions, AppState::global(cx).workspace_service().cloned())
+        }) else {
+            return;
+        };
+
+        let Some(latest) = sessions.last().cloned() else {
+            return;
+        };
+        // The user may have come back while we were waiting
+        if cx.update(|cx| any_window_active(cx)).unwrap_or(true) {
+            return;
+        }
+
+        let (title, body) = if sessions.len() == 1 {
+            let snippet = match &workspace_service {
+                Some(service) => service
+                    .get_task_by_session(&latest.session_id)
+                    .await
+                    .and_then(|task| task.last_message),
+                None => None,
+            };
+            (
+                t!(
+                    "notifications.agent_finished.title",
+                    agent = latest.agent_name
+                )
+                .to_string(),
+                snippet
+                    .map(|text| truncate_snippet(&text))
+                    .unwrap_or_else(|| t!("notifications.agent_finished.body").to_string()),
+            )
+        } else {
+            let mut agents: Vec<&str> = sessions.iter().map(|s| s.agent_name.as_str()).collect();
+            agents.sort_unstable();
+            agents.dedup();
+            (
+                t!(
+                    "notifications.agents_finished.title",
+                    count = sessions.len()
+                )
+                .to_string(),
+                agents.join(", "),
+            )
+        };
+
+        // On platforms without click callbacks, activating the app via the
+        // notification is the only signal we get, so open the session if the
+        // window is focused shortly after
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        let _ = cx.update(|cx| {
+            cx.global_mut::<DesktopNotifications>().focus_fallback =
+                Some((latest.session_id.clone(), Instant::now()));
+        });
+
+        let (click_tx, mut click_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let session_id = latest.session_id.clone();
+        std::thread::spawn(move || show_notification(&title, &body, session_id, click_tx));
+
+        if let Some(session_id) = click_rx.recv().await {
+            let _ = cx.update(|cx| open_session(session_id, cx));
+        }
+    })
+    .detach();
+}
+
+/// Show the notification and report clicks (blocking; runs on its own thread)
+fn show_notification(
+    title: &str,
+    body: &str,
+    session_id: String,
+    on_click: tokio::sync::mpsc::UnboundedSender<String>,
+) {
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .appname("AgentX Studio")
+        .summary(title)
+        .body(body);
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        notification.action("default", &t!("notifications.action.open"));
+        match notification.show() {
+            Ok(handle) => handle.wait_for_action(|action| {
+                if action == "default" {
+                    let _ = on_click.send(session_id);
+                }
+            }),
+            Err(e) => log::warn!("Failed to show desktop notification: {}", e),
+        }
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = (session_id, on_click);
+        if let Err(e) = notification.show() {
+            log::warn!("Failed to show desktop notification: {}", e);
+        }
+    }
+}
+
+fn any_window_active(cx: &mut App) -> bool {
+    cx.windows().iter().any(|window| {
+        window
+            .update(cx, |_, window, _| window.is_window_active())
+            .unwrap_or(false)
+    })
+}
+
+fn truncate_snippet(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= SNIPPET_MAX_CHARS {
+        return text;
+    }
+    let mut snippet: String = text.chars().take(SNIPPET_MAX_CHARS).collect();
+    snippet.push('…');
+    snippet
+}
@@ -1,6 +1,9 @@
 use gpui::{App, KeyBinding};
 
-use crate::app::actions::{Open, Paste, Quit, ToggleSearch};
+use crate::app::actions::{
+    AddSelectionToChat, FocusNextField, FocusPreviousField, NextHunk, Open, Paste, PreviousHunk,
+    Quit, SaveFile, ToggleSearch,
+};
 use gpui_term::{Clear, Copy, SelectAll};
 
 // 导出KeyBinding设置函数,供主应用使用
@@ -19,6 +22,21 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("cmd-v", Paste, None),
         #[cfg(not(target_os = "macos"))]
         KeyBinding::new("ctrl-v", Paste, None),
+        // Code editor keybindings
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-s", SaveFile, Some("CodeEditor")),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-s", SaveFile, Some("CodeEditor")),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-l", AddSelectionToChat, Some("CodeEditor")),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-l", AddSelectionToChat, Some("CodeEditor")),
+        // Tool call detail keybindings
+        KeyBinding::new("n", NextHunk, Some("ToolCallDetail")),
+        KeyBinding::new("shift-n", PreviousHunk, Some("ToolCallDetail")),
+        // Settings dialog keybindings
+        KeyBinding::new("tab", FocusNextField, Some("SettingsDialog")),
+        KeyBinding::new("shift-tab", FocusPreviousField, Some("SettingsDialog")),
         // Terminal keybindings
         #[cfg(target_os = "macos")]
         KeyBinding::new("cmd-c", Copy, Some("Terminal")),
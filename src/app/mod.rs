@@ -1,8 +1,13 @@
 pub mod actions;
+pub mod agent_startup_errors;
 pub mod app_menus;
 pub mod app_state;
+pub mod config_recovery;
+pub mod contrast;
+pub mod desktop_notifications;
 pub mod key_binding;
 pub mod menu;
+pub mod profiles;
 pub mod service_registry;
 pub mod system_tray;
 pub mod themes;
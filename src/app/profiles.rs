@@ -0,0 +1,63 @@
+//! Switching between named profiles.
+//!
+//! A profile is a separate data directory (config, workspaces, sessions, ...)
+//! chosen at launch with `--profile NAME`. Switching starts a new instance in
+//! the selected profile; the caller then quits the running one.
+
+use anyhow::{Context as _, Result};
+use std::process::Command;
+
+/// The current command line with any `--profile` replaced by `profile`;
+/// `None` relaunches into the default profile
+fn relaunch_args(args: impl IntoIterator<Item = String>, profile: Option<&str>) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            args.next();
+            continue;
+        }
+        result.push(arg);
+    }
+    if let Some(profile) = profile {
+        result.push("--profile".to_string());
+        result.push(profile.to_string());
+    }
+    result
+}
+
+/// Start a new instance of the app in `profile`
+pub fn relaunch_into(profile: Option<&str>) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the running executable")?;
+    let args = relaunch_args(std::env::args().skip(1), profile);
+    log::info!("Relaunching {:?} into profile {:?}", exe, profile);
+    Command::new(&exe)
+        .args(&args)
+        .spawn()
+        .with_context(|| format!("Failed to relaunch {}", exe.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn relaunch_args_replace_the_profile() {
+        assert_eq!(
+            relaunch_args(
+                args(&["--profile", "work", "--data-dir", "/d"]),
+                Some("home")
+            ),
+            args(&["--data-dir", "/d", "--profile", "home"])
+        );
+        assert_eq!(
+            relaunch_args(args(&["--profile", "work"]), None),
+            Vec::<String>::new()
+        );
+    }
+}
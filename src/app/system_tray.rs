@@ -1,13 +1,34 @@
 use anyhow::{Context, Result};
-use std::sync::Arc;
+use gpui::{App, AsyncApp, Global};
+use rust_i18n::t;
+use std::time::Duration;
 use tray_icon::{
     MouseButton, TrayIcon, TrayIconBuilder, TrayIconEvent, TrayIconId,
-    menu::{Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem},
+    menu::{Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem, Submenu},
+};
+
+use crate::{
+    AppSettings, AppState, StartTaskSession,
+    app::desktop_notifications,
+    core::{
+        event_bus::WorkspaceUpdateEvent,
+        updater::{UpdateCheckResult, UpdateManager},
+    },
 };
 
 // 定义菜单项ID常量
 const MENU_SHOW_ID: &str = "show_window";
+const MENU_HIDE_ID: &str = "hide_window";
+const MENU_CHECK_UPDATES_ID: &str = "check_updates";
 const MENU_QUIT_ID: &str = "quit_app";
+// 动态菜单项ID前缀，后面拼接会话ID或代理名称
+const MENU_SESSION_PREFIX: &str = "session:";
+const MENU_NEW_SESSION_PREFIX: &str = "new_session:";
+
+// 最近会话列表的最大条目数，避免会话很多时菜单过长
+const RECENT_SESSIONS_LIMIT: usize = 10;
+// 菜单重建的防抖时间，合并短时间内的多次变更
+const MENU_REBUILD_DEBOUNCE: Duration = Duration::from_millis(500);
 
 // 定义唯一的托盘图标 ID，避免与其他应用冲突
 const TRAY_ICON_ID: &str = "plus.agentx.app.tray";
@@ -27,37 +48,32 @@ pub fn init_platform() -> Result<()> {
     Ok(())
 }
 
+/// 托盘菜单中的一个最近会话
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraySession {
+    pub session_id: String,
+    pub label: String,
+}
+
+/// 托盘菜单的动态内容
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrayMenuModel {
+    /// 最近会话，按时间倒序
+    pub recent_sessions: Vec<TraySession>,
+    /// 已配置的代理名称
+    pub agents: Vec<String>,
+}
+
 /// 系统托盘管理器
 pub struct SystemTray {
     tray_icon: TrayIcon,
-    show_menu_id: MenuId,
-    quit_menu_id: MenuId,
 }
 
 impl SystemTray {
     /// 创建系统托盘
     pub fn new() -> Result<Self> {
-        // 创建托盘菜单
-        let tray_menu = Menu::new();
-
-        // 创建菜单项ID
-        let show_menu_id = MenuId::new(MENU_SHOW_ID);
-        let quit_menu_id = MenuId::new(MENU_QUIT_ID);
-
-        // 添加显示/隐藏窗口菜单项
-        let show_item = MenuItem::with_id(show_menu_id.clone(), "显示主窗口", true, None);
-        let separator = PredefinedMenuItem::separator();
-        let quit_item = MenuItem::with_id(quit_menu_id.clone(), "退出", true, None);
-
-        tray_menu
-            .append(&show_item)
-            .context("Failed to append show item")?;
-        tray_menu
-            .append(&separator)
-            .context("Failed to append separator")?;
-        tray_menu
-            .append(&quit_item)
-            .context("Failed to append quit item")?;
+        // 创建托盘菜单（代理和会话列表稍后异步填充）
+        let tray_menu = build_menu(&TrayMenuModel::default())?;
 
         // 加载托盘图标
         let icon = load_icon()?;
@@ -74,23 +90,163 @@ impl SystemTray {
             .build()
             .context("Failed to build tray icon")?;
 
-        Ok(Self {
-            tray_icon,
-            show_menu_id,
-            quit_menu_id,
-        })
+        Ok(Self { tray_icon })
+    }
+
+    /// 使用新的内容重建托盘菜单
+    pub fn update_menu(&self, model: &TrayMenuModel) -> Result<()> {
+        let menu = build_menu(model)?;
+        self.tray_icon.set_menu(Some(Box::new(menu)));
+        Ok(())
     }
 }
 
+/// 构建托盘菜单
+fn build_menu(model: &TrayMenuModel) -> Result<Menu> {
+    let tray_menu = Menu::new();
+
+    // 最近会话子菜单
+    let recent_menu = Submenu::new(
+        t!("tray.recent_sessions").to_string(),
+        !model.recent_sessions.is_empty(),
+    );
+    for session in &model.recent_sessions {
+        let item = MenuItem::with_id(
+            MenuId::new(format!("{}{}", MENU_SESSION_PREFIX, session.session_id)),
+            &session.label,
+            true,
+            None,
+        );
+        recent_menu
+            .append(&item)
+            .context("Failed to append recent session item")?;
+    }
+
+    // 每个代理一个"新建会话"菜单项
+    let new_session_menu =
+        Submenu::new(t!("tray.new_session").to_string(), !model.agents.is_empty());
+    for agent in &model.agents {
+        let item = MenuItem::with_id(
+            MenuId::new(format!("{}{}", MENU_NEW_SESSION_PREFIX, agent)),
+            agent,
+            true,
+            None,
+        );
+        new_session_menu
+            .append(&item)
+            .context("Failed to append new session item")?;
+    }
+
+    let show_item = MenuItem::with_id(
+        MenuId::new(MENU_SHOW_ID),
+        t!("tray.show_window"),
+        true,
+        None,
+    );
+    let hide_item = MenuItem::with_id(
+        MenuId::new(MENU_HIDE_ID),
+        t!("tray.hide_window"),
+        true,
+        None,
+    );
+    let check_updates_item = MenuItem::with_id(
+        MenuId::new(MENU_CHECK_UPDATES_ID),
+        t!("tray.check_updates"),
+        true,
+        None,
+    );
+    let quit_item = MenuItem::with_id(MenuId::new(MENU_QUIT_ID), t!("tray.quit"), true, None);
+
+    tray_menu
+        .append_items(&[
+            &recent_menu,
+            &new_session_menu,
+            &PredefinedMenuItem::separator(),
+            &show_item,
+            &hide_item,
+            &check_updates_item,
+            &PredefinedMenuItem::separator(),
+            &quit_item,
+        ])
+        .context("Failed to append tray menu items")?;
+
+    Ok(tray_menu)
+}
+
 /// 托盘事件类型
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TrayEvent {
     /// 显示窗口
     Show,
+    /// 隐藏（最小化）窗口
+    Hide,
+    /// 检查更新
+    CheckForUpdates,
+    /// 打开指定会话
+    OpenSession(String),
+    /// 使用指定代理新建会话
+    NewSession(String),
     /// 退出应用
     Quit,
 }
 
+impl TrayEvent {
+    /// 根据菜单项ID解析托盘事件
+    fn from_menu_id(id: &str) -> Option<Self> {
+        match id {
+            MENU_SHOW_ID => Some(TrayEvent::Show),
+            MENU_HIDE_ID => Some(TrayEvent::Hide),
+            MENU_CHECK_UPDATES_ID => Some(TrayEvent::CheckForUpdates),
+            MENU_QUIT_ID => Some(TrayEvent::Quit),
+            _ => {
+                if let Some(session_id) = id.strip_prefix(MENU_SESSION_PREFIX) {
+                    Some(TrayEvent::OpenSession(session_id.to_string()))
+                } else {
+                    id.strip_prefix(MENU_NEW_SESSION_PREFIX)
+                        .map(|agent| TrayEvent::NewSession(agent.to_string()))
+                }
+            }
+        }
+    }
+}
+
+/// 保存托盘实例的全局状态，保持托盘图标的生命周期
+struct TrayState {
+    tray: SystemTray,
+    rebuild_scheduled: bool,
+    /// 当前菜单的内容，内容未变化时跳过重建
+    model: TrayMenuModel,
+}
+
+impl Global for TrayState {}
+
+/// 托盘图标是否已成功创建
+pub fn is_available(cx: &App) -> bool {
+    cx.has_global::<TrayState>()
+}
+
+/// 将窗口隐藏到托盘，应用和代理继续在后台运行
+///
+/// GPUI 没有跨平台的隐藏窗口接口：macOS 上隐藏整个应用，其他平台最小化窗口
+pub fn hide_to_tray(window: &mut gpui::Window, cx: &mut App) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = window;
+        cx.hide();
+    }
+    #[cfg(not(target_os = "macos"))]
+    window.minimize_window();
+
+    // 第一次隐藏时提示用户应用仍在托盘中运行
+    if !crate::themes::close_to_tray_hint_shown() {
+        crate::themes::set_close_to_tray_hint_shown();
+        desktop_notifications::show_message(
+            t!("tray.hint.title").to_string(),
+            t!("tray.hint.body").to_string(),
+        );
+    }
+}
+
 /// 加载托盘图标
 fn load_icon() -> Result<tray_icon::Icon> {
     // 使用内嵌的 logo 图片
@@ -111,21 +267,20 @@ fn load_icon() -> Result<tray_icon::Icon> {
 /// 注册托盘事件处理器到 GPUI
 ///
 /// 这个函数会启动一个后台线程,持续监听托盘事件并触发相应的操作
-pub fn setup_tray_event_handler(tray: SystemTray, cx: &mut gpui::App) {
-    use gpui::AppContext;
-
+pub fn setup_tray_event_handler(tray: SystemTray, cx: &mut App) {
     // 获取全局菜单事件接收器
     let menu_event_receiver = MenuEvent::receiver().clone();
     // 获取托盘图标事件接收器
     let tray_icon_event_receiver = TrayIconEvent::receiver().clone();
 
-    // 提取菜单ID用于后台线程
-    let show_menu_id = tray.show_menu_id.clone();
-    let quit_menu_id = tray.quit_menu_id.clone();
-
-    // 将 SystemTray 存储为 static，保持托盘图标的生命周期
-    // 这样托盘图标就不会被销毁
-    let _tray = Box::leak(Box::new(tray));
+    // 将 SystemTray 存储为全局状态，保持托盘图标的生命周期
+    // 这样托盘图标就不会被销毁，并且可以在会话或代理变化时重建菜单
+    cx.set_global(TrayState {
+        tray,
+        rebuild_scheduled: false,
+        model: TrayMenuModel::default(),
+    });
+    subscribe_menu_updates(cx);
 
     // 创建通道用于跨线程通信
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<TrayEvent>();
@@ -169,18 +324,11 @@ pub fn setup_tray_event_handler(tray: SystemTray, cx: &mut gpui::App) {
             // 轮询菜单事件
             if let Ok(event) = menu_event_receiver.try_recv() {
                 has_event = true;
-                let menu_id = event.id();
 
                 // 根据菜单ID判断操作
-                let tray_event = if menu_id == &show_menu_id {
-                    Some(TrayEvent::Show)
-                } else if menu_id == &quit_menu_id {
-                    Some(TrayEvent::Quit)
-                } else {
-                    None
-                };
+                if let Some(tray_event) = TrayEvent::from_menu_id(&event.id().0) {
+                    let is_quit = tray_event == TrayEvent::Quit;
 
-                if let Some(tray_event) = tray_event {
                     // 发送事件到通道
                     if tx.send(tray_event).is_err() {
                         log::error!("Failed to send tray event, receiver dropped");
@@ -188,7 +336,7 @@ pub fn setup_tray_event_handler(tray: SystemTray, cx: &mut gpui::App) {
                     }
 
                     // 如果是退出事件，停止轮询
-                    if tray_event == TrayEvent::Quit {
+                    if is_quit {
                         break;
                     }
                 }
@@ -217,6 +365,52 @@ pub fn setup_tray_event_handler(tray: SystemTray, cx: &mut gpui::App) {
                         }
                     });
                 }
+                TrayEvent::Hide => {
+                    log::info!("Tray event: Hide window");
+                    let _ = cx.update(|cx| {
+                        if let Some(window) = cx.windows().first() {
+                            let _ = window.update(cx, |_, window, _| {
+                                window.minimize_window();
+                            });
+                        }
+                    });
+                }
+                TrayEvent::OpenSession(session_id) => {
+                    log::info!("Tray event: Open session {}", session_id);
+                    let _ = cx.update(|cx| desktop_notifications::open_session(session_id, cx));
+                }
+                TrayEvent::NewSession(agent_name) => {
+                    log::info!("Tray event: New session for agent {}", agent_name);
+                    // Don't hold up other tray events while the agent starts the session
+                    cx.spawn(async move |cx| new_task_session(agent_name, cx).await)
+                        .detach();
+                }
+                TrayEvent::CheckForUpdates => {
+                    log::info!("Tray event: Check for updates");
+                    let proxy = cx
+                        .update(|cx| AppState::global(cx).proxy_config())
+                        .unwrap_or_default();
+                    let result = UpdateManager::default()
+                        .with_proxy(proxy)
+                        .check_for_updates()
+                        .await;
+                    let _ = cx.update(|cx| {
+                        AppSettings::global_mut(cx).record_update_check(&result);
+                    });
+                    let body = match result {
+                        UpdateCheckResult::UpdateAvailable(info) => {
+                            t!("settings.update.status.available", version = info.version)
+                        }
+                        UpdateCheckResult::NoUpdate => t!("settings.update.status.up_to_date"),
+                        UpdateCheckResult::Error(err) => {
+                            t!("settings.update.status.error", error = err)
+                        }
+                    };
+                    desktop_notifications::show_message(
+                        t!("settings.update.title").to_string(),
+                        body.to_string(),
+                    );
+                }
                 TrayEvent::Quit => {
                     // 退出应用
                     log::info!("Tray event: Quit application");
@@ -232,3 +426,175 @@ pub fn setup_tray_event_handler(tray: SystemTray, cx: &mut gpui::App) {
     })
     .detach();
 }
+
+/// 在当前工作区为代理新建任务，再通过 StartTaskSession 启动会话并打开
+///
+/// 与任务面板走同一流程，会话关联到任务，会出现在任务和会话列表中
+async fn new_task_session(agent_name: String, cx: &mut AsyncApp) {
+    let Ok(Some(workspace_service)) =
+        cx.update(|cx| AppState::global(cx).workspace_service().cloned())
+    else {
+        log::warn!("WorkspaceService not initialized, cannot create session");
+        return;
+    };
+    let Some(workspace) = workspace_service.get_active_workspace().await else {
+        desktop_notifications::show_message(
+            t!("tray.new_session").to_string(),
+            t!("tray.new_session_no_workspace").to_string(),
+        );
+        return;
+    };
+
+    let task = match workspace_service
+        .create_task(
+            &workspace.id,
+            t!("tray.new_session").to_string(),
+            agent_name,
+            "Auto".to_string(),
+        )
+        .await
+    {
+        Ok(task) => task,
+        Err(e) => {
+            log::error!("Failed to create task from tray: {}", e);
+            desktop_notifications::show_message(
+                t!("tray.new_session").to_string(),
+                t!("tray.new_session_failed", error = e.to_string()).to_string(),
+            );
+            return;
+        }
+    };
+
+    let _ = cx.update(|cx| {
+        if let Some(window) = cx.windows().first() {
+            let _ = window.update(cx, |_, window, cx| {
+                window.activate_window();
+                window.dispatch_action(Box::new(StartTaskSession { task_id: task.id }), cx);
+            });
+        }
+    });
+}
+
+/// 订阅会话和代理的变化，重建托盘菜单
+fn subscribe_menu_updates(cx: &mut App) {
+    let event_hub = AppState::global(cx).event_hub().clone();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+
+    let workspace_tx = tx.clone();
+    event_hub.subscribe_workspace_updates(move |event| {
+        if matches!(
+            event,
+            WorkspaceUpdateEvent::TaskCreated { .. }
+                | WorkspaceUpdateEvent::TaskRemoved { .. }
+                | WorkspaceUpdateEvent::TaskUpdated { .. }
+                | WorkspaceUpdateEvent::SessionStatusUpdated { .. }
+        ) {
+            let _ = workspace_tx.send(());
+        }
+    });
+
+    let agent_tx = tx.clone();
+    event_hub.subscribe_agent_config_agent_events(move |_| {
+        let _ = agent_tx.send(());
+    });
+
+    // AgentService 在后台初始化完成后才会设置到 AppState
+    cx.observe_global::<AppState>(move |_| {
+        let _ = tx.send(());
+    })
+    .detach();
+
+    cx.spawn(async move |cx| {
+        while rx.recv().await.is_some() {
+            let _ = cx.update(schedule_menu_rebuild);
+        }
+    })
+    .detach();
+}
+
+/// 防抖地重建托盘菜单
+fn schedule_menu_rebuild(cx: &mut App) {
+    let state = cx.global_mut::<TrayState>();
+    if state.rebuild_scheduled {
+        return;
+    }
+    state.rebuild_scheduled = true;
+
+    cx.spawn(async move |cx| {
+        smol::Timer::after(MENU_REBUILD_DEBOUNCE).await;
+
+        let Ok((agent_service, workspace_service)) = cx.update(|cx| {
+            cx.global_mut::<TrayState>().rebuild_scheduled = false;
+            (
+                AppState::global(cx).agent_service().cloned(),
+                AppState::global(cx).workspace_service().cloned(),
+            )
+        }) else {
+            return;
+        };
+
+        let mut model = TrayMenuModel::default();
+
+        if let Some(agent_service) = agent_service {
+            model.agents = agent_service.list_agents().await;
+            model.agents.sort();
+        }
+
+        if let Some(workspace_service) = workspace_service {
+            let archived = workspace_service.archived_sessions().await;
+            let mut tasks: Vec<_> = workspace_service
+                .get_all_tasks()
+                .await
+                .into_iter()
+                .filter(|task| {
+                    task.session_id
+                        .as_ref()
+                        .is_some_and(|id| !archived.contains(id))
+                })
+                .collect();
+            tasks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+            model.recent_sessions = tasks
+                .into_iter()
+                .take(RECENT_SESSIONS_LIMIT)
+                .filter_map(|task| {
+                    Some(TraySession {
+                        label: format!("{} · {}", task.name, task.agent_name),
+                        session_id: task.session_id?,
+                    })
+                })
+                .collect();
+        }
+
+        let _ = cx.update(|cx| {
+            let state = cx.global_mut::<TrayState>();
+            if state.model == model {
+                return;
+            }
+            match state.tray.update_menu(&model) {
+                Ok(()) => state.model = model,
+                Err(e) => log::error!("Failed to rebuild tray menu: {}", e),
+            }
+        });
+    })
+    .detach();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tray_event_from_menu_id() {
+        assert_eq!(TrayEvent::from_menu_id(MENU_QUIT_ID), Some(TrayEvent::Quit));
+        assert_eq!(
+            TrayEvent::from_menu_id("session:abc-123"),
+            Some(TrayEvent::OpenSession("abc-123".into()))
+        );
+        assert_eq!(
+            TrayEvent::from_menu_id("new_session:Claude Code"),
+            Some(TrayEvent::NewSession("Claude Code".into()))
+        );
+        assert_eq!(TrayEvent::from_menu_id("unknown"), None);
+    }
+}
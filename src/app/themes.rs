@@ -1,10 +1,17 @@
-use std::path::PathBuf;
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
 
-use gpui::{App, SharedString, px};
-use gpui_component::{ActiveTheme, Theme, ThemeRegistry, scroll::ScrollbarShow};
+use anyhow::{Context as _, Result, anyhow, bail};
+use gpui::{App, SharedString, Window, WindowAppearance, px};
+use gpui_component::{
+    ActiveTheme, Theme, ThemeConfig, ThemeMode, ThemeRegistry, WindowExt as _,
+    notification::Notification, scroll::ScrollbarShow,
+};
+use rust_i18n::t;
 use serde::{Deserialize, Serialize};
 
-use crate::app::actions::{SwitchTheme, SwitchThemeMode};
+use crate::app::actions::{FollowSystemThemeMode, SwitchTheme, SwitchThemeMode};
+use crate::app::contrast;
 use crate::panels::AppSettings;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +22,10 @@ struct State {
     app_settings: Option<AppSettings>,
     #[serde(default)]
     startup_completed: bool,
+    #[serde(default)]
+    close_to_tray_hint_shown: bool,
+    #[serde(default)]
+    quarantine_warning_shown: bool,
 }
 
 impl Default for State {
@@ -24,6 +35,8 @@ impl Default for State {
             scrollbar_show: None,
             app_settings: None,
             startup_completed: false,
+            close_to_tray_hint_shown: false,
+            quarantine_warning_shown: false,
         }
     }
 }
@@ -56,6 +69,322 @@ pub(crate) fn set_startup_completed(completed: bool) {
     write_state_file(&state);
 }
 
+pub(crate) fn close_to_tray_hint_shown() -> bool {
+    load_state_file().close_to_tray_hint_shown
+}
+
+pub(crate) fn set_close_to_tray_hint_shown() {
+    let mut state = load_state_file();
+    if state.close_to_tray_hint_shown {
+        return;
+    }
+    state.close_to_tray_hint_shown = true;
+    write_state_file(&state);
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn quarantine_warning_shown() -> bool {
+    load_state_file().quarantine_warning_shown
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn set_quarantine_warning_shown() {
+    let mut state = load_state_file();
+    if state.quarantine_warning_shown {
+        return;
+    }
+    state.quarantine_warning_shown = true;
+    write_state_file(&state);
+}
+
+/// Directory the theme registry loads from and watches
+fn themes_dir() -> PathBuf {
+    if cfg!(debug_assertions) {
+        // Debug mode: use local ./themes for development
+        return PathBuf::from("./themes");
+    }
+
+    // Release mode: use user data directory, fallback to ./themes
+    match crate::core::config_manager::initialize_themes_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to initialize user themes directory: {}, falling back to ./themes",
+                e
+            );
+            PathBuf::from("./themes")
+        }
+    }
+}
+
+/// Theme file layout understood by the registry: a named set of themes
+#[derive(Debug, Serialize, Deserialize)]
+struct ThemeFile {
+    name: SharedString,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<SharedString>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<SharedString>,
+    themes: Vec<ThemeConfig>,
+}
+
+/// Parse and check a theme file before it is added to the themes directory
+fn validate_theme_file(json: &str) -> Result<ThemeFile> {
+    let file: ThemeFile =
+        serde_json::from_str(json).map_err(|e| anyhow!("Not a valid theme file: {}", e))?;
+    if file.name.trim().is_empty() {
+        bail!("The theme file has no name");
+    }
+    if file.themes.is_empty() {
+        bail!("The theme file does not contain any themes");
+    }
+    if let Some(theme) = file
+        .themes
+        .iter()
+        .find(|theme| theme.name.trim().is_empty())
+    {
+        bail!(
+            "A theme in '{}' has no name ({:?} mode)",
+            file.name,
+            theme.mode
+        );
+    }
+    Ok(file)
+}
+
+/// File name an imported theme set is stored under, derived from its name
+fn theme_file_name(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('-');
+    format!("{}.json", if slug.is_empty() { "imported" } else { slug })
+}
+
+/// Copy a theme file into the themes directory
+///
+/// The directory watcher adds the themes to the registry, so imported themes
+/// are available right away and after restarts. Returns the theme names.
+fn import_theme_file(path: &Path) -> Result<Vec<SharedString>> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let file = validate_theme_file(&json)?;
+
+    let dir = themes_dir();
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create themes directory: {:?}", dir))?;
+    let target = dir.join(theme_file_name(&file.name));
+    std::fs::write(&target, json)
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    tracing::info!("Imported theme file {:?} to {:?}", path, target);
+
+    Ok(file.themes.into_iter().map(|theme| theme.name).collect())
+}
+
+/// Serialize the active theme as a theme file others can import
+fn export_current_theme(cx: &App) -> Result<(String, String)> {
+    let name = cx.theme().theme_name().clone();
+    let config = ThemeRegistry::global(cx)
+        .themes()
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| anyhow!("Theme '{}' is not in the registry", name))?;
+    let file = ThemeFile {
+        name: name.clone(),
+        author: None,
+        url: None,
+        themes: vec![(*config).clone()],
+    };
+    Ok((theme_file_name(&name), serde_json::to_string_pretty(&file)?))
+}
+
+/// Ask for a theme file and import it, reporting the outcome as a notification
+pub(crate) fn import_theme(window: &mut Window, cx: &mut App) {
+    window
+        .spawn(cx, async move |cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .set_title(t!("settings.general.appearance.theme_files.import_title").to_string())
+                .add_filter("JSON", &["json"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+
+            let path = file.path().to_path_buf();
+            let result = smol::unblock(move || import_theme_file(&path)).await;
+
+            _ = cx.update(|window, cx| {
+                struct ThemeImported;
+                let note = match result {
+                    Ok(names) => Notification::success(
+                        t!(
+                            "settings.general.appearance.theme_files.imported",
+                            themes = names.join(", ")
+                        )
+                        .to_string(),
+                    ),
+                    Err(e) => Notification::error(
+                        t!(
+                            "settings.general.appearance.theme_files.import_failed",
+                            error = e.to_string()
+                        )
+                        .to_string(),
+                    ),
+                }
+                .id::<ThemeImported>();
+                window.push_notification(note, cx);
+            });
+        })
+        .detach();
+}
+
+/// Ask for a destination and write the active theme there
+pub(crate) fn export_theme(window: &mut Window, cx: &mut App) {
+    let exported = export_current_theme(cx);
+
+    window
+        .spawn(cx, async move |cx| {
+            let result = match exported {
+                Ok((file_name, json)) => {
+                    let Some(file) = rfd::AsyncFileDialog::new()
+                        .set_title(
+                            t!("settings.general.appearance.theme_files.export_title").to_string(),
+                        )
+                        .set_file_name(file_name)
+                        .save_file()
+                        .await
+                    else {
+                        return;
+                    };
+                    let path = file.path().to_path_buf();
+                    smol::unblock({
+                        let path = path.clone();
+                        move || std::fs::write(&path, json)
+                    })
+                    .await
+                    .map(|_| path)
+                    .map_err(anyhow::Error::from)
+                }
+                Err(e) => Err(e),
+            };
+
+            _ = cx.update(|window, cx| {
+                struct ThemeExported;
+                let note = match result {
+                    Ok(path) => Notification::success(
+                        t!(
+                            "settings.general.appearance.theme_files.exported",
+                            path = path.display()
+                        )
+                        .to_string(),
+                    ),
+                    Err(e) => Notification::error(
+                        t!(
+                            "settings.general.appearance.theme_files.export_failed",
+                            error = e.to_string()
+                        )
+                        .to_string(),
+                    ),
+                }
+                .id::<ThemeExported>();
+                window.push_notification(note, cx);
+            });
+        })
+        .detach();
+}
+
+/// Whether the OS appearance can be read on this platform
+pub(crate) fn system_appearance_supported() -> bool {
+    cfg!(any(
+        target_os = "macos",
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "freebsd"
+    ))
+}
+
+/// Whether the theme mode follows the OS appearance instead of a manual choice
+pub(crate) fn follows_system_appearance(cx: &App) -> bool {
+    system_appearance_supported() && AppSettings::global(cx).auto_switch_theme
+}
+
+/// Switch the theme mode to match the window's appearance when following the system
+pub(crate) fn sync_system_appearance(window: &mut Window, cx: &mut App) {
+    if !follows_system_appearance(cx) {
+        return;
+    }
+
+    let mode = match window.appearance() {
+        WindowAppearance::Dark | WindowAppearance::VibrantDark => ThemeMode::Dark,
+        WindowAppearance::Light | WindowAppearance::VibrantLight => ThemeMode::Light,
+    };
+    if cx.theme().mode != mode {
+        tracing::info!(
+            "System appearance changed, switching theme mode to {:?}",
+            mode
+        );
+        Theme::change(mode, Some(window), cx);
+        apply_min_contrast(cx);
+        cx.refresh_windows();
+    }
+}
+
+/// Raise low-contrast theme colors when the accessibility setting asks for it
+///
+/// Must run after every change of the theme colors, since applying a theme
+/// config resets them.
+pub(crate) fn apply_min_contrast(cx: &mut App) {
+    if AppSettings::global(cx).enforce_min_contrast {
+        contrast::enforce_min_contrast(Theme::global_mut(cx));
+    }
+}
+
+/// Turn minimum contrast enforcement on or off and re-apply the current theme
+pub(crate) fn set_min_contrast(enabled: bool, cx: &mut App) {
+    if AppSettings::global(cx).enforce_min_contrast == enabled {
+        return;
+    }
+    AppSettings::global_mut(cx).enforce_min_contrast = enabled;
+
+    // Start from the theme's own colors so turning it off restores them
+    let theme_name = cx.theme().theme_name().clone();
+    if let Some(theme) = ThemeRegistry::global(cx).themes().get(&theme_name).cloned() {
+        Theme::global_mut(cx).apply_config(&theme);
+        let font_size = AppSettings::global(cx).font_size;
+        Theme::global_mut(cx).font_size = px(font_size as f32);
+    }
+    apply_min_contrast(cx);
+    cx.refresh_windows();
+}
+
+/// Turn following the system appearance on or off
+///
+/// Turning it off keeps the current mode as the manual choice.
+pub(crate) fn follow_system_appearance(enabled: bool, cx: &mut App) {
+    if enabled && !system_appearance_supported() {
+        return;
+    }
+    if AppSettings::global(cx).auto_switch_theme != enabled {
+        AppSettings::global_mut(cx).auto_switch_theme = enabled;
+    }
+    if !enabled {
+        return;
+    }
+
+    // The appearance is shared by all windows, so the active one is enough
+    if let Some(window) = cx.active_window() {
+        _ = window.update(cx, |_, window, cx| sync_system_appearance(window, cx));
+    }
+}
+
 pub fn init(cx: &mut App) {
     // Get state file path based on build mode
     let state_file = crate::core::config_manager::get_state_file_path();
@@ -70,40 +399,21 @@ pub fn init(cx: &mut App) {
         "Loaded app_settings with font_size: {}",
         app_settings.font_size
     );
+    crate::core::log_capture::set_verbose(app_settings.verbose_logging);
     cx.set_global::<AppSettings>(app_settings.clone());
 
     // Get themes directory based on build mode
-    let themes_dir = if cfg!(debug_assertions) {
-        // Debug mode: use local ./themes for development
-        let dir = PathBuf::from("./themes");
-        tracing::info!("Debug mode: using local themes directory: {:?}", dir);
-        dir
-    } else {
-        // Release mode: use user data directory, fallback to ./themes
-        match crate::core::config_manager::initialize_themes_dir() {
-            Ok(dir) => {
-                tracing::info!(
-                    "Release mode: using themes from user data directory: {:?}",
-                    dir
-                );
-                dir
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "Failed to initialize user themes directory: {}, falling back to ./themes",
-                    e
-                );
-                PathBuf::from("./themes")
-            }
-        }
-    };
+    let themes_dir = themes_dir();
+    tracing::info!("Using themes directory: {:?}", themes_dir);
 
+    // The directory is reloaded whenever a theme file changes (e.g. on import),
+    // so re-apply the theme that is active at that point
+    let initial_theme = Cell::new(Some(state.theme.clone()));
     if let Err(err) = ThemeRegistry::watch_dir(themes_dir, cx, move |cx| {
-        if let Some(theme) = ThemeRegistry::global(cx)
-            .themes()
-            .get(&state.theme)
-            .cloned()
-        {
+        let theme_name = initial_theme
+            .take()
+            .unwrap_or_else(|| cx.theme().theme_name().clone());
+        if let Some(theme) = ThemeRegistry::global(cx).themes().get(&theme_name).cloned() {
             Theme::global_mut(cx).apply_config(&theme);
 
             // Re-sync font_size from AppSettings after applying theme config
@@ -114,6 +424,7 @@ pub fn init(cx: &mut App) {
                 font_size
             );
             Theme::global_mut(cx).font_size = px(font_size as f32);
+            apply_min_contrast(cx);
             cx.refresh_windows();
         }
     }) {
@@ -169,14 +480,21 @@ pub fn init(cx: &mut App) {
                 font_size
             );
             Theme::global_mut(cx).font_size = px(font_size as f32);
+            apply_min_contrast(cx);
         }
         cx.refresh_windows();
     });
     cx.on_action(|switch: &SwitchThemeMode, cx| {
+        // An explicit choice stops following the system appearance
+        follow_system_appearance(false, cx);
         let mode = switch.0;
         Theme::change(mode, None, cx);
+        apply_min_contrast(cx);
         cx.refresh_windows();
     });
+    cx.on_action(|_: &FollowSystemThemeMode, cx| {
+        follow_system_appearance(true, cx);
+    });
 }
 
 /// Helper function to save current state to file
@@ -187,7 +505,30 @@ pub(crate) fn save_state(cx: &mut App) {
         scrollbar_show: Some(cx.theme().scrollbar_show),
         app_settings: Some(AppSettings::global(cx).clone()),
         startup_completed: existing_state.startup_completed,
+        close_to_tray_hint_shown: existing_state.close_to_tray_hint_shown,
+        quarantine_warning_shown: existing_state.quarantine_warning_shown,
     };
 
     write_state_file(&state);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_theme_file() {
+        let file = validate_theme_file(include_str!("../../themes/ayu.json")).unwrap();
+        assert_eq!(file.name.as_ref(), "Ayu Light");
+        assert!(!file.themes.is_empty());
+
+        assert!(validate_theme_file("{").is_err());
+        assert!(validate_theme_file(r#"{"name": "Empty", "themes": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_theme_file_name() {
+        assert_eq!(theme_file_name("Ayu Light"), "ayu-light.json");
+        assert_eq!(theme_file_name("  "), "imported.json");
+    }
+}
@@ -15,7 +15,7 @@ use gpui_component::{
 };
 use rust_i18n::t;
 
-use crate::{AppState, SelectFont, SelectRadius, SelectScrollbarShow, app_menus};
+use crate::{AppSettings, AppState, SelectFont, SelectRadius, SelectScrollbarShow, app_menus};
 
 actions!(title_bar, [OpenSettings]);
 
@@ -39,11 +39,14 @@ impl AppTitleBar {
         let font_size_selector = cx.new(|cx| FontSizeSelector::new(window, cx));
         let app_menu_bar = AppMenuBar::new(cx);
 
+        // Re-render when an update check finds a new version
+        let subscriptions = vec![cx.observe_global::<AppSettings>(|_, cx| cx.notify())];
+
         Self {
             app_menu_bar,
             font_size_selector,
             child: Rc::new(|_, _| div().into_any_element()),
-            _subscriptions: vec![],
+            _subscriptions: subscriptions,
         }
     }
 
@@ -69,6 +72,10 @@ impl AppTitleBar {
 impl Render for AppTitleBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let notifications_count = window.notifications(cx).len();
+        let available_update = AppSettings::global(cx)
+            .available_update()
+            .map(|info| info.version.clone());
+        let has_update = available_update.is_some();
 
         div()
             .on_action(cx.listener(Self::on_action_open_settings))
@@ -93,15 +100,30 @@ impl Render for AppTitleBar {
                             .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
                             .child((self.child.clone())(window, cx))
                             .child(self.font_size_selector.clone())
-                            .child(
-                                Button::new("settings-btn")
+                            .child({
+                                let settings_button = Button::new("settings-btn")
                                     .icon(IconName::Settings)
                                     .small()
                                     .ghost()
+                                    .when_some(available_update, |this, version| {
+                                        this.tooltip(
+                                            t!(
+                                                "settings.update.status.available",
+                                                version = version
+                                            )
+                                            .to_string(),
+                                        )
+                                    })
                                     .on_click(|_, window, cx| {
                                         window.dispatch_action(Box::new(OpenSettings), cx);
-                                    }),
-                            ),
+                                    });
+                                // A dot on the settings button is the only hint of an update
+                                if has_update {
+                                    Badge::new().dot().child(settings_button).into_any_element()
+                                } else {
+                                    settings_button.into_any_element()
+                                }
+                            }),
                     ),
             )
     }
@@ -14,6 +14,7 @@ pub struct Assets;
 #[derive(RustEmbed)]
 #[folder = "./"]
 #[include = "config.json"]
+#[include = "agent_templates.json"]
 pub struct ConfigAssets;
 
 #[derive(RustEmbed)]
@@ -66,6 +67,7 @@ pub enum Icon {
     MoveRight,
     TextWrap,
     ArrowRightToLine,
+    StarFill,
 }
 
 impl IconNamed for Icon {
@@ -101,11 +103,61 @@ impl IconNamed for Icon {
             Icon::MoveRight => "icons2/move-right.svg",
             Icon::TextWrap => "icons2/text-wrap.svg",
             Icon::ArrowRightToLine => "icons2/arrow-right-to-line.svg",
+            Icon::StarFill => "icons/star-fill.svg",
         }
         .into()
     }
 }
 
+/// Icon keys that can be configured for an agent (`AgentProcessConfig::icon`)
+pub const AGENT_ICON_KEYS: &[&str] = &[
+    "augment", "claude", "cursor", "deepseek", "gemini", "iflow", "kimi", "mcp", "minimax",
+    "moonshot", "openai", "opencode", "qoder", "qwen", "zai",
+];
+
+impl Icon {
+    /// Look up an agent logo by its configuration key
+    pub fn from_agent_icon_key(key: &str) -> Option<Self> {
+        let icon = match key.trim().to_lowercase().as_str() {
+            "augment" => Icon::AugmentCode,
+            "claude" => Icon::Claude,
+            "cursor" => Icon::Cursor,
+            "deepseek" => Icon::DeepSeek,
+            "gemini" => Icon::Gemini,
+            "iflow" => Icon::Iflow,
+            "kimi" => Icon::Kimi,
+            "mcp" => Icon::MCP,
+            "minimax" => Icon::Minimax,
+            "moonshot" => Icon::Moonshot,
+            "openai" => Icon::OpenAI,
+            "opencode" => Icon::OpenCode,
+            "qoder" => Icon::Qoder,
+            "qwen" => Icon::Qwen,
+            "zai" => Icon::Zai,
+            _ => return None,
+        };
+        Some(icon)
+    }
+}
+
+/// Get the icon for an agent, preferring its configured icon key over the name-based mapping
+pub fn resolve_agent_icon(name: &str, icon: Option<&str>) -> Icon {
+    icon.and_then(Icon::from_agent_icon_key)
+        .unwrap_or_else(|| get_agent_icon(name))
+}
+
+/// Get the icon key configured for an agent in the current config
+pub fn agent_icon_key(name: &str, cx: &App) -> Option<String> {
+    crate::AppState::global(cx)
+        .agent_config_service()
+        .and_then(|service| service.agent_icon(name))
+}
+
+/// Get the icon for a configured agent, honoring its configured icon key
+pub fn agent_icon(name: &str, cx: &App) -> Icon {
+    resolve_agent_icon(name, agent_icon_key(name, cx).as_deref())
+}
+
 /// Get icon based on agent name
 pub fn get_agent_icon(name: &str) -> Icon {
     let name_lower = name.to_lowercase();
@@ -151,6 +203,12 @@ pub fn get_default_config() -> Option<String> {
     ConfigAssets::get("config.json").map(|file| String::from_utf8_lossy(&file.data).to_string())
 }
 
+/// Get the agent templates (agent_templates.json) embedded in the binary
+pub fn get_agent_templates() -> Option<String> {
+    ConfigAssets::get("agent_templates.json")
+        .map(|file| String::from_utf8_lossy(&file.data).to_string())
+}
+
 /// Get all embedded theme files
 pub fn get_embedded_themes() -> Vec<(String, String)> {
     ThemeAssets::iter()
@@ -167,3 +225,31 @@ pub fn get_embedded_themes() -> Vec<(String, String)> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_icon_overrides_name_mapping() {
+        assert_eq!(
+            resolve_agent_icon("my-agent", Some("Gemini")).path(),
+            Icon::Gemini.path()
+        );
+        assert_eq!(
+            resolve_agent_icon("kimi-cli", Some("unknown")).path(),
+            Icon::Kimi.path()
+        );
+        assert_eq!(
+            resolve_agent_icon("kimi-cli", None).path(),
+            Icon::Kimi.path()
+        );
+    }
+
+    #[test]
+    fn every_agent_icon_key_resolves() {
+        for key in AGENT_ICON_KEYS {
+            assert!(Icon::from_agent_icon_key(key).is_some(), "{key}");
+        }
+    }
+}
@@ -5,11 +5,21 @@ use gpui_component::{Icon, Sizable, h_flex, select::SelectItem};
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AgentItem {
     pub name: String,
+    /// Configured icon key, falls back to the name-based icon
+    pub icon: Option<String>,
 }
 
 impl AgentItem {
     pub fn new(name: impl Into<String>) -> Self {
-        Self { name: name.into() }
+        Self {
+            name: name.into(),
+            icon: None,
+        }
+    }
+
+    pub fn icon(mut self, icon: Option<String>) -> Self {
+        self.icon = icon;
+        self
     }
 }
 
@@ -21,7 +31,7 @@ impl SelectItem for AgentItem {
     }
 
     fn display_title(&self) -> Option<AnyElement> {
-        let icon = crate::assets::get_agent_icon(&self.name);
+        let icon = crate::assets::resolve_agent_icon(&self.name, self.icon.as_deref());
         Some(
             h_flex()
                 .gap_2()
@@ -33,7 +43,7 @@ impl SelectItem for AgentItem {
     }
 
     fn render(&self, _window: &mut Window, _cx: &mut App) -> impl gpui::IntoElement {
-        let icon = crate::assets::get_agent_icon(&self.name);
+        let icon = crate::assets::resolve_agent_icon(&self.name, self.icon.as_deref());
         h_flex()
             .gap_2()
             .items_center()
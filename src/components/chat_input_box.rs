@@ -13,6 +13,7 @@ use gpui_component::{
     select::{Select, SelectState},
     v_flex,
 };
+use rust_i18n::t;
 
 use agent_client_protocol::{AvailableCommand, ImageContent};
 
@@ -106,6 +107,8 @@ pub struct ChatInputBox {
     on_mcp_toggle: Option<Rc<dyn Fn(&(String, bool), &mut Window, &mut App) + 'static>>,
     /// Whether the input is disabled (e.g., for closed/failed sessions)
     disabled: bool,
+    /// Why the input is disabled, shown as the send button tooltip
+    disabled_reason: Option<SharedString>,
 }
 
 impl ChatInputBox {
@@ -138,6 +141,7 @@ impl ChatInputBox {
             selected_mcps: Vec::new(),
             on_mcp_toggle: None,
             disabled: false,
+            disabled_reason: None,
         }
     }
 
@@ -311,6 +315,12 @@ impl ChatInputBox {
         self.disabled = disabled;
         self
     }
+
+    /// Disable the input and explain why on the send button
+    pub fn disabled_reason(mut self, reason: impl Into<SharedString>) -> Self {
+        self.disabled_reason = Some(reason.into());
+        self
+    }
 }
 
 impl RenderOnce for ChatInputBox {
@@ -320,7 +330,8 @@ impl RenderOnce for ChatInputBox {
         let on_paste_callback = self.on_paste.clone();
         let input_state_for_paste = self.input_state.clone();
         let input_state = self.input_state.clone();
-        let disabled = self.disabled;
+        let disabled = self.disabled || self.disabled_reason.is_some();
+        let disabled_reason = self.disabled_reason.clone();
         let suggestion_state_id =
             ElementId::NamedChild(Arc::new(self.id.clone()), "command-suggestions".into());
         let suggestion_state = window.use_keyed_state(suggestion_state_id, cx, |window, cx| {
@@ -814,6 +825,10 @@ impl RenderOnce for ChatInputBox {
                                     )
                                 };
 
+                                // While the agent responds, sending queues the message
+                                // behind the current turn
+                                let mut queue_btn = None;
+
                                 // Attach click handler
                                 if is_in_progress {
                                     if let Some(on_cancel_handler) = on_cancel {
@@ -821,13 +836,40 @@ impl RenderOnce for ChatInputBox {
                                             on_cancel_handler(ev, window, cx);
                                         });
                                     }
+                                    if let Some(handler) = on_send
+                                        && !disabled
+                                        && (!is_empty || has_attachments)
+                                    {
+                                        queue_btn = Some(
+                                            Button::new("queue-message")
+                                                .icon(Icon::new(IconName::ArrowUp))
+                                                .rounded_full()
+                                                .small()
+                                                .custom(
+                                                    ButtonCustomVariant::new(cx)
+                                                        .color(theme.primary)
+                                                        .foreground(theme.background)
+                                                        .hover(theme.primary.opacity(0.9)),
+                                                )
+                                                .tooltip(
+                                                    t!("conversation.queue.send_tooltip")
+                                                        .to_string(),
+                                                )
+                                                .on_click(move |ev, window, cx| {
+                                                    handler(ev, window, cx);
+                                                }),
+                                        );
+                                    }
                                 } else if let Some(handler) = on_send {
                                     btn = btn.on_click(move |ev, window, cx| {
                                         handler(ev, window, cx);
                                     });
                                 }
+                                if let Some(reason) = disabled_reason {
+                                    btn = btn.tooltip(reason);
+                                }
 
-                                btn
+                                h_flex().gap_1().children(queue_btn).child(btn)
                             }),
                     ),
             )
@@ -3,17 +3,19 @@ mod chat_input_box;
 mod command_suggestions_popover;
 mod file_picker;
 mod input_suggestion;
+mod no_agents_state;
 mod select_items;
 mod status_indicator;
 // mod task_list_item;
 // ACP UI components live in the agentx-acp-ui crate.
 pub use agentx_acp_ui::{
-    AcpMessageStream, AcpMessageStreamOptions, AgentMessage, AgentMessageData, AgentMessageMeta,
-    AgentMessageOptions, AgentMessageView, AgentThoughtItem, AgentTodoList, AgentTodoListView,
-    DiffSummary, DiffSummaryData, DiffSummaryOptions, DiffSummaryToolCallHandler, DiffView,
-    FileChangeStats, PermissionRequest, PermissionRequestOptions, PermissionRequestView,
-    PermissionResponseHandler, PlanMeta, ToolCallItem, ToolCallItemOptions, ToolCallItemView,
-    UserMessage, UserMessageData, UserMessageView,
+    AcpMessageStream, AcpMessageStreamEvent, AcpMessageStreamOptions, AgentMessage,
+    AgentMessageData, AgentMessageMeta, AgentMessageOptions, AgentMessageView, AgentThoughtItem,
+    AgentTodoList, AgentTodoListView, DiffHunkPositions, DiffLoadMoreHandler, DiffSummary,
+    DiffSummaryData, DiffSummaryOptions, DiffSummaryToolCallHandler, DiffView, FileChangeStats,
+    PermissionRequest, PermissionRequestOptions, PermissionRequestView, PermissionResponseHandler,
+    PlanMeta, RevealTextResult, ToolCallItem, ToolCallItemOptions, ToolCallItemView, UserMessage,
+    UserMessageData, UserMessageView,
 };
 
 pub use agent_select::AgentItem;
@@ -22,6 +24,8 @@ pub use chat_input_box::ChatInputBox;
 
 pub use input_suggestion::{InputSuggestion, InputSuggestionItem, InputSuggestionState};
 
+pub use no_agents_state::NoAgentsState;
+
 pub use file_picker::{FileItem, FilePickerDelegate};
 
 pub use select_items::{ModeSelectItem, ModelSelectItem};
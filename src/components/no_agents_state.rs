@@ -0,0 +1,93 @@
+use gpui::{App, IntoElement, ParentElement, RenderOnce, Styled, Window, px};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    h_flex, v_flex,
+};
+use rust_i18n::t;
+
+use crate::app::{actions::RerunStartupWizard, title_bar::OpenSettings};
+
+/// Empty state shown when no agents are configured
+///
+/// Explains why nothing can be started and links to the agent settings and
+/// the startup wizard.
+#[derive(IntoElement)]
+pub struct NoAgentsState {
+    id: &'static str,
+    compact: bool,
+}
+
+impl NoAgentsState {
+    /// `id` prefixes the button ids so several instances can share a window
+    pub fn new(id: &'static str) -> Self {
+        Self { id, compact: false }
+    }
+
+    /// Left-aligned without the icon and title, for use inside other content
+    pub fn compact(mut self) -> Self {
+        self.compact = true;
+        self
+    }
+
+    /// Tooltip for actions that need an agent
+    pub fn disabled_reason() -> String {
+        t!("no_agents.disabled_reason").to_string()
+    }
+}
+
+impl RenderOnce for NoAgentsState {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let buttons = h_flex()
+            .gap_2()
+            .child(
+                Button::new((self.id, 0))
+                    .label(t!("no_agents.run_wizard").to_string())
+                    .icon(Icon::new(IconName::Settings2))
+                    .small()
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(RerunStartupWizard), cx);
+                    }),
+            )
+            .child(
+                Button::new((self.id, 1))
+                    .label(t!("no_agents.open_settings").to_string())
+                    .icon(Icon::new(IconName::Plus))
+                    .ghost()
+                    .small()
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(OpenSettings), cx);
+                    }),
+            );
+
+        let description = gpui::div()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(t!("no_agents.description").to_string());
+
+        if self.compact {
+            return v_flex().gap_2().child(description).child(buttons);
+        }
+
+        v_flex()
+            .size_full()
+            .items_center()
+            .justify_center()
+            .gap_3()
+            .p_4()
+            .child(
+                Icon::new(IconName::Bot)
+                    .size(px(32.))
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .child(
+                gpui::div()
+                    .text_base()
+                    .font_semibold()
+                    .text_color(cx.theme().foreground)
+                    .child(t!("no_agents.title").to_string()),
+            )
+            .child(description.text_center())
+            .child(buttons)
+    }
+}
@@ -0,0 +1,97 @@
+//! Curated presets for known ACP agents
+//!
+//! The list lives in `agent_templates.json`, embedded in the binary, so new
+//! agents can be added without code changes.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::core::config::AgentProcessConfig;
+
+/// A known agent with the command that starts it in ACP mode
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AgentTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Variables the user has to fill in; values are left empty
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Built-in icon key
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+impl AgentTemplate {
+    /// Whether the template works as is, without filling in variables
+    pub fn is_ready_to_use(&self) -> bool {
+        self.env.values().all(|value| !value.is_empty())
+    }
+
+    pub fn to_process_config(&self) -> AgentProcessConfig {
+        AgentProcessConfig {
+            command: self.command.clone(),
+            args: self.args.clone(),
+            env: self.env.clone(),
+            icon: self.icon.clone(),
+            order: None,
+            pinned: false,
+            request_timeout_secs: None,
+            startup_timeout_secs: None,
+            working_dir_override: None,
+            modes: Vec::new(),
+            nodejs_path: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TemplateFile {
+    templates: Vec<AgentTemplate>,
+}
+
+/// All embedded agent templates, in display order
+pub fn agent_templates() -> Vec<AgentTemplate> {
+    let Some(raw) = crate::assets::get_agent_templates() else {
+        log::warn!("Embedded agent_templates.json not found");
+        return Vec::new();
+    };
+    parse_templates(&raw).unwrap_or_else(|e| {
+        log::error!("Failed to parse embedded agent_templates.json: {}", e);
+        Vec::new()
+    })
+}
+
+fn parse_templates(raw: &str) -> serde_json::Result<Vec<AgentTemplate>> {
+    serde_json::from_str::<TemplateFile>(raw).map(|file| file.templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_templates_are_valid() {
+        let templates = parse_templates(include_str!("../../agent_templates.json")).unwrap();
+        assert!(!templates.is_empty());
+        for template in &templates {
+            assert!(!template.command.is_empty(), "{}", template.name);
+            if let Some(icon) = &template.icon {
+                assert!(
+                    crate::assets::AGENT_ICON_KEYS.contains(&icon.as_str()),
+                    "{}",
+                    template.name
+                );
+            }
+        }
+
+        let mut names: Vec<_> = templates.iter().map(|t| t.name.as_str()).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), templates.len());
+    }
+}
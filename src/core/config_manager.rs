@@ -1,11 +1,133 @@
 use anyhow::{Context, Result};
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-/// Get the user data directory for AgentX
-/// - macOS: ~/.agentx/
-/// - Windows: %APPDATA%\agentx\
-/// - Linux: ~/.config/agentx/
+/// Environment variable that overrides the user data directory
+pub const DATA_DIR_ENV: &str = "AGENTX_DATA_DIR";
+
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `dir` as the user data directory for the rest of the process.
+/// Set from the `--data-dir` flag before anything reads a persisted path;
+/// later calls are ignored.
+pub fn set_data_dir_override(dir: PathBuf) {
+    if DATA_DIR_OVERRIDE.set(dir).is_err() {
+        log::warn!("User data directory override was already set");
+    }
+}
+
+/// Subdirectory of the base data directory that holds named profiles
+const PROFILES_DIR_NAME: &str = "profiles";
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Run the rest of the process in the named profile, set from `--profile`.
+/// The default profile is the base data directory itself.
+pub fn set_profile(name: String) -> Result<()> {
+    validate_profile_name(&name)?;
+    if PROFILE.set(name).is_err() {
+        log::warn!("Profile was already set");
+    }
+    Ok(())
+}
+
+/// The profile this process runs in; `None` is the default profile
+pub fn current_profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+/// Profile names become directory names, so keep them to a safe charset
+pub fn validate_profile_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        anyhow::bail!("Profile name cannot be empty");
+    }
+    if name.len() > 64 {
+        anyhow::bail!("Profile name is longer than 64 characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Profile name may only contain letters, digits, '-' and '_'");
+    }
+    Ok(())
+}
+
+fn profile_data_dir(base: &Path, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => base.join(PROFILES_DIR_NAME).join(name),
+        None => base.to_path_buf(),
+    }
+}
+
+/// Names of the profiles created so far, sorted; the default profile is not listed
+pub fn list_profiles() -> Vec<String> {
+    let Ok(base) = base_data_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(base.join(PROFILES_DIR_NAME)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| validate_profile_name(name).is_ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Create the data directory of a new profile
+pub fn create_profile(name: &str) -> Result<PathBuf> {
+    validate_profile_name(name)?;
+    let dir = profile_data_dir(&base_data_dir()?, Some(name));
+    if dir.exists() {
+        anyhow::bail!("Profile '{}' already exists", name);
+    }
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create profile directory: {:?}", dir))?;
+    Ok(dir)
+}
+
+/// Pick the data directory override: `--data-dir` wins over `AGENTX_DATA_DIR`.
+/// Returns `None` when neither is set, meaning the platform default applies.
+fn resolve_data_dir_override(flag: Option<&Path>, env: Option<OsString>) -> Option<PathBuf> {
+    if let Some(dir) = flag {
+        return Some(dir.to_path_buf());
+    }
+    env.filter(|value| !value.is_empty()).map(PathBuf::from)
+}
+
+/// Get the user data directory for AgentX.
+///
+/// Resolved in order of precedence:
+/// 1. the `--data-dir` command line flag
+/// 2. the `AGENTX_DATA_DIR` environment variable
+/// 3. the platform default:
+///    - macOS: ~/.agentx/
+///    - Windows: %APPDATA%\agentx\
+///    - Linux: ~/.config/agentx/
+///
+/// Every persisted path (config, themes, state, layouts, sessions, traces,
+/// update history) is derived from this directory. A `--profile` other than
+/// the default lives under `profiles/<name>` inside it.
 pub fn get_user_data_dir() -> Result<PathBuf> {
+    Ok(profile_data_dir(&base_data_dir()?, current_profile()))
+}
+
+fn base_data_dir() -> Result<PathBuf> {
+    if let Some(dir) = resolve_data_dir_override(
+        DATA_DIR_OVERRIDE.get().map(PathBuf::as_path),
+        std::env::var_os(DATA_DIR_ENV),
+    ) {
+        return Ok(dir);
+    }
+    default_user_data_dir()
+}
+
+fn default_user_data_dir() -> Result<PathBuf> {
     #[cfg(target_os = "macos")]
     {
         let home =
@@ -77,6 +199,23 @@ pub fn ensure_default_config_at(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Move a config that failed to parse aside as `<name>.invalid-<timestamp>`,
+/// so saving the fallback config doesn't overwrite it
+pub fn preserve_invalid_config(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "config.json".to_string());
+    let preserved = path.with_file_name(format!(
+        "{}.invalid-{}",
+        file_name,
+        chrono::Local::now().format("%Y%m%dT%H%M%S")
+    ));
+    std::fs::rename(path, &preserved)
+        .with_context(|| format!("Failed to move invalid config to {:?}", preserved))?;
+    Ok(preserved)
+}
+
 /// Get the config file path in the user data directory
 pub fn get_user_config_path() -> Result<PathBuf> {
     Ok(get_user_data_dir()?.join("config.json"))
@@ -106,6 +245,41 @@ pub fn initialize_user_config() -> Result<PathBuf> {
     Ok(config_path)
 }
 
+/// What reading the config file at startup found
+pub enum ConfigFileStatus {
+    Loaded(crate::core::config::Config),
+    /// No file yet, e.g. on first run; the default config should be created
+    Missing,
+    /// The file exists but isn't a valid config, with the parse error and offending line
+    Invalid(String),
+    /// The file exists but couldn't be read
+    Unreadable(anyhow::Error),
+}
+
+/// Read the config file, telling a missing file apart from a broken one
+///
+/// A missing file is normal on first run, while a broken one holds the user's
+/// agents and must not be replaced without asking.
+pub fn read_config_file(path: &Path) -> ConfigFileStatus {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return ConfigFileStatus::Missing,
+        Err(e) => {
+            return ConfigFileStatus::Unreadable(
+                anyhow::Error::new(e).context(format!("Failed to read {}", path.display())),
+            );
+        }
+    };
+    match serde_json::from_str(&raw) {
+        Ok(config) => ConfigFileStatus::Loaded(config),
+        Err(e) => ConfigFileStatus::Invalid(format!(
+            "invalid config at {}: {}",
+            path.display(),
+            crate::core::config::describe_json_error(&raw, &e)
+        )),
+    }
+}
+
 /// Load config from user data directory
 /// Falls back to embedded default if file doesn't exist or is invalid
 pub fn load_user_config() -> Result<crate::core::config::Config> {
@@ -114,8 +288,14 @@ pub fn load_user_config() -> Result<crate::core::config::Config> {
     let config_content = std::fs::read_to_string(&config_path)
         .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
 
-    let config: crate::core::config::Config = serde_json::from_str(&config_content)
-        .with_context(|| format!("Failed to parse config file: {:?}", config_path))?;
+    let config: crate::core::config::Config =
+        serde_json::from_str(&config_content).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to parse config file {:?}: {}",
+                config_path,
+                crate::core::config::describe_json_error(&config_content, &e)
+            )
+        })?;
 
     Ok(config)
 }
@@ -178,8 +358,87 @@ pub fn get_docks_layout_path() -> PathBuf {
     user_data_dir_or_temp().join("docks-layout.json")
 }
 
+/// Get update history file path
+/// Always uses user data directory: <user_data_dir>/update-history.json
+pub fn get_update_history_path() -> PathBuf {
+    user_data_dir_or_temp().join("update-history.json")
+}
+
+/// Get the directory downloaded updates are staged in
+/// Always uses user data directory: <user_data_dir>/updates, never the shared temp dir
+pub fn get_updates_dir() -> Result<PathBuf> {
+    Ok(ensure_user_data_dir()?.join("updates"))
+}
+
+/// Get ACP trace directory path
+/// Always uses user data directory: <user_data_dir>/traces
+pub fn get_agent_traces_dir() -> PathBuf {
+    user_data_dir_or_temp().join("traces")
+}
+
 /// Get sessions directory path
 /// Always uses user data directory: <user_data_dir>/sessions
 pub fn get_sessions_dir() -> PathBuf {
     user_data_dir_or_temp().join("sessions")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_config_file_tells_missing_from_invalid() {
+        let dir = std::env::temp_dir().join(format!("agentx-config-status-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        let _ = std::fs::remove_file(&path);
+
+        assert!(matches!(read_config_file(&path), ConfigFileStatus::Missing));
+
+        std::fs::write(&path, "{\n  \"agent_servers\": {,\n}").unwrap();
+        match read_config_file(&path) {
+            ConfigFileStatus::Invalid(detail) => assert!(detail.contains("line 2")),
+            _ => panic!("expected an invalid config"),
+        }
+
+        std::fs::write(&path, crate::assets::get_default_config().unwrap()).unwrap();
+        assert!(matches!(
+            read_config_file(&path),
+            ConfigFileStatus::Loaded(_)
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn data_dir_flag_takes_precedence_over_env() {
+        let flag = Path::new("/from/flag");
+        let env = || Some(OsString::from("/from/env"));
+
+        assert_eq!(
+            resolve_data_dir_override(Some(flag), env()),
+            Some(PathBuf::from("/from/flag"))
+        );
+        assert_eq!(
+            resolve_data_dir_override(None, env()),
+            Some(PathBuf::from("/from/env"))
+        );
+        assert_eq!(resolve_data_dir_override(None, Some(OsString::new())), None);
+        assert_eq!(resolve_data_dir_override(None, None), None);
+    }
+
+    #[test]
+    fn profiles_live_under_the_base_data_dir() {
+        let base = Path::new("/data");
+        assert_eq!(profile_data_dir(base, None), PathBuf::from("/data"));
+        assert_eq!(
+            profile_data_dir(base, Some("work")),
+            PathBuf::from("/data/profiles/work")
+        );
+
+        assert!(validate_profile_name("work-2_b").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../escape").is_err());
+        assert!(validate_profile_name("with space").is_err());
+    }
+}
@@ -1,16 +1,18 @@
-use agentx_event_bus::{CodeSelectionEvent, EventHub};
+use agentx_event_bus::{CodeSelectionEvent, EventHub, EventSubscription};
 use agentx_types::events::CodeSelectionData;
 use gpui::{App, Context, Entity};
 
 /// Helper function to subscribe a panel entity to code selection events.
 /// This reduces boilerplate by encapsulating the channel + background task pattern.
+/// The panel keeps the returned subscription; dropping it stops the delivery.
 pub fn subscribe_entity_to_code_selections<T, F>(
     entity: &Entity<T>,
     event_hub: EventHub,
     panel_name: &'static str,
     on_selection: F,
     cx: &mut App,
-) where
+) -> EventSubscription
+where
     T: 'static,
     F: Fn(&mut T, CodeSelectionData, &mut Context<T>) + 'static,
 {
@@ -19,7 +21,7 @@ pub fn subscribe_entity_to_code_selections<T, F>(
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<CodeSelectionEvent>();
 
     log::info!("[{}] Subscribing to code selection events", panel_name);
-    event_hub.subscribe_code_selections(move |event| {
+    let id = event_hub.subscribe_code_selections(move |event| {
         log::debug!(
             "[{}] Received selection: {}:{}~{}",
             panel_name,
@@ -44,4 +46,6 @@ pub fn subscribe_entity_to_code_selections<T, F>(
         }
     })
     .detach();
+
+    EventSubscription::new(&event_hub, id)
 }
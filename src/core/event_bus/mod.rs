@@ -7,7 +7,7 @@ pub use agentx_event_bus::batching::{
     BatchedEventCollector, BatchedEvents, Debouncer, DebouncerContainer,
 };
 pub use agentx_event_bus::core::{EventBus, EventBusContainer, EventBusStats, SubscriptionId};
-pub use agentx_event_bus::hub::{AppEvent, EventHub};
+pub use agentx_event_bus::hub::{AppEvent, EventHub, EventSubscription};
 pub use agentx_event_bus::{
     AgentConfigEvent, CodeSelectionEvent, PermissionRequestEvent, SessionUpdateEvent,
     WorkspaceUpdateEvent,
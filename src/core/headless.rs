@@ -0,0 +1,561 @@
+//! Headless mode - run a single prompt against an agent without the GUI
+//!
+//! `agentx --headless --agent NAME --prompt TEXT [--cwd DIR] [--allow-all]`
+//! starts only the named agent from the config, creates a session in `--cwd`,
+//! sends the prompt and streams the reply to stdout. Tool calls are reported on
+//! stderr. Permission requests are rejected unless `--allow-all` is given.
+//!
+//! With `--format json` every session notification is written to stdout as one
+//! JSON object per line (see [`HeadlessEvent`]), followed by a `summary` line.
+//! Errors are written to stderr as `error` lines.
+//!
+//! Exit codes: 0 when the agent ends its turn, 1 on any other stop reason or
+//! error, 2 on invalid arguments.
+
+use std::{
+    collections::HashMap,
+    io::{Read as _, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use agent_client_protocol::{
+    self as acp, ContentBlock, PermissionOptionKind, SessionUpdate, ToolCallContent,
+};
+use anyhow::{Context as _, Result, anyhow, bail};
+use serde::Serialize;
+
+use crate::core::{
+    agent::{AgentManager, PermissionStore},
+    config::{Config, config_dir},
+    event_bus::{AgentConfigEvent, EventHub},
+    services::{AgentService, RetryPolicy},
+};
+
+/// How long to wait for the agent process to finish `initialize`
+const AGENT_READY_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Version of the `--format json` event schema; bump on incompatible changes
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+pub const USAGE: &str = "Usage: agentx --headless --agent NAME [--prompt TEXT] [--cwd DIR] \
+[--allow-all] [--format text|json] [--config PATH] [--data-dir DIR] [--profile NAME]\n\n\
+The prompt is read from stdin when --prompt is omitted.";
+
+/// How headless output is written
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// Agent text on stdout, activity on stderr
+    #[default]
+    Text,
+    /// Newline-delimited JSON events
+    Json,
+}
+
+/// Command-line options for headless mode
+#[derive(Debug, PartialEq)]
+pub struct HeadlessArgs {
+    pub agent: String,
+    /// Prompt text; read from stdin when not given
+    pub prompt: Option<String>,
+    pub cwd: Option<PathBuf>,
+    /// Approve every permission request instead of rejecting it
+    pub allow_all: bool,
+    pub format: OutputFormat,
+}
+
+impl HeadlessArgs {
+    /// Parse headless options; returns None when `--headless` is not present
+    pub fn parse(args: &[String]) -> Option<Result<Self>> {
+        if !args.iter().any(|arg| arg == "--headless") {
+            return None;
+        }
+        Some(Self::parse_flags(args))
+    }
+
+    fn parse_flags(args: &[String]) -> Result<Self> {
+        let mut agent = None;
+        let mut prompt = None;
+        let mut cwd = None;
+        let mut allow_all = false;
+        let mut format = OutputFormat::default();
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let mut value = || {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| anyhow!("missing value for {}", flag))
+            };
+            match flag.as_str() {
+                "--headless" => {}
+                "--agent" => agent = Some(value()?),
+                "--prompt" => prompt = Some(value()?),
+                "--cwd" => cwd = Some(PathBuf::from(value()?)),
+                // Handled by the config loader in main.rs
+                "--config" | "--data-dir" | "--profile" => {
+                    value()?;
+                }
+                "--allow-all" => allow_all = true,
+                "--format" => {
+                    format = match value()?.as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        other => bail!("unknown format: {} (expected text or json)", other),
+                    }
+                }
+                other => bail!("unknown argument: {}", other),
+            }
+        }
+
+        Ok(Self {
+            agent: agent.context("--agent is required")?,
+            prompt,
+            cwd,
+            allow_all,
+            format,
+        })
+    }
+}
+
+/// One line of `--format json` output
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HeadlessEvent<'a> {
+    SessionStarted {
+        session_id: &'a str,
+        agent: &'a str,
+        cwd: &'a Path,
+    },
+    MessageDelta {
+        content: &'a ContentBlock,
+    },
+    ThoughtDelta {
+        content: &'a ContentBlock,
+    },
+    ToolCall {
+        tool_call: &'a acp::ToolCall,
+    },
+    ToolCallUpdate {
+        update: &'a acp::ToolCallUpdate,
+    },
+    /// File edit carried by a tool call, split out for convenience
+    Diff {
+        tool_call_id: &'a acp::ToolCallId,
+        diff: &'a acp::Diff,
+    },
+    Plan {
+        plan: &'a acp::Plan,
+    },
+    /// Any other session notification, passed through unchanged
+    SessionUpdate {
+        update: &'a SessionUpdate,
+    },
+    Permission {
+        tool: &'a str,
+        allowed: bool,
+    },
+    Summary {
+        exit_code: i32,
+        stop_reason: Option<&'a acp::StopReason>,
+        /// Token usage as reported by the agent, if any
+        usage: Option<serde_json::Value>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Serialize)]
+struct EventLine<'a> {
+    version: u32,
+    #[serde(flatten)]
+    event: HeadlessEvent<'a>,
+}
+
+/// Write one event as a JSON line
+fn emit(out: &mut impl Write, event: HeadlessEvent) {
+    let line = EventLine {
+        version: EVENT_SCHEMA_VERSION,
+        event,
+    };
+    match serde_json::to_string(&line) {
+        Ok(json) => {
+            let _ = writeln!(out, "{}", json);
+            let _ = out.flush();
+        }
+        Err(e) => log::error!("Failed to serialize headless event: {}", e),
+    }
+}
+
+impl OutputFormat {
+    fn session_started(self, session_id: &str, agent: &str, cwd: &Path) {
+        if self == OutputFormat::Json {
+            emit(
+                &mut std::io::stdout(),
+                HeadlessEvent::SessionStarted {
+                    session_id,
+                    agent,
+                    cwd,
+                },
+            );
+        }
+    }
+
+    fn update(self, update: &SessionUpdate) {
+        match self {
+            OutputFormat::Text => print_update(update),
+            OutputFormat::Json => emit_update(&mut std::io::stdout(), update),
+        }
+    }
+
+    fn permission(self, tool: &str, allowed: bool) {
+        match self {
+            OutputFormat::Text => eprintln!(
+                "[permission] {} -> {}",
+                tool,
+                if allowed { "allowed" } else { "rejected" }
+            ),
+            OutputFormat::Json => emit(
+                &mut std::io::stdout(),
+                HeadlessEvent::Permission { tool, allowed },
+            ),
+        }
+    }
+
+    fn finish(self, result: &Result<acp::PromptResponse>) -> i32 {
+        let exit_code = match result {
+            Ok(response) if matches!(response.stop_reason, acp::StopReason::EndTurn) => 0,
+            _ => 1,
+        };
+        match self {
+            OutputFormat::Text => match result {
+                Ok(response) => {
+                    println!();
+                    if exit_code != 0 {
+                        eprintln!("Agent stopped: {:?}", response.stop_reason);
+                    }
+                }
+                Err(e) => eprintln!("Error: {:#}", e),
+            },
+            OutputFormat::Json => {
+                if let Err(e) = result {
+                    emit(
+                        &mut std::io::stderr(),
+                        HeadlessEvent::Error {
+                            message: format!("{:#}", e),
+                        },
+                    );
+                }
+                let response = result.as_ref().ok();
+                emit(
+                    &mut std::io::stdout(),
+                    HeadlessEvent::Summary {
+                        exit_code,
+                        stop_reason: response.map(|response| &response.stop_reason),
+                        usage: response.and_then(prompt_usage),
+                    },
+                );
+            }
+        }
+        exit_code
+    }
+}
+
+/// Run headless mode to completion and return the process exit code
+pub fn run(args: HeadlessArgs, config: Config, config_path: &Path) -> i32 {
+    let format = args.format;
+    let result = smol::block_on(run_prompt(args, config, config_path));
+    format.finish(&result)
+}
+
+async fn run_prompt(
+    args: HeadlessArgs,
+    config: Config,
+    config_path: &Path,
+) -> Result<acp::PromptResponse> {
+    let format = args.format;
+    let prompt = match args.prompt {
+        Some(prompt) => prompt,
+        None => {
+            let mut prompt = String::new();
+            std::io::stdin()
+                .read_to_string(&mut prompt)
+                .context("failed to read prompt from stdin")?;
+            prompt
+        }
+    };
+    if prompt.trim().is_empty() {
+        bail!("prompt is empty");
+    }
+
+    let cwd = match args.cwd {
+        Some(cwd) => cwd
+            .canonicalize()
+            .with_context(|| format!("invalid --cwd {}", cwd.display()))?,
+        None => std::env::current_dir().context("failed to read current directory")?,
+    };
+
+    let agent_config = config
+        .agent_servers
+        .get(&args.agent)
+        .map(|agent| agent.with_resolved_command(&config_dir(config_path)))
+        .with_context(|| format!("agent '{}' is not defined in the config", args.agent))?;
+
+    let event_hub = EventHub::new();
+    let permission_store = Arc::new(PermissionStore::default());
+
+    // Subscribe before starting the agent so its ready/failed event is not missed
+    let (ready_tx, ready_rx) = smol::channel::bounded(1);
+    let ready_agent = args.agent.clone();
+    let ready_subscription = event_hub.subscribe_agent_config_agent_events(move |event| {
+        let result = match event {
+            AgentConfigEvent::AgentReady { name } if *name == ready_agent => Ok(()),
+            AgentConfigEvent::AgentFailed { name, error } if *name == ready_agent => {
+                Err(error.clone())
+            }
+            _ => return,
+        };
+        let _ = ready_tx.try_send(result);
+    });
+
+    let manager = AgentManager::initialize(
+        HashMap::from([(args.agent.clone(), agent_config)]),
+        permission_store.clone(),
+        event_hub.clone(),
+        config.proxy.clone(),
+    )
+    .await?;
+
+    let ready = smol::future::or(async { ready_rx.recv().await.ok() }, async {
+        smol::Timer::after(AGENT_READY_TIMEOUT).await;
+        None
+    })
+    .await;
+    event_hub.unsubscribe(ready_subscription);
+    match ready {
+        Some(Ok(())) => {}
+        Some(Err(error)) => bail!("agent '{}' failed to start: {}", args.agent, error),
+        None => bail!(
+            "agent '{}' did not start within {:?}",
+            args.agent,
+            AGENT_READY_TIMEOUT
+        ),
+    }
+
+    let mut agent_service = AgentService::new(manager.clone());
+    agent_service.set_event_hub(event_hub.clone());
+    agent_service.set_agent_request_timeouts(&config.agent_servers);
+    agent_service.set_retry_policy(RetryPolicy::from(&config.request_retry));
+    let agent_service = Arc::new(agent_service);
+
+    let mcp_servers = config
+        .mcp_servers
+        .iter()
+        .filter(|(_, server)| server.enabled)
+        .map(|(name, server)| server.to_acp_mcp_server(name.clone()))
+        .collect();
+    let session_id = agent_service
+        .create_session_with_mcp_and_cwd(&args.agent, mcp_servers, cwd.clone())
+        .await?;
+    format.session_started(&session_id, &args.agent, &cwd);
+
+    let update_subscription =
+        event_hub.subscribe_session_updates_for_session(session_id.clone(), move |event| {
+            format.update(&event.update);
+        });
+
+    let allow_all = args.allow_all;
+    let store = permission_store.clone();
+    let permission_subscription = event_hub.subscribe_permission_requests(move |event| {
+        let response = permission_response(&event.options, allow_all);
+        let title = event.tool_call.fields.title.clone().unwrap_or_default();
+        format.permission(&title, allow_all);
+        let store = store.clone();
+        let permission_id = event.permission_id.clone();
+        smol::spawn(async move {
+            if let Err(e) = store.respond(&permission_id, response).await {
+                log::error!("Failed to answer permission request: {}", e);
+            }
+        })
+        .detach();
+    });
+
+    let result = agent_service
+        .send_prompt(&session_id, vec![prompt.into()])
+        .await;
+
+    event_hub.unsubscribe(update_subscription);
+    event_hub.unsubscribe(permission_subscription);
+    manager.shutdown_all().await;
+
+    result
+}
+
+/// Write agent text to stdout and tool activity to stderr
+fn print_update(update: &SessionUpdate) {
+    match update {
+        SessionUpdate::AgentMessageChunk(chunk) => {
+            if let ContentBlock::Text(text) = &chunk.content {
+                print!("{}", text.text);
+                let _ = std::io::stdout().flush();
+            }
+        }
+        SessionUpdate::ToolCall(tool_call) => {
+            eprintln!("[tool] {}", tool_call.title);
+        }
+        _ => {}
+    }
+}
+
+/// Map a session notification to typed JSON events
+fn emit_update(out: &mut impl Write, update: &SessionUpdate) {
+    match update {
+        SessionUpdate::AgentMessageChunk(chunk) => emit(
+            out,
+            HeadlessEvent::MessageDelta {
+                content: &chunk.content,
+            },
+        ),
+        SessionUpdate::AgentThoughtChunk(chunk) => emit(
+            out,
+            HeadlessEvent::ThoughtDelta {
+                content: &chunk.content,
+            },
+        ),
+        SessionUpdate::ToolCall(tool_call) => {
+            emit(out, HeadlessEvent::ToolCall { tool_call });
+            emit_diffs(out, &tool_call.tool_call_id, &tool_call.content);
+        }
+        SessionUpdate::ToolCallUpdate(update) => {
+            emit(out, HeadlessEvent::ToolCallUpdate { update });
+            if let Some(content) = &update.fields.content {
+                emit_diffs(out, &update.tool_call_id, content);
+            }
+        }
+        SessionUpdate::Plan(plan) => emit(out, HeadlessEvent::Plan { plan }),
+        update => emit(out, HeadlessEvent::SessionUpdate { update }),
+    }
+}
+
+fn emit_diffs(out: &mut impl Write, tool_call_id: &acp::ToolCallId, content: &[ToolCallContent]) {
+    for item in content {
+        if let ToolCallContent::Diff(diff) = item {
+            emit(out, HeadlessEvent::Diff { tool_call_id, diff });
+        }
+    }
+}
+
+/// Token usage from a prompt response, if the agent reported any
+///
+/// Read from the serialized response so both the `usage` field and agents that
+/// only put it in `_meta` are covered.
+fn prompt_usage(response: &acp::PromptResponse) -> Option<serde_json::Value> {
+    let value = serde_json::to_value(response).ok()?;
+    value
+        .get("usage")
+        .or_else(|| value.get("_meta").and_then(|meta| meta.get("usage")))
+        .filter(|usage| !usage.is_null())
+        .cloned()
+}
+
+/// Pick the first allow (or reject) option; cancel when none matches
+fn permission_response(
+    options: &[acp::PermissionOption],
+    allow_all: bool,
+) -> acp::RequestPermissionResponse {
+    let outcome = options
+        .iter()
+        .find(|option| {
+            let allows = matches!(
+                option.kind,
+                PermissionOptionKind::AllowOnce | PermissionOptionKind::AllowAlways
+            );
+            allows == allow_all
+        })
+        .map(|option| {
+            acp::RequestPermissionOutcome::Selected(acp::SelectedPermissionOutcome::new(
+                option.option_id.clone(),
+            ))
+        })
+        .unwrap_or(acp::RequestPermissionOutcome::Cancelled);
+    acp::RequestPermissionResponse::new(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_headless_args() {
+        assert!(HeadlessArgs::parse(&args(&["--config", "c.json"])).is_none());
+
+        let parsed = HeadlessArgs::parse(&args(&[
+            "--headless",
+            "--config",
+            "c.json",
+            "--agent",
+            "claude",
+            "--prompt",
+            "hi",
+            "--allow-all",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            parsed,
+            HeadlessArgs {
+                agent: "claude".to_string(),
+                prompt: Some("hi".to_string()),
+                cwd: None,
+                allow_all: true,
+                format: OutputFormat::Text,
+            }
+        );
+
+        let parsed =
+            HeadlessArgs::parse(&args(&["--headless", "--agent", "a", "--format", "json"]))
+                .unwrap()
+                .unwrap();
+        assert_eq!(parsed.format, OutputFormat::Json);
+        assert!(
+            HeadlessArgs::parse(&args(&["--headless", "--agent", "a", "--format", "xml"]))
+                .unwrap()
+                .is_err()
+        );
+
+        assert!(
+            HeadlessArgs::parse(&args(&["--headless", "--prompt", "hi"]))
+                .unwrap()
+                .is_err()
+        );
+        assert!(
+            HeadlessArgs::parse(&args(&["--headless", "--agent"]))
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn json_events_are_versioned_lines() {
+        let mut out = Vec::new();
+        emit(
+            &mut out,
+            HeadlessEvent::Permission {
+                tool: "Edit file",
+                allowed: false,
+            },
+        );
+        let line = String::from_utf8(out).unwrap();
+        assert!(line.ends_with('\n'));
+        let value: serde_json::Value = serde_json::from_str(line.trim_end()).unwrap();
+        assert_eq!(value["version"], EVENT_SCHEMA_VERSION);
+        assert_eq!(value["type"], "permission");
+        assert_eq!(value["tool"], "Edit file");
+    }
+}
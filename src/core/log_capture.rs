@@ -0,0 +1,146 @@
+//! In-memory capture of application logs for the log viewer
+//!
+//! `log` records are bridged into `tracing`, so a single layer sees both.
+//! Only the most recent `LOG_BUFFER_CAPACITY` records are kept.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{
+    LazyLock, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use chrono::{DateTime, Local};
+use tracing::{Event, Level, Subscriber, field::Field};
+use tracing_subscriber::{Layer, filter::FilterFn, layer::Context, registry::LookupSpan};
+
+/// Maximum number of records kept in memory
+pub const LOG_BUFFER_CAPACITY: usize = 5000;
+
+/// A single captured log record
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogRecord {
+    /// Single-line representation used for copying and saving
+    pub fn format(&self) -> String {
+        format!(
+            "{} {:<5} {}: {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+#[derive(Default)]
+struct LogBuffer {
+    records: VecDeque<LogRecord>,
+    /// Incremented on every new record so viewers can detect changes cheaply
+    generation: u64,
+}
+
+static BUFFER: LazyLock<Mutex<LogBuffer>> = LazyLock::new(Default::default);
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Layer that records events into the in-memory buffer
+///
+/// Debug and trace events are only captured while verbose logging is enabled.
+pub fn layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    LogCaptureLayer.with_filter(FilterFn::new(|metadata| {
+        *metadata.level() <= Level::INFO || VERBOSE.load(Ordering::Relaxed)
+    }))
+}
+
+/// Capture debug and trace records as well
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Current generation; changes whenever a record is added or the buffer is cleared
+pub fn generation() -> u64 {
+    BUFFER.lock().unwrap().generation
+}
+
+/// Copy of all buffered records, oldest first
+pub fn records() -> Vec<LogRecord> {
+    BUFFER.lock().unwrap().records.iter().cloned().collect()
+}
+
+pub fn clear() {
+    let mut buffer = BUFFER.lock().unwrap();
+    buffer.records.clear();
+    buffer.generation += 1;
+}
+
+fn push(record: LogRecord) {
+    let mut buffer = BUFFER.lock().unwrap();
+    if buffer.records.len() == LOG_BUFFER_CAPACITY {
+        buffer.records.pop_front();
+    }
+    buffer.records.push_back(record);
+    buffer.generation += 1;
+}
+
+pub struct LogCaptureLayer;
+
+impl<S: Subscriber> Layer<S> for LogCaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+
+        push(LogRecord {
+            timestamp: Local::now(),
+            level: *event.metadata().level(),
+            // Records bridged from `log` carry their real target as a field
+            target: visitor
+                .log_target
+                .unwrap_or_else(|| event.metadata().target().to_string()),
+            message: visitor.message,
+        });
+    }
+}
+
+#[derive(Default)]
+struct RecordVisitor {
+    message: String,
+    log_target: Option<String>,
+}
+
+impl tracing::field::Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message.push_str(value),
+            "log.target" => self.log_target = Some(value.to_string()),
+            name if name.starts_with("log.") => {}
+            name => {
+                let _ = write!(self.message, " {name}={value}");
+            }
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "message" => {
+                let _ = write!(self.message, "{value:?}");
+            }
+            name if name.starts_with("log.") => {}
+            name => {
+                let _ = write!(self.message, " {name}={value:?}");
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
 // Core infrastructure modules
 pub mod agent;
+pub mod agent_templates;
 pub mod config;
 pub mod config_manager;
 pub mod event_bus;
+pub mod headless;
+pub mod log_capture;
 pub mod nodejs;
 pub mod services;
 pub mod updater;
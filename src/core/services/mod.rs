@@ -12,3 +12,11 @@ pub use agentx_services::MessageService;
 pub use agentx_services::PersistenceService;
 pub use agentx_services::SessionStatus;
 pub use agentx_services::WorkspaceService;
+pub use agentx_services::coalesce_session_updates;
+pub use agentx_services::{
+    AgentConfigField, AgentConfigIssue, AgentConflict, AgentConflictKind, McpServerChanges,
+    UploadDirStatus, detect_agent_conflicts,
+};
+pub use agentx_services::{CompactionReport, RetentionPolicy, StorageUsage};
+pub use agentx_services::{IndexedRole, SessionSearchHit};
+pub use agentx_services::{RequestTimeout, RetryPolicy, SessionListUnsupported};
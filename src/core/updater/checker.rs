@@ -1,4 +1,5 @@
 use super::version::Version;
+use crate::core::config::ProxyConfig;
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
@@ -6,7 +7,7 @@ use std::time::Duration;
 
 static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
 
-fn tokio_handle() -> tokio::runtime::Handle {
+pub(super) fn tokio_handle() -> tokio::runtime::Handle {
     tokio::runtime::Handle::try_current().unwrap_or_else(|_| {
         RUNTIME
             .get_or_init(|| {
@@ -21,13 +22,16 @@ fn tokio_handle() -> tokio::runtime::Handle {
     })
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UpdateInfo {
     pub version: String,
     pub download_url: String,
     pub release_notes: String,
     pub published_at: String,
     pub file_size: Option<u64>,
+    /// Hex SHA-256 of the download, from the release metadata
+    #[serde(default)]
+    pub sha256: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +45,7 @@ pub enum UpdateCheckResult {
 pub struct UpdateChecker {
     check_url: String,
     timeout: Duration,
+    proxy: Option<ProxyConfig>,
 }
 
 impl UpdateChecker {
@@ -49,16 +54,24 @@ impl UpdateChecker {
             check_url: "https://api.github.com/repos/sxhxliang/agent-studio/releases/latest"
                 .to_string(),
             timeout: Duration::from_secs(10),
+            proxy: None,
         }
     }
 
+    /// Route the check through the configured proxy (ignored when disabled)
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = proxy.enabled.then_some(proxy);
+        self
+    }
+
     /// Safe to call from any async executor (GPUI, tokio, etc.).
     pub async fn check_for_updates(&self) -> UpdateCheckResult {
         let check_url = self.check_url.clone();
         let timeout = self.timeout;
+        let proxy = self.proxy.clone();
 
         let fetch_result = tokio_handle()
-            .spawn(async move { fetch_latest_release(&check_url, timeout).await })
+            .spawn(async move { fetch_latest_release(&check_url, timeout, proxy.as_ref()).await })
             .await;
 
         let info = match fetch_result {
@@ -97,14 +110,14 @@ impl Default for UpdateChecker {
     }
 }
 
-async fn fetch_latest_release(check_url: &str, timeout: Duration) -> Result<UpdateInfo> {
+async fn fetch_latest_release(
+    check_url: &str,
+    timeout: Duration,
+    proxy: Option<&ProxyConfig>,
+) -> Result<UpdateInfo> {
     log::info!("Fetching latest release from: {}", check_url);
 
-    let client = reqwest::Client::builder()
-        .timeout(timeout)
-        .user_agent(format!("AgentStudio/{}", env!("CARGO_PKG_VERSION")))
-        .build()?;
-
+    let client = http_client(Some(timeout), proxy)?;
     let response = client
         .get(check_url)
         .header("Accept", "application/vnd.github.v3+json")
@@ -117,18 +130,115 @@ async fn fetch_latest_release(check_url: &str, timeout: Duration) -> Result<Upda
 
     let body = response.text().await?;
     let release: GitHubRelease = serde_json::from_str(&body)?;
-    let download_url = find_platform_asset(&release.assets);
+    let asset = find_platform_asset(&release.assets);
+    let sha256 = match asset {
+        Some(asset) => asset_sha256(&client, asset, &release.assets).await,
+        None => None,
+    };
 
     Ok(UpdateInfo {
         version: release.tag_name,
-        download_url,
+        download_url: asset
+            .map(|a| a.browser_download_url.clone())
+            .unwrap_or_default(),
         release_notes: release.body.unwrap_or_default(),
         published_at: release.published_at,
-        file_size: release.assets.first().map(|a| a.size),
+        file_size: asset.map(|a| a.size),
+        sha256,
     })
 }
 
-fn find_platform_asset(assets: &[GitHubAsset]) -> String {
+/// HTTP client for update traffic, routed through `proxy` when given
+pub(super) fn http_client(
+    timeout: Option<Duration>,
+    proxy: Option<&ProxyConfig>,
+) -> Result<reqwest::Client> {
+    let mut builder =
+        reqwest::Client::builder().user_agent(format!("AgentStudio/{}", env!("CARGO_PKG_VERSION")));
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    // Same variables the agents get, so update traffic goes where agent traffic goes
+    for (key, url) in proxy.map(ProxyConfig::env_vars).unwrap_or_default() {
+        let proxy = match key.as_str() {
+            "HTTP_PROXY" => reqwest::Proxy::http(&url)?,
+            "HTTPS_PROXY" => reqwest::Proxy::https(&url)?,
+            "ALL_PROXY" => reqwest::Proxy::all(&url)?,
+            _ => continue,
+        };
+        builder = builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_env()));
+    }
+    Ok(builder.build()?)
+}
+
+/// SHA-256 published for `asset`
+///
+/// Uses the digest GitHub records for the asset, falling back to a
+/// `<name>.sha256` file or a `SHA256SUMS` list attached to the release.
+async fn asset_sha256(
+    client: &reqwest::Client,
+    asset: &GitHubAsset,
+    assets: &[GitHubAsset],
+) -> Option<String> {
+    if let Some(hash) = asset
+        .digest
+        .as_deref()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+    {
+        return Some(hash.to_lowercase());
+    }
+
+    let checksum_name = format!("{}.sha256", asset.name).to_lowercase();
+    let checksums = assets.iter().find(|a| {
+        let name = a.name.to_lowercase();
+        name == checksum_name || name == "sha256sums" || name == "sha256sums.txt"
+    })?;
+    let text = match fetch_text(client, &checksums.browser_download_url).await {
+        Ok(text) => text,
+        Err(e) => {
+            log::warn!("Failed to fetch update checksums: {}", e);
+            return None;
+        }
+    };
+    parse_checksum(&text, &asset.name)
+}
+
+async fn fetch_text(client: &reqwest::Client, url: &str) -> Result<String> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "Checksum download returned status: {}",
+            response.status()
+        ));
+    }
+    Ok(response.text().await?)
+}
+
+/// Find the hash for `file_name` in `sha256sum` output
+///
+/// A single bare hash (a `<name>.sha256` file) applies to the file it was published for.
+fn parse_checksum(text: &str, file_name: &str) -> Option<String> {
+    let is_hash = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    let entries: Vec<Vec<&str>> = text
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .filter(|parts| !parts.is_empty())
+        .collect();
+    if let [entry] = entries.as_slice()
+        && let [hash] = entry.as_slice()
+        && is_hash(hash)
+    {
+        return Some(hash.to_lowercase());
+    }
+    entries.iter().find_map(|parts| match parts.as_slice() {
+        [hash, name, ..] if is_hash(hash) && name.trim_start_matches('*') == file_name => {
+            Some(hash.to_lowercase())
+        }
+        _ => None,
+    })
+}
+
+fn find_platform_asset(assets: &[GitHubAsset]) -> Option<&GitHubAsset> {
     let patterns: &[&str] = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("macos", "aarch64") => &["aarch64-apple-darwin", "arm64-macos", "darwin-arm64"],
         ("macos", "x86_64") => &["x86_64-apple-darwin", "x64-macos", "darwin-x64"],
@@ -139,19 +249,23 @@ fn find_platform_asset(assets: &[GitHubAsset]) -> String {
         _ => &[],
     };
 
+    // Checksum files share the package name, so never pick them as the package
+    let packages = || {
+        assets.iter().filter(|a| {
+            let name = a.name.to_lowercase();
+            !name.ends_with(".sha256") && !name.starts_with("sha256sums")
+        })
+    };
     for pattern in patterns {
-        if let Some(asset) = assets.iter().find(|a| {
+        if let Some(asset) = packages().find(|a| {
             a.name.to_lowercase().contains(pattern)
                 || a.browser_download_url.to_lowercase().contains(pattern)
         }) {
-            return asset.browser_download_url.clone();
+            return Some(asset);
         }
     }
 
-    assets
-        .first()
-        .map(|a| a.browser_download_url.clone())
-        .unwrap_or_default()
+    packages().next()
 }
 
 #[derive(Debug, Deserialize)]
@@ -167,4 +281,28 @@ struct GitHubAsset {
     name: String,
     browser_download_url: String,
     size: u64,
+    /// `sha256:<hex>` on releases GitHub has computed a digest for
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_checksum() {
+        let hash = "a".repeat(64);
+        let other = "b".repeat(64);
+        let sums = format!("{other}  app-linux.AppImage\n{hash} *app-macos.dmg\n");
+        assert_eq!(parse_checksum(&sums, "app-macos.dmg"), Some(hash.clone()));
+        assert_eq!(parse_checksum(&sums, "app-win64.msi"), None);
+
+        // A `<name>.sha256` file may hold just the hash
+        assert_eq!(
+            parse_checksum(&format!("{hash}\n"), "app-macos.dmg"),
+            Some(hash)
+        );
+        assert_eq!(parse_checksum("not a hash", "app-macos.dmg"), None);
+    }
 }
@@ -1,6 +1,8 @@
 use anyhow::{Result, anyhow};
 use std::path::{Path, PathBuf};
 
+use crate::core::{config::ProxyConfig, config_manager};
+
 /// Progress callback for download operations
 pub type ProgressCallback = Box<dyn Fn(u64, u64) + Send + Sync>;
 
@@ -8,21 +10,34 @@ pub type ProgressCallback = Box<dyn Fn(u64, u64) + Send + Sync>;
 pub struct UpdateDownloader {
     /// Directory to download updates to
     download_dir: PathBuf,
+    proxy: Option<ProxyConfig>,
 }
 
 impl UpdateDownloader {
-    /// Create a new downloader with default download directory
+    /// Create a new downloader staging into the per-user updates directory
     pub fn new() -> Result<Self> {
-        let download_dir = std::env::temp_dir().join("agentx_updates");
-        std::fs::create_dir_all(&download_dir)?;
-
-        Ok(Self { download_dir })
+        Self::with_dir(config_manager::get_updates_dir()?)
     }
 
     /// Create downloader with custom download directory
     pub fn with_dir(dir: PathBuf) -> Result<Self> {
         std::fs::create_dir_all(&dir)?;
-        Ok(Self { download_dir: dir })
+        // Keep other local users from swapping the package before it's installed
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt as _;
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))?;
+        }
+        Ok(Self {
+            download_dir: dir,
+            proxy: None,
+        })
+    }
+
+    /// Route downloads through the configured proxy (ignored when disabled)
+    pub fn with_proxy(mut self, proxy: Option<ProxyConfig>) -> Self {
+        self.proxy = proxy.filter(|proxy| proxy.enabled);
+        self
     }
 
     /// Download update from URL to local file
@@ -38,7 +53,7 @@ impl UpdateDownloader {
         &self,
         url: &str,
         filename: Option<&str>,
-        _progress: Option<ProgressCallback>,
+        progress: Option<ProgressCallback>,
     ) -> Result<PathBuf> {
         let filename = filename
             .map(|s| s.to_string())
@@ -46,31 +61,16 @@ impl UpdateDownloader {
             .ok_or_else(|| anyhow!("Could not determine filename"))?;
 
         let file_path = self.download_dir.join(&filename);
+        log::info!("Downloading update from {} to {:?}", url, file_path);
+
+        let url = url.to_string();
+        let target = file_path.clone();
+        let proxy = self.proxy.clone();
+        super::checker::tokio_handle()
+            .spawn(async move { download_to(&url, &target, proxy.as_ref(), progress).await })
+            .await??;
 
-        // TODO: Implement real HTTP download
-        // Example implementation:
-        // let client = reqwest::Client::new();
-        // let mut response = client.get(url).send().await?;
-        // let total_size = response.content_length().unwrap_or(0);
-        //
-        // let mut file = tokio::fs::File::create(&file_path).await?;
-        // let mut downloaded = 0u64;
-        //
-        // while let Some(chunk) = response.chunk().await? {
-        //     file.write_all(&chunk).await?;
-        //     downloaded += chunk.len() as u64;
-        //     if let Some(ref callback) = progress {
-        //         callback(downloaded, total_size);
-        //     }
-        // }
-        //
-        // file.flush().await?;
-
-        log::info!("Download would save to: {:?}", file_path);
-
-        Err(anyhow!(
-            "Download not yet implemented. Please add reqwest dependency and implement HTTP client."
-        ))
+        Ok(file_path)
     }
 
     /// Extract filename from URL
@@ -96,6 +96,42 @@ impl UpdateDownloader {
     }
 }
 
+async fn download_to(
+    url: &str,
+    path: &Path,
+    proxy: Option<&ProxyConfig>,
+    progress: Option<ProgressCallback>,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt as _;
+
+    let client = super::checker::http_client(None, proxy)?;
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "Download failed with status: {}",
+            response.status()
+        ));
+    }
+    let total_size = response.content_length().unwrap_or(0);
+
+    // Write to a partial file so an interrupted download is never installed
+    let partial = path.with_extension("part");
+    let mut file = tokio::fs::File::create(&partial).await?;
+    let mut downloaded = 0u64;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        if let Some(callback) = progress.as_ref() {
+            callback(downloaded, total_size);
+        }
+    }
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&partial, path).await?;
+    Ok(())
+}
+
 impl Default for UpdateDownloader {
     fn default() -> Self {
         Self::new().expect("Failed to create default downloader")
@@ -0,0 +1,114 @@
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::core::config_manager;
+
+/// Entries kept in the history file
+const MAX_HISTORY_ENTRIES: usize = 50;
+
+/// One version change made by the updater
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateHistoryEntry {
+    pub from_version: String,
+    pub to_version: String,
+    /// Unix timestamp in seconds
+    pub changed_at: i64,
+    /// Set when this change restored a previous version
+    #[serde(default)]
+    pub rollback: bool,
+    /// SHA-256 of the build this change kept as the backup, taken as it was moved aside
+    #[serde(default)]
+    pub backup_sha256: Option<String>,
+}
+
+/// Installed-version history, oldest first
+pub fn load_history() -> Vec<UpdateHistoryEntry> {
+    load_history_from(&config_manager::get_update_history_path())
+}
+
+/// Append a version change to the history
+pub fn record_version_change(
+    from: &str,
+    to: &str,
+    rollback: bool,
+    backup_sha256: Option<String>,
+) -> Result<()> {
+    let entry = UpdateHistoryEntry {
+        from_version: from.to_string(),
+        to_version: to.to_string(),
+        changed_at: chrono::Utc::now().timestamp(),
+        rollback,
+        backup_sha256,
+    };
+    append_to(&config_manager::get_update_history_path(), entry)
+}
+
+/// Version held by the kept backup, which is the one the last change replaced
+pub fn previous_version() -> Option<String> {
+    load_history()
+        .last()
+        .map(|entry| entry.from_version.clone())
+}
+
+/// Hash the kept backup must still have before a rollback may restore it
+pub fn backup_sha256() -> Option<String> {
+    load_history().last()?.backup_sha256.clone()
+}
+
+fn load_history_from(path: &Path) -> Vec<UpdateHistoryEntry> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!("Ignoring unreadable update history {:?}: {}", path, e);
+        Vec::new()
+    })
+}
+
+fn append_to(path: &Path, entry: UpdateHistoryEntry) -> Result<()> {
+    let mut history = load_history_from(path);
+    history.push(entry);
+    if history.len() > MAX_HISTORY_ENTRIES {
+        history.drain(..history.len() - MAX_HISTORY_ENTRIES);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&history)?)
+        .with_context(|| format!("Failed to write update history: {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_is_appended_and_capped() {
+        let path = std::env::temp_dir().join(format!(
+            "agentx_update_history_test_{}.json",
+            std::process::id()
+        ));
+        _ = std::fs::remove_file(&path);
+
+        for i in 0..MAX_HISTORY_ENTRIES + 2 {
+            let entry = UpdateHistoryEntry {
+                from_version: format!("0.{}.0", i),
+                to_version: format!("0.{}.0", i + 1),
+                changed_at: i as i64,
+                rollback: false,
+                backup_sha256: None,
+            };
+            append_to(&path, entry).unwrap();
+        }
+
+        let history = load_history_from(&path);
+        assert_eq!(history.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(
+            history.last().unwrap().from_version,
+            format!("0.{}.0", MAX_HISTORY_ENTRIES + 1)
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,450 @@
+use anyhow::{Context as _, Result, anyhow, bail};
+use sha2::{Digest as _, Sha256};
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Result of installing a downloaded update
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallOutcome {
+    /// The new version replaced the running one; relaunch `app` to use it
+    Installed { app: PathBuf },
+    /// A platform installer was started and takes over from here
+    InstallerLaunched,
+    /// The app could not replace itself; the user has to install `path` manually
+    ManualInstallRequired { path: PathBuf, reason: String },
+}
+
+/// Install a downloaded update for the current platform
+///
+/// - macOS: mount the `.dmg` (or unpack the `.zip`) and swap the `.app` bundle
+/// - Windows: run `.msi`/setup installers, or swap a portable `.exe`
+/// - Linux: swap the running AppImage
+///
+/// Nothing is run or copied unless the package's SHA-256 matches `expected_sha256`.
+/// The replaced version is kept next to the new one with a `.previous` suffix.
+/// Anything that can't be replaced in place (missing permissions, unknown
+/// package types) is reported as [`InstallOutcome::ManualInstallRequired`].
+pub fn install_update(path: &Path, expected_sha256: &str) -> Result<InstallOutcome> {
+    if !path.is_file() {
+        bail!("Downloaded update not found: {}", path.display());
+    }
+    let actual = hash_path(path)?;
+    if !actual.eq_ignore_ascii_case(expected_sha256.trim()) {
+        _ = std::fs::remove_file(path);
+        bail!(
+            "The downloaded update failed verification (expected SHA-256 {}, got {})",
+            expected_sha256,
+            actual
+        );
+    }
+    log::info!("Installing verified update from {:?}", path);
+
+    match platform_install(path) {
+        Ok(outcome) => Ok(outcome),
+        Err(e) if is_permission_error(&e) => Ok(InstallOutcome::ManualInstallRequired {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        }),
+        Err(e) => Err(e),
+    }
+}
+
+/// Start the installed app and let the caller quit the running one
+pub fn relaunch(app: &Path) -> Result<()> {
+    log::info!("Relaunching {:?}", app);
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg("-n").arg(app);
+        command
+    } else {
+        Command::new(app)
+    };
+    command
+        .spawn()
+        .with_context(|| format!("Failed to relaunch {}", app.display()))?;
+    Ok(())
+}
+
+/// Where the running app is installed, if it can replace itself
+///
+/// The `.app` bundle on macOS, the executable on Windows and the AppImage on Linux.
+pub fn install_location() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        std::env::current_exe()
+            .ok()?
+            .ancestors()
+            .find(|dir| extension(dir) == "app")
+            .map(Path::to_path_buf)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        std::env::current_exe().ok()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        std::env::var_os("APPIMAGE").map(PathBuf::from)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Whether a version replaced by an update is kept and can be restored
+pub fn can_roll_back() -> bool {
+    install_location().is_some_and(|target| backup_path(&target).exists())
+}
+
+/// Restore the version replaced by the last update and return the app to relaunch
+///
+/// The kept version is only swapped in when it still hashes to `expected_sha256`,
+/// the hash recorded when it was moved aside. The installed and the kept
+/// version trade places, so a rollback can itself be undone the same way.
+pub fn rollback(expected_sha256: &str) -> Result<PathBuf> {
+    let target =
+        install_location().ok_or_else(|| anyhow!("This installation can't replace itself"))?;
+    let backup = backup_path(&target);
+    if !backup.exists() {
+        bail!("No previous version found at {}", backup.display());
+    }
+    if hash_path(&backup)? != expected_sha256 {
+        bail!(
+            "The previous version at {} changed after it was kept; refusing to restore it",
+            backup.display()
+        );
+    }
+    log::info!("Rolling back {:?} to {:?}", target, backup);
+    exchange(&target, &backup)?;
+    Ok(target)
+}
+
+/// Path the replaced version is kept at
+pub fn backup_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".previous");
+    target.with_file_name(name)
+}
+
+/// Hex SHA-256 of a file, or of every file under a directory (e.g. an `.app` bundle)
+///
+/// Directory entries are hashed in path order together with their relative
+/// paths, so renaming, adding or removing a file changes the result.
+pub fn hash_path(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    if path.is_dir() {
+        hash_dir(path, path, &mut hasher)?;
+    } else {
+        hash_file(path, &mut hasher)?;
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn hash_dir(root: &Path, dir: &Path, hasher: &mut Sha256) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        let relative = entry.strip_prefix(root).unwrap_or(&entry);
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        let metadata = std::fs::symlink_metadata(&entry)?;
+        if metadata.is_symlink() {
+            hasher.update(std::fs::read_link(&entry)?.to_string_lossy().as_bytes());
+        } else if metadata.is_dir() {
+            hash_dir(root, &entry, hasher)?;
+        } else {
+            hash_file(&entry, hasher)?;
+        }
+        hasher.update([0]);
+    }
+    Ok(())
+}
+
+fn hash_file(path: &Path, hasher: &mut Sha256) -> Result<()> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+fn is_permission_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|e| e.kind() == std::io::ErrorKind::PermissionDenied)
+    })
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn manual_install(path: &Path, reason: impl Into<String>) -> InstallOutcome {
+    InstallOutcome::ManualInstallRequired {
+        path: path.to_path_buf(),
+        reason: reason.into(),
+    }
+}
+
+/// Move `new` into place at `target`, keeping the old version as a backup
+fn swap_into_place(new: &Path, target: &Path) -> Result<()> {
+    let backup = backup_path(target);
+    if backup.exists() {
+        remove_path(&backup)?;
+    }
+    std::fs::rename(target, &backup)
+        .with_context(|| format!("Failed to move {} aside", target.display()))?;
+    if let Err(e) = std::fs::rename(new, target) {
+        // Put the running version back so the app keeps working
+        _ = std::fs::rename(&backup, target);
+        return Err(anyhow::Error::from(e)
+            .context(format!("Failed to move the update to {}", target.display())));
+    }
+    Ok(())
+}
+
+/// Swap two files or directories
+fn exchange(a: &Path, b: &Path) -> Result<()> {
+    let mut name = a.file_name().unwrap_or_default().to_os_string();
+    name.push(".rollback");
+    let temp = a.with_file_name(name);
+    if temp.exists() {
+        remove_path(&temp)?;
+    }
+
+    std::fs::rename(a, &temp).with_context(|| format!("Failed to move {} aside", a.display()))?;
+    if let Err(e) = std::fs::rename(b, a) {
+        _ = std::fs::rename(&temp, a);
+        return Err(anyhow::Error::from(e).context(format!("Failed to restore {}", b.display())));
+    }
+    std::fs::rename(&temp, b).with_context(|| format!("Failed to keep {}", b.display()))?;
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn run(command: &mut Command) -> Result<String> {
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !output.status.success() {
+        bail!(
+            "{:?} failed: {}",
+            command.get_program(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn platform_install(path: &Path) -> Result<InstallOutcome> {
+    let Some(bundle) = install_location() else {
+        return Ok(manual_install(path, "Not running from an .app bundle"));
+    };
+
+    let staging = std::env::temp_dir().join("agentx_update_staging");
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+
+    let source_dir = match extension(path).as_str() {
+        "dmg" => {
+            let mount_point = staging.join("mount");
+            run(Command::new("hdiutil")
+                .args(["attach", "-nobrowse", "-readonly", "-mountpoint"])
+                .arg(&mount_point)
+                .arg(path))?;
+            mount_point
+        }
+        "zip" => {
+            let unpacked = staging.join("unpacked");
+            run(Command::new("ditto")
+                .args(["-x", "-k"])
+                .arg(path)
+                .arg(&unpacked))?;
+            unpacked
+        }
+        other => {
+            return Ok(manual_install(
+                path,
+                format!("Unsupported update package: .{}", other),
+            ));
+        }
+    };
+
+    let result = (|| {
+        let new_app = std::fs::read_dir(&source_dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .find(|entry| extension(entry) == "app")
+            .ok_or_else(|| anyhow!("No .app bundle found in the update"))?;
+
+        // Copy next to the installed bundle so the final swap is a rename
+        let pending = bundle.with_extension("app.new");
+        if pending.exists() {
+            std::fs::remove_dir_all(&pending)?;
+        }
+        run(Command::new("ditto").arg(&new_app).arg(&pending))?;
+        swap_into_place(&pending, &bundle)
+    })();
+
+    if extension(path) == "dmg" {
+        _ = run(Command::new("hdiutil")
+            .args(["detach", "-quiet"])
+            .arg(&source_dir));
+    }
+    _ = std::fs::remove_dir_all(&staging);
+
+    result.map(|_| InstallOutcome::Installed { app: bundle })
+}
+
+#[cfg(target_os = "windows")]
+fn platform_install(path: &Path) -> Result<InstallOutcome> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match extension(path).as_str() {
+        "msi" => {
+            Command::new("msiexec")
+                .arg("/i")
+                .arg(path)
+                .spawn()
+                .context("Failed to start the installer")?;
+            Ok(InstallOutcome::InstallerLaunched)
+        }
+        "exe" if file_name.contains("setup") || file_name.contains("installer") => {
+            Command::new(path)
+                .spawn()
+                .context("Failed to start the installer")?;
+            Ok(InstallOutcome::InstallerLaunched)
+        }
+        "exe" => {
+            // A running exe can be renamed but not overwritten on Windows
+            let exe = std::env::current_exe()?;
+            let pending = exe.with_extension("exe.new");
+            std::fs::copy(path, &pending)
+                .with_context(|| format!("Failed to copy the update to {}", pending.display()))?;
+            swap_into_place(&pending, &exe)?;
+            Ok(InstallOutcome::Installed { app: exe })
+        }
+        other => Ok(manual_install(
+            path,
+            format!("Unsupported update package: .{}", other),
+        )),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn platform_install(path: &Path) -> Result<InstallOutcome> {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    // Only AppImages can replace themselves; packages go through the system
+    let Some(appimage) = install_location() else {
+        return Ok(manual_install(path, "Not running as an AppImage"));
+    };
+    if extension(path) != "appimage" {
+        return Ok(manual_install(
+            path,
+            "The update is not an AppImage and needs to be installed manually",
+        ));
+    }
+
+    let pending = appimage.with_extension("AppImage.new");
+    std::fs::copy(path, &pending)
+        .with_context(|| format!("Failed to copy the update to {}", pending.display()))?;
+    std::fs::set_permissions(&pending, std::fs::Permissions::from_mode(0o755))?;
+    swap_into_place(&pending, &appimage)?;
+    Ok(InstallOutcome::Installed { app: appimage })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn platform_install(path: &Path) -> Result<InstallOutcome> {
+    Ok(manual_install(
+        path,
+        "Automatic installation is not supported on this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_into_place_keeps_backup() {
+        let dir =
+            std::env::temp_dir().join(format!("agentx_installer_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("app.bin");
+        let pending = dir.join("app.bin.new");
+        std::fs::write(&target, "old").unwrap();
+        std::fs::write(&pending, "new").unwrap();
+
+        swap_into_place(&pending, &target).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&target)).unwrap(),
+            "old"
+        );
+        assert!(!pending.exists());
+
+        // Rolling back swaps the versions again
+        exchange(&target, &backup_path(&target)).unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "old");
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&target)).unwrap(),
+            "new"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_hash_path_notices_changed_bundles() {
+        let dir = std::env::temp_dir().join(format!("agentx_hash_test_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("Contents")).unwrap();
+        std::fs::write(dir.join("Contents/app"), "build").unwrap();
+
+        let original = hash_path(&dir).unwrap();
+        assert_eq!(hash_path(&dir).unwrap(), original);
+
+        std::fs::write(dir.join("Contents/app"), "patched").unwrap();
+        assert_ne!(hash_path(&dir).unwrap(), original);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_install_refuses_unverified_package() {
+        let path = std::env::temp_dir().join(format!(
+            "agentx_installer_verify_test_{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, "tampered").unwrap();
+
+        let error = install_update(&path, &"0".repeat(64)).unwrap_err();
+        assert!(error.to_string().contains("failed verification"));
+        assert!(!path.exists());
+    }
+}
@@ -1,15 +1,20 @@
 mod checker;
 mod downloader;
+mod history;
+mod installer;
 mod version;
 
 pub use checker::{UpdateCheckResult, UpdateChecker, UpdateInfo};
 pub use downloader::{ProgressCallback, UpdateDownloader};
+pub use history::{UpdateHistoryEntry, load_history};
+pub use installer::InstallOutcome;
 pub use version::Version;
 
 /// Update manager that coordinates checking, downloading, and installing updates
 #[derive(Clone)]
 pub struct UpdateManager {
     checker: UpdateChecker,
+    proxy: Option<crate::core::config::ProxyConfig>,
 }
 
 impl UpdateManager {
@@ -17,24 +22,112 @@ impl UpdateManager {
     pub fn new() -> anyhow::Result<Self> {
         Ok(Self {
             checker: UpdateChecker::new(),
+            proxy: None,
         })
     }
 
+    /// Route update checks and downloads through the configured proxy
+    pub fn with_proxy(mut self, proxy: crate::core::config::ProxyConfig) -> Self {
+        self.checker = self.checker.with_proxy(proxy.clone());
+        self.proxy = Some(proxy);
+        self
+    }
+
     /// Check for available updates
     pub async fn check_for_updates(&self) -> UpdateCheckResult {
         self.checker.check_for_updates().await
     }
 
-    /// Download an update
+    /// Download an update into the per-user updates directory
+    ///
+    /// Fails when the release publishes no checksum for the package or the
+    /// download doesn't have the advertised size.
     pub async fn download_update(
         &self,
         info: &UpdateInfo,
         progress: Option<ProgressCallback>,
     ) -> anyhow::Result<std::path::PathBuf> {
-        let downloader = UpdateDownloader::new()?;
-        downloader
+        if info.sha256.is_none() {
+            anyhow::bail!("The release publishes no checksum for this update");
+        }
+        let downloader = UpdateDownloader::new()?.with_proxy(self.proxy.clone());
+        let path = downloader
             .download(&info.download_url, None, progress)
-            .await
+            .await?;
+        if let Some(expected) = info.file_size {
+            let actual = std::fs::metadata(&path)?.len();
+            if actual != expected {
+                _ = std::fs::remove_file(&path);
+                anyhow::bail!(
+                    "The downloaded update is {} bytes, expected {}",
+                    actual,
+                    expected
+                );
+            }
+        }
+        Ok(path)
+    }
+
+    /// Verify and install a downloaded update of `info`, see [`installer::install_update`]
+    ///
+    /// Blocks while files are hashed and copied; run it off the main thread.
+    pub fn install_update(
+        &self,
+        path: &std::path::Path,
+        info: &UpdateInfo,
+    ) -> anyhow::Result<InstallOutcome> {
+        let expected = info
+            .sha256
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("The release publishes no checksum for this update"))?;
+        let outcome = installer::install_update(path, expected)?;
+        if let InstallOutcome::Installed { app } = &outcome {
+            let current = Version::current().to_string();
+            let backup_sha256 = Self::hash_backup(app);
+            if let Err(e) =
+                history::record_version_change(&current, &info.version, false, backup_sha256)
+            {
+                log::warn!("Failed to record update history: {}", e);
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Version a rollback would restore, if the replaced version was kept with a known hash
+    pub fn rollback_version() -> Option<String> {
+        (installer::can_roll_back() && history::backup_sha256().is_some())
+            .then(|| history::previous_version().unwrap_or_else(|| "unknown".to_string()))
+    }
+
+    /// Restore the version replaced by the last update and return the app to relaunch
+    ///
+    /// Refuses when the kept version no longer matches the hash recorded for it.
+    /// Blocks while files are hashed and moved; run it off the main thread.
+    pub fn rollback() -> anyhow::Result<std::path::PathBuf> {
+        let restored = Self::rollback_version();
+        let expected = history::backup_sha256()
+            .ok_or_else(|| anyhow::anyhow!("No verified previous version to restore"))?;
+        let app = installer::rollback(&expected)?;
+        let current = Version::current().to_string();
+        let restored = restored.unwrap_or_else(|| "unknown".to_string());
+        // The version rolled away from is now the kept one
+        let backup_sha256 = Self::hash_backup(&app);
+        if let Err(e) = history::record_version_change(&current, &restored, true, backup_sha256) {
+            log::warn!("Failed to record update history: {}", e);
+        }
+        Ok(app)
+    }
+
+    /// Hash of the build kept next to `app`, recorded so a rollback can check it
+    fn hash_backup(app: &std::path::Path) -> Option<String> {
+        installer::hash_path(&installer::backup_path(app))
+            .inspect_err(|e| log::warn!("Failed to hash the kept version: {}", e))
+            .ok()
+    }
+
+    /// Start the freshly installed app; the caller quits the running one
+    pub fn relaunch(app: &std::path::Path) -> anyhow::Result<()> {
+        installer::relaunch(app)
     }
 
     /// Get current application version
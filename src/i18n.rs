@@ -1,25 +1,215 @@
-use gpui::App;
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+use gpui::{App, SharedString};
+use rust_i18n::t;
 
 use crate::AppState;
 use crate::app::actions::SelectLocale;
 use crate::app::app_menus;
 use crate::panels::AppSettings;
 
+/// Locale files, embedded to find keys missing from a translation
+const LOCALE_SOURCES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.yml")),
+    ("zh-CN", include_str!("../locales/zh-CN.yml")),
+    ("ja", include_str!("../locales/ja.yml")),
+    ("de", include_str!("../locales/de.yml")),
+    ("fr", include_str!("../locales/fr.yml")),
+    ("es", include_str!("../locales/es.yml")),
+];
+
+/// Locales whose missing keys have already been logged
+static REPORTED_LOCALES: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(Default::default);
+
 pub fn init(cx: &mut App) {
-    let locale = AppSettings::global(cx).locale.clone();
-    rust_i18n::set_locale(locale.as_ref());
-
-    cx.on_action(|action: &SelectLocale, cx| {
-        change_locale(action.0.as_ref());
-        AppSettings::global_mut(cx).locale = action.0.clone();
-        let title = AppState::global(cx).app_title().clone();
-        if !title.is_empty() {
-            app_menus::init(title, cx);
-        }
-        cx.refresh_windows();
-    });
+    // Saved settings may name a locale that is no longer shipped
+    let locale = resolve_locale(&AppSettings::global(cx).locale);
+    if AppSettings::global(cx).locale.as_ref() != locale {
+        AppSettings::global_mut(cx).locale = locale.into();
+    }
+    change_locale(locale);
+
+    cx.on_action(|action: &SelectLocale, cx| select_locale(action.0.as_ref(), cx));
+}
+
+/// Switch the UI language, remember it in the app settings and rebuild the menus
+pub fn select_locale(locale: &str, cx: &mut App) {
+    let locale = resolve_locale(locale);
+    change_locale(locale);
+    AppSettings::global_mut(cx).locale = locale.into();
+    let title = AppState::global(cx).app_title().clone();
+    if !title.is_empty() {
+        app_menus::init(title, cx);
+    }
+    cx.refresh_windows();
 }
 
 pub fn change_locale(locale: &str) {
     rust_i18n::set_locale(locale);
+    report_missing_translations(locale);
+}
+
+/// Supported locales and their native names, in display order
+pub fn available_locales() -> Vec<(&'static str, SharedString)> {
+    let mut locales = rust_i18n::available_locales!();
+    // English first, the rest alphabetically by code
+    locales.sort_by_key(|locale| (*locale != FALLBACK_LOCALE, *locale));
+    locales
+        .into_iter()
+        .map(|locale| {
+            (
+                locale,
+                t!("locale.name", locale = locale).to_string().into(),
+            )
+        })
+        .collect()
+}
+
+/// Shipped locale to use for `requested`
+///
+/// Falls back from the exact locale to another region of the same language
+/// (`zh-TW` → `zh-CN`) and finally to English. Keys missing from the chosen
+/// locale are rendered in English.
+pub fn resolve_locale(requested: &str) -> &'static str {
+    let available = rust_i18n::available_locales!();
+    match_supported_locale(requested, &available).unwrap_or(FALLBACK_LOCALE)
+}
+
+/// Log, once per locale, the keys that fall back to English
+fn report_missing_translations(locale: &str) {
+    if !REPORTED_LOCALES.lock().unwrap().insert(locale.to_string()) {
+        return;
+    }
+    let missing = missing_translation_keys(locale);
+    if !missing.is_empty() {
+        log::warn!(
+            "Locale {} is missing {} translation key(s), showing English instead: {}",
+            locale,
+            missing.len(),
+            missing.join(", ")
+        );
+    }
+}
+
+/// Keys of the English locale file that `locale` does not translate
+fn missing_translation_keys(locale: &str) -> Vec<&'static str> {
+    let source = |code: &str| {
+        LOCALE_SOURCES
+            .iter()
+            .find(|(name, _)| *name == code)
+            .map(|(_, source)| *source)
+    };
+    let (Some(english), Some(translated)) = (source(FALLBACK_LOCALE), source(locale)) else {
+        return Vec::new();
+    };
+
+    let translated: HashSet<_> = translation_keys(translated).collect();
+    translation_keys(english)
+        .filter(|key| !translated.contains(key))
+        .collect()
+}
+
+/// Keys defined in a flat `key: "value"` locale file
+fn translation_keys(source: &'static str) -> impl Iterator<Item = &'static str> {
+    source
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, _)| key.trim())
+        .filter(|key| !key.is_empty() && !key.starts_with('_') && !key.starts_with('#'))
+}
+
+/// Locale used when the system locale is not supported
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Best supported locale for the operating system's language settings
+///
+/// Used on first launch, before the user has picked a locale.
+pub fn detect_system_locale() -> SharedString {
+    let available = rust_i18n::available_locales!();
+    let locale = system_locale_candidates()
+        .iter()
+        .find_map(|raw| match_supported_locale(raw, &available))
+        .unwrap_or(FALLBACK_LOCALE);
+    log::info!("Detected system locale: {}", locale);
+    locale.into()
+}
+
+/// Raw locale strings from the OS, most specific first
+fn system_locale_candidates() -> Vec<String> {
+    let mut candidates: Vec<String> = sys_locale::get_locales().collect();
+    for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        if let Ok(value) = std::env::var(var)
+            && !value.is_empty()
+        {
+            candidates.push(value);
+        }
+    }
+    candidates
+}
+
+/// Map a raw system locale (`zh_CN.UTF-8`, `en-US`, `zh-Hant-TW`, ...) to a supported locale
+///
+/// An exact language-region match wins; otherwise any supported locale with the
+/// same language is used.
+fn match_supported_locale<'a>(raw: &str, supported: &[&'a str]) -> Option<&'a str> {
+    // Strip the encoding and modifier, e.g. `de_DE.UTF-8@euro`
+    let tag = raw.split(['.', '@']).next()?.replace('_', "-");
+    let language = tag.split('-').next()?;
+    if language.is_empty() || language.eq_ignore_ascii_case("c") || language == "POSIX" {
+        return None;
+    }
+
+    supported
+        .iter()
+        .find(|locale| locale.eq_ignore_ascii_case(&tag))
+        .or_else(|| {
+            supported.iter().find(|locale| {
+                locale
+                    .split('-')
+                    .next()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(language))
+            })
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_supported_locale() {
+        let supported = ["en", "zh-CN"];
+        let cases = [
+            ("en-US", Some("en")),
+            ("en_GB.UTF-8", Some("en")),
+            ("zh-CN", Some("zh-CN")),
+            ("zh_CN.UTF-8", Some("zh-CN")),
+            ("zh-Hant-TW", Some("zh-CN")),
+            ("de_DE.UTF-8@euro", None),
+            ("C", None),
+            ("POSIX", None),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(match_supported_locale(raw, &supported), expected, "{raw}");
+        }
+    }
+
+    #[test]
+    fn test_locale_files_are_complete() {
+        let mut shipped: Vec<_> = LOCALE_SOURCES.iter().map(|(code, _)| *code).collect();
+        let mut available = rust_i18n::available_locales!();
+        shipped.sort();
+        available.sort();
+        assert_eq!(shipped, available);
+
+        for (locale, _) in LOCALE_SOURCES {
+            assert_eq!(
+                missing_translation_keys(locale),
+                Vec::<&str>::new(),
+                "{locale}"
+            );
+        }
+    }
 }
@@ -18,8 +18,8 @@ pub use assets::get_default_config;
 // Re-export from panels module
 use crate::panels::{DockPanelContainer, DockPanelState};
 pub use panels::{
-    AppSettings, CodeEditorPanel, ConversationPanel, SessionManagerPanel, SettingsPanel, TaskPanel,
-    TerminalPanel, ToolCallDetailPanel, WelcomePanel,
+    AppSettings, CodeEditorPanel, ConversationPanel, LogViewerPanel, SessionManagerPanel,
+    SettingsPanel, TaskPanel, TerminalPanel, ToolCallDetailPanel, WelcomePanel,
 };
 
 // Re-export from core module
@@ -38,12 +38,12 @@ pub use app::{
     actions::{
         About, AddAgent, AddSessionToList, CancelSession, CloseWindow, CreateTaskFromWelcome, Info,
         NewSessionConversationPanel, Open, OpenSessionManager, PanelAction, Quit,
-        ReloadAgentConfig, RemoveAgent, RestartAgent, SelectFont, SelectLocale, SelectRadius,
-        SelectScrollbarShow, SelectedAgentTask, SendMessageToSession, SetUploadDir, ShowPanelInfo,
-        Tab, TabPrev, TestAction, ToggleDockToggleButton, TogglePanelVisible, ToggleSearch,
-        UpdateAgent,
+        ReloadAgentConfig, RemoveAgent, RestartAgent, RestoreConfigBackup, SelectFont,
+        SelectLocale, SelectRadius, SelectScrollbarShow, SelectedAgentTask, SendMessageToSession,
+        SetUploadDir, ShowPanelInfo, StartTaskSession, Tab, TabPrev, TestAction,
+        ToggleDockToggleButton, TogglePanelVisible, ToggleSearch, UpdateAgent,
     },
-    app_menus, menu, system_tray, themes, title_bar,
+    app_menus, config_recovery, menu, system_tray, themes, title_bar,
 };
 use gpui::{
     AnyView, App, AppContext, Bounds, Context, Entity, IntoElement, ParentElement, Pixels, Render,
@@ -56,12 +56,13 @@ pub use title_bar::AppTitleBar;
 
 // Export components
 pub use components::{
-    AcpMessageStream, AcpMessageStreamOptions, AgentMessage, AgentMessageData, AgentMessageMeta,
-    AgentMessageOptions, AgentMessageView, AgentThoughtItem, AgentTodoList, AgentTodoListView,
-    ChatInputBox, DiffSummary, DiffSummaryData, DiffSummaryOptions, DiffSummaryToolCallHandler,
-    FileChangeStats, PermissionRequest, PermissionRequestOptions, PermissionRequestView,
-    PermissionResponseHandler, PlanMeta, StatusIndicator, ToolCallItem, ToolCallItemOptions,
-    ToolCallItemView, UserMessage, UserMessageData, UserMessageView,
+    AcpMessageStream, AcpMessageStreamEvent, AcpMessageStreamOptions, AgentMessage,
+    AgentMessageData, AgentMessageMeta, AgentMessageOptions, AgentMessageView, AgentThoughtItem,
+    AgentTodoList, AgentTodoListView, ChatInputBox, DiffSummary, DiffSummaryData,
+    DiffSummaryOptions, DiffSummaryToolCallHandler, FileChangeStats, PermissionRequest,
+    PermissionRequestOptions, PermissionRequestView, PermissionResponseHandler, PlanMeta,
+    StatusIndicator, ToolCallItem, ToolCallItemOptions, ToolCallItemView, UserMessage,
+    UserMessageData, UserMessageView,
 };
 
 // Re-export ACP types for convenience
@@ -75,7 +76,7 @@ use gpui_component::{
     dock::{PanelInfo, register_panel},
     v_flex,
 };
-use tracing_subscriber::{layer::SubscriberExt as _, util::SubscriberInitExt as _};
+use tracing_subscriber::{Layer as _, layer::SubscriberExt as _, util::SubscriberInitExt as _};
 
 const PANEL_NAME: &str = "DockPanelContainer";
 
@@ -155,6 +156,14 @@ impl DockRoot {
         cx: &mut Context<Self>,
     ) -> Self {
         let title_bar = cx.new(|cx| AppTitleBar::new(title, window, cx));
+
+        // Track light/dark changes of the OS when the theme mode is set to follow it
+        themes::sync_system_appearance(window, cx);
+        cx.observe_window_appearance(window, |_, window, cx| {
+            themes::sync_system_appearance(window, cx);
+        })
+        .detach();
+
         Self {
             title_bar,
             view: view.into(),
@@ -183,18 +192,23 @@ impl Render for DockRoot {
 }
 
 pub fn init(cx: &mut App) {
+    // RUST_LOG only filters terminal output; the log viewer has its own level filter
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
         .with(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("gpui_component=trace".parse().unwrap()),
+            tracing_subscriber::fmt::layer().with_filter(
+                tracing_subscriber::EnvFilter::from_default_env()
+                    .add_directive("gpui_component=trace".parse().unwrap()),
+            ),
         )
+        .with(crate::core::log_capture::layer())
         .init();
 
     gpui_component::init(cx);
     AppState::init(cx);
     themes::init(cx);
     i18n::init(cx);
+    app::desktop_notifications::init(cx);
+    app::agent_startup_errors::init(cx);
     panels::code_editor::init();
     menu::init(cx);
     key_binding::init(cx);
@@ -213,8 +227,10 @@ pub fn init(cx: &mut App) {
     cx.on_action(workspace::actions::remove_agent);
     cx.on_action(workspace::actions::restart_agent);
     cx.on_action(workspace::actions::reload_agent_config);
+    cx.on_action(workspace::actions::restore_config_backup);
     cx.on_action(workspace::actions::set_upload_dir);
     cx.on_action(workspace::actions::change_config_path);
+    cx.on_action(workspace::actions::set_session_mode);
 
     register_panel(cx, PANEL_NAME, |_, _, info, window, cx| {
         let agent_state = match info {
@@ -1,16 +1,51 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use agentx::Assets;
-use agentx::core::config_manager;
+use agentx::config_recovery::{ConfigLoadFailure, ConfigRecovery};
+use agentx::core::config_manager::{self, ConfigFileStatus};
+use agentx::core::headless::{self, HeadlessArgs};
+use agentx::core::updater::UpdateManager;
 use agentx::{AgentManager, Config, PermissionStore, workspace::open_new};
 use anyhow::Context as _;
 use gpui::Application;
 use std::sync::Arc;
 
 fn main() {
+    // `--data-dir` must be applied before anything reads a persisted path
+    if let Some(data_dir) = parse_data_dir() {
+        config_manager::set_data_dir_override(data_dir);
+    }
+    if let Some(profile) = parse_profile() {
+        if let Err(e) = config_manager::set_profile(profile) {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(2);
+        }
+    }
+
+    // `--rollback` restores the version replaced by the last update and starts it
+    if std::env::args().skip(1).any(|arg| arg == "--rollback") {
+        std::process::exit(rollback_and_relaunch());
+    }
+
     // Parse config path from command line arguments
     let config_path = parse_config_path();
 
+    // `--headless` runs a single prompt from the command line and exits
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(headless_args) = HeadlessArgs::parse(&args) {
+        let headless_args = match headless_args {
+            Ok(headless_args) => headless_args,
+            Err(e) => {
+                eprintln!("Error: {:#}\n\n{}", e, headless::USAGE);
+                std::process::exit(2);
+            }
+        };
+        let Some(config) = load_config(&config_path) else {
+            std::process::exit(1);
+        };
+        std::process::exit(headless::run(headless_args, config, &config_path));
+    }
+
     let app = Application::new().with_assets(Assets);
     app.run(move |cx| {
         agentx::init(cx);
@@ -42,44 +77,22 @@ fn main() {
 
         // Initialize agents in the background (async, non-blocking)
         cx.spawn(async move |cx| {
-            let config: Config = match std::fs::read_to_string(&config_path)
-                .with_context(|| format!("failed to read {}", config_path.display()))
-            {
-                Ok(raw) => match serde_json::from_str(&raw)
-                    .with_context(|| format!("invalid config at {}", config_path.display()))
-                {
-                    Ok(config) => config,
-                    Err(e) => {
-                        eprintln!("Failed to parse config: {}", e);
-                        match load_default_config() {
-                            Ok(config) => config,
-                            Err(e) => {
-                                eprintln!("Failed to load default config: {}", e);
-                                return;
-                            }
-                        }
-                    }
-                },
-                Err(e) => {
-                    eprintln!("Failed to read config file: {}", e);
-                    match load_default_config() {
-                        Ok(config) => config,
-                        Err(e) => {
-                            eprintln!("Failed to load default config: {}", e);
-                            return;
-                        }
-                    }
-                }
+            let Some(config) = load_config_or_recover(&config_path, cx) else {
+                return;
             };
 
-            println!("Config loaded from {}", config_path.display());
-
             // Inject nodejs_path from AppSettings into agent configs
             let nodejs_path = cx.update(|cx| {
                 agentx::AppSettings::global(cx).nodejs_path.clone()
             });
 
-            let mut agent_servers = config.agent_servers.clone();
+            // Relative command paths are relative to the config file
+            let config_dir = agentx::core::config::config_dir(&config_path);
+            let mut agent_servers: std::collections::HashMap<_, _> = config
+                .agent_servers
+                .iter()
+                .map(|(name, agent)| (name.clone(), agent.with_resolved_command(&config_dir)))
+                .collect();
             if !nodejs_path.is_empty() {
                 log::info!("Using custom Node.js path from settings: {}", nodejs_path);
                 // Inject nodejs_path into all agent configs
@@ -101,6 +114,7 @@ fn main() {
             .await
             {
                 Ok(manager) => {
+                    manager.set_log_buffer_size(config.agent_log_buffer_kb * 1024);
                     println!(
                         "Initializing {} agents in background...",
                         agent_server_count
@@ -114,12 +128,23 @@ fn main() {
                         agentx::AppState::global_mut(cx).set_agent_manager(manager, config);
                         agentx::AppState::global_mut(cx).set_permission_store(permission_store);
 
-                        // Get message service for persistence initialization
-                        agentx::AppState::global(cx).message_service().cloned()
+                        // Get services that finish initializing in async context
+                        let state = agentx::AppState::global(cx);
+                        (
+                            state.message_service().cloned(),
+                            state.agent_service().cloned(),
+                        )
                     });
+                    let (message_service, agent_service) = init_result;
+
+                    if let Some(agent_service) = agent_service {
+                        agent_service.load_session_titles().await;
+                        agent_service.load_session_modes().await;
+                        agent_service.load_agent_capabilities().await;
+                    }
 
                     // Initialize persistence subscription in async context
-                    if let Some(message_service) = init_result {
+                    if let Some(message_service) = message_service {
                         message_service.init_persistence();
                         println!("Agent initialization started - agents will appear as they are ready");
                     } else {
@@ -136,6 +161,45 @@ fn main() {
     });
 }
 
+/// Handle `--rollback`, returning the process exit code
+fn rollback_and_relaunch() -> i32 {
+    eprintln!(
+        "Warning: data written by the newer version may not be readable by the previous one."
+    );
+    match UpdateManager::rollback().and_then(|app| UpdateManager::relaunch(&app)) {
+        Ok(()) => {
+            eprintln!("Rolled back to the previous version");
+            0
+        }
+        Err(e) => {
+            eprintln!("Rollback failed: {:#}", e);
+            1
+        }
+    }
+}
+
+/// Data directory given with `--data-dir`, if any
+fn parse_data_dir() -> Option<std::path::PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        if flag == "--data-dir" {
+            return args.next().map(std::path::PathBuf::from);
+        }
+    }
+    None
+}
+
+/// Profile name given with `--profile`, if any
+fn parse_profile() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        if flag == "--profile" {
+            return args.next();
+        }
+    }
+    None
+}
+
 /// Parse config path from command line arguments or use user data directory
 fn parse_config_path() -> std::path::PathBuf {
     let mut args = std::env::args().skip(1);
@@ -152,7 +216,7 @@ fn parse_config_path() -> std::path::PathBuf {
     // No custom config specified, use user data directory
     match config_manager::initialize_user_config() {
         Ok(path) => {
-            println!("Using config from user data directory: {}", path.display());
+            eprintln!("Using config from user data directory: {}", path.display());
             path
         }
         Err(e) => {
@@ -171,6 +235,90 @@ fn parse_config_path() -> std::path::PathBuf {
     }
 }
 
+/// Read the config file, falling back to the embedded default when it is unusable
+///
+/// A missing file is created from the default first.
+fn load_config(config_path: &std::path::Path) -> Option<Config> {
+    match config_manager::read_config_file(config_path) {
+        ConfigFileStatus::Loaded(config) => {
+            eprintln!("Config loaded from {}", config_path.display());
+            Some(config)
+        }
+        ConfigFileStatus::Missing => create_default_config(config_path),
+        ConfigFileStatus::Invalid(detail) => {
+            eprintln!("Failed to parse config: {}", detail);
+            load_fallback_config()
+        }
+        ConfigFileStatus::Unreadable(e) => {
+            eprintln!("Failed to read config file: {:#}", e);
+            load_fallback_config()
+        }
+    }
+}
+
+/// Like [`load_config`], but a config file that exists and can't be used is
+/// reported in the workspace before falling back, instead of being replaced
+/// by the default on the next save. An unparsable file is moved aside first.
+fn load_config_or_recover(
+    config_path: &std::path::Path,
+    cx: &mut gpui::AsyncApp,
+) -> Option<Config> {
+    let (detail, preserved_path) = match config_manager::read_config_file(config_path) {
+        ConfigFileStatus::Loaded(config) => {
+            eprintln!("Config loaded from {}", config_path.display());
+            return Some(config);
+        }
+        // First run: nothing to recover, the startup wizard takes it from here
+        ConfigFileStatus::Missing => return create_default_config(config_path),
+        ConfigFileStatus::Invalid(detail) => {
+            eprintln!("Failed to parse config: {}", detail);
+            let preserved_path = match config_manager::preserve_invalid_config(config_path) {
+                Ok(path) => {
+                    eprintln!("Kept the invalid config at {}", path.display());
+                    Some(path)
+                }
+                Err(e) => {
+                    eprintln!("{:#}", e);
+                    None
+                }
+            };
+            (detail, preserved_path)
+        }
+        ConfigFileStatus::Unreadable(e) => {
+            eprintln!("Failed to read config file: {:#}", e);
+            (format!("{:#}", e), None)
+        }
+    };
+
+    let failure = ConfigLoadFailure {
+        config_path: config_path.to_path_buf(),
+        preserved_path,
+        detail,
+    };
+    let _ = cx.update(|cx| ConfigRecovery::report(failure, cx));
+
+    load_fallback_config()
+}
+
+/// Write the default config to a missing config file and load it
+fn create_default_config(config_path: &std::path::Path) -> Option<Config> {
+    match config_manager::ensure_default_config_at(config_path) {
+        Ok(()) => eprintln!("Created default config at {}", config_path.display()),
+        Err(e) => eprintln!("Failed to create default config: {:#}", e),
+    }
+    load_fallback_config()
+}
+
+fn load_fallback_config() -> Option<Config> {
+    match load_default_config() {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("Failed to load default config: {}", e);
+            None
+        }
+    }
+}
+
 fn load_default_config() -> anyhow::Result<Config> {
     let raw = agentx::get_default_config()
         .ok_or_else(|| anyhow::anyhow!("embedded default config missing"))?;
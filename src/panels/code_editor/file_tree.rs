@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use autocorrect::ignorer::Ignorer;
+use gpui_component::tree::TreeItem;
+use rust_i18n::t;
+
+/// Number of entries shown per directory before a "show more" row is added
+const DIR_PAGE_SIZE: usize = 200;
+/// Maximum number of matches returned by a filter search
+const SEARCH_RESULT_LIMIT: usize = 500;
+/// Maximum number of entries visited by a filter search
+const SEARCH_VISIT_LIMIT: usize = 50_000;
+
+const LOADING_ID_PREFIX: &str = "loading:";
+const MORE_ID_PREFIX: &str = "more:";
+
+/// A single file system entry in the workspace tree
+#[derive(Clone, Debug)]
+pub struct FsEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// What a clicked tree row refers to
+pub enum FileTreeNode {
+    File(PathBuf),
+    Dir(PathBuf),
+    /// "Show more" row of a partially displayed directory
+    More(PathBuf),
+    /// Placeholder row of a directory that is still loading
+    Loading,
+}
+
+impl FileTreeNode {
+    pub fn from_id(id: &str) -> Self {
+        if id.starts_with(LOADING_ID_PREFIX) {
+            FileTreeNode::Loading
+        } else if let Some(dir) = id.strip_prefix(MORE_ID_PREFIX) {
+            FileTreeNode::More(PathBuf::from(dir))
+        } else {
+            let path = PathBuf::from(id);
+            if path.is_dir() {
+                FileTreeNode::Dir(path)
+            } else {
+                FileTreeNode::File(path)
+            }
+        }
+    }
+}
+
+/// Lazily loaded file tree rooted at a workspace folder
+///
+/// Directory listings are fetched on first expand and only the first
+/// `DIR_PAGE_SIZE` entries of a directory are shown until more are requested.
+pub struct FileTree {
+    root: PathBuf,
+    listings: HashMap<PathBuf, Vec<FsEntry>>,
+    expanded: HashSet<PathBuf>,
+    visible: HashMap<PathBuf, usize>,
+}
+
+impl FileTree {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            listings: HashMap::new(),
+            expanded: HashSet::new(),
+            visible: HashMap::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Point the tree at a new root, dropping all loaded state
+    pub fn set_root(&mut self, root: PathBuf) {
+        *self = Self::new(root);
+    }
+
+    pub fn is_loaded(&self, dir: &Path) -> bool {
+        self.listings.contains_key(dir)
+    }
+
+    pub fn set_listing(&mut self, dir: PathBuf, entries: Vec<FsEntry>) {
+        self.listings.insert(dir, entries);
+    }
+
+    /// Forget all listings; returns the directories that should be reloaded
+    pub fn invalidate(&mut self) -> Vec<PathBuf> {
+        self.listings.clear();
+        let mut dirs = vec![self.root.clone()];
+        dirs.extend(self.expanded.iter().cloned());
+        dirs
+    }
+
+    /// Toggle a directory; returns true if its listing still needs loading
+    pub fn toggle_expanded(&mut self, dir: &Path) -> bool {
+        if !self.expanded.remove(dir) {
+            self.expanded.insert(dir.to_path_buf());
+        }
+        self.expanded.contains(dir) && !self.is_loaded(dir)
+    }
+
+    pub fn show_more(&mut self, dir: &Path) {
+        let visible = self
+            .visible
+            .entry(dir.to_path_buf())
+            .or_insert(DIR_PAGE_SIZE);
+        *visible += DIR_PAGE_SIZE;
+    }
+
+    /// Build tree items for all loaded directories
+    pub fn items(&self) -> Vec<TreeItem> {
+        self.dir_items(&self.root)
+    }
+
+    fn dir_items(&self, dir: &Path) -> Vec<TreeItem> {
+        let Some(entries) = self.listings.get(dir) else {
+            return Vec::new();
+        };
+
+        let limit = self.visible.get(dir).copied().unwrap_or(DIR_PAGE_SIZE);
+        let mut items: Vec<TreeItem> = entries
+            .iter()
+            .take(limit)
+            .map(|entry| {
+                let id = entry.path.to_string_lossy().to_string();
+                if !entry.is_dir {
+                    return TreeItem::new(id, entry.name.clone());
+                }
+
+                let children = if self.is_loaded(&entry.path) {
+                    self.dir_items(&entry.path)
+                } else {
+                    // Placeholder so the row renders as an expandable folder
+                    vec![TreeItem::new(
+                        format!("{}{}", LOADING_ID_PREFIX, id),
+                        t!("code_editor.file_tree.loading").to_string(),
+                    )]
+                };
+                TreeItem::new(id, entry.name.clone())
+                    .expanded(self.expanded.contains(&entry.path))
+                    .children(children)
+            })
+            .collect();
+
+        if entries.len() > limit {
+            items.push(TreeItem::new(
+                format!("{}{}", MORE_ID_PREFIX, dir.to_string_lossy()),
+                t!(
+                    "code_editor.file_tree.show_more",
+                    count = entries.len() - limit
+                )
+                .to_string(),
+            ));
+        }
+
+        items
+    }
+}
+
+/// Build flat tree items for filter results, labelled with their relative path
+pub fn search_items(root: &Path, entries: &[FsEntry]) -> Vec<TreeItem> {
+    entries
+        .iter()
+        .map(|entry| {
+            let label = entry
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&entry.path)
+                .to_string_lossy()
+                .to_string();
+            TreeItem::new(entry.path.to_string_lossy().to_string(), label)
+        })
+        .collect()
+}
+
+/// List a single directory, skipping gitignored entries (blocking)
+pub fn list_dir(root: &Path, dir: &Path) -> Vec<FsEntry> {
+    let ignorer = Ignorer::new(&root.to_string_lossy());
+    list_dir_with(&ignorer, root, dir)
+}
+
+fn list_dir_with(ignorer: &Ignorer, root: &Path, dir: &Path) -> Vec<FsEntry> {
+    let mut entries = Vec::new();
+
+    if let Ok(read_dir) = std::fs::read_dir(dir) {
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let relative_path = path.strip_prefix(root).unwrap_or(&path);
+            if ignorer.is_ignored(&relative_path.to_string_lossy())
+                || relative_path.ends_with(".git")
+            {
+                continue;
+            }
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Unknown")
+                .to_string();
+            let is_dir = path.is_dir();
+            entries.push(FsEntry { path, name, is_dir });
+        }
+    }
+
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
+    entries
+}
+
+/// Find files whose name contains `query` (case-insensitive, blocking)
+pub fn search(root: &Path, query: &str) -> Vec<FsEntry> {
+    let ignorer = Ignorer::new(&root.to_string_lossy());
+    let query = query.to_lowercase();
+    let mut results = Vec::new();
+    let mut visited = 0;
+    let mut queue = VecDeque::from([root.to_path_buf()]);
+
+    while let Some(dir) = queue.pop_front() {
+        for entry in list_dir_with(&ignorer, root, &dir) {
+            visited += 1;
+            if entry.is_dir {
+                queue.push_back(entry.path);
+            } else if entry.name.to_lowercase().contains(&query) {
+                results.push(entry);
+                if results.len() >= SEARCH_RESULT_LIMIT {
+                    return results;
+                }
+            }
+            if visited >= SEARCH_VISIT_LIMIT {
+                return results;
+            }
+        }
+    }
+
+    results
+}
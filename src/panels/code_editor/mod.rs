@@ -1,3 +1,4 @@
+mod file_tree;
 mod lsp_providers;
 mod lsp_store;
 mod panel;
@@ -1,6 +1,10 @@
-use std::{path::PathBuf, rc::Rc, str::FromStr};
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
 
-use autocorrect::ignorer::Ignorer;
 use gpui::{prelude::FluentBuilder, *};
 use gpui_component::{
     ActiveTheme, Icon, IconName, Sizable, StyledExt, WindowExt,
@@ -9,6 +13,7 @@ use gpui_component::{
     highlighter::{Diagnostic, DiagnosticSeverity, Language},
     input::{Input, InputEvent, InputState, Position, RopeExt, TabSize},
     list::ListItem,
+    notification::Notification,
     resizable::{h_resizable, resizable_panel},
     tree::{TreeState, tree},
     v_flex,
@@ -16,14 +21,20 @@ use gpui_component::{
 use lsp_types::{CodeActionKind, TextEdit, WorkspaceEdit};
 use rust_i18n::t;
 
+use super::file_tree::{self, FileTree, FileTreeNode};
 use super::lsp_providers::TextConvertor;
 use super::lsp_store::CodeEditorPanelLspStore;
-use super::types::build_file_items;
 use crate::AppState;
+use crate::app::actions::{AddCodeSelection, AddSelectionToChat, SaveFile};
+
+const FILTER_DEBOUNCE_MS: u64 = 200;
 
 pub struct CodeEditorPanel {
     editor: Entity<InputState>,
     tree_state: Entity<TreeState>,
+    file_tree: FileTree,
+    filter_state: Entity<InputState>,
+    filter_query: String,
     go_to_line_state: Entity<InputState>,
     language: Language,
     line_number: bool,
@@ -34,11 +45,21 @@ pub struct CodeEditorPanel {
     lsp_store: CodeEditorPanelLspStore,
     current_file_path: Option<PathBuf>,
     has_opened_file: bool,
+    /// File content as of the last load or save
+    saved_content: SharedString,
+    /// Whether the editor content differs from `saved_content`
+    modified: bool,
+    /// Modification time of the file when it was loaded or saved
+    loaded_mtime: Option<SystemTime>,
     workspace_id: Option<String>,
     workspace_name: Option<String>,
     working_directory: PathBuf,
+    /// Root the file tree at the active workspace (no explicit directory given)
+    follow_active_workspace: bool,
+    weak_self: WeakEntity<Self>,
     _subscriptions: Vec<Subscription>,
     _lint_task: Task<()>,
+    _filter_task: Task<()>,
 }
 
 impl crate::panels::dock_panel::DockPanel for CodeEditorPanel {
@@ -54,12 +75,13 @@ impl crate::panels::dock_panel::DockPanel for CodeEditorPanel {
         "A list displays a series of items."
     }
 
-    fn on_active(&mut self, active: bool, _: &mut Window, cx: &mut App) {
+    fn on_active(&mut self, active: bool, window: &mut Window, cx: &mut App) {
         if !active {
             return;
         }
 
         self.ensure_file_tree_loaded(cx);
+        self.check_external_changes(window, cx);
     }
 
     fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render> {
@@ -110,18 +132,36 @@ impl CodeEditorPanel {
             editor
         });
         let go_to_line_state = cx.new(|cx| InputState::new(window, cx));
+        let filter_state = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(t!("code_editor.file_tree.filter").to_string())
+        });
 
         let tree_state = cx.new(|cx| TreeState::new(cx));
+        let follow_active_workspace = working_dir.is_none();
         let working_dir =
             working_dir.unwrap_or_else(|| AppState::global(cx).current_working_dir().clone());
 
-        let _subscriptions = vec![cx.subscribe(&editor, |this, _, _: &InputEvent, cx| {
-            this.lint_document(cx);
-        })];
+        let _subscriptions = vec![
+            cx.subscribe(&editor, |this, _, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    this.update_modified(cx);
+                }
+                this.lint_document(cx);
+            }),
+            cx.subscribe(&filter_state, |this, state, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    let query = state.read(cx).value().trim().to_string();
+                    this.set_filter_query(query, cx);
+                }
+            }),
+        ];
 
         Self {
             editor,
             tree_state,
+            file_tree: FileTree::new(working_dir.clone()),
+            filter_state,
+            filter_query: String::new(),
             go_to_line_state,
             language: default_language,
             line_number: true,
@@ -132,41 +172,157 @@ impl CodeEditorPanel {
             lsp_store,
             current_file_path: None,
             has_opened_file: false,
+            saved_content: SharedString::default(),
+            modified: false,
+            loaded_mtime: None,
             workspace_id: None,
             workspace_name: None,
             working_directory: working_dir,
+            follow_active_workspace,
+            weak_self: cx.entity().downgrade(),
             _subscriptions,
             _lint_task: Task::ready(()),
+            _filter_task: Task::ready(()),
         }
     }
 
-    fn load_files(state: Entity<TreeState>, path: PathBuf, cx: &mut App) {
-        if !path.is_dir() {
-            return;
-        }
-
-        if path.parent().is_none() {
+    /// Load a single directory listing in the background and refresh the tree
+    fn load_dir(&self, dir: PathBuf, cx: &mut App) {
+        let root = self.file_tree.root().to_path_buf();
+        if !root.is_dir() || root.parent().is_none() {
             return;
         }
 
+        let weak_self = self.weak_self.clone();
         cx.spawn(async move |cx| {
-            let ignorer = Ignorer::new(&path.to_string_lossy());
-            let items = build_file_items(&ignorer, &path, &path);
+            let entries = smol::unblock({
+                let dir = dir.clone();
+                move || file_tree::list_dir(&root, &dir)
+            })
+            .await;
 
-            _ = state.update(cx, |state, cx| {
-                state.set_items(items, cx);
+            _ = weak_self.update(cx, |this, cx| {
+                // Ignore stale results from a previous root
+                if !dir.starts_with(this.file_tree.root()) {
+                    return;
+                }
+                this.file_tree.set_listing(dir, entries);
+                this.rebuild_tree(cx);
             });
         })
         .detach();
     }
 
+    fn rebuild_tree(&mut self, cx: &mut App) {
+        if !self.filter_query.is_empty() {
+            return;
+        }
+
+        let items = self.file_tree.items();
+        self.tree_state.update(cx, |state, cx| {
+            state.set_items(items, cx);
+        });
+    }
+
     fn ensure_file_tree_loaded(&mut self, cx: &mut App) {
         if self.files_loaded || !crate::themes::startup_completed() {
             return;
         }
 
         self.files_loaded = true;
-        Self::load_files(self.tree_state.clone(), self.working_directory.clone(), cx);
+
+        if !self.follow_active_workspace {
+            self.load_dir(self.working_directory.clone(), cx);
+            return;
+        }
+
+        // Root the tree at the active workspace when one is available
+        let workspace_service = AppState::global(cx).workspace_service().cloned();
+        let weak_self = self.weak_self.clone();
+        cx.spawn(async move |cx| {
+            let workspace = match workspace_service {
+                Some(service) => service.get_active_workspace().await,
+                None => None,
+            };
+
+            _ = weak_self.update(cx, |this, cx| {
+                if let Some(workspace) = workspace {
+                    this.workspace_id = Some(workspace.id);
+                    this.workspace_name = Some(workspace.name);
+                    this.working_directory = workspace.path.clone();
+                    this.file_tree.set_root(workspace.path);
+                }
+                this.load_dir(this.working_directory.clone(), cx);
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Reload the root and all expanded directories
+    fn refresh_file_tree(&mut self, cx: &mut Context<Self>) {
+        for dir in self.file_tree.invalidate() {
+            self.load_dir(dir, cx);
+        }
+        if !self.filter_query.is_empty() {
+            let query = self.filter_query.clone();
+            self.set_filter_query(query, cx);
+        }
+    }
+
+    fn set_filter_query(&mut self, query: String, cx: &mut Context<Self>) {
+        self.filter_query = query.clone();
+
+        if query.is_empty() {
+            self._filter_task = Task::ready(());
+            self.rebuild_tree(cx);
+            return;
+        }
+
+        let root = self.file_tree.root().to_path_buf();
+        self._filter_task = cx.spawn(async move |this, cx| {
+            smol::Timer::after(Duration::from_millis(FILTER_DEBOUNCE_MS)).await;
+
+            let entries = smol::unblock({
+                let root = root.clone();
+                let query = query.clone();
+                move || file_tree::search(&root, &query)
+            })
+            .await;
+
+            _ = this.update(cx, |this, cx| {
+                if this.filter_query != query {
+                    return;
+                }
+                let items = file_tree::search_items(&root, &entries);
+                this.tree_state.update(cx, |state, cx| {
+                    state.set_items(items, cx);
+                });
+            });
+        });
+    }
+
+    fn on_file_tree_click(&mut self, id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        match FileTreeNode::from_id(id) {
+            FileTreeNode::File(path) => {
+                if self.current_file_path.as_ref() == Some(&path) {
+                    return;
+                }
+                self.resolve_unsaved_changes(window, cx, move |this, window, cx| {
+                    this.open_path(path, window, cx);
+                });
+            }
+            FileTreeNode::Dir(dir) => {
+                if self.file_tree.toggle_expanded(&dir) {
+                    self.load_dir(dir, cx);
+                }
+            }
+            FileTreeNode::More(dir) => {
+                self.file_tree.show_more(&dir);
+                self.rebuild_tree(cx);
+            }
+            FileTreeNode::Loading => {}
+        }
     }
 
     /// Get the workspace_id (if available)
@@ -306,11 +462,17 @@ impl CodeEditorPanel {
             .unwrap_or_default();
         let language = Language::from_str(&language);
         let content = std::fs::read_to_string(&path)?;
+        let mtime = file_mtime(&path);
         let path_clone = path.clone();
 
         window
             .spawn(cx, async move |window| {
                 _ = view.update_in(window, |this, window, cx| {
+                    // Set before the value so the resulting change event sees a clean buffer
+                    this.saved_content = SharedString::from(content.clone());
+                    this.modified = false;
+                    this.loaded_mtime = mtime;
+
                     _ = this.editor.update(cx, |this, cx| {
                         this.set_highlighter(language.name(), cx);
                         this.set_value(content, window, cx);
@@ -327,7 +489,306 @@ impl CodeEditorPanel {
         Ok(())
     }
 
-    fn render_file_tree(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    /// Open a file, reporting read errors as a notification
+    fn open_path(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(e) = Self::open_file(cx.entity(), path.clone(), window, cx) {
+            log::error!("Failed to open {:?}: {}", path, e);
+            struct OpenFileError;
+            let note = Notification::error(format!("Failed to open {}: {}", path.display(), e))
+                .id::<OpenFileError>();
+            window.push_notification(note, cx);
+        }
+    }
+
+    /// Whether the open file has unsaved changes
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Tab title for the open file, with a dot when it has unsaved changes
+    pub fn tab_title(&self) -> Option<SharedString> {
+        let name = self
+            .current_file_path
+            .as_ref()?
+            .file_name()?
+            .to_string_lossy();
+        Some(if self.modified {
+            format!("{} ●", name).into()
+        } else {
+            name.to_string().into()
+        })
+    }
+
+    fn current_file_name(&self) -> String {
+        self.current_file_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "untitled".to_string())
+    }
+
+    fn update_modified(&mut self, cx: &mut Context<Self>) {
+        let modified = self.has_opened_file && self.editor.read(cx).value() != self.saved_content;
+        if modified != self.modified {
+            self.modified = modified;
+            cx.notify();
+        }
+    }
+
+    /// Whether the open file was changed on disk since it was loaded or saved
+    fn changed_on_disk(&self) -> bool {
+        match &self.current_file_path {
+            Some(path) => file_mtime(path) != self.loaded_mtime,
+            None => false,
+        }
+    }
+
+    fn on_action_save_file(&mut self, _: &SaveFile, window: &mut Window, cx: &mut Context<Self>) {
+        if self.current_file_path.is_none() {
+            return;
+        }
+
+        if !self.changed_on_disk() {
+            self.write_current_file(window, cx, |_, _, _| {});
+            return;
+        }
+
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &t!(
+                "code_editor.changed_on_disk.title",
+                name = self.current_file_name()
+            ),
+            Some(&t!("code_editor.changed_on_disk.save_detail")),
+            &[
+                t!("code_editor.changed_on_disk.overwrite").as_ref(),
+                t!("code_editor.changed_on_disk.reload").as_ref(),
+                t!("code_editor.prompt.cancel").as_ref(),
+            ],
+            cx,
+        );
+
+        cx.spawn_in(window, async move |this, window| {
+            let Ok(answer) = answer.await else {
+                return;
+            };
+            _ = this.update_in(window, |this, window, cx| match answer {
+                0 => this.write_current_file(window, cx, |_, _, _| {}),
+                1 => this.reload_current_file(window, cx),
+                _ => {}
+            });
+        })
+        .detach();
+    }
+
+    /// Write the editor content to the open file, then run `then` if it was saved
+    ///
+    /// The file is written in the background and replaced atomically, so a
+    /// crash mid-save never leaves it truncated.
+    fn write_current_file(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        then: impl FnOnce(&mut Self, &mut Window, &mut Context<Self>) + 'static,
+    ) {
+        let Some(path) = self.current_file_path.clone() else {
+            return;
+        };
+
+        let content = self.editor.read(cx).value();
+        let write = cx.background_spawn({
+            let path = path.clone();
+            let content = content.clone();
+            async move { write_file_atomic(&path, content.as_bytes()) }
+        });
+
+        // Hold a strong handle: the panel may be closing while it saves
+        let entity = cx.entity();
+        window
+            .spawn(cx, async move |window| {
+                let result = write.await;
+                _ = window.update(|window, cx| {
+                    entity.update(cx, |this, cx| match result {
+                        Ok(()) => {
+                            log::info!("[CodeEditorPanel] Saved {:?}", path);
+                            // Another file may have been opened while saving
+                            if this.current_file_path.as_ref() == Some(&path) {
+                                this.saved_content = content;
+                                this.loaded_mtime = file_mtime(&path);
+                                this.update_modified(cx);
+                            }
+                            then(this, window, cx);
+                        }
+                        Err(e) => {
+                            log::error!("[CodeEditorPanel] Failed to save {:?}: {}", path, e);
+                            struct SaveFileError;
+                            let note = Notification::error(
+                                t!("code_editor.save_failed", path = path.display(), error = e)
+                                    .to_string(),
+                            )
+                            .id::<SaveFileError>();
+                            window.push_notification(note, cx);
+                        }
+                    });
+                });
+            })
+            .detach();
+    }
+
+    fn reload_current_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(path) = self.current_file_path.clone() {
+            self.open_path(path, window, cx);
+        }
+    }
+
+    /// Ask what to do with unsaved changes before running `then`
+    fn resolve_unsaved_changes(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        then: impl FnOnce(&mut Self, &mut Window, &mut Context<Self>) + 'static,
+    ) {
+        if !self.modified {
+            then(self, window, cx);
+            return;
+        }
+
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &t!("code_editor.unsaved.title", name = self.current_file_name()),
+            Some(&t!("code_editor.unsaved.detail")),
+            &[
+                t!("code_editor.unsaved.save").as_ref(),
+                t!("code_editor.unsaved.discard").as_ref(),
+                t!("code_editor.prompt.cancel").as_ref(),
+            ],
+            cx,
+        );
+
+        cx.spawn_in(window, async move |this, window| {
+            let Ok(answer) = answer.await else {
+                return;
+            };
+            _ = this.update_in(window, |this, window, cx| match answer {
+                0 => this.write_current_file(window, cx, then),
+                1 => then(this, window, cx),
+                _ => {}
+            });
+        })
+        .detach();
+    }
+
+    /// Offer to save unsaved changes when the panel is being closed
+    pub fn prompt_save_before_close(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.modified {
+            return;
+        }
+
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &t!("code_editor.unsaved.title", name = self.current_file_name()),
+            Some(&t!("code_editor.unsaved.close_detail")),
+            &[
+                t!("code_editor.unsaved.save").as_ref(),
+                t!("code_editor.unsaved.discard").as_ref(),
+            ],
+            cx,
+        );
+
+        // Hold a strong handle: the panel is already detached from the dock
+        let entity = cx.entity();
+        cx.spawn_in(window, async move |_, window| {
+            if answer.await == Ok(0) {
+                _ = window.update(|window, cx| {
+                    entity.update(cx, |this, cx| {
+                        this.write_current_file(window, cx, |_, _, _| {});
+                    });
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Reload or warn when the open file was modified by another program
+    fn check_external_changes(&mut self, window: &mut Window, cx: &mut App) {
+        if !self.has_opened_file || !self.changed_on_disk() {
+            return;
+        }
+        let Some(path) = self.current_file_path.clone() else {
+            return;
+        };
+
+        if !self.modified {
+            // Nothing to lose, pick up the new content silently
+            if let Some(view) = self.weak_self.upgrade() {
+                Self::open_file(view, path, window, cx).ok();
+            }
+            return;
+        }
+
+        // Only ask once per external change
+        self.loaded_mtime = file_mtime(&path);
+
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &t!(
+                "code_editor.changed_on_disk.title",
+                name = self.current_file_name()
+            ),
+            Some(&t!("code_editor.changed_on_disk.reload_detail")),
+            &[
+                t!("code_editor.changed_on_disk.reload").as_ref(),
+                t!("code_editor.changed_on_disk.keep").as_ref(),
+            ],
+            cx,
+        );
+
+        let weak_self = self.weak_self.clone();
+        window
+            .spawn(cx, async move |window| {
+                if answer.await == Ok(0) {
+                    _ = weak_self.update_in(window, |this, window, cx| {
+                        this.reload_current_file(window, cx);
+                    });
+                }
+            })
+            .detach();
+    }
+
+    fn render_file_tree(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .bg(cx.theme().sidebar)
+            .child(
+                h_flex()
+                    .p_1()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(Input::new(&self.filter_state).xsmall().cleanable(true)),
+                    )
+                    .child(
+                        Button::new("refresh-file-tree")
+                            .icon(IconName::Replace)
+                            .ghost()
+                            .xsmall()
+                            .tooltip(t!("code_editor.file_tree.refresh").to_string())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.refresh_file_tree(cx);
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .child(self.render_file_tree_items(window, cx)),
+            )
+    }
+
+    fn render_file_tree_items(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let view = cx.entity();
         tree(
             &self.tree_state,
@@ -350,20 +811,9 @@ impl CodeEditorPanel {
                         .pl(px(16.) * entry.depth() + px(8.))
                         .child(h_flex().gap_2().child(icon).child(item.label.clone()))
                         .on_click(cx.listener({
-                            let item = item.clone();
-                            move |_, _, _window, cx| {
-                                if item.is_folder() {
-                                    return;
-                                }
-
-                                Self::open_file(
-                                    cx.entity(),
-                                    PathBuf::from(item.id.as_str()),
-                                    _window,
-                                    cx,
-                                )
-                                .ok();
-
+                            let id = item.id.clone();
+                            move |this, _, window, cx| {
+                                this.on_file_tree_click(&id, window, cx);
                                 cx.notify();
                             }
                         }))
@@ -529,6 +979,7 @@ impl CodeEditorPanel {
                         .icon(IconName::SquareTerminal)
                         .ghost()
                         .xsmall()
+                        .tooltip(t!("code_editor.tooltip.add_selection_to_chat").to_string())
                         .on_click(cx.listener(move |this, _, window, cx| {
                             this.add_selection_to_chat(window, cx, (start_pos, end_pos));
                         })),
@@ -543,8 +994,55 @@ impl CodeEditorPanel {
         }
     }
 
+    /// Current non-empty selection as (start, end, byte length)
+    fn selection_info(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<(Position, Position, usize)> {
+        use gpui_component::input::RopeExt;
+
+        self.editor.update(cx, |state, cx| {
+            let selection_utf16 = state.selected_text_range(false, window, cx);
+
+            if let Some(utf16_sel) = selection_utf16 {
+                let range = utf16_sel.range;
+
+                // 如果选择范围为空（只有光标）
+                if range.start == range.end {
+                    return None;
+                }
+
+                // 将 UTF-16 偏移量转换回字节偏移量
+                let text = state.text();
+                let start_offset = text.offset_utf16_to_offset(range.start);
+                let end_offset = text.offset_utf16_to_offset(range.end);
+
+                // 转换为行列位置
+                let start_pos = text.offset_to_position(start_offset);
+                let end_pos = text.offset_to_position(end_offset);
+                let length = end_offset - start_offset;
+
+                Some((start_pos, end_pos, length))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn on_action_add_selection_to_chat(
+        &mut self,
+        _: &AddSelectionToChat,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((start_pos, end_pos, _)) = self.selection_info(window, cx) {
+            self.add_selection_to_chat(window, cx, (start_pos, end_pos));
+        }
+    }
+
     fn add_selection_to_chat(
-        &self,
+        &mut self,
         _window: &mut Window,
         cx: &mut Context<Self>,
         selection: (Position, Position),
@@ -579,12 +1077,19 @@ impl CodeEditorPanel {
             content.len()
         );
 
+        // 选区结束于下一行行首时，不把该行计入行范围
+        let end_line = if end_pos.character == 0 && end_pos.line > start_pos.line {
+            end_pos.line
+        } else {
+            end_pos.line + 1
+        };
+
         // 创建 action
-        let action = crate::app::actions::AddCodeSelection {
+        let action = AddCodeSelection {
             file_path: file_path.clone(),
             start_line: start_pos.line + 1,
             start_column: start_pos.character + 1,
-            end_line: end_pos.line + 1,
+            end_line,
             end_column: end_pos.character + 1,
             content: content.clone(),
         };
@@ -649,32 +1154,7 @@ impl Render for CodeEditorPanel {
         // }
 
         // 提取选择范围信息
-        let selection_info = self.editor.update(cx, |state, cx| {
-            let selection_utf16 = state.selected_text_range(false, window, cx);
-
-            if let Some(utf16_sel) = selection_utf16 {
-                let range = utf16_sel.range;
-
-                // 如果选择范围为空（只有光标）
-                if range.start == range.end {
-                    return None;
-                }
-
-                // 将 UTF-16 偏移量转换回字节偏移量
-                let text = state.text();
-                let start_offset = text.offset_utf16_to_offset(range.start);
-                let end_offset = text.offset_utf16_to_offset(range.end);
-
-                // 转换为行列位置
-                let start_pos = text.offset_to_position(start_offset);
-                let end_pos = text.offset_to_position(end_offset);
-                let length = end_offset - start_offset;
-
-                Some((start_pos, end_pos, length))
-            } else {
-                None
-            }
-        });
+        let selection_info = self.selection_info(window, cx);
 
         let editor_input = Input::new(&self.editor)
             .bordered(false)
@@ -719,38 +1199,81 @@ impl Render for CodeEditorPanel {
             }
         };
 
-        v_flex().id("app").size_full().child(
-            v_flex()
-                .id("source")
-                .w_full()
-                .flex_1()
-                .child(main_content)
-                .child(
-                    h_flex()
-                        .justify_between()
-                        .text_sm()
-                        .bg(cx.theme().background)
-                        // .py_1p5()
-                        .h(px(30.))
-                        .px_4()
-                        .border_t_1()
-                        .border_color(cx.theme().border)
-                        .text_color(cx.theme().muted_foreground)
-                        .child(
-                            h_flex()
-                                .gap_3()
-                                .child(self.render_toggle_file_tree_button(window, cx))
-                                .child(self.render_line_number_button(window, cx))
-                                .child(self.render_soft_wrap_button(window, cx))
-                                .child(self.render_indent_guides_button(window, cx)),
-                        )
-                        .child(
-                            h_flex()
-                                .gap_3()
-                                .child(self.render_selection_range_info(window, cx, selection_info))
-                                .child(self.render_go_to_line_button(window, cx)),
-                        ),
-                ),
-        )
+        v_flex()
+            .id("app")
+            .key_context("CodeEditor")
+            .on_action(cx.listener(Self::on_action_save_file))
+            .on_action(cx.listener(Self::on_action_add_selection_to_chat))
+            .size_full()
+            .child(
+                v_flex()
+                    .id("source")
+                    .w_full()
+                    .flex_1()
+                    .child(main_content)
+                    .child(
+                        h_flex()
+                            .justify_between()
+                            .text_sm()
+                            .bg(cx.theme().background)
+                            // .py_1p5()
+                            .h(px(30.))
+                            .px_4()
+                            .border_t_1()
+                            .border_color(cx.theme().border)
+                            .text_color(cx.theme().muted_foreground)
+                            .child(
+                                h_flex()
+                                    .gap_3()
+                                    .child(self.render_toggle_file_tree_button(window, cx))
+                                    .child(self.render_line_number_button(window, cx))
+                                    .child(self.render_soft_wrap_button(window, cx))
+                                    .child(self.render_indent_guides_button(window, cx)),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_3()
+                                    .child(self.render_selection_range_info(
+                                        window,
+                                        cx,
+                                        selection_info,
+                                    ))
+                                    .child(self.render_go_to_line_button(window, cx)),
+                            ),
+                    ),
+            )
+    }
+}
+
+fn file_mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Replace a file through a synced temp file next to it and a rename
+///
+/// Symlinks are followed so the link itself is kept, and the file keeps its
+/// permissions.
+fn write_file_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let target = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = target.with_file_name(format!(".{}.saving", file_name));
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        if let Ok(metadata) = std::fs::metadata(&target) {
+            std::fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        std::fs::rename(&temp_path, &target)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
     }
+    result
 }
@@ -1,11 +1,19 @@
 use gpui::{
     App, ClipboardEntry, Context, Entity, FocusHandle, Focusable, IntoElement, ParentElement,
-    Render, ScrollHandle, SharedString, Styled, Window, div, prelude::*, px,
+    Pixels, Render, ScrollHandle, ScrollWheelEvent, SharedString, Styled, Subscription, Window,
+    div, point, prelude::*, px,
 };
 
 use gpui_component::{
-    ActiveTheme, Icon, IconName, Sizable, StyledExt, h_flex, input::InputState, skeleton::Skeleton,
-    spinner::Spinner, v_flex,
+    ActiveTheme, Icon, IconName, Sizable, StyledExt, WindowExt as _,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    menu::DropdownMenu as _,
+    notification::Notification,
+    skeleton::Skeleton,
+    spinner::Spinner,
+    v_flex,
 };
 
 // Use the published ACP schema crate
@@ -13,13 +21,26 @@ use agent_client_protocol::{ImageContent, PlanEntryStatus, RequestPermissionResp
 use chrono::{DateTime, Utc};
 use rust_i18n::t;
 use smol::Timer;
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use crate::assets::get_agent_icon;
+use crate::assets::resolve_agent_icon;
 use crate::{
-    AcpMessageStream, AcpMessageStreamOptions, AppState, ChatInputBox, DiffSummaryOptions,
-    PanelAction, PermissionRequestOptions, SendMessageToSession, ToolCallItemOptions,
-    app::actions::AddCodeSelection, core::services::SessionStatus, panels::dock_panel::DockPanel,
+    AcpMessageStream, AcpMessageStreamEvent, AcpMessageStreamOptions, AppState, ChatInputBox,
+    DiffSummaryOptions, PanelAction, PermissionRequestOptions, SendMessageToSession,
+    StatusIndicator, ToolCallItemOptions,
+    app::actions::{AddCodeSelection, SetSessionMode},
+    components::{NoAgentsState, RevealTextResult},
+    core::{
+        event_bus::EventSubscription,
+        services::{AgentSessionInfo, SessionStatus, coalesce_session_updates},
+    },
+    panels::dock_panel::DockPanel,
+    utils::git::GitStatus,
+    workspace::DockWorkspace,
 };
 
 /// Session status information for display
@@ -31,6 +52,29 @@ pub struct SessionStatusInfo {
     pub message_count: usize,
 }
 
+/// A prompt typed while the agent was still responding, sent when the turn ends
+#[derive(Clone, Debug)]
+struct QueuedPrompt {
+    text: String,
+    images: Vec<(ImageContent, String)>,
+    code_selections: Vec<AddCodeSelection>,
+}
+
+impl QueuedPrompt {
+    /// First line of the message, for the queue list
+    fn preview(&self) -> String {
+        let line = self.text.lines().find(|line| !line.trim().is_empty());
+        match line {
+            Some(line) => line.trim().to_string(),
+            None => t!(
+                "conversation.queue.attachments_only",
+                count = self.images.len() + self.code_selections.len()
+            )
+            .to_string(),
+        }
+    }
+}
+
 /// Conversation panel that displays SessionUpdate messages from ACP
 pub struct ConversationPanel {
     focus_handle: FocusHandle,
@@ -40,32 +84,69 @@ pub struct ConversationPanel {
     session_id: Option<String>,
     /// Scroll handle for auto-scrolling to bottom
     scroll_handle: ScrollHandle,
+    /// Follow new content; cleared when the user scrolls up, set again at the bottom
+    stick_to_bottom: bool,
+    /// Content arrived while scrolled up, offered through "Jump to latest"
+    has_unseen_content: bool,
     /// Input state for the chat input box
     input_state: Entity<InputState>,
     /// List of pasted images: (ImageContent, filename)
     pasted_images: Vec<(ImageContent, String)>,
     /// List of code selections from editor
     code_selections: Vec<AddCodeSelection>,
+    /// `file:line` references waiting to be inserted into the input (needs a window)
+    pending_references: Vec<String>,
+    /// Global search match (query, occurrence) to scroll to once the history is laid out
+    pending_search_reveal: Option<(String, usize)>,
+    /// Set once the persisted history has been added to the message stream
+    history_loaded: bool,
     /// Session status information for display
     session_status: Option<SessionStatusInfo>,
+    /// Prompts waiting for the current turn to end, oldest first
+    queued_prompts: VecDeque<QueuedPrompt>,
+    /// Agent that owns the session (used for the tab title)
+    agent_name: Option<String>,
+    /// User-given session title (None shows the short session id)
+    session_title: Option<String>,
+    /// Inline title editor shown in the header while renaming
+    title_input: Option<(Entity<InputState>, Subscription)>,
+    /// Snapshot of the session's metadata (model, cwd, timestamps)
+    session_info: Option<AgentSessionInfo>,
+    /// Whether the metadata row under the title is shown
+    details_expanded: bool,
     /// Workspace information
     workspace_id: Option<String>,
     workspace_name: Option<String>,
     working_directory: Option<String>,
+    /// Git status of the workspace folder (None for non-git folders)
+    git_status: Option<GitStatus>,
+    /// Set when an unbound panel finds no configured agents
+    no_agents: bool,
+    /// Event hub subscriptions, removed from the hub when the panel is dropped
+    _bus_subscriptions: Vec<EventSubscription>,
 }
 
 const MESSAGE_SERVICE_RETRY_DELAY_MS: u64 = 500;
+const GIT_STATUS_POLL_INTERVAL_SECS: u64 = 5;
 const MESSAGE_SERVICE_MAX_RETRIES: usize = 60;
+/// Minimum time between two batches of session updates (about 30 per second)
+const UPDATE_FLUSH_INTERVAL_MS: u64 = 33;
 const AUTO_SCROLL_THRESHOLD_PX: f32 = 120.0;
+const REVEAL_SCROLL_MARGIN_PX: f32 = 12.0;
 
 impl ConversationPanel {
-    /// Create a new panel with mock data (for demo purposes)
+    /// Create a new panel that is not bound to a session yet
+    ///
+    /// It does not subscribe to session updates or permission requests, so
+    /// several conversation panels can be open side by side without mixing
+    /// the output of other sessions.
     pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
         log::info!("🚀 Creating ConversationPanel view");
         let entity = cx.new(|cx| Self::new(window, cx));
-        Self::subscribe_to_updates(&entity, None, cx);
-        Self::subscribe_to_permissions(&entity, None, cx);
-        Self::subscribe_to_code_selections(&entity, cx);
+        Self::subscribe_to_message_stream(&entity, cx);
+        Self::subscribe_to_code_selections(&entity, window, cx);
+        Self::subscribe_to_git_status(&entity, None, cx);
+        Self::check_agents_available(&entity, cx);
         log::info!("✅ ConversationPanel view created and subscribed");
         entity
     }
@@ -78,18 +159,62 @@ impl ConversationPanel {
         // Load historical messages before subscribing to new updates
         Self::load_history_for_session(&entity, session_id.clone(), cx);
 
+        Self::subscribe_to_message_stream(&entity, cx);
         Self::subscribe_to_updates(&entity, Some(session_id.clone()), cx);
         Self::subscribe_to_permissions(&entity, Some(session_id.clone()), cx);
-        Self::subscribe_to_code_selections(&entity, cx);
+        Self::subscribe_to_code_selections(&entity, window, cx);
         Self::subscribe_to_status_updates(&entity, Some(session_id.clone()), cx);
+        Self::subscribe_to_title_updates(&entity, session_id.clone(), cx);
+        Self::subscribe_to_git_status(&entity, Some(session_id.clone()), cx);
         log::info!("✅ ConversationPanel created for session: {}", session_id);
         entity
     }
 
+    /// Tie a hub subscription to the panel's lifetime
+    fn keep_bus_subscription(entity: &Entity<Self>, subscription: EventSubscription, cx: &mut App) {
+        entity.update(cx, |this, _| this._bus_subscriptions.push(subscription));
+    }
+
+    /// Look up whether any agent is configured, for the empty state of unbound panels
+    fn check_agents_available(entity: &Entity<Self>, cx: &mut App) {
+        let Some(agent_service) = AppState::global(cx).agent_service().cloned() else {
+            return;
+        };
+        let weak = entity.downgrade();
+        cx.spawn(async move |cx| {
+            let no_agents = agent_service.list_agents().await.is_empty();
+            _ = weak.update(cx, |this, cx| {
+                this.no_agents = no_agents;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
     pub fn session_id(&self) -> Option<String> {
         self.session_id.clone()
     }
 
+    /// Tab title: agent name and session title or short id, e.g. "claude · 1a2b3c4d"
+    pub fn tab_title(&self) -> Option<SharedString> {
+        let session_id = self.session_id.as_deref()?;
+        let label = self
+            .session_title
+            .clone()
+            .unwrap_or_else(|| session_id.chars().take(8).collect());
+
+        Some(match self.owning_agent_name() {
+            Some(agent_name) => format!("{} · {}", agent_name, label).into(),
+            None => label.into(),
+        })
+    }
+
+    fn owning_agent_name(&self) -> Option<&str> {
+        self.agent_name
+            .as_deref()
+            .or_else(|| self.session_status.as_ref().map(|s| s.agent_name.as_str()))
+    }
+
     /// Get the workspace_id (if available)
     pub fn workspace_id(&self) -> Option<String> {
         self.workspace_id.clone()
@@ -123,19 +248,42 @@ impl ConversationPanel {
         let scroll_handle = ScrollHandle::new();
         let input_state = Self::create_input_state(window, cx);
         let message_stream = Self::create_message_stream(cx);
+        let agent_service = AppState::global(cx).agent_service();
+        let agent_name = session_id.as_deref().and_then(|session_id| {
+            agent_service.and_then(|service| service.get_agent_for_session(session_id))
+        });
+        let session_title = session_id.as_deref().and_then(|session_id| {
+            agent_service.and_then(|service| service.session_title(session_id))
+        });
+        let session_info =
+            Self::lookup_session_info(session_id.as_deref(), agent_name.as_deref(), cx);
 
         Self {
             focus_handle,
             message_stream,
             session_id,
             scroll_handle,
+            stick_to_bottom: true,
+            has_unseen_content: false,
             input_state,
             pasted_images: Vec::new(),
             code_selections: Vec::new(),
+            pending_references: Vec::new(),
+            pending_search_reveal: None,
+            history_loaded: false,
             session_status: None,
+            queued_prompts: VecDeque::new(),
+            agent_name,
+            session_title,
+            title_input: None,
+            session_info,
+            details_expanded: false,
             workspace_id: None,
             workspace_name: None,
             working_directory: None,
+            git_status: None,
+            no_agents: false,
+            _bus_subscriptions: Vec::new(),
         }
     }
 
@@ -149,9 +297,17 @@ impl ConversationPanel {
     }
 
     fn create_message_stream(cx: &mut App) -> Entity<AcpMessageStream> {
-        let icon_provider = Arc::new(|name: &str| Icon::new(get_agent_icon(name)));
+        let config_service = AppState::global(cx).agent_config_service().cloned();
+        let icon_provider = Arc::new(move |name: &str| {
+            let configured = config_service
+                .as_ref()
+                .and_then(|service| service.agent_icon(name));
+            Icon::new(resolve_agent_icon(name, configured.as_deref()))
+        });
         let tool_call_options = ToolCallItemOptions::default()
-            .preview_max_lines(AppState::global(cx).tool_call_preview_max_lines())
+            .preview_max_lines_provider(Arc::new(|cx| {
+                AppState::global(cx).tool_call_preview_max_lines()
+            }))
             .on_open_detail(Arc::new(|tool_call, window, cx| {
                 let action = PanelAction::show_tool_call_detail(
                     tool_call.tool_call_id.to_string(),
@@ -180,13 +336,114 @@ impl ConversationPanel {
         cx.new(|_| AcpMessageStream::with_options(options))
     }
 
-    fn should_auto_scroll(&self) -> bool {
+    fn is_near_bottom(&self) -> bool {
         let max_offset = self.scroll_handle.max_offset().height;
         let offset = self.scroll_handle.offset().y;
         let distance_to_bottom = max_offset + offset;
         distance_to_bottom <= px(AUTO_SCROLL_THRESHOLD_PX)
     }
 
+    /// Follow content that was just added, or flag it when the user is reading further up
+    fn follow_new_content(&mut self) {
+        if self.stick_to_bottom {
+            self.scroll_handle.scroll_to_bottom();
+        } else {
+            self.has_unseen_content = true;
+        }
+    }
+
+    fn jump_to_latest(&mut self, cx: &mut Context<Self>) {
+        self.stick_to_bottom = true;
+        self.has_unseen_content = false;
+        self.scroll_handle.scroll_to_bottom();
+        cx.notify();
+    }
+
+    /// Stop following on upward scrolls; follow again once scrolled back to the bottom
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if event.delta.pixel_delta(window.line_height()).y > px(0.) {
+            self.stick_to_bottom = false;
+        } else if self.is_near_bottom() {
+            self.stick_to_bottom = true;
+            self.has_unseen_content = false;
+        }
+        cx.notify();
+    }
+
+    fn render_jump_to_latest(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .absolute()
+            .bottom_3()
+            .left_0()
+            .right_0()
+            .justify_center()
+            .child(
+                Button::new("jump-to-latest")
+                    .label(t!("conversation.jump_to_latest").to_string())
+                    .icon(IconName::ArrowDown)
+                    .small()
+                    .rounded_full()
+                    .shadow_md()
+                    .on_click(cx.listener(|this, _, _, cx| this.jump_to_latest(cx))),
+            )
+    }
+
+    /// Scroll so that a window-space position sits just below the top of the conversation
+    fn scroll_to_window_position(&mut self, top: Pixels, cx: &mut Context<Self>) {
+        let anchor = self.scroll_handle.bounds().top() + px(REVEAL_SCROLL_MARGIN_PX);
+        let offset = self.scroll_handle.offset();
+        let max_offset = self.scroll_handle.max_offset().height;
+        let y = (offset.y - (top - anchor)).clamp(-max_offset, px(0.));
+        self.scroll_handle.set_offset(point(offset.x, y));
+        self.stick_to_bottom = false;
+        cx.notify();
+    }
+
+    /// Scroll to a message found by the global session search
+    pub fn reveal_search_match(
+        &mut self,
+        query: String,
+        occurrence: usize,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending_search_reveal = Some((query, occurrence));
+        cx.notify();
+    }
+
+    fn reveal_pending_search_match(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.history_loaded {
+            return;
+        }
+        let Some((query, occurrence)) = self.pending_search_reveal.clone() else {
+            return;
+        };
+        let result = self
+            .message_stream
+            .update(cx, |stream, cx| stream.reveal_text(&query, occurrence, cx));
+        if result == RevealTextResult::NotLaidOut {
+            // Message positions are recorded during layout; try again on the next frame
+            cx.on_next_frame(window, |_, _, cx| cx.notify());
+        } else {
+            self.pending_search_reveal = None;
+        }
+    }
+
+    /// Subscribe to events of the message stream, e.g. jumping from a plan entry to its tool calls
+    fn subscribe_to_message_stream(entity: &Entity<Self>, cx: &mut App) {
+        let message_stream = entity.read(cx).message_stream.clone();
+        entity.update(cx, |_, cx| {
+            cx.subscribe(&message_stream, |this, _, event, cx| match event {
+                AcpMessageStreamEvent::ScrollTo(top) => this.scroll_to_window_position(*top, cx),
+            })
+            .detach();
+        });
+    }
+
     /// Load historical messages for a session
     pub fn load_history_for_session(entity: &Entity<Self>, session_id: String, cx: &mut App) {
         let persistence_service = match AppState::global(cx).persistence_service() {
@@ -244,8 +501,8 @@ impl ConversationPanel {
                                 this.message_stream.update(cx, |stream, cx| {
                                     stream.add_diff_summary_if_needed(cx);
                                 });
-                                this.scroll_handle.scroll_to_bottom();
-                                cx.notify();
+                                this.jump_to_latest(cx);
+                                this.history_loaded = true;
                             });
                         } else {
                             log::warn!("Entity dropped while loading history");
@@ -316,7 +573,8 @@ impl ConversationPanel {
         let session_filter_log_end = session_filter.clone();
 
         // Use MessageService to subscribe with automatic filtering
-        let mut rx = message_service.subscribe_session_updates(session_filter);
+        let (subscription, mut rx) = message_service.subscribe_session_updates(session_filter);
+        Self::keep_bus_subscription(&entity, subscription, cx);
 
         // Spawn background task to receive updates and update entity
         cx.spawn(async move |cx| {
@@ -325,15 +583,30 @@ impl ConversationPanel {
                 session_filter_log.as_deref().unwrap_or("all")
             );
 
+            let flush_interval = Duration::from_millis(UPDATE_FLUSH_INTERVAL_MS);
+            let mut last_flush: Option<Instant> = None;
+
             while let Some(event) = rx.recv().await {
+                // Apply at most one batch per frame; updates arriving in the
+                // meantime are queued and merged into the next batch
+                if let Some(wait) = last_flush
+                    .map(|last| flush_interval.saturating_sub(last.elapsed()))
+                    .filter(|wait| !wait.is_zero())
+                {
+                    Timer::after(wait).await;
+                }
+
                 let mut events = vec![event];
                 while let Ok(event) = rx.try_recv() {
                     events.push(event);
                 }
+                let received = events.len();
+                let events = coalesce_session_updates(events);
                 let events_len = events.len();
 
-                log::info!(
-                    "Background task received {} updates for session: {}",
+                log::debug!(
+                    "Received {} updates ({} after coalescing) for session: {}",
+                    received,
                     events_len,
                     session_filter_log.as_deref().unwrap_or("all")
                 );
@@ -341,9 +614,8 @@ impl ConversationPanel {
                 let weak = weak_entity.clone();
                 let _ = cx.update(move |cx| {
                     if let Some(entity) = weak.upgrade() {
+                        let started = Instant::now();
                         entity.update(cx, |this, cx| {
-                            let should_auto_scroll = this.should_auto_scroll();
-
                             for event in events {
                                 let session_id = event.session_id.clone();
                                 let agent_name = event.agent_name.clone();
@@ -358,15 +630,14 @@ impl ConversationPanel {
                                 });
                             }
 
-                            if should_auto_scroll {
-                                this.scroll_handle.scroll_to_bottom();
-                            }
+                            this.follow_new_content();
                             cx.notify();
 
                             let total_items = this.message_stream.read(cx).len();
-                            log::info!(
-                                "Rendered {} session updates, total items: {}",
+                            log::debug!(
+                                "Applied {} session updates in {:?}, total items: {}",
                                 events_len,
+                                started.elapsed(),
                                 total_items
                             );
                         });
@@ -374,6 +645,7 @@ impl ConversationPanel {
                         log::warn!("Entity dropped, skipping update");
                     }
                 });
+                last_flush = Some(Instant::now());
             }
 
             log::info!(
@@ -407,7 +679,7 @@ impl ConversationPanel {
         let filter_log_inner = session_filter.clone();
 
         // Subscribe to permission requests, send requests to channel in callback
-        if let Some(filter_id) = session_filter.clone() {
+        let id = if let Some(filter_id) = session_filter.clone() {
             event_hub.subscribe_permission_requests_for_session(filter_id, move |event| {
                 // This callback runs in agent I/O thread
                 let _ = tx.send(event.clone());
@@ -416,7 +688,7 @@ impl ConversationPanel {
                     event.permission_id,
                     event.session_id
                 );
-            });
+            })
         } else {
             event_hub.subscribe_permission_requests(move |event| {
                 // This callback runs in agent I/O thread
@@ -426,8 +698,9 @@ impl ConversationPanel {
                     event.permission_id,
                     event.session_id
                 );
-            });
-        }
+            })
+        };
+        Self::keep_bus_subscription(entity, EventSubscription::new(&event_hub, id), cx);
 
         // Spawn background task to receive from channel and update entity
         cx.spawn(async move |cx| {
@@ -498,17 +771,9 @@ impl ConversationPanel {
                                 stream.add_permission_request(permission_view, cx);
                             });
 
+                            this.follow_new_content();
                             cx.notify(); // Trigger re-render immediately
 
-                            // Scroll to bottom after render completes
-                            // Use a very large offset to ensure we reach the bottom
-                            let scroll_handle = this.scroll_handle.clone();
-                            cx.defer(move |_| {
-                                // Set to a very large Y offset to ensure scrolling to bottom
-                                scroll_handle
-                                    .set_offset(gpui::point(gpui::px(0.), gpui::px(999999.)));
-                            });
-
                             log::info!(
                                 "Rendered permission request, total items: {}",
                                 this.message_stream.read(cx).len()
@@ -531,17 +796,35 @@ impl ConversationPanel {
     }
 
     /// Subscribe to code selection events via EventHub
-    pub fn subscribe_to_code_selections(entity: &Entity<Self>, cx: &mut App) {
-        crate::core::event_bus::subscribe_entity_to_code_selections(
+    pub fn subscribe_to_code_selections(entity: &Entity<Self>, window: &Window, cx: &mut App) {
+        let window_handle = window.window_handle();
+        let subscription = crate::core::event_bus::subscribe_entity_to_code_selections(
             entity,
             AppState::global(cx).event_hub().clone(),
             "ConversationPanel",
             |panel, selection, cx| {
-                panel.code_selections.push(selection.into());
+                let selection: AddCodeSelection = selection.into();
+                // Ignore repeated identical selections
+                if panel.code_selections.contains(&selection) {
+                    return;
+                }
+                panel
+                    .pending_references
+                    .push(format!("`{}`", selection.reference()));
+                panel.code_selections.push(selection);
+                // The input needs the window, which this bus callback doesn't
+                // get; apply the references once the current update is done
+                let entity = cx.entity();
+                cx.defer(move |cx| {
+                    _ = window_handle.update(cx, |_, window, cx| {
+                        entity.update(cx, |panel, cx| panel.insert_pending_references(window, cx));
+                    });
+                });
                 cx.notify();
             },
             cx,
         );
+        Self::keep_bus_subscription(entity, subscription, cx);
     }
 
     /// Subscribe to WorkspaceUpdateBus to receive session status updates
@@ -561,7 +844,7 @@ impl ConversationPanel {
         let filter_log3 = session_filter.clone();
 
         // Subscribe to workspace bus, send status updates to channel in callback
-        event_hub.subscribe_workspace_updates(move |event| {
+        let id = event_hub.subscribe_workspace_updates(move |event| {
             // Only handle SessionStatusUpdated events
             if let crate::core::event_bus::WorkspaceUpdateEvent::SessionStatusUpdated {
                 session_id,
@@ -583,6 +866,7 @@ impl ConversationPanel {
                 );
             }
         });
+        Self::keep_bus_subscription(entity, EventSubscription::new(&event_hub, id), cx);
 
         // Spawn background task to receive from channel and update entity
         cx.spawn(async move |cx| {
@@ -633,6 +917,14 @@ impl ConversationPanel {
                                     last_active,
                                     message_count,
                                 });
+                                this.session_info = Self::lookup_session_info(
+                                    this.session_id.as_deref(),
+                                    this.agent_name.as_deref(),
+                                    cx,
+                                );
+                                if !this.is_turn_in_progress() {
+                                    this.send_next_queued_prompt(cx);
+                                }
                                 cx.notify(); // Trigger re-render
                             });
                         } else {
@@ -654,6 +946,469 @@ impl ConversationPanel {
         );
     }
 
+    /// Keep the header and tab title in sync when the session is renamed elsewhere
+    fn subscribe_to_title_updates(entity: &Entity<Self>, session_id: String, cx: &mut App) {
+        let weak_entity = entity.downgrade();
+        let event_hub = AppState::global(cx).event_hub().clone();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Option<String>>();
+
+        let id = event_hub.subscribe_workspace_updates(move |event| {
+            if let crate::core::event_bus::WorkspaceUpdateEvent::SessionTitleUpdated {
+                session_id: updated_id,
+                title,
+                ..
+            } = event
+                && updated_id == &session_id
+            {
+                let _ = tx.send(title.clone());
+            }
+        });
+        Self::keep_bus_subscription(entity, EventSubscription::new(&event_hub, id), cx);
+
+        cx.spawn(async move |cx| {
+            while let Some(title) = rx.recv().await {
+                let Some(entity) = weak_entity.upgrade() else {
+                    break;
+                };
+                let _ = cx.update(|cx| {
+                    entity.update(cx, |this, cx| {
+                        this.session_title = title;
+                        cx.notify();
+                    });
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Show the inline title editor in the header
+    fn start_rename(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current = self.session_title.clone().unwrap_or_default();
+        let input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx)
+                .placeholder(t!("conversation.session.title_placeholder").to_string());
+            state.set_value(current, window, cx);
+            state
+        });
+        input.update(cx, |state, cx| state.focus(window, cx));
+
+        let subscription = cx.subscribe_in(
+            &input,
+            window,
+            |this, _input, event: &InputEvent, _window, cx| match event {
+                InputEvent::PressEnter { .. } | InputEvent::Blur => this.commit_rename(cx),
+                _ => {}
+            },
+        );
+        self.title_input = Some((input, subscription));
+        cx.notify();
+    }
+
+    /// Save the edited title; an empty title reverts to the short id
+    fn commit_rename(&mut self, cx: &mut Context<Self>) {
+        let Some((input, _subscription)) = self.title_input.take() else {
+            return;
+        };
+        let (Some(session_id), Some(agent_name)) =
+            (self.session_id.clone(), self.owning_agent_name())
+        else {
+            cx.notify();
+            return;
+        };
+        let agent_name = agent_name.to_string();
+        let title = input.read(cx).value().trim().to_string();
+        self.session_title = Some(title.clone()).filter(|title| !title.is_empty());
+        cx.notify();
+
+        let Some(agent_service) = AppState::global(cx).agent_service().cloned() else {
+            return;
+        };
+        cx.spawn(async move |_this, _cx| {
+            if let Err(e) = agent_service
+                .set_session_title(&agent_name, &session_id, &title)
+                .await
+            {
+                log::error!("Failed to rename session {}: {}", session_id, e);
+            }
+        })
+        .detach();
+    }
+
+    /// Fetch the session's metadata from AgentService
+    fn lookup_session_info(
+        session_id: Option<&str>,
+        agent_name: Option<&str>,
+        cx: &App,
+    ) -> Option<AgentSessionInfo> {
+        let service = AppState::global(cx).agent_service()?;
+        service.get_session_info(agent_name?, session_id?)
+    }
+
+    /// Modes offered for the session as (id, name): the ones the agent
+    /// reported, else the list configured for the agent
+    fn mode_options(&self, cx: &App) -> Vec<(String, String)> {
+        let state = AppState::global(cx);
+        if let Some(modes) = self.session_id.as_deref().and_then(|session_id| {
+            state
+                .agent_service()
+                .and_then(|service| service.session_mode_state(session_id))
+        }) {
+            return modes
+                .available_modes
+                .iter()
+                .map(|mode| (mode.id.to_string(), mode.name.clone()))
+                .collect();
+        }
+        match (self.agent_name.as_deref(), state.agent_config_service()) {
+            (Some(agent_name), Some(service)) => service
+                .agent_modes(agent_name)
+                .into_iter()
+                .map(|mode| (mode.clone(), mode))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Mode menu in the session header; hidden when no modes are known
+    fn render_mode_selector(&self, session_id: &str, cx: &App) -> Option<impl IntoElement> {
+        let modes = self.mode_options(cx);
+        if modes.is_empty() {
+            return None;
+        }
+        let current = AppState::global(cx)
+            .agent_service()
+            .and_then(|service| service.session_mode(session_id));
+        let label = current
+            .as_ref()
+            .and_then(|current| modes.iter().find(|(id, _)| id == current))
+            .map(|(_, name)| name.clone())
+            .or_else(|| current.clone())
+            .unwrap_or_else(|| t!("conversation.mode.none").to_string());
+        let session_id = session_id.to_string();
+
+        Some(
+            Button::new("session-mode")
+                .label(label)
+                .icon(IconName::ChevronDown)
+                .ghost()
+                .xsmall()
+                .tooltip(t!("conversation.mode.tooltip").to_string())
+                .dropdown_menu(move |menu, _, _| {
+                    modes.iter().fold(menu, |menu, (id, name)| {
+                        menu.menu_with_check(
+                            name.clone(),
+                            current.as_deref() == Some(id.as_str()),
+                            Box::new(SetSessionMode {
+                                session_id: session_id.clone(),
+                                mode_id: id.clone(),
+                            }),
+                        )
+                    })
+                }),
+        )
+    }
+
+    /// Display name of the session's current model, if the agent reported one
+    fn current_model_name(info: &AgentSessionInfo) -> Option<String> {
+        let models = info.new_session_response.as_ref()?.models.as_ref()?;
+        let name = models
+            .available_models
+            .iter()
+            .find(|model| model.model_id == models.current_model_id)
+            .map(|model| model.name.clone())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| models.current_model_id.to_string());
+        Some(name)
+    }
+
+    fn status_label(status: &SessionStatus) -> String {
+        match status {
+            SessionStatus::Active => t!("task_panel.status.active"),
+            SessionStatus::Idle => t!("task_panel.status.idle"),
+            SessionStatus::Pending => t!("task_panel.status.pending"),
+            SessionStatus::InProgress => t!("task_panel.status.in_progress"),
+            SessionStatus::Completed => t!("task_panel.status.completed"),
+            SessionStatus::Failed => t!("task_panel.status.failed"),
+            SessionStatus::Closed => t!("task_panel.status.closed"),
+        }
+        .to_string()
+    }
+
+    /// Render the collapsible metadata row: agent, model, status and timestamps
+    fn render_session_details(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let info = self.session_info.as_ref();
+        let status = self
+            .session_status
+            .as_ref()
+            .map(|status| status.status.clone())
+            .or_else(|| info.map(|info| info.status.clone()));
+        let last_active = self
+            .session_status
+            .as_ref()
+            .map(|status| status.last_active)
+            .or_else(|| info.map(|info| info.last_active));
+        let format_time = |time: DateTime<Utc>| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        };
+
+        h_flex()
+            .px_3()
+            .pb_1()
+            .gap_3()
+            .flex_wrap()
+            .items_center()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .when_some(self.agent_name.clone(), |this, agent_name| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .child(Icon::new(crate::assets::agent_icon(&agent_name, cx)).size(px(12.)))
+                        .child(agent_name),
+                )
+            })
+            .when_some(info.and_then(Self::current_model_name), |this, model| {
+                this.child(t!("conversation.session.details.model", model = model).to_string())
+            })
+            .when_some(status, |this, status| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .child(StatusIndicator::new(status.clone()).size(6.))
+                        .child(Self::status_label(&status)),
+                )
+            })
+            .when_some(info.map(|info| info.created_at), |this, created_at| {
+                this.child(
+                    t!(
+                        "conversation.session.details.created",
+                        time = format_time(created_at)
+                    )
+                    .to_string(),
+                )
+            })
+            .when_some(last_active, |this, last_active| {
+                this.child(
+                    t!(
+                        "conversation.session.details.updated",
+                        time = format_time(last_active)
+                    )
+                    .to_string(),
+                )
+            })
+            .when_some(self.working_directory.clone(), |this, dir| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .child(Icon::new(IconName::Folder).size(px(12.)))
+                        .child(dir),
+                )
+            })
+    }
+
+    /// Render the session title header with inline rename
+    fn render_title_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(session_id) = self.session_id.as_deref() else {
+            return div().into_any_element();
+        };
+        let short_id: String = session_id.chars().take(8).collect();
+
+        let title_row = h_flex()
+            .px_3()
+            .py_1()
+            .gap_2()
+            .items_center()
+            .map(|this| match &self.title_input {
+                Some((input, _)) => this.child(div().flex_1().child(Input::new(input).xsmall())),
+                None => this
+                    .child(div().flex_1().text_sm().font_semibold().truncate().child(
+                        self.session_title.clone().unwrap_or_else(|| {
+                            t!("conversation.session.untitled", id = short_id).to_string()
+                        }),
+                    ))
+                    .child(
+                        Button::new("rename-session")
+                            .label(t!("conversation.session.rename").to_string())
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.start_rename(window, cx);
+                            })),
+                    ),
+            })
+            .children(self.render_mode_selector(session_id, cx))
+            .child(
+                Button::new("toggle-session-details")
+                    .icon(Icon::new(if self.details_expanded {
+                        IconName::ChevronDown
+                    } else {
+                        IconName::ChevronRight
+                    }))
+                    .tooltip(if self.details_expanded {
+                        t!("conversation.session.details.hide").to_string()
+                    } else {
+                        t!("conversation.session.details.show").to_string()
+                    })
+                    .ghost()
+                    .xsmall()
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.details_expanded = !this.details_expanded;
+                        cx.notify();
+                    })),
+            )
+            .when_some(
+                self.working_directory
+                    .clone()
+                    .filter(|_| !self.details_expanded),
+                |this, dir| {
+                    this.child(
+                        h_flex()
+                            .max_w(px(240.))
+                            .gap_1()
+                            .items_center()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(Icon::new(IconName::Folder).size(px(12.)))
+                            .child(div().truncate().child(dir)),
+                    )
+                },
+            );
+
+        v_flex()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(title_row)
+            .when(self.details_expanded, |this| {
+                this.child(self.render_session_details(cx))
+            })
+            .into_any_element()
+    }
+
+    /// Poll the git status of the session's workspace folder
+    ///
+    /// Uses the session's own cwd and the workspace of the task bound to it.
+    /// The active workspace is only a fallback until a directory is known, so
+    /// switching workspaces never moves an open session. Non-git folders simply
+    /// leave `git_status` as None.
+    pub fn subscribe_to_git_status(
+        entity: &Entity<Self>,
+        session_id: Option<String>,
+        cx: &mut App,
+    ) {
+        let weak_entity = entity.downgrade();
+
+        cx.spawn(async move |cx| {
+            loop {
+                let Ok((workspace_service, session_cwd, known_dir)) = cx.update(|cx| {
+                    let state = AppState::global(cx);
+                    let session_cwd = session_id.as_deref().and_then(|session_id| {
+                        state
+                            .agent_service()
+                            .and_then(|service| service.session_cwd(session_id))
+                    });
+                    let known_dir = weak_entity
+                        .upgrade()
+                        .and_then(|entity| entity.read(cx).working_directory.clone());
+                    (state.workspace_service().cloned(), session_cwd, known_dir)
+                }) else {
+                    break;
+                };
+
+                let workspace = match workspace_service {
+                    Some(service) => {
+                        let task_workspace = match &session_id {
+                            Some(session_id) => match service.get_task_by_session(session_id).await
+                            {
+                                Some(task) => service.get_workspace(&task.workspace_id).await,
+                                None => None,
+                            },
+                            None => None,
+                        };
+                        match task_workspace {
+                            Some(workspace) => Some(workspace),
+                            None if known_dir.is_none() && session_cwd.is_none() => {
+                                service.get_active_workspace().await
+                            }
+                            None => None,
+                        }
+                    }
+                    None => None,
+                };
+
+                let working_directory = session_cwd
+                    .map(|cwd| cwd.to_string_lossy().to_string())
+                    .or_else(|| {
+                        workspace
+                            .as_ref()
+                            .map(|workspace| workspace.path.to_string_lossy().to_string())
+                    })
+                    .or(known_dir);
+
+                let git_status = match &working_directory {
+                    Some(path) => {
+                        let path = std::path::PathBuf::from(path);
+                        smol::unblock(move || crate::utils::git::git_status(&path)).await
+                    }
+                    None => None,
+                };
+
+                let weak = weak_entity.clone();
+                let alive = cx
+                    .update(|cx| {
+                        let Some(entity) = weak.upgrade() else {
+                            return false;
+                        };
+                        entity.update(cx, |this, cx| {
+                            if let Some(workspace) = &workspace {
+                                this.workspace_id = Some(workspace.id.clone());
+                                this.workspace_name = Some(workspace.name.clone());
+                            }
+                            if this.working_directory != working_directory {
+                                this.working_directory = working_directory;
+                                cx.notify();
+                            }
+                            if this.git_status != git_status {
+                                this.git_status = git_status;
+                                cx.notify();
+                            }
+                        });
+                        true
+                    })
+                    .unwrap_or(false);
+
+                if !alive {
+                    break;
+                }
+
+                Timer::after(Duration::from_secs(GIT_STATUS_POLL_INTERVAL_SECS)).await;
+            }
+        })
+        .detach();
+    }
+
+    /// Append queued code references to the end of the input text
+    fn insert_pending_references(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.pending_references.is_empty() {
+            return;
+        }
+
+        let references = std::mem::take(&mut self.pending_references).join(" ");
+        self.input_state.update(cx, |state, cx| {
+            let current = state.value();
+            let value = if current.is_empty() || current.ends_with(char::is_whitespace) {
+                format!("{}{} ", current, references)
+            } else {
+                format!("{} {} ", current, references)
+            };
+            state.set_value(SharedString::from(value), window, cx);
+        });
+    }
+
     /// Handle paste event and add images to pasted_images list
     /// Returns true if we handled the paste (had images), false otherwise
     fn handle_paste(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
@@ -661,6 +1416,17 @@ impl ConversationPanel {
 
         let mut handled = false;
         if let Some(clipboard_item) = cx.read_from_clipboard() {
+            let has_images = clipboard_item
+                .entries()
+                .iter()
+                .any(|entry| matches!(entry, ClipboardEntry::Image(_)));
+            if has_images && !self.agent_accepts_images(cx) {
+                struct ImagesUnsupported;
+                let note = Notification::warning(t!("conversation.images_unsupported").to_string())
+                    .id::<ImagesUnsupported>();
+                window.push_notification(note, cx);
+                return true;
+            }
             for entry in clipboard_item.entries().iter() {
                 if let ClipboardEntry::Image(image) = entry {
                     log::info!("Processing pasted image: {:?}", image.format);
@@ -689,6 +1455,154 @@ impl ConversationPanel {
         handled
     }
 
+    /// Whether the session's agent advertised image support (assumed until known)
+    fn agent_accepts_images(&self, cx: &App) -> bool {
+        match (
+            self.agent_name.as_deref(),
+            AppState::global(cx).agent_service(),
+        ) {
+            (Some(agent_name), Some(service)) => service.supports_images(agent_name),
+            _ => true,
+        }
+    }
+
+    /// Whether the agent is still working on the last prompt
+    fn is_turn_in_progress(&self) -> bool {
+        self.session_status
+            .as_ref()
+            .is_some_and(|info| info.status == SessionStatus::InProgress)
+    }
+
+    /// Send the message now, or queue it behind the turn in progress.
+    /// Queued prompts keep their order: nothing skips ahead of the queue.
+    fn send_or_queue(
+        &mut self,
+        text: String,
+        images: Vec<(ImageContent, String)>,
+        code_selections: Vec<AddCodeSelection>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.session_id.is_some()
+            && (self.is_turn_in_progress() || !self.queued_prompts.is_empty())
+        {
+            self.queued_prompts.push_back(QueuedPrompt {
+                text,
+                images,
+                code_selections,
+            });
+            cx.notify();
+            return;
+        }
+        self.send_message(text, images, code_selections, window, cx);
+        self.mark_turn_started();
+    }
+
+    /// Treat the turn as running before the agent reports it, so a prompt
+    /// sent right after this one is queued instead of racing it
+    fn mark_turn_started(&mut self) {
+        if let Some(info) = self.session_status.as_mut() {
+            info.status = SessionStatus::InProgress;
+        }
+    }
+
+    /// Start the next queued prompt once the previous turn has ended
+    fn send_next_queued_prompt(&mut self, cx: &mut Context<Self>) {
+        let Some(session_id) = self.session_id.clone() else {
+            return;
+        };
+        let Some(prompt) = self.queued_prompts.pop_front() else {
+            return;
+        };
+        log::info!(
+            "Sending queued prompt to session {} ({} still queued)",
+            session_id,
+            self.queued_prompts.len()
+        );
+        DockWorkspace::send_message_to_session(
+            SendMessageToSession {
+                session_id,
+                message: prompt.text,
+                images: prompt.images,
+                code_selections: prompt.code_selections,
+            },
+            cx,
+        );
+        self.mark_turn_started();
+        self.jump_to_latest(cx);
+    }
+
+    /// Prompts waiting for the current turn, each of which can be removed
+    fn render_prompt_queue(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.queued_prompts.is_empty() {
+            return div().into_any_element();
+        }
+
+        v_flex()
+            .mx(px(24.))
+            .mb_1()
+            .px_3()
+            .py_2()
+            .gap_1()
+            .rounded(px(8.))
+            .border_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().muted.opacity(0.3))
+            .child(
+                h_flex()
+                    .justify_between()
+                    .items_center()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(
+                        t!(
+                            "conversation.queue.count",
+                            count = self.queued_prompts.len()
+                        )
+                        .to_string(),
+                    )
+                    .child(
+                        Button::new("clear-queued-prompts")
+                            .label(t!("conversation.queue.clear").to_string())
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.queued_prompts.clear();
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .children(self.queued_prompts.iter().enumerate().map(|(ix, prompt)| {
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .text_sm()
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w_0()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .whitespace_nowrap()
+                            .child(prompt.preview()),
+                    )
+                    .child(
+                        Button::new(("remove-queued-prompt", ix))
+                            .icon(IconName::Close)
+                            .ghost()
+                            .xsmall()
+                            .tooltip(t!("conversation.queue.remove").to_string())
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                if ix < this.queued_prompts.len() {
+                                    this.queued_prompts.remove(ix);
+                                    cx.notify();
+                                }
+                            })),
+                    )
+            }))
+            .into_any_element()
+    }
+
     /// Send a message to the current session
     /// Dispatches SendMessageToSession action to workspace for handling
     fn send_message(
@@ -780,6 +1694,45 @@ impl ConversationPanel {
         }
     }
 
+    /// Render the git branch strip shown above the input box
+    fn render_git_status(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(status) = &self.git_status else {
+            return div().into_any_element();
+        };
+
+        let branch = status
+            .branch
+            .clone()
+            .unwrap_or_else(|| t!("conversation.git.detached").to_string());
+
+        h_flex()
+            .px_3()
+            .py_1()
+            .gap_2()
+            .items_center()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .child(Icon::new(IconName::GitHub).size(px(12.)))
+            .child(div().child(branch))
+            .when(status.ahead > 0, |this| {
+                this.child(div().child(format!("↑{}", status.ahead)))
+            })
+            .when(status.behind > 0, |this| {
+                this.child(div().child(format!("↓{}", status.behind)))
+            })
+            .when(status.dirty, |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .text_color(cx.theme().warning)
+                        .child(Icon::new(IconName::TriangleAlert).size(px(12.)))
+                        .child(t!("conversation.git.dirty").to_string()),
+                )
+            })
+            .into_any_element()
+    }
+
     /// Render the loading skeleton and status info when session is in progress
     fn render_loading_skeleton(&self, cx: &mut Context<Self>) -> impl IntoElement {
         // Only show loading skeleton when session is actively processing
@@ -947,7 +1900,28 @@ impl DockPanel for ConversationPanel {
     }
 
     fn on_active_any(view: gpui::AnyView, active: bool, window: &mut Window, cx: &mut App) {
-        let _ = (view, active, window, cx);
+        let _ = window;
+        if !active {
+            return;
+        }
+        // Called while the dock updates its tabs; read the panel once that is done
+        cx.defer(move |cx| {
+            let Ok(panel) = view.downcast::<Self>() else {
+                return;
+            };
+            let Some(session_id) = panel.read(cx).session_id.clone() else {
+                return;
+            };
+            let Some(workspace_service) = AppState::global(cx).workspace_service().cloned() else {
+                return;
+            };
+            cx.spawn(async move |_cx| {
+                if let Err(e) = workspace_service.set_last_active_session(&session_id).await {
+                    log::warn!("Failed to remember last active session: {}", e);
+                }
+            })
+            .detach();
+        });
     }
 
     fn paddings() -> gpui::Pixels {
@@ -962,8 +1936,22 @@ impl Focusable for ConversationPanel {
 }
 
 impl Render for ConversationPanel {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Scrolling updates the message stream, which must not happen while rendering
+        if self.pending_search_reveal.is_some() && self.history_loaded {
+            cx.defer_in(window, |this, window, cx| {
+                this.reveal_pending_search_match(window, cx);
+            });
+        }
+
+        // Scrolled back down (e.g. by dragging the scrollbar): follow new content again
+        if !self.stick_to_bottom && self.is_near_bottom() {
+            self.stick_to_bottom = true;
+            self.has_unseen_content = false;
+        }
+
         let is_empty = self.message_stream.read(cx).is_empty();
+        let no_agents = self.no_agents && self.session_id.is_none();
         let message_list = v_flex()
             .p_4()
             .gap_3()
@@ -975,35 +1963,48 @@ impl Render for ConversationPanel {
         v_flex()
             .id("messages")
             .size_full()
+            .child(self.render_title_header(cx))
             .child(
-                // Scrollable message area - takes remaining space
                 div()
-                    .id("conversation-scroll-container")
+                    .relative()
                     .flex_1()
+                    .min_h_0()
                     .w_full()
-                    .track_scroll(&self.scroll_handle)
-                    .overflow_y_scroll()
-                    .size_full()
-                    .when(is_empty, |this| {
-                        // Show empty state with centered text
-                        this.child(
-                            div()
-                                .size_full()
-                                .flex()
-                                .items_center()
-                                .justify_center()
-                                .child(
+                    .child(
+                        // Scrollable message area - takes remaining space
+                        div()
+                            .id("conversation-scroll-container")
+                            .track_scroll(&self.scroll_handle)
+                            .overflow_y_scroll()
+                            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+                            .size_full()
+                            .when(is_empty && no_agents, |this| {
+                                this.child(NoAgentsState::new("conversation-no-agents"))
+                            })
+                            .when(is_empty && !no_agents, |this| {
+                                // Show empty state with centered text
+                                this.child(
                                     div()
-                                        .text_color(cx.theme().muted_foreground)
-                                        .text_sm()
-                                        .child(t!("conversation.empty").to_string()),
-                                ),
-                        )
-                    })
-                    .when(!is_empty, |this| {
-                        // Show message list
-                        this.pb_3() // Add padding at bottom so messages don't get hidden behind input box
-                            .child(message_list)
+                                        .size_full()
+                                        .flex()
+                                        .items_center()
+                                        .justify_center()
+                                        .child(
+                                            div()
+                                                .text_color(cx.theme().muted_foreground)
+                                                .text_sm()
+                                                .child(t!("conversation.empty").to_string()),
+                                        ),
+                                )
+                            })
+                            .when(!is_empty, |this| {
+                                // Show message list
+                                this.pb_3() // Add padding at bottom so messages don't get hidden behind input box
+                                    .child(message_list)
+                            }),
+                    )
+                    .when(self.has_unseen_content, |this| {
+                        this.child(self.render_jump_to_latest(cx))
                     }),
             )
             .child(
@@ -1015,11 +2016,16 @@ impl Render for ConversationPanel {
                     // .border_t_1()
                     .p_1()
                     // .border_color(cx.theme().border)
+                    .child(self.render_prompt_queue(cx))
+                    .child(self.render_git_status(cx))
                     .child({
                         let entity = cx.entity().clone();
                         let is_disabled = self.is_input_disabled();
-                        ChatInputBox::new("chat-input", self.input_state.clone())
-                            .pasted_images(self.pasted_images.clone())
+                        let mut chat = ChatInputBox::new("chat-input", self.input_state.clone());
+                        if no_agents {
+                            chat = chat.disabled_reason(NoAgentsState::disabled_reason());
+                        }
+                        chat.pasted_images(self.pasted_images.clone())
                             .code_selections(self.code_selections.clone())
                             .session_status(
                                 self.session_status.as_ref().map(|info| info.status.clone()),
@@ -1058,7 +2064,8 @@ impl Render for ConversationPanel {
                                     // Send the message with images and code selections
                                     let images = std::mem::take(&mut this.pasted_images);
                                     let code_selections = std::mem::take(&mut this.code_selections);
-                                    this.send_message(text, images, code_selections, window, cx);
+                                    this.send_or_queue(text, images, code_selections, window, cx);
+                                    this.jump_to_latest(cx);
 
                                     cx.notify();
                                 }
@@ -3,7 +3,7 @@ use gpui::{prelude::FluentBuilder, *};
 use gpui_component::{
     ActiveTheme, IconName, WindowExt,
     button::Button,
-    dock::{Panel, PanelControl, PanelEvent, PanelInfo, PanelState, TitleStyle},
+    dock::{ClosePanel, Panel, PanelControl, PanelEvent, PanelInfo, PanelState, TitleStyle},
     group_box::{GroupBox, GroupBoxVariants as _},
     h_flex,
     menu::PopupMenu,
@@ -15,8 +15,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::AppState;
 use crate::panels::{
-    CodeEditorPanel, ConversationPanel, SessionManagerPanel, SettingsPanel, TaskPanel,
-    TerminalPanel, ToolCallDetailPanel, WelcomePanel,
+    CodeEditorPanel, ConversationPanel, LogViewerPanel, SessionManagerPanel, SettingsPanel,
+    TaskPanel, TerminalPanel, ToolCallDetailPanel, WelcomePanel,
+    pop_out::{PopOutContent, PopOutPanel},
 };
 use crate::{ShowPanelInfo, ToggleSearch};
 
@@ -262,6 +263,27 @@ impl DockPanelContainer {
     }
     /// Create a panel for a specific session (currently only supports ConversationPanel)
     /// This will load the conversation hiagent_studio for that session
+    /// Whether a session referenced by a saved layout can still be shown
+    ///
+    /// Sessions without a history file that no agent knows about were
+    /// deleted since the layout was saved; their tab is restored empty.
+    fn session_still_exists(session_id: &str, cx: &App) -> bool {
+        let state = AppState::global(cx);
+        let has_history = state
+            .persistence_service()
+            .map_or(true, |service| service.session_file_exists(session_id));
+        let is_live = state
+            .agent_service()
+            .is_some_and(|service| service.get_agent_for_session(session_id).is_some());
+        if !has_history && !is_live {
+            log::warn!(
+                "Saved layout references missing session {}, opening an empty conversation",
+                session_id
+            );
+        }
+        has_history || is_live
+    }
+
     pub fn panel_for_session(
         session_id: String,
         window: &mut Window,
@@ -291,6 +313,40 @@ impl DockPanelContainer {
         view
     }
 
+    /// Content to show in a pop-out window, for panels that can be popped out
+    fn pop_out_content(&self, cx: &App) -> Option<PopOutContent> {
+        let view = self.agent_studio.clone()?;
+        match self.agent_studio_klass.as_deref()? {
+            "ConversationPanel" => {
+                let session_id = view
+                    .downcast::<ConversationPanel>()
+                    .ok()?
+                    .read(cx)
+                    .session_id()?;
+                Some(PopOutContent::Conversation { session_id })
+            }
+            "ToolCallDetailPanel" => {
+                let tool_call = view
+                    .downcast::<ToolCallDetailPanel>()
+                    .ok()?
+                    .read(cx)
+                    .tool_call()?
+                    .clone();
+                Some(PopOutContent::ToolCallDetail { tool_call })
+            }
+            _ => None,
+        }
+    }
+
+    /// Move this panel into its own window and close its dock tab
+    fn pop_out(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(content) = self.pop_out_content(cx) else {
+            return;
+        };
+        PopOutPanel::open(content, cx);
+        window.dispatch_action(Box::new(ClosePanel), cx);
+    }
+
     pub fn replace_with_conversation_session(
         &mut self,
         session_id: Option<String>,
@@ -426,12 +482,14 @@ impl DockPanelContainer {
             "TaskPanel" => Self::panel::<TaskPanel>(window, cx),
             "SessionManagerPanel" => Self::panel::<SessionManagerPanel>(window, cx),
             "SettingsPanel" => Self::panel::<SettingsPanel>(window, cx),
+            "LogViewerPanel" => Self::panel::<LogViewerPanel>(window, cx),
             "ToolCallDetailPanel" => Self::panel::<ToolCallDetailPanel>(window, cx),
             "ConversationPanel" => {
                 if let Some(session_id) = agent_state
                     .session_id
                     .as_deref()
                     .filter(|id| !id.is_empty())
+                    .filter(|id| Self::session_still_exists(id, cx))
                 {
                     Self::panel_for_session(session_id.to_string(), window, cx)
                 } else {
@@ -622,8 +680,28 @@ impl Panel for DockPanelContainer {
     fn title(
         &mut self,
         _window: &mut gpui::Window,
-        _cx: &mut gpui::Context<'_, DockPanelContainer>,
+        cx: &mut gpui::Context<'_, DockPanelContainer>,
     ) -> impl gpui::IntoElement {
+        // Code editors show the open file name and its unsaved state
+        if let Some(title) = self
+            .agent_studio
+            .clone()
+            .and_then(|view| view.downcast::<CodeEditorPanel>().ok())
+            .and_then(|panel| panel.read(cx).tab_title())
+        {
+            return title.into_any_element();
+        }
+
+        // Conversations show the agent and session they belong to
+        if let Some(title) = self
+            .agent_studio
+            .clone()
+            .and_then(|view| view.downcast::<ConversationPanel>().ok())
+            .and_then(|panel| panel.read(cx).tab_title())
+        {
+            return title.into_any_element();
+        }
+
         let title = if let Some(key) = &self.title_key {
             SharedString::from(t!(key.as_ref()).to_string())
         } else {
@@ -647,6 +725,23 @@ impl Panel for DockPanelContainer {
         self.closable
     }
 
+    fn on_removed(&mut self, window: &mut Window, cx: &mut gpui::Context<'_, DockPanelContainer>) {
+        if let Some(panel) = self
+            .agent_studio
+            .clone()
+            .and_then(|view| view.downcast::<CodeEditorPanel>().ok())
+        {
+            panel.update(cx, |panel, cx| panel.prompt_save_before_close(window, cx));
+        }
+        if let Some(panel) = self
+            .agent_studio
+            .clone()
+            .and_then(|view| view.downcast::<SettingsPanel>().ok())
+        {
+            panel.update(cx, |panel, cx| panel.prompt_save_before_close(window, cx));
+        }
+    }
+
     fn zoomable(&self, _cx: &App) -> Option<PanelControl> {
         self.zoomable
     }
@@ -693,20 +788,28 @@ impl Panel for DockPanelContainer {
     fn toolbar_buttons(
         &mut self,
         _window: &mut Window,
-        _cx: &mut gpui::Context<'_, DockPanelContainer>,
+        cx: &mut gpui::Context<'_, DockPanelContainer>,
     ) -> Option<Vec<Button>> {
-        Some(vec![
+        let mut buttons = vec![
             // Button::new("info")
             //     .icon(IconName::Info)
             //     .on_click(|_, window, cx| {
             //         window.push_notification("You have clicked info button", cx);
             //     }),
-            // Button::new("search")
-            //     .icon(IconName::Search)
-            //     .on_click(|_, window, cx| {
-            //         window.push_notification("You have clicked search button", cx);
-            //     }),
-        ])
+        ];
+
+        if self.pop_out_content(cx).is_some() {
+            buttons.push(
+                Button::new("pop-out")
+                    .icon(IconName::ExternalLink)
+                    .tooltip(t!("dock.pop_out").to_string())
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.pop_out(window, cx);
+                    })),
+            );
+        }
+
+        Some(buttons)
     }
 
     fn dump(&self, cx: &App) -> PanelState {
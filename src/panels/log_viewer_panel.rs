@@ -0,0 +1,410 @@
+use std::time::Duration;
+
+use gpui::{
+    App, AppContext, ClipboardItem, Context, Entity, FocusHandle, Focusable, Hsla,
+    InteractiveElement as _, IntoElement, ParentElement, Pixels, Render, Styled, Subscription,
+    Task, Window, div, px, uniform_list,
+};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable, WindowExt as _,
+    button::{Button, ButtonGroup, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    notification::Notification,
+    switch::Switch,
+    v_flex,
+};
+use rust_i18n::t;
+use tracing::Level;
+
+use crate::{
+    AppSettings,
+    core::log_capture::{self, LogRecord},
+    panels::dock_panel::DockPanel,
+};
+
+/// How often the panel checks for new log records
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+const ROW_HEIGHT: Pixels = px(22.);
+
+/// Minimum level of records shown in the viewer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LevelFilter {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LevelFilter {
+    const ALL: [Self; 4] = [Self::Error, Self::Warn, Self::Info, Self::Debug];
+
+    fn label(self) -> String {
+        match self {
+            Self::Error => t!("log_viewer.level.error"),
+            Self::Warn => t!("log_viewer.level.warn"),
+            Self::Info => t!("log_viewer.level.info"),
+            Self::Debug => t!("log_viewer.level.debug"),
+        }
+        .to_string()
+    }
+
+    fn includes(self, level: Level) -> bool {
+        match self {
+            Self::Error => level == Level::ERROR,
+            Self::Warn => level <= Level::WARN,
+            Self::Info => level <= Level::INFO,
+            // Debug also shows trace records
+            Self::Debug => true,
+        }
+    }
+}
+
+/// Log Viewer Panel - Shows application logs captured in memory
+pub struct LogViewerPanel {
+    focus_handle: FocusHandle,
+    search_input: Entity<InputState>,
+    level: LevelFilter,
+    /// Records matching the current level and search, oldest first
+    records: Vec<LogRecord>,
+    /// Buffer generation the records were loaded from
+    generation: u64,
+    _refresh_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DockPanel for LogViewerPanel {
+    fn title() -> &'static str {
+        "Logs"
+    }
+
+    fn title_key() -> Option<&'static str> {
+        Some("log_viewer.title")
+    }
+
+    fn description() -> &'static str {
+        "Application logs captured in memory"
+    }
+
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render> {
+        Self::view(window, cx)
+    }
+}
+
+impl LogViewerPanel {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search_input = cx
+            .new(|cx| InputState::new(window, cx).placeholder(t!("log_viewer.search").to_string()));
+
+        let _subscriptions =
+            vec![
+                cx.subscribe(&search_input, |this, _, event: &InputEvent, cx| {
+                    if let InputEvent::Change = event {
+                        this.reload(cx);
+                    }
+                }),
+            ];
+
+        let _refresh_task = cx.spawn(async move |this, cx| {
+            loop {
+                smol::Timer::after(REFRESH_INTERVAL).await;
+                let result = this.update(cx, |this, cx| {
+                    if this.generation != log_capture::generation() {
+                        this.reload(cx);
+                    }
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut panel = Self {
+            focus_handle: cx.focus_handle(),
+            search_input,
+            level: LevelFilter::Info,
+            records: Vec::new(),
+            generation: 0,
+            _refresh_task,
+            _subscriptions,
+        };
+        panel.reload(cx);
+        panel
+    }
+
+    /// Reload records from the capture buffer and apply the filters
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.generation = log_capture::generation();
+
+        let query = self.search_input.read(cx).value().trim().to_lowercase();
+        self.records = log_capture::records()
+            .into_iter()
+            .filter(|record| self.level.includes(record.level))
+            .filter(|record| {
+                query.is_empty()
+                    || record.message.to_lowercase().contains(&query)
+                    || record.target.to_lowercase().contains(&query)
+            })
+            .collect();
+        cx.notify();
+    }
+
+    fn set_level(&mut self, level: LevelFilter, cx: &mut Context<Self>) {
+        self.level = level;
+        self.reload(cx);
+    }
+
+    fn set_verbose(&mut self, verbose: bool, cx: &mut Context<Self>) {
+        log_capture::set_verbose(verbose);
+        AppSettings::global_mut(cx).verbose_logging = verbose;
+        // Debug records are only captured from now on
+        if verbose && self.level != LevelFilter::Debug {
+            self.set_level(LevelFilter::Debug, cx);
+        } else {
+            cx.notify();
+        }
+    }
+
+    /// The visible records as plain text
+    fn visible_text(&self) -> String {
+        self.records
+            .iter()
+            .map(LogRecord::format)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn copy_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(self.visible_text()));
+
+        struct LogsCopied;
+        let note = Notification::success(t!("log_viewer.copied").to_string()).id::<LogsCopied>();
+        window.push_notification(note, cx);
+    }
+
+    fn save_to_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.visible_text();
+        let file_name = format!(
+            "agentx-studio-{}.log",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+
+        cx.spawn_in(window, async move |_this, window| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .set_title(t!("log_viewer.save_dialog_title").to_string())
+                .set_file_name(file_name)
+                .save_file()
+                .await
+            else {
+                return;
+            };
+
+            let path = file.path().to_path_buf();
+            let result = smol::unblock({
+                let path = path.clone();
+                move || std::fs::write(&path, text)
+            })
+            .await;
+
+            _ = window.update(|window, cx| {
+                struct LogsSaved;
+                let note = match result {
+                    Ok(()) => Notification::success(
+                        t!("log_viewer.saved", path = path.display()).to_string(),
+                    ),
+                    Err(e) => Notification::error(
+                        t!("log_viewer.save_failed", error = e.to_string()).to_string(),
+                    ),
+                }
+                .id::<LogsSaved>();
+                window.push_notification(note, cx);
+            });
+        })
+        .detach();
+    }
+
+    fn level_color(level: Level, cx: &App) -> Hsla {
+        let theme = cx.theme();
+        match level {
+            Level::ERROR => theme.danger,
+            Level::WARN => theme.warning,
+            Level::INFO => theme.info,
+            _ => theme.muted_foreground,
+        }
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let level = self.level;
+
+        let mut levels = ButtonGroup::new("log-levels").small();
+        for filter in LevelFilter::ALL {
+            levels = levels.child(
+                Button::new(("log-level", filter as usize))
+                    .label(filter.label())
+                    .ghost()
+                    .xsmall()
+                    .selected(level == filter)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.set_level(filter, cx);
+                    })),
+            );
+        }
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .items_center()
+            .px_3()
+            .py_2()
+            .border_b_1()
+            .border_color(theme.border)
+            .child(levels)
+            .child(
+                div().flex_1().child(
+                    Input::new(&self.search_input)
+                        .small()
+                        .cleanable(true)
+                        .prefix(
+                            Icon::new(IconName::Search)
+                                .size_4()
+                                .text_color(theme.muted_foreground),
+                        ),
+                ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        Switch::new("log-verbose")
+                            .checked(log_capture::is_verbose())
+                            .small()
+                            .on_click(cx.listener(|this, checked, _, cx| {
+                                this.set_verbose(*checked, cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .child(t!("log_viewer.verbose").to_string()),
+                    ),
+            )
+            .child(
+                Button::new("log-copy-all")
+                    .label(t!("log_viewer.copy_all").to_string())
+                    .icon(IconName::Copy)
+                    .ghost()
+                    .xsmall()
+                    .on_click(cx.listener(|this, _, window, cx| this.copy_all(window, cx))),
+            )
+            .child(
+                Button::new("log-save")
+                    .label(t!("log_viewer.save").to_string())
+                    .icon(IconName::ArrowDown)
+                    .ghost()
+                    .xsmall()
+                    .on_click(cx.listener(|this, _, window, cx| this.save_to_file(window, cx))),
+            )
+            .child(
+                Button::new("log-clear")
+                    .label(t!("log_viewer.clear").to_string())
+                    .icon(IconName::Delete)
+                    .ghost()
+                    .xsmall()
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        log_capture::clear();
+                        this.reload(cx);
+                    })),
+            )
+    }
+
+    fn render_row(record: &LogRecord, cx: &App) -> impl IntoElement {
+        let theme = cx.theme();
+
+        h_flex()
+            .h(ROW_HEIGHT)
+            .w_full()
+            .px_3()
+            .gap_2()
+            .items_center()
+            .font_family("Monaco, 'Courier New', monospace")
+            .text_xs()
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_color(theme.muted_foreground)
+                    .child(record.timestamp.format("%H:%M:%S%.3f").to_string()),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .w(px(44.))
+                    .text_color(Self::level_color(record.level, cx))
+                    .child(record.level.to_string()),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .max_w(px(220.))
+                    .truncate()
+                    .text_color(theme.muted_foreground)
+                    .child(record.target.clone()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .truncate()
+                    .text_color(theme.foreground)
+                    .child(record.message.replace('\n', " ")),
+            )
+    }
+}
+
+impl Focusable for LogViewerPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for LogViewerPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.records.is_empty() {
+            div()
+                .flex_1()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(t!("log_viewer.empty").to_string())
+                .into_any_element()
+        } else {
+            uniform_list(
+                "log-records",
+                self.records.len(),
+                cx.processor(|this, range: std::ops::Range<usize>, _window, cx| {
+                    this.records[range]
+                        .iter()
+                        .map(|record| Self::render_row(record, cx))
+                        .collect::<Vec<_>>()
+                }),
+            )
+            .flex_1()
+            .into_any_element()
+        };
+
+        v_flex()
+            .id("log-viewer-panel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_toolbar(cx))
+            .child(content)
+    }
+}
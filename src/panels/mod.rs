@@ -3,6 +3,8 @@
 pub mod code_editor;
 pub mod conversation;
 pub mod dock_panel;
+mod log_viewer_panel;
+mod pop_out;
 mod session_manager;
 mod settings_panel;
 mod task_panel;
@@ -14,6 +16,7 @@ mod welcome_panel;
 pub use code_editor::CodeEditorPanel;
 pub use conversation::ConversationPanel;
 pub use dock_panel::{DockPanel, DockPanelContainer, DockPanelState};
+pub use log_viewer_panel::LogViewerPanel;
 pub use session_manager::SessionManagerPanel;
 pub use settings_panel::{AppSettings, SettingsPanel};
 pub use task_panel::TaskPanel;
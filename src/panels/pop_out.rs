@@ -0,0 +1,141 @@
+use agent_client_protocol::ToolCall;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Root, Sizable,
+    button::{Button, ButtonVariants as _},
+    dock::DockPlacement,
+    h_flex, v_flex,
+};
+use rust_i18n::t;
+
+use crate::panels::{ConversationPanel, DockPanel, ToolCallDetailPanel};
+use crate::{PanelAction, workspace::DockWorkspace};
+
+/// What a pop-out window shows
+///
+/// Only the data needed to rebuild the panel is kept, so the panel can be
+/// constructed in the new window and rebuilt in the dock when docked back.
+#[derive(Clone)]
+pub enum PopOutContent {
+    Conversation { session_id: String },
+    ToolCallDetail { tool_call: ToolCall },
+}
+
+impl PopOutContent {
+    fn title(&self) -> String {
+        match self {
+            Self::Conversation { .. } => ConversationPanel::title().to_string(),
+            Self::ToolCallDetail { .. } => ToolCallDetailPanel::title().to_string(),
+        }
+    }
+
+    fn build_view(&self, window: &mut Window, cx: &mut App) -> AnyView {
+        match self {
+            // A fresh panel loads the session history and subscribes to the
+            // same session updates as the docked one did
+            Self::Conversation { session_id } => {
+                ConversationPanel::view_for_session(session_id.clone(), window, cx).into()
+            }
+            Self::ToolCallDetail { tool_call } => {
+                let tool_call = tool_call.clone();
+                cx.new(|cx| {
+                    let mut panel = ToolCallDetailPanel::new(window, cx);
+                    panel.set_tool_call(tool_call);
+                    panel
+                })
+                .into()
+            }
+        }
+    }
+
+    /// Action that puts the panel back into the main window's dock
+    fn dock_back_action(&self) -> PanelAction {
+        match self {
+            Self::Conversation { session_id } => {
+                PanelAction::add_conversation_for_session(session_id.clone(), DockPlacement::Center)
+            }
+            Self::ToolCallDetail { tool_call } => PanelAction::show_tool_call_detail(
+                tool_call.tool_call_id.to_string(),
+                tool_call.clone(),
+            ),
+        }
+    }
+}
+
+/// A dock panel shown in its own OS window
+pub struct PopOutPanel {
+    content: PopOutContent,
+    view: AnyView,
+}
+
+impl PopOutPanel {
+    /// Open `content` in a new window
+    pub fn open(content: PopOutContent, cx: &mut App) {
+        let title = content.title();
+        crate::create_new_window_with_size(
+            &title,
+            Some(size(px(900.), px(800.))),
+            move |window, cx| {
+                let view = content.build_view(window, cx);
+                cx.new(|_| Self { content, view })
+            },
+            cx,
+        );
+    }
+
+    /// Return the panel to the main window and close this one
+    fn dock_back(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(main_window) = Self::main_window(cx) else {
+            log::warn!("Main window not found, keeping the pop-out window open");
+            return;
+        };
+
+        let action = self.content.dock_back_action();
+        let docked = main_window.update(cx, |_, window, cx| {
+            window.activate_window();
+            window.dispatch_action(Box::new(action), cx);
+        });
+        if docked.is_ok() {
+            window.remove_window();
+        }
+    }
+
+    /// The window hosting the dock workspace
+    fn main_window(cx: &App) -> Option<WindowHandle<Root>> {
+        cx.windows()
+            .into_iter()
+            .filter_map(|handle| handle.downcast::<Root>())
+            .find(|handle| {
+                handle
+                    .read(cx)
+                    .is_ok_and(|root| root.view().clone().downcast::<DockWorkspace>().is_ok())
+            })
+    }
+}
+
+impl Render for PopOutPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .bg(cx.theme().background)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_end()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        Button::new("dock-back")
+                            .label(t!("dock.dock_back").to_string())
+                            .icon(Icon::new(IconName::PanelLeft))
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.dock_back(window, cx);
+                            })),
+                    ),
+            )
+            .child(div().flex_1().overflow_hidden().child(self.view.clone()))
+    }
+}
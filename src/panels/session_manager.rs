@@ -1,24 +1,53 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
 
 use agent_client_protocol as acp;
 use gpui::{
-    App, AppContext, Context, Entity, FocusHandle, Focusable, IntoElement, ParentElement, Pixels,
-    Render, Styled, Window, prelude::FluentBuilder, px,
+    App, AppContext, AsyncApp, Context, ElementId, Entity, FocusHandle, Focusable, IntoElement,
+    ParentElement, Pixels, Render, Styled, Subscription, WeakEntity, Window,
+    prelude::FluentBuilder, px,
 };
 use gpui_component::{
-    ActiveTheme, Icon, IconName, Sizable,
+    ActiveTheme, Disableable, Icon, IconName, Sizable, WindowExt as _,
     button::{Button, ButtonVariants},
     h_flex,
+    input::{Input, InputEvent, InputState},
+    notification::Notification,
     scroll::ScrollableElement as _,
     v_flex,
 };
+use rust_i18n::t;
 
 use crate::{
     AppState,
-    core::services::{AgentSessionInfo, SessionStatus},
+    app::actions::RevealSessionMatch,
+    components::NoAgentsState,
+    core::{
+        agent::AgentLifecycle,
+        event_bus::EventSubscription,
+        services::{
+            AgentSessionInfo, IndexedRole, RequestTimeout, SessionListUnsupported,
+            SessionSearchHit, SessionStatus,
+        },
+    },
     panels::dock_panel::DockPanel,
+    schemas::workspace_schema::SessionImportOutcome,
+    utils,
 };
 
+/// Agent sessions rendered (or fetched) per "Load more" step
+const AGENT_SESSIONS_PAGE_SIZE: usize = 50;
+/// Most hits shown for a search across all sessions
+const SEARCH_RESULT_LIMIT: usize = 50;
+
+/// Element id for a session row, unique per list section
+fn session_row_id(section: &str, agent_idx: usize, session_idx: usize) -> ElementId {
+    ElementId::Name(format!("{section}-{agent_idx}-{session_idx}").into())
+}
+
 #[derive(Clone, Default)]
 struct AgentSessionListState {
     sessions: Vec<acp::SessionInfo>,
@@ -26,6 +55,65 @@ struct AgentSessionListState {
     is_loading: bool,
     has_loaded: bool,
     is_importing: bool,
+    /// Cursor of the next page reported by the agent, if any
+    next_cursor: Option<String>,
+    is_loading_more: bool,
+    /// Number of loaded sessions rendered; the rest sit behind "Load more"
+    visible_count: usize,
+    /// Sessions processed and total pending in the running import
+    import_progress: Option<(usize, usize)>,
+    /// Set to stop the running import before the next session
+    import_cancel: Option<Arc<AtomicBool>>,
+    /// Outcome of the most recent import run
+    import_summary: Option<ImportSummary>,
+}
+
+/// Per-session results of an "Import All" run
+#[derive(Clone, Default)]
+struct ImportSummary {
+    imported: usize,
+    /// Sessions already in the workspace or imported by an earlier run
+    skipped: usize,
+    /// Session IDs that failed to import, with the reason
+    failed: Vec<(String, String)>,
+    /// Sessions left unattempted because the run was cancelled
+    cancelled: usize,
+}
+
+impl ImportSummary {
+    fn describe(&self) -> String {
+        let mut text = t!(
+            "session_manager.import.summary",
+            imported = self.imported,
+            skipped = self.skipped,
+            failed = self.failed.len()
+        )
+        .to_string();
+        if self.cancelled > 0 {
+            text.push_str(&t!(
+                "session_manager.import.summary_cancelled",
+                count = self.cancelled
+            ));
+        }
+        text
+    }
+}
+
+/// Results of a search across the messages of all sessions
+#[derive(Clone, Default)]
+struct SessionSearchState {
+    query: String,
+    hits: Vec<SessionSearchHit>,
+    is_searching: bool,
+    error: Option<String>,
+}
+
+/// Inline title editor for a workspace session row
+struct TitleEditor {
+    agent_name: String,
+    session_id: String,
+    input: Entity<InputState>,
+    _subscription: Subscription,
 }
 
 /// Session Manager Panel - Displays and manages all agent sessions
@@ -33,6 +121,25 @@ pub struct SessionManagerPanel {
     focus_handle: FocusHandle,
     sessions_by_agent: Vec<(String, Vec<AgentSessionInfo>)>,
     agent_sessions_by_agent: HashMap<String, AgentSessionListState>,
+    /// Session IDs hidden from the active list (persisted by WorkspaceService)
+    archived_session_ids: BTreeSet<String>,
+    /// Agents whose "Archived" section is expanded
+    expanded_archived_agents: HashSet<String>,
+    /// Session IDs listed above the others (persisted by WorkspaceService)
+    pinned_session_ids: BTreeSet<String>,
+    /// Session currently being renamed
+    title_editor: Option<TitleEditor>,
+    /// Whether the agent list has been loaded at least once
+    agents_loaded: bool,
+    /// Agents with a "Reconnect" restart in flight
+    reconnecting_agents: HashSet<String>,
+    /// Query input for searching all sessions
+    search_input: Entity<InputState>,
+    /// Latest search across all sessions; None while the query is empty
+    search: Option<SessionSearchState>,
+    _search_subscription: Subscription,
+    /// Config bus subscription, removed from the hub with the panel
+    _agent_subscription: Option<EventSubscription>,
 }
 
 impl DockPanel for SessionManagerPanel {
@@ -62,19 +169,80 @@ impl SessionManagerPanel {
         cx.new(|cx| Self::new(window, cx))
     }
 
-    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(t!("session_manager.search.placeholder").to_string())
+        });
+        let search_subscription = cx.subscribe(
+            &search_input,
+            |this, input, event: &InputEvent, cx| match event {
+                InputEvent::PressEnter { .. } => this.search_sessions(cx),
+                InputEvent::Change if input.read(cx).value().trim().is_empty() => {
+                    this.search = None;
+                    cx.notify();
+                }
+                _ => {}
+            },
+        );
+
         let mut panel = Self {
             focus_handle: cx.focus_handle(),
             sessions_by_agent: Vec::new(),
             agent_sessions_by_agent: HashMap::new(),
+            archived_session_ids: BTreeSet::new(),
+            expanded_archived_agents: HashSet::new(),
+            pinned_session_ids: BTreeSet::new(),
+            title_editor: None,
+            agents_loaded: false,
+            reconnecting_agents: HashSet::new(),
+            search_input,
+            search: None,
+            _search_subscription: search_subscription,
+            _agent_subscription: None,
         };
 
         // Load initial session data
         panel.refresh_sessions(cx);
+        panel.subscribe_to_agent_changes(cx);
 
         panel
     }
 
+    /// Reload the list when agents are added, removed, start or fail
+    fn subscribe_to_agent_changes(&mut self, cx: &mut Context<Self>) {
+        use crate::core::event_bus::AgentConfigEvent;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+        let event_hub = AppState::global(cx).event_hub().clone();
+        let id = event_hub.subscribe_agent_config_updates(move |event| {
+            if matches!(
+                event,
+                AgentConfigEvent::AgentAdded { .. }
+                    | AgentConfigEvent::AgentRemoved { .. }
+                    | AgentConfigEvent::ConfigReloaded { .. }
+                    | AgentConfigEvent::AgentStarting { .. }
+                    | AgentConfigEvent::AgentReady { .. }
+                    | AgentConfigEvent::AgentFailed { .. }
+            ) {
+                let _ = tx.send(());
+            }
+        });
+        self._agent_subscription = Some(EventSubscription::new(&event_hub, id));
+
+        cx.spawn(async move |this, cx| {
+            while rx.recv().await.is_some() {
+                if this
+                    .update(cx, |this, cx| this.refresh_sessions(cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
     /// Refresh sessions from AgentService
     fn refresh_sessions(&mut self, cx: &mut Context<Self>) {
         let agent_service = match AppState::global(cx).agent_service() {
@@ -85,10 +253,20 @@ impl SessionManagerPanel {
             }
         };
 
+        let workspace_service = AppState::global(cx).workspace_service().cloned();
+        let agent_config_service = AppState::global(cx).agent_config_service().cloned();
+
         let weak_self = cx.entity().downgrade();
         cx.spawn(async move |_entity, cx| {
-            // Get all agents
-            let agents = agent_service.list_agents().await;
+            // Get all agents in the configured order, including ones still starting
+            let mut agents: Vec<String> = agent_service
+                .agent_states()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+            if let Some(service) = &agent_config_service {
+                service.sort_agent_names(&mut agents).await;
+            }
 
             // Group sessions by agent
             let mut sessions_by_agent = Vec::new();
@@ -97,10 +275,21 @@ impl SessionManagerPanel {
                 sessions_by_agent.push((agent_name, sessions));
             }
 
+            let (archived_session_ids, pinned_session_ids) = match workspace_service {
+                Some(service) => (
+                    service.archived_sessions().await,
+                    service.pinned_sessions().await,
+                ),
+                None => (BTreeSet::new(), BTreeSet::new()),
+            };
+
             _ = cx.update(|cx| {
                 if let Some(this) = weak_self.upgrade() {
                     this.update(cx, |this, cx| {
                         this.sessions_by_agent = sessions_by_agent;
+                        this.agents_loaded = true;
+                        this.archived_session_ids = archived_session_ids;
+                        this.pinned_session_ids = pinned_session_ids;
                         cx.notify();
                     });
                 }
@@ -109,8 +298,177 @@ impl SessionManagerPanel {
         .detach();
     }
 
+    /// Search the persisted messages of all sessions for the query in the search input
+    fn search_sessions(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_input.read(cx).value().trim().to_string();
+        if query.is_empty() {
+            self.search = None;
+            cx.notify();
+            return;
+        }
+        let Some(message_service) = AppState::global(cx).message_service().cloned() else {
+            log::warn!("[SessionManagerPanel] MessageService not initialized");
+            return;
+        };
+
+        self.search = Some(SessionSearchState {
+            query: query.clone(),
+            is_searching: true,
+            ..Default::default()
+        });
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let result = message_service
+                .search_sessions(&query, SEARCH_RESULT_LIMIT)
+                .await;
+            _ = this.update(cx, |this, cx| {
+                // Ignore results of a query that has since been replaced
+                let Some(search) = this.search.as_mut().filter(|search| search.query == query)
+                else {
+                    return;
+                };
+                search.is_searching = false;
+                match result {
+                    Ok(hits) => search.hits = hits,
+                    Err(e) => {
+                        log::error!("[SessionManagerPanel] Session search failed: {}", e);
+                        search.error = Some(e.to_string());
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Agent name and display title of a workspace session, for search results
+    fn describe_session(&self, session_id: &str) -> (Option<String>, String) {
+        let session = self
+            .sessions_by_agent
+            .iter()
+            .flat_map(|(_, sessions)| sessions.iter())
+            .find(|session| session.session_id == session_id);
+        let title = session
+            .and_then(|session| session.title.clone())
+            .unwrap_or_else(|| {
+                let short_id = session_id.get(..12).unwrap_or(session_id);
+                format!("Session {}", short_id)
+            });
+        (session.map(|session| session.agent_name.clone()), title)
+    }
+
+    fn render_search_results(
+        &self,
+        search: &SessionSearchState,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let theme = cx.theme();
+        let status = if search.is_searching {
+            t!("session_manager.search.searching").to_string()
+        } else if let Some(error) = &search.error {
+            t!("session_manager.search.failed", error = error).to_string()
+        } else if search.hits.is_empty() {
+            t!("session_manager.search.no_results", query = search.query).to_string()
+        } else {
+            t!(
+                "session_manager.search.results",
+                count = search.hits.len(),
+                query = search.query
+            )
+            .to_string()
+        };
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .px_2()
+            .child(
+                gpui::div()
+                    .text_xs()
+                    .text_color(theme.muted_foreground)
+                    .child(status),
+            )
+            .child(
+                gpui::div().max_h(px(280.)).overflow_y_scrollbar().child(
+                    v_flex()
+                        .w_full()
+                        .gap_1()
+                        .children(search.hits.iter().enumerate().map(|(ix, hit)| {
+                            let (agent_name, title) = self.describe_session(&hit.session_id);
+                            let role = match hit.role {
+                                IndexedRole::User => t!("session_manager.search.role.user"),
+                                IndexedRole::Agent => t!("session_manager.search.role.agent"),
+                            };
+                            let time = chrono::DateTime::parse_from_rfc3339(&hit.timestamp)
+                                .map(|time| utils::time::format_time_friendly(&time))
+                                .unwrap_or_default();
+                            let action = RevealSessionMatch {
+                                session_id: hit.session_id.clone(),
+                                query: search.query.clone(),
+                                occurrence: hit.occurrence,
+                            };
+
+                            v_flex()
+                                .id(("search-hit", ix))
+                                .w_full()
+                                .gap_0p5()
+                                .p_2()
+                                .rounded(px(6.))
+                                .bg(theme.background)
+                                .border_1()
+                                .border_color(theme.border.opacity(0.5))
+                                .cursor_pointer()
+                                .hover(|style| style.bg(theme.secondary))
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .items_center()
+                                        .text_xs()
+                                        .child(
+                                            gpui::div()
+                                                .font_weight(gpui::FontWeight::MEDIUM)
+                                                .text_color(theme.foreground)
+                                                .child(title),
+                                        )
+                                        .child(
+                                            gpui::div().text_color(theme.muted_foreground).child(
+                                                match agent_name {
+                                                    Some(agent_name) => {
+                                                        format!(
+                                                            "{} · {} · {}",
+                                                            agent_name, role, time
+                                                        )
+                                                    }
+                                                    None => format!("{} · {}", role, time),
+                                                },
+                                            ),
+                                        ),
+                                )
+                                .child(
+                                    gpui::div()
+                                        .text_xs()
+                                        .text_color(theme.muted_foreground)
+                                        .child(hit.snippet.clone()),
+                                )
+                                .on_click(move |_, window, cx| {
+                                    window.dispatch_action(Box::new(action.clone()), cx);
+                                })
+                        })),
+                ),
+            )
+    }
+
     /// Fetch agent-reported sessions for the given agent (session/list).
-    fn list_agent_sessions(&mut self, agent_name: String, cx: &mut Context<Self>) {
+    ///
+    /// Without a cursor the list is replaced by the first page; with the
+    /// cursor of the previous page the next page is appended.
+    fn list_agent_sessions(
+        &mut self,
+        agent_name: String,
+        cursor: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
         let agent_service = match AppState::global(cx).agent_service() {
             Some(service) => service.clone(),
             None => {
@@ -123,13 +481,19 @@ impl SessionManagerPanel {
             .agent_sessions_by_agent
             .entry(agent_name.clone())
             .or_default();
-        state.is_loading = true;
+        let append = cursor.is_some();
+        if append {
+            state.is_loading_more = true;
+        } else {
+            state.is_loading = true;
+            state.visible_count = AGENT_SESSIONS_PAGE_SIZE;
+        }
         state.error = None;
         cx.notify();
 
         let weak_self = cx.entity().downgrade();
         cx.spawn(async move |_entity, cx| {
-            let request = acp::ListSessionsRequest::new();
+            let request = acp::ListSessionsRequest::new().cursor(cursor);
             let result = agent_service
                 .list_agent_sessions(&agent_name, request)
                 .await;
@@ -142,15 +506,36 @@ impl SessionManagerPanel {
                             .entry(agent_name.clone())
                             .or_default();
                         state.is_loading = false;
+                        state.is_loading_more = false;
                         state.has_loaded = true;
                         match result {
+                            Ok(response) if append => {
+                                state.visible_count =
+                                    state.sessions.len() + AGENT_SESSIONS_PAGE_SIZE;
+                                state.sessions.extend(response.sessions);
+                                state.next_cursor = response.next_cursor;
+                            }
                             Ok(response) => {
                                 state.sessions = response.sessions;
+                                state.next_cursor = response.next_cursor;
                                 state.error = None;
                             }
+                            // Keep the pages already loaded; "Load more" can be retried
+                            Err(err) if append => {
+                                log::error!(
+                                    "[SessionManagerPanel] Failed to load more sessions for agent {}: {}",
+                                    agent_name,
+                                    err
+                                );
+                            }
                             Err(err) => {
                                 state.sessions = Vec::new();
-                                state.error = Some(err.to_string());
+                                state.next_cursor = None;
+                                // Rendered as a notice rather than a failure
+                                state.error = err
+                                    .downcast_ref::<SessionListUnsupported>()
+                                    .is_none()
+                                    .then(|| err.to_string());
                             }
                         }
                         cx.notify();
@@ -161,7 +546,17 @@ impl SessionManagerPanel {
         .detach();
     }
 
-    fn import_agent_sessions(&mut self, agent_name: String, cx: &mut Context<Self>) {
+    /// Resume the agent's sessions into the workspace.
+    ///
+    /// Sessions already in the workspace or imported by an earlier run are
+    /// skipped. With `only_failed`, just the sessions whose last import
+    /// failed are attempted again.
+    fn import_agent_sessions(
+        &mut self,
+        agent_name: String,
+        only_failed: bool,
+        cx: &mut Context<Self>,
+    ) {
         let agent_service = match AppState::global(cx).agent_service() {
             Some(service) => service.clone(),
             None => {
@@ -169,6 +564,7 @@ impl SessionManagerPanel {
                 return;
             }
         };
+        let workspace_service = AppState::global(cx).workspace_service().cloned();
 
         let state = self
             .agent_sessions_by_agent
@@ -177,7 +573,10 @@ impl SessionManagerPanel {
         if state.is_importing {
             return;
         }
+        let cancel = Arc::new(AtomicBool::new(false));
         state.is_importing = true;
+        state.import_progress = None;
+        state.import_cancel = Some(cancel.clone());
         state.error = None;
         cx.notify();
 
@@ -195,41 +594,107 @@ impl SessionManagerPanel {
 
         let weak_self = cx.entity().downgrade();
         cx.spawn(async move |_entity, cx| {
-            let request = acp::ListSessionsRequest::new();
-            let list_result = agent_service.list_agent_sessions(&agent_name, request).await;
+            let previous_imports = match &workspace_service {
+                Some(service) => service.session_imports(&agent_name).await,
+                None => Default::default(),
+            };
+
+            let list_result = agent_service.list_all_agent_sessions(&agent_name).await;
             let mut sessions = Vec::new();
-            let mut failed_imports = 0usize;
+            let mut summary = ImportSummary::default();
+            let mut outcomes = Vec::new();
             let mut error = None;
             let mut list_ok = false;
 
             match list_result {
-                Ok(response) => {
-                    sessions = response.sessions;
+                Ok(all_sessions) => {
+                    sessions = all_sessions;
                     list_ok = true;
+                    let mut pending = Vec::new();
                     for session in sessions.iter() {
                         let session_id = session.session_id.to_string();
+                        let previous = previous_imports.get(&session_id);
+                        if only_failed
+                            && !matches!(previous, Some(SessionImportOutcome::Failed { .. }))
+                        {
+                            continue;
+                        }
                         if existing_session_ids.contains(&session_id) {
+                            summary.skipped += 1;
+                            if previous != Some(&SessionImportOutcome::Imported) {
+                                outcomes.push((session_id, SessionImportOutcome::Imported));
+                            }
                             continue;
                         }
-                        if let Err(err) = agent_service
-                            .resume_session(&agent_name, &session_id)
-                            .await
-                        {
-                            failed_imports += 1;
-                            log::error!(
-                                "[SessionManagerPanel] Failed to import session {} for agent {}: {}",
-                                session_id,
+                        if previous == Some(&SessionImportOutcome::Imported) {
+                            summary.skipped += 1;
+                            continue;
+                        }
+                        pending.push((session_id, session.cwd.clone()));
+                    }
+
+                    let total = pending.len();
+                    Self::update_import_progress(&weak_self, &agent_name, 0, total, cx);
+                    for (index, (session_id, cwd)) in pending.into_iter().enumerate() {
+                        if cancel.load(Ordering::SeqCst) {
+                            summary.cancelled = total - index;
+                            log::info!(
+                                "[SessionManagerPanel] Import for agent {} cancelled with {} sessions left",
                                 agent_name,
-                                err
+                                summary.cancelled
                             );
+                            break;
                         }
+                        // Keep the directory the agent recorded for the session
+                        match agent_service
+                            .resume_session_with_mcp_and_cwd(
+                                &agent_name,
+                                &session_id,
+                                Vec::new(),
+                                cwd,
+                            )
+                            .await
+                        {
+                            Ok(_) => {
+                                summary.imported += 1;
+                                outcomes.push((session_id, SessionImportOutcome::Imported));
+                            }
+                            Err(err) => {
+                                log::error!(
+                                    "[SessionManagerPanel] Failed to import session {} for agent {}: {}",
+                                    session_id,
+                                    agent_name,
+                                    err
+                                );
+                                let reason = err.to_string();
+                                summary.failed.push((session_id.clone(), reason.clone()));
+                                outcomes.push((session_id, SessionImportOutcome::Failed { reason }));
+                            }
+                        }
+                        Self::update_import_progress(&weak_self, &agent_name, index + 1, total, cx);
                     }
                 }
+                Err(err) if err.downcast_ref::<SessionListUnsupported>().is_some() => {}
                 Err(err) => {
                     error = Some(err.to_string());
                 }
             }
 
+            if let Some(service) = &workspace_service
+                && let Err(err) = service.record_session_imports(&agent_name, outcomes).await
+            {
+                log::error!(
+                    "[SessionManagerPanel] Failed to record session imports for agent {}: {}",
+                    agent_name,
+                    err
+                );
+            }
+
+            // Imported sessions pick up the titles the agent reported
+            agent_service
+                .apply_agent_session_titles(&agent_name, &sessions)
+                .await;
+
             _ = cx.update(|cx| {
                 if let Some(this) = weak_self.upgrade() {
                     this.update(cx, |this, cx| {
@@ -238,17 +703,16 @@ impl SessionManagerPanel {
                             .entry(agent_name.clone())
                             .or_default();
                         state.is_importing = false;
-                        if error.is_some() {
-                            state.error = error;
-                        } else if failed_imports > 0 {
-                            state.error =
-                                Some(format!("Imported with {} errors", failed_imports));
-                        } else {
-                            state.error = None;
-                        }
+                        state.import_progress = None;
+                        state.import_cancel = None;
+                        state.error = error;
                         if list_ok {
                             state.sessions = sessions;
+                            state.next_cursor = None;
+                            state.visible_count =
+                                state.visible_count.max(AGENT_SESSIONS_PAGE_SIZE);
                             state.has_loaded = true;
+                            state.import_summary = Some(summary);
                         }
                         this.refresh_sessions(cx);
                         cx.notify();
@@ -259,6 +723,55 @@ impl SessionManagerPanel {
         .detach();
     }
 
+    /// Show how many of the pending sessions an import run has processed
+    fn update_import_progress(
+        weak_self: &WeakEntity<Self>,
+        agent_name: &str,
+        done: usize,
+        total: usize,
+        cx: &mut AsyncApp,
+    ) {
+        _ = cx.update(|cx| {
+            if let Some(this) = weak_self.upgrade() {
+                this.update(cx, |this, cx| {
+                    if let Some(state) = this.agent_sessions_by_agent.get_mut(agent_name) {
+                        state.import_progress = Some((done, total));
+                        cx.notify();
+                    }
+                });
+            }
+        });
+    }
+
+    /// Stop an import run after the session currently being resumed
+    fn cancel_import(&mut self, agent_name: &str, cx: &mut Context<Self>) {
+        if let Some(cancel) = self
+            .agent_sessions_by_agent
+            .get(agent_name)
+            .and_then(|state| state.import_cancel.as_ref())
+        {
+            cancel.store(true, Ordering::SeqCst);
+            cx.notify();
+        }
+    }
+
+    /// Reveal more of the loaded agent sessions, fetching the next page once
+    /// every loaded session is shown
+    fn load_more_agent_sessions(&mut self, agent_name: String, cx: &mut Context<Self>) {
+        let Some(state) = self.agent_sessions_by_agent.get_mut(&agent_name) else {
+            return;
+        };
+        if state.is_loading_more {
+            return;
+        }
+        if state.visible_count < state.sessions.len() {
+            state.visible_count += AGENT_SESSIONS_PAGE_SIZE;
+            cx.notify();
+        } else if let Some(cursor) = state.next_cursor.clone() {
+            self.list_agent_sessions(agent_name, Some(cursor), cx);
+        }
+    }
+
     fn has_workspace_session(&self, agent_name: &str, session_id: &str) -> bool {
         self.sessions_by_agent
             .iter()
@@ -303,6 +816,8 @@ impl SessionManagerPanel {
                     );
                     Ok(loaded_session_id)
                 }
+                // A wedged agent will not answer session/resume either
+                Err(err) if err.downcast_ref::<RequestTimeout>().is_some() => Err(err),
                 Err(err) => {
                     log::warn!(
                         "[SessionManagerPanel] Failed to load session {} for agent {}: {}",
@@ -332,6 +847,12 @@ impl SessionManagerPanel {
                         agent_name,
                         e
                     );
+                    _ = window.update(|window, cx| {
+                        struct OpenSessionError;
+                        let note = Notification::error(format!("Failed to open session: {}", e))
+                            .id::<OpenSessionError>();
+                        window.push_notification(note, cx);
+                    });
                 }
             }
         })
@@ -388,12 +909,62 @@ impl SessionManagerPanel {
                 }
                 Err(e) => {
                     log::error!("[SessionManagerPanel] Failed to create session: {}", e);
+                    _ = window.update(|window, cx| {
+                        struct CreateSessionError;
+                        let note = Notification::error(format!("Failed to create session: {}", e))
+                            .id::<CreateSessionError>();
+                        window.push_notification(note, cx);
+                    });
                 }
             }
         })
         .detach();
     }
 
+    /// Restart a failed agent with its current configuration
+    ///
+    /// The agent's lifecycle events refresh the list; a failed restart is
+    /// reported in a notification and keeps the agent in its failed state.
+    fn reconnect_agent(&mut self, agent_name: String, window: &mut Window, cx: &mut Context<Self>) {
+        let agent_config_service = match AppState::global(cx).agent_config_service() {
+            Some(service) => service.clone(),
+            None => {
+                log::error!("[SessionManagerPanel] AgentConfigService not initialized");
+                return;
+            }
+        };
+        if !self.reconnecting_agents.insert(agent_name.clone()) {
+            return;
+        }
+        cx.notify();
+
+        let weak_self = cx.entity().downgrade();
+        cx.spawn_in(window, async move |_this, window| {
+            let result = agent_config_service.restart_agent(&agent_name).await;
+            _ = window.update(|window, cx| {
+                if let Err(e) = &result {
+                    log::error!(
+                        "[SessionManagerPanel] Failed to reconnect agent {}: {}",
+                        agent_name,
+                        e
+                    );
+                    struct ReconnectAgentError;
+                    let note =
+                        Notification::error(format!("Failed to reconnect {}: {}", agent_name, e))
+                            .id::<ReconnectAgentError>();
+                    window.push_notification(note, cx);
+                }
+                if let Some(entity) = weak_self.upgrade() {
+                    entity.update(cx, |this, cx| {
+                        this.reconnecting_agents.remove(&agent_name);
+                        this.refresh_sessions(cx);
+                    });
+                }
+            });
+        })
+        .detach();
+    }
+
     /// Close a session
     fn close_session(
         &mut self,
@@ -435,13 +1006,382 @@ impl SessionManagerPanel {
         .detach();
     }
 
+    /// Archive or unarchive a session (history is kept either way)
+    fn set_session_archived(
+        &mut self,
+        session_id: String,
+        archived: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace_service = match AppState::global(cx).workspace_service() {
+            Some(service) => service.clone(),
+            None => {
+                log::error!("[SessionManagerPanel] WorkspaceService not initialized");
+                return;
+            }
+        };
+
+        // Update local state immediately so the list reflects the change
+        if archived {
+            self.archived_session_ids.insert(session_id.clone());
+        } else {
+            self.archived_session_ids.remove(&session_id);
+        }
+        cx.notify();
+
+        cx.spawn_in(window, async move |_this, _window| {
+            if let Err(e) = workspace_service
+                .set_session_archived(&session_id, archived)
+                .await
+            {
+                log::error!(
+                    "[SessionManagerPanel] Failed to update archived state for {}: {}",
+                    session_id,
+                    e
+                );
+            }
+        })
+        .detach();
+    }
+
+    /// Pin or unpin a session so it is listed first
+    fn set_session_pinned(
+        &mut self,
+        session_id: String,
+        pinned: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace_service = match AppState::global(cx).workspace_service() {
+            Some(service) => service.clone(),
+            None => {
+                log::error!("[SessionManagerPanel] WorkspaceService not initialized");
+                return;
+            }
+        };
+
+        if pinned {
+            self.pinned_session_ids.insert(session_id.clone());
+        } else {
+            self.pinned_session_ids.remove(&session_id);
+        }
+        cx.notify();
+
+        cx.spawn_in(window, async move |_this, _window| {
+            if let Err(e) = workspace_service
+                .set_session_pinned(&session_id, pinned)
+                .await
+            {
+                log::error!(
+                    "[SessionManagerPanel] Failed to update pinned state for {}: {}",
+                    session_id,
+                    e
+                );
+            }
+        })
+        .detach();
+    }
+
+    /// Show an inline input for renaming a session
+    fn start_rename(
+        &mut self,
+        agent_name: String,
+        session_id: String,
+        current_title: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("Session title");
+            state.set_value(current_title.unwrap_or_default(), window, cx);
+            state
+        });
+        input.update(cx, |state, cx| state.focus(window, cx));
+
+        let subscription = cx.subscribe_in(
+            &input,
+            window,
+            |this, _input, event: &InputEvent, _window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.commit_rename(cx);
+                }
+            },
+        );
+
+        self.title_editor = Some(TitleEditor {
+            agent_name,
+            session_id,
+            input,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    /// Save the title being edited (empty reverts to the short id)
+    fn commit_rename(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = self.title_editor.take() else {
+            return;
+        };
+        let title = editor.input.read(cx).value().trim().to_string();
+
+        // Update local state immediately so the list reflects the change
+        if let Some(session) = self
+            .sessions_by_agent
+            .iter_mut()
+            .filter(|(name, _)| name == &editor.agent_name)
+            .flat_map(|(_, sessions)| sessions.iter_mut())
+            .find(|session| session.session_id == editor.session_id)
+        {
+            session.title = Some(title.clone()).filter(|title| !title.is_empty());
+        }
+        cx.notify();
+
+        let Some(agent_service) = AppState::global(cx).agent_service().cloned() else {
+            log::error!("[SessionManagerPanel] AgentService not initialized");
+            return;
+        };
+        cx.spawn(async move |_this, _cx| {
+            if let Err(e) = agent_service
+                .set_session_title(&editor.agent_name, &editor.session_id, &title)
+                .await
+            {
+                log::error!(
+                    "[SessionManagerPanel] Failed to rename session {}: {}",
+                    editor.session_id,
+                    e
+                );
+            }
+        })
+        .detach();
+    }
+
+    fn cancel_rename(&mut self, cx: &mut Context<Self>) {
+        self.title_editor = None;
+        cx.notify();
+    }
+
+    fn toggle_archived_section(&mut self, agent_name: String, cx: &mut Context<Self>) {
+        if !self.expanded_archived_agents.remove(&agent_name) {
+            self.expanded_archived_agents.insert(agent_name);
+        }
+        cx.notify();
+    }
+
+    fn is_archived(&self, session_id: &str) -> bool {
+        self.archived_session_ids.contains(session_id)
+    }
+
+    fn is_pinned(&self, session_id: &str) -> bool {
+        self.pinned_session_ids.contains(session_id)
+    }
+
+    /// Render a single workspace session row
+    fn render_session_row(
+        &self,
+        row_id: ElementId,
+        agent_name: &str,
+        session: &AgentSessionInfo,
+        archived: bool,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let theme = cx.theme();
+        let child_id =
+            |name: &'static str| ElementId::NamedChild(Arc::new(row_id.clone()), name.into());
+        let session_id = session.session_id.clone();
+        let agent_name_for_close = agent_name.to_string();
+        let session_id_for_close = session_id.clone();
+        let session_id_for_open = session_id.clone();
+        let session_id_for_archive = session_id.clone();
+        let session_id_for_pin = session_id.clone();
+        let pinned = self.is_pinned(&session_id);
+        let session_id_for_rename = session_id.clone();
+        let agent_name_for_rename = agent_name.to_string();
+        let current_title = session.title.clone();
+        let status_color = self.status_color(&session.status, cx);
+        let short_id = if session_id.len() > 12 {
+            &session_id[..12]
+        } else {
+            &session_id
+        };
+        let display_title = session
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Session {}", short_id));
+        let editing_input = self
+            .title_editor
+            .as_ref()
+            .filter(|editor| editor.session_id == session_id)
+            .map(|editor| editor.input.clone());
+
+        h_flex()
+            .w_full()
+            .items_center()
+            .justify_between()
+            .p_2()
+            .rounded(px(6.))
+            .bg(theme.background)
+            .border_1()
+            .border_color(theme.border.opacity(0.5))
+            .when(archived, |this| this.opacity(0.7))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        Button::new(child_id("pin"))
+                            .icon(if pinned {
+                                Icon::new(crate::assets::Icon::StarFill).text_color(theme.warning)
+                            } else {
+                                Icon::new(IconName::Star).text_color(theme.muted_foreground)
+                            })
+                            .ghost()
+                            .xsmall()
+                            .tooltip(if pinned {
+                                t!("session_manager.session.unpin").to_string()
+                            } else {
+                                t!("session_manager.session.pin").to_string()
+                            })
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.set_session_pinned(
+                                    session_id_for_pin.clone(),
+                                    !pinned,
+                                    window,
+                                    cx,
+                                );
+                            })),
+                    )
+                    .child(
+                        // Status indicator
+                        gpui::div()
+                            .w(px(8.))
+                            .h(px(8.))
+                            .rounded(px(4.))
+                            .bg(status_color),
+                    )
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(match editing_input.clone() {
+                                Some(input) => gpui::div()
+                                    .w(px(220.))
+                                    .child(Input::new(&input).xsmall())
+                                    .into_any_element(),
+                                None => gpui::div()
+                                    .text_xs()
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .text_color(theme.foreground)
+                                    .child(display_title)
+                                    .into_any_element(),
+                            })
+                            .child(
+                                gpui::div()
+                                    .text_xs()
+                                    .text_color(theme.muted_foreground)
+                                    .child(format!(
+                                        "{} | Last active: {}",
+                                        Self::status_text(&session.status),
+                                        session.last_active.format("%H:%M:%S")
+                                    )),
+                            ),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .map(|this| {
+                        if editing_input.is_some() {
+                            this.child(
+                                Button::new(child_id("rename-save"))
+                                    .label(t!("session_manager.rename.save").to_string())
+                                    .ghost()
+                                    .small()
+                                    .on_click(cx.listener(|this, _, _window, cx| {
+                                        this.commit_rename(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new(child_id("rename-cancel"))
+                                    .label(t!("session_manager.rename.cancel").to_string())
+                                    .ghost()
+                                    .small()
+                                    .on_click(cx.listener(|this, _, _window, cx| {
+                                        this.cancel_rename(cx);
+                                    })),
+                            )
+                        } else {
+                            this.child(
+                                Button::new(child_id("rename"))
+                                    .label(t!("session_manager.rename.button").to_string())
+                                    .ghost()
+                                    .small()
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.start_rename(
+                                            agent_name_for_rename.clone(),
+                                            session_id_for_rename.clone(),
+                                            current_title.clone(),
+                                            window,
+                                            cx,
+                                        );
+                                    })),
+                            )
+                        }
+                    })
+                    .child(
+                        Button::new(child_id("open"))
+                            .label("Open")
+                            .ghost()
+                            .small()
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.open_session(session_id_for_open.clone(), window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new(child_id("archive"))
+                            .label(if archived {
+                                t!("session_manager.session.unarchive").to_string()
+                            } else {
+                                t!("session_manager.session.archive").to_string()
+                            })
+                            .ghost()
+                            .small()
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.set_session_archived(
+                                    session_id_for_archive.clone(),
+                                    !archived,
+                                    window,
+                                    cx,
+                                );
+                            })),
+                    )
+                    .when(
+                        !archived && session.status != SessionStatus::Closed,
+                        |this| {
+                            this.child(
+                                Button::new(child_id("close"))
+                                    .label("Close")
+                                    .ghost()
+                                    .small()
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.close_session(
+                                            agent_name_for_close.clone(),
+                                            session_id_for_close.clone(),
+                                            window,
+                                            cx,
+                                        );
+                                    })),
+                            )
+                        },
+                    ),
+            )
+    }
+
     /// Open a conversation panel for the given session
     fn open_session(&self, session_id: String, window: &mut Window, cx: &mut Context<Self>) {
-        // Dispatch PanelAction to open the conversation panel
+        // Open beside the current conversation instead of replacing it
         window.dispatch_action(
-            Box::new(crate::PanelAction::add_conversation_for_session(
+            Box::new(crate::PanelAction::split_conversation_for_session(
                 session_id,
-                gpui_component::dock::DockPlacement::Center,
             )),
             cx,
         );
@@ -484,6 +1424,9 @@ impl Focusable for SessionManagerPanel {
 impl Render for SessionManagerPanel {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
+        let no_agents = self.agents_loaded && self.sessions_by_agent.is_empty();
+        let agent_service = AppState::global(cx).agent_service().cloned();
+        let success_color = theme.success_active;
 
         v_flex()
             .size_full()
@@ -509,15 +1452,35 @@ impl Render for SessionManagerPanel {
                             .child("Sessions"),
                     )
                     .child(
-                        Button::new("refresh")
-                            .icon(Icon::new(IconName::LoaderCircle))
-                            .ghost()
-                            .small()
-                            .on_click(cx.listener(|this, _, _window, cx| {
-                                this.refresh_sessions(cx);
-                            })),
+                        h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(
+                                gpui::div().w(px(280.)).child(
+                                    Input::new(&self.search_input)
+                                        .small()
+                                        .cleanable(true)
+                                        .prefix(
+                                            Icon::new(IconName::Search)
+                                                .size_4()
+                                                .text_color(theme.muted_foreground),
+                                        ),
+                                ),
+                            )
+                            .child(
+                                Button::new("refresh")
+                                    .icon(Icon::new(IconName::LoaderCircle))
+                                    .ghost()
+                                    .small()
+                                    .on_click(cx.listener(|this, _, _window, cx| {
+                                        this.refresh_sessions(cx);
+                                    })),
+                            ),
                     ),
             )
+            .when_some(self.search.as_ref(), |this, search| {
+                this.child(self.render_search_results(search, cx))
+            })
             .child(
                 // Scrollable session list
                 gpui::div()
@@ -535,6 +1498,9 @@ impl Render for SessionManagerPanel {
                                 v_flex()
                                     .w_full()
                                     .gap_3()
+                                    .when(no_agents, |this| {
+                                        this.child(NoAgentsState::new("session-manager-no-agents"))
+                                    })
                                     .children(self.sessions_by_agent.iter().enumerate().map(|(agent_idx, (agent_name, sessions))| {
                                         let agent_name_clone = agent_name.clone();
                                         let agent_list_state = self.agent_sessions_by_agent.get(agent_name).cloned();
@@ -543,10 +1509,53 @@ impl Render for SessionManagerPanel {
                                             .get(agent_name)
                                             .map(|state| state.is_importing)
                                             .unwrap_or(false);
+                                        let import_progress = agent_list_state
+                                            .as_ref()
+                                            .and_then(|state| state.import_progress);
+                                        let import_cancelling = agent_list_state
+                                            .as_ref()
+                                            .and_then(|state| state.import_cancel.as_ref())
+                                            .map(|cancel| cancel.load(Ordering::SeqCst))
+                                            .unwrap_or(false);
+                                        let import_label = match (agent_is_importing, import_progress) {
+                                            (true, Some((done, total))) => t!("session_manager.import.progress", done = done, total = total).to_string(),
+                                            (true, None) => t!("session_manager.import.running").to_string(),
+                                            (false, _) => t!("session_manager.import.button").to_string(),
+                                        };
+                                        let import_summary = agent_list_state
+                                            .as_ref()
+                                            .and_then(|state| state.import_summary.clone());
+                                        let failed_import_count = import_summary
+                                            .as_ref()
+                                            .map(|summary| summary.failed.len())
+                                            .unwrap_or(0);
                                         let workspace_session_ids: HashSet<String> = sessions
                                             .iter()
                                             .map(|session| session.session_id.clone())
                                             .collect();
+                                        let (mut archived_sessions, mut active_sessions): (Vec<_>, Vec<_>) = sessions
+                                            .iter()
+                                            .partition(|session| self.is_archived(&session.session_id));
+                                        // Pinned sessions float to the top of each list
+                                        active_sessions.sort_by_key(|session| !self.is_pinned(&session.session_id));
+                                        archived_sessions.sort_by_key(|session| !self.is_pinned(&session.session_id));
+                                        let archived_expanded = self.expanded_archived_agents.contains(agent_name);
+                                        let lifecycle = agent_service
+                                            .as_ref()
+                                            .and_then(|service| service.agent_state(agent_name));
+                                        // Sessions can only be started or listed once the agent is ready
+                                        let agent_ready = lifecycle == Some(AgentLifecycle::Ready);
+                                        let agent_failed = matches!(lifecycle, Some(AgentLifecycle::Failed(_)));
+                                        let reconnecting = self.reconnecting_agents.contains(agent_name);
+                                        let (state_label, state_color) = match &lifecycle {
+                                            Some(AgentLifecycle::Ready) => ("Ready", success_color),
+                                            Some(AgentLifecycle::Failed(_)) => ("Failed to start", theme.danger),
+                                            _ => ("Starting...", theme.muted_foreground),
+                                        };
+                                        // From the agent's capabilities or its first list attempt
+                                        let list_unsupported = agent_service
+                                            .as_ref()
+                                            .is_some_and(|service| !service.supports_session_list(agent_name));
 
                                         v_flex()
                                             .w_full()
@@ -563,21 +1572,58 @@ impl Render for SessionManagerPanel {
                                                     .items_center()
                                                     .justify_between()
                                                     .child(
-                                                        gpui::div()
-                                                            .text_sm()
-                                                            .font_weight(gpui::FontWeight::SEMIBOLD)
-                                                            .text_color(theme.foreground)
-                                                            .child(format!("{} ({} sessions)", agent_name, sessions.len())),
+                                                        h_flex()
+                                                            .gap_2()
+                                                            .items_center()
+                                                            .child(
+                                                                Icon::new(crate::assets::agent_icon(agent_name, cx))
+                                                                    .small()
+                                                                    .text_color(theme.foreground),
+                                                            )
+                                                            .child(
+                                                                gpui::div()
+                                                                    .text_sm()
+                                                                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                                                                    .text_color(theme.foreground)
+                                                                    .child(format!("{} ({} sessions)", agent_name, active_sessions.len())),
+                                                            )
+                                                            .child(
+                                                                gpui::div()
+                                                                    .px_1()
+                                                                    .rounded(px(4.))
+                                                                    .border_1()
+                                                                    .border_color(state_color.opacity(0.5))
+                                                                    .text_xs()
+                                                                    .text_color(state_color)
+                                                                    .child(state_label),
+                                                            ),
                                                     )
                                                     .child(
                                                         h_flex()
                                                             .gap_1()
+                                                            .when(agent_failed || reconnecting, |this| {
+                                                                this.child(
+                                                                    Button::new(("reconnect-agent", agent_idx))
+                                                                        .label(if reconnecting { t!("session_manager.agent.reconnecting").to_string() } else { t!("session_manager.agent.reconnect").to_string() })
+                                                                        .icon(Icon::new(IconName::Redo))
+                                                                        .ghost()
+                                                                        .small()
+                                                                        .disabled(reconnecting)
+                                                                        .on_click({
+                                                                            let agent_name = agent_name_clone.clone();
+                                                                            cx.listener(move |this, _, window, cx| {
+                                                                                this.reconnect_agent(agent_name.clone(), window, cx);
+                                                                            })
+                                                                        }),
+                                                                )
+                                                            })
                                                             .child(
                                                                 Button::new(("new-session", agent_idx))
                                                                     .label("New")
                                                                     .icon(Icon::new(IconName::Plus))
                                                                     .ghost()
                                                                     .small()
+                                                                    .disabled(!agent_ready)
                                                                     .on_click({
                                                                         let agent_name = agent_name_clone.clone();
                                                                         cx.listener(move |this, _, window, cx| {
@@ -585,34 +1631,88 @@ impl Render for SessionManagerPanel {
                                                                         })
                                                                     }),
                                                             )
-                                                            .child(
-                                                                Button::new(("list-agent-sessions", agent_idx))
-                                                                    .label("List")
-                                                                    .icon(Icon::new(IconName::Search))
-                                                                    .ghost()
-                                                                    .small()
-                                                                    .on_click({
-                                                                        let agent_name = agent_name_clone.clone();
-                                                                        cx.listener(move |this, _, _window, cx| {
-                                                                            this.list_agent_sessions(agent_name.clone(), cx);
-                                                                        })
-                                                                    }),
-                                                            )
-                                                            .child(
-                                                                Button::new(("import-agent-sessions", agent_idx))
-                                                                    .label(if agent_is_importing { "Importing..." } else { "Import All" })
-                                                                    .icon(Icon::new(IconName::ArrowDown))
-                                                                    .ghost()
-                                                                    .small()
-                                                                    .on_click({
-                                                                        let agent_name = agent_name_clone.clone();
-                                                                        cx.listener(move |this, _, _window, cx| {
-                                                                            this.import_agent_sessions(agent_name.clone(), cx);
-                                                                        })
-                                                                    }),
-                                                            ),
+                                                            .when(!list_unsupported, |this| {
+                                                                this.child(
+                                                                    Button::new(("list-agent-sessions", agent_idx))
+                                                                        .label("List")
+                                                                        .icon(Icon::new(IconName::Search))
+                                                                        .ghost()
+                                                                        .small()
+                                                                        .disabled(!agent_ready)
+                                                                        .on_click({
+                                                                            let agent_name = agent_name_clone.clone();
+                                                                            cx.listener(move |this, _, _window, cx| {
+                                                                                this.list_agent_sessions(agent_name.clone(), None, cx);
+                                                                            })
+                                                                        }),
+                                                                )
+                                                                .child(
+                                                                    Button::new(("import-agent-sessions", agent_idx))
+                                                                        .label(import_label)
+                                                                        .icon(Icon::new(IconName::ArrowDown))
+                                                                        .ghost()
+                                                                        .small()
+                                                                        .disabled(!agent_ready)
+                                                                        .on_click({
+                                                                            let agent_name = agent_name_clone.clone();
+                                                                            cx.listener(move |this, _, _window, cx| {
+                                                                                this.import_agent_sessions(agent_name.clone(), false, cx);
+                                                                            })
+                                                                        }),
+                                                                )
+                                                            })
+                                                            .when(agent_is_importing, |this| {
+                                                                this.child(
+                                                                    Button::new(("cancel-import", agent_idx))
+                                                                        .label(if import_cancelling { t!("session_manager.import.cancelling").to_string() } else { t!("session_manager.import.cancel").to_string() })
+                                                                        .icon(Icon::new(IconName::Close))
+                                                                        .ghost()
+                                                                        .small()
+                                                                        .disabled(import_cancelling)
+                                                                        .on_click({
+                                                                            let agent_name = agent_name_clone.clone();
+                                                                            cx.listener(move |this, _, _window, cx| {
+                                                                                this.cancel_import(&agent_name, cx);
+                                                                            })
+                                                                        }),
+                                                                )
+                                                            })
+                                                            .when(failed_import_count > 0 && !agent_is_importing && !list_unsupported, |this| {
+                                                                this.child(
+                                                                    Button::new(("retry-failed-imports", agent_idx))
+                                                                        .label(t!("session_manager.import.retry_failed", count = failed_import_count).to_string())
+                                                                        .icon(Icon::new(IconName::Redo))
+                                                                        .ghost()
+                                                                        .small()
+                                                                        .on_click({
+                                                                            let agent_name = agent_name_clone.clone();
+                                                                            cx.listener(move |this, _, _window, cx| {
+                                                                                this.import_agent_sessions(agent_name.clone(), true, cx);
+                                                                            })
+                                                                        }),
+                                                                )
+                                                            }),
                                                     ),
                                             )
+                                            .when_some(import_summary, |this, summary| {
+                                                this.child(
+                                                    v_flex()
+                                                        .w_full()
+                                                        .gap_1()
+                                                        .child(
+                                                            gpui::div()
+                                                                .text_xs()
+                                                                .text_color(theme.muted_foreground)
+                                                                .child(summary.describe()),
+                                                        )
+                                                        .children(summary.failed.iter().map(|(session_id, reason)| {
+                                                            gpui::div()
+                                                                .text_xs()
+                                                                .text_color(theme.danger)
+                                                                .child(format!("{}: {}", session_id, reason))
+                                                        })),
+                                                )
+                                            })
                                             .child(
                                                 gpui::div()
                                                     .text_xs()
@@ -625,92 +1725,41 @@ impl Render for SessionManagerPanel {
                                                 v_flex()
                                                     .w_full()
                                                     .gap_2()
-                                                    .children(sessions.iter().enumerate().map(|(session_idx, session)| {
-                                                        let session_id = session.session_id.clone();
-                                                        let agent_name_for_close = agent_name_clone.clone();
-                                                        let session_id_for_close = session_id.clone();
-                                                        let session_id_for_open = session_id.clone();
-                                                        let status_color = self.status_color(&session.status, cx);
-                                                        let short_id = if session_id.len() > 12 {
-                                                            &session_id[..12]
-                                                        } else {
-                                                            &session_id
-                                                        };
-                                                        // Create unique button ID from indices
-                                                        let btn_id = agent_idx * 1000 + session_idx;
-
-                                                        h_flex()
-                                                            .w_full()
-                                                            .items_center()
-                                                            .justify_between()
-                                                            .p_2()
-                                                            .rounded(px(6.))
-                                                            .bg(theme.background)
-                                                            .border_1()
-                                                            .border_color(theme.border.opacity(0.5))
-                                                            .child(
-                                                                h_flex()
-                                                                    .gap_2()
-                                                                    .items_center()
-                                                                    .child(
-                                                                        // Status indicator
-                                                                        gpui::div()
-                                                                            .w(px(8.))
-                                                                            .h(px(8.))
-                                                                            .rounded(px(4.))
-                                                                            .bg(status_color),
-                                                                    )
-                                                                    .child(
-                                                                        v_flex()
-                                                                            .gap_1()
-                                                                            .child(
-                                                                                gpui::div()
-                                                                                    .text_xs()
-                                                                                    .font_weight(gpui::FontWeight::MEDIUM)
-                                                                                    .text_color(theme.foreground)
-                                                                                    .child(format!("Session {}", short_id)),
-                                                                            )
-                                                                            .child(
-                                                                                gpui::div()
-                                                                                    .text_xs()
-                                                                                    .text_color(theme.muted_foreground)
-                                                                                    .child(format!("{} | Last active: {}",
-                                                                                        Self::status_text(&session.status),
-                                                                                        session.last_active.format("%H:%M:%S")
-                                                                                    )),
-                                                                            ),
-                                                                    ),
-                                                            )
-                                                            .child(
-                                                                h_flex()
-                                                                    .gap_1()
-                                                                    .child(
-                                                                        Button::new(("open", btn_id))
-                                                                            .label("Open")
-                                                                            .ghost()
-                                                                            .small()
-                                                                            .on_click(cx.listener(move |this, _, window, cx| {
-                                                                                this.open_session(session_id_for_open.clone(), window, cx);
-                                                                            })),
-                                                                    )
-                                                                    .when(session.status != SessionStatus::Closed, |this| {
-                                                                        this.child(
-                                                                            Button::new(("close", btn_id))
-                                                                                .label("Close")
-                                                                                .ghost()
-                                                                                .small()
-                                                                                .on_click({
-                                                                                    let agent_name = agent_name_for_close.clone();
-                                                                                    let session_id = session_id_for_close.clone();
-                                                                                    cx.listener(move |this, _, window, cx| {
-                                                                                        this.close_session(agent_name.clone(), session_id.clone(), window, cx);
-                                                                                    })
-                                                                                }),
-                                                                        )
-                                                                    }),
-                                                            )
+                                                    .children(active_sessions.iter().enumerate().map(|(session_idx, session)| {
+                                                        let row_id = session_row_id("workspace-session", agent_idx, session_idx);
+                                                        self.render_session_row(row_id, agent_name, session, false, cx)
                                                     })),
                                             )
+                                            .when(!archived_sessions.is_empty(), |this| {
+                                                this.child(
+                                                    Button::new(("toggle-archived", agent_idx))
+                                                        .label(format!("Archived ({})", archived_sessions.len()))
+                                                        .icon(Icon::new(if archived_expanded {
+                                                            IconName::ChevronDown
+                                                        } else {
+                                                            IconName::ChevronRight
+                                                        }))
+                                                        .ghost()
+                                                        .xsmall()
+                                                        .on_click({
+                                                            let agent_name = agent_name_clone.clone();
+                                                            cx.listener(move |this, _, _window, cx| {
+                                                                this.toggle_archived_section(agent_name.clone(), cx);
+                                                            })
+                                                        }),
+                                                )
+                                                .when(archived_expanded, |this| {
+                                                    this.child(
+                                                        v_flex()
+                                                            .w_full()
+                                                            .gap_2()
+                                                            .children(archived_sessions.iter().enumerate().map(|(session_idx, session)| {
+                                                                let row_id = session_row_id("archived-session", agent_idx, session_idx);
+                                                                self.render_session_row(row_id, agent_name, session, true, cx)
+                                                            })),
+                                                    )
+                                                })
+                                            })
                                             .child(
                                                 gpui::div()
                                                     .text_xs()
@@ -720,7 +1769,12 @@ impl Render for SessionManagerPanel {
                                             )
                                             .child({
                                                 let state = agent_list_state.unwrap_or_default();
-                                                if !state.has_loaded && !state.is_loading {
+                                                if list_unsupported {
+                                                    gpui::div()
+                                                        .text_xs()
+                                                        .text_color(theme.muted_foreground)
+                                                        .child(t!("session_manager.agent_sessions.list_unsupported").to_string())
+                                                } else if !state.has_loaded && !state.is_loading {
                                                     gpui::div()
                                                         .text_xs()
                                                         .text_color(theme.muted_foreground)
@@ -744,7 +1798,7 @@ impl Render for SessionManagerPanel {
                                                     v_flex()
                                                         .w_full()
                                                         .gap_2()
-                                                        .children(state.sessions.iter().enumerate().map(|(session_idx, session)| {
+                                                        .children(state.sessions.iter().take(state.visible_count).enumerate().map(|(session_idx, session)| {
                                                             let session_id = session.session_id.to_string();
                                                             let short_id = if session_id.len() > 12 {
                                                                 &session_id[..12]
@@ -765,7 +1819,7 @@ impl Render for SessionManagerPanel {
                                                             };
                                                             let agent_name_for_open = agent_name_clone.clone();
                                                             let session_id_for_open = session_id.clone();
-                                                            let btn_id = agent_idx * 1000 + session_idx + 500;
+                                                            let row_id = session_row_id("agent-session", agent_idx, session_idx);
 
                                                             h_flex()
                                                                 .w_full()
@@ -794,7 +1848,7 @@ impl Render for SessionManagerPanel {
                                                                         ),
                                                                 )
                                                                 .child(
-                                                                    Button::new(("agent-open", btn_id))
+                                                                    Button::new(ElementId::NamedChild(Arc::new(row_id), "open".into()))
                                                                         .label("Open")
                                                                         .ghost()
                                                                         .small()
@@ -808,6 +1862,32 @@ impl Render for SessionManagerPanel {
                                                                         })),
                                                                 )
                                                         }))
+                                                        .when(
+                                                            state.visible_count < state.sessions.len() || state.next_cursor.is_some(),
+                                                            |this| {
+                                                                let hidden = state.sessions.len().saturating_sub(state.visible_count);
+                                                                this.child(
+                                                                    Button::new(("load-more-agent-sessions", agent_idx))
+                                                                        .label(if state.is_loading_more {
+                                                                            t!("session_manager.agent_sessions.loading_more").to_string()
+                                                                        } else if hidden > 0 {
+                                                                            t!("session_manager.agent_sessions.load_more_hidden", count = hidden).to_string()
+                                                                        } else {
+                                                                            t!("session_manager.agent_sessions.load_more").to_string()
+                                                                        })
+                                                                        .icon(Icon::new(IconName::ChevronDown))
+                                                                        .ghost()
+                                                                        .xsmall()
+                                                                        .disabled(state.is_loading_more)
+                                                                        .on_click({
+                                                                            let agent_name = agent_name_clone.clone();
+                                                                            cx.listener(move |this, _, _window, cx| {
+                                                                                this.load_more_agent_sessions(agent_name.clone(), cx);
+                                                                            })
+                                                                        }),
+                                                                )
+                                                            },
+                                                        )
                                                 }
                                             })
                                     })),
@@ -1,25 +1,247 @@
-use gpui::{AppContext as _, Context, Entity, ParentElement as _, Styled, Window, px};
+use gpui::{
+    App, AppContext as _, ClipboardItem, Context, Entity, InteractiveElement as _, IntoElement,
+    ParentElement as _, Render, StatefulInteractiveElement as _, Styled, Subscription, Task,
+    Window, div, prelude::FluentBuilder as _, px,
+};
 use gpui_component::{
-    ActiveTheme, IconName, Sizable, WindowExt as _,
-    button::Button,
+    ActiveTheme, Disableable, Icon, IconName, Selectable as _, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants as _},
     dialog::DialogButtonProps,
     h_flex,
-    input::{Input, InputState},
+    input::{Input, InputEvent, InputState},
     label::Label,
+    notification::Notification,
     setting::{SettingField, SettingGroup, SettingItem, SettingPage},
     v_flex,
 };
 use rust_i18n::t;
-use std::collections::HashMap;
+use std::{collections::HashSet, path::PathBuf, rc::Rc, sync::Arc, time::Duration};
 
+use super::dialog_keys::{
+    FieldList, SubmitHandler, focus_first, focus_trap, submit_on_enter, submit_on_event,
+};
+use super::env_editor::{EnvVarEditor, EnvVarSubmit};
 use super::panel::SettingsPanel;
 use crate::{
-    AppState,
+    AppSettings, AppState,
     app::actions::{
-        AddAgent, ChangeConfigPath, ReloadAgentConfig, RemoveAgent, RestartAgent, UpdateAgent,
+        AddAgent, ChangeConfigPath, ReloadAgentConfig, RemoveAgent, RestartAgent,
+        RestoreConfigBackup, UpdateAgent,
+    },
+    app::agent_startup_errors::AgentStartupErrors,
+    assets::{AGENT_ICON_KEYS, resolve_agent_icon},
+    core::agent::AgentLifecycle,
+    core::agent_templates::agent_templates,
+    core::config::{AgentProcessConfig, compare_agents},
+    core::services::{
+        AgentConfigField, AgentConfigIssue, AgentConfigService, AgentConflictKind, UploadDirStatus,
+        detect_agent_conflicts,
     },
+    utils::diagnostics::redact_trace_message,
 };
 
+/// Validation messages for the dialog fields selected by `matches`
+fn render_field_issues(
+    issues: &[AgentConfigIssue],
+    matches: impl Fn(&AgentConfigField) -> bool,
+    cx: &App,
+) -> Vec<Label> {
+    issues
+        .iter()
+        .filter(|issue| matches(&issue.field))
+        .map(|issue| {
+            Label::new(issue.message.clone())
+                .text_xs()
+                .text_color(cx.theme().danger)
+        })
+        .collect()
+}
+
+/// Outline a dialog field in the danger color while it has a validation problem
+fn highlight_invalid(invalid: bool, field: impl IntoElement, cx: &App) -> impl IntoElement {
+    div()
+        .w_full()
+        .when(invalid, |this| {
+            this.rounded(px(6.))
+                .border_1()
+                .border_color(cx.theme().danger)
+        })
+        .child(field)
+}
+
+/// Delay after the last keystroke before the command field is checked
+const COMMAND_CHECK_DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// How often an expanded agent log section is refreshed
+const AGENT_LOGS_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Outcome of verifying an agent command, with the PATH it was resolved against
+struct CommandCheck {
+    resolved: Result<String, String>,
+    path: String,
+    path_from_env: bool,
+    /// List the PATH entries; only for an explicit "Verify", not the live check
+    show_path: bool,
+}
+
+impl CommandCheck {
+    fn run(command: &str, path_override: Option<String>, show_path: bool) -> Self {
+        let resolved = if command.is_empty() {
+            Err(t!("settings.agents.verify.empty_command").to_string())
+        } else {
+            AgentConfigService::resolve_command(command, path_override.as_deref())
+                .map(|path| path.display().to_string())
+                .map_err(|err| err.to_string())
+        };
+        let path_from_env = path_override.is_some();
+        let path = path_override
+            .or_else(|| std::env::var("PATH").ok())
+            .unwrap_or_default();
+
+        Self {
+            resolved,
+            path,
+            path_from_env,
+            show_path,
+        }
+    }
+
+    fn render(&self, cx: &App) -> impl IntoElement {
+        let path_entries = std::env::split_paths(&self.path)
+            .map(|entry| entry.display().to_string())
+            .collect::<Vec<_>>();
+        let path_label = if self.path_from_env {
+            t!("settings.agents.verify.path_from_env")
+        } else {
+            t!("settings.agents.verify.path_label")
+        };
+
+        v_flex()
+            .gap_1()
+            .p_2()
+            .rounded(px(6.))
+            .bg(cx.theme().muted)
+            .child(match &self.resolved {
+                Ok(path) => h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::CircleCheck)
+                            .xsmall()
+                            .text_color(cx.theme().success),
+                    )
+                    .child(
+                        Label::new(t!("settings.agents.verify.resolved", path = path).to_string())
+                            .text_xs(),
+                    ),
+                Err(error) => h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::CircleX)
+                            .xsmall()
+                            .text_color(cx.theme().danger),
+                    )
+                    .child(
+                        Label::new(error.clone())
+                            .text_xs()
+                            .text_color(cx.theme().danger),
+                    ),
+            })
+            .when(self.show_path, |this| {
+                this.child(
+                    Label::new(path_label.to_string())
+                        .text_xs()
+                        .font_weight(gpui::FontWeight::SEMIBOLD)
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .children(path_entries.into_iter().map(|entry| {
+                    Label::new(entry)
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                }))
+            })
+    }
+}
+
+/// The dialog's command as it would be spawned, relative paths resolved against the config dir
+fn dialog_command(command_input: &Entity<InputState>, cx: &App) -> String {
+    let command = command_input.read(cx).text().to_string();
+    match AppState::global(cx).agent_config_service() {
+        Some(service) => service.resolve_config_command(&command),
+        None => command,
+    }
+}
+
+/// Check the command shortly after the user stops typing, resolving it off the UI thread
+fn check_command_as_typed(
+    command_input: &Entity<InputState>,
+    env_editor: Entity<EnvVarEditor>,
+    command_check: Entity<Option<CommandCheck>>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Subscription {
+    // Replacing the task cancels a check still waiting out the debounce
+    let mut pending: Option<Task<()>> = None;
+    window.subscribe(
+        command_input,
+        cx,
+        move |command_input, event: &InputEvent, _window, cx| {
+            if !matches!(event, InputEvent::Change) {
+                return;
+            }
+            let env_editor = env_editor.clone();
+            let command_check = command_check.clone();
+            pending = Some(cx.spawn(async move |cx| {
+                smol::Timer::after(COMMAND_CHECK_DEBOUNCE).await;
+
+                let Ok((command, path_override)) = cx.update(|cx| {
+                    (
+                        dialog_command(&command_input, cx),
+                        env_editor.read(cx).value("PATH", cx),
+                    )
+                }) else {
+                    return;
+                };
+                let check = if command.trim().is_empty() {
+                    None
+                } else {
+                    Some(
+                        smol::unblock(move || {
+                            CommandCheck::run(command.trim(), path_override, false)
+                        })
+                        .await,
+                    )
+                };
+                _ = cx.update(|cx| {
+                    command_check.update(cx, |state, cx| {
+                        *state = check;
+                        cx.notify();
+                    })
+                });
+            }));
+        },
+    )
+}
+
+/// Drag payload for reordering agents in the configured list
+#[derive(Clone)]
+struct DraggedAgent {
+    name: String,
+}
+
+impl Render for DraggedAgent {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_3()
+            .py_1()
+            .rounded(px(6.))
+            .bg(cx.theme().secondary)
+            .border_1()
+            .border_color(cx.theme().border)
+            .text_sm()
+            .child(self.name.clone())
+    }
+}
+
 impl SettingsPanel {
     pub fn agent_page(&self, view: &Entity<Self>) -> SettingPage {
         SettingPage::new(t!("settings.agents.title").to_string())
@@ -92,6 +314,24 @@ impl SettingsPanel {
                                                             );
                                                         })
                                                 )
+                                                .child(
+                                                    Button::new("restore-config")
+                                                        .label(
+                                                            t!("settings.agents.config.backup.restore")
+                                                                .to_string(),
+                                                        )
+                                                        .icon(IconName::Undo2)
+                                                        .outline()
+                                                        .small()
+                                                        .on_click({
+                                                            let view = view.clone();
+                                                            move |_, window, cx| {
+                                                                view.update(cx, |this, cx| {
+                                                                    this.show_restore_backup_dialog(window, cx);
+                                                                });
+                                                            }
+                                                        })
+                                                )
                                         )
                                 }
                             }),
@@ -104,23 +344,72 @@ impl SettingsPanel {
                             SettingField::render({
                                 let view = view.clone();
                                 move |_options, _window, cx| {
-                                    let upload_dir = view.read(cx).cached_upload_dir.to_string_lossy().to_string();
-                                    let display = if upload_dir.is_empty() {
-                                        t!("settings.agents.upload_dir.not_configured").to_string()
-                                    } else {
-                                        upload_dir
-                                    };
-
-                                    gpui::div()
+                                    let status = view.read(cx).cached_upload_dir.clone();
+                                    let display = status
+                                        .as_ref()
+                                        .map(|status| status.path.to_string_lossy().to_string())
+                                        .filter(|path| !path.is_empty())
+                                        .unwrap_or_else(|| {
+                                            t!("settings.agents.upload_dir.not_configured").to_string()
+                                        });
+
+                                    v_flex()
                                         .w_full()
                                         .min_w(px(0.))
+                                        .gap_1()
                                         .overflow_x_hidden()
                                         .child(
-                                            Label::new(display)
-                                                .text_sm()
-                                                .text_color(cx.theme().muted_foreground)
-                                                .whitespace_nowrap()
+                                            h_flex()
+                                                .w_full()
+                                                .gap_2()
+                                                .justify_between()
+                                                .child(
+                                                    Label::new(display)
+                                                        .text_sm()
+                                                        .text_color(cx.theme().muted_foreground)
+                                                        .whitespace_nowrap()
+                                                )
+                                                .child(
+                                                    Button::new("browse-upload-dir")
+                                                        .label(
+                                                            t!("settings.agents.upload_dir.browse")
+                                                                .to_string(),
+                                                        )
+                                                        .icon(IconName::FolderOpen)
+                                                        .outline()
+                                                        .small()
+                                                        .on_click({
+                                                            let view = view.clone();
+                                                            move |_, window, cx| {
+                                                                view.update(cx, |this, cx| {
+                                                                    this.show_upload_dir_picker(window, cx);
+                                                                });
+                                                            }
+                                                        })
+                                                )
                                         )
+                                        .when_some(status, |this, status| {
+                                            let (icon, color, text) = match &status.error {
+                                                None => (
+                                                    IconName::CircleCheck,
+                                                    cx.theme().success,
+                                                    t!("settings.agents.upload_dir.writable").to_string(),
+                                                ),
+                                                Some(error) => (
+                                                    IconName::TriangleAlert,
+                                                    cx.theme().danger,
+                                                    t!("settings.agents.upload_dir.not_writable", error = error).to_string(),
+                                                ),
+                                            };
+                                            this.child(
+                                                h_flex()
+                                                    .gap_1()
+                                                    .text_xs()
+                                                    .text_color(color)
+                                                    .child(Icon::new(icon).xsmall())
+                                                    .child(text),
+                                            )
+                                        })
                                 }
                             }),
                         )
@@ -133,16 +422,43 @@ impl SettingsPanel {
                     .item(SettingItem::render({
                         let view = view.clone();
                         move |_options, _window, cx| {
-                            let agent_configs = view.read(cx).cached_agents.clone();
+                            let mut agent_configs: Vec<_> =
+                                view.read(cx).cached_agents.clone().into_iter().collect();
+                            agent_configs.sort_by(|a, b| {
+                                compare_agents((&a.0, Some(&a.1)), (&b.0, Some(&b.1)))
+                            });
+                            let agent_order: Vec<String> =
+                                agent_configs.iter().map(|(name, _)| name.clone()).collect();
+                            let has_custom_order =
+                                agent_configs.iter().any(|(_, config)| config.order.is_some());
 
                             let mut content = v_flex()
                                 .w_full()
                                 .gap_3()
                                 .child(
-                                    // Add New Agent button
+                                    // Sort and Add New Agent buttons
                                     h_flex()
                                         .w_full()
                                         .justify_end()
+                                        .gap_2()
+                                        .child(
+                                            Button::new("sort-agents-btn")
+                                                .label(
+                                                    t!("settings.agents.button.sort_alphabetically")
+                                                        .to_string(),
+                                                )
+                                                .ghost()
+                                                .small()
+                                                .disabled(!has_custom_order)
+                                                .on_click({
+                                                    let view = view.clone();
+                                                    move |_, _, cx| {
+                                                        view.update(cx, |this, cx| {
+                                                            this.reset_agent_order(cx);
+                                                        });
+                                                    }
+                                                })
+                                        )
                                         .child(
                                             Button::new("add-agent-btn")
                                                 .label(
@@ -161,6 +477,9 @@ impl SettingsPanel {
                                         )
                                 );
 
+                            content = content
+                                .children(view.read(cx).render_agent_conflicts(&view, cx));
+
                             if agent_configs.is_empty() {
                                 content = content.child(
                                     h_flex()
@@ -176,16 +495,62 @@ impl SettingsPanel {
                             } else {
                                 for (idx, (name, config)) in agent_configs.iter().enumerate() {
                                     let name_for_edit = name.clone();
+                                    let name_for_duplicate = name.clone();
                                     let name_for_restart = name.clone();
                                     let name_for_remove = name.clone();
+                                    let name_for_mute = name.clone();
+                                    let name_for_pin = name.clone();
+                                    let pinned = config.pinned;
+                                    let muted = AppSettings::global(cx).muted_agents.contains(name);
 
                                     let mut agent_info = v_flex()
                                         .flex_1()
                                         .gap_1()
                                         .child(
-                                            Label::new(name.clone())
-                                                .text_sm()
-                                                .font_weight(gpui::FontWeight::SEMIBOLD)
+                                            h_flex()
+                                                .gap_2()
+                                                .items_center()
+                                                .child(
+                                                    Icon::new(resolve_agent_icon(name, config.icon.as_deref()))
+                                                        .small()
+                                                )
+                                                .child(
+                                                    Label::new(name.clone())
+                                                        .text_sm()
+                                                        .font_weight(gpui::FontWeight::SEMIBOLD)
+                                                )
+                                                .child(
+                                                    Button::new(("pin-btn", idx))
+                                                        .icon(if pinned {
+                                                            Icon::new(crate::assets::Icon::StarFill)
+                                                                .text_color(cx.theme().warning)
+                                                        } else {
+                                                            Icon::new(IconName::Star)
+                                                                .text_color(cx.theme().muted_foreground)
+                                                        })
+                                                        .ghost()
+                                                        .xsmall()
+                                                        .tooltip(
+                                                            if pinned {
+                                                                t!("settings.agents.button.unpin")
+                                                            } else {
+                                                                t!("settings.agents.button.pin")
+                                                            }
+                                                            .to_string(),
+                                                        )
+                                                        .on_click({
+                                                            let view = view.clone();
+                                                            move |_, _, cx| {
+                                                                view.update(cx, |this, cx| {
+                                                                    this.set_agent_pinned(
+                                                                        name_for_pin.clone(),
+                                                                        !pinned,
+                                                                        cx,
+                                                                    );
+                                                                });
+                                                            }
+                                                        })
+                                                )
                                         )
                                         .child(
                                             Label::new(
@@ -227,87 +592,250 @@ impl SettingsPanel {
                                         );
                                     }
 
+                                    let startup_error = AgentStartupErrors::error(name, cx);
+                                    if let Some(error) = startup_error.clone() {
+                                        agent_info = agent_info.child(
+                                            v_flex()
+                                                .gap_1()
+                                                .child(
+                                                    h_flex()
+                                                        .gap_1()
+                                                        .items_center()
+                                                        .child(
+                                                            Icon::new(IconName::TriangleAlert)
+                                                                .xsmall()
+                                                                .text_color(cx.theme().danger)
+                                                        )
+                                                        .child(
+                                                            Label::new(
+                                                                t!("settings.agents.status.failed")
+                                                                    .to_string(),
+                                                            )
+                                                                .text_xs()
+                                                                .font_weight(gpui::FontWeight::SEMIBOLD)
+                                                                .text_color(cx.theme().danger)
+                                                        )
+                                                        .child(
+                                                            Button::new(("copy-error-btn", idx))
+                                                                .label(
+                                                                    t!("settings.agents.button.copy_error")
+                                                                        .to_string(),
+                                                                )
+                                                                .icon(IconName::Copy)
+                                                                .ghost()
+                                                                .xsmall()
+                                                                .on_click({
+                                                                    let error = error.clone();
+                                                                    move |_, _, cx| {
+                                                                        cx.write_to_clipboard(
+                                                                            ClipboardItem::new_string(error.clone()),
+                                                                        );
+                                                                    }
+                                                                })
+                                                        )
+                                                )
+                                                .child(
+                                                    Label::new(error)
+                                                        .text_xs()
+                                                        .text_color(cx.theme().danger)
+                                                )
+                                        );
+                                    } else if AppState::global(cx)
+                                        .agent_service()
+                                        .and_then(|service| service.agent_state(name))
+                                        == Some(AgentLifecycle::Initializing)
+                                    {
+                                        agent_info = agent_info.child(
+                                            Label::new(t!("settings.agents.status.starting").to_string())
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground)
+                                        );
+                                    }
+
+                                    let logs_expanded = view.read(cx).expanded_agent_logs.contains(name);
+                                    let logs_section =
+                                        Self::render_agent_logs_section(idx, name, logs_expanded, &view, cx);
+
                                     content = content.child(
-                                        h_flex()
+                                        v_flex()
+                                            .id(("agent-card", idx))
                                             .w_full()
-                                            .items_start()
-                                            .justify_between()
                                             .p_3()
-                                            .gap_3()
+                                            .gap_2()
                                             .rounded(px(6.))
                                             .bg(cx.theme().secondary)
                                             .border_1()
-                                            .border_color(cx.theme().border)
-                                            .child(agent_info)
+                                            .border_color(if startup_error.is_some() {
+                                                cx.theme().danger
+                                            } else {
+                                                cx.theme().border
+                                            })
+                                            .drag_over::<DraggedAgent>(|style, _, _, cx| {
+                                                style.border_color(cx.theme().primary)
+                                            })
+                                            .on_drop({
+                                                let view = view.clone();
+                                                let target = name.clone();
+                                                let agent_order = agent_order.clone();
+                                                move |dragged: &DraggedAgent, _, cx| {
+                                                    view.update(cx, |this, cx| {
+                                                        this.move_agent(
+                                                            &dragged.name,
+                                                            &target,
+                                                            agent_order.clone(),
+                                                            cx,
+                                                        );
+                                                    });
+                                                }
+                                            })
                                             .child(
-                                                // Action buttons column
                                                 h_flex()
-                                                    .gap_2()
-                                                    .items_center()
+                                                    .w_full()
+                                                    .items_start()
+                                                    .justify_between()
+                                                    .gap_3()
                                                     .child(
-                                                        Button::new(("edit-btn", idx))
-                                                            .label(
-                                                                t!("settings.agents.button.edit")
-                                                                    .to_string(),
+                                                        // Drag handle
+                                                        div()
+                                                            .id(("agent-drag-handle", idx))
+                                                            .mt(px(2.))
+                                                            .cursor_grab()
+                                                            .on_drag(
+                                                                DraggedAgent { name: name.clone() },
+                                                                |dragged, _, _, cx| {
+                                                                    cx.new(|_| dragged.clone())
+                                                                },
                                                             )
-                                                            .icon(IconName::Settings)
-                                                            .outline()
-                                                            .small()
-                                                            .on_click({
-                                                                let view = view.clone();
-                                                                move |_, window, cx| {
-                                                                    view.update(cx, |this, cx| {
-                                                                        this.show_add_edit_agent_dialog(
-                                                                            window,
-                                                                            cx,
-                                                                            Some(name_for_edit.clone())
-                                                                        );
-                                                                    });
-                                                                }
-                                                            })
-                                                    )
-                                                    .child(
-                                                        Button::new(("restart-btn", idx))
-                                                            .label(
-                                                                t!("settings.agents.button.restart")
-                                                                    .to_string(),
+                                                            .child(
+                                                                Icon::new(IconName::Menu)
+                                                                    .small()
+                                                                    .text_color(cx.theme().muted_foreground)
                                                             )
-                                                            .icon(IconName::LoaderCircle)
-                                                            .outline()
-                                                            .small()
-                                                            .on_click(move |_, window, cx| {
-                                                                log::info!("Restart agent: {}", name_for_restart);
-                                                                window.dispatch_action(
-                                                                    Box::new(RestartAgent {
-                                                                        name: name_for_restart.clone(),
-                                                                    }),
-                                                                    cx
-                                                                );
-                                                            })
                                                     )
-                                                    .child(
-                                                        Button::new(("remove-btn", idx))
-                                                            .label(
-                                                                t!("settings.agents.button.remove")
+                                                    .child(agent_info)
+                                                .child(
+                                                    // Action buttons column
+                                                    h_flex()
+                                                        .gap_2()
+                                                        .items_center()
+                                                        .child(
+                                                            Button::new(("edit-btn", idx))
+                                                                .label(
+                                                                    t!("settings.agents.button.edit")
+                                                                        .to_string(),
+                                                                )
+                                                                .icon(IconName::Settings)
+                                                                .outline()
+                                                                .small()
+                                                                .on_click({
+                                                                    let view = view.clone();
+                                                                    move |_, window, cx| {
+                                                                        view.update(cx, |this, cx| {
+                                                                            this.show_add_edit_agent_dialog(
+                                                                                window,
+                                                                                cx,
+                                                                                Some(name_for_edit.clone())
+                                                                            );
+                                                                        });
+                                                                    }
+                                                                })
+                                                        )
+                                                        .child(
+                                                            Button::new(("duplicate-btn", idx))
+                                                                .label(
+                                                                    t!("settings.agents.button.duplicate")
+                                                                        .to_string(),
+                                                                )
+                                                                .icon(IconName::Copy)
+                                                                .outline()
+                                                                .small()
+                                                                .on_click({
+                                                                    let view = view.clone();
+                                                                    move |_, window, cx| {
+                                                                        view.update(cx, |this, cx| {
+                                                                            this.show_duplicate_agent_dialog(
+                                                                                window,
+                                                                                cx,
+                                                                                name_for_duplicate.clone()
+                                                                            );
+                                                                        });
+                                                                    }
+                                                                })
+                                                        )
+                                                        .child(
+                                                            Button::new(("mute-btn", idx))
+                                                                .label(
+                                                                    if muted {
+                                                                        t!("settings.agents.button.unmute")
+                                                                    } else {
+                                                                        t!("settings.agents.button.mute")
+                                                                    }
                                                                     .to_string(),
-                                                            )
-                                                            .icon(IconName::Delete)
-                                                            .outline()
-                                                            .small()
-                                                            .on_click({
-                                                                let view = view.clone();
-                                                                move |_, window, cx| {
-                                                                    view.update(cx, |this, cx| {
-                                                                        this.show_delete_confirm_dialog(
-                                                                            window,
-                                                                            cx,
-                                                                            name_for_remove.clone()
-                                                                        );
-                                                                    });
-                                                                }
-                                                            })
-                                                    )
+                                                                )
+                                                                .icon(IconName::Bell)
+                                                                .outline()
+                                                                .small()
+                                                                .on_click({
+                                                                    let view = view.clone();
+                                                                    move |_, _window, cx| {
+                                                                        let muted_agents =
+                                                                            &mut AppSettings::global_mut(cx).muted_agents;
+                                                                        if let Some(pos) = muted_agents
+                                                                            .iter()
+                                                                            .position(|n| n == &name_for_mute)
+                                                                        {
+                                                                            muted_agents.remove(pos);
+                                                                        } else {
+                                                                            muted_agents.push(name_for_mute.clone());
+                                                                        }
+                                                                        view.update(cx, |_, cx| cx.notify());
+                                                                    }
+                                                                })
+                                                        )
+                                                        .child(
+                                                            Button::new(("restart-btn", idx))
+                                                                .label(
+                                                                    t!("settings.agents.button.restart")
+                                                                        .to_string(),
+                                                                )
+                                                                .icon(IconName::LoaderCircle)
+                                                                .outline()
+                                                                .small()
+                                                                .on_click(move |_, window, cx| {
+                                                                    log::info!("Restart agent: {}", name_for_restart);
+                                                                    window.dispatch_action(
+                                                                        Box::new(RestartAgent {
+                                                                            name: name_for_restart.clone(),
+                                                                        }),
+                                                                        cx
+                                                                    );
+                                                                })
+                                                        )
+                                                        .child(
+                                                            Button::new(("remove-btn", idx))
+                                                                .label(
+                                                                    t!("settings.agents.button.remove")
+                                                                        .to_string(),
+                                                                )
+                                                                .icon(IconName::Delete)
+                                                                .outline()
+                                                                .small()
+                                                                .on_click({
+                                                                    let view = view.clone();
+                                                                    move |_, window, cx| {
+                                                                        view.update(cx, |this, cx| {
+                                                                            this.show_delete_confirm_dialog(
+                                                                                window,
+                                                                                cx,
+                                                                                name_for_remove.clone()
+                                                                            );
+                                                                        });
+                                                                    }
+                                                                })
+                                                        )
+                                                )
                                             )
+                                            .child(logs_section)
                                     );
                                 }
                             }
@@ -318,6 +846,314 @@ impl SettingsPanel {
             ])
     }
 
+    /// Collapsible section showing an agent's recent stderr output
+    fn render_agent_logs_section(
+        idx: usize,
+        name: &str,
+        expanded: bool,
+        view: &Entity<Self>,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        let toggle = Button::new(("logs-btn", idx))
+            .label(t!("settings.agents.logs.title").to_string())
+            .icon(if expanded {
+                IconName::ChevronDown
+            } else {
+                IconName::ChevronRight
+            })
+            .ghost()
+            .xsmall()
+            .on_click({
+                let view = view.clone();
+                let name = name.to_string();
+                move |_, _, cx| {
+                    view.update(cx, |this, cx| this.toggle_agent_logs(name.clone(), cx));
+                }
+            });
+
+        let header = h_flex()
+            .gap_1()
+            .child(toggle)
+            .child(Self::render_trace_controls(idx, name, cx));
+
+        if !expanded {
+            return v_flex().child(header);
+        }
+
+        let logs = AppState::global(cx)
+            .agent_manager()
+            .map(|manager| manager.agent_logs(name))
+            .unwrap_or_default();
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex().justify_between().child(header).child(
+                    Button::new(("copy-logs-btn", idx))
+                        .label(t!("settings.agents.logs.copy").to_string())
+                        .icon(IconName::Copy)
+                        .ghost()
+                        .xsmall()
+                        .disabled(logs.is_empty())
+                        .on_click({
+                            let logs = logs.clone();
+                            move |_, _, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(logs.clone()));
+                            }
+                        }),
+                ),
+            )
+            .child(
+                div()
+                    .id(("agent-logs", idx))
+                    .w_full()
+                    .max_h(px(240.))
+                    .overflow_y_scroll()
+                    .p_2()
+                    .rounded(px(4.))
+                    .bg(cx.theme().background)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .font_family("Monaco, 'Courier New', monospace")
+                    .text_xs()
+                    .child(if logs.is_empty() {
+                        t!("settings.agents.logs.empty").to_string()
+                    } else {
+                        logs
+                    }),
+            )
+    }
+
+    /// "Record trace" toggle, plus a link to the trace file while recording
+    fn render_trace_controls(idx: usize, name: &str, cx: &App) -> impl IntoElement {
+        let trace_path = AppState::global(cx)
+            .agent_manager()
+            .and_then(|manager| manager.trace_path(name));
+        let recording = trace_path.is_some();
+
+        h_flex()
+            .gap_1()
+            .child(
+                Button::new(("trace-btn", idx))
+                    .label(if recording {
+                        t!("settings.agents.trace.stop").to_string()
+                    } else {
+                        t!("settings.agents.trace.start").to_string()
+                    })
+                    .icon(if recording {
+                        IconName::CircleX
+                    } else {
+                        IconName::Eye
+                    })
+                    .ghost()
+                    .xsmall()
+                    .tooltip(t!("settings.agents.trace.tooltip").to_string())
+                    .on_click({
+                        let name = name.to_string();
+                        move |_, window, cx| {
+                            Self::toggle_agent_trace(&name, cx);
+                            window.refresh();
+                        }
+                    }),
+            )
+            .when_some(trace_path, |this, path| {
+                this.child(
+                    Button::new(("trace-file-btn", idx))
+                        .label(t!("settings.agents.trace.show_file").to_string())
+                        .icon(IconName::FolderOpen)
+                        .ghost()
+                        .xsmall()
+                        .tooltip(path.display().to_string())
+                        .on_click(move |_, _, cx| cx.reveal_path(&path)),
+                )
+            })
+    }
+
+    /// Start or stop recording an agent's ACP traffic
+    fn toggle_agent_trace(name: &str, cx: &mut App) {
+        let Some(manager) = AppState::global(cx).agent_manager().cloned() else {
+            return;
+        };
+        if manager.stop_trace(name).is_some() {
+            return;
+        }
+
+        let slug: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let file_name = format!(
+            "{}-{}.log",
+            slug,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let path = crate::core::config_manager::get_agent_traces_dir().join(file_name);
+        if let Err(e) = manager.start_trace(name, path, Arc::new(redact_trace_message)) {
+            log::error!("Failed to start ACP trace for '{}': {:#}", name, e);
+        }
+    }
+
+    /// Expand or collapse an agent's log section
+    ///
+    /// While any section is open the page re-renders periodically so new
+    /// stderr lines show up as they arrive.
+    fn toggle_agent_logs(&mut self, name: String, cx: &mut Context<Self>) {
+        if !self.expanded_agent_logs.remove(&name) {
+            self.expanded_agent_logs.insert(name);
+        }
+
+        if self.expanded_agent_logs.is_empty() {
+            self.agent_logs_refresh = None;
+        } else if self.agent_logs_refresh.is_none() {
+            self.agent_logs_refresh = Some(cx.spawn(async move |this, cx| {
+                loop {
+                    smol::Timer::after(AGENT_LOGS_REFRESH_INTERVAL).await;
+                    if this.update(cx, |_, cx| cx.notify()).is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+        cx.notify();
+    }
+
+    /// Move a dragged agent to the position of the agent it was dropped on and persist the order
+    fn move_agent(
+        &mut self,
+        dragged: &str,
+        target: &str,
+        mut order: Vec<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(from), Some(to)) = (
+            order.iter().position(|name| name == dragged),
+            order.iter().position(|name| name == target),
+        ) else {
+            return;
+        };
+        if from == to {
+            return;
+        }
+        let name = order.remove(from);
+        order.insert(to, name);
+
+        for (index, name) in order.iter().enumerate() {
+            if let Some(config) = self.cached_agents.get_mut(name) {
+                config.order = Some(index);
+            }
+        }
+        cx.notify();
+
+        if let Some(service) = AppState::global(cx).agent_config_service() {
+            let service = service.clone();
+            cx.spawn(async move |_, _| {
+                if let Err(err) = service.reorder_agents(&order).await {
+                    log::error!("Failed to reorder agents: {}", err);
+                }
+            })
+            .detach();
+        }
+    }
+
+    /// Pin or unpin an agent so it is listed first
+    fn set_agent_pinned(&mut self, name: String, pinned: bool, cx: &mut Context<Self>) {
+        if let Some(config) = self.cached_agents.get_mut(&name) {
+            config.pinned = pinned;
+        }
+        cx.notify();
+
+        if let Some(service) = AppState::global(cx).agent_config_service() {
+            let service = service.clone();
+            cx.spawn(async move |_, _| {
+                if let Err(err) = service.set_agent_pinned(&name, pinned).await {
+                    log::error!("Failed to update pinned state for {}: {}", name, err);
+                }
+            })
+            .detach();
+        }
+    }
+
+    /// Drop the custom agent order and go back to alphabetical sorting
+    fn reset_agent_order(&mut self, cx: &mut Context<Self>) {
+        for config in self.cached_agents.values_mut() {
+            config.order = None;
+        }
+        cx.notify();
+
+        if let Some(service) = AppState::global(cx).agent_config_service() {
+            let service = service.clone();
+            cx.spawn(async move |_, _| {
+                if let Err(err) = service.reset_agent_order().await {
+                    log::error!("Failed to reset agent order: {}", err);
+                }
+            })
+            .detach();
+        }
+    }
+
+    /// Warnings for agents that look like they would get in each other's way,
+    /// minus the ones dismissed in this session
+    fn render_agent_conflicts(&self, view: &Entity<Self>, cx: &App) -> Vec<gpui::AnyElement> {
+        detect_agent_conflicts(&self.cached_agents)
+            .into_iter()
+            .filter(|conflict| !self.dismissed_agent_conflicts.contains(&conflict.key()))
+            .enumerate()
+            .map(|(idx, conflict)| {
+                let agents = conflict.agents.join(", ");
+                let message = match conflict.kind {
+                    AgentConflictKind::Duplicate => {
+                        t!("settings.agents.conflict.duplicate", agents = agents)
+                    }
+                    AgentConflictKind::Port(port) => {
+                        t!(
+                            "settings.agents.conflict.port",
+                            agents = agents,
+                            port = port
+                        )
+                    }
+                };
+                let key = conflict.key();
+                h_flex()
+                    .w_full()
+                    .px_3()
+                    .py_2()
+                    .gap_2()
+                    .items_center()
+                    .rounded(px(6.))
+                    .bg(cx.theme().warning.opacity(0.12))
+                    .border_1()
+                    .border_color(cx.theme().warning)
+                    .child(
+                        Icon::new(IconName::TriangleAlert)
+                            .small()
+                            .text_color(cx.theme().warning),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w_0()
+                            .child(Label::new(message.to_string()).text_xs()),
+                    )
+                    .child(
+                        Button::new(("dismiss-agent-conflict", idx))
+                            .label(t!("settings.agents.conflict.dismiss").to_string())
+                            .ghost()
+                            .xsmall()
+                            .on_click({
+                                let view = view.clone();
+                                move |_, _, cx| {
+                                    view.update(cx, |this, cx| {
+                                        this.dismissed_agent_conflicts.insert(key.clone());
+                                        cx.notify();
+                                    });
+                                }
+                            }),
+                    )
+                    .into_any_element()
+            })
+            .collect()
+    }
+
     /// Show dialog to add or edit an agent
     pub fn show_add_edit_agent_dialog(
         &mut self,
@@ -325,17 +1161,43 @@ impl SettingsPanel {
         cx: &mut Context<Self>,
         agent_name: Option<String>,
     ) {
+        // Get existing config if editing
+        let existing_config = agent_name
+            .as_ref()
+            .and_then(|name| self.cached_agents.get(name).cloned());
         let is_edit = agent_name.is_some();
+        self.open_agent_dialog(window, cx, is_edit, agent_name, existing_config);
+    }
+
+    /// Show the add dialog pre-filled with a copy of an existing agent under a free name
+    pub fn show_duplicate_agent_dialog(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        source_name: String,
+    ) {
+        let Some(source_config) = self.cached_agents.get(&source_name).cloned() else {
+            log::warn!("Cannot duplicate unknown agent: {}", source_name);
+            return;
+        };
+        let name = unique_copy_name(&source_name, |name| self.cached_agents.contains_key(name));
+        self.open_agent_dialog(window, cx, false, Some(name), Some(source_config));
+    }
+
+    fn open_agent_dialog(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        is_edit: bool,
+        agent_name: Option<String>,
+        existing_config: Option<AgentProcessConfig>,
+    ) {
         let title = if is_edit {
             t!("settings.agents.dialog.edit.title").to_string()
         } else {
             t!("settings.agents.dialog.add.title").to_string()
         };
-
-        // Get existing config if editing
-        let existing_config = agent_name
-            .as_ref()
-            .and_then(|name| self.cached_agents.get(name).cloned());
+        let existing_names: HashSet<String> = self.cached_agents.keys().cloned().collect();
 
         // Create input states
         let name_input = cx.new(|cx| {
@@ -365,22 +1227,294 @@ impl SettingsPanel {
             state
         });
 
-        let env_input = cx.new(|cx| {
+        let working_dir_input = cx.new(|cx| {
             let mut state = InputState::new(window, cx)
-                .placeholder(t!("settings.agents.input.env.placeholder").to_string());
-            if let Some(config) = &existing_config {
-                let env_text = config
-                    .env
-                    .iter()
-                    .map(|(k, v)| format!("{}={}", k, v))
-                    .collect::<Vec<_>>()
-                    .join("\n");
-                state.set_value(env_text, window, cx);
+                .placeholder(t!("settings.agents.input.working_dir.placeholder").to_string());
+            if let Some(dir) = existing_config
+                .as_ref()
+                .and_then(|config| config.working_dir_override.as_ref())
+            {
+                state.set_value(dir.display().to_string(), window, cx);
             }
             state
         });
 
+        let env_editor = cx.new(|cx| {
+            let env = existing_config
+                .as_ref()
+                .map(|config| config.env.clone())
+                .unwrap_or_default();
+            EnvVarEditor::new(&env, window, cx)
+        });
+
+        // Result of the last "Verify" click for the command
+        let command_check = cx.new(|_| None::<CommandCheck>);
+
+        // Command and argument problems found when confirming
+        let field_issues = cx.new(|_| Vec::<AgentConfigIssue>::new());
+
+        // Problem with the name found when confirming
+        let name_error = cx.new(|_| None::<String>);
+
+        // Selected icon key, None follows the agent name
+        let icon_state = cx.new(|_| {
+            existing_config
+                .as_ref()
+                .and_then(|config| config.icon.clone())
+        });
+
+        // Presets for known agents, offered when adding
+        let templates = if is_edit {
+            Vec::new()
+        } else {
+            agent_templates()
+        };
+
+        // Shared by the OK button and Enter in the form fields
+        let submit: SubmitHandler = Rc::new({
+            let name_input = name_input.clone();
+            let command_input = command_input.clone();
+            let args_input = args_input.clone();
+            let working_dir_input = working_dir_input.clone();
+            let env_editor = env_editor.clone();
+            let icon_state = icon_state.clone();
+            let field_issues = field_issues.clone();
+            let name_error = name_error.clone();
+
+            move |window: &mut Window, cx: &mut App| {
+                let name = name_input.read(cx).text().to_string();
+                let name = name.trim();
+                let command = command_input.read(cx).text().to_string();
+                let command = command.trim();
+                let args_text = args_input.read(cx).text().to_string();
+                let working_dir = working_dir_input.read(cx).text().to_string();
+                let working_dir_override =
+                    (!working_dir.trim().is_empty()).then(|| PathBuf::from(working_dir.trim()));
+                let icon = icon_state.read(cx).clone();
+
+                // Check every field before bailing out so all problems show at once
+                let name_problem = if name.is_empty() {
+                    Some(t!("settings.agents.dialog.error.name_required").to_string())
+                } else if !is_edit && existing_names.contains(name) {
+                    Some(t!("settings.agents.dialog.error.name_exists", name = name).to_string())
+                } else {
+                    None
+                };
+                name_error.update(cx, |error, cx| {
+                    *error = name_problem.clone();
+                    cx.notify();
+                });
+
+                // Parse args and collect env (the editor shows its own errors)
+                let args: Vec<String> = args_text.split_whitespace().map(String::from).collect();
+                let env = env_editor.update(cx, |editor, cx| editor.validate(cx));
+
+                // Catch what would make the agent fail to spawn, including a
+                // command that can't be found in PATH, and point at the
+                // offending fields
+                let mut issues = Vec::new();
+                let mut env_errors = Vec::new();
+                if command.is_empty() {
+                    issues.push(AgentConfigIssue {
+                        field: AgentConfigField::Command,
+                        message: t!("settings.agents.dialog.error.command_required").to_string(),
+                    });
+                } else {
+                    let config = AgentProcessConfig {
+                        command: command.to_string(),
+                        args: args.clone(),
+                        env: env.clone().unwrap_or_default(),
+                        icon: None,
+                        order: None,
+                        pinned: false,
+                        request_timeout_secs: None,
+                        startup_timeout_secs: None,
+                        working_dir_override: working_dir_override.clone(),
+                        modes: Vec::new(),
+                        nodejs_path: None,
+                    };
+                    let config = match AppState::global(cx).agent_config_service() {
+                        Some(service) => service.spawn_config(&config),
+                        None => config,
+                    };
+                    if let Err(err) = AgentConfigService::validate_process_config(&config) {
+                        for issue in err.issues {
+                            match &issue.field {
+                                AgentConfigField::EnvKey(key) => {
+                                    env_errors.push((key.clone(), issue.message))
+                                }
+                                _ => issues.push(issue),
+                            }
+                        }
+                    }
+                }
+                let has_env_errors = !env_errors.is_empty();
+                if has_env_errors {
+                    env_editor.update(cx, |editor, cx| editor.show_key_errors(env_errors, cx));
+                }
+                let has_issues = !issues.is_empty();
+                field_issues.update(cx, |field_issues, cx| {
+                    *field_issues = issues;
+                    cx.notify();
+                });
+
+                let Some(env) = env else {
+                    return false;
+                };
+                if name_problem.is_some() || has_issues || has_env_errors {
+                    return false;
+                }
+
+                // Dispatch appropriate action
+                if is_edit {
+                    window.dispatch_action(
+                        Box::new(UpdateAgent {
+                            name: name.to_string(),
+                            command: command.to_string(),
+                            args,
+                            env,
+                            icon,
+                            working_dir_override,
+                        }),
+                        cx,
+                    );
+                } else {
+                    window.dispatch_action(
+                        Box::new(AddAgent {
+                            name: name.to_string(),
+                            command: command.to_string(),
+                            args,
+                            env,
+                            icon,
+                            working_dir_override,
+                        }),
+                        cx,
+                    );
+                }
+
+                true // Close dialog
+            }
+        });
+
+        // Enter submits from the single-line fields, Tab cycles through all of them
+        let first_input = if is_edit { &command_input } else { &name_input };
+        let mut subscriptions = submit_on_enter(
+            &[
+                name_input.clone(),
+                command_input.clone(),
+                args_input.clone(),
+                working_dir_input.clone(),
+            ],
+            &submit,
+            window,
+            cx,
+        );
+        subscriptions.push(check_command_as_typed(
+            &command_input,
+            env_editor.clone(),
+            command_check.clone(),
+            window,
+            cx,
+        ));
+        subscriptions.push(submit_on_event::<_, EnvVarSubmit>(
+            &env_editor,
+            &submit,
+            window,
+            cx,
+        ));
+        let fields: FieldList = Rc::new({
+            let name_input = name_input.clone();
+            let command_input = command_input.clone();
+            let args_input = args_input.clone();
+            let working_dir_input = working_dir_input.clone();
+            let env_editor = env_editor.clone();
+            move |cx| {
+                let mut fields = Vec::new();
+                if !is_edit {
+                    fields.push(name_input.clone());
+                }
+                fields.push(command_input.clone());
+                fields.push(args_input.clone());
+                fields.extend(env_editor.read(cx).inputs());
+                fields.push(working_dir_input.clone());
+                fields
+            }
+        });
+        focus_first(first_input, window, cx);
+
         window.open_dialog(cx, move |dialog, _window, cx| {
+            // Dropped with the dialog
+            let _ = &subscriptions;
+            let selected_icon = icon_state.read(cx).clone();
+            let issues = field_issues.read(cx).clone();
+            let has_issue =
+                |matches: fn(&AgentConfigField) -> bool| issues.iter().any(|i| matches(&i.field));
+            let template_buttons = templates.iter().enumerate().map(|(idx, template)| {
+                let template = template.clone();
+                let name_input = name_input.clone();
+                let command_input = command_input.clone();
+                let args_input = args_input.clone();
+                let env_editor = env_editor.clone();
+                let icon_state = icon_state.clone();
+                Button::new(("agent-template", idx))
+                    .label(template.name.clone())
+                    .icon(Icon::new(resolve_agent_icon(
+                        &template.name,
+                        template.icon.as_deref(),
+                    )))
+                    .outline()
+                    .small()
+                    .tooltip(template.description.clone())
+                    .on_click(move |_, window, cx| {
+                        // Fill the form; everything stays editable before saving
+                        name_input.update(cx, |state, cx| {
+                            state.set_value(template.name.clone(), window, cx)
+                        });
+                        command_input.update(cx, |state, cx| {
+                            state.set_value(template.command.clone(), window, cx)
+                        });
+                        args_input.update(cx, |state, cx| {
+                            state.set_value(template.args.join(" "), window, cx)
+                        });
+                        env_editor
+                            .update(cx, |editor, cx| editor.set_vars(&template.env, window, cx));
+                        icon_state.update(cx, |icon, cx| {
+                            *icon = template.icon.clone();
+                            cx.notify();
+                        });
+                    })
+            });
+            let template_section = (!templates.is_empty()).then(|| {
+                v_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(t!("settings.agents.field.template_label").to_string())
+                            .text_sm()
+                            .font_weight(gpui::FontWeight::SEMIBOLD),
+                    )
+                    .child(h_flex().gap_1().flex_wrap().children(template_buttons))
+                    .child(
+                        Label::new(t!("settings.agents.field.template_help").to_string())
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground),
+                    )
+            });
+            let icon_buttons = AGENT_ICON_KEYS.iter().enumerate().map(|(idx, key)| {
+                let icon_state = icon_state.clone();
+                Button::new(("agent-icon", idx))
+                    .icon(Icon::new(resolve_agent_icon("", Some(key))))
+                    .ghost()
+                    .small()
+                    .tooltip(key.to_string())
+                    .selected(selected_icon.as_deref() == Some(*key))
+                    .on_click(move |_, _, cx| {
+                        icon_state.update(cx, |icon, cx| {
+                            *icon = Some(key.to_string());
+                            cx.notify();
+                        });
+                    })
+            });
+
             dialog
                 .title(title.clone())
                 .confirm()
@@ -394,71 +1528,16 @@ impl SettingsPanel {
                         .cancel_text(t!("settings.agents.dialog.cancel").to_string()),
                 )
                 .on_ok({
-                    let name_input = name_input.clone();
-                    let command_input = command_input.clone();
-                    let args_input = args_input.clone();
-                    let env_input = env_input.clone();
-                    let _agent_name = agent_name.clone();
-
-                    move |_, window, cx| {
-                        let name = name_input.read(cx).text().to_string();
-                        let name = name.trim();
-                        let command = command_input.read(cx).text().to_string();
-                        let command = command.trim();
-                        let args_text = args_input.read(cx).text().to_string();
-                        let env_text = env_input.read(cx).text().to_string();
-
-                        // Validate inputs
-                        if name.is_empty() || command.is_empty() {
-                            log::warn!("Agent name and command cannot be empty");
-                            return false;
-                        }
-
-                        // Parse args and env
-                        let args: Vec<String> =
-                            args_text.split_whitespace().map(String::from).collect();
-
-                        let mut env = HashMap::new();
-                        for line in env_text.lines() {
-                            if let Some((key, value)) = line.trim().split_once('=') {
-                                env.insert(key.trim().to_string(), value.trim().to_string());
-                            } else if !line.trim().is_empty() {
-                                log::warn!("Invalid env format (should be KEY=VALUE): {}", line);
-                                return false;
-                            }
-                        }
-
-                        // Dispatch appropriate action
-                        if is_edit {
-                            window.dispatch_action(
-                                Box::new(UpdateAgent {
-                                    name: name.to_string(),
-                                    command: command.to_string(),
-                                    args,
-                                    env,
-                                }),
-                                cx,
-                            );
-                        } else {
-                            window.dispatch_action(
-                                Box::new(AddAgent {
-                                    name: name.to_string(),
-                                    command: command.to_string(),
-                                    args,
-                                    env,
-                                }),
-                                cx,
-                            );
-                        }
-
-                        true // Close dialog
-                    }
+                    let submit = submit.clone();
+                    move |_, window, cx| submit(window, cx)
                 })
-                .child(
+                .child(focus_trap(
+                    fields.clone(),
                     v_flex()
                         .w_full()
                         .gap_4()
                         .p_4()
+                        .children(template_section)
                         .child(
                             v_flex()
                                 .gap_2()
@@ -467,9 +1546,14 @@ impl SettingsPanel {
                                         .text_sm()
                                         .font_weight(gpui::FontWeight::SEMIBOLD),
                                 )
-                                .child(
+                                .child(highlight_invalid(
+                                    name_error.read(cx).is_some(),
                                     Input::new(&name_input).disabled(is_edit), // Can't change name when editing
-                                ),
+                                    cx,
+                                ))
+                                .children(name_error.read(cx).clone().map(|error| {
+                                    Label::new(error).text_xs().text_color(cx.theme().danger)
+                                })),
                         )
                         .child(
                             v_flex()
@@ -481,14 +1565,61 @@ impl SettingsPanel {
                                     .text_sm()
                                     .font_weight(gpui::FontWeight::SEMIBOLD),
                                 )
-                                .child(Input::new(&command_input))
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .child(div().flex_1().child(highlight_invalid(
+                                            has_issue(|field| *field == AgentConfigField::Command),
+                                            Input::new(&command_input),
+                                            cx,
+                                        )))
+                                        .child(
+                                            Button::new("verify-command-btn")
+                                                .label(
+                                                    t!("settings.agents.button.verify").to_string(),
+                                                )
+                                                .outline()
+                                                .small()
+                                                .on_click({
+                                                    let command_input = command_input.clone();
+                                                    let env_editor = env_editor.clone();
+                                                    let command_check = command_check.clone();
+                                                    move |_, _, cx| {
+                                                        let command =
+                                                            dialog_command(&command_input, cx);
+                                                        let path_override =
+                                                            env_editor.read(cx).value("PATH", cx);
+                                                        let check = CommandCheck::run(
+                                                            command.trim(),
+                                                            path_override,
+                                                            true,
+                                                        );
+                                                        command_check.update(cx, |state, cx| {
+                                                            *state = Some(check);
+                                                            cx.notify();
+                                                        });
+                                                    }
+                                                }),
+                                        ),
+                                )
                                 .child(
                                     Label::new(
                                         t!("settings.agents.field.command_help").to_string(),
                                     )
                                     .text_xs()
                                     .text_color(cx.theme().muted_foreground),
-                                ),
+                                )
+                                .children(
+                                    command_check
+                                        .read(cx)
+                                        .as_ref()
+                                        .map(|check| check.render(cx)),
+                                )
+                                .children(render_field_issues(
+                                    field_issues.read(cx),
+                                    |field| *field == AgentConfigField::Command,
+                                    cx,
+                                )),
                         )
                         .child(
                             v_flex()
@@ -498,7 +1629,16 @@ impl SettingsPanel {
                                         .text_sm()
                                         .font_weight(gpui::FontWeight::SEMIBOLD),
                                 )
-                                .child(Input::new(&args_input)),
+                                .child(highlight_invalid(
+                                    has_issue(|field| matches!(field, AgentConfigField::Arg(_))),
+                                    Input::new(&args_input),
+                                    cx,
+                                ))
+                                .children(render_field_issues(
+                                    field_issues.read(cx),
+                                    |field| matches!(field, AgentConfigField::Arg(_)),
+                                    cx,
+                                )),
                         )
                         .child(
                             v_flex()
@@ -508,14 +1648,73 @@ impl SettingsPanel {
                                         .text_sm()
                                         .font_weight(gpui::FontWeight::SEMIBOLD),
                                 )
-                                .child(Input::new(&env_input))
+                                .child(env_editor.clone()),
+                        )
+                        .child(
+                            v_flex()
+                                .gap_2()
+                                .child(
+                                    Label::new(
+                                        t!("settings.agents.field.working_dir_label").to_string(),
+                                    )
+                                    .text_sm()
+                                    .font_weight(gpui::FontWeight::SEMIBOLD),
+                                )
+                                .child(highlight_invalid(
+                                    has_issue(|field| *field == AgentConfigField::WorkingDir),
+                                    Input::new(&working_dir_input),
+                                    cx,
+                                ))
+                                .child(
+                                    Label::new(
+                                        t!("settings.agents.field.working_dir_help").to_string(),
+                                    )
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground),
+                                )
+                                .children(render_field_issues(
+                                    field_issues.read(cx),
+                                    |field| *field == AgentConfigField::WorkingDir,
+                                    cx,
+                                )),
+                        )
+                        .child(
+                            v_flex()
+                                .gap_2()
+                                .child(
+                                    Label::new(t!("settings.agents.field.icon_label").to_string())
+                                        .text_sm()
+                                        .font_weight(gpui::FontWeight::SEMIBOLD),
+                                )
                                 .child(
-                                    Label::new(t!("settings.agents.field.env_help").to_string())
+                                    h_flex()
+                                        .gap_1()
+                                        .flex_wrap()
+                                        .child(
+                                            Button::new("agent-icon-auto")
+                                                .label(t!("settings.agents.icon.auto").to_string())
+                                                .ghost()
+                                                .small()
+                                                .selected(selected_icon.is_none())
+                                                .on_click({
+                                                    let icon_state = icon_state.clone();
+                                                    move |_, _, cx| {
+                                                        icon_state.update(cx, |icon, cx| {
+                                                            *icon = None;
+                                                            cx.notify();
+                                                        });
+                                                    }
+                                                }),
+                                        )
+                                        .children(icon_buttons),
+                                )
+                                .child(
+                                    Label::new(t!("settings.agents.field.icon_help").to_string())
                                         .text_xs()
                                         .text_color(cx.theme().muted_foreground),
                                 ),
                         ),
-                )
+                ))
         });
     }
 
@@ -554,6 +1753,150 @@ impl SettingsPanel {
         });
     }
 
+    /// Show the list of config backups with a preview of the selected one
+    pub fn show_restore_backup_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let backups = AppState::global(cx)
+            .agent_config_service()
+            .map(|service| service.list_backups())
+            .unwrap_or_default();
+
+        // Selected backup index and its file contents
+        let selection = cx.new(|_| None::<(usize, String)>);
+
+        window.open_dialog(cx, move |dialog, _window, cx| {
+            let selected = selection.read(cx).clone();
+            let rows = backups.iter().enumerate().map(|(idx, backup)| {
+                let selection = selection.clone();
+                let path = backup.path.clone();
+                let created_at = backup
+                    .created_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string();
+                Button::new(("config-backup", idx))
+                    .label(created_at)
+                    .icon(IconName::File)
+                    .ghost()
+                    .small()
+                    .w_full()
+                    .selected(selected.as_ref().map(|(i, _)| *i) == Some(idx))
+                    .on_click(move |_, _, cx| {
+                        let contents = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+                            t!("settings.agents.config.backup.read_failed", error = e).to_string()
+                        });
+                        selection.update(cx, |selection, cx| {
+                            *selection = Some((idx, contents));
+                            cx.notify();
+                        });
+                    })
+            });
+
+            let list = if backups.is_empty() {
+                v_flex().child(
+                    Label::new(t!("settings.agents.config.backup.empty").to_string())
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            } else {
+                v_flex().gap_1().children(rows)
+            };
+
+            dialog
+                .title(t!("settings.agents.config.backup.title").to_string())
+                .confirm()
+                .button_props(
+                    DialogButtonProps::default()
+                        .ok_text(t!("settings.agents.config.backup.ok").to_string())
+                        .cancel_text(t!("settings.agents.dialog.cancel").to_string()),
+                )
+                .on_ok({
+                    let backups = backups.clone();
+                    let selection = selection.clone();
+                    move |_, window, cx| {
+                        let Some(path) = selection
+                            .read(cx)
+                            .as_ref()
+                            .and_then(|(idx, _)| backups.get(*idx))
+                            .map(|backup| backup.path.clone())
+                        else {
+                            return false;
+                        };
+                        log::info!("Restoring config backup: {:?}", path);
+                        window.dispatch_action(Box::new(RestoreConfigBackup { path }), cx);
+                        true
+                    }
+                })
+                .child(
+                    v_flex()
+                        .w_full()
+                        .gap_3()
+                        .p_4()
+                        .child(
+                            Label::new(t!("settings.agents.config.backup.description").to_string())
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground),
+                        )
+                        .child(list)
+                        .when_some(selected, |this, (_, contents)| {
+                            this.child(
+                                div()
+                                    .id("config-backup-preview")
+                                    .w_full()
+                                    .max_h(px(240.))
+                                    .overflow_y_scroll()
+                                    .p_2()
+                                    .rounded(px(4.))
+                                    .bg(cx.theme().background)
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .font_family("Monaco, 'Courier New', monospace")
+                                    .text_xs()
+                                    .child(contents),
+                            )
+                        }),
+                )
+        });
+    }
+
+    /// Pick a new upload directory and save it if it can be written to
+    fn show_upload_dir_picker(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(service) = AppState::global(cx).agent_config_service().cloned() else {
+            log::error!("AgentConfigService not initialized");
+            return;
+        };
+
+        cx.spawn_in(window, async move |this, window| {
+            let title = t!("settings.agents.upload_dir.dialog_title").to_string();
+            let Some(path) = crate::utils::pick_folder(&title).await else {
+                return;
+            };
+
+            let result = service.set_upload_dir(path.clone()).await;
+            _ = this.update_in(window, |this, window, cx| match result {
+                Ok(()) => {
+                    this.cached_upload_dir = Some(UploadDirStatus::check(path));
+                    cx.notify();
+                }
+                Err(e) => {
+                    log::error!("Failed to set upload directory: {e:#}");
+                    struct UploadDirFailed;
+                    window.push_notification(
+                        Notification::error(
+                            t!(
+                                "settings.agents.upload_dir.failed",
+                                error = format!("{e:#}")
+                            )
+                            .to_string(),
+                        )
+                        .id::<UploadDirFailed>(),
+                        cx,
+                    );
+                }
+            });
+        })
+        .detach();
+    }
+
     /// Show file picker to select config file
     pub fn show_config_file_picker(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         let weak_entity = cx.entity().downgrade();
@@ -586,3 +1929,33 @@ impl SettingsPanel {
         .detach();
     }
 }
+
+/// Suggest "<name> copy", then "<name> copy 2", ... until the name is free
+fn unique_copy_name(source: &str, exists: impl Fn(&str) -> bool) -> String {
+    let base = format!("{} copy", source);
+    if !exists(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{} {}", base, n))
+        .find(|name| !exists(name))
+        .unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_copy_name_skips_taken_names() {
+        let taken: HashSet<&str> = ["Claude Code copy", "Claude Code copy 2"].into();
+        assert_eq!(
+            unique_copy_name("Gemini", |n| taken.contains(n)),
+            "Gemini copy"
+        );
+        assert_eq!(
+            unique_copy_name("Claude Code", |n| taken.contains(n)),
+            "Claude Code copy 3"
+        );
+    }
+}
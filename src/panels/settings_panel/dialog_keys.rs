@@ -0,0 +1,126 @@
+//! Keyboard handling shared by the settings dialogs
+//!
+//! Dialogs already close on Esc. This adds focusing the first field on open,
+//! Enter submitting from single-line fields and Tab/Shift-Tab cycling through
+//! the fields without leaving the dialog. Multi-line fields keep Enter for
+//! newlines.
+
+use std::rc::Rc;
+
+use gpui::{
+    App, Entity, EventEmitter, InteractiveElement as _, IntoElement, ParentElement as _, Styled,
+    Subscription, Window, div,
+};
+use gpui_component::{
+    WindowExt as _,
+    input::{InputEvent, InputState},
+};
+
+use crate::app::actions::{FocusNextField, FocusPreviousField};
+
+/// Validates and applies a dialog, returning whether it should close
+pub(super) type SubmitHandler = Rc<dyn Fn(&mut Window, &mut App) -> bool>;
+
+/// The focusable fields of a dialog in Tab order, read at key press time so
+/// rows added while the dialog is open are included
+pub(super) type FieldList = Rc<dyn Fn(&App) -> Vec<Entity<InputState>>>;
+
+/// Wrap dialog content so Tab and Shift-Tab cycle through `fields`
+pub(super) fn focus_trap(fields: FieldList, content: impl IntoElement) -> impl IntoElement {
+    let previous = fields.clone();
+    div()
+        .key_context("SettingsDialog")
+        .on_action(move |_: &FocusNextField, window, cx| cycle_focus(&fields(cx), 1, window, cx))
+        .on_action(move |_: &FocusPreviousField, window, cx| {
+            cycle_focus(&previous(cx), -1, window, cx)
+        })
+        .w_full()
+        .child(content)
+}
+
+fn cycle_focus(fields: &[Entity<InputState>], step: isize, window: &mut Window, cx: &mut App) {
+    let current = fields
+        .iter()
+        .position(|field| field.read(cx).focus_handle(cx).is_focused(window));
+    let Some(next) = next_index(current, step, fields.len()) else {
+        return;
+    };
+    fields[next].update(cx, |state, cx| state.focus(window, cx));
+}
+
+/// Index of the field after `current` moving by `step`, wrapping around;
+/// starts from the first (or last, going back) field when none is focused
+fn next_index(current: Option<usize>, step: isize, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let len = len as isize;
+    let next = match current {
+        Some(ix) => (ix as isize + step).rem_euclid(len),
+        None if step < 0 => len - 1,
+        None => 0,
+    };
+    Some(next as usize)
+}
+
+/// Submit the dialog when Enter is pressed in one of `inputs`, closing it on success
+pub(super) fn submit_on_enter(
+    inputs: &[Entity<InputState>],
+    submit: &SubmitHandler,
+    window: &mut Window,
+    cx: &mut App,
+) -> Vec<Subscription> {
+    inputs
+        .iter()
+        .map(|input| {
+            let submit = submit.clone();
+            window.subscribe(input, cx, move |_, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { secondary: false } = event {
+                    submit_and_close(&submit, window, cx);
+                }
+            })
+        })
+        .collect()
+}
+
+/// Submit the dialog when `editor` emits `E`, for composite fields that raise their own submit
+pub(super) fn submit_on_event<T: EventEmitter<E>, E: 'static>(
+    editor: &Entity<T>,
+    submit: &SubmitHandler,
+    window: &mut Window,
+    cx: &mut App,
+) -> Subscription {
+    let submit = submit.clone();
+    window.subscribe(editor, cx, move |_, _: &E, window, cx| {
+        submit_and_close(&submit, window, cx)
+    })
+}
+
+fn submit_and_close(submit: &SubmitHandler, window: &mut Window, cx: &mut App) {
+    if submit(window, cx) {
+        window.close_dialog(cx);
+    }
+}
+
+/// Focus `input` once the dialog has been opened
+pub(super) fn focus_first(input: &Entity<InputState>, window: &mut Window, cx: &mut App) {
+    let input = input.clone();
+    window.defer(cx, move |window, cx| {
+        input.update(cx, |state, cx| state.focus(window, cx));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_index_wraps_in_both_directions() {
+        assert_eq!(next_index(Some(0), 1, 3), Some(1));
+        assert_eq!(next_index(Some(2), 1, 3), Some(0));
+        assert_eq!(next_index(Some(0), -1, 3), Some(2));
+        assert_eq!(next_index(None, 1, 3), Some(0));
+        assert_eq!(next_index(None, -1, 3), Some(2));
+        assert_eq!(next_index(None, 1, 0), None);
+    }
+}
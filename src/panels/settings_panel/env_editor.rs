@@ -0,0 +1,417 @@
+//! Key/value row editor for agent environment variables
+
+use std::collections::{HashMap, HashSet};
+
+use gpui::{
+    App, AppContext as _, Context, Entity, EventEmitter, IntoElement, ParentElement as _, Render,
+    Styled, Subscription, Window, div, prelude::FluentBuilder as _, px,
+};
+use gpui_component::{
+    ActiveTheme, IconName, Sizable,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    v_flex,
+};
+use rust_i18n::t;
+
+use crate::utils::diagnostics::is_secret_key;
+
+struct EnvVarRow {
+    id: usize,
+    key: Entity<InputState>,
+    value: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// Emitted when Enter is pressed in a row, so the enclosing dialog can submit
+pub(super) struct EnvVarSubmit;
+
+/// Editable list of environment variables, with a bulk `KEY=VALUE` import
+pub(super) struct EnvVarEditor {
+    rows: Vec<EnvVarRow>,
+    next_id: usize,
+    /// Text input for pasting variables, shown on demand
+    import_input: Option<Entity<InputState>>,
+    error: Option<String>,
+    /// Keys rejected by validation, highlighted until edited
+    invalid_keys: HashSet<String>,
+}
+
+impl EnvVarEditor {
+    pub(super) fn new(
+        env: &HashMap<String, String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut editor = Self {
+            rows: Vec::new(),
+            next_id: 0,
+            import_input: None,
+            error: None,
+            invalid_keys: HashSet::new(),
+        };
+
+        let mut vars: Vec<_> = env.iter().collect();
+        vars.sort();
+        for (key, value) in vars {
+            editor.add_row(key, value, window, cx);
+        }
+        if editor.rows.is_empty() {
+            editor.add_row("", "", window, cx);
+        }
+        editor
+    }
+
+    /// Replace all rows with `env`
+    pub(super) fn set_vars(
+        &mut self,
+        env: &HashMap<String, String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.rows.clear();
+        let mut vars: Vec<_> = env.iter().collect();
+        vars.sort();
+        for (key, value) in vars {
+            self.add_row(key, value, window, cx);
+        }
+        if self.rows.is_empty() {
+            self.add_row("", "", window, cx);
+        }
+        self.error = None;
+        self.invalid_keys.clear();
+        cx.notify();
+    }
+
+    fn add_row(&mut self, key: &str, value: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let masked = is_secret_key(key);
+        let key_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx)
+                .placeholder(t!("settings.agents.env.key_placeholder").to_string());
+            state.set_value(key.to_string(), window, cx);
+            state
+        });
+        let value_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx)
+                .placeholder(t!("settings.agents.env.value_placeholder").to_string())
+                .masked(masked);
+            state.set_value(value.to_string(), window, cx);
+            state
+        });
+
+        // Mask the value while the key looks like it names a secret
+        let value_for_mask = value_input.clone();
+        let subscription = cx.subscribe_in(
+            &key_input,
+            window,
+            move |this, key_input, event: &InputEvent, window, cx| {
+                if let InputEvent::Change = event {
+                    let masked = is_secret_key(&key_input.read(cx).value());
+                    value_for_mask.update(cx, |state, cx| state.set_masked(masked, window, cx));
+                    this.error = None;
+                    this.invalid_keys.clear();
+                    cx.notify();
+                }
+            },
+        );
+
+        let subscriptions = vec![
+            subscription,
+            cx.subscribe(&key_input, Self::submit_on_enter),
+            cx.subscribe(&value_input, Self::submit_on_enter),
+        ];
+
+        self.rows.push(EnvVarRow {
+            id: self.next_id,
+            key: key_input,
+            value: value_input,
+            _subscriptions: subscriptions,
+        });
+        self.next_id += 1;
+    }
+
+    fn submit_on_enter(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::PressEnter { secondary: false } = event {
+            cx.emit(EnvVarSubmit);
+        }
+    }
+
+    fn remove_row(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.rows.retain(|row| row.id != id);
+        self.error = None;
+        cx.notify();
+    }
+
+    fn toggle_import(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.import_input = match self.import_input.take() {
+            Some(_) => None,
+            None => Some(cx.new(|cx| {
+                InputState::new(window, cx)
+                    .multi_line(true)
+                    .auto_grow(3, 8)
+                    .placeholder(t!("settings.agents.input.env.placeholder").to_string())
+            })),
+        };
+        cx.notify();
+    }
+
+    /// Merge pasted `KEY=VALUE` lines into the rows, replacing values of existing keys
+    fn apply_import(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(import_input) = self.import_input.clone() else {
+            return;
+        };
+        let text = import_input.read(cx).value().to_string();
+        let vars = match parse_env_text(&text) {
+            Ok(vars) => vars,
+            Err(line) => {
+                self.error = Some(t!("settings.agents.env.error.invalid_line", line = line).into());
+                cx.notify();
+                return;
+            }
+        };
+
+        // Drop the placeholder row left by an empty editor
+        self.rows.retain(|row| {
+            !(row.key.read(cx).value().is_empty() && row.value.read(cx).value().is_empty())
+        });
+        for (key, value) in vars {
+            let existing = self
+                .rows
+                .iter()
+                .find(|row| row.key.read(cx).value().trim() == key)
+                .map(|row| row.value.clone());
+            match existing {
+                Some(value_input) => {
+                    value_input.update(cx, |state, cx| state.set_value(value, window, cx))
+                }
+                None => self.add_row(&key, &value, window, cx),
+            }
+        }
+
+        self.import_input = None;
+        self.error = None;
+        cx.notify();
+    }
+
+    /// Key and value inputs of every row in display order, for Tab navigation
+    pub(super) fn inputs(&self) -> Vec<Entity<InputState>> {
+        self.rows
+            .iter()
+            .flat_map(|row| [row.key.clone(), row.value.clone()])
+            .collect()
+    }
+
+    /// Current value of a variable, if a row defines it
+    pub(super) fn value(&self, key: &str, cx: &App) -> Option<String> {
+        self.rows
+            .iter()
+            .find(|row| row.key.read(cx).value().trim() == key)
+            .map(|row| row.value.read(cx).value().to_string())
+    }
+
+    /// Highlight keys rejected by the agent config validation, with their messages
+    pub(super) fn show_key_errors(
+        &mut self,
+        errors: Vec<(String, String)>,
+        cx: &mut Context<Self>,
+    ) {
+        self.error = Some(
+            errors
+                .iter()
+                .map(|(_, message)| message.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        self.invalid_keys = errors.into_iter().map(|(key, _)| key).collect();
+        cx.notify();
+    }
+
+    /// Collect the variables, showing an error and returning `None` if they are invalid
+    pub(super) fn validate(&mut self, cx: &mut Context<Self>) -> Option<HashMap<String, String>> {
+        let pairs = self
+            .rows
+            .iter()
+            .map(|row| {
+                (
+                    row.key.read(cx).value().to_string(),
+                    row.value.read(cx).value().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        match collect_env(pairs) {
+            Ok(env) => {
+                self.error = None;
+                Some(env)
+            }
+            Err(error) => {
+                self.error = Some(error);
+                cx.notify();
+                None
+            }
+        }
+    }
+}
+
+impl EventEmitter<EnvVarSubmit> for EnvVarEditor {}
+
+/// Parse `KEY=VALUE` lines, returning the first invalid line on failure
+fn parse_env_text(text: &str) -> Result<Vec<(String, String)>, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(line.to_string()),
+        })
+        .collect()
+}
+
+/// Build the environment map from key/value rows, skipping blank rows
+fn collect_env(pairs: Vec<(String, String)>) -> Result<HashMap<String, String>, String> {
+    let mut env = HashMap::new();
+    for (key, value) in pairs {
+        let key = key.trim();
+        if key.is_empty() {
+            if value.trim().is_empty() {
+                continue;
+            }
+            return Err(t!("settings.agents.env.error.missing_key").to_string());
+        }
+        if env.insert(key.to_string(), value).is_some() {
+            return Err(t!("settings.agents.env.error.duplicate", key = key).to_string());
+        }
+    }
+    Ok(env)
+}
+
+impl Render for EnvVarEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows = self.rows.iter().map(|row| {
+            let id = row.id;
+            let key = row.key.read(cx).value();
+            let masked = is_secret_key(&key);
+            let invalid = self.invalid_keys.contains(key.trim());
+            h_flex()
+                .w_full()
+                .gap_2()
+                .child(
+                    div()
+                        .flex_1()
+                        .when(invalid, |this| {
+                            this.rounded(px(6.))
+                                .border_1()
+                                .border_color(cx.theme().danger)
+                        })
+                        .child(Input::new(&row.key).small()),
+                )
+                .child(
+                    div().flex_1().child(
+                        Input::new(&row.value)
+                            .small()
+                            .when(masked, |input| input.mask_toggle()),
+                    ),
+                )
+                .child(
+                    Button::new(("env-remove", id))
+                        .icon(IconName::Minus)
+                        .ghost()
+                        .xsmall()
+                        .on_click(cx.listener(move |this, _, _, cx| this.remove_row(id, cx))),
+                )
+        });
+
+        v_flex()
+            .w_full()
+            .gap_2()
+            .children(rows)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("env-add")
+                            .label(t!("settings.agents.env.add").to_string())
+                            .icon(IconName::Plus)
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.add_row("", "", window, cx);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("env-paste")
+                            .label(t!("settings.agents.env.paste").to_string())
+                            .ghost()
+                            .xsmall()
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.toggle_import(window, cx)),
+                            ),
+                    ),
+            )
+            .when_some(self.import_input.clone(), |this, import_input| {
+                this.child(
+                    v_flex().gap_1().child(Input::new(&import_input)).child(
+                        h_flex()
+                            .justify_between()
+                            .items_center()
+                            .child(
+                                Label::new(t!("settings.agents.field.env_help").to_string())
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground),
+                            )
+                            .child(
+                                Button::new("env-import")
+                                    .label(t!("settings.agents.env.import").to_string())
+                                    .outline()
+                                    .xsmall()
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.apply_import(window, cx)
+                                    })),
+                            ),
+                    ),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(Label::new(error).text_xs().text_color(cx.theme().danger))
+            })
+            .min_w(px(0.))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_env_text_reports_invalid_lines() {
+        assert_eq!(
+            parse_env_text("# comment\nA=1\n\n B = x=y \n").unwrap(),
+            vec![
+                ("A".to_string(), "1".to_string()),
+                ("B".to_string(), "x=y".to_string())
+            ]
+        );
+        assert_eq!(parse_env_text("A=1\nBROKEN").unwrap_err(), "BROKEN");
+    }
+
+    #[test]
+    fn collect_env_rejects_duplicates_and_missing_keys() {
+        let pair = |k: &str, v: &str| (k.to_string(), v.to_string());
+
+        let env = collect_env(vec![pair("A", "1"), pair(" ", ""), pair("B", "2")]).unwrap();
+        assert_eq!(env.len(), 2);
+
+        assert!(collect_env(vec![pair("A", "1"), pair("A ", "2")]).is_err());
+        assert!(collect_env(vec![pair("", "orphan")]).is_err());
+    }
+}
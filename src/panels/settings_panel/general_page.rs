@@ -1,15 +1,33 @@
-use gpui::{App, Axis, Entity, ParentElement as _, SharedString, Styled};
+use gpui::{
+    App, AppContext as _, Axis, Entity, IntoElement as _, ParentElement as _, SharedString, Styled,
+    Window,
+};
 use gpui_component::{
-    ActiveTheme, IconName, Sizable, Size, Theme, ThemeMode,
+    ActiveTheme, IconName, Sizable, Size, Theme, ThemeMode, WindowExt as _,
     button::Button,
+    dialog::DialogButtonProps,
     group_box::GroupBoxVariant,
     h_flex,
+    input::{Input, InputState},
+    label::Label,
+    notification::Notification,
     setting::{NumberFieldOptions, SettingField, SettingGroup, SettingItem, SettingPage},
+    v_flex,
 };
 use rust_i18n::t;
+use std::rc::Rc;
 
+use super::dialog_keys::{FieldList, SubmitHandler, focus_first, focus_trap, submit_on_enter};
 use super::panel::SettingsPanel;
 use super::types::AppSettings;
+use crate::app::actions::RerunStartupWizard;
+use crate::app::{profiles, themes};
+use crate::core::config::{
+    DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES, MAX_TOOL_CALL_PREVIEW_MAX_LINES,
+    MIN_TOOL_CALL_PREVIEW_MAX_LINES,
+};
+use crate::core::config_manager;
+use crate::{AppState, Quit};
 
 impl SettingsPanel {
     pub fn general_page(&self, _view: &Entity<Self>, resettable: bool) -> SettingPage {
@@ -22,6 +40,23 @@ impl SettingsPanel {
                 SettingGroup::new()
                     .title(t!("settings.general.group.appearance").to_string())
                     .items(vec![
+                        SettingItem::new(
+                            t!("settings.general.appearance.language.label").to_string(),
+                            SettingField::dropdown(
+                                crate::i18n::available_locales()
+                                    .into_iter()
+                                    .map(|(locale, name)| (locale.into(), name))
+                                    .collect(),
+                                |cx: &App| AppSettings::global(cx).locale.clone(),
+                                |val: SharedString, cx: &mut App| {
+                                    crate::i18n::select_locale(val.as_ref(), cx);
+                                },
+                            )
+                            .default_value(default_settings.locale.clone()),
+                        )
+                        .description(
+                            t!("settings.general.appearance.language.description").to_string(),
+                        ),
                         SettingItem::new(
                             t!("settings.general.appearance.dark_mode.label").to_string(),
                             SettingField::switch(
@@ -32,8 +67,10 @@ impl SettingsPanel {
                                     } else {
                                         ThemeMode::Light
                                     };
+                                    themes::follow_system_appearance(false, cx);
                                     Theme::global_mut(cx).mode = mode;
                                     Theme::change(mode, None, cx);
+                                    themes::apply_min_contrast(cx);
                                 },
                             )
                             .default_value(false),
@@ -46,7 +83,7 @@ impl SettingsPanel {
                             SettingField::checkbox(
                                 |cx: &App| AppSettings::global(cx).auto_switch_theme,
                                 |val: bool, cx: &mut App| {
-                                    AppSettings::global_mut(cx).auto_switch_theme = val;
+                                    themes::follow_system_appearance(val, cx);
                                 },
                             )
                             .default_value(default_settings.auto_switch_theme),
@@ -54,6 +91,59 @@ impl SettingsPanel {
                         .description(
                             t!("settings.general.appearance.auto_switch.description").to_string(),
                         ),
+                        SettingItem::new(
+                            t!("settings.general.appearance.min_contrast.label").to_string(),
+                            SettingField::switch(
+                                |cx: &App| AppSettings::global(cx).enforce_min_contrast,
+                                |val: bool, cx: &mut App| {
+                                    themes::set_min_contrast(val, cx);
+                                },
+                            )
+                            .default_value(default_settings.enforce_min_contrast),
+                        )
+                        .description(
+                            t!("settings.general.appearance.min_contrast.description").to_string(),
+                        ),
+                        SettingItem::render(|options, _, _| {
+                            h_flex()
+                                .w_full()
+                                .justify_between()
+                                .flex_wrap()
+                                .gap_3()
+                                .child(
+                                    t!("settings.general.appearance.theme_files.label").to_string(),
+                                )
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .child(
+                                            Button::new("import-theme")
+                                                .icon(IconName::FolderOpen)
+                                                .label(
+                                                    t!("settings.general.appearance.theme_files.import")
+                                                        .to_string(),
+                                                )
+                                                .outline()
+                                                .with_size(options.size)
+                                                .on_click(|_, window, cx| {
+                                                    themes::import_theme(window, cx);
+                                                }),
+                                        )
+                                        .child(
+                                            Button::new("export-theme")
+                                                .icon(IconName::ArrowDown)
+                                                .label(
+                                                    t!("settings.general.appearance.theme_files.export")
+                                                        .to_string(),
+                                                )
+                                                .outline()
+                                                .with_size(options.size)
+                                                .on_click(|_, window, cx| {
+                                                    themes::export_theme(window, cx);
+                                                }),
+                                        ),
+                                )
+                        }),
                         SettingItem::new(
                             t!("settings.general.appearance.resettable.label").to_string(),
                             SettingField::switch(
@@ -219,6 +309,115 @@ impl SettingsPanel {
                                         }),
                                 )
                         }),
+                        SettingItem::render(|options, _, _| {
+                            h_flex()
+                                .w_full()
+                                .justify_between()
+                                .flex_wrap()
+                                .gap_3()
+                                .child(t!("settings.general.other.setup_wizard.label").to_string())
+                                .child(
+                                    Button::new("rerun-setup-wizard")
+                                        .icon(IconName::Settings)
+                                        .label(
+                                            t!("settings.general.other.setup_wizard.button")
+                                                .to_string(),
+                                        )
+                                        .outline()
+                                        .with_size(options.size)
+                                        .on_click(|_, window, cx| {
+                                            window
+                                                .dispatch_action(Box::new(RerunStartupWizard), cx);
+                                        }),
+                                )
+                        }),
+                        SettingItem::new(
+                            t!("settings.general.other.close_to_tray.label").to_string(),
+                            SettingField::switch(
+                                |cx: &App| AppSettings::global(cx).close_to_tray,
+                                |val: bool, cx: &mut App| {
+                                    AppSettings::global_mut(cx).close_to_tray = val;
+                                },
+                            )
+                            .default_value(default_settings.close_to_tray),
+                        )
+                        .description(
+                            t!("settings.general.other.close_to_tray.description").to_string(),
+                        ),
+                        SettingItem::new(
+                            t!("settings.general.other.auto_title.label").to_string(),
+                            SettingField::switch(
+                                |cx: &App| AppSettings::global(cx).auto_title_sessions,
+                                |val: bool, cx: &mut App| {
+                                    AppSettings::global_mut(cx).auto_title_sessions = val;
+                                },
+                            )
+                            .default_value(default_settings.auto_title_sessions),
+                        )
+                        .description(
+                            t!("settings.general.other.auto_title.description").to_string(),
+                        ),
+                        SettingItem::new(
+                            t!("settings.general.other.tool_call_preview.label").to_string(),
+                            SettingField::number_input(
+                                NumberFieldOptions {
+                                    min: MIN_TOOL_CALL_PREVIEW_MAX_LINES as f64,
+                                    max: MAX_TOOL_CALL_PREVIEW_MAX_LINES as f64,
+                                    ..Default::default()
+                                },
+                                |cx: &App| AppState::global(cx).tool_call_preview_max_lines() as f64,
+                                |val: f64, cx: &mut App| {
+                                    let max_lines = (val.round() as usize).clamp(
+                                        MIN_TOOL_CALL_PREVIEW_MAX_LINES,
+                                        MAX_TOOL_CALL_PREVIEW_MAX_LINES,
+                                    );
+                                    AppState::global_mut(cx)
+                                        .set_tool_call_preview_max_lines(max_lines);
+                                    cx.refresh_windows();
+
+                                    if let Some(service) =
+                                        AppState::global(cx).agent_config_service()
+                                    {
+                                        let service = service.clone();
+                                        cx.spawn(async move |_cx| {
+                                            if let Err(err) = service
+                                                .update_tool_call_preview_max_lines(max_lines)
+                                                .await
+                                            {
+                                                log::error!(
+                                                    "Failed to update tool call preview line limit: {}",
+                                                    err
+                                                );
+                                            }
+                                        })
+                                        .detach();
+                                    }
+                                },
+                            )
+                            .default_value(DEFAULT_TOOL_CALL_PREVIEW_MAX_LINES as f64),
+                        )
+                        .description(
+                            t!("settings.general.other.tool_call_preview.description").to_string(),
+                        ),
+                        SettingItem::new(
+                            t!("settings.general.other.diff_max_lines.label").to_string(),
+                            SettingField::number_input(
+                                NumberFieldOptions {
+                                    min: 100.0,
+                                    max: 100_000.0,
+                                    ..Default::default()
+                                },
+                                |cx: &App| AppSettings::global(cx).diff_max_lines,
+                                |val: f64, cx: &mut App| {
+                                    AppSettings::global_mut(cx).diff_max_lines =
+                                        val.round().clamp(100.0, 100_000.0);
+                                },
+                            )
+                            .default_value(default_settings.diff_max_lines),
+                        )
+                        .description(
+                            t!("settings.general.other.diff_max_lines.description").to_string(),
+                        ),
                         SettingItem::new(
                             t!("settings.general.other.cli_path.label").to_string(),
                             SettingField::input(
@@ -248,6 +447,177 @@ impl SettingsPanel {
                             t!("settings.general.other.nodejs_path.description").to_string(),
                         ),
                     ]),
+                Self::profiles_group(),
             ])
     }
+
+    /// Profiles known in the data directory, with a switcher that relaunches the app
+    fn profiles_group() -> SettingGroup {
+        SettingGroup::new()
+            .title(t!("settings.general.group.profiles").to_string())
+            .description(t!("settings.general.profiles.description").to_string())
+            .item(SettingItem::render(|options, _, cx| {
+                let current = config_manager::current_profile();
+                let profiles = std::iter::once(None)
+                    .chain(config_manager::list_profiles().into_iter().map(Some));
+
+                v_flex()
+                    .w_full()
+                    .gap_2()
+                    .children(profiles.enumerate().map(|(ix, profile)| {
+                        let is_current = profile.as_deref() == current;
+                        let name = profile
+                            .clone()
+                            .unwrap_or_else(|| t!("settings.general.profiles.default").to_string());
+                        h_flex()
+                            .w_full()
+                            .justify_between()
+                            .gap_3()
+                            .child(name)
+                            .child(if is_current {
+                                Label::new(t!("settings.general.profiles.current").to_string())
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .into_any_element()
+                            } else {
+                                Button::new(("switch-profile", ix))
+                                    .label(t!("settings.general.profiles.switch").to_string())
+                                    .outline()
+                                    .with_size(options.size)
+                                    .on_click(move |_, window, cx| {
+                                        confirm_switch_profile(profile.clone(), window, cx);
+                                    })
+                                    .into_any_element()
+                            })
+                    }))
+                    .child(
+                        h_flex().w_full().justify_end().child(
+                            Button::new("new-profile")
+                                .icon(IconName::Plus)
+                                .label(t!("settings.general.profiles.new").to_string())
+                                .outline()
+                                .with_size(options.size)
+                                .on_click(|_, window, cx| open_new_profile_dialog(window, cx)),
+                        ),
+                    )
+            }))
+    }
+}
+
+/// Ask before relaunching, since switching quits the running instance
+fn confirm_switch_profile(profile: Option<String>, window: &mut Window, cx: &mut App) {
+    let name = profile
+        .clone()
+        .unwrap_or_else(|| t!("settings.general.profiles.default").to_string());
+    window.open_dialog(cx, move |dialog, _window, _cx| {
+        let profile = profile.clone();
+        dialog
+            .title(t!("settings.general.profiles.switch_dialog.title").to_string())
+            .confirm()
+            .button_props(
+                DialogButtonProps::default()
+                    .ok_text(t!("settings.general.profiles.switch_dialog.ok").to_string())
+                    .cancel_text(t!("settings.general.profiles.cancel").to_string()),
+            )
+            .on_ok(move |_, window, cx| {
+                switch_profile(profile.as_deref(), window, cx);
+                true
+            })
+            .child(
+                v_flex().w_full().p_4().child(
+                    Label::new(
+                        t!(
+                            "settings.general.profiles.switch_dialog.message",
+                            name = name
+                        )
+                        .to_string(),
+                    )
+                    .text_sm(),
+                ),
+            )
+    });
+}
+
+fn switch_profile(profile: Option<&str>, window: &mut Window, cx: &mut App) {
+    match profiles::relaunch_into(profile) {
+        Ok(()) => window.dispatch_action(Box::new(Quit), cx),
+        Err(e) => {
+            log::error!("Failed to switch profile: {:#}", e);
+            window.push_notification(
+                Notification::error(
+                    t!(
+                        "settings.general.profiles.switch_failed",
+                        error = format!("{:#}", e)
+                    )
+                    .to_string(),
+                ),
+                cx,
+            );
+        }
+    }
+}
+
+/// Create a profile from a name typed by the user, then switch to it
+fn open_new_profile_dialog(window: &mut Window, cx: &mut App) {
+    let name_input = cx.new(|cx| {
+        InputState::new(window, cx)
+            .placeholder(t!("settings.general.profiles.new_dialog.placeholder").to_string())
+    });
+    let error = cx.new(|_| None::<String>);
+
+    let submit: SubmitHandler = Rc::new({
+        let name_input = name_input.clone();
+        let error = error.clone();
+        move |window, cx| {
+            let name = name_input.read(cx).value().trim().to_string();
+            if let Err(e) = config_manager::create_profile(&name) {
+                error.update(cx, |error, cx| {
+                    *error = Some(e.to_string());
+                    cx.notify();
+                });
+                return false;
+            }
+            switch_profile(Some(&name), window, cx);
+            true
+        }
+    });
+    let subscriptions = submit_on_enter(&[name_input.clone()], &submit, window, cx);
+    let fields: FieldList = Rc::new({
+        let name_input = name_input.clone();
+        move |_| vec![name_input.clone()]
+    });
+    focus_first(&name_input, window, cx);
+
+    window.open_dialog(cx, move |dialog, _window, cx| {
+        // Dropped with the dialog
+        let _ = &subscriptions;
+        let error = error.read(cx).clone();
+        dialog
+            .title(t!("settings.general.profiles.new_dialog.title").to_string())
+            .confirm()
+            .button_props(
+                DialogButtonProps::default()
+                    .ok_text(t!("settings.general.profiles.new_dialog.ok").to_string())
+                    .cancel_text(t!("settings.general.profiles.cancel").to_string()),
+            )
+            .on_ok({
+                let submit = submit.clone();
+                move |_, window, cx| submit(window, cx)
+            })
+            .child(focus_trap(
+                fields.clone(),
+                v_flex()
+                    .w_full()
+                    .gap_2()
+                    .p_4()
+                    .child(Label::new(
+                        t!("settings.general.profiles.new_dialog.label").to_string(),
+                    ))
+                    .child(Input::new(&name_input))
+                    .children(
+                        error
+                            .map(|error| Label::new(error).text_sm().text_color(cx.theme().danger)),
+                    ),
+            ))
+    });
 }
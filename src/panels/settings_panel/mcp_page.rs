@@ -1,4 +1,7 @@
-use gpui::{AppContext as _, Context, Entity, IntoElement, ParentElement as _, Styled, Window, px};
+use gpui::{
+    App, AppContext as _, Context, Entity, IntoElement, ParentElement as _, PromptLevel, Styled,
+    Window, prelude::FluentBuilder as _, px,
+};
 use gpui_component::{
     ActiveTheme, IconName, Sizable, WindowExt as _,
     button::Button,
@@ -11,10 +14,20 @@ use gpui_component::{
     v_flex,
 };
 use rust_i18n::t;
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
+use super::dialog_keys::{FieldList, SubmitHandler, focus_first, focus_trap, submit_on_enter};
 use super::panel::SettingsPanel;
-use crate::{AppState, core::config::McpServerConfig};
+use crate::{
+    AppState,
+    core::{
+        config::{McpServerConfig, describe_json_error},
+        services::McpServerChanges,
+    },
+};
+
+/// Index of the JSON editor in the MCP page's tab bar
+const MCP_JSON_TAB: usize = 1;
 
 impl SettingsPanel {
     pub fn mcp_page(&self, view: &Entity<Self>) -> SettingPage {
@@ -35,10 +48,9 @@ impl SettingsPanel {
                                 .selected_index(active_tab)
                                 .on_click({
                                     let view = view.clone();
-                                    move |ix: &usize, _window, cx| {
+                                    move |ix: &usize, window, cx| {
                                         view.update(cx, |this, cx| {
-                                            this.mcp_active_tab = *ix;
-                                            cx.notify();
+                                            this.switch_mcp_tab(*ix, window, cx);
                                         });
                                     }
                                 })
@@ -223,6 +235,10 @@ impl SettingsPanel {
             .children(json_error.map(|error| {
                 Label::new(error.clone())
                     .text_sm()
+                    // Parse errors carry a snippet with a caret that needs fixed-width columns
+                    .when(error.contains('\n'), |label| {
+                        label.font_family(cx.theme().mono_font_family.clone())
+                    })
                     .text_color(if error.starts_with("✓") {
                         gpui::green()
                     } else {
@@ -276,7 +292,82 @@ impl SettingsPanel {
                 .placeholder(t!("settings.mcp.dialog.add.config.placeholder").to_string())
         });
 
+        // Shared by the OK button and Enter in the name field; the JSON
+        // field is multi-line and keeps Enter for newlines
+        let submit: SubmitHandler = Rc::new({
+            let name_input = name_input.clone();
+            let config_input = config_input.clone();
+
+            move |_: &mut Window, cx: &mut App| {
+                let name = name_input.read(cx).text().to_string().trim().to_string();
+                let config_str = config_input.read(cx).text().to_string().trim().to_string();
+
+                if name.is_empty() {
+                    log::warn!("Name cannot be empty");
+                    return false;
+                }
+
+                // Parse config JSON (without enabled field, we'll add it)
+                #[derive(serde::Deserialize)]
+                struct TempMcpConfig {
+                    command: String,
+                    #[serde(default)]
+                    args: Vec<String>,
+                    #[serde(default)]
+                    env: std::collections::HashMap<String, String>,
+                }
+
+                let temp_config: TempMcpConfig = if !config_str.is_empty() {
+                    match serde_json::from_str(&config_str) {
+                        Ok(config) => config,
+                        Err(e) => {
+                            log::error!("Failed to parse MCP server config: {}", e);
+                            return false;
+                        }
+                    }
+                } else {
+                    log::error!("MCP server config cannot be empty");
+                    return false;
+                };
+
+                // Save to config file
+                if let Some(service) = AppState::global(cx).agent_config_service() {
+                    let service = service.clone();
+                    let config = crate::core::config::McpServerConfig {
+                        enabled: true,
+                        command: temp_config.command,
+                        args: temp_config.args,
+                        env: temp_config.env,
+                    };
+
+                    cx.spawn(async move |cx| {
+                        match service.add_mcp_server(name.clone(), config).await {
+                            Ok(_) => {
+                                log::info!("Successfully added MCP server: {}", name);
+                                _ = cx.update(|_cx| {});
+                            }
+                            Err(e) => {
+                                log::error!("Failed to add MCP server: {}", e);
+                            }
+                        }
+                    })
+                    .detach();
+                }
+
+                true
+            }
+        });
+        let subscriptions = submit_on_enter(&[name_input.clone()], &submit, window, cx);
+        let fields: FieldList = Rc::new({
+            let name_input = name_input.clone();
+            let config_input = config_input.clone();
+            move |_| vec![name_input.clone(), config_input.clone()]
+        });
+        focus_first(&name_input, window, cx);
+
         window.open_dialog(cx, move |dialog, _window, _cx| {
+            // Dropped with the dialog
+            let _ = &subscriptions;
             dialog
                 .title(t!("settings.mcp.dialog.add.title").to_string())
                 .confirm()
@@ -286,70 +377,11 @@ impl SettingsPanel {
                         .cancel_text(t!("settings.mcp.dialog.cancel").to_string()),
                 )
                 .on_ok({
-                    let name_input = name_input.clone();
-                    let config_input = config_input.clone();
-
-                    move |_, _window, cx| {
-                        let name = name_input.read(cx).text().to_string().trim().to_string();
-                        let config_str =
-                            config_input.read(cx).text().to_string().trim().to_string();
-
-                        if name.is_empty() {
-                            log::warn!("Name cannot be empty");
-                            return false;
-                        }
-
-                        // Parse config JSON (without enabled field, we'll add it)
-                        #[derive(serde::Deserialize)]
-                        struct TempMcpConfig {
-                            command: String,
-                            #[serde(default)]
-                            args: Vec<String>,
-                            #[serde(default)]
-                            env: std::collections::HashMap<String, String>,
-                        }
-
-                        let temp_config: TempMcpConfig = if !config_str.is_empty() {
-                            match serde_json::from_str(&config_str) {
-                                Ok(config) => config,
-                                Err(e) => {
-                                    log::error!("Failed to parse MCP server config: {}", e);
-                                    return false;
-                                }
-                            }
-                        } else {
-                            log::error!("MCP server config cannot be empty");
-                            return false;
-                        };
-
-                        // Save to config file
-                        if let Some(service) = AppState::global(cx).agent_config_service() {
-                            let service = service.clone();
-                            let config = crate::core::config::McpServerConfig {
-                                enabled: true,
-                                command: temp_config.command,
-                                args: temp_config.args,
-                                env: temp_config.env,
-                            };
-
-                            cx.spawn(async move |cx| {
-                                match service.add_mcp_server(name.clone(), config).await {
-                                    Ok(_) => {
-                                        log::info!("Successfully added MCP server: {}", name);
-                                        _ = cx.update(|_cx| {});
-                                    }
-                                    Err(e) => {
-                                        log::error!("Failed to add MCP server: {}", e);
-                                    }
-                                }
-                            })
-                            .detach();
-                        }
-
-                        true
-                    }
+                    let submit = submit.clone();
+                    move |_, window, cx| submit(window, cx)
                 })
-                .child(
+                .child(focus_trap(
+                    fields.clone(),
                     v_flex()
                         .w_full()
                         .gap_3()
@@ -370,7 +402,7 @@ impl SettingsPanel {
                                 ))
                                 .child(Input::new(&config_input)),
                         ),
-                )
+                ))
         });
     }
 
@@ -424,18 +456,7 @@ impl SettingsPanel {
         &self,
         cx: &Context<Self>,
     ) -> Result<HashMap<String, McpServerConfig>, String> {
-        let json_text = self.mcp_json_editor.read(cx).text().to_string();
-
-        let value = serde_json::from_str::<serde_json::Value>(&json_text)
-            .map_err(|e| t!("settings.mcp.json.error.invalid_json", error = e).to_string())?;
-
-        let mcp_servers = value
-            .as_object()
-            .and_then(|obj| obj.get("mcpServers").or_else(|| obj.get("mcp_servers")))
-            .ok_or_else(|| t!("settings.mcp.json.error.missing_field").to_string())?;
-
-        serde_json::from_value::<HashMap<String, McpServerConfig>>(mcp_servers.clone())
-            .map_err(|e| t!("settings.mcp.json.error.invalid_config", error = e).to_string())
+        parse_mcp_servers_json(&self.mcp_json_editor.read(cx).text().to_string())
     }
 
     pub fn load_mcp_servers_to_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -454,13 +475,99 @@ impl SettingsPanel {
             .unwrap_or_else(|e| format!("{{\"error\": \"Failed to serialize: {}\"}}", e));
 
         self.mcp_json_editor.update(cx, |input, cx| {
-            input.set_value(json_str, window, cx);
+            input.set_value(json_str.clone(), window, cx);
         });
+        self.mcp_json_baseline = json_str;
 
         self.mcp_json_error = None;
         cx.notify();
     }
 
+    /// Whether the JSON editor has edits that were neither saved nor discarded
+    pub fn mcp_json_dirty(&self, cx: &gpui::App) -> bool {
+        self.mcp_json_editor.read(cx).text().to_string() != self.mcp_json_baseline
+    }
+
+    /// Put the editor back to the text it had when last loaded or saved
+    pub fn discard_mcp_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let baseline = self.mcp_json_baseline.clone();
+        self.mcp_json_editor.update(cx, |input, cx| {
+            input.set_value(baseline, window, cx);
+        });
+        self.mcp_json_error = None;
+        cx.notify();
+    }
+
+    /// Switch between the interactive and JSON tabs, asking first when
+    /// leaving unsaved JSON edits
+    ///
+    /// Saving keeps the JSON tab open so the confirmation and its result
+    /// stay visible.
+    fn switch_mcp_tab(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if ix == self.mcp_active_tab {
+            return;
+        }
+        if self.mcp_active_tab != MCP_JSON_TAB || !self.mcp_json_dirty(cx) {
+            self.mcp_active_tab = ix;
+            cx.notify();
+            return;
+        }
+
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &t!("settings.mcp.json.unsaved.title"),
+            Some(&t!("settings.mcp.json.unsaved.detail")),
+            &[
+                t!("settings.mcp.json.unsaved.save").as_ref(),
+                t!("settings.mcp.json.unsaved.discard").as_ref(),
+                t!("settings.mcp.json.unsaved.cancel").as_ref(),
+            ],
+            cx,
+        );
+        cx.spawn_in(window, async move |this, window| {
+            let answer = answer.await;
+            _ = this.update_in(window, |this, window, cx| match answer {
+                Ok(0) => this.save_mcp_json(window, cx),
+                Ok(1) => {
+                    this.discard_mcp_json(window, cx);
+                    this.mcp_active_tab = ix;
+                    cx.notify();
+                }
+                _ => {}
+            });
+        })
+        .detach();
+    }
+
+    /// Offer to save unsaved JSON edits when the settings panel is closed
+    pub fn prompt_save_before_close(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.mcp_json_dirty(cx) {
+            return;
+        }
+
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &t!("settings.mcp.json.unsaved.title"),
+            Some(&t!("settings.mcp.json.unsaved.closed_detail")),
+            &[
+                t!("settings.mcp.json.unsaved.save").as_ref(),
+                t!("settings.mcp.json.unsaved.discard").as_ref(),
+            ],
+            cx,
+        );
+
+        // Hold a strong handle: the panel is already detached from the dock
+        let entity = cx.entity();
+        cx.spawn_in(window, async move |_, window| {
+            if answer.await == Ok(0) {
+                _ = window.update(|window, cx| {
+                    entity.update(cx, |this, cx| this.save_mcp_json(window, cx));
+                });
+            }
+        })
+        .detach();
+    }
+
     pub fn validate_mcp_json(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         self.mcp_json_error = match self.parse_mcp_json(cx) {
             Ok(servers) => Some(t!("settings.mcp.json.valid", count = servers.len()).to_string()),
@@ -469,33 +576,242 @@ impl SettingsPanel {
         cx.notify();
     }
 
-    pub fn save_mcp_json(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        match self.parse_mcp_json(cx) {
-            Ok(servers) => {
-                if let Some(service) = AppState::global(cx).agent_config_service() {
-                    let service = service.clone();
-                    cx.spawn_in(_window, async move |_this, _cx| {
-                        // Remove old servers
-                        for (name, _) in service.list_mcp_servers().await {
-                            let _ = service.remove_mcp_server(&name).await;
-                        }
-                        // Add new servers
-                        for (name, config) in servers {
-                            let _ = service.add_mcp_server(name, config).await;
-                        }
-                        log::info!("MCP servers saved successfully");
-                    })
-                    .detach();
-                    self.mcp_json_error = Some(t!("settings.mcp.json.saved").to_string());
-                } else {
-                    self.mcp_json_error =
-                        Some(t!("settings.mcp.json.service_unavailable").to_string());
-                }
-            }
+    /// Ask for confirmation with a summary of the changes, then apply them
+    pub fn save_mcp_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let servers = match self.parse_mcp_json(cx) {
+            Ok(servers) => servers,
             Err(e) => {
                 self.mcp_json_error = Some(t!("settings.mcp.json.invalid", error = e).to_string());
+                cx.notify();
+                return;
             }
+        };
+
+        let changes = McpServerChanges::between(&self.cached_mcp_servers, &servers);
+        if changes.is_empty() {
+            // Matches the saved config, e.g. after reformatting
+            self.mcp_json_baseline = self.mcp_json_editor.read(cx).text().to_string();
+            self.mcp_json_error = Some(t!("settings.mcp.json.unchanged").to_string());
+            cx.notify();
+            return;
         }
+
+        let view = cx.entity();
+        window.open_dialog(cx, move |dialog, _window, cx| {
+            let ok_variant = if changes.removed.is_empty() {
+                gpui_component::button::ButtonVariant::Primary
+            } else {
+                gpui_component::button::ButtonVariant::Danger
+            };
+            dialog
+                .title(t!("settings.mcp.json.confirm.title").to_string())
+                .confirm()
+                .button_props(
+                    DialogButtonProps::default()
+                        .ok_text(t!("settings.mcp.json.confirm.ok").to_string())
+                        .ok_variant(ok_variant)
+                        .cancel_text(t!("settings.mcp.dialog.cancel").to_string()),
+                )
+                .on_ok({
+                    let view = view.clone();
+                    let servers = servers.clone();
+                    move |_, window, cx| {
+                        view.update(cx, |this, cx| {
+                            this.apply_mcp_servers(servers.clone(), window, cx)
+                        });
+                        true
+                    }
+                })
+                .child(
+                    v_flex()
+                        .w_full()
+                        .gap_2()
+                        .p_4()
+                        .child(
+                            Label::new(
+                                t!(
+                                    "settings.mcp.json.confirm.message",
+                                    added = changes.added.len(),
+                                    updated = changes.updated.len(),
+                                    removed = changes.removed.len()
+                                )
+                                .to_string(),
+                            )
+                            .text_sm(),
+                        )
+                        .children(mcp_change_lines(&changes).into_iter().map(|line| {
+                            Label::new(line)
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                        })),
+                )
+        });
+    }
+
+    fn apply_mcp_servers(
+        &mut self,
+        servers: HashMap<String, McpServerConfig>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let saved_text = self.mcp_json_editor.read(cx).text().to_string();
+        let Some(service) = AppState::global(cx).agent_config_service().cloned() else {
+            self.mcp_json_error = Some(t!("settings.mcp.json.service_unavailable").to_string());
+            cx.notify();
+            return;
+        };
+
+        cx.spawn_in(window, async move |this, window| {
+            // Only servers that were added, changed or removed are touched
+            let result = service.replace_mcp_servers(servers).await;
+            _ = this.update_in(window, |this, _window, cx| {
+                if result.is_ok() {
+                    this.mcp_json_baseline = saved_text;
+                }
+                this.mcp_json_error = Some(match result {
+                    Ok(changes) if changes.is_empty() => {
+                        t!("settings.mcp.json.unchanged").to_string()
+                    }
+                    Ok(changes) => {
+                        log::info!("MCP servers saved: {:?}", changes);
+                        t!(
+                            "settings.mcp.json.saved",
+                            changes = mcp_change_lines(&changes).join("; ")
+                        )
+                        .to_string()
+                    }
+                    Err(e) => {
+                        log::error!("Failed to save MCP servers: {}", e);
+                        t!("settings.mcp.json.save_failed", error = e).to_string()
+                    }
+                });
+                cx.notify();
+            });
+        })
+        .detach();
+        self.mcp_json_error = Some(t!("settings.mcp.json.saving").to_string());
         cx.notify();
     }
 }
+
+/// One line per kind of change, e.g. "removed github, slack"
+fn mcp_change_lines(changes: &McpServerChanges) -> Vec<String> {
+    [
+        ("settings.mcp.json.changes.added", &changes.added),
+        ("settings.mcp.json.changes.updated", &changes.updated),
+        ("settings.mcp.json.changes.removed", &changes.removed),
+    ]
+    .into_iter()
+    .filter(|(_, names)| !names.is_empty())
+    .map(|(key, names)| t!(key, names = names.join(", ")).to_string())
+    .collect()
+}
+
+/// Parse the `mcpServers` object of the JSON editor
+///
+/// Each server is checked before deserializing, so that a server for an
+/// unsupported transport or without a command is reported by name instead
+/// of being saved half-configured.
+fn parse_mcp_servers_json(json_text: &str) -> Result<HashMap<String, McpServerConfig>, String> {
+    let value = serde_json::from_str::<serde_json::Value>(json_text).map_err(|e| {
+        t!(
+            "settings.mcp.json.error.invalid_json",
+            error = describe_json_error(json_text, &e)
+        )
+        .to_string()
+    })?;
+
+    let mcp_servers = value
+        .as_object()
+        .and_then(|obj| obj.get("mcpServers").or_else(|| obj.get("mcp_servers")))
+        .ok_or_else(|| t!("settings.mcp.json.error.missing_field").to_string())?;
+
+    let servers = mcp_servers
+        .as_object()
+        .ok_or_else(|| t!("settings.mcp.json.error.missing_field").to_string())?;
+    for (name, server) in servers {
+        validate_mcp_server_json(name, server)?;
+    }
+
+    serde_json::from_value::<HashMap<String, McpServerConfig>>(mcp_servers.clone())
+        .map_err(|e| t!("settings.mcp.json.error.invalid_config", error = e).to_string())
+}
+
+/// Check the fields a stdio server needs, the only transport supported
+fn validate_mcp_server_json(name: &str, server: &serde_json::Value) -> Result<(), String> {
+    use serde_json::Value;
+
+    if name.trim().is_empty() {
+        return Err(t!("settings.mcp.json.error.empty_name").to_string());
+    }
+    let Value::Object(server) = server else {
+        return Err(t!("settings.mcp.json.error.not_object", name = name).to_string());
+    };
+
+    let transport = server.get("type").or_else(|| server.get("transport"));
+    if let Some(transport) = transport.filter(|transport| transport.as_str() != Some("stdio")) {
+        let transport = transport
+            .as_str()
+            .map_or_else(|| transport.to_string(), String::from);
+        return Err(t!(
+            "settings.mcp.json.error.unsupported_transport",
+            name = name,
+            transport = transport
+        )
+        .to_string());
+    }
+
+    match server.get("command") {
+        Some(Value::String(command)) if !command.trim().is_empty() => {}
+        _ => return Err(t!("settings.mcp.json.error.missing_command", name = name).to_string()),
+    }
+
+    if let Some(args) = server.get("args") {
+        let valid = args
+            .as_array()
+            .is_some_and(|args| args.iter().all(Value::is_string));
+        if !valid {
+            return Err(t!("settings.mcp.json.error.invalid_args", name = name).to_string());
+        }
+    }
+
+    if let Some(env) = server.get("env") {
+        let valid = env.as_object().is_some_and(|env| {
+            env.iter().all(|(key, value)| {
+                !key.is_empty()
+                    && !key.contains('=')
+                    && !key.contains(char::is_whitespace)
+                    && value.is_string()
+            })
+        });
+        if !valid {
+            return Err(t!("settings.mcp.json.error.invalid_env", name = name).to_string());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mcp_servers_json_checks_each_server() {
+        let servers = parse_mcp_servers_json(
+            r#"{"mcpServers": {"fs": {"command": "npx", "args": ["mcp-fs"], "env": {"ROOT": "/tmp"}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(servers["fs"].args, vec!["mcp-fs"]);
+
+        for invalid in [
+            r#"{"mcpServers": {"web": {"type": "http", "url": "https://example.com/mcp"}}}"#,
+            r#"{"mcpServers": {"fs": {"command": " "}}}"#,
+            r#"{"mcpServers": {"fs": {"command": "npx", "args": "mcp-fs"}}}"#,
+            r#"{"mcpServers": {"fs": {"command": "npx", "env": {"BAD KEY": "1"}}}}"#,
+            r#"{"mcpServers": ["fs"]}"#,
+        ] {
+            assert!(parse_mcp_servers_json(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
@@ -1,12 +1,15 @@
 mod about_page;
 mod agent_page;
 mod command_page;
+mod dialog_keys;
+mod env_editor;
 mod general_page;
 mod mcp_page;
 mod model_page;
 mod network_page;
 mod panel;
 mod prompt_page;
+mod storage_page;
 mod types;
 mod update_page;
 
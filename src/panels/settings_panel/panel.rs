@@ -1,17 +1,20 @@
 use gpui::{
-    App, AppContext as _, Context, Entity, FocusHandle, Focusable, IntoElement, Render, Window, px,
+    App, AppContext as _, Context, Entity, FocusHandle, Focusable, IntoElement, Render, Task,
+    Window, px,
 };
 use gpui_component::{
     input::InputState,
     setting::{SettingPage, Settings},
 };
 use rust_i18n::t;
-use std::{collections::HashMap, path::PathBuf};
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     AppState,
     core::{
         config::{AgentProcessConfig, CommandConfig, McpServerConfig, ModelConfig},
+        event_bus::EventSubscription,
+        services::{StorageUsage, UploadDirStatus},
         updater::UpdateManager,
     },
 };
@@ -22,22 +25,37 @@ pub struct SettingsPanel {
     pub(super) focus_handle: FocusHandle,
     pub(super) update_status: UpdateStatus,
     pub(super) update_manager: UpdateManager,
+    /// Version a rollback would restore, when the updater kept one
+    pub(super) rollback_version: Option<String>,
     // Cached configuration state (synchronized by events)
     pub(super) cached_agents: HashMap<String, AgentProcessConfig>,
     pub(super) cached_models: HashMap<String, ModelConfig>,
     pub(super) cached_mcp_servers: HashMap<String, McpServerConfig>,
     pub(super) cached_commands: HashMap<String, CommandConfig>,
-    pub(super) cached_upload_dir: PathBuf,
+    /// Resolved upload directory, `None` until the config is loaded
+    pub(super) cached_upload_dir: Option<UploadDirStatus>,
     pub(super) cached_proxy: crate::core::config::ProxyConfig,
+    /// Session storage on disk, `None` until measured
+    pub(super) storage_usage: Option<StorageUsage>,
+    pub(super) storage_cleaning: bool,
+    // Agents whose log section is expanded, refreshed while any is open
+    pub(super) expanded_agent_logs: HashSet<String>,
+    pub(super) agent_logs_refresh: Option<Task<()>>,
+    /// Agent conflict warnings hidden until the next restart, by conflict key
+    pub(super) dismissed_agent_conflicts: HashSet<String>,
     // JSON editor state for MCP servers
     pub(super) mcp_json_editor: Entity<InputState>,
     pub(super) mcp_json_error: Option<String>,
+    /// Editor text as last loaded or saved; the editor is dirty when it differs
+    pub(super) mcp_json_baseline: String,
     pub(super) mcp_active_tab: usize,
     // System prompts input states
     pub(super) doc_comment_input: Entity<InputState>,
     pub(super) inline_comment_input: Entity<InputState>,
     pub(super) explain_input: Entity<InputState>,
     pub(super) improve_input: Entity<InputState>,
+    /// Config bus subscription, removed from the hub with the panel
+    _config_subscription: Option<EventSubscription>,
 }
 
 impl crate::panels::dock_panel::DockPanel for SettingsPanel {
@@ -89,23 +107,36 @@ impl SettingsPanel {
         let explain_input = cx.new(|cx| InputState::new(window, cx));
         let improve_input = cx.new(|cx| InputState::new(window, cx));
 
-        let panel = Self {
+        let mut panel = Self {
             focus_handle: cx.focus_handle(),
-            update_status: UpdateStatus::Idle,
+            // Show what the last automatic check found until the user checks again
+            update_status: AppSettings::global(cx)
+                .available_update()
+                .cloned()
+                .map(UpdateStatus::Available)
+                .unwrap_or(UpdateStatus::Idle),
             update_manager: UpdateManager::default(),
+            rollback_version: UpdateManager::rollback_version(),
             cached_agents: HashMap::new(),
             cached_models: HashMap::new(),
             cached_mcp_servers: HashMap::new(),
             cached_commands: HashMap::new(),
-            cached_upload_dir: PathBuf::from("."),
+            cached_upload_dir: None,
             cached_proxy: crate::core::config::ProxyConfig::default(),
+            storage_usage: None,
+            storage_cleaning: false,
+            expanded_agent_logs: HashSet::new(),
+            agent_logs_refresh: None,
+            dismissed_agent_conflicts: HashSet::new(),
             mcp_json_editor,
             mcp_json_error: None,
+            mcp_json_baseline: String::new(),
             mcp_active_tab: 0,
             doc_comment_input,
             inline_comment_input,
             explain_input,
             improve_input,
+            _config_subscription: None,
         };
 
         // Load all configuration from service asynchronously
@@ -117,7 +148,7 @@ impl SettingsPanel {
                 let models = service.list_models().await;
                 let mcp_servers = service.list_mcp_servers().await;
                 let commands = service.list_commands().await;
-                let upload_dir = service.get_upload_dir().await;
+                let upload_dir = service.upload_dir_status().await;
                 let proxy = service.proxy_config();
 
                 _ = window.update(|window, cx| {
@@ -127,7 +158,7 @@ impl SettingsPanel {
                             this.cached_models = models.into_iter().collect();
                             this.cached_mcp_servers = mcp_servers.into_iter().collect();
                             this.cached_commands = commands.into_iter().collect();
-                            this.cached_upload_dir = upload_dir;
+                            this.cached_upload_dir = Some(upload_dir);
                             this.cached_proxy = proxy;
                             // Load system prompts into input fields
                             this.load_system_prompts(window, cx);
@@ -144,9 +175,10 @@ impl SettingsPanel {
         let weak_entity = cx.entity().downgrade();
         let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
 
-        event_hub.subscribe_agent_config_updates(move |event| {
+        let id = event_hub.subscribe_agent_config_updates(move |event| {
             let _ = tx.send(event.clone());
         });
+        panel._config_subscription = Some(EventSubscription::new(&event_hub, id));
 
         cx.spawn_in(window, async move |_this, window| {
             while let Some(event) = rx.recv().await {
@@ -163,6 +195,8 @@ impl SettingsPanel {
         })
         .detach();
 
+        panel.refresh_storage_usage(cx);
+
         panel
     }
 
@@ -188,6 +222,9 @@ impl SettingsPanel {
             AgentConfigEvent::AgentRemoved { name } => {
                 self.cached_agents.remove(name);
             }
+            AgentConfigEvent::AgentStarting { .. }
+            | AgentConfigEvent::AgentReady { .. }
+            | AgentConfigEvent::AgentFailed { .. } => {}
 
             // Model events
             AgentConfigEvent::ModelAdded { name, config } => {
@@ -228,9 +265,18 @@ impl SettingsPanel {
                 self.cached_models = config.models.clone();
                 self.cached_mcp_servers = config.mcp_servers.clone();
                 self.cached_commands = config.commands.clone();
-                self.cached_upload_dir = config.upload_dir.clone();
+                self.cached_upload_dir =
+                    AppState::global(cx).agent_config_service().map(|service| {
+                        UploadDirStatus::check(service.resolve_config_path(&config.upload_dir))
+                    });
                 self.cached_proxy = config.proxy.clone();
             }
+            AgentConfigEvent::ConfigConflict { keys } => {
+                log::warn!(
+                    "[SettingsPanel] Config file changed elsewhere, kept local values for: {}",
+                    keys.join(", ")
+                );
+            }
         }
 
         // Trigger re-render
@@ -245,6 +291,7 @@ impl SettingsPanel {
             self.general_page(&view, resettable),
             self.network_page(&view),
             self.update_page(&view, resettable),
+            self.storage_page(&view, resettable),
             self.agent_page(&view),
             self.model_page(&view),
             self.prompt_page(&view),
@@ -0,0 +1,242 @@
+use gpui::{App, Context, Entity, ParentElement as _, Styled, Window};
+use gpui_component::{
+    ActiveTheme, Disableable as _, IconName, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    label::Label,
+    notification::Notification,
+    setting::{NumberFieldOptions, SettingField, SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+use rust_i18n::t;
+
+use super::panel::SettingsPanel;
+use super::types::AppSettings;
+use crate::AppState;
+
+/// Human-readable size, e.g. "12.3 MB"
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Number input for a retention limit where 0 means unlimited
+fn limit_field(
+    max: f64,
+    get: fn(&AppSettings) -> f64,
+    set: fn(&mut AppSettings, f64),
+) -> SettingField<f64> {
+    SettingField::number_input(
+        NumberFieldOptions {
+            min: 0.0,
+            max,
+            step: 1.0,
+            ..Default::default()
+        },
+        move |cx: &App| get(AppSettings::global(cx)),
+        move |val: f64, cx: &mut App| set(AppSettings::global_mut(cx), val.round().clamp(0.0, max)),
+    )
+    .default_value(0.0)
+}
+
+impl SettingsPanel {
+    pub fn storage_page(&self, view: &Entity<Self>, resettable: bool) -> SettingPage {
+        let default_settings = AppSettings::default();
+
+        SettingPage::new(t!("settings.storage.title").to_string())
+            .resettable(resettable)
+            .default_open(false)
+            .groups(vec![
+                SettingGroup::new()
+                    .title(t!("settings.storage.group.usage").to_string())
+                    .item(SettingItem::render({
+                        let view = view.clone();
+                        move |options, _window, cx| {
+                            let panel = view.read(cx);
+                            let usage = match &panel.storage_usage {
+                                Some(usage) => t!(
+                                    "settings.storage.usage.summary",
+                                    count = usage.session_count,
+                                    size = format_bytes(usage.session_bytes),
+                                    archive = format_bytes(usage.archive_bytes),
+                                    index = format_bytes(usage.index_bytes)
+                                )
+                                .to_string(),
+                                None => t!("settings.storage.usage.loading").to_string(),
+                            };
+                            let total = panel
+                                .storage_usage
+                                .as_ref()
+                                .map(|usage| format_bytes(usage.total_bytes()))
+                                .unwrap_or_default();
+
+                            h_flex()
+                                .w_full()
+                                .justify_between()
+                                .flex_wrap()
+                                .gap_3()
+                                .child(
+                                    v_flex()
+                                        .gap_1()
+                                        .child(
+                                            Label::new(
+                                                t!("settings.storage.usage.label", total = total)
+                                                    .to_string(),
+                                            )
+                                            .text_sm(),
+                                        )
+                                        .child(
+                                            Label::new(usage)
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground),
+                                        ),
+                                )
+                                .child(
+                                    Button::new("clean-up-storage")
+                                        .icon(IconName::Delete)
+                                        .label(if panel.storage_cleaning {
+                                            t!("settings.storage.cleanup.running").to_string()
+                                        } else {
+                                            t!("settings.storage.cleanup.button").to_string()
+                                        })
+                                        .outline()
+                                        .with_size(options.size)
+                                        .disabled(panel.storage_cleaning)
+                                        .on_click({
+                                            let view = view.clone();
+                                            move |_, window, cx| {
+                                                view.update(cx, |this, cx| {
+                                                    this.clean_up_storage(window, cx);
+                                                });
+                                            }
+                                        }),
+                                )
+                        }
+                    })),
+                SettingGroup::new()
+                    .title(t!("settings.storage.group.retention").to_string())
+                    .description(t!("settings.storage.retention.description").to_string())
+                    .items(vec![
+                        SettingItem::new(
+                            t!("settings.storage.max_sessions.label").to_string(),
+                            limit_field(
+                                100_000.0,
+                                |settings| settings.retention_max_sessions,
+                                |settings, val| settings.retention_max_sessions = val,
+                            ),
+                        )
+                        .description(t!("settings.storage.max_sessions.description").to_string()),
+                        SettingItem::new(
+                            t!("settings.storage.max_age.label").to_string(),
+                            limit_field(
+                                3650.0,
+                                |settings| settings.retention_max_age_days,
+                                |settings, val| settings.retention_max_age_days = val,
+                            ),
+                        )
+                        .description(t!("settings.storage.max_age.description").to_string()),
+                        SettingItem::new(
+                            t!("settings.storage.max_messages.label").to_string(),
+                            limit_field(
+                                1_000_000.0,
+                                |settings| settings.retention_max_messages,
+                                |settings, val| settings.retention_max_messages = val,
+                            ),
+                        )
+                        .description(t!("settings.storage.max_messages.description").to_string()),
+                        SettingItem::new(
+                            t!("settings.storage.max_session_size.label").to_string(),
+                            limit_field(
+                                10_000.0,
+                                |settings| settings.retention_max_session_mb,
+                                |settings, val| settings.retention_max_session_mb = val,
+                            ),
+                        )
+                        .description(
+                            t!("settings.storage.max_session_size.description").to_string(),
+                        ),
+                        SettingItem::new(
+                            t!("settings.storage.archive.label").to_string(),
+                            SettingField::switch(
+                                |cx: &App| AppSettings::global(cx).retention_archive,
+                                |val: bool, cx: &mut App| {
+                                    AppSettings::global_mut(cx).retention_archive = val;
+                                },
+                            )
+                            .default_value(default_settings.retention_archive),
+                        )
+                        .description(t!("settings.storage.archive.description").to_string()),
+                    ]),
+            ])
+    }
+
+    /// Reload the disk usage shown on the storage page
+    pub(super) fn refresh_storage_usage(&mut self, cx: &mut Context<Self>) {
+        let Some(persistence_service) = AppState::global(cx).persistence_service().cloned() else {
+            return;
+        };
+        cx.spawn(async move |this, cx| {
+            let usage = persistence_service.storage_usage().await;
+            _ = this.update(cx, |this, cx| {
+                match usage {
+                    Ok(usage) => this.storage_usage = Some(usage),
+                    Err(e) => log::warn!("Failed to read session storage usage: {}", e),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Run a compaction pass now with the current retention settings
+    fn clean_up_storage(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let policy = AppSettings::global(cx).retention_policy();
+        let Some(compaction) = AppState::global(cx).compact_session_storage(policy) else {
+            return;
+        };
+        self.storage_cleaning = true;
+        cx.notify();
+
+        cx.spawn_in(window, async move |this, window| {
+            let result = compaction.await;
+
+            _ = this.update_in(window, |this, window, cx| {
+                struct StorageCleanedUp;
+                let note = match result {
+                    Ok(report) => Notification::success(
+                        t!(
+                            "settings.storage.cleanup.done",
+                            removed = report.sessions_removed,
+                            trimmed = report.sessions_trimmed,
+                            skipped = report.sessions_skipped,
+                            size = format_bytes(report.bytes_removed)
+                        )
+                        .to_string(),
+                    ),
+                    Err(e) => {
+                        log::error!("Failed to clean up session storage: {e:#}");
+                        Notification::error(
+                            t!("settings.storage.cleanup.failed", error = e.to_string())
+                                .to_string(),
+                        )
+                    }
+                }
+                .id::<StorageCleanedUp>();
+                window.push_notification(note, cx);
+
+                this.storage_cleaning = false;
+                this.refresh_storage_usage(cx);
+            });
+        })
+        .detach();
+    }
+}
@@ -6,9 +6,16 @@ use gpui_component::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::core::services::RetentionPolicy;
+use crate::core::updater::{UpdateCheckResult, UpdateInfo, Version};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
+    /// Follow the OS light/dark appearance instead of the manually chosen mode
     pub auto_switch_theme: bool,
+    /// Raise secondary text and border colors to a minimum contrast ratio
+    #[serde(default)]
+    pub enforce_min_contrast: bool,
     pub cli_path: SharedString,
     #[serde(default)]
     pub nodejs_path: SharedString,
@@ -18,19 +25,75 @@ pub struct AppSettings {
     pub locale: SharedString,
     pub line_height: f64,
     pub notifications_enabled: bool,
+    /// Hide the window to the system tray instead of quitting when it is closed
+    #[serde(default)]
+    pub close_to_tray: bool,
+    /// Title untitled sessions from the first user message
+    #[serde(default = "default_auto_title_sessions")]
+    pub auto_title_sessions: bool,
+    /// Agents that never trigger desktop notifications
+    #[serde(default)]
+    pub muted_agents: Vec<String>,
+    /// Capture debug and trace records in the log viewer
+    #[serde(default)]
+    pub verbose_logging: bool,
+    /// Diff lines shown in the tool call details before "Load remaining lines"
+    #[serde(default = "default_diff_max_lines")]
+    pub diff_max_lines: f64,
+    /// Persisted sessions kept, most recent first; 0 keeps all
+    #[serde(default)]
+    pub retention_max_sessions: f64,
+    /// Days a session is kept after its last message; 0 keeps sessions forever
+    #[serde(default)]
+    pub retention_max_age_days: f64,
+    /// Newest messages kept per session; 0 keeps all
+    #[serde(default)]
+    pub retention_max_messages: f64,
+    /// Newest megabytes kept per session; 0 keeps all
+    #[serde(default)]
+    pub retention_max_session_mb: f64,
+    /// Archive removed session data for a while instead of deleting it right away
+    #[serde(default = "default_retention_archive")]
+    pub retention_archive: bool,
     pub auto_update: bool,
+    /// Master switch for automatic update checks
+    #[serde(default = "default_auto_check_updates")]
+    pub auto_check_updates: bool,
     pub auto_check_on_startup: bool,
-    pub check_frequency_days: f64,
+    /// Hours between automatic checks while running; 0 checks only on startup
+    #[serde(default = "default_check_interval_hours")]
+    pub check_interval_hours: f64,
+    /// Outcome of the most recent update check
+    #[serde(default)]
+    pub last_update_check: Option<UpdateCheckRecord>,
+    /// Version the user chose to skip; newer versions are offered again
+    #[serde(default)]
+    pub skipped_update_version: Option<String>,
     pub resettable: bool,
     pub group_variant: SharedString,
     pub size: SharedString,
 }
 
+/// Time and outcome of an update check, kept across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCheckRecord {
+    /// Unix timestamp in seconds
+    pub checked_at: i64,
+    /// Release found by the check, with its notes
+    #[serde(default)]
+    pub available: Option<UpdateInfo>,
+    /// Why the check failed
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UpdateStatus {
     Idle,
     Checking,
-    Available { version: String, notes: String },
+    Available(UpdateInfo),
+    /// Downloading and installing an update
+    Installing,
     NoUpdate,
     Error(String),
 }
@@ -39,6 +102,7 @@ impl Default for AppSettings {
     fn default() -> Self {
         Self {
             auto_switch_theme: false,
+            enforce_min_contrast: false,
             cli_path: "/usr/local/bin/bash".into(),
             nodejs_path: "".into(),
             font_family: "Arial".into(),
@@ -46,9 +110,22 @@ impl Default for AppSettings {
             locale: default_locale(),
             line_height: 12.0,
             notifications_enabled: true,
+            close_to_tray: false,
+            auto_title_sessions: default_auto_title_sessions(),
+            muted_agents: Vec::new(),
+            verbose_logging: false,
+            diff_max_lines: default_diff_max_lines(),
+            retention_max_sessions: 0.0,
+            retention_max_age_days: 0.0,
+            retention_max_messages: 0.0,
+            retention_max_session_mb: 0.0,
+            retention_archive: default_retention_archive(),
             auto_update: true,
+            auto_check_updates: default_auto_check_updates(),
             auto_check_on_startup: true,
-            check_frequency_days: 7.0,
+            check_interval_hours: default_check_interval_hours(),
+            last_update_check: None,
+            skipped_update_version: None,
             resettable: true,
             group_variant: "Fill".into(),
             size: "Small".into(),
@@ -59,23 +136,27 @@ impl Default for AppSettings {
 impl Global for AppSettings {}
 
 fn default_locale() -> SharedString {
-    detect_system_locale().unwrap_or_else(|| "en".into())
+    crate::i18n::detect_system_locale()
 }
 
-fn detect_system_locale() -> Option<SharedString> {
-    let raw_locale = sys_locale::get_locale().or_else(|| std::env::var("LANG").ok())?;
-    normalize_locale(&raw_locale).map(SharedString::from)
+fn default_auto_title_sessions() -> bool {
+    true
 }
 
-fn normalize_locale(locale: &str) -> Option<&'static str> {
-    let lower = locale.to_lowercase();
-    if lower.starts_with("zh") {
-        return Some("zh-CN");
-    }
-    if lower.starts_with("en") {
-        return Some("en");
-    }
-    None
+fn default_diff_max_lines() -> f64 {
+    5000.0
+}
+
+fn default_retention_archive() -> bool {
+    true
+}
+
+fn default_auto_check_updates() -> bool {
+    true
+}
+
+fn default_check_interval_hours() -> f64 {
+    24.0
 }
 
 impl AppSettings {
@@ -86,6 +167,69 @@ impl AppSettings {
     pub fn global_mut(cx: &mut App) -> &mut AppSettings {
         cx.global_mut::<AppSettings>()
     }
+
+    /// Remember the outcome of an update check
+    ///
+    /// A failed check keeps the previously found release so the badge stays.
+    pub fn record_update_check(&mut self, result: &UpdateCheckResult) {
+        let previous = self
+            .last_update_check
+            .as_ref()
+            .and_then(|record| record.available.clone());
+        let (available, error) = match result {
+            UpdateCheckResult::NoUpdate => (None, None),
+            UpdateCheckResult::UpdateAvailable(info) => (Some(info.clone()), None),
+            UpdateCheckResult::Error(err) => (previous, Some(err.clone())),
+        };
+        self.last_update_check = Some(UpdateCheckRecord {
+            checked_at: chrono::Utc::now().timestamp(),
+            available,
+            error,
+        });
+    }
+
+    /// Release found by the last check that is newer than the running one
+    /// and was not skipped
+    pub fn available_update(&self) -> Option<&UpdateInfo> {
+        let info = self.last_update_check.as_ref()?.available.as_ref()?;
+        if self.is_update_skipped(&info.version) {
+            return None;
+        }
+        Version::parse(&info.version)
+            .is_ok_and(|latest| latest.is_newer_than(&Version::current()))
+            .then_some(info)
+    }
+
+    /// Whether the user chose to skip `version`
+    pub fn is_update_skipped(&self, version: &str) -> bool {
+        self.skipped_update_version.as_deref() == Some(version)
+    }
+
+    /// Retention limits for persisted sessions; zero settings mean unlimited
+    pub fn retention_policy(&self) -> RetentionPolicy {
+        let limit = |value: f64| (value >= 1.0).then_some(value.round());
+        RetentionPolicy {
+            max_sessions: limit(self.retention_max_sessions).map(|max| max as usize),
+            max_age: limit(self.retention_max_age_days)
+                .map(|days| std::time::Duration::from_secs(days as u64 * 24 * 60 * 60)),
+            max_messages_per_session: limit(self.retention_max_messages).map(|max| max as usize),
+            max_bytes_per_session: limit(self.retention_max_session_mb)
+                .map(|mb| mb as u64 * 1024 * 1024),
+            archive: self.retention_archive,
+        }
+    }
+
+    /// Whether an automatic check is due now
+    pub fn update_check_due(&self) -> bool {
+        if !self.auto_check_updates || self.check_interval_hours <= 0.0 {
+            return false;
+        }
+        let Some(record) = self.last_update_check.as_ref() else {
+            return true;
+        };
+        let elapsed_secs = chrono::Utc::now().timestamp() - record.checked_at;
+        elapsed_secs as f64 >= self.check_interval_hours * 3600.0
+    }
 }
 
 pub struct OpenURLSettingField {
@@ -120,3 +264,62 @@ impl SettingFieldElement for OpenURLSettingField {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(version: &str) -> UpdateInfo {
+        UpdateInfo {
+            version: version.into(),
+            download_url: String::new(),
+            release_notes: String::new(),
+            published_at: String::new(),
+            file_size: None,
+            sha256: None,
+        }
+    }
+
+    #[test]
+    fn test_update_check_schedule() {
+        let mut settings = AppSettings::default();
+        assert!(settings.update_check_due());
+
+        settings.record_update_check(&UpdateCheckResult::NoUpdate);
+        assert!(!settings.update_check_due());
+        assert!(settings.available_update().is_none());
+
+        settings.last_update_check.as_mut().unwrap().checked_at -= 25 * 3600;
+        assert!(settings.update_check_due());
+
+        settings.check_interval_hours = 0.0;
+        assert!(!settings.update_check_due());
+    }
+
+    #[test]
+    fn test_failed_check_keeps_available_version() {
+        let mut settings = AppSettings::default();
+        settings.record_update_check(&UpdateCheckResult::UpdateAvailable(release("999.0.0")));
+        settings.record_update_check(&UpdateCheckResult::Error("offline".into()));
+
+        let record = settings.last_update_check.as_ref().unwrap();
+        assert_eq!(record.error.as_deref(), Some("offline"));
+        assert_eq!(
+            settings
+                .available_update()
+                .map(|info| info.version.as_str()),
+            Some("999.0.0")
+        );
+    }
+
+    #[test]
+    fn test_skipped_version_is_hidden_until_a_newer_one() {
+        let mut settings = AppSettings::default();
+        settings.record_update_check(&UpdateCheckResult::UpdateAvailable(release("999.0.0")));
+        settings.skipped_update_version = Some("999.0.0".into());
+        assert!(settings.available_update().is_none());
+
+        settings.record_update_check(&UpdateCheckResult::UpdateAvailable(release("999.1.0")));
+        assert!(settings.available_update().is_some());
+    }
+}
@@ -1,17 +1,31 @@
-use gpui::{App, Context, Entity, ParentElement as _, Styled, Window};
+use gpui::{
+    App, Context, Entity, InteractiveElement as _, ParentElement as _,
+    StatefulInteractiveElement as _, Styled, Window, div, prelude::FluentBuilder as _, px,
+};
 use gpui_component::{
-    ActiveTheme, Icon, IconName, Sizable,
-    button::Button,
+    ActiveTheme, Icon, IconName, Sizable, WindowExt as _,
+    button::{Button, ButtonVariant, ButtonVariants as _},
+    dialog::DialogButtonProps,
     h_flex,
     label::Label,
+    notification::Notification,
     setting::{NumberFieldOptions, SettingField, SettingGroup, SettingItem, SettingPage},
+    text::TextView,
     v_flex,
 };
 use rust_i18n::t;
 
 use super::panel::SettingsPanel;
 use super::types::{AppSettings, UpdateStatus};
-use crate::core::updater::{UpdateCheckResult, Version};
+use crate::{
+    AppState, Quit,
+    core::{
+        log_capture::{self, LogRecord},
+        nodejs::NodeJsChecker,
+        updater::{InstallOutcome, UpdateCheckResult, UpdateInfo, UpdateManager, Version},
+    },
+    utils::diagnostics::Diagnostics,
+};
 
 fn os_display_name() -> String {
     match std::env::consts::OS {
@@ -78,6 +92,115 @@ fn os_version() -> String {
     }
 }
 
+/// Human readable download size
+fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Version, date, size and release notes of an available update
+fn render_release(info: &UpdateInfo, view: Entity<SettingsPanel>, cx: &App) -> gpui::Div {
+    let published = chrono::DateTime::parse_from_rfc3339(&info.published_at)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string()
+        })
+        .ok();
+    let details = published
+        .into_iter()
+        .chain(info.file_size.map(format_file_size))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let notes = info.release_notes.trim();
+    let version = info.version.clone();
+
+    v_flex()
+        .gap_2()
+        .w_full()
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(Icon::new(IconName::ArrowDown).size_4())
+                .child(
+                    Label::new(
+                        t!("settings.update.status.available", version = info.version).to_string(),
+                    )
+                    .text_xs()
+                    .text_color(cx.theme().accent_foreground),
+                )
+                .when(!details.is_empty(), |this| {
+                    this.child(
+                        Label::new(details)
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("skip-update-version")
+                        .label(t!("settings.update.skip_version").to_string())
+                        .ghost()
+                        .xsmall()
+                        .on_click({
+                            let view = view.clone();
+                            move |_, _, cx| {
+                                view.update(cx, |this, cx| {
+                                    this.skip_update_version(version.clone(), cx);
+                                });
+                            }
+                        }),
+                )
+                .child(
+                    Button::new("install-update")
+                        .icon(IconName::ArrowDown)
+                        .label(t!("settings.update.install.button").to_string())
+                        .primary()
+                        .xsmall()
+                        .on_click({
+                            let info = info.clone();
+                            move |_, window, cx| {
+                                view.update(cx, |this, cx| {
+                                    this.install_update(info.clone(), window, cx);
+                                });
+                            }
+                        }),
+                ),
+        )
+        .when(!notes.is_empty(), |this| {
+            // GitHub release bodies are markdown; some feeds send HTML instead
+            let notes_view = if notes.starts_with('<') {
+                TextView::html("update-release-notes", notes.to_string())
+            } else {
+                TextView::markdown("update-release-notes", notes.to_string())
+            };
+            this.child(
+                div()
+                    .id("update-release-notes-scroll")
+                    .w_full()
+                    .max_h(px(320.))
+                    .overflow_y_scroll()
+                    .p_3()
+                    .rounded(cx.theme().radius)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .text_xs()
+                    .child(notes_view.selectable(true)),
+            )
+        })
+}
+
 impl SettingsPanel {
     pub fn update_page(&self, view: &Entity<Self>, resettable: bool) -> SettingPage {
         let default_settings = AppSettings::default();
@@ -87,10 +210,155 @@ impl SettingsPanel {
             .groups(vec![
                 SettingGroup::new()
                     .title(t!("settings.update.group.version").to_string())
+                    .items(
+                        vec![
+                            SettingItem::render({
+                                let current_version = Version::current().to_string();
+                                let update_status = self.update_status.clone();
+                                let view = view.clone();
+                                move |_options, _window, cx| {
+                                    v_flex()
+                                        .gap_2()
+                                        .w_full()
+                                        .child(
+                                            h_flex()
+                                                .gap_2()
+                                                .items_center()
+                                                .child(
+                                                    Label::new(
+                                                        t!("settings.update.current_version.label")
+                                                            .to_string(),
+                                                    )
+                                                    .text_sm(),
+                                                )
+                                                .child(
+                                                    Label::new(&current_version)
+                                                        .text_sm()
+                                                        .text_color(cx.theme().muted_foreground),
+                                                ),
+                                        )
+                                        .when_some(
+                                            AppSettings::global(cx).last_update_check.as_ref(),
+                                            |this, record| {
+                                                let checked_at = chrono::DateTime::from_timestamp(
+                                                    record.checked_at,
+                                                    0,
+                                                )
+                                                .map(|time| {
+                                                    time.with_timezone(&chrono::Local)
+                                                        .format("%Y-%m-%d %H:%M")
+                                                        .to_string()
+                                                })
+                                                .unwrap_or_default();
+                                                this.child(
+                                                    Label::new(
+                                                        t!(
+                                                            "settings.update.last_checked",
+                                                            time = checked_at
+                                                        )
+                                                        .to_string(),
+                                                    )
+                                                    .text_xs()
+                                                    .text_color(cx.theme().muted_foreground),
+                                                )
+                                            },
+                                        )
+                                        .child(match &update_status {
+                                            UpdateStatus::Idle | UpdateStatus::NoUpdate => h_flex()
+                                                .gap_2()
+                                                .items_center()
+                                                .child(Icon::new(IconName::Check).size_4())
+                                                .child(
+                                                    Label::new(
+                                                        t!("settings.update.status.up_to_date")
+                                                            .to_string(),
+                                                    )
+                                                    .text_xs()
+                                                    .text_color(cx.theme().success_foreground),
+                                                ),
+                                            UpdateStatus::Checking => h_flex()
+                                                .gap_2()
+                                                .items_center()
+                                                .child(Icon::new(IconName::LoaderCircle).size_4())
+                                                .child(
+                                                    Label::new(
+                                                        t!("settings.update.status.checking")
+                                                            .to_string(),
+                                                    )
+                                                    .text_xs()
+                                                    .text_color(cx.theme().muted_foreground),
+                                                ),
+                                            UpdateStatus::Available(info) => {
+                                                render_release(info, view.clone(), cx)
+                                            }
+                                            UpdateStatus::Installing => h_flex()
+                                                .gap_2()
+                                                .items_center()
+                                                .child(Icon::new(IconName::LoaderCircle).size_4())
+                                                .child(
+                                                    Label::new(
+                                                        t!("settings.update.status.installing")
+                                                            .to_string(),
+                                                    )
+                                                    .text_xs()
+                                                    .text_color(cx.theme().muted_foreground),
+                                                ),
+                                            UpdateStatus::Error(err) => {
+                                                h_flex()
+                                                    .gap_2()
+                                                    .items_center()
+                                                    .child(Icon::new(IconName::CircleX).size_4())
+                                                    .child(
+                                                        Label::new(
+                                                            t!(
+                                                                "settings.update.status.error",
+                                                                error = err
+                                                            )
+                                                            .to_string(),
+                                                        )
+                                                        .text_xs()
+                                                        .text_color(
+                                                            cx.theme().colors.danger_foreground,
+                                                        ),
+                                                    )
+                                            }
+                                        })
+                                }
+                            }),
+                            SettingItem::new(
+                                t!("settings.update.check.label").to_string(),
+                                SettingField::render({
+                                    let view = view.clone();
+                                    move |options, _window, _cx| {
+                                        Button::new("check-updates")
+                                            .icon(IconName::LoaderCircle)
+                                            .label(t!("settings.update.check.button").to_string())
+                                            .outline()
+                                            .with_size(options.size)
+                                            .on_click({
+                                                let view = view.clone();
+                                                move |_, window, cx| {
+                                                    view.update(cx, |this, cx| {
+                                                        this.check_for_updates(window, cx);
+                                                    });
+                                                }
+                                            })
+                                    }
+                                }),
+                            )
+                            .description(t!("settings.update.check.description").to_string()),
+                        ]
+                        .into_iter()
+                        .chain(self.rollback_item(view))
+                        .collect::<Vec<_>>(),
+                    ),
+                // System Information
+                SettingGroup::new()
+                    .title(t!("settings.update.group.system").to_string())
                     .items(vec![
                         SettingItem::render({
-                            let current_version = Version::current().to_string();
-                            let update_status = self.update_status.clone();
+                            let os = format!("{} {}", os_display_name(), os_version());
+                            let arch = arch_display_name();
                             move |_options, _window, cx| {
                                 v_flex()
                                     .gap_2()
@@ -99,162 +367,78 @@ impl SettingsPanel {
                                         h_flex()
                                             .gap_2()
                                             .items_center()
+                                            .child(Icon::new(IconName::Settings).size_4())
                                             .child(
                                                 Label::new(
-                                                    t!("settings.update.current_version.label")
-                                                        .to_string(),
+                                                    t!("settings.update.system.os").to_string(),
                                                 )
                                                 .text_sm(),
                                             )
                                             .child(
-                                                Label::new(&current_version)
+                                                Label::new(&os)
                                                     .text_sm()
                                                     .text_color(cx.theme().muted_foreground),
                                             ),
                                     )
-                                    .child(match &update_status {
-                                        UpdateStatus::Idle | UpdateStatus::NoUpdate => h_flex()
-                                            .gap_2()
-                                            .items_center()
-                                            .child(Icon::new(IconName::Check).size_4())
-                                            .child(
-                                                Label::new(
-                                                    t!("settings.update.status.up_to_date")
-                                                        .to_string(),
-                                                )
-                                                .text_xs()
-                                                .text_color(cx.theme().success_foreground),
-                                            ),
-                                        UpdateStatus::Checking => h_flex()
+                                    .child(
+                                        h_flex()
                                             .gap_2()
                                             .items_center()
-                                            .child(Icon::new(IconName::LoaderCircle).size_4())
+                                            .child(Icon::new(IconName::Folder).size_4())
                                             .child(
                                                 Label::new(
-                                                    t!("settings.update.status.checking")
-                                                        .to_string(),
-                                                )
-                                                .text_xs()
-                                                .text_color(cx.theme().muted_foreground),
-                                            ),
-                                        UpdateStatus::Available { version, notes } => {
-                                            let notes_elem = if notes.is_empty() {
-                                                None
-                                            } else {
-                                                Some(
-                                                    Label::new(notes)
-                                                        .text_xs()
-                                                        .text_color(cx.theme().muted_foreground),
+                                                    t!("settings.update.system.arch").to_string(),
                                                 )
-                                            };
-                                            v_flex()
-                                            .gap_2()
-                                            .w_full()
-                                            .child(
-                                                h_flex()
-                                                    .gap_2()
-                                                    .items_center()
-                                                    .child(Icon::new(IconName::ArrowDown).size_4())
-                                                    .child(
-                                                        Label::new(
-                                                            t!(
-                                                                "settings.update.status.available",
-                                                                version = version
-                                                            )
-                                                            .to_string(),
-                                                        )
-                                                        .text_xs()
-                                                        .text_color(cx.theme().accent_foreground),
-                                                    ),
+                                                .text_sm(),
                                             )
-                                            .children(notes_elem)
-                                        }
-                                        UpdateStatus::Error(err) => h_flex()
-                                            .gap_2()
-                                            .items_center()
-                                            .child(Icon::new(IconName::CircleX).size_4())
                                             .child(
-                                                Label::new(
-                                                    t!("settings.update.status.error", error = err)
-                                                        .to_string(),
-                                                )
-                                                .text_xs()
-                                                .text_color(cx.theme().colors.danger_foreground),
+                                                Label::new(&arch)
+                                                    .text_sm()
+                                                    .text_color(cx.theme().muted_foreground),
                                             ),
-                                    })
+                                    )
                             }
                         }),
                         SettingItem::new(
-                            t!("settings.update.check.label").to_string(),
+                            t!("settings.update.diagnostics.label").to_string(),
                             SettingField::render({
                                 let view = view.clone();
                                 move |options, _window, _cx| {
-                                    Button::new("check-updates")
-                                        .icon(IconName::LoaderCircle)
-                                        .label(t!("settings.update.check.button").to_string())
+                                    Button::new("generate-diagnostics")
+                                        .icon(IconName::ArrowDown)
+                                        .label(t!("settings.update.diagnostics.button").to_string())
                                         .outline()
                                         .with_size(options.size)
                                         .on_click({
                                             let view = view.clone();
                                             move |_, window, cx| {
                                                 view.update(cx, |this, cx| {
-                                                    this.check_for_updates(window, cx);
+                                                    this.generate_diagnostics(window, cx);
                                                 });
                                             }
                                         })
                                 }
                             }),
                         )
-                        .description(t!("settings.update.check.description").to_string()),
+                        .description(t!("settings.update.diagnostics.description").to_string()),
                     ]),
-                // System Information
-                SettingGroup::new()
-                    .title(t!("settings.update.group.system").to_string())
-                    .items(vec![SettingItem::render({
-                        let os = format!("{} {}", os_display_name(), os_version());
-                        let arch = arch_display_name();
-                        move |_options, _window, cx| {
-                            v_flex()
-                                .gap_2()
-                                .w_full()
-                                .child(
-                                    h_flex()
-                                        .gap_2()
-                                        .items_center()
-                                        .child(Icon::new(IconName::Settings).size_4())
-                                        .child(
-                                            Label::new(t!("settings.update.system.os").to_string())
-                                                .text_sm(),
-                                        )
-                                        .child(
-                                            Label::new(&os)
-                                                .text_sm()
-                                                .text_color(cx.theme().muted_foreground),
-                                        ),
-                                )
-                                .child(
-                                    h_flex()
-                                        .gap_2()
-                                        .items_center()
-                                        .child(Icon::new(IconName::Folder).size_4())
-                                        .child(
-                                            Label::new(
-                                                t!("settings.update.system.arch").to_string(),
-                                            )
-                                            .text_sm(),
-                                        )
-                                        .child(
-                                            Label::new(&arch)
-                                                .text_sm()
-                                                .text_color(cx.theme().muted_foreground),
-                                        ),
-                                )
-                        }
-                    })]),
                 // Update Settings
                 SettingGroup::new()
                     .title(t!("settings.update.group.settings").to_string())
                     .items(vec![
+                        SettingItem::new(
+                            t!("settings.update.auto_check_master.label").to_string(),
+                            SettingField::switch(
+                                |cx: &App| AppSettings::global(cx).auto_check_updates,
+                                |val: bool, cx: &mut App| {
+                                    AppSettings::global_mut(cx).auto_check_updates = val;
+                                },
+                            )
+                            .default_value(default_settings.auto_check_updates),
+                        )
+                        .description(
+                            t!("settings.update.auto_check_master.description").to_string(),
+                        ),
                         SettingItem::new(
                             t!("settings.update.auto_check.label").to_string(),
                             SettingField::switch(
@@ -292,17 +476,17 @@ impl SettingsPanel {
                             t!("settings.update.frequency.label").to_string(),
                             SettingField::number_input(
                                 NumberFieldOptions {
-                                    min: 1.0,
-                                    max: 30.0,
+                                    min: 0.0,
+                                    max: 168.0,
                                     step: 1.0,
                                     ..Default::default()
                                 },
-                                |cx: &App| AppSettings::global(cx).check_frequency_days,
+                                |cx: &App| AppSettings::global(cx).check_interval_hours,
                                 |val: f64, cx: &mut App| {
-                                    AppSettings::global_mut(cx).check_frequency_days = val;
+                                    AppSettings::global_mut(cx).check_interval_hours = val;
                                 },
                             )
-                            .default_value(default_settings.check_frequency_days),
+                            .default_value(default_settings.check_interval_hours),
                         )
                         .description(t!("settings.update.frequency.description").to_string()),
                     ]),
@@ -313,20 +497,21 @@ impl SettingsPanel {
         self.update_status = UpdateStatus::Checking;
         cx.notify();
 
-        let update_manager = self.update_manager.clone();
+        let update_manager = self
+            .update_manager
+            .clone()
+            .with_proxy(AppState::global(cx).proxy_config());
         let entity = cx.entity().downgrade();
 
         cx.spawn(async move |_this, cx| {
             let result = update_manager.check_for_updates().await;
 
             let _ = cx.update(|cx| {
+                AppSettings::global_mut(cx).record_update_check(&result);
                 let _ = entity.update(cx, |this, cx| {
                     this.update_status = match result {
                         UpdateCheckResult::NoUpdate => UpdateStatus::NoUpdate,
-                        UpdateCheckResult::UpdateAvailable(info) => UpdateStatus::Available {
-                            version: info.version,
-                            notes: info.release_notes,
-                        },
+                        UpdateCheckResult::UpdateAvailable(info) => UpdateStatus::Available(info),
                         UpdateCheckResult::Error(err) => UpdateStatus::Error(err),
                     };
                     cx.notify();
@@ -335,4 +520,292 @@ impl SettingsPanel {
         })
         .detach();
     }
+
+    /// Download and install `info`, then relaunch into the new version
+    ///
+    /// When the app can't replace itself the downloaded file is revealed so
+    /// it can be installed by hand.
+    pub fn install_update(
+        &mut self,
+        info: UpdateInfo,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if info.download_url.is_empty() {
+            self.update_status =
+                UpdateStatus::Error(t!("settings.update.install.no_download").to_string());
+            cx.notify();
+            return;
+        }
+
+        self.update_status = UpdateStatus::Installing;
+        cx.notify();
+
+        let update_manager = self
+            .update_manager
+            .clone()
+            .with_proxy(AppState::global(cx).proxy_config());
+        cx.spawn_in(window, async move |this, window| {
+            let result = match update_manager.download_update(&info, None).await {
+                Ok(path) => {
+                    let manager = update_manager.clone();
+                    let release = info.clone();
+                    smol::unblock(move || manager.install_update(&path, &release)).await
+                }
+                Err(e) => Err(e),
+            };
+
+            _ = this.update_in(window, |this, window, cx| {
+                match result {
+                    Ok(InstallOutcome::Installed { app }) => match UpdateManager::relaunch(&app) {
+                        Ok(()) => window.dispatch_action(Box::new(Quit), cx),
+                        Err(e) => {
+                            log::error!("Failed to relaunch after update: {}", e);
+                            this.update_status = UpdateStatus::Error(
+                                t!("settings.update.install.restart_manually").to_string(),
+                            );
+                        }
+                    },
+                    Ok(InstallOutcome::InstallerLaunched) => {
+                        window.dispatch_action(Box::new(Quit), cx);
+                    }
+                    Ok(InstallOutcome::ManualInstallRequired { path, reason }) => {
+                        log::warn!("Update needs a manual install: {}", reason);
+                        cx.reveal_path(&path);
+                        struct ManualInstall;
+                        window.push_notification(
+                            Notification::warning(
+                                t!("settings.update.install.manual", reason = reason).to_string(),
+                            )
+                            .id::<ManualInstall>(),
+                            cx,
+                        );
+                        this.update_status = UpdateStatus::Available(info);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to install update: {}", e);
+                        this.update_status = UpdateStatus::Error(e.to_string());
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Setting row offering to restore the version replaced by the last update
+    fn rollback_item(&self, view: &Entity<Self>) -> Option<SettingItem> {
+        let version = self.rollback_version.clone()?;
+        let view = view.clone();
+        Some(
+            SettingItem::new(
+                t!("settings.update.rollback.label").to_string(),
+                SettingField::render(move |options, _window, _cx| {
+                    Button::new("rollback-update")
+                        .icon(IconName::Undo2)
+                        .label(t!("settings.update.rollback.button", version = version).to_string())
+                        .outline()
+                        .with_size(options.size)
+                        .on_click({
+                            let view = view.clone();
+                            move |_, window, cx| {
+                                view.update(cx, |this, cx| this.confirm_rollback(window, cx));
+                            }
+                        })
+                }),
+            )
+            .description(t!("settings.update.rollback.description").to_string()),
+        )
+    }
+
+    /// Warn about data written by the newer version, then roll back and relaunch
+    fn confirm_rollback(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(version) = self.rollback_version.clone() else {
+            return;
+        };
+        let view = cx.entity();
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            let view = view.clone();
+            dialog
+                .title(t!("settings.update.rollback.dialog.title", version = version).to_string())
+                .confirm()
+                .button_props(
+                    DialogButtonProps::default()
+                        .ok_text(t!("settings.update.rollback.dialog.ok").to_string())
+                        .ok_variant(ButtonVariant::Danger)
+                        .cancel_text(t!("settings.update.rollback.dialog.cancel").to_string()),
+                )
+                .on_ok(move |_, window, cx| {
+                    view.update(cx, |this, cx| this.rollback(window, cx));
+                    true
+                })
+                .child(v_flex().w_full().gap_2().p_4().child(
+                    Label::new(t!("settings.update.rollback.dialog.warning").to_string()).text_sm(),
+                ))
+        });
+    }
+
+    fn rollback(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_status = UpdateStatus::Installing;
+        cx.notify();
+
+        cx.spawn_in(window, async move |this, window| {
+            let result = smol::unblock(UpdateManager::rollback).await;
+
+            _ = this.update_in(window, |this, window, cx| {
+                match result.and_then(|app| UpdateManager::relaunch(&app)) {
+                    Ok(()) => window.dispatch_action(Box::new(Quit), cx),
+                    Err(e) => {
+                        log::error!("Failed to roll back: {}", e);
+                        this.update_status = UpdateStatus::Error(e.to_string());
+                        this.rollback_version = UpdateManager::rollback_version();
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Stop offering `version` until a newer release appears
+    fn skip_update_version(&mut self, version: String, cx: &mut Context<Self>) {
+        log::info!("Skipping update version {}", version);
+        AppSettings::global_mut(cx).skipped_update_version = Some(version);
+        self.update_status = UpdateStatus::Idle;
+        cx.notify();
+    }
+
+    /// Collect system info, config and logs into a zip chosen by the user
+    ///
+    /// Secrets are redacted before anything is written.
+    pub fn generate_diagnostics(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let gpu = window
+            .gpu_specs()
+            .map(|specs| {
+                format!(
+                    "{} ({} {}){}",
+                    specs.device_name,
+                    specs.driver_name,
+                    specs.driver_info,
+                    if specs.is_software_emulated {
+                        ", software emulated"
+                    } else {
+                        ""
+                    }
+                )
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        let system = format!(
+            "App version: {}\nOS: {} {}\nArchitecture: {}\nLocale: {}\nGPU: {}\n",
+            Version::current(),
+            os_display_name(),
+            os_version(),
+            arch_display_name(),
+            rust_i18n::locale().to_string(),
+            gpu,
+        );
+
+        let nodejs_path = AppSettings::global(cx).nodejs_path.trim().to_string();
+        let custom_nodejs_path = (!nodejs_path.is_empty()).then(|| nodejs_path.into());
+
+        let app_state = AppState::global(cx);
+        let (proxy, config_path) = match app_state.agent_config_service() {
+            Some(service) => (service.proxy_config(), Some(service.config_path().clone())),
+            None => (self.cached_proxy.clone(), None),
+        };
+        let mut agent_logs: Vec<(String, String)> = match app_state.agent_manager() {
+            Some(manager) => self
+                .cached_agents
+                .keys()
+                .map(|name| (name.clone(), manager.agent_logs(name)))
+                .collect(),
+            None => Vec::new(),
+        };
+        agent_logs.sort();
+        let app_logs = log_capture::records()
+            .iter()
+            .map(LogRecord::format)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let file_name = format!(
+            "agentx-diagnostics-{}.zip",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+
+        cx.spawn_in(window, async move |_this, window| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .set_title(t!("settings.update.diagnostics.dialog_title").to_string())
+                .set_file_name(file_name)
+                .save_file()
+                .await
+            else {
+                return;
+            };
+
+            let path = file.path().to_path_buf();
+            let result = smol::unblock({
+                let path = path.clone();
+                move || -> anyhow::Result<usize> {
+                    let mut diagnostics = Diagnostics::new();
+                    diagnostics.add_text("system.txt", &system);
+
+                    let nodejs = match NodeJsChecker::new(custom_nodejs_path)
+                        .check_nodejs_available_blocking()
+                    {
+                        Ok(result) => format!("{result:#?}\n"),
+                        Err(e) => format!("Node.js check failed: {e:#}\n"),
+                    };
+                    diagnostics.add_text("nodejs.txt", &nodejs);
+                    diagnostics.add_json("proxy.json", serde_json::to_value(&proxy)?);
+
+                    if let Some(config_path) = config_path {
+                        match std::fs::read_to_string(&config_path)
+                            .map_err(anyhow::Error::from)
+                            .and_then(|text| Ok(serde_json::from_str(&text)?))
+                        {
+                            Ok(config) => diagnostics.add_json("config.json", config),
+                            Err(e) => diagnostics.add_text(
+                                "config.txt",
+                                &format!("Failed to read {}: {e:#}\n", config_path.display()),
+                            ),
+                        }
+                    }
+
+                    diagnostics.add_text("logs/app.log", &app_logs);
+                    for (name, logs) in &agent_logs {
+                        diagnostics.add_text(format!("logs/agents/{name}.log"), logs);
+                    }
+
+                    diagnostics.write_zip(&path)?;
+                    Ok(diagnostics.redactions())
+                }
+            })
+            .await;
+
+            _ = window.update(|window, cx| {
+                struct DiagnosticsSaved;
+                let note = match result {
+                    Ok(count) => Notification::success(
+                        t!(
+                            "settings.update.diagnostics.saved",
+                            path = path.display(),
+                            count = count
+                        )
+                        .to_string(),
+                    ),
+                    Err(e) => {
+                        log::error!("Failed to generate diagnostics: {e:#}");
+                        Notification::error(
+                            t!("settings.update.diagnostics.failed", error = e.to_string())
+                                .to_string(),
+                        )
+                    }
+                }
+                .id::<DiagnosticsSaved>();
+                window.push_notification(note, cx);
+            });
+        })
+        .detach();
+    }
 }
@@ -6,16 +6,18 @@
 //! - Tree view (by workspace) and timeline view (by date)
 
 use gpui::{
-    App, AppContext, ClickEvent, Context, Entity, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, MouseButton, ParentElement, Pixels, Render, SharedString,
+    App, AppContext, ClickEvent, Context, Entity, FocusHandle, Focusable, Global,
+    InteractiveElement, IntoElement, MouseButton, ParentElement, Pixels, Render, SharedString,
     StatefulInteractiveElement, Styled, Subscription, Window, div, prelude::FluentBuilder, px,
 };
 use gpui_component::{
-    ActiveTheme, Icon, IconName, Selectable, Sizable, StyledExt,
-    button::{Button, ButtonGroup, ButtonVariants},
+    ActiveTheme, Icon, IconName, Selectable, Sizable, StyledExt, WindowExt as _,
+    button::{Button, ButtonGroup, ButtonVariant, ButtonVariants},
+    dialog::DialogButtonProps,
     dock::DockPlacement,
     h_flex,
     input::{Input, InputState},
+    label::Label,
     menu::{ContextMenuExt, DropdownMenu, PopupMenuItem},
     scroll::ScrollableElement as _,
     v_flex,
@@ -27,10 +29,13 @@ use std::rc::Rc;
 use std::time::Duration;
 
 use crate::core::services::WorkspaceService;
-use crate::core::{event_bus::WorkspaceUpdateEvent, services::SessionStatus};
+use crate::core::{
+    event_bus::{EventSubscription, WorkspaceUpdateEvent},
+    services::SessionStatus,
+};
 use crate::panels::dock_panel::DockPanel;
 use crate::schemas::workspace_schema::WorkspaceTask;
-use crate::{AppState, OpenSessionManager, PanelAction, StatusIndicator, utils};
+use crate::{AppState, OpenSessionManager, PanelAction, StartTaskSession, StatusIndicator, utils};
 
 // ============================================================================
 // Constants - Layout spacing
@@ -56,6 +61,101 @@ pub struct WorkspaceGroup {
 pub enum ViewMode {
     Tree,
     Timeline,
+    Board,
+}
+
+/// Which workspaces the task list shows
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum WorkspaceScope {
+    #[default]
+    All,
+    /// Only the active workspace
+    Active,
+    Workspace(String),
+}
+
+/// Task list filters, combined with AND semantics
+///
+/// Kept as a global for the rest of the app session so the filter survives
+/// closing and reopening the panel.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TaskFilter {
+    workspace: WorkspaceScope,
+    agent_name: Option<String>,
+    /// Free-text filter on task name, agent, mode and last message
+    query: String,
+}
+
+impl Global for TaskFilter {}
+
+impl TaskFilter {
+    fn is_active(&self) -> bool {
+        *self != TaskFilter::default()
+    }
+
+    fn matches_task(&self, task: &WorkspaceTask) -> bool {
+        if self
+            .agent_name
+            .as_ref()
+            .is_some_and(|agent| &task.agent_name != agent)
+        {
+            return false;
+        }
+
+        let query = self.query.to_lowercase();
+        query.is_empty()
+            || task.name.to_lowercase().contains(&query)
+            || task.agent_name.to_lowercase().contains(&query)
+            || task.mode.to_lowercase().contains(&query)
+            || task
+                .last_message
+                .as_ref()
+                .is_some_and(|msg| msg.to_lowercase().contains(&query))
+    }
+}
+
+/// Board view columns, in display order
+const BOARD_COLUMNS: [SessionStatus; 4] = [
+    SessionStatus::Pending,
+    SessionStatus::InProgress,
+    SessionStatus::Completed,
+    SessionStatus::Failed,
+];
+
+/// Board column a task status is shown in
+///
+/// Sessions that are open or awaiting input count as in progress, and closed
+/// sessions as completed.
+fn board_column(status: &SessionStatus) -> SessionStatus {
+    match status {
+        SessionStatus::Pending => SessionStatus::Pending,
+        SessionStatus::Active | SessionStatus::Idle | SessionStatus::InProgress => {
+            SessionStatus::InProgress
+        }
+        SessionStatus::Completed | SessionStatus::Closed => SessionStatus::Completed,
+        SessionStatus::Failed => SessionStatus::Failed,
+    }
+}
+
+/// Drag payload for moving a task between board columns
+#[derive(Clone)]
+struct DraggedTask {
+    task_id: String,
+    name: String,
+}
+
+impl Render for DraggedTask {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_3()
+            .py_1()
+            .rounded(px(6.))
+            .bg(cx.theme().secondary)
+            .border_1()
+            .border_color(cx.theme().border)
+            .text_sm()
+            .child(self.name.clone())
+    }
 }
 
 // ============================================================================
@@ -69,8 +169,14 @@ pub struct TaskPanel {
     context_menu_task_id: Option<String>,
     view_mode: ViewMode,
     _subscriptions: Vec<Subscription>,
+    /// Workspace bus subscription, removed from the hub with the panel
+    _workspace_subscription: Option<EventSubscription>,
     /// Search input state
     search_input: Entity<InputState>,
+    /// Active filters (the query mirrors the search input)
+    filter: TaskFilter,
+    /// Active workspace ID, used by the "active workspace" filter
+    active_workspace_id: Option<String>,
     load_generation: u64,
     pending_click_generation: u64,
     last_click_task_id: Option<String>,
@@ -117,13 +223,25 @@ impl TaskPanel {
     }
 
     fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let search_input = cx.new(|cx| InputState::new(window, cx));
+        // Restore the filter used last time the panel was open
+        let filter = cx.try_global::<TaskFilter>().cloned().unwrap_or_default();
+
+        let search_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx);
+            state.set_value(filter.query.clone(), window, cx);
+            state
+        });
 
-        // Subscribe to search input changes to trigger re-render
+        // Keep the query filter in sync with the search input
         let search_subscription = cx.subscribe(
             &search_input,
-            |_this, _input, _event: &gpui_component::input::InputEvent, cx| {
-                cx.notify(); // Trigger re-render when search input changes
+            |this, input, _event: &gpui_component::input::InputEvent, cx| {
+                let query = input.read(cx).text().to_string();
+                if this.filter.query != query {
+                    let mut filter = this.filter.clone();
+                    filter.query = query;
+                    this.set_filter(filter, cx);
+                }
             },
         );
 
@@ -134,7 +252,10 @@ impl TaskPanel {
             context_menu_task_id: None,
             view_mode: ViewMode::Tree,
             _subscriptions: vec![search_subscription],
+            _workspace_subscription: None,
             search_input,
+            filter,
+            active_workspace_id: None,
             load_generation: 0,
             pending_click_generation: 0,
             last_click_task_id: None,
@@ -173,6 +294,7 @@ impl TaskPanel {
         let entity_clone = entity.clone();
         cx.spawn(async move |cx| {
             let config = workspace_service.get_config().await;
+            let active_workspace_id = config.active_workspace_id;
             let workspaces_list = config.workspaces;
             let tasks = config.tasks;
 
@@ -213,6 +335,7 @@ impl TaskPanel {
                             }
                         })
                         .collect();
+                    this.active_workspace_id = active_workspace_id;
 
                     this.ensure_selected_task_valid();
                     this.is_loading = false;
@@ -411,9 +534,13 @@ impl TaskPanel {
         let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
 
         // Subscribe to workspace bus
-        event_hub.subscribe_workspace_updates(move |event| {
+        let id = event_hub.subscribe_workspace_updates(move |event| {
             let _ = tx.send(event.clone());
         });
+        let subscription = EventSubscription::new(&event_hub, id);
+        entity.update(cx, |this, _| {
+            this._workspace_subscription = Some(subscription)
+        });
 
         // Spawn task to process events and update UI
         cx.spawn(async move |cx| {
@@ -518,6 +645,9 @@ impl TaskPanel {
                             });
                         }
                     }
+                    WorkspaceUpdateEvent::SessionTitleUpdated { .. } => {
+                        // Tasks keep their own names
+                    }
                 }
             }
         })
@@ -611,6 +741,41 @@ impl TaskPanel {
         }
     }
 
+    /// Move a task to a board column, persisting the column's status
+    fn move_task_to_status(
+        &mut self,
+        task_id: String,
+        status: SessionStatus,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(task) = self
+            .workspaces
+            .iter_mut()
+            .flat_map(|w| w.tasks.iter_mut())
+            .find(|t| t.id == task_id)
+        else {
+            return;
+        };
+        if board_column(&task.status) == status {
+            return;
+        }
+
+        let mut updated_task = (**task).clone();
+        updated_task.status = status.clone();
+        *task = Rc::new(updated_task);
+        cx.notify();
+
+        if let Some(workspace_service) = AppState::global(cx).workspace_service() {
+            let workspace_service = workspace_service.clone();
+            cx.spawn(async move |_entity, _cx| {
+                if let Err(e) = workspace_service.update_task_status(&task_id, status).await {
+                    log::error!("Failed to move task {}: {}", task_id, e);
+                }
+            })
+            .detach();
+        }
+    }
+
     // ========================================================================
     // Event Handlers
     // ========================================================================
@@ -677,7 +842,66 @@ impl TaskPanel {
         .detach();
     }
 
-    fn remove_workspace(&mut self, workspace_id: String, cx: &mut Context<Self>) {
+    /// Ask for confirmation before removing a workspace, stating how many tasks
+    /// and sessions go with it
+    fn confirm_remove_workspace(
+        &mut self,
+        workspace_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspaces.iter().find(|w| w.id == workspace_id) else {
+            return;
+        };
+        let name = workspace.name.clone();
+        let task_count = workspace.tasks.len();
+        let session_count = workspace
+            .tasks
+            .iter()
+            .filter(|task| task.session_id.is_some())
+            .count();
+        let message = if task_count == 0 {
+            t!("task_panel.workspace.remove_dialog.message", name = name).to_string()
+        } else {
+            t!(
+                "task_panel.workspace.remove_dialog.message_tasks",
+                name = name,
+                tasks = task_count,
+                sessions = session_count
+            )
+            .to_string()
+        };
+
+        let entity = cx.entity();
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            let entity = entity.clone();
+            let workspace_id = workspace_id.clone();
+            dialog
+                .title(t!("task_panel.workspace.remove_dialog.title").to_string())
+                .confirm()
+                .button_props(
+                    DialogButtonProps::default()
+                        .ok_text(t!("task_panel.workspace.remove_dialog.ok").to_string())
+                        .ok_variant(ButtonVariant::Danger)
+                        .cancel_text(t!("task_panel.workspace.remove_dialog.cancel").to_string()),
+                )
+                .on_ok(move |_, _window, cx| {
+                    entity.update(cx, |this, cx| {
+                        this.remove_workspace(workspace_id.clone(), task_count > 0, cx);
+                    });
+                    true
+                })
+                .child(
+                    v_flex()
+                        .w_full()
+                        .p_4()
+                        .child(Label::new(message.clone()).text_sm()),
+                )
+        });
+    }
+
+    /// Remove a workspace; `cascade` also removes its tasks
+    fn remove_workspace(&mut self, workspace_id: String, cascade: bool, cx: &mut Context<Self>) {
         let workspace_service = match AppState::global(cx).workspace_service() {
             Some(service) => service.clone(),
             None => {
@@ -687,7 +911,15 @@ impl TaskPanel {
         };
 
         cx.spawn(async move |entity, cx| {
-            match workspace_service.remove_workspace(&workspace_id).await {
+            let result = if cascade {
+                workspace_service
+                    .remove_workspace_cascade(&workspace_id)
+                    .await
+                    .map(|_| ())
+            } else {
+                workspace_service.remove_workspace(&workspace_id).await
+            };
+            match result {
                 Ok(_) => {
                     log::info!("Successfully removed workspace: {}", workspace_id);
                     cx.update(|cx| {
@@ -860,37 +1092,60 @@ impl TaskPanel {
     // Search & Filter
     // ========================================================================
 
-    fn get_filtered_workspaces(&self, cx: &Context<Self>) -> Vec<WorkspaceGroup> {
-        let search_query = self.search_input.read(cx).text().to_string().to_lowercase();
+    fn set_filter(&mut self, filter: TaskFilter, cx: &mut Context<Self>) {
+        cx.set_global(filter.clone());
+        self.filter = filter;
+        cx.notify();
+    }
 
-        if search_query.is_empty() {
-            return self.workspaces.clone();
-        }
+    fn clear_filters(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_filter(TaskFilter::default(), cx);
+        self.search_input
+            .update(cx, |state, cx| state.set_value("", window, cx));
+    }
+
+    /// Distinct agent names across all tasks, for the agent filter
+    fn task_agent_names(&self) -> Vec<String> {
+        let mut agents: Vec<String> = self
+            .workspaces
+            .iter()
+            .flat_map(|w| w.tasks.iter().map(|t| t.agent_name.clone()))
+            .collect();
+        agents.sort();
+        agents.dedup();
+        agents
+    }
+
+    fn get_filtered_workspaces(&self, _cx: &Context<Self>) -> Vec<WorkspaceGroup> {
+        let filter = &self.filter;
+        let search_query = filter.query.to_lowercase();
+        let filters_tasks = filter.agent_name.is_some() || !search_query.is_empty();
 
         self.workspaces
             .iter()
+            .filter(|workspace| match &filter.workspace {
+                WorkspaceScope::All => true,
+                WorkspaceScope::Active => self.active_workspace_id.as_ref() == Some(&workspace.id),
+                WorkspaceScope::Workspace(id) => &workspace.id == id,
+            })
             .filter_map(|workspace| {
-                // Filter tasks that match the search query
+                if !filters_tasks {
+                    return Some(workspace.clone());
+                }
+
+                // Filter tasks that match the agent and search query
                 let filtered_tasks: Vec<_> = workspace
                     .tasks
                     .iter()
-                    .filter(|task| {
-                        task.name.to_lowercase().contains(&search_query)
-                            || task.agent_name.to_lowercase().contains(&search_query)
-                            || task.mode.to_lowercase().contains(&search_query)
-                            || task
-                                .last_message
-                                .as_ref()
-                                .map(|msg| msg.to_lowercase().contains(&search_query))
-                                .unwrap_or(false)
-                    })
+                    .filter(|task| filter.matches_task(task))
                     .cloned()
                     .collect();
 
                 // Include workspace if it has matching tasks or its name matches
-                if !filtered_tasks.is_empty()
-                    || workspace.name.to_lowercase().contains(&search_query)
-                {
+                let name_matches = filter.agent_name.is_none()
+                    && !search_query.is_empty()
+                    && workspace.name.to_lowercase().contains(&search_query);
+                if !filtered_tasks.is_empty() || name_matches {
                     Some(WorkspaceGroup {
                         id: workspace.id.clone(),
                         name: workspace.name.clone(),
@@ -958,10 +1213,201 @@ impl TaskPanel {
                             .on_click(cx.listener(|this, _, _, cx| {
                                 this.set_view_mode(ViewMode::Timeline, cx);
                             })),
+                    )
+                    .child(
+                        Button::new("board-view")
+                            .icon(IconName::GalleryVerticalEnd)
+                            .ghost()
+                            .xsmall()
+                            .selected(view_mode == ViewMode::Board)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_view_mode(ViewMode::Board, cx);
+                            })),
                     ),
             )
     }
 
+    /// Workspace and agent filter dropdowns, followed by chips for active filters
+    fn render_filter_bar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let entity = cx.entity().clone();
+        let filter = self.filter.clone();
+
+        let workspace_label = match &filter.workspace {
+            WorkspaceScope::All => t!("task_panel.filter.workspace_all").to_string(),
+            WorkspaceScope::Active => t!("task_panel.filter.workspace_active").to_string(),
+            WorkspaceScope::Workspace(id) => self
+                .workspaces
+                .iter()
+                .find(|w| &w.id == id)
+                .map(|w| w.name.clone())
+                .unwrap_or_else(|| t!("task_panel.filter.workspace_all").to_string()),
+        };
+        let agent_label = filter
+            .agent_name
+            .clone()
+            .unwrap_or_else(|| t!("task_panel.filter.agent_all").to_string());
+
+        let mut workspace_options = vec![
+            (
+                WorkspaceScope::All,
+                t!("task_panel.filter.workspace_all").to_string(),
+            ),
+            (
+                WorkspaceScope::Active,
+                t!("task_panel.filter.workspace_active").to_string(),
+            ),
+        ];
+        workspace_options.extend(
+            self.workspaces
+                .iter()
+                .map(|w| (WorkspaceScope::Workspace(w.id.clone()), w.name.clone())),
+        );
+        let mut agent_options = vec![(None, t!("task_panel.filter.agent_all").to_string())];
+        agent_options.extend(
+            self.task_agent_names()
+                .into_iter()
+                .map(|name| (Some(name.clone()), name)),
+        );
+
+        // Chips for each active filter, each removing just that filter
+        let mut chips: Vec<(&'static str, String, TaskFilter)> = Vec::new();
+        if filter.workspace != WorkspaceScope::All {
+            chips.push((
+                "filter-chip-workspace",
+                workspace_label.clone(),
+                TaskFilter {
+                    workspace: WorkspaceScope::All,
+                    ..filter.clone()
+                },
+            ));
+        }
+        if let Some(agent) = &filter.agent_name {
+            chips.push((
+                "filter-chip-agent",
+                agent.clone(),
+                TaskFilter {
+                    agent_name: None,
+                    ..filter.clone()
+                },
+            ));
+        }
+
+        v_flex()
+            .w_full()
+            .gap_1p5()
+            .px_3()
+            .py_2()
+            .border_b_1()
+            .border_color(theme.border)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        Button::new("filter-workspace")
+                            .label(workspace_label)
+                            .icon(IconName::ChevronDown)
+                            .ghost()
+                            .xsmall()
+                            .dropdown_menu({
+                                let entity = entity.clone();
+                                let current = filter.clone();
+                                move |mut menu, _, _| {
+                                    for (scope, label) in workspace_options.clone() {
+                                        let mut item = PopupMenuItem::new(label);
+                                        if current.workspace == scope {
+                                            item = item.icon(IconName::Check);
+                                        }
+                                        let entity = entity.clone();
+                                        menu = menu.item(item.on_click(move |_, _, cx| {
+                                            entity.update(cx, |this, cx| {
+                                                let mut filter = this.filter.clone();
+                                                filter.workspace = scope.clone();
+                                                this.set_filter(filter, cx);
+                                            });
+                                        }));
+                                    }
+                                    menu
+                                }
+                            }),
+                    )
+                    .child(
+                        Button::new("filter-agent")
+                            .label(agent_label)
+                            .icon(IconName::ChevronDown)
+                            .ghost()
+                            .xsmall()
+                            .dropdown_menu({
+                                let entity = entity.clone();
+                                let current = filter.clone();
+                                move |mut menu, _, _| {
+                                    for (agent, label) in agent_options.clone() {
+                                        let mut item = PopupMenuItem::new(label);
+                                        if current.agent_name == agent {
+                                            item = item.icon(IconName::Check);
+                                        }
+                                        let entity = entity.clone();
+                                        menu = menu.item(item.on_click(move |_, _, cx| {
+                                            entity.update(cx, |this, cx| {
+                                                let mut filter = this.filter.clone();
+                                                filter.agent_name = agent.clone();
+                                                this.set_filter(filter, cx);
+                                            });
+                                        }));
+                                    }
+                                    menu
+                                }
+                            }),
+                    ),
+            )
+            .when(filter.is_active(), |this| {
+                this.child(
+                    h_flex()
+                        .w_full()
+                        .flex_wrap()
+                        .gap_1()
+                        .items_center()
+                        .children(chips.into_iter().map(|(id, label, without)| {
+                            Button::new(id)
+                                .label(label)
+                                .icon(IconName::Close)
+                                .outline()
+                                .xsmall()
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.set_filter(without.clone(), cx);
+                                }))
+                        }))
+                        .when(!filter.query.is_empty(), |this| {
+                            this.child(
+                                Button::new("filter-chip-query")
+                                    .label(format!("\"{}\"", filter.query))
+                                    .icon(IconName::Close)
+                                    .outline()
+                                    .xsmall()
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.search_input.update(cx, |state, cx| {
+                                            state.set_value("", window, cx)
+                                        });
+                                        let mut filter = this.filter.clone();
+                                        filter.query.clear();
+                                        this.set_filter(filter, cx);
+                                    })),
+                            )
+                        })
+                        .child(
+                            Button::new("filter-clear-all")
+                                .label(t!("task_panel.filter.clear_all").to_string())
+                                .ghost()
+                                .xsmall()
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.clear_filters(window, cx);
+                                })),
+                        ),
+                )
+            })
+    }
+
     fn render_footer(&self, cx: &Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
 
@@ -1219,9 +1665,13 @@ impl TaskPanel {
                             menu.separator().item(
                                 PopupMenuItem::new(t!("task_panel.workspace.remove").to_string())
                                     .icon(Icon::new(crate::assets::Icon::Trash2))
-                                    .on_click(move |_, _, cx| {
+                                    .on_click(move |_, window, cx| {
                                         entity.update(cx, |this, cx| {
-                                            this.remove_workspace(workspace_id.clone(), cx);
+                                            this.confirm_remove_workspace(
+                                                workspace_id.clone(),
+                                                window,
+                                                cx,
+                                            );
                                         });
                                     }),
                             )
@@ -1576,6 +2026,164 @@ impl TaskPanel {
             )
     }
 
+    // ========================================================================
+    // Render - Board View
+    // ========================================================================
+
+    fn render_board_view(&self, cx: &Context<Self>) -> impl IntoElement {
+        let filtered_workspaces = self.get_filtered_workspaces(cx);
+
+        let mut all_tasks: Vec<Rc<WorkspaceTask>> = filtered_workspaces
+            .iter()
+            .flat_map(|w| w.tasks.clone())
+            .collect();
+        all_tasks.sort_by_key(|task| std::cmp::Reverse(task.created_at));
+
+        h_flex()
+            .id("task-board-scroll")
+            .flex_1()
+            .min_h_0()
+            .items_start()
+            .gap_2()
+            .p_2()
+            .overflow_x_scroll()
+            .children(BOARD_COLUMNS.iter().enumerate().map(|(idx, status)| {
+                let column_tasks: Vec<_> = all_tasks
+                    .iter()
+                    .filter(|task| board_column(&task.status) == *status)
+                    .collect();
+                self.render_board_column(idx, status.clone(), &column_tasks, cx)
+            }))
+    }
+
+    fn render_board_column(
+        &self,
+        idx: usize,
+        status: SessionStatus,
+        tasks: &[&Rc<WorkspaceTask>],
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let theme = cx.theme();
+
+        v_flex()
+            .id(("task-board-column", idx))
+            .w(px(200.))
+            .flex_shrink_0()
+            .h_full()
+            .rounded(px(8.))
+            .bg(theme.secondary)
+            .border_1()
+            .border_color(theme.border)
+            .drag_over::<DraggedTask>(|style, _, _, cx| style.border_color(cx.theme().primary))
+            .on_drop(cx.listener({
+                let status = status.clone();
+                move |this, dragged: &DraggedTask, _, cx| {
+                    this.move_task_to_status(dragged.task_id.clone(), status.clone(), cx);
+                }
+            }))
+            .child(
+                h_flex()
+                    .w_full()
+                    .justify_between()
+                    .items_center()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(theme.border.opacity(0.5))
+                    .child(self.render_status_badge(&status, cx))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .child(tasks.len().to_string()),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id(("task-board-column-scroll", idx))
+                    .flex_1()
+                    .min_h_0()
+                    .gap_1p5()
+                    .p_1p5()
+                    .overflow_y_scroll()
+                    .children(tasks.iter().map(|task| self.render_board_card(task, cx))),
+            )
+    }
+
+    fn render_board_card(&self, task: &Rc<WorkspaceTask>, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let task_id = task.id.clone();
+        let is_selected = self.selected_task_id.as_ref() == Some(&task_id);
+
+        v_flex()
+            .id(SharedString::from(format!("board-task-{}", task_id)))
+            .w_full()
+            .gap_1()
+            .p_2()
+            .rounded(px(6.))
+            .bg(theme.background)
+            .border_1()
+            .border_color(if is_selected {
+                theme.primary
+            } else {
+                theme.border
+            })
+            .cursor_grab()
+            .on_drag(
+                DraggedTask {
+                    task_id: task_id.clone(),
+                    name: task.name.clone(),
+                },
+                |dragged, _, _, cx| cx.new(|_| dragged.clone()),
+            )
+            .on_click(cx.listener({
+                let task_id = task_id.clone();
+                move |this, _: &ClickEvent, window, cx| {
+                    this.select_task(task_id.clone(), cx);
+                    if this.session_id_for_task(&task_id).is_some() {
+                        this.open_task_in_current_panel(&task_id, window, cx);
+                    }
+                }
+            }))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener({
+                    let task_id = task_id.clone();
+                    move |this, _, _, cx| {
+                        this.select_task_for_context_menu(task_id.clone(), cx);
+                        cx.stop_propagation();
+                    }
+                }),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .font_medium()
+                    .text_color(theme.foreground)
+                    .overflow_x_hidden()
+                    .text_ellipsis()
+                    .child(task.name.clone()),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .items_center()
+                    .text_xs()
+                    .text_color(theme.muted_foreground)
+                    .child(StatusIndicator::new(task.status.clone()).size(6.0))
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w_0()
+                            .overflow_x_hidden()
+                            .text_ellipsis()
+                            .child(task.agent_name.clone()),
+                    )
+                    .child(self.format_relative_time(&task.created_at)),
+            )
+    }
+
     // ========================================================================
     // Time formatting helpers
     // ========================================================================
@@ -1699,6 +2307,7 @@ impl Render for TaskPanel {
             .track_focus(&self.focus_handle)
             .size_full()
             .child(self.render_header(cx))
+            .child(self.render_filter_bar(cx))
             .child(
                 v_flex()
                     .id("task-panel-content")
@@ -1713,13 +2322,34 @@ impl Render for TaskPanel {
                     .child(match self.view_mode {
                         ViewMode::Tree => self.render_tree_view(cx).into_any_element(),
                         ViewMode::Timeline => self.render_timeline_view(cx).into_any_element(),
+                        ViewMode::Board => self.render_board_view(cx).into_any_element(),
                     })
                     .context_menu(move |menu, _, cx| {
                         let Some(task_id) = entity.read(cx).context_menu_task_id.clone() else {
                             return menu;
                         };
+                        let session_label =
+                            if entity.read(cx).session_id_for_task(&task_id).is_some() {
+                                t!("task_panel.task.open_session")
+                            } else {
+                                t!("task_panel.task.start_session")
+                            };
+                        let action_task_id = task_id.clone();
                         let entity = entity.clone();
                         menu.item(
+                            PopupMenuItem::new(session_label.to_string())
+                                .icon(IconName::SquareTerminal)
+                                .on_click(move |_, window, cx| {
+                                    window.dispatch_action(
+                                        Box::new(StartTaskSession {
+                                            task_id: action_task_id.clone(),
+                                        }),
+                                        cx,
+                                    );
+                                }),
+                        )
+                        .separator()
+                        .item(
                             PopupMenuItem::new(t!("task_panel.task.delete").to_string())
                                 .icon(Icon::new(crate::assets::Icon::Trash2))
                                 .on_click(move |_, _, cx| {
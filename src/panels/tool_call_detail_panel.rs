@@ -1,21 +1,47 @@
+use std::sync::Arc;
+
 use gpui::{
     AnyElement, App, Context, Entity, FocusHandle, Focusable, InteractiveElement, IntoElement,
-    ParentElement, Render, ScrollHandle, SharedString, StatefulInteractiveElement, Styled, Window,
-    div, prelude::*, px,
+    ParentElement, Render, ScrollHandle, SharedString, StatefulInteractiveElement, Styled,
+    Subscription, Window, div, point, prelude::*, px,
 };
 use gpui_component::{ActiveTheme, Icon, IconName, h_flex, text::TextView, v_flex};
+use rust_i18n::t;
 
 use agent_client_protocol::{ContentBlock, ToolCall, ToolCallContent};
 
-use crate::components::DiffView;
+use crate::AppSettings;
+use crate::app::actions::{NextHunk, PreviousHunk};
+use crate::components::{DiffHunkPositions, DiffView};
 use crate::panels::dock_panel::DockPanel;
 
+/// Diff lines rendered per chunk; more are rendered as the user scrolls down
+const DIFF_RENDER_CHUNK_LINES: usize = 500;
+
+/// Distance from the bottom of the scroll area at which the next chunk is rendered
+const DIFF_RENDER_AHEAD: f32 = 600.;
+
+/// Space left above a hunk scrolled into view
+const HUNK_SCROLL_MARGIN: f32 = 24.;
+
 /// Panel that displays detailed tool call content
 pub struct ToolCallDetailPanel {
     focus_handle: FocusHandle,
     scroll_handle: ScrollHandle,
     /// The tool call to display
     tool_call: Option<ToolCall>,
+    /// Times the user loaded another `diff_max_lines` lines past the configured limit
+    diff_pages_loaded: usize,
+    /// Diff lines rendered so far, grown in chunks while scrolling
+    diff_rendered_lines: usize,
+    /// Where the rendered hunks were laid out in the last frame
+    hunk_positions: DiffHunkPositions,
+    /// Number of hunks in the last frame, for the indicator
+    hunk_count: usize,
+    /// Hunk last jumped to with n/N, cleared when the user scrolls
+    current_hunk: Option<usize>,
+    /// Observation of the selected tool call, dropped with the panel
+    _selection_subscription: Option<Subscription>,
 }
 
 impl ToolCallDetailPanel {
@@ -27,13 +53,19 @@ impl ToolCallDetailPanel {
             focus_handle,
             scroll_handle,
             tool_call: None,
+            diff_pages_loaded: 0,
+            diff_rendered_lines: DIFF_RENDER_CHUNK_LINES,
+            hunk_positions: DiffHunkPositions::default(),
+            hunk_count: 0,
+            current_hunk: None,
+            _selection_subscription: None,
         }
     }
 
     pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
         cx.new(|cx| {
-            let panel = Self::new(window, cx);
-            Self::subscribe_to_tool_call_updates(cx);
+            let mut panel = Self::new(window, cx);
+            panel._selection_subscription = Some(Self::subscribe_to_tool_call_updates(cx));
             panel
         })
     }
@@ -48,17 +80,114 @@ impl ToolCallDetailPanel {
     // }
     /// Update the tool call to display
     pub fn update_tool_call(&mut self, tool_call: ToolCall, cx: &mut Context<Self>) {
-        self.tool_call = Some(tool_call);
+        self.set_tool_call(tool_call);
         cx.notify();
     }
     /// Setup the tool call to display
     pub fn set_tool_call(&mut self, tool_call: ToolCall) {
+        let same_tool_call = self
+            .tool_call
+            .as_ref()
+            .is_some_and(|current| current.tool_call_id == tool_call.tool_call_id);
+        if !same_tool_call {
+            self.reset_diff_limits();
+        }
         self.tool_call = Some(tool_call);
     }
 
+    /// The tool call currently displayed
+    pub fn tool_call(&self) -> Option<&ToolCall> {
+        self.tool_call.as_ref()
+    }
+
     /// Clear the displayed tool call
     pub fn clear(&mut self, cx: &mut Context<Self>) {
         self.tool_call = None;
+        self.reset_diff_limits();
+        cx.notify();
+    }
+
+    fn reset_diff_limits(&mut self) {
+        self.diff_pages_loaded = 0;
+        self.diff_rendered_lines = DIFF_RENDER_CHUNK_LINES;
+        self.current_hunk = None;
+    }
+
+    /// Configured diff line limit
+    fn diff_max_lines(cx: &App) -> usize {
+        AppSettings::global(cx).diff_max_lines.max(1.) as usize
+    }
+
+    /// Configured limit, raised by one limit's worth of lines per "load more"
+    fn diff_line_limit(&self, cx: &App) -> usize {
+        Self::diff_max_lines(cx).saturating_mul(self.diff_pages_loaded + 1)
+    }
+
+    /// Raise the limit by another `diff_max_lines` lines; they are still rendered
+    /// chunk by chunk while scrolling
+    fn load_more_diff_lines(&mut self, cx: &mut Context<Self>) {
+        self.diff_pages_loaded += 1;
+        self.diff_rendered_lines += DIFF_RENDER_CHUNK_LINES;
+        cx.notify();
+    }
+
+    /// Render the next chunk of diff lines once the view is scrolled close to the end
+    ///
+    /// Checked after every layout, so wheel, scrollbar and keyboard scrolling all count.
+    fn render_more_diff_lines_if_needed(&mut self, cx: &mut Context<Self>) {
+        if self.diff_rendered_lines >= self.diff_line_limit(cx) {
+            return;
+        }
+
+        let scrolled = -self.scroll_handle.offset().y;
+        let max_offset = self.scroll_handle.max_offset().height;
+        if max_offset - scrolled < px(DIFF_RENDER_AHEAD) {
+            self.diff_rendered_lines += DIFF_RENDER_CHUNK_LINES;
+            cx.notify();
+        }
+    }
+
+    fn on_action_next_hunk(&mut self, _: &NextHunk, _: &mut Window, cx: &mut Context<Self>) {
+        self.navigate_hunk(true, cx);
+    }
+
+    fn on_action_previous_hunk(
+        &mut self,
+        _: &PreviousHunk,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.navigate_hunk(false, cx);
+    }
+
+    /// Scroll the next or previous hunk to the top of the panel, wrapping around
+    fn navigate_hunk(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let tops = self.hunk_positions.tops();
+        if tops.is_empty() {
+            return;
+        }
+
+        let count = tops.len();
+        let anchor = self.scroll_handle.bounds().top() + px(HUNK_SCROLL_MARGIN);
+        let target = match self.current_hunk.filter(|current| *current < count) {
+            Some(current) if forward => (current + 1) % count,
+            Some(current) => (current + count - 1) % count,
+            // Nothing selected yet: continue from what is currently on screen
+            None if forward => tops
+                .iter()
+                .position(|top| *top > anchor + px(1.))
+                .unwrap_or(0),
+            None => tops
+                .iter()
+                .rposition(|top| *top < anchor - px(1.))
+                .unwrap_or(count - 1),
+        };
+
+        let offset = self.scroll_handle.offset();
+        let max_offset = self.scroll_handle.max_offset().height;
+        let y = (offset.y - (tops[target] - anchor)).clamp(-max_offset, px(0.));
+        self.scroll_handle.set_offset(point(offset.x, y));
+        self.current_hunk = Some(target);
         cx.notify();
     }
 
@@ -69,13 +198,29 @@ impl ToolCallDetailPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> AnyElement {
-        let diff_view = DiffView::new(diff.clone()).context_lines(5).max_lines(5000);
+        let limit = self.diff_line_limit(cx);
+        let at_limit = self.diff_rendered_lines >= limit;
+        let panel = cx.entity().downgrade();
+        let mut diff_view = DiffView::new(diff.clone())
+            .context_lines(5)
+            .max_lines(limit.min(self.diff_rendered_lines))
+            // Only warn when the configured limit is reached, not at chunk boundaries
+            .show_truncation_warning(at_limit)
+            .track_hunks(self.hunk_positions.clone());
+        if at_limit {
+            diff_view = diff_view.on_load_more(
+                Self::diff_max_lines(cx),
+                Arc::new(move |_window, cx| {
+                    _ = panel.update(cx, |this, cx| this.load_more_diff_lines(cx));
+                }),
+            );
+        }
 
         diff_view.render(window, cx).into_any_element()
     }
 
     /// Subscribe to the global selected tool call state
-    pub fn subscribe_to_tool_call_updates(cx: &mut Context<Self>) {
+    pub fn subscribe_to_tool_call_updates(cx: &mut Context<Self>) -> Subscription {
         let app_state = crate::AppState::global(cx);
         let selected_tool_call = app_state.selected_tool_call.clone();
 
@@ -87,7 +232,6 @@ impl ToolCallDetailPanel {
                 this.clear(cx);
             }
         })
-        .detach();
     }
 
     /// Render content based on ToolCallContent type
@@ -222,15 +366,42 @@ impl Render for ToolCallDetailPanel {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let scroll_handle = self.scroll_handle.clone();
 
+        // Hunk positions are re-collected while this frame is laid out
+        self.hunk_positions.clear();
+        cx.on_next_frame(window, |this, _, cx| {
+            let count = this.hunk_positions.len();
+            if count != this.hunk_count {
+                this.hunk_count = count;
+                cx.notify();
+            }
+            this.render_more_diff_lines_if_needed(cx);
+        });
+        let hunk_indicator = match self.current_hunk {
+            Some(current) if current < self.hunk_count => t!(
+                "tool_call_detail.hunk_position",
+                current = current + 1,
+                total = self.hunk_count
+            )
+            .to_string(),
+            _ if self.hunk_count == 1 => t!("tool_call_detail.hunk_count_one").to_string(),
+            _ => t!("tool_call_detail.hunk_count", count = self.hunk_count).to_string(),
+        };
+
         div()
             .size_full()
-            // .track_focus(&self.focus_handle)
+            .key_context("ToolCallDetail")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::on_action_next_hunk))
+            .on_action(cx.listener(Self::on_action_previous_hunk))
             .child(
                 div()
                     .id("tool-call-detail-scroll")
                     .size_full()
                     .overflow_y_scroll()
                     .track_scroll(&scroll_handle)
+                    .on_scroll_wheel(cx.listener(|this, _, _, _| {
+                        this.current_hunk = None;
+                    }))
                     .child(
                         v_flex()
                             .w_full()
@@ -256,7 +427,15 @@ impl Render for ToolCallDetailPanel {
                                                         .font_weight(gpui::FontWeight::SEMIBOLD)
                                                         .text_color(cx.theme().foreground)
                                                         .child(tool_call.title.clone()),
-                                                ),
+                                                )
+                                                .when(self.hunk_count > 0, |this| {
+                                                    this.child(div().flex_1()).child(
+                                                        div()
+                                                            .text_size(px(12.))
+                                                            .text_color(cx.theme().muted_foreground)
+                                                            .child(hunk_indicator),
+                                                    )
+                                                }),
                                         )
                                         .child(div().w_full().h(px(1.)).bg(cx.theme().border))
                                         .children(tool_call.content.iter().map(|content| {
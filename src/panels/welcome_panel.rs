@@ -1,12 +1,15 @@
 use gpui::{
     App, AppContext, ClipboardEntry, Context, Entity, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, ParentElement, Render, SharedString, Styled, Subscription, Window, px,
+    IntoElement, ParentElement, Render, SharedString, Styled, Subscription, Window,
+    prelude::FluentBuilder, px,
 };
 use rust_i18n::t;
 use std::collections::HashSet;
 
 use gpui_component::{
-    ActiveTheme, IndexPath, StyledExt, WindowExt,
+    ActiveTheme, Icon, IconName, IndexPath, Sizable, StyledExt, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
     input::InputState,
     list::ListState,
     notification::Notification,
@@ -17,17 +20,21 @@ use gpui_component::{
 use agent_client_protocol::{self as acp, AvailableCommand, ImageContent};
 
 use crate::{
-    AppState, CreateTaskFromWelcome, WelcomeSession,
+    AppState, CreateTaskFromWelcome, PanelAction, WelcomeSession,
     app::actions::AddCodeSelection,
     components::{
         AgentItem, ChatInputBox, FileItem, FilePickerDelegate, ModeSelectItem, ModelSelectItem,
+        NoAgentsState,
     },
-    core::{config::McpServerConfig, services::AgentSessionInfo},
+    core::{config::McpServerConfig, event_bus::EventSubscription, services::AgentSessionInfo},
+    schemas::workspace_schema::Workspace,
 };
 
 // File picker delegate is now imported from components module
 
 const MAX_FILE_SUGGESTIONS: usize = 8;
+/// Entries shown in each "recent" list of the dashboard
+const RECENT_ITEMS_LIMIT: usize = 5;
 
 /// Welcome panel displayed when creating a new task.
 /// Shows a centered input form with title, instructions, and send button.
@@ -62,6 +69,7 @@ pub struct WelcomePanel {
     show_command_suggestions: bool,
     /// Selected command index for keyboard navigation
     _subscriptions: Vec<Subscription>,
+    _bus_subscriptions: Vec<EventSubscription>,
     /// Available MCP servers (name, config)
     available_mcps: Vec<(String, McpServerConfig)>,
     /// Selected MCP server names
@@ -72,6 +80,14 @@ pub struct WelcomePanel {
     mcp_selection_overridden: bool,
     /// Whether we should recreate the session after MCP config changes
     pending_mcp_session_recreate: bool,
+    /// Most recently active sessions, newest first
+    recent_sessions: Vec<AgentSessionInfo>,
+    /// Most recently accessed workspaces, newest first
+    recent_workspaces: Vec<Workspace>,
+    /// Configured agents offered as quick actions
+    dashboard_agents: Vec<String>,
+    /// Whether the dashboard data has been loaded once
+    dashboard_loaded: bool,
 }
 
 impl crate::panels::dock_panel::DockPanel for WelcomePanel {
@@ -172,16 +188,22 @@ impl WelcomePanel {
         let entity = cx.new(|cx| Self::new(workspace_id.clone(), working_directory, window, cx));
 
         // Subscribe to code selection events using the shared helper function
-        crate::core::event_bus::subscribe_entity_to_code_selections(
-            &entity,
-            AppState::global(cx).event_hub().clone(),
-            "WelcomePanel",
-            |panel, selection, cx| {
-                panel.code_selections.push(selection.into());
-                cx.notify();
-            },
-            cx,
-        );
+        let mut bus_subscriptions =
+            vec![crate::core::event_bus::subscribe_entity_to_code_selections(
+                &entity,
+                AppState::global(cx).event_hub().clone(),
+                "WelcomePanel",
+                |panel, selection, cx| {
+                    let selection: AddCodeSelection = selection.into();
+                    // Ignore repeated identical selections
+                    if panel.code_selections.contains(&selection) {
+                        return;
+                    }
+                    panel.code_selections.push(selection);
+                    cx.notify();
+                },
+                cx,
+            )];
 
         // Subscribe to agent config events for dynamic agent list updates
         {
@@ -191,10 +213,11 @@ impl WelcomePanel {
 
             // Subscribe to hub
             log::info!("[WelcomePanel] Subscribing to agent config events");
-            event_hub.subscribe_agent_config_updates(move |event| {
+            let id = event_hub.subscribe_agent_config_updates(move |event| {
                 log::debug!("[WelcomePanel] Received agent config event");
                 let _ = tx.send(event.clone());
             });
+            bus_subscriptions.push(EventSubscription::new(&event_hub, id));
 
             // Spawn background task to process events
             cx.spawn(async move |cx| {
@@ -213,6 +236,45 @@ impl WelcomePanel {
             .detach();
         }
 
+        // Keep the recent sessions and workspaces current
+        {
+            let event_hub = AppState::global(cx).event_hub().clone();
+            let weak_entity = entity.downgrade();
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+
+            let id = event_hub.subscribe_workspace_updates(move |event| {
+                use crate::core::event_bus::WorkspaceUpdateEvent;
+                if matches!(
+                    event,
+                    WorkspaceUpdateEvent::WorkspaceAdded { .. }
+                        | WorkspaceUpdateEvent::WorkspaceRemoved { .. }
+                        | WorkspaceUpdateEvent::TaskCreated { .. }
+                        | WorkspaceUpdateEvent::SessionStatusUpdated { .. }
+                        | WorkspaceUpdateEvent::SessionTitleUpdated { .. }
+                ) {
+                    let _ = tx.send(());
+                }
+            });
+            bus_subscriptions.push(EventSubscription::new(&event_hub, id));
+
+            cx.spawn(async move |cx| {
+                while rx.recv().await.is_some() {
+                    // Coalesce bursts of updates into one refresh
+                    while rx.try_recv().is_ok() {}
+                    let Some(entity) = weak_entity.upgrade() else {
+                        break;
+                    };
+                    _ = cx.update(|cx| {
+                        entity.update(cx, |this, cx| this.refresh_dashboard(cx));
+                    });
+                }
+            })
+            .detach();
+        }
+
+        // Removed from the hub when the panel is closed
+        entity.update(cx, |this, _| this._bus_subscriptions = bus_subscriptions);
+
         // Subscribe to agent_select focus to refresh agents list when no agents available
         entity.update(cx, |this, cx| {
             // Subscribe to input changes to detect @ symbol
@@ -417,11 +479,16 @@ impl WelcomePanel {
             command_suggestions: Vec::new(),
             show_command_suggestions: false,
             _subscriptions: Vec::new(),
+            _bus_subscriptions: Vec::new(),
             available_mcps: Vec::new(),
             selected_mcps: Vec::new(),
             mcp_selection_initialized: false,
             mcp_selection_overridden: false,
             pending_mcp_session_recreate: false,
+            recent_sessions: Vec::new(),
+            recent_workspaces: Vec::new(),
+            dashboard_agents: Vec::new(),
+            dashboard_loaded: false,
         };
 
         // Load sessions for the initially selected agent if any
@@ -433,10 +500,307 @@ impl WelcomePanel {
 
         // Load MCP servers asynchronously
         panel.load_mcp_servers(cx);
+        panel.refresh_dashboard(cx);
 
         panel
     }
 
+    /// Reload recent sessions, recent workspaces and the agent list
+    fn refresh_dashboard(&mut self, cx: &mut Context<Self>) {
+        let agent_service = AppState::global(cx).agent_service().cloned();
+        let workspace_service = AppState::global(cx).workspace_service().cloned();
+        let agent_config_service = AppState::global(cx).agent_config_service().cloned();
+
+        cx.spawn(async move |this, cx| {
+            let (mut sessions, mut agents) = match &agent_service {
+                Some(service) => (
+                    service.list_workspace_sessions(),
+                    service.list_agents().await,
+                ),
+                None => (Vec::new(), Vec::new()),
+            };
+            if let Some(service) = &agent_config_service {
+                service.sort_agent_names(&mut agents).await;
+            }
+            let mut workspaces = match &workspace_service {
+                Some(service) => service.list_workspaces().await,
+                None => Vec::new(),
+            };
+
+            sessions.sort_by(|a, b| b.last_active.cmp(&a.last_active));
+            workspaces.sort_by(|a, b| b.last_accessed.cmp(&a.last_accessed));
+            workspaces.truncate(RECENT_ITEMS_LIMIT);
+
+            _ = this.update(cx, |this, cx| {
+                // The panel's own pre-created session is not "recent activity"
+                let own_session = this.current_session_id.clone();
+                this.recent_sessions = sessions
+                    .into_iter()
+                    .filter(|session| Some(&session.session_id) != own_session.as_ref())
+                    .take(RECENT_ITEMS_LIMIT)
+                    .collect();
+                this.recent_workspaces = workspaces;
+                this.dashboard_agents = agents;
+                this.dashboard_loaded = true;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Quick action: create a session for the agent in this panel's folder and open it
+    fn start_session_with_agent(
+        &mut self,
+        agent_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(agent_service) = AppState::global(cx).agent_service().cloned() else {
+            log::error!("[WelcomePanel] AgentService not initialized");
+            return;
+        };
+        let mcp_servers =
+            Self::collect_mcp_servers_from_selection(&self.available_mcps, &self.selected_mcps);
+        let cwd = self.working_directory.clone();
+
+        cx.spawn_in(window, async move |_this, window| {
+            match agent_service
+                .create_session_with_mcp_and_cwd(&agent_name, mcp_servers, cwd)
+                .await
+            {
+                Ok(session_id) => {
+                    log::info!(
+                        "[WelcomePanel] Started session {} for agent {}",
+                        session_id,
+                        agent_name
+                    );
+                    _ = window.update(|window, cx| {
+                        window.dispatch_action(
+                            Box::new(PanelAction::show_conversation(Some(session_id))),
+                            cx,
+                        );
+                    });
+                }
+                Err(e) => {
+                    log::error!("[WelcomePanel] Failed to start session: {}", e);
+                    _ = window.update(|window, cx| {
+                        struct StartSessionError;
+                        let note = Notification::error(
+                            t!("welcome.dashboard.start_failed", error = e.to_string()).to_string(),
+                        )
+                        .id::<StartSessionError>();
+                        window.push_notification(note, cx);
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Make a recent workspace the active one and show it in this panel
+    ///
+    /// The conversation last active in that workspace is reopened as well.
+    fn activate_workspace(
+        &mut self,
+        workspace_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace_service) = AppState::global(cx).workspace_service().cloned() else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |this, window| {
+            if let Err(e) = workspace_service.set_active_workspace(&workspace_id).await {
+                log::error!("[WelcomePanel] Failed to activate workspace: {}", e);
+                return;
+            }
+            let last_session = workspace_service.last_active_session(&workspace_id).await;
+            _ = window.update(|window, cx| {
+                if let Some(entity) = this.upgrade() {
+                    entity.update(cx, |this, cx| {
+                        this.workspace_id = Some(workspace_id.clone());
+                        this.refresh_dashboard(cx);
+                    });
+                    Self::load_workspace_info(&entity, Some(&workspace_id), cx);
+                }
+                if let Some(session_id) = last_session {
+                    window.dispatch_action(
+                        Box::new(PanelAction::show_conversation(Some(session_id))),
+                        cx,
+                    );
+                }
+            });
+        })
+        .detach();
+    }
+
+    fn render_dashboard_heading(label: String, cx: &Context<Self>) -> impl IntoElement {
+        gpui::div()
+            .text_xs()
+            .font_semibold()
+            .text_color(cx.theme().muted_foreground)
+            .child(label)
+    }
+
+    /// Whether loading finished without finding any agent
+    fn no_agents_configured(&self) -> bool {
+        self.dashboard_loaded && self.dashboard_agents.is_empty()
+    }
+
+    fn render_dashboard_hint(label: String, cx: &Context<Self>) -> impl IntoElement {
+        gpui::div()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(label)
+    }
+
+    /// Quick actions and recent activity shown under the input box
+    fn render_dashboard(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        if !self.dashboard_loaded {
+            return v_flex().into_any_element();
+        }
+
+        let quick_actions = if self.dashboard_agents.is_empty() {
+            // First-time users: point at the wizard or the agent settings
+            v_flex().child(NoAgentsState::new("welcome-no-agents").compact())
+        } else {
+            v_flex().child(
+                h_flex().gap_2().flex_wrap().children(
+                    self.dashboard_agents
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, agent_name)| {
+                            let name = agent_name.clone();
+                            Button::new(("welcome-start-agent", idx))
+                                .label(agent_name.clone())
+                                .icon(Icon::new(crate::assets::agent_icon(agent_name, cx)))
+                                .outline()
+                                .small()
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.start_session_with_agent(name.clone(), window, cx);
+                                }))
+                        }),
+                ),
+            )
+        };
+
+        let recent_sessions = if self.recent_sessions.is_empty() {
+            v_flex().child(Self::render_dashboard_hint(
+                t!("welcome.dashboard.no_sessions").to_string(),
+                cx,
+            ))
+        } else {
+            v_flex()
+                .gap_1()
+                .children(
+                    self.recent_sessions
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, session)| {
+                            let short_id: String = session.session_id.chars().take(8).collect();
+                            let title = session
+                                .title
+                                .clone()
+                                .unwrap_or_else(|| Self::session_label(&short_id));
+                            let session_id = session.session_id.clone();
+                            Button::new(("welcome-recent-session", idx))
+                                .label(format!(
+                                    "{} · {}",
+                                    title,
+                                    session
+                                        .last_active
+                                        .with_timezone(&chrono::Local)
+                                        .format("%m-%d %H:%M")
+                                ))
+                                .icon(Icon::new(crate::assets::agent_icon(
+                                    &session.agent_name,
+                                    cx,
+                                )))
+                                .ghost()
+                                .small()
+                                .on_click(move |_, window, cx| {
+                                    window.dispatch_action(
+                                        Box::new(PanelAction::show_conversation(Some(
+                                            session_id.clone(),
+                                        ))),
+                                        cx,
+                                    );
+                                })
+                        }),
+                )
+        };
+
+        let recent_workspaces = if self.recent_workspaces.is_empty() {
+            v_flex().child(Self::render_dashboard_hint(
+                t!("welcome.dashboard.no_workspaces").to_string(),
+                cx,
+            ))
+        } else {
+            v_flex()
+                .gap_1()
+                .children(
+                    self.recent_workspaces
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, workspace)| {
+                            let workspace_id = workspace.id.clone();
+                            Button::new(("welcome-recent-workspace", idx))
+                                .label(format!("{} · {}", workspace.name, workspace.path.display()))
+                                .icon(Icon::new(IconName::Folder))
+                                .ghost()
+                                .small()
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.activate_workspace(workspace_id.clone(), window, cx);
+                                }))
+                        }),
+                )
+        };
+
+        v_flex()
+            .w_full()
+            .gap_4()
+            .px(px(32.))
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(Self::render_dashboard_heading(
+                        t!("welcome.dashboard.quick_start").to_string(),
+                        cx,
+                    ))
+                    .child(quick_actions),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_6()
+                    .items_start()
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .min_w_0()
+                            .gap_2()
+                            .child(Self::render_dashboard_heading(
+                                t!("welcome.dashboard.recent_sessions").to_string(),
+                                cx,
+                            ))
+                            .child(recent_sessions),
+                    )
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .min_w_0()
+                            .gap_2()
+                            .child(Self::render_dashboard_heading(
+                                t!("welcome.dashboard.recent_workspaces").to_string(),
+                                cx,
+                            ))
+                            .child(recent_workspaces),
+                    ),
+            )
+            .into_any_element()
+    }
+
     /// Load MCP servers from AgentConfigService
     fn load_mcp_servers(&mut self, cx: &mut Context<Self>) {
         let agent_config_service = match AppState::global(cx).agent_config_service() {
@@ -526,12 +890,16 @@ impl WelcomePanel {
             None => return,
         };
 
+        let agent_config_service = AppState::global(cx).agent_config_service().cloned();
         let agent_select = self.agent_select.clone();
         let current_selection = self.agent_select.read(cx).selected_value().cloned();
         let no_agents_label = Self::no_agents_label();
         let weak_self = cx.entity().downgrade();
         cx.spawn_in(window, async move |_this, window| {
-            let agents = agent_service.list_agents().await;
+            let mut agents = agent_service.list_agents().await;
+            if let Some(service) = &agent_config_service {
+                service.sort_agent_names(&mut agents).await;
+            }
 
             _ = window.update(|window, cx| {
                 if let Some(this) = weak_self.upgrade() {
@@ -552,9 +920,11 @@ impl WelcomePanel {
 
                         this.has_agents = true;
                         let agent_items: Vec<AgentItem> = agents
-                            .clone()
-                            .into_iter()
-                            .map(|name| AgentItem::new(name))
+                            .iter()
+                            .map(|name| {
+                                AgentItem::new(name.clone())
+                                    .icon(crate::assets::agent_icon_key(name, cx))
+                            })
                             .collect();
                         let selected_index = current_selection
                             .as_ref()
@@ -591,11 +961,13 @@ impl WelcomePanel {
                 log::info!("[WelcomePanel] Agent added: {}", name);
                 // Force refresh to include new agent
                 self.has_agents = false;
+                self.refresh_dashboard(cx);
             }
             AgentConfigEvent::AgentRemoved { name } => {
                 log::info!("[WelcomePanel] Agent removed: {}", name);
                 // Force refresh to remove deleted agent
                 self.has_agents = false;
+                self.refresh_dashboard(cx);
             }
             AgentConfigEvent::AgentUpdated { name, .. } => {
                 log::info!("[WelcomePanel] Agent updated: {}", name);
@@ -605,6 +977,7 @@ impl WelcomePanel {
                 log::info!("[WelcomePanel] Agent config reloaded");
                 // Force full refresh
                 self.has_agents = false;
+                self.refresh_dashboard(cx);
                 self.available_mcps = config.mcp_servers.clone().into_iter().collect();
                 self.on_mcp_servers_changed();
             }
@@ -620,8 +993,15 @@ impl WelcomePanel {
                 log::info!("[WelcomePanel] MCP server removed: {}", name);
                 self.remove_mcp_server(name);
             }
-            // Model and Command events don't affect WelcomePanel
-            AgentConfigEvent::ModelAdded { .. }
+            AgentConfigEvent::AgentReady { .. } => {
+                // Agents that finished starting become quick actions
+                self.refresh_dashboard(cx);
+            }
+            // Model, Command and lifecycle events don't affect WelcomePanel
+            AgentConfigEvent::AgentStarting { .. }
+            | AgentConfigEvent::AgentFailed { .. }
+            | AgentConfigEvent::ConfigConflict { .. }
+            | AgentConfigEvent::ModelAdded { .. }
             | AgentConfigEvent::ModelUpdated { .. }
             | AgentConfigEvent::ModelRemoved { .. }
             | AgentConfigEvent::CommandAdded { .. }
@@ -739,21 +1119,19 @@ impl WelcomePanel {
             }
         };
 
-        // Get the agent name
-        let agent_name = match self.agent_select.read(cx).selected_value().cloned() {
-            Some(name) if name != no_agents_label => name,
+        match self.agent_select.read(cx).selected_value() {
+            Some(name) if *name != no_agents_label => {}
             _ => {
                 log::debug!("[WelcomePanel] Cannot change mode: no agent selected");
                 return;
             }
-        };
+        }
 
-        // Get the agent service to access agent manager
-        let app_state = AppState::global(cx);
-        let agent_manager = match app_state.agent_manager() {
-            Some(manager) => manager.clone(),
+        // The agent service remembers the mode, so the session keeps it once it becomes a task
+        let agent_service = match AppState::global(cx).agent_service() {
+            Some(service) => service.clone(),
             None => {
-                log::error!("[WelcomePanel] Cannot change mode: agent manager not available");
+                log::error!("[WelcomePanel] Cannot change mode: agent service not available");
                 return;
             }
         };
@@ -766,31 +1144,12 @@ impl WelcomePanel {
 
         // Send SetSessionMode command to agent asynchronously
         cx.spawn(async move |_entity, _cx| {
-            // Get the agent handle
-            let agent_handle = match agent_manager.get(&agent_name).await {
-                Some(handle) => handle,
-                None => {
-                    log::error!("[WelcomePanel] Cannot change mode: agent '{}' not found", agent_name);
-                    return;
-                }
-            };
-
-            // Create the SetSessionModeRequest
-            use agent_client_protocol as acp;
-            let mut request = acp::SetSessionModeRequest::new(
-                acp::SessionId::from(session_id.clone()),
-                mode.clone(),
-            );
-            request.meta = None;
-
-            // Send the request to the agent
-            match agent_handle.set_session_mode(request).await {
-                Ok(response) => {
+            match agent_service.set_session_mode(&session_id, &mode).await {
+                Ok(()) => {
                     log::info!(
-                        "[WelcomePanel] Successfully set session mode to '{}' for session '{}': {:?}",
+                        "[WelcomePanel] Successfully set session mode to '{}' for session '{}'",
                         mode,
-                        session_id,
-                        response
+                        session_id
                     );
                 }
                 Err(e) => {
@@ -1216,7 +1575,9 @@ impl WelcomePanel {
                     } else {
                         &s.session_id
                     };
-                    Self::session_label(short_id)
+                    s.title
+                        .clone()
+                        .unwrap_or_else(|| Self::session_label(short_id))
                 })
                 .collect();
 
@@ -1583,6 +1944,9 @@ impl Render for WelcomePanel {
                             if self.is_session_loading {
                                 chat = chat.agent_status_text(t!("welcome.loading").to_string());
                             }
+                            if self.no_agents_configured() {
+                                chat = chat.disabled_reason(NoAgentsState::disabled_reason());
+                            }
 
                             // log::debug!(
                             //     "[WelcomePanel::render] Creating ChatInputBox with {} code_selections",
@@ -1689,7 +2053,8 @@ impl Render for WelcomePanel {
                                     this.handle_send_task(window, cx);
                                 }))
                         },
-                    ),
+                    )
+                    .child(self.render_dashboard(cx)),
             )
     }
 }
@@ -0,0 +1,101 @@
+//! Lightweight git status helpers (shells out to the `git` CLI)
+
+use std::path::Path;
+use std::process::Command;
+
+/// Summary of a repository's working tree state
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Current branch name, or `None` for a detached HEAD
+    pub branch: Option<String>,
+    /// Whether the working tree has uncommitted or untracked changes
+    pub dirty: bool,
+    /// Commits ahead of the upstream branch
+    pub ahead: u32,
+    /// Commits behind the upstream branch
+    pub behind: u32,
+}
+
+/// Get the git status for a workspace path
+///
+/// Returns `None` if the path is not inside a git repository or `git` is unavailable.
+/// This is blocking; call it from a background task.
+pub fn git_status(workspace_path: &Path) -> Option<GitStatus> {
+    if !workspace_path.is_dir() {
+        return None;
+    }
+
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(workspace_path)
+        .args(["status", "--porcelain=v2", "--branch"]);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(parse_porcelain_v2(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse the output of `git status --porcelain=v2 --branch`
+fn parse_porcelain_v2(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            if head != "(detached)" {
+                status.branch = Some(head.to_string());
+            }
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            for part in ab.split_whitespace() {
+                if let Some(ahead) = part.strip_prefix('+') {
+                    status.ahead = ahead.parse().unwrap_or(0);
+                } else if let Some(behind) = part.strip_prefix('-') {
+                    status.behind = behind.parse().unwrap_or(0);
+                }
+            }
+        } else if !line.starts_with('#') && !line.is_empty() {
+            status.dirty = true;
+        }
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clean_branch_with_upstream() {
+        let output = "# branch.oid 1234abcd\n\
+                      # branch.head main\n\
+                      # branch.upstream origin/main\n\
+                      # branch.ab +2 -1\n";
+        let status = parse_porcelain_v2(output);
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert!(!status.dirty);
+        assert_eq!((status.ahead, status.behind), (2, 1));
+    }
+
+    #[test]
+    fn test_parse_dirty_detached_head() {
+        let output = "# branch.oid 1234abcd\n\
+                      # branch.head (detached)\n\
+                      1 .M N... 100644 100644 100644 aaaa bbbb src/main.rs\n\
+                      ? notes.txt\n";
+        let status = parse_porcelain_v2(output);
+        assert_eq!(status.branch, None);
+        assert!(status.dirty);
+        assert_eq!((status.ahead, status.behind), (0, 0));
+    }
+}
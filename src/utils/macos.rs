@@ -0,0 +1,83 @@
+//! macOS-specific helpers
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Extended attribute Gatekeeper sets on downloaded files
+const QUARANTINE_XATTR: &str = "com.apple.quarantine";
+
+/// Gatekeeper state of the running app bundle
+#[derive(Debug, Clone)]
+pub struct QuarantineStatus {
+    pub bundle_path: PathBuf,
+    /// The bundle still carries the `com.apple.quarantine` attribute
+    pub quarantined: bool,
+    /// `codesign --verify` accepts the bundle
+    pub signed: bool,
+    /// Gatekeeper (`spctl --assess`) accepts the bundle, i.e. it is signed and notarized
+    pub notarized: bool,
+}
+
+impl QuarantineStatus {
+    /// Whether the user should be told how to clear the quarantine attribute
+    pub fn needs_attention(&self) -> bool {
+        !self.notarized && (self.quarantined || !self.signed)
+    }
+
+    /// Command that removes the quarantine attribute from the bundle
+    pub fn fix_command(&self) -> String {
+        format!(
+            "xattr -dr {} \"{}\"",
+            QUARANTINE_XATTR,
+            self.bundle_path.display()
+        )
+    }
+}
+
+/// Inspect the running app bundle (blocking, runs `xattr`, `codesign` and `spctl`)
+///
+/// Returns `None` when the app is not running from an `.app` bundle, e.g. `cargo run`.
+pub fn quarantine_status() -> Option<QuarantineStatus> {
+    let exe = std::env::current_exe().ok()?;
+    let bundle_path = app_bundle_path(&exe)?.to_path_buf();
+
+    let quarantined = command_succeeds(
+        Command::new("xattr")
+            .arg("-p")
+            .arg(QUARANTINE_XATTR)
+            .arg(&bundle_path),
+    );
+    let signed = command_succeeds(
+        Command::new("codesign")
+            .args(["--verify", "--deep", "--strict"])
+            .arg(&bundle_path),
+    );
+    let notarized = signed
+        && command_succeeds(
+            Command::new("spctl")
+                .args(["--assess", "--type", "execute"])
+                .arg(&bundle_path),
+        );
+
+    let status = QuarantineStatus {
+        bundle_path,
+        quarantined,
+        signed,
+        notarized,
+    };
+    log::info!("App bundle Gatekeeper status: {:?}", status);
+    Some(status)
+}
+
+/// The enclosing `.app` directory of an executable inside a bundle
+fn app_bundle_path(exe: &Path) -> Option<&Path> {
+    exe.ancestors()
+        .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+}
+
+fn command_succeeds(command: &mut Command) -> bool {
+    command
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
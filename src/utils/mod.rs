@@ -1,6 +1,10 @@
 pub mod clipboard;
+pub mod diagnostics;
 pub mod external_editor;
 pub mod file;
+pub mod git;
+#[cfg(target_os = "macos")]
+pub mod macos;
 pub mod time;
 pub mod tool_call;
 /// Open a folder picker dialog and return the selected path
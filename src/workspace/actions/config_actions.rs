@@ -3,8 +3,8 @@ use gpui::*;
 use crate::{
     AppState,
     app::actions::{
-        AddAgent, ChangeConfigPath, ReloadAgentConfig, RemoveAgent, RestartAgent, SetUploadDir,
-        UpdateAgent,
+        AddAgent, ChangeConfigPath, ReloadAgentConfig, RemoveAgent, RestartAgent,
+        RestoreConfigBackup, SetUploadDir, UpdateAgent,
     },
 };
 
@@ -22,6 +22,13 @@ pub fn add_agent(action: &AddAgent, cx: &mut App) {
         command: action.command.clone(),
         args: action.args.clone(),
         env: action.env.clone(),
+        icon: action.icon.clone(),
+        order: None,
+        pinned: false,
+        request_timeout_secs: None,
+        startup_timeout_secs: None,
+        working_dir_override: action.working_dir_override.clone(),
+        modes: Vec::new(),
         nodejs_path: None,
     };
 
@@ -53,6 +60,13 @@ pub fn update_agent(action: &UpdateAgent, cx: &mut App) {
         command: action.command.clone(),
         args: action.args.clone(),
         env: action.env.clone(),
+        icon: action.icon.clone(),
+        order: None,
+        pinned: false,
+        request_timeout_secs: None,
+        startup_timeout_secs: None,
+        working_dir_override: action.working_dir_override.clone(),
+        modes: Vec::new(),
         nodejs_path: None,
     };
 
@@ -149,6 +163,31 @@ pub fn reload_agent_config(_action: &ReloadAgentConfig, cx: &mut App) {
         .detach();
 }
 
+pub fn restore_config_backup(action: &RestoreConfigBackup, cx: &mut App) {
+    let agent_config_service = match AppState::global(cx).agent_config_service() {
+        Some(service) => service.clone(),
+        None => {
+            log::error!("AgentConfigService not initialized");
+            return;
+        }
+    };
+
+    let path = action.path.clone();
+
+    let _ = cx
+        .spawn(
+            async move |_cx| match agent_config_service.restore_backup(&path).await {
+                Ok(()) => {
+                    log::info!("Successfully restored configuration from: {:?}", path);
+                }
+                Err(e) => {
+                    log::error!("Failed to restore configuration backup: {}", e);
+                }
+            },
+        )
+        .detach();
+}
+
 pub fn set_upload_dir(action: &SetUploadDir, cx: &mut App) {
     let agent_config_service = match AppState::global(cx).agent_config_service() {
         Some(service) => service.clone(),
@@ -209,7 +248,10 @@ pub fn change_config_path(action: &ChangeConfigPath, cx: &mut App) {
                 }
             }
             Err(e) => {
-                log::error!("Invalid config file format: {}", e);
+                log::error!(
+                    "Invalid config file format: {}",
+                    crate::core::config::describe_json_error(&json, &e)
+                );
             }
         },
         Err(e) => {
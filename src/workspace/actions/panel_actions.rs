@@ -6,9 +6,11 @@ use gpui_component::{
 use std::sync::Arc;
 
 use crate::{
-    AppState, ConversationPanel, OpenSessionManager, PanelAction, SessionManagerPanel,
-    SettingsPanel, ToggleDockToggleButton, TogglePanelVisible, WelcomePanel,
-    app::actions::{PanelCommand, PanelKind, Submit},
+    AppState, ConversationPanel, LogViewerPanel, OpenSessionManager, PanelAction,
+    SessionManagerPanel, SettingsPanel, ToggleDockToggleButton, TogglePanelVisible, WelcomePanel,
+    app::actions::{
+        OpenLogViewer, PanelCommand, PanelKind, ResetLayout, RevealSessionMatch, Submit,
+    },
     panels::{
         DockPanel,
         dock_panel::{DockPanelContainer, DockPanelState},
@@ -98,6 +100,51 @@ impl DockWorkspace {
         });
     }
 
+    /// Open a session and scroll to the message matched by a global search
+    pub(in crate::workspace) fn on_action_reveal_session_match(
+        &mut self,
+        action: &RevealSessionMatch,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_conversation_panel(Some(action.session_id.clone()), window, cx);
+
+        let items = self.dock_area.read(cx).center().clone();
+        match Self::active_conversation_in_item(&items, &action.session_id, cx) {
+            Some(conversation) => conversation.update(cx, |panel, cx| {
+                panel.reveal_search_match(action.query.clone(), action.occurrence, cx);
+            }),
+            None => log::warn!(
+                "No conversation panel to reveal search match: session_id={}",
+                action.session_id
+            ),
+        }
+    }
+
+    /// The shown conversation panel for a session, after it has been activated
+    fn active_conversation_in_item(
+        item: &DockItem,
+        session_id: &str,
+        cx: &App,
+    ) -> Option<Entity<ConversationPanel>> {
+        let panel = match item {
+            DockItem::Tabs { view, .. } => view.read(cx).active_panel(cx)?,
+            DockItem::Panel { view, .. } => view.clone(),
+            DockItem::Split { items, .. } => {
+                return items
+                    .iter()
+                    .find_map(|item| Self::active_conversation_in_item(item, session_id, cx));
+            }
+            DockItem::Tiles { .. } => return None,
+        };
+        if !Self::panel_matches_session(&panel, session_id, cx) {
+            return None;
+        }
+        let container = panel.view().downcast::<DockPanelContainer>().ok()?;
+        let agent_studio = container.read(cx).agent_studio.clone()?;
+        agent_studio.downcast::<ConversationPanel>().ok()
+    }
+
     pub(in crate::workspace) fn resume_session_if_needed(session_id: &str, cx: &mut Context<Self>) {
         let agent_service = AppState::global(cx).agent_service().cloned();
         if let Some(agent_service) = agent_service {
@@ -476,7 +523,65 @@ impl DockWorkspace {
                     self.show_tool_call_detail_panel((**tool_call).clone(), window, cx);
                 }
             },
+            PanelCommand::Split(panel) => match panel {
+                PanelKind::Conversation {
+                    session_id: Some(session_id),
+                } if !session_id.is_empty() => {
+                    self.split_conversation_panel(session_id.clone(), window, cx);
+                }
+                _ => {
+                    log::warn!("Split is only supported for session conversation panels");
+                }
+            },
+        }
+    }
+
+    /// Open a session's conversation in a pane beside the current center content
+    ///
+    /// The first split adds a second column; further sessions are added as tabs
+    /// to the last column so panes don't keep getting narrower.
+    fn split_conversation_panel(
+        &mut self,
+        session_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.activate_existing_session_panel(&session_id, window, cx) {
+            return;
+        }
+
+        Self::resume_session_if_needed(&session_id, cx);
+
+        let panel: Arc<dyn PanelView> = Arc::new(Self::panel_for_session(session_id, window, cx));
+        let center = self.dock_area.read(cx).center().clone();
+
+        if let DockItem::Split {
+            axis: Axis::Horizontal,
+            items,
+            ..
+        } = &center
+        {
+            if let Some(DockItem::Tabs { view, .. }) = items.last() {
+                view.update(cx, |tab_panel, cx| {
+                    tab_panel.add_panel(panel, window, cx);
+                });
+                return;
+            }
         }
+
+        let weak_dock_area = self.dock_area.downgrade();
+        let side_tabs = DockItem::tabs(vec![panel], &weak_dock_area, window, cx);
+        let split = DockItem::split_with_sizes(
+            Axis::Horizontal,
+            vec![center, side_tabs],
+            vec![None, None],
+            &weak_dock_area,
+            window,
+            cx,
+        );
+        self.dock_area.update(cx, |dock_area, cx| {
+            dock_area.set_center(split, window, cx);
+        });
     }
 
     fn add_conversation_panel_to(
@@ -669,6 +774,31 @@ impl DockWorkspace {
         });
     }
 
+    pub(in crate::workspace) fn on_action_open_log_viewer(
+        &mut self,
+        _: &OpenLogViewer,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let panel = Arc::new(DockPanelContainer::panel::<LogViewerPanel>(window, cx));
+
+        self.dock_area.update(cx, |dock_area, cx| {
+            dock_area.add_panel(panel, DockPlacement::Center, None, window, cx);
+        });
+    }
+
+    /// Replace the current dock arrangement with the default layout
+    pub(in crate::workspace) fn on_action_reset_layout(
+        &mut self,
+        _: &ResetLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        log::info!("Resetting dock layout to default");
+        Self::reset_default_layout(self.dock_area.downgrade(), window, cx);
+        self.last_layout_state = Some(self.dock_area.read(cx).dump(cx));
+    }
+
     pub(in crate::workspace) fn on_action_open_session_manager(
         &mut self,
         _: &OpenSessionManager,
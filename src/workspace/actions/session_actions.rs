@@ -8,9 +8,9 @@ use gpui_component::{
 use std::sync::Arc;
 
 use crate::{
-    AppState, ConversationPanel, CreateTaskFromWelcome, NewSessionConversationPanel,
-    SendMessageToSession,
-    app::actions::{AddCodeSelection, CancelSession},
+    AppSettings, AppState, ConversationPanel, CreateTaskFromWelcome, NewSessionConversationPanel,
+    PanelAction, SendMessageToSession, StartTaskSession,
+    app::actions::{AddCodeSelection, CancelSession, SetSessionMode},
     panels::{DockPanel, dock_panel::DockPanelContainer},
 };
 
@@ -52,6 +52,7 @@ impl DockWorkspace {
         );
 
         let welcome_session = AppState::global(cx).welcome_session().cloned();
+        let auto_title = AppSettings::global(cx).auto_title_sessions;
 
         let agent_service = match AppState::global(cx).agent_service() {
             Some(service) => service.clone(),
@@ -290,21 +291,20 @@ impl DockWorkspace {
                 log::info!("[DockWorkspace] Task created ({})", task_id);
             });
 
-            // Step 5: Build content blocks and send message
-            let mut prompt_blocks: Vec<acp::ContentBlock> = Vec::new();
-            // Add code selections as text context before the user message
-            for selection in code_selections.iter() {
-                let code_context = format_code_selection_as_context(selection);
-                prompt_blocks.push(code_context.into());
-            }
-            prompt_blocks.push(task_input.into());
-            for (image_content, _filename) in images.iter() {
-                prompt_blocks.push(acp::ContentBlock::Image(image_content.clone()));
+            if auto_title
+                && let Err(e) = agent_service
+                    .title_session_from_message(&agent_name, &session_id_for_send, &task_input)
+                    .await
+            {
+                log::warn!("Failed to title session {}: {}", session_id_for_send, e);
             }
+
+            // Step 5: Build content blocks and send message
+            let prompt_blocks = prompt_content(task_input, &images, &code_selections);
             log::debug!("Built {} content blocks for prompt", prompt_blocks.len());
 
             match message_service
-                .send_message_to_session(&agent_name, &session_id_for_send, prompt_blocks)
+                .send_message_to_session(&session_id_for_send, prompt_blocks)
                 .await
             {
                 Ok(response) => {
@@ -331,6 +331,105 @@ impl DockWorkspace {
         .detach();
     }
 
+    /// Handle StartTaskSession action - create a session for a task and open it
+    pub(in crate::workspace) fn on_action_start_task_session(
+        &mut self,
+        action: &StartTaskSession,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let task_id = action.task_id.clone();
+
+        let Some(workspace_service) = AppState::global(cx).workspace_service().cloned() else {
+            log::error!("WorkspaceService not initialized");
+            return;
+        };
+        let Some(agent_service) = AppState::global(cx).agent_service().cloned() else {
+            log::error!("AgentService not initialized");
+            return;
+        };
+        let agent_config_service = AppState::global(cx).agent_config_service().cloned();
+
+        cx.spawn_in(window, async move |_this, window| {
+            let Some(task) = workspace_service.get_task(&task_id).await else {
+                log::error!("Task not found: {}", task_id);
+                return;
+            };
+
+            // Reuse the session the task is already linked to
+            if let Some(session_id) = task.session_id.clone() {
+                _ = window.update(|window, cx| {
+                    window.dispatch_action(
+                        Box::new(PanelAction::show_conversation(Some(session_id))),
+                        cx,
+                    );
+                });
+                return;
+            }
+
+            let Some(workspace) = workspace_service.get_workspace(&task.workspace_id).await else {
+                log::error!(
+                    "Workspace not found for task {}: {}",
+                    task_id,
+                    task.workspace_id
+                );
+                return;
+            };
+
+            let mcp_servers = if let Some(service) = agent_config_service {
+                service
+                    .list_mcp_servers()
+                    .await
+                    .into_iter()
+                    .filter(|(_, config)| config.enabled)
+                    .map(|(name, config)| config.to_acp_mcp_server(name))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            log::info!(
+                "Starting session for task {} with agent '{}' in {:?}",
+                task_id,
+                task.agent_name,
+                workspace.path
+            );
+
+            let session_id = match agent_service
+                .create_session_with_mcp_and_cwd(&task.agent_name, mcp_servers, workspace.path)
+                .await
+            {
+                Ok(session_id) => session_id,
+                Err(e) => {
+                    log::error!("Failed to start session for task {}: {}", task_id, e);
+                    _ = window.update(|window, cx| {
+                        struct TaskSessionError;
+                        let note = Notification::error(format!("Failed to start session: {}", e))
+                            .id::<TaskSessionError>();
+                        window.push_notification(note, cx);
+                    });
+                    return;
+                }
+            };
+
+            // Linking the session also moves the task to in-progress
+            if let Err(e) = workspace_service
+                .set_task_session(&task_id, session_id.clone())
+                .await
+            {
+                log::error!("Failed to associate session with task: {}", e);
+            }
+
+            _ = window.update(|window, cx| {
+                window.dispatch_action(
+                    Box::new(PanelAction::show_conversation(Some(session_id))),
+                    cx,
+                );
+            });
+        })
+        .detach();
+    }
+
     /// Create a panel specifically for a session (ConversationPanel only)
     pub fn panel_for_session(
         session_id: String,
@@ -368,14 +467,25 @@ impl DockWorkspace {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let session_id = action.session_id.clone();
-        let message = action.message.clone();
-        let images = action.images.clone();
-        let code_selections = action.code_selections.clone();
+        Self::send_message_to_session(action.clone(), cx);
+    }
+
+    /// Send a message to an existing session as a new prompt turn
+    ///
+    /// Needs no window, so queued prompts can be sent when a turn ends
+    /// while the app is in the background.
+    pub(crate) fn send_message_to_session(action: SendMessageToSession, cx: &mut App) {
+        let SendMessageToSession {
+            session_id,
+            message,
+            images,
+            code_selections,
+        } = action;
 
         log::info!("Sending message to session: {}", session_id);
 
-        cx.spawn(async move |_this, cx| {
+        let auto_title = AppSettings::global(cx).auto_title_sessions;
+        cx.spawn(async move |cx| {
             let agent_service = cx.update(|cx| AppState::global(cx).agent_service().cloned());
             let message_service = cx.update(|cx| AppState::global(cx).message_service().cloned());
 
@@ -398,17 +508,16 @@ impl DockWorkspace {
                 }
             };
 
-            let mut prompt_blocks: Vec<acp::ContentBlock> = Vec::new();
-            // Add code selections as text context before the user message
-            for selection in code_selections.iter() {
-                let code_context = format_code_selection_as_context(selection);
-                prompt_blocks.push(code_context.into());
-            }
-            prompt_blocks.push(message.clone().into());
-            for (image_content, _filename) in images.iter() {
-                prompt_blocks.push(acp::ContentBlock::Image(image_content.clone()));
+            if auto_title
+                && let Err(e) = agent_service
+                    .title_session_from_message(&agent_name, &session_id, &message)
+                    .await
+            {
+                log::warn!("Failed to title session {}: {}", session_id, e);
             }
 
+            let prompt_blocks = prompt_content(message, &images, &code_selections);
+
             log::debug!(
                 "Sending prompt to agent {} for session {}",
                 agent_name,
@@ -416,7 +525,7 @@ impl DockWorkspace {
             );
 
             match message_service
-                .send_message_to_session(&agent_name, &session_id, prompt_blocks)
+                .send_message_to_session(&session_id, prompt_blocks)
                 .await
             {
                 Ok(_response) => {
@@ -478,6 +587,52 @@ impl DockWorkspace {
     }
 }
 
+/// Switch a session's mode; the conversation header re-renders with the new mode
+pub fn set_session_mode(action: &SetSessionMode, cx: &mut App) {
+    let Some(agent_service) = AppState::global(cx).agent_service().cloned() else {
+        log::error!("AgentService not initialized");
+        return;
+    };
+    let SetSessionMode {
+        session_id,
+        mode_id,
+    } = action.clone();
+
+    cx.spawn(async move |cx| {
+        if let Err(e) = agent_service.set_session_mode(&session_id, &mode_id).await {
+            log::error!(
+                "Failed to set mode {} for session {}: {}",
+                mode_id,
+                session_id,
+                e
+            );
+        }
+        let _ = cx.update(|cx| cx.refresh_windows());
+    })
+    .detach();
+}
+
+/// Build the content of a prompt turn from what the user attached.
+///
+/// Code selections go first as text context, then the message itself,
+/// then any images.
+fn prompt_content(
+    message: String,
+    images: &[(acp::ImageContent, String)],
+    code_selections: &[AddCodeSelection],
+) -> Vec<acp::ContentBlock> {
+    code_selections
+        .iter()
+        .map(|selection| format_code_selection_as_context(selection).into())
+        .chain(std::iter::once(message.into()))
+        .chain(
+            images
+                .iter()
+                .map(|(image_content, _filename)| acp::ContentBlock::Image(image_content.clone())),
+        )
+        .collect()
+}
+
 /// Format a code selection as text context for the ACP prompt.
 ///
 /// Produces a markdown-style code block with file path and line range metadata,
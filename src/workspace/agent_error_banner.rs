@@ -0,0 +1,94 @@
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable,
+    button::{Button, ButtonVariants as _},
+    h_flex, v_flex,
+};
+use rust_i18n::t;
+
+use crate::app::{agent_startup_errors::AgentStartupErrors, title_bar::OpenSettings};
+use crate::workspace::DockWorkspace;
+
+impl DockWorkspace {
+    /// Banner listing agents that failed to start, shown above the dock area
+    pub(in crate::workspace) fn render_agent_error_banner(
+        &self,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        let failures = AgentStartupErrors::visible_failures(cx);
+        let (first_name, first_error) = failures.first()?.clone();
+
+        let title = if failures.len() == 1 {
+            t!("agent_errors.banner.title_one", agent = first_name).to_string()
+        } else {
+            t!("agent_errors.banner.title_many", count = failures.len()).to_string()
+        };
+        // The first line of the error is the summary; the rest is command and stderr
+        let summary = first_error.lines().next().unwrap_or_default().to_string();
+        let report = AgentStartupErrors::report(&failures);
+
+        Some(
+            h_flex()
+                .id("agent-error-banner")
+                .w_full()
+                .px_3()
+                .py_2()
+                .gap_3()
+                .items_center()
+                .bg(cx.theme().danger.opacity(0.12))
+                .border_b_1()
+                .border_color(cx.theme().danger)
+                .child(
+                    Icon::new(IconName::TriangleAlert)
+                        .small()
+                        .text_color(cx.theme().danger),
+                )
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .min_w_0()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .child(title),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .truncate()
+                                .child(summary),
+                        ),
+                )
+                .child(
+                    Button::new("agent-error-open-settings")
+                        .label(t!("agent_errors.banner.open_settings").to_string())
+                        .icon(IconName::Settings)
+                        .outline()
+                        .small()
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(Box::new(OpenSettings), cx);
+                        }),
+                )
+                .child(
+                    Button::new("agent-error-copy")
+                        .label(t!("agent_errors.banner.copy_error").to_string())
+                        .icon(IconName::Copy)
+                        .outline()
+                        .small()
+                        .on_click(move |_, _, cx| {
+                            cx.write_to_clipboard(ClipboardItem::new_string(report.clone()));
+                        }),
+                )
+                .child(
+                    Button::new("agent-error-dismiss")
+                        .icon(IconName::Close)
+                        .ghost()
+                        .small()
+                        .on_click(|_, _, cx| AgentStartupErrors::dismiss(cx)),
+                )
+                .into_any_element(),
+        )
+    }
+}
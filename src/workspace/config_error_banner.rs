@@ -0,0 +1,104 @@
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    notification::Notification,
+    v_flex,
+};
+use rust_i18n::t;
+
+use crate::app::config_recovery::ConfigRecovery;
+use crate::workspace::DockWorkspace;
+
+impl DockWorkspace {
+    /// Banner for a config file that failed to parse, shown above the dock area
+    /// while the app runs on the default config
+    pub(in crate::workspace) fn render_config_error_banner(
+        &self,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        let failure = ConfigRecovery::failure(cx)?;
+        let kept_at = match &failure.preserved_path {
+            Some(path) => t!(
+                "config_recovery.banner.kept_at",
+                path = path.display().to_string()
+            ),
+            None => t!(
+                "config_recovery.banner.not_moved",
+                path = failure.config_path.display().to_string()
+            ),
+        };
+
+        Some(
+            h_flex()
+                .id("config-error-banner")
+                .w_full()
+                .px_3()
+                .py_2()
+                .gap_3()
+                .items_start()
+                .bg(cx.theme().danger.opacity(0.12))
+                .border_b_1()
+                .border_color(cx.theme().danger)
+                .child(
+                    Icon::new(IconName::TriangleAlert)
+                        .small()
+                        .text_color(cx.theme().danger),
+                )
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .min_w_0()
+                        .gap_1()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .child(t!("config_recovery.banner.title").to_string()),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(kept_at.to_string()),
+                        )
+                        .child(
+                            // The detail has a snippet with a caret under the error column
+                            div()
+                                .text_xs()
+                                .font_family(cx.theme().mono_font_family.clone())
+                                .child(failure.detail.clone()),
+                        ),
+                )
+                .child(
+                    Button::new("config-error-edit")
+                        .label(t!("config_recovery.banner.edit").to_string())
+                        .icon(IconName::File)
+                        .outline()
+                        .small()
+                        .on_click(|_, window, cx| {
+                            if let Err(error) = ConfigRecovery::edit(cx) {
+                                window.push_notification(Notification::error(error), cx);
+                            }
+                        }),
+                )
+                .child(
+                    Button::new("config-error-load")
+                        .label(t!("config_recovery.banner.load_fixed").to_string())
+                        .icon(IconName::Check)
+                        .outline()
+                        .small()
+                        .on_click(|_, _, cx| ConfigRecovery::load_fixed(cx)),
+                )
+                .child(
+                    Button::new("config-error-reset")
+                        .label(t!("config_recovery.banner.reset").to_string())
+                        .ghost()
+                        .small()
+                        .on_click(|_, _, cx| ConfigRecovery::keep_default(cx)),
+                )
+                .into_any_element(),
+        )
+    }
+}
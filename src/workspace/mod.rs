@@ -5,14 +5,30 @@ use gpui_component::dock::{
     DockArea, DockAreaState, DockEvent, DockItem, DockPlacement, PanelState,
 };
 use smol::Timer;
-use std::{sync::Arc, time::Duration};
+use std::{cell::Cell, rc::Rc, sync::Arc, time::Duration};
 
 /// Panels that should be excluded from loading and saving
 const EXCLUDED_PANELS: &[&str] = &["CodeEditorPanel", "ToolCallDetailPanel"];
 
+/// Longest time the main window stays hidden while waiting for its first frame
+const FIRST_FRAME_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Delay before the startup update check, so it stays out of the way of initialization
+const UPDATE_CHECK_STARTUP_DELAY: Duration = Duration::from_secs(15);
+
+/// How often the running app looks at whether a periodic update check is due
+const UPDATE_CHECK_POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Delay before the first session storage compaction after startup
+const STORAGE_COMPACTION_STARTUP_DELAY: Duration = Duration::from_secs(60);
+
+/// Time between two background session storage compactions
+const STORAGE_COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 use crate::{
-    AppSettings, AppTitleBar, CodeEditorPanel, ConversationPanel, SessionManagerPanel, TaskPanel,
-    TerminalPanel,
+    AppSettings, AppState, AppTitleBar, CodeEditorPanel, ConversationPanel, PanelAction,
+    SessionManagerPanel, TaskPanel, TerminalPanel,
+    app::desktop_notifications::DesktopNotifications,
     core::updater::{UpdateCheckResult, UpdateManager},
     panels::dock_panel::DockPanelContainer,
 };
@@ -21,6 +37,10 @@ use self::startup::StartupState;
 
 // Action handlers module
 pub mod actions;
+mod agent_error_banner;
+mod config_error_banner;
+#[cfg(target_os = "macos")]
+mod quarantine_warning;
 mod startup;
 
 const MAIN_DOCK_AREA: DockAreaTab = DockAreaTab {
@@ -36,7 +56,15 @@ pub struct DockWorkspace {
     _save_layout_task: Option<Task<()>>,
     startup_state: StartupState,
     startup_completed: bool,
+    /// Set while the startup wizard is re-run from settings (can be cancelled)
+    startup_rerun: bool,
     update_checked_on_startup: bool,
+    /// Whether the background session storage compaction has been started
+    storage_compaction_started: bool,
+    /// Whether the last active session was reopened after startup
+    last_session_restored: bool,
+    #[cfg(target_os = "macos")]
+    quarantine_checked: bool,
 }
 
 struct DockAreaTab {
@@ -74,11 +102,23 @@ impl DockWorkspace {
             let dock_area = dock_area.clone();
             move |_, cx| {
                 let state = dock_area.read(cx).dump(cx);
+                let workspace_service = AppState::global(cx).workspace_service().cloned();
+                let agent_manager = AppState::global(cx).agent_manager().cloned();
                 cx.background_executor().spawn(async move {
                     // Save layout before quitting
                     if let Err(e) = Self::save_state(&state) {
                         log::warn!("Failed to save layout state: {}", e);
                     }
+                    // Write any pending debounced workspace changes
+                    if let Some(service) = workspace_service {
+                        if let Err(e) = service.flush().await {
+                            log::warn!("Failed to save workspace config: {}", e);
+                        }
+                    }
+                    // Stop agent processes so none outlive the app
+                    if let Some(manager) = agent_manager {
+                        manager.shutdown_all().await;
+                    }
                 })
             }
         })
@@ -168,35 +208,135 @@ impl DockWorkspace {
             _save_layout_task: None,
             startup_state: StartupState::new(),
             startup_completed: crate::themes::startup_completed(),
+            startup_rerun: false,
             update_checked_on_startup: false,
+            storage_compaction_started: false,
+            last_session_restored: false,
+            #[cfg(target_os = "macos")]
+            quarantine_checked: false,
         }
     }
 
-    /// Check for updates on startup if auto-check is enabled
-    fn maybe_check_updates_on_startup(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+    /// Reopen the conversation that was active when the app was last used
+    ///
+    /// Looks up the active workspace's last session; nothing happens if its
+    /// task was removed. A panel restored with the layout is just focused.
+    fn restore_last_active_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.last_session_restored {
+            return;
+        }
+        self.last_session_restored = true;
+
+        let Some(workspace_service) = AppState::global(cx).workspace_service().cloned() else {
+            return;
+        };
+        cx.spawn_in(window, async move |this, window| {
+            let Some(workspace) = workspace_service.get_active_workspace().await else {
+                return;
+            };
+            let Some(session_id) = workspace_service.last_active_session(&workspace.id).await
+            else {
+                return;
+            };
+            log::info!(
+                "Reopening last active session {} of workspace {}",
+                session_id,
+                workspace.name
+            );
+            _ = this.update_in(window, |this, window, cx| {
+                this.show_conversation_panel(Some(session_id), window, cx);
+            });
+        })
+        .detach();
+    }
+
+    /// Run automatic update checks: on startup and then every configured interval
+    ///
+    /// Settings are re-read before every check so changes apply without a
+    /// restart. Failures are only logged; the user is never interrupted.
+    fn start_update_checks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.update_checked_on_startup {
             return;
         }
         self.update_checked_on_startup = true;
 
-        if !AppSettings::global(cx).auto_check_on_startup {
+        cx.spawn_in(window, async move |_this, window| {
+            Timer::after(UPDATE_CHECK_STARTUP_DELAY).await;
+
+            let mut on_startup = true;
+            loop {
+                let Ok(update_manager) = window.update(|_, cx| {
+                    let settings = AppSettings::global(cx);
+                    let due = (on_startup
+                        && settings.auto_check_updates
+                        && settings.auto_check_on_startup)
+                        || settings.update_check_due();
+                    due.then(|| {
+                        UpdateManager::default().with_proxy(AppState::global(cx).proxy_config())
+                    })
+                }) else {
+                    break;
+                };
+                on_startup = false;
+
+                if let Some(update_manager) = update_manager {
+                    log::info!("Automatically checking for updates...");
+                    let result = update_manager.check_for_updates().await;
+                    match &result {
+                        UpdateCheckResult::UpdateAvailable(info) => {
+                            log::info!("Update available: {}", info.version);
+                        }
+                        UpdateCheckResult::NoUpdate => {
+                            log::info!("No updates available");
+                        }
+                        UpdateCheckResult::Error(err) => {
+                            log::warn!("Failed to check for updates: {}", err);
+                        }
+                    }
+                    let recorded = window.update(|_, cx| {
+                        AppSettings::global_mut(cx).record_update_check(&result);
+                    });
+                    if recorded.is_err() {
+                        break;
+                    }
+                }
+
+                Timer::after(UPDATE_CHECK_POLL_INTERVAL).await;
+            }
+        })
+        .detach();
+    }
+
+    /// Trim persisted sessions to the retention settings, on startup and then hourly
+    ///
+    /// Settings are re-read before every pass; nothing runs while all limits are off.
+    fn start_storage_compaction(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.storage_compaction_started {
             return;
         }
+        self.storage_compaction_started = true;
 
-        log::info!("Auto-checking for updates on startup...");
-        let update_manager = UpdateManager::default();
+        cx.spawn_in(window, async move |_this, window| {
+            Timer::after(STORAGE_COMPACTION_STARTUP_DELAY).await;
 
-        cx.spawn_in(window, async move |_this, _window| {
-            match update_manager.check_for_updates().await {
-                UpdateCheckResult::UpdateAvailable(info) => {
-                    log::info!("Update available: {}", info.version);
-                }
-                UpdateCheckResult::NoUpdate => {
-                    log::info!("No updates available");
-                }
-                UpdateCheckResult::Error(err) => {
-                    log::warn!("Failed to check for updates: {}", err);
+            loop {
+                let Ok(compaction) = window.update(|_, cx| {
+                    let policy = AppSettings::global(cx).retention_policy();
+                    if policy.is_unlimited() {
+                        return None;
+                    }
+                    AppState::global(cx).compact_session_storage(policy)
+                }) else {
+                    break;
+                };
+
+                if let Some(compaction) = compaction {
+                    if let Err(e) = compaction.await {
+                        log::warn!("Failed to compact session storage: {}", e);
+                    }
                 }
+
+                Timer::after(STORAGE_COMPACTION_INTERVAL).await;
             }
         })
         .detach();
@@ -416,6 +556,9 @@ impl DockWorkspace {
                 #[cfg(target_os = "linux")]
                 window_decorations: Some(gpui::WindowDecorations::Client),
                 kind: WindowKind::Normal,
+                // Shown after the first frame is rendered, so the compositor never
+                // maps an empty (transparent) surface
+                show: false,
                 ..Default::default()
             };
 
@@ -424,10 +567,25 @@ impl DockWorkspace {
                 cx.new(|cx| Root::new(agent_studio_view, window, cx))
             })?;
 
+            let shown = Rc::new(Cell::new(false));
             window
                 .update(cx, |_, window, cx| {
-                    window.activate_window();
+                    window.on_next_frame({
+                        let shown = shown.clone();
+                        move |window, _| show_window_once(&shown, window)
+                    });
+                    window.refresh();
                     window.set_window_title("Agent Studio");
+                    // Keep running in the tray instead of quitting when enabled
+                    window.on_window_should_close(cx, |window, cx| {
+                        if AppSettings::global(cx).close_to_tray
+                            && crate::system_tray::is_available(cx)
+                        {
+                            crate::system_tray::hide_to_tray(window, cx);
+                            return false;
+                        }
+                        true
+                    });
                     cx.on_release(|_, cx| {
                         // exit app
                         cx.quit();
@@ -436,11 +594,25 @@ impl DockWorkspace {
                 })
                 .expect("failed to update window");
 
+            // Never leave the window hidden if no frame gets presented while it is
+            // unmapped (some platforms don't draw invisible windows)
+            cx.spawn(async move |cx| {
+                Timer::after(FIRST_FRAME_TIMEOUT).await;
+                let _ = window.update(cx, |_, window, _| show_window_once(&shown, window));
+            })
+            .detach();
+
             Ok(window)
         })
     }
 }
 
+fn show_window_once(shown: &Cell<bool>, window: &mut Window) {
+    if !shown.replace(true) {
+        window.activate_window();
+    }
+}
+
 impl Render for DockWorkspace {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.ensure_startup_initialized(window, cx);
@@ -448,21 +620,41 @@ impl Render for DockWorkspace {
         if self.startup_state.is_complete() && !self.startup_completed {
             crate::themes::set_startup_completed(true);
             self.startup_completed = true;
+            self.startup_rerun = false;
         }
 
-        // Check for updates on startup (after startup wizard is complete)
+        // Start automatic update checks (after startup wizard is complete)
         if self.startup_completed {
-            self.maybe_check_updates_on_startup(window, cx);
+            self.start_update_checks(window, cx);
+            self.start_storage_compaction(window, cx);
+            self.restore_last_active_session(window, cx);
+            #[cfg(target_os = "macos")]
+            self.maybe_warn_quarantine(window, cx);
+        }
+
+        // Open the session of a clicked desktop notification
+        if let Some(session_id) = DesktopNotifications::take_pending_session(window, cx) {
+            cx.defer_in(window, move |this, window, cx| {
+                this.on_action_panel_action(
+                    &PanelAction::show_conversation(Some(session_id)),
+                    window,
+                    cx,
+                );
+            });
         }
 
         let sheet_layer = Root::render_sheet_layer(window, cx);
         let dialog_layer = Root::render_dialog_layer(window, cx);
         let notification_layer = Root::render_notification_layer(window, cx);
 
-        let content = if self.startup_completed || self.startup_state.is_complete() {
-            self.dock_area.clone().into_any_element()
+        let (banner, content) = if self.startup_completed || self.startup_state.is_complete() {
+            (
+                self.render_config_error_banner(cx)
+                    .or_else(|| self.render_agent_error_banner(cx)),
+                self.dock_area.clone().into_any_element(),
+            )
         } else {
-            self.render_startup(cx)
+            (None, self.render_startup(cx))
         };
 
         div()
@@ -472,16 +664,22 @@ impl Render for DockWorkspace {
             .on_action(cx.listener(Self::on_action_toggle_dock_toggle_button))
             .on_action(cx.listener(Self::on_action_open_setting_panel))
             .on_action(cx.listener(Self::on_action_open_session_manager))
+            .on_action(cx.listener(Self::on_action_open_log_viewer))
+            .on_action(cx.listener(Self::on_action_reveal_session_match))
+            .on_action(cx.listener(Self::on_action_reset_layout))
             .on_action(cx.listener(Self::on_action_new_session_conversation_panel))
             .on_action(cx.listener(Self::on_action_create_task_from_welcome))
             .on_action(cx.listener(Self::on_action_send_message_to_session))
             .on_action(cx.listener(Self::on_action_cancel_session))
+            .on_action(cx.listener(Self::on_action_start_task_session))
             .on_action(cx.listener(Self::on_action_open))
+            .on_action(cx.listener(Self::on_action_rerun_startup_wizard))
             .relative()
             .size_full()
             .flex()
             .flex_col()
             .child(self.title_bar.clone())
+            .children(banner)
             .child(content)
             .children(sheet_layer)
             .children(dialog_layer)
@@ -0,0 +1,114 @@
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, IconName, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants as _},
+    dialog::DialogButtonProps,
+    h_flex,
+    notification::Notification,
+    v_flex,
+};
+use rust_i18n::t;
+
+use crate::utils::macos::{self, QuarantineStatus};
+use crate::workspace::DockWorkspace;
+
+impl DockWorkspace {
+    /// Check the app bundle once and explain how to clear the Gatekeeper quarantine
+    pub(in crate::workspace) fn maybe_warn_quarantine(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.quarantine_checked {
+            return;
+        }
+        self.quarantine_checked = true;
+
+        if crate::themes::quarantine_warning_shown() {
+            return;
+        }
+
+        cx.spawn_in(window, async move |_this, window| {
+            let Some(status) = smol::unblock(macos::quarantine_status).await else {
+                return;
+            };
+            if !status.needs_attention() {
+                return;
+            }
+
+            _ = window.update(|window, cx| {
+                crate::themes::set_quarantine_warning_shown();
+                open_quarantine_dialog(status, window, cx);
+            });
+        })
+        .detach();
+    }
+}
+
+fn open_quarantine_dialog(status: QuarantineStatus, window: &mut Window, cx: &mut App) {
+    let command = status.fix_command();
+    let message = if status.signed {
+        t!("quarantine.dialog.quarantined")
+    } else {
+        t!("quarantine.dialog.unsigned")
+    }
+    .to_string();
+
+    window.open_dialog(cx, move |dialog, _window, cx| {
+        let command = command.clone();
+        dialog
+            .title(t!("quarantine.dialog.title").to_string())
+            .alert()
+            .button_props(
+                DialogButtonProps::default().ok_text(t!("quarantine.dialog.ok").to_string()),
+            )
+            .child(
+                v_flex()
+                    .w_full()
+                    .gap_3()
+                    .child(div().text_sm().child(message.clone()))
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t!("quarantine.dialog.command_hint").to_string()),
+                    )
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .items_center()
+                            .p_2()
+                            .rounded(cx.theme().radius)
+                            .bg(cx.theme().muted)
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .min_w_0()
+                                    .font_family("Monaco, 'Courier New', monospace")
+                                    .text_xs()
+                                    .child(command.clone()),
+                            )
+                            .child(
+                                Button::new("quarantine-copy-command")
+                                    .icon(IconName::Copy)
+                                    .label(t!("quarantine.dialog.copy").to_string())
+                                    .ghost()
+                                    .xsmall()
+                                    .on_click(move |_, window, cx| {
+                                        cx.write_to_clipboard(ClipboardItem::new_string(
+                                            command.clone(),
+                                        ));
+
+                                        struct CommandCopied;
+                                        let note = Notification::success(
+                                            t!("quarantine.dialog.copied").to_string(),
+                                        )
+                                        .id::<CommandCopied>();
+                                        window.push_notification(note, cx);
+                                    }),
+                            ),
+                    ),
+            )
+    });
+}
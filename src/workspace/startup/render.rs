@@ -16,8 +16,9 @@ use rust_i18n::t;
 
 use crate::{
     AppSettings, AppState,
-    app::actions::{SelectLocale, SwitchTheme, SwitchThemeMode},
-    assets::get_agent_icon,
+    app::actions::{FollowSystemThemeMode, SelectLocale, SwitchTheme, SwitchThemeMode},
+    app::themes,
+    assets::resolve_agent_icon,
     core::nodejs::NodeJsDetectionMode,
 };
 
@@ -176,6 +177,19 @@ impl DockWorkspace {
                     .w_full()
                     .max_w(px(960.))
                     .gap_8()
+                    .when(self.startup_rerun, |this| {
+                        this.child(
+                            h_flex().w_full().justify_end().child(
+                                Button::new("startup-cancel-rerun")
+                                    .label(t!("startup.cancel_rerun").to_string())
+                                    .icon(IconName::Close)
+                                    .ghost()
+                                    .on_click(cx.listener(|this, _ev, _window, cx| {
+                                        this.cancel_startup_wizard(cx);
+                                    })),
+                            ),
+                        )
+                    })
                     .child(
                         div()
                             .text_size(px(36.))
@@ -206,36 +220,31 @@ impl DockWorkspace {
         let current_locale = AppSettings::global(cx).locale.clone();
         let current_theme = cx.theme().theme_name().clone();
         let is_dark = cx.theme().mode.is_dark();
+        let follows_system = themes::follows_system_appearance(cx);
         let themes = ThemeRegistry::global(cx).sorted_themes();
 
-        let locale_buttons = h_flex()
-            .gap_2()
-            .child(
-                Button::new("startup-locale-en")
-                    .label(t!("startup.preferences.locale.en").to_string())
-                    .when(current_locale.as_ref() == "en", |btn| btn.primary())
-                    .when(current_locale.as_ref() != "en", |btn| btn.outline())
-                    .on_click(cx.listener(|_, _ev, window, cx| {
-                        window.dispatch_action(Box::new(SelectLocale("en".into())), cx);
-                    })),
-            )
-            .child(
-                Button::new("startup-locale-zh")
-                    .label(t!("startup.preferences.locale.zh_cn").to_string())
-                    .when(current_locale.as_ref() == "zh-CN", |btn| btn.primary())
-                    .when(current_locale.as_ref() != "zh-CN", |btn| btn.outline())
-                    .on_click(cx.listener(|_, _ev, window, cx| {
-                        window.dispatch_action(Box::new(SelectLocale("zh-CN".into())), cx);
-                    })),
-            );
+        let locale_buttons = h_flex().gap_2().flex_wrap().children(
+            crate::i18n::available_locales()
+                .into_iter()
+                .map(|(locale, name)| {
+                    let selected = current_locale.as_ref() == locale;
+                    Button::new(SharedString::from(format!("startup-locale-{locale}")))
+                        .label(name)
+                        .when(selected, |btn| btn.primary())
+                        .when(!selected, |btn| btn.outline())
+                        .on_click(cx.listener(move |_, _ev, window, cx| {
+                            window.dispatch_action(Box::new(SelectLocale(locale.into())), cx);
+                        }))
+                }),
+        );
 
         let theme_mode_buttons = h_flex()
             .gap_2()
             .child(
                 Button::new("startup-theme-light")
                     .label(t!("startup.preferences.mode.light").to_string())
-                    .when(!is_dark, |btn| btn.primary())
-                    .when(is_dark, |btn| btn.outline())
+                    .when(!follows_system && !is_dark, |btn| btn.primary())
+                    .when(follows_system || is_dark, |btn| btn.outline())
                     .on_click(cx.listener(|_, _ev, window, cx| {
                         window.dispatch_action(Box::new(SwitchThemeMode(ThemeMode::Light)), cx);
                     })),
@@ -243,12 +252,23 @@ impl DockWorkspace {
             .child(
                 Button::new("startup-theme-dark")
                     .label(t!("startup.preferences.mode.dark").to_string())
-                    .when(is_dark, |btn| btn.primary())
-                    .when(!is_dark, |btn| btn.outline())
+                    .when(!follows_system && is_dark, |btn| btn.primary())
+                    .when(follows_system || !is_dark, |btn| btn.outline())
                     .on_click(cx.listener(|_, _ev, window, cx| {
                         window.dispatch_action(Box::new(SwitchThemeMode(ThemeMode::Dark)), cx);
                     })),
-            );
+            )
+            .when(themes::system_appearance_supported(), |this| {
+                this.child(
+                    Button::new("startup-theme-auto")
+                        .label(t!("startup.preferences.mode.auto").to_string())
+                        .when(follows_system, |btn| btn.primary())
+                        .when(!follows_system, |btn| btn.outline())
+                        .on_click(cx.listener(|_, _ev, window, cx| {
+                            window.dispatch_action(Box::new(FollowSystemThemeMode), cx);
+                        })),
+                )
+            });
 
         let mut theme_buttons = h_flex().w_full().gap_2().flex_wrap();
         for (idx, theme_config) in themes.iter().enumerate() {
@@ -314,19 +334,32 @@ impl DockWorkspace {
                     .child(theme_buttons),
             );
 
-        let actions = h_flex().gap_3().mt_6().justify_end().child(
-            Button::new("startup-preferences-next")
-                .label(t!("startup.preferences.continue").to_string())
-                .primary()
-                .on_click(cx.listener(|this, _ev, _window, cx| {
-                    this.startup_state.intro_completed = true;
-                    if this.startup_state.step == 0 {
-                        this.startup_state.step = 1;
-                    }
-                    this.startup_state.advance_step_if_needed();
-                    cx.notify();
-                })),
-        );
+        let actions = h_flex()
+            .gap_3()
+            .mt_6()
+            .justify_end()
+            .child(
+                Button::new("startup-preferences-skip-all")
+                    .label(t!("startup.preferences.skip_all").to_string())
+                    .ghost()
+                    .on_click(cx.listener(|this, _ev, _window, cx| {
+                        this.startup_state.skip_to_workspace();
+                        cx.notify();
+                    })),
+            )
+            .child(
+                Button::new("startup-preferences-next")
+                    .label(t!("startup.preferences.continue").to_string())
+                    .primary()
+                    .on_click(cx.listener(|this, _ev, _window, cx| {
+                        this.startup_state.intro_completed = true;
+                        if this.startup_state.step == 0 {
+                            this.startup_state.step = 1;
+                        }
+                        this.startup_state.advance_step_if_needed();
+                        cx.notify();
+                    })),
+            );
 
         content = content.child(actions);
         content.into_any_element()
@@ -603,6 +636,10 @@ impl DockWorkspace {
                     .border_1()
                     .border_color(cx.theme().border)
                     .text_color(theme.colors.danger_foreground)
+                    // Parse errors carry a snippet with a caret that needs fixed-width columns
+                    .when(error.contains('\n'), |this| {
+                        this.font_family(theme.mono_font_family.clone())
+                    })
                     .child(format!("⚠ {}", error)),
             );
         }
@@ -624,8 +661,19 @@ impl DockWorkspace {
             for (idx, choice) in self.startup_state.agent_choices.iter().enumerate() {
                 let name = choice.name.clone();
                 let checked = choice.enabled;
-
-                let icon = get_agent_icon(&name);
+                let pinned = self
+                    .startup_state
+                    .default_agent_configs
+                    .get(&name)
+                    .is_some_and(|config| config.pinned);
+
+                let icon = resolve_agent_icon(
+                    &name,
+                    self.startup_state
+                        .default_agent_configs
+                        .get(&name)
+                        .and_then(|config| config.icon.as_deref()),
+                );
 
                 list = list.child(
                     h_flex()
@@ -677,6 +725,34 @@ impl DockWorkspace {
                                         .font_weight(FontWeight::MEDIUM)
                                         .text_color(theme.foreground)
                                         .child(name.clone()),
+                                )
+                                .child(
+                                    Button::new(("startup-agent-pin", idx))
+                                        .icon(if pinned {
+                                            Icon::new(crate::assets::Icon::StarFill)
+                                                .text_color(theme.warning)
+                                        } else {
+                                            Icon::new(IconName::Star)
+                                                .text_color(theme.muted_foreground)
+                                        })
+                                        .ghost()
+                                        .xsmall()
+                                        .disabled(disabled)
+                                        .tooltip(
+                                            if pinned {
+                                                t!("startup.agents.action.unpin")
+                                            } else {
+                                                t!("startup.agents.action.pin")
+                                            }
+                                            .to_string(),
+                                        )
+                                        .on_click(cx.listener({
+                                            let name = name.clone();
+                                            move |this, _, _, cx| {
+                                                this.startup_state.set_agent_pinned(&name, !pinned);
+                                                cx.notify();
+                                            }
+                                        })),
                                 ),
                         )
                         .child(
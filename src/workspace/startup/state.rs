@@ -3,7 +3,8 @@ use std::path::PathBuf;
 
 use gpui::*;
 
-use crate::core::config::{AgentProcessConfig, Config};
+use crate::core::agent_templates::agent_templates;
+use crate::core::config::{AgentProcessConfig, Config, compare_agents, describe_json_error};
 
 #[derive(Clone, Debug)]
 pub(in crate::workspace) struct AgentChoice {
@@ -128,6 +129,37 @@ impl StartupState {
             && self.workspace_ready()
     }
 
+    /// Accept the defaults for every step that doesn't need input and jump
+    /// straight to the workspace step.
+    ///
+    /// Node.js detection is skipped and the current agent and proxy config are
+    /// kept as-is. A workspace is still required before startup is complete.
+    pub(in crate::workspace) fn skip_to_workspace(&mut self) {
+        self.intro_completed = true;
+        self.nodejs_skipped = true;
+        self.agent_applied = true;
+        self.proxy_applied = true;
+        self.advance_step_if_needed();
+    }
+
+    /// Pin or unpin an agent choice and re-sort the list so pinned agents come first
+    pub(in crate::workspace) fn set_agent_pinned(&mut self, name: &str, pinned: bool) {
+        if let Some(config) = self.default_agent_configs.get_mut(name) {
+            config.pinned = pinned;
+        }
+        self.sort_agent_choices();
+    }
+
+    pub(in crate::workspace) fn sort_agent_choices(&mut self) {
+        let configs = &self.default_agent_configs;
+        self.agent_choices.sort_by(|a, b| {
+            compare_agents(
+                (&a.name, configs.get(&a.name)),
+                (&b.name, configs.get(&b.name)),
+            )
+        });
+    }
+
     pub(in crate::workspace) fn advance_step_if_needed(&mut self) {
         if self.step == 0 && self.intro_completed {
             self.step = 1;
@@ -165,17 +197,18 @@ impl StartupState {
         let config: Config = match serde_json::from_str(&raw) {
             Ok(config) => config,
             Err(err) => {
-                log::error!("Failed to parse embedded config.json: {}", err);
+                let error = describe_json_error(&raw, &err);
+                log::error!("Failed to parse embedded config.json: {}", error);
                 return (
                     Vec::new(),
                     HashMap::new(),
-                    Some(format!("Failed to parse embedded config.json: {}", err)),
+                    Some(format!("Failed to parse embedded config.json: {}", error)),
                 );
             }
         };
 
         let mut agent_entries: Vec<_> = config.agent_servers.into_iter().collect();
-        agent_entries.sort_by(|a, b| a.0.cmp(&b.0));
+        agent_entries.sort_by(|a, b| compare_agents((&a.0, Some(&a.1)), (&b.0, Some(&b.1))));
 
         let mut agent_choices = Vec::new();
         let mut default_agent_configs = HashMap::new();
@@ -188,6 +221,19 @@ impl StartupState {
             });
         }
 
+        // Known agents beyond the default config are offered unchecked;
+        // templates that need variables filled in are left to the add dialog
+        for template in agent_templates() {
+            if !template.is_ready_to_use() || default_agent_configs.contains_key(&template.name) {
+                continue;
+            }
+            default_agent_configs.insert(template.name.clone(), template.to_process_config());
+            agent_choices.push(AgentChoice {
+                name: template.name,
+                enabled: false,
+            });
+        }
+
         (agent_choices, default_agent_configs, None)
     }
 }
@@ -6,11 +6,12 @@ use std::path::PathBuf;
 
 use crate::{
     AppSettings, AppState,
+    app::actions::RerunStartupWizard,
     core::nodejs::{NodeJsChecker, NodeJsDetectionMode},
     utils,
 };
 
-use super::state::NodeJsStatus;
+use super::state::{NodeJsStatus, StartupState};
 use crate::workspace::DockWorkspace;
 
 impl DockWorkspace {
@@ -35,6 +36,29 @@ impl DockWorkspace {
         }
     }
 
+    /// Show the startup wizard again without touching the saved config
+    ///
+    /// The fresh state picks up the current agents and workspace through
+    /// `maybe_sync_agents` and `maybe_check_workspace`.
+    pub(in crate::workspace) fn on_action_rerun_startup_wizard(
+        &mut self,
+        _: &RerunStartupWizard,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.startup_state = StartupState::new();
+        self.startup_completed = false;
+        self.startup_rerun = true;
+        cx.notify();
+    }
+
+    /// Leave a re-run wizard and return to the workspace
+    pub(in crate::workspace) fn cancel_startup_wizard(&mut self, cx: &mut Context<Self>) {
+        self.startup_completed = true;
+        self.startup_rerun = false;
+        cx.notify();
+    }
+
     fn ensure_nodejs_input_initialized(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.startup_state.nodejs_custom_path_input.is_some() {
             return;
@@ -263,13 +287,17 @@ impl DockWorkspace {
 
         cx.spawn_in(window, async move |this, window| {
             let current_agents = agent_config_service.list_agents().await;
-            let current_names: HashSet<String> =
-                current_agents.into_iter().map(|(name, _)| name).collect();
 
             _ = this.update_in(window, |this, _, cx| {
-                for choice in &mut this.startup_state.agent_choices {
-                    choice.enabled = current_names.contains(&choice.name);
+                let state = &mut this.startup_state;
+                for choice in &mut state.agent_choices {
+                    let current = current_agents.iter().find(|(name, _)| name == &choice.name);
+                    choice.enabled = current.is_some();
+                    if let Some(config) = state.default_agent_configs.get_mut(&choice.name) {
+                        config.pinned = current.is_some_and(|(_, config)| config.pinned);
+                    }
                 }
+                state.sort_agent_choices();
 
                 this.startup_state.agent_synced = true;
                 this.startup_state.agent_sync_in_progress = false;
@@ -356,6 +384,11 @@ impl DockWorkspace {
 
         cx.spawn_in(window, async move |this, window| {
             let current_agents = agent_config_service.list_agents().await;
+            let pinned_names: HashSet<String> = current_agents
+                .iter()
+                .filter(|(_, config)| config.pinned)
+                .map(|(name, _)| name.clone())
+                .collect();
             let current_names: HashSet<String> =
                 current_agents.into_iter().map(|(name, _)| name).collect();
             let mut errors = Vec::new();
@@ -382,6 +415,14 @@ impl DockWorkspace {
                     if let Err(err) = agent_config_service.remove_agent(&choice.name).await {
                         errors.push(format!("Failed to disable {}: {}", choice.name, err));
                     }
+                } else if choice.enabled
+                    && let Some(config) = default_configs.get(&choice.name)
+                    && config.pinned != pinned_names.contains(&choice.name)
+                    && let Err(err) = agent_config_service
+                        .set_agent_pinned(&choice.name, config.pinned)
+                        .await
+                {
+                    errors.push(format!("Failed to pin {}: {}", choice.name, err));
                 }
             }
 